@@ -0,0 +1,403 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use neutrino::utils::icon::BreezeIcon;
+use neutrino::utils::theme::Theme;
+use neutrino::widgets::accordion::Accordion;
+use neutrino::widgets::autocomplete::AutoComplete;
+use neutrino::widgets::button::Button;
+use neutrino::widgets::canvas::Canvas;
+use neutrino::widgets::checkbox::CheckBox;
+use neutrino::widgets::combo::Combo;
+use neutrino::widgets::container::{Alignment, Container, Direction};
+use neutrino::widgets::dangerconfirm::DangerConfirm;
+use neutrino::widgets::datepicker::DatePicker;
+use neutrino::widgets::image::Image;
+use neutrino::widgets::label::Label;
+use neutrino::widgets::listbox::ListBox;
+use neutrino::widgets::listview::ListView;
+use neutrino::widgets::popover::{Placement, Popover};
+use neutrino::widgets::progressbar::ProgressBar;
+use neutrino::widgets::radio::Radio;
+use neutrino::widgets::range::Range;
+use neutrino::widgets::snackbar::Snackbar;
+use neutrino::widgets::spinbox::SpinBox;
+use neutrino::widgets::tabs::Tabs;
+use neutrino::widgets::textinput::TextInput;
+use neutrino::widgets::timepicker::TimePicker;
+use neutrino::widgets::toolbar::{ToolBar, ToolButton};
+use neutrino::widgets::widget::Widget;
+use neutrino::{App, Window};
+
+mod gallery_mod;
+use gallery_mod::listeners::{
+    ChoiceListener, DeleteButtonListener, DisabledListener, PagesListener,
+    PopoverButtonListener, SampleAutoCompleteListener, SampleButtonListener,
+    SampleCanvasListener, SampleCheckBoxListener, SampleDangerConfirmListener,
+    SampleDatePickerListener, SampleLabelListener, SampleListBoxListener,
+    SamplePopoverListener, SampleProgressBarListener, SampleSnackbarListener,
+    SampleTimePickerListener, SampleToolBarListener, SnackbarButtonListener,
+    StretchedListener, TextListener, ValueListener,
+};
+use gallery_mod::models::{Properties, Samples};
+
+const CHOICES: [&str; 4] = ["Apple", "Banana", "Cherry", "Durian"];
+
+const WIDGETS: [&str; 21] = [
+    "Accordion",
+    "AutoComplete",
+    "Button",
+    "Canvas",
+    "CheckBox",
+    "Combo",
+    "DangerConfirm",
+    "DatePicker",
+    "Image",
+    "Label",
+    "ListBox",
+    "ListView",
+    "Popover",
+    "ProgressBar",
+    "Radio",
+    "Range",
+    "Snackbar",
+    "SpinBox",
+    "TextInput",
+    "TimePicker",
+    "ToolBar",
+];
+
+fn page(name: &str, widgets: Vec<Box<dyn Widget>>) -> Container {
+    let mut container = Container::new(name);
+    container.set_direction(Direction::Vertical);
+    container.set_scrollable();
+    container.set_stretched();
+    for widget in widgets {
+        container.add(widget);
+    }
+    container
+}
+
+fn main() {
+    let theme = match std::env::args().nth(1).as_ref().map(String::as_ref) {
+        Some("adwaita") => Theme::Adwaita,
+        Some("breeze") => Theme::Breeze,
+        Some("osx") => Theme::OSX,
+        _ => Theme::Default,
+    };
+
+    let properties = Rc::new(RefCell::new(Properties::new()));
+    let samples = Rc::new(RefCell::new(Samples::new()));
+
+    // Properties
+
+    let mut properties_title = Label::new("properties_title");
+    properties_title.set_text("Properties");
+
+    let mut text_label = Label::new("text_label");
+    text_label.set_text("Text");
+
+    let mut text = TextInput::new("text");
+    text.set_listener(Box::new(TextListener::new(Rc::clone(&properties))));
+
+    let mut value_label = Label::new("value_label");
+    value_label.set_text("Value");
+
+    let mut value = Range::new("value");
+    value.set_min(0);
+    value.set_max(100);
+    value.set_listener(Box::new(ValueListener::new(Rc::clone(&properties))));
+
+    let mut disabled = CheckBox::new("disabled");
+    disabled.set_text("Disabled");
+    disabled
+        .set_listener(Box::new(DisabledListener::new(Rc::clone(&properties))));
+
+    let mut stretched = CheckBox::new("stretched");
+    stretched.set_text("Stretched");
+    stretched
+        .set_listener(Box::new(StretchedListener::new(Rc::clone(&properties))));
+
+    let mut properties_panel = Container::new("properties_panel");
+    properties_panel.set_direction(Direction::Vertical);
+    properties_panel.add(Box::new(properties_title));
+    properties_panel.add(Box::new(text_label));
+    properties_panel.add(Box::new(text));
+    properties_panel.add(Box::new(value_label));
+    properties_panel.add(Box::new(value));
+    properties_panel.add(Box::new(disabled));
+    properties_panel.add(Box::new(stretched));
+
+    // Buttons
+
+    let mut button = Button::new("sample_button");
+    button.set_icon(Box::new(BreezeIcon::Check));
+    button.set_listener(Box::new(SampleButtonListener::new(
+        Rc::clone(&properties),
+        Rc::clone(&samples),
+    )));
+
+    let mut checkbox = CheckBox::new("sample_checkbox");
+    checkbox.set_listener(Box::new(SampleCheckBoxListener::new(
+        Rc::clone(&properties),
+        Rc::clone(&samples),
+    )));
+
+    let mut radio = Radio::new("sample_radio");
+    radio.set_choices(CHOICES.to_vec());
+    radio.set_listener(Box::new(ChoiceListener::new(
+        Rc::clone(&properties),
+        Rc::clone(&samples),
+    )));
+
+    let mut combo = Combo::new("sample_combo");
+    combo.set_choices(CHOICES.to_vec());
+    combo.set_icon(Box::new(BreezeIcon::Down));
+    combo.set_listener(Box::new(ChoiceListener::new(
+        Rc::clone(&properties),
+        Rc::clone(&samples),
+    )));
+
+    let mut save = ToolButton::new("Save");
+    save.set_icon(Box::new(BreezeIcon::Save));
+
+    let mut bold = ToolButton::new("Bold");
+    bold.set_toggle();
+
+    let mut italic = ToolButton::new("Italic");
+    italic.set_toggle();
+
+    let mut toolbar = ToolBar::new("sample_toolbar");
+    toolbar.add(save);
+    toolbar.add_separator();
+    toolbar.add(bold);
+    toolbar.add(italic);
+    toolbar.add_separator();
+    let mut undo = ToolButton::new("Undo");
+    undo.set_icon(Box::new(BreezeIcon::Undo));
+
+    let mut redo = ToolButton::new("Redo");
+    redo.set_icon(Box::new(BreezeIcon::Redo));
+
+    toolbar.add(undo);
+    toolbar.add(redo);
+    toolbar.set_listener(Box::new(SampleToolBarListener::new(
+        Rc::clone(&properties),
+        Rc::clone(&samples),
+    )));
+
+    let buttons = page(
+        "buttons",
+        vec![
+            Box::new(toolbar),
+            Box::new(button),
+            Box::new(checkbox),
+            Box::new(radio),
+            Box::new(combo),
+        ],
+    );
+
+    // Inputs
+
+    let mut textinput = TextInput::new("sample_textinput");
+    textinput.set_listener(Box::new(TextListener::new(Rc::clone(&properties))));
+
+    let mut spinbox = SpinBox::new("sample_spinbox");
+    spinbox.set_min(0.0);
+    spinbox.set_max(100.0);
+    spinbox.set_listener(Box::new(ValueListener::new(Rc::clone(&properties))));
+
+    let mut range = Range::new("sample_range");
+    range.set_min(0);
+    range.set_max(100);
+    range.set_listener(Box::new(ValueListener::new(Rc::clone(&properties))));
+
+    let mut autocomplete = AutoComplete::new("sample_autocomplete");
+    autocomplete.set_suggestions(WIDGETS.to_vec());
+    autocomplete.set_filtered();
+    autocomplete.set_placeholder("Search a widget");
+    autocomplete.set_listener(Box::new(SampleAutoCompleteListener::new(
+        Rc::clone(&properties),
+        Rc::clone(&samples),
+    )));
+
+    let mut datepicker = DatePicker::new("sample_datepicker");
+    datepicker.set_placeholder("Pick a date");
+    datepicker.set_listener(Box::new(SampleDatePickerListener::new(
+        Rc::clone(&properties),
+        Rc::clone(&samples),
+    )));
+
+    let mut timepicker = TimePicker::new("sample_timepicker");
+    timepicker.set_placeholder("Pick a time");
+    timepicker.set_listener(Box::new(SampleTimePickerListener::new(
+        Rc::clone(&properties),
+        Rc::clone(&samples),
+    )));
+
+    let inputs = page(
+        "inputs",
+        vec![
+            Box::new(textinput),
+            Box::new(spinbox),
+            Box::new(range),
+            Box::new(autocomplete),
+            Box::new(datepicker),
+            Box::new(timepicker),
+        ],
+    );
+
+    // Display
+
+    let mut label = Label::new("sample_label");
+    label.set_listener(Box::new(SampleLabelListener::new(Rc::clone(
+        &properties,
+    ))));
+
+    let mut progressbar = ProgressBar::new("sample_progressbar");
+    progressbar.set_min(0);
+    progressbar.set_max(100);
+    progressbar.set_listener(Box::new(SampleProgressBarListener::new(
+        Rc::clone(&properties),
+    )));
+
+    let image = Image::from_icon("sample_image", Box::new(BreezeIcon::Check));
+
+    let mut canvas = Canvas::new("sample_canvas");
+    canvas.set_size(220, 100);
+    canvas.set_listener(Box::new(SampleCanvasListener::new(
+        Rc::clone(&properties),
+        Rc::clone(&samples),
+    )));
+
+    let display = page(
+        "display",
+        vec![
+            Box::new(label),
+            Box::new(progressbar),
+            Box::new(image),
+            Box::new(canvas),
+        ],
+    );
+
+    // Lists
+
+    let mut listview = ListView::new("sample_listview");
+    listview.set_items(CHOICES.to_vec());
+    listview.set_listener(Box::new(ChoiceListener::new(
+        Rc::clone(&properties),
+        Rc::clone(&samples),
+    )));
+
+    let mut listbox = ListBox::new("sample_listbox");
+    listbox.set_items(WIDGETS.to_vec());
+    listbox.set_multiple();
+    listbox.set_listener(Box::new(SampleListBoxListener::new(
+        Rc::clone(&properties),
+        Rc::clone(&samples),
+    )));
+
+    let mut listview_label = Label::new("listview_label");
+    listview_label
+        .set_text("Selecting a fruit also changes the Radio and the Combo.");
+
+    let mut listbox_label = Label::new("listbox_label");
+    listbox_label
+        .set_text("Ctrl+click and Shift+click select several widgets.");
+
+    let mut accordion = Accordion::new("sample_accordion");
+    accordion.add("ListView", Box::new(listview_label));
+    accordion.add("ListBox", Box::new(listbox_label));
+    accordion.set_exclusive();
+
+    let lists = page(
+        "lists",
+        vec![Box::new(listview), Box::new(listbox), Box::new(accordion)],
+    );
+
+    // Overlays
+
+    let mut popover_button = Button::new("popover_button");
+    popover_button.set_text("Popover");
+    popover_button.set_listener(Box::new(PopoverButtonListener::new(
+        Rc::clone(&samples),
+    )));
+
+    let mut popover_label = Label::new("popover_label");
+    popover_label.set_text("Click outside or press Escape to close");
+
+    let mut popover = Popover::new("sample_popover");
+    popover.set_anchor("popover_button");
+    popover.set_placement(Placement::Right);
+    popover.set_child(Box::new(popover_label));
+    popover.set_listener(Box::new(SamplePopoverListener::new(Rc::clone(
+        &samples,
+    ))));
+
+    let mut snackbar_button = Button::new("snackbar_button");
+    snackbar_button.set_text("Snackbar");
+    snackbar_button.set_listener(Box::new(SnackbarButtonListener::new(
+        Rc::clone(&properties),
+        Rc::clone(&samples),
+    )));
+
+    let mut delete_button = Button::new("delete_button");
+    delete_button.set_text("DangerConfirm");
+    delete_button
+        .set_listener(Box::new(DeleteButtonListener::new(Rc::clone(&samples))));
+
+    let mut dangerconfirm = DangerConfirm::new("sample_dangerconfirm");
+    dangerconfirm.set_title("Are you sure?");
+    dangerconfirm.set_message("Type the text property to confirm.");
+    dangerconfirm.set_confirm("Confirm");
+    dangerconfirm.set_listener(Box::new(SampleDangerConfirmListener::new(
+        Rc::clone(&properties),
+        Rc::clone(&samples),
+    )));
+
+    let overlays = page(
+        "overlays",
+        vec![
+            Box::new(popover_button),
+            Box::new(popover),
+            Box::new(snackbar_button),
+            Box::new(delete_button),
+            Box::new(dangerconfirm),
+        ],
+    );
+
+    // Window
+
+    let mut pages = Tabs::new("pages");
+    pages.set_stretched();
+    pages.add("Buttons", Box::new(buttons));
+    pages.add("Inputs", Box::new(inputs));
+    pages.add("Display", Box::new(display));
+    pages.add("Lists", Box::new(lists));
+    pages.add("Overlays", Box::new(overlays));
+    pages.set_icon(1, Box::new(BreezeIcon::Edit));
+    pages.set_icon(4, Box::new(BreezeIcon::Bell));
+    pages.set_listener(Box::new(PagesListener::new(Rc::clone(&samples))));
+
+    let mut snackbar = Snackbar::new("snackbar");
+    snackbar.set_listener(Box::new(SampleSnackbarListener::new(Rc::clone(
+        &samples,
+    ))));
+
+    let mut gallery = Container::new("gallery");
+    gallery.set_direction(Direction::Horizontal);
+    gallery.set_alignment(Alignment::Start);
+    gallery.set_stretched();
+    gallery.add(Box::new(pages));
+    gallery.add(Box::new(properties_panel));
+    gallery.add(Box::new(snackbar));
+
+    let mut window = Window::new();
+    window.set_title("Neutrino Gallery");
+    window.set_size(720, 480);
+    window.set_resizable();
+    window.set_child(Box::new(gallery));
+    window.set_theme(theme);
+
+    App::run(window);
+}
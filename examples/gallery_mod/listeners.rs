@@ -0,0 +1,647 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use neutrino::widgets::autocomplete::{
+    AutoCompleteListener, AutoCompleteState,
+};
+use neutrino::widgets::button::{ButtonListener, ButtonState};
+use neutrino::widgets::canvas::{CanvasListener, CanvasState, DrawCommand};
+use neutrino::widgets::checkbox::{CheckBoxListener, CheckBoxState};
+use neutrino::widgets::combo::{ComboListener, ComboState};
+use neutrino::widgets::dangerconfirm::{
+    DangerConfirmListener, DangerConfirmState,
+};
+use neutrino::widgets::datepicker::{DatePickerListener, DatePickerState};
+use neutrino::widgets::label::{LabelListener, LabelState};
+use neutrino::widgets::listbox::{ListBoxListener, ListBoxState};
+use neutrino::widgets::listview::{ListViewListener, ListViewState};
+use neutrino::widgets::popover::{PopoverListener, PopoverState};
+use neutrino::widgets::progressbar::{ProgressBarListener, ProgressBarState};
+use neutrino::widgets::radio::{RadioListener, RadioState};
+use neutrino::widgets::range::{RangeListener, RangeState};
+use neutrino::widgets::snackbar::{SnackbarListener, SnackbarState};
+use neutrino::widgets::spinbox::{SpinBoxListener, SpinBoxState};
+use neutrino::widgets::tabs::{TabsListener, TabsState};
+use neutrino::widgets::textinput::{TextInputListener, TextInputState};
+use neutrino::widgets::timepicker::{TimePickerListener, TimePickerState};
+use neutrino::widgets::toolbar::{ToolBarListener, ToolBarState};
+
+use super::models::{Properties, Samples};
+
+/* Properties */
+
+pub struct TextListener {
+    properties: Rc<RefCell<Properties>>,
+}
+
+impl TextListener {
+    pub fn new(properties: Rc<RefCell<Properties>>) -> Self {
+        Self { properties }
+    }
+}
+
+impl TextInputListener for TextListener {
+    fn on_update(&self, state: &mut TextInputState) {
+        let properties = self.properties.borrow();
+        state.set_value(properties.text());
+        state.set_stretched(properties.stretched());
+    }
+
+    fn on_change(&self, state: &TextInputState) {
+        self.properties.borrow_mut().set_text(state.value());
+    }
+}
+
+pub struct ValueListener {
+    properties: Rc<RefCell<Properties>>,
+}
+
+impl ValueListener {
+    pub fn new(properties: Rc<RefCell<Properties>>) -> Self {
+        Self { properties }
+    }
+}
+
+impl RangeListener for ValueListener {
+    fn on_update(&self, state: &mut RangeState) {
+        let properties = self.properties.borrow();
+        state.set_value(properties.value());
+        state.set_stretched(properties.stretched());
+    }
+
+    fn on_change(&self, state: &RangeState) {
+        self.properties.borrow_mut().set_value(state.value());
+    }
+}
+
+impl SpinBoxListener for ValueListener {
+    fn on_update(&self, state: &mut SpinBoxState) {
+        let properties = self.properties.borrow();
+        state.set_value(f64::from(properties.value()));
+        state.set_stretched(properties.stretched());
+    }
+
+    fn on_change(&self, state: &SpinBoxState) {
+        self.properties
+            .borrow_mut()
+            .set_value(state.value().round() as i32);
+    }
+}
+
+pub struct DisabledListener {
+    properties: Rc<RefCell<Properties>>,
+}
+
+impl DisabledListener {
+    pub fn new(properties: Rc<RefCell<Properties>>) -> Self {
+        Self { properties }
+    }
+}
+
+impl CheckBoxListener for DisabledListener {
+    fn on_change(&self, state: &CheckBoxState) {
+        self.properties.borrow_mut().set_disabled(state.checked());
+    }
+
+    fn on_update(&self, state: &mut CheckBoxState) {
+        state.set_checked(self.properties.borrow().disabled());
+    }
+}
+
+pub struct StretchedListener {
+    properties: Rc<RefCell<Properties>>,
+}
+
+impl StretchedListener {
+    pub fn new(properties: Rc<RefCell<Properties>>) -> Self {
+        Self { properties }
+    }
+}
+
+impl CheckBoxListener for StretchedListener {
+    fn on_change(&self, state: &CheckBoxState) {
+        self.properties.borrow_mut().set_stretched(state.checked());
+    }
+
+    fn on_update(&self, state: &mut CheckBoxState) {
+        state.set_checked(self.properties.borrow().stretched());
+    }
+}
+
+/* Pages */
+
+pub struct PagesListener {
+    samples: Rc<RefCell<Samples>>,
+}
+
+impl PagesListener {
+    pub fn new(samples: Rc<RefCell<Samples>>) -> Self {
+        Self { samples }
+    }
+}
+
+impl TabsListener for PagesListener {
+    fn on_update(&self, state: &mut TabsState) {
+        state.set_selected(self.samples.borrow().page());
+    }
+
+    fn on_change(&self, state: &TabsState) {
+        self.samples.borrow_mut().set_page(state.selected());
+    }
+}
+
+/* Buttons */
+
+pub struct SampleButtonListener {
+    properties: Rc<RefCell<Properties>>,
+    samples: Rc<RefCell<Samples>>,
+}
+
+impl SampleButtonListener {
+    pub fn new(
+        properties: Rc<RefCell<Properties>>,
+        samples: Rc<RefCell<Samples>>,
+    ) -> Self {
+        Self {
+            properties,
+            samples,
+        }
+    }
+}
+
+impl ButtonListener for SampleButtonListener {
+    fn on_change(&self, _state: &ButtonState) {
+        self.samples
+            .borrow_mut()
+            .notify("The button has been clicked");
+    }
+
+    fn on_update(&self, state: &mut ButtonState) {
+        let properties = self.properties.borrow();
+        state.set_text(properties.text());
+        state.set_disabled(properties.disabled());
+        state.set_stretched(properties.stretched());
+    }
+}
+
+pub struct SampleCheckBoxListener {
+    properties: Rc<RefCell<Properties>>,
+    samples: Rc<RefCell<Samples>>,
+}
+
+impl SampleCheckBoxListener {
+    pub fn new(
+        properties: Rc<RefCell<Properties>>,
+        samples: Rc<RefCell<Samples>>,
+    ) -> Self {
+        Self {
+            properties,
+            samples,
+        }
+    }
+}
+
+impl CheckBoxListener for SampleCheckBoxListener {
+    fn on_change(&self, state: &CheckBoxState) {
+        self.samples.borrow_mut().set_checked(state.checked());
+    }
+
+    fn on_update(&self, state: &mut CheckBoxState) {
+        state.set_text(self.properties.borrow().text());
+        state.set_checked(self.samples.borrow().checked());
+    }
+}
+
+pub struct ChoiceListener {
+    properties: Rc<RefCell<Properties>>,
+    samples: Rc<RefCell<Samples>>,
+}
+
+impl ChoiceListener {
+    pub fn new(
+        properties: Rc<RefCell<Properties>>,
+        samples: Rc<RefCell<Samples>>,
+    ) -> Self {
+        Self {
+            properties,
+            samples,
+        }
+    }
+}
+
+impl RadioListener for ChoiceListener {
+    fn on_change(&self, state: &RadioState) {
+        self.samples.borrow_mut().set_choice(state.selected());
+    }
+
+    fn on_update(&self, state: &mut RadioState) {
+        state.set_selected(self.samples.borrow().choice());
+    }
+}
+
+impl ComboListener for ChoiceListener {
+    fn on_change(&self, state: &ComboState) {
+        self.samples.borrow_mut().set_choice(state.selected());
+    }
+
+    fn on_update(&self, state: &mut ComboState) {
+        state.set_selected(self.samples.borrow().choice());
+        state.set_stretched(self.properties.borrow().stretched());
+    }
+}
+
+impl ListViewListener for ChoiceListener {
+    fn on_update(&self, state: &mut ListViewState) {
+        state.set_selected(Some(self.samples.borrow().choice()));
+        state.set_stretched(self.properties.borrow().stretched());
+    }
+
+    fn on_change(&self, state: &ListViewState) {
+        if let Some(selected) = state.selected() {
+            self.samples.borrow_mut().set_choice(selected);
+        }
+    }
+
+    fn on_load_more(&self, _state: &ListViewState) {}
+}
+
+pub struct SampleToolBarListener {
+    properties: Rc<RefCell<Properties>>,
+    samples: Rc<RefCell<Samples>>,
+}
+
+impl SampleToolBarListener {
+    pub fn new(
+        properties: Rc<RefCell<Properties>>,
+        samples: Rc<RefCell<Samples>>,
+    ) -> Self {
+        Self {
+            properties,
+            samples,
+        }
+    }
+}
+
+impl ToolBarListener for SampleToolBarListener {
+    fn on_update(&self, state: &mut ToolBarState) {
+        let properties = self.properties.borrow();
+        state.set_disabled(0, properties.disabled());
+        state.set_stretched(properties.stretched());
+    }
+
+    fn on_change(&self, state: &ToolBarState) {
+        let button = state.selected().and_then(|index| state.button(index));
+        if let Some(button) = button {
+            self.samples
+                .borrow_mut()
+                .notify(&format!("{} has been clicked", button.text()));
+        }
+    }
+}
+
+/* Inputs */
+
+pub struct SampleAutoCompleteListener {
+    properties: Rc<RefCell<Properties>>,
+    samples: Rc<RefCell<Samples>>,
+}
+
+impl SampleAutoCompleteListener {
+    pub fn new(
+        properties: Rc<RefCell<Properties>>,
+        samples: Rc<RefCell<Samples>>,
+    ) -> Self {
+        Self {
+            properties,
+            samples,
+        }
+    }
+}
+
+impl AutoCompleteListener for SampleAutoCompleteListener {
+    fn on_update(&self, state: &mut AutoCompleteState) {
+        state.set_stretched(self.properties.borrow().stretched());
+    }
+
+    fn on_query(&self, _state: &AutoCompleteState) {}
+
+    fn on_select(&self, state: &AutoCompleteState) {
+        if let Some(selected) = state.selected() {
+            self.samples
+                .borrow_mut()
+                .notify(&format!("{} has been selected", selected));
+        }
+    }
+}
+
+pub struct SampleDatePickerListener {
+    properties: Rc<RefCell<Properties>>,
+    samples: Rc<RefCell<Samples>>,
+}
+
+impl SampleDatePickerListener {
+    pub fn new(
+        properties: Rc<RefCell<Properties>>,
+        samples: Rc<RefCell<Samples>>,
+    ) -> Self {
+        Self {
+            properties,
+            samples,
+        }
+    }
+}
+
+impl DatePickerListener for SampleDatePickerListener {
+    fn on_update(&self, state: &mut DatePickerState) {
+        state.set_date(self.samples.borrow().date());
+        state.set_stretched(self.properties.borrow().stretched());
+    }
+
+    fn on_change(&self, state: &DatePickerState) {
+        self.samples.borrow_mut().set_date(state.date());
+    }
+}
+
+pub struct SampleTimePickerListener {
+    properties: Rc<RefCell<Properties>>,
+    samples: Rc<RefCell<Samples>>,
+}
+
+impl SampleTimePickerListener {
+    pub fn new(
+        properties: Rc<RefCell<Properties>>,
+        samples: Rc<RefCell<Samples>>,
+    ) -> Self {
+        Self {
+            properties,
+            samples,
+        }
+    }
+}
+
+impl TimePickerListener for SampleTimePickerListener {
+    fn on_update(&self, state: &mut TimePickerState) {
+        state.set_time(self.samples.borrow().time());
+        state.set_stretched(self.properties.borrow().stretched());
+    }
+
+    fn on_change(&self, state: &TimePickerState) {
+        self.samples.borrow_mut().set_time(state.time());
+    }
+}
+
+/* Display */
+
+pub struct SampleLabelListener {
+    properties: Rc<RefCell<Properties>>,
+}
+
+impl SampleLabelListener {
+    pub fn new(properties: Rc<RefCell<Properties>>) -> Self {
+        Self { properties }
+    }
+}
+
+impl LabelListener for SampleLabelListener {
+    fn on_update(&self, state: &mut LabelState) {
+        let properties = self.properties.borrow();
+        state.set_text(properties.text());
+        state.set_stretched(properties.stretched());
+    }
+}
+
+pub struct SampleProgressBarListener {
+    properties: Rc<RefCell<Properties>>,
+}
+
+impl SampleProgressBarListener {
+    pub fn new(properties: Rc<RefCell<Properties>>) -> Self {
+        Self { properties }
+    }
+}
+
+impl ProgressBarListener for SampleProgressBarListener {
+    fn on_update(&self, state: &mut ProgressBarState) {
+        let properties = self.properties.borrow();
+        state.set_value(properties.value());
+        state.set_stretched(properties.stretched());
+    }
+}
+
+pub struct SampleCanvasListener {
+    properties: Rc<RefCell<Properties>>,
+    samples: Rc<RefCell<Samples>>,
+}
+
+impl SampleCanvasListener {
+    pub fn new(
+        properties: Rc<RefCell<Properties>>,
+        samples: Rc<RefCell<Samples>>,
+    ) -> Self {
+        Self {
+            properties,
+            samples,
+        }
+    }
+}
+
+impl CanvasListener for SampleCanvasListener {
+    fn on_update(&self, state: &mut CanvasState) {
+        let properties = self.properties.borrow();
+        let width = f64::from(properties.value()) * 2.0;
+        state.set_commands(vec![
+            DrawCommand::Rect {
+                x: 10.0,
+                y: 10.0,
+                width,
+                height: 40.0,
+                fill: Some("#3daee9".to_string()),
+                stroke: None,
+            },
+            DrawCommand::Text {
+                x: 10.0,
+                y: 80.0,
+                text: properties.text().to_string(),
+                font: "16px sans-serif".to_string(),
+                color: "#31363b".to_string(),
+            },
+        ]);
+    }
+
+    fn on_click(&self, _state: &CanvasState, x: f64, y: f64) {
+        self.samples
+            .borrow_mut()
+            .notify(&format!("Click at ({:.0}, {:.0})", x, y));
+    }
+}
+
+/* Lists */
+
+pub struct SampleListBoxListener {
+    properties: Rc<RefCell<Properties>>,
+    samples: Rc<RefCell<Samples>>,
+}
+
+impl SampleListBoxListener {
+    pub fn new(
+        properties: Rc<RefCell<Properties>>,
+        samples: Rc<RefCell<Samples>>,
+    ) -> Self {
+        Self {
+            properties,
+            samples,
+        }
+    }
+}
+
+impl ListBoxListener for SampleListBoxListener {
+    fn on_update(&self, state: &mut ListBoxState) {
+        state.set_selected(self.samples.borrow().selected().clone());
+        state.set_stretched(self.properties.borrow().stretched());
+    }
+
+    fn on_change(&self, state: &ListBoxState) {
+        self.samples
+            .borrow_mut()
+            .set_selected(state.selected().clone());
+    }
+}
+
+/* Overlays */
+
+pub struct PopoverButtonListener {
+    samples: Rc<RefCell<Samples>>,
+}
+
+impl PopoverButtonListener {
+    pub fn new(samples: Rc<RefCell<Samples>>) -> Self {
+        Self { samples }
+    }
+}
+
+impl ButtonListener for PopoverButtonListener {
+    fn on_change(&self, _state: &ButtonState) {
+        let popover = self.samples.borrow().popover();
+        self.samples.borrow_mut().set_popover(!popover);
+    }
+
+    fn on_update(&self, _state: &mut ButtonState) {}
+}
+
+pub struct SamplePopoverListener {
+    samples: Rc<RefCell<Samples>>,
+}
+
+impl SamplePopoverListener {
+    pub fn new(samples: Rc<RefCell<Samples>>) -> Self {
+        Self { samples }
+    }
+}
+
+impl PopoverListener for SamplePopoverListener {
+    fn on_update(&self, state: &mut PopoverState) {
+        state.set_opened(self.samples.borrow().popover());
+    }
+
+    fn on_dismiss(&self, _state: &PopoverState) {
+        self.samples.borrow_mut().set_popover(false);
+    }
+}
+
+pub struct SnackbarButtonListener {
+    properties: Rc<RefCell<Properties>>,
+    samples: Rc<RefCell<Samples>>,
+}
+
+impl SnackbarButtonListener {
+    pub fn new(
+        properties: Rc<RefCell<Properties>>,
+        samples: Rc<RefCell<Samples>>,
+    ) -> Self {
+        Self {
+            properties,
+            samples,
+        }
+    }
+}
+
+impl ButtonListener for SnackbarButtonListener {
+    fn on_change(&self, _state: &ButtonState) {
+        let text = self.properties.borrow().text().to_string();
+        self.samples.borrow_mut().notify(&text);
+    }
+
+    fn on_update(&self, _state: &mut ButtonState) {}
+}
+
+pub struct SampleSnackbarListener {
+    samples: Rc<RefCell<Samples>>,
+}
+
+impl SampleSnackbarListener {
+    pub fn new(samples: Rc<RefCell<Samples>>) -> Self {
+        Self { samples }
+    }
+}
+
+impl SnackbarListener for SampleSnackbarListener {
+    fn on_update(&self, state: &mut SnackbarState) {
+        if let Some(message) = self.samples.borrow_mut().take_notification() {
+            state.show(&message);
+        }
+    }
+
+    fn on_action(&self, _state: &SnackbarState) {}
+}
+
+pub struct DeleteButtonListener {
+    samples: Rc<RefCell<Samples>>,
+}
+
+impl DeleteButtonListener {
+    pub fn new(samples: Rc<RefCell<Samples>>) -> Self {
+        Self { samples }
+    }
+}
+
+impl ButtonListener for DeleteButtonListener {
+    fn on_change(&self, _state: &ButtonState) {
+        self.samples.borrow_mut().set_deleting(true);
+    }
+
+    fn on_update(&self, _state: &mut ButtonState) {}
+}
+
+pub struct SampleDangerConfirmListener {
+    properties: Rc<RefCell<Properties>>,
+    samples: Rc<RefCell<Samples>>,
+}
+
+impl SampleDangerConfirmListener {
+    pub fn new(
+        properties: Rc<RefCell<Properties>>,
+        samples: Rc<RefCell<Samples>>,
+    ) -> Self {
+        Self {
+            properties,
+            samples,
+        }
+    }
+}
+
+impl DangerConfirmListener for SampleDangerConfirmListener {
+    fn on_update(&self, state: &mut DangerConfirmState) {
+        state.set_phrase(self.properties.borrow().text());
+        state.set_opened(self.samples.borrow().deleting());
+    }
+
+    fn on_confirm(&self, _state: &DangerConfirmState) {
+        let mut samples = self.samples.borrow_mut();
+        samples.set_deleting(false);
+        samples.notify("The action has been confirmed");
+    }
+
+    fn on_cancel(&self, _state: &DangerConfirmState) {
+        self.samples.borrow_mut().set_deleting(false);
+    }
+}
@@ -0,0 +1,151 @@
+use neutrino::utils::datetime::{Date, Time};
+
+pub struct Properties {
+    text: String,
+    value: i32,
+    disabled: bool,
+    stretched: bool,
+}
+
+impl Properties {
+    pub fn new() -> Self {
+        Self {
+            text: "Hello".to_string(),
+            value: 50,
+            disabled: false,
+            stretched: false,
+        }
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn value(&self) -> i32 {
+        self.value
+    }
+
+    pub fn disabled(&self) -> bool {
+        self.disabled
+    }
+
+    pub fn stretched(&self) -> bool {
+        self.stretched
+    }
+
+    pub fn set_text(&mut self, text: &str) {
+        self.text = text.to_string();
+    }
+
+    pub fn set_value(&mut self, value: i32) {
+        self.value = value.clamp(0, 100);
+    }
+
+    pub fn set_disabled(&mut self, disabled: bool) {
+        self.disabled = disabled;
+    }
+
+    pub fn set_stretched(&mut self, stretched: bool) {
+        self.stretched = stretched;
+    }
+}
+
+pub struct Samples {
+    page: u32,
+    checked: bool,
+    choice: u32,
+    date: Option<Date>,
+    time: Option<Time>,
+    selected: Vec<u32>,
+    popover: bool,
+    notification: Option<String>,
+    deleting: bool,
+}
+
+impl Samples {
+    pub fn new() -> Self {
+        Self {
+            page: 0,
+            checked: false,
+            choice: 0,
+            date: None,
+            time: None,
+            selected: vec![],
+            popover: false,
+            notification: None,
+            deleting: false,
+        }
+    }
+
+    pub fn page(&self) -> u32 {
+        self.page
+    }
+
+    pub fn checked(&self) -> bool {
+        self.checked
+    }
+
+    pub fn choice(&self) -> u32 {
+        self.choice
+    }
+
+    pub fn date(&self) -> Option<Date> {
+        self.date
+    }
+
+    pub fn time(&self) -> Option<Time> {
+        self.time
+    }
+
+    pub fn selected(&self) -> &Vec<u32> {
+        &self.selected
+    }
+
+    pub fn popover(&self) -> bool {
+        self.popover
+    }
+
+    pub fn deleting(&self) -> bool {
+        self.deleting
+    }
+
+    pub fn set_page(&mut self, page: u32) {
+        self.page = page;
+    }
+
+    pub fn set_checked(&mut self, checked: bool) {
+        self.checked = checked;
+    }
+
+    pub fn set_choice(&mut self, choice: u32) {
+        self.choice = choice;
+    }
+
+    pub fn set_date(&mut self, date: Option<Date>) {
+        self.date = date;
+    }
+
+    pub fn set_time(&mut self, time: Option<Time>) {
+        self.time = time;
+    }
+
+    pub fn set_selected(&mut self, selected: Vec<u32>) {
+        self.selected = selected;
+    }
+
+    pub fn set_popover(&mut self, popover: bool) {
+        self.popover = popover;
+    }
+
+    pub fn set_deleting(&mut self, deleting: bool) {
+        self.deleting = deleting;
+    }
+
+    pub fn notify(&mut self, message: &str) {
+        self.notification = Some(message.to_string());
+    }
+
+    pub fn take_notification(&mut self) -> Option<String> {
+        self.notification.take()
+    }
+}
@@ -0,0 +1,451 @@
+//! # Backends
+//!
+//! The page of a Window is displayed in a native window by a backend.
+//! `App::run` uses the backend built on web-view, with the `web-view`
+//! feature which is on by default, or the backend built on wry, with the
+//! `wry` feature, so that an application having trouble packaging or
+//! rendering with web-view on a platform can switch backend without changing
+//! the rest of its code. `App::run_with` runs a Window with another
+//! implementation of `Backend`. Without the `web-view` feature, neutrino
+//! does not depend on web-view and its system libraries.
+//!
+//! ```text
+//! neutrino = { version = "<last_version>", default-features = false, features = ["wry"] }
+//! ```
+//!
+//! A backend opens a window with the given `Settings`, displays the HTML
+//! page of the Window, and calls the handler with each message sent by the
+//! page with `window.external.invoke`, giving it a `View` to evaluate
+//! javascript in the page. Before running, it gives a `Proxy` to the start
+//! function, which hands it to the threads of neutrino, such as the updater
+//! or the sync queue, to evaluate javascript from another thread.
+//!
+//! ## Example
+//!
+//! ```
+//! use neutrino::backend::{Backend, Proxy, Settings, View};
+//! use neutrino::{App, Window};
+//!
+//!
+//! struct Console {
+//!     title: String,
+//! }
+//!
+//! impl View for Console {
+//!     fn eval(&mut self, js: &str) -> Result<(), String> {
+//!         println!("{}: {} bytes of javascript", self.title, js.len());
+//!         Ok(())
+//!     }
+//!
+//!     fn set_title(&mut self, title: &str) -> Result<(), String> {
+//!         self.title = title.to_string();
+//!         Ok(())
+//!     }
+//!
+//!     fn terminate(&mut self) {}
+//! }
+//!
+//!
+//! struct ConsoleBackend;
+//!
+//! impl Backend for ConsoleBackend {
+//!     fn run(
+//!         &mut self,
+//!         settings: Settings,
+//!         start: Box<dyn FnOnce(Proxy)>,
+//!         handler: &mut dyn FnMut(&mut dyn View, &str) -> Result<(), String>,
+//!     ) -> Result<(), String> {
+//!         let mut console = Console { title: settings.title().to_string() };
+//!         start(Proxy::new(|_js| false));
+//!         handler(&mut console, r#"{"type": "Update"}"#)
+//!     }
+//! }
+//!
+//!
+//! let mut my_window = Window::new();
+//! my_window.set_title("Console");
+//!
+//! App::run_with(my_window, ConsoleBackend).unwrap();
+//! ```
+
+#[cfg(feature = "web-view")]
+use std::cell::RefCell;
+use std::sync::Arc;
+#[cfg(feature = "wry")]
+use std::sync::Mutex;
+
+#[cfg(all(feature = "tray", any(feature = "web-view", feature = "wry")))]
+use crate::tray;
+#[cfg(feature = "tray")]
+use crate::tray::Tray;
+
+/// # The settings of the native window opened by a backend
+///
+/// ## Fields
+///
+/// ```text
+/// title: String
+/// html: String
+/// width: i32
+/// height: i32
+/// resizable: bool
+/// fullscreen: bool
+/// debug: bool
+/// tray: Option<Tray>
+/// ```
+pub struct Settings {
+    pub(crate) title: String,
+    pub(crate) html: String,
+    pub(crate) width: i32,
+    pub(crate) height: i32,
+    pub(crate) resizable: bool,
+    pub(crate) fullscreen: bool,
+    pub(crate) debug: bool,
+    #[cfg(feature = "tray")]
+    pub(crate) tray: Option<Tray>,
+}
+
+impl Settings {
+    /// Get the title
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    /// Get the HTML page
+    pub fn html(&self) -> &str {
+        &self.html
+    }
+
+    /// Get the width
+    pub fn width(&self) -> i32 {
+        self.width
+    }
+
+    /// Get the height
+    pub fn height(&self) -> i32 {
+        self.height
+    }
+
+    /// Get the resizable flag
+    pub fn resizable(&self) -> bool {
+        self.resizable
+    }
+
+    /// Get the fullscreen flag
+    pub fn fullscreen(&self) -> bool {
+        self.fullscreen
+    }
+
+    /// Get the debug flag: the devtools are enabled
+    pub fn debug(&self) -> bool {
+        self.debug
+    }
+
+    /// Get the icon of the window in the system tray, if any. A backend
+    /// displaying it sends a tray event to the handler when it is clicked
+    /// (see the `tray` module).
+    #[cfg(feature = "tray")]
+    pub fn tray(&self) -> Option<&Tray> {
+        self.tray.as_ref()
+    }
+}
+
+/// # A proxy evaluating javascript in the page from another thread
+#[derive(Clone)]
+pub struct Proxy {
+    eval: Arc<dyn Fn(String) -> bool + Send + Sync>,
+}
+
+impl Proxy {
+    /// Create a Proxy from a function scheduling the evaluation of
+    /// javascript in the page, returning false once the window is closed
+    pub fn new<F>(eval: F) -> Self
+    where
+        F: Fn(String) -> bool + Send + Sync + 'static,
+    {
+        Self {
+            eval: Arc::new(eval),
+        }
+    }
+
+    /// Evaluate javascript in the page, returning false once the window is
+    /// closed
+    pub fn eval(&self, js: &str) -> bool {
+        (self.eval)(js.to_string())
+    }
+}
+
+/// # The native window displaying the page
+pub trait View {
+    /// Function evaluating javascript in the page
+    fn eval(&mut self, js: &str) -> Result<(), String>;
+
+    /// Function setting the title of the window
+    fn set_title(&mut self, title: &str) -> Result<(), String>;
+
+    /// Function closing the window once the current message is handled
+    fn terminate(&mut self);
+
+    /// Function hiding or showing the window, the backends which cannot
+    /// hide it returning an error. The backends of neutrino can hide it.
+    #[cfg(feature = "tray")]
+    fn set_visible(&mut self, _visible: bool) -> Result<(), String> {
+        Err("the backend cannot hide the window".to_string())
+    }
+}
+
+/// # A backend displaying the page of a Window in a native window
+pub trait Backend {
+    /// Function opening the window and running it until it is closed,
+    /// calling the start function with a proxy before the first message
+    /// and the handler with each message sent by the page
+    fn run(
+        &mut self,
+        settings: Settings,
+        start: Box<dyn FnOnce(Proxy)>,
+        handler: &mut dyn FnMut(&mut dyn View, &str) -> Result<(), String>,
+    ) -> Result<(), String>;
+}
+
+/// # The backend built on web-view
+#[cfg(feature = "web-view")]
+pub struct WebViewBackend;
+
+#[cfg(feature = "web-view")]
+impl View for web_view::WebView<'_, ()> {
+    fn eval(&mut self, js: &str) -> Result<(), String> {
+        web_view::WebView::eval(self, js).map_err(|error| error.to_string())
+    }
+
+    fn set_title(&mut self, title: &str) -> Result<(), String> {
+        web_view::WebView::set_title(self, title)
+            .map_err(|error| error.to_string())
+    }
+
+    fn terminate(&mut self) {
+        web_view::WebView::terminate(self);
+    }
+
+    #[cfg(feature = "tray")]
+    fn set_visible(&mut self, visible: bool) -> Result<(), String> {
+        tray::set_windows_visible(visible)
+    }
+}
+
+#[cfg(feature = "web-view")]
+impl Backend for WebViewBackend {
+    fn run(
+        &mut self,
+        settings: Settings,
+        start: Box<dyn FnOnce(Proxy)>,
+        handler: &mut dyn FnMut(&mut dyn View, &str) -> Result<(), String>,
+    ) -> Result<(), String> {
+        let handler = RefCell::new(handler);
+        let mut webview = web_view::builder()
+            .title(&settings.title)
+            .content(web_view::Content::Html(&settings.html))
+            .size(settings.width, settings.height)
+            .resizable(settings.resizable)
+            .user_data(())
+            .debug(settings.debug)
+            .invoke_handler(|webview, arg| {
+                let mut handler = handler.borrow_mut();
+                handler(webview, arg).map_err(web_view::Error::custom)
+            })
+            .build()
+            .map_err(|error| error.to_string())?;
+
+        if settings.fullscreen {
+            webview.set_fullscreen(true);
+        }
+
+        let handle = webview.handle();
+        start(Proxy::new(move |js| {
+            handle.dispatch(move |webview| webview.eval(&js)).is_ok()
+        }));
+
+        #[cfg(feature = "tray")]
+        let _tray = match &settings.tray {
+            Some(tray) => Some(tray.display()?),
+            None => None,
+        };
+
+        while let Some(result) = webview.step() {
+            result.map_err(|error| error.to_string())?;
+
+            #[cfg(feature = "tray")]
+            for message in tray::messages() {
+                let mut handler = handler.borrow_mut();
+                handler(&mut webview, &message)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The javascript giving the page of the wry backend the
+/// `window.external.invoke` function of web-view
+#[cfg(feature = "wry")]
+const WRY_JS: &str = r#"window.external = {
+    invoke: function(message) {
+        window.ipc.postMessage(message);
+    }
+};"#;
+
+/// # The backend built on wry
+///
+/// With the `wry` feature, `App::run` uses it instead of the backend built
+/// on web-view.
+#[cfg(feature = "wry")]
+pub struct WryBackend;
+
+/// An event of the loop of the wry backend
+#[cfg(feature = "wry")]
+enum WryEvent {
+    Message(String),
+    Eval(String),
+}
+
+/// The native window of the wry backend
+#[cfg(feature = "wry")]
+struct WryView<'a> {
+    window: &'a tao::window::Window,
+    webview: &'a wry::WebView,
+    terminated: bool,
+}
+
+#[cfg(feature = "wry")]
+impl View for WryView<'_> {
+    fn eval(&mut self, js: &str) -> Result<(), String> {
+        self.webview
+            .evaluate_script(js)
+            .map_err(|error| error.to_string())
+    }
+
+    fn set_title(&mut self, title: &str) -> Result<(), String> {
+        self.window.set_title(title);
+        Ok(())
+    }
+
+    fn terminate(&mut self) {
+        self.terminated = true;
+    }
+
+    #[cfg(feature = "tray")]
+    fn set_visible(&mut self, visible: bool) -> Result<(), String> {
+        self.window.set_visible(visible);
+        if visible {
+            self.window.set_focus();
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "wry")]
+impl Backend for WryBackend {
+    fn run(
+        &mut self,
+        settings: Settings,
+        start: Box<dyn FnOnce(Proxy)>,
+        handler: &mut dyn FnMut(&mut dyn View, &str) -> Result<(), String>,
+    ) -> Result<(), String> {
+        use tao::dpi::LogicalSize;
+        use tao::event::{Event as LoopEvent, WindowEvent};
+        use tao::event_loop::{ControlFlow, EventLoopBuilder};
+        use tao::platform::run_return::EventLoopExtRunReturn;
+        use tao::window::{Fullscreen, WindowBuilder};
+
+        let mut event_loop =
+            EventLoopBuilder::<WryEvent>::with_user_event().build();
+
+        let window = WindowBuilder::new()
+            .with_title(&settings.title)
+            .with_inner_size(LogicalSize::new(settings.width, settings.height))
+            .with_resizable(settings.resizable)
+            .with_fullscreen(if settings.fullscreen {
+                Some(Fullscreen::Borderless(None))
+            } else {
+                None
+            })
+            .build(&event_loop)
+            .map_err(|error| error.to_string())?;
+
+        let messages = event_loop.create_proxy();
+        let builder = wry::WebViewBuilder::new()
+            .with_initialization_script(WRY_JS)
+            .with_html(settings.html.as_str())
+            .with_devtools(settings.debug)
+            .with_ipc_handler(move |request| {
+                let _ =
+                    messages.send_event(WryEvent::Message(request.into_body()));
+            });
+
+        #[cfg(target_os = "linux")]
+        let webview = {
+            use tao::platform::unix::WindowExtUnix;
+            use wry::WebViewBuilderExtUnix;
+
+            match window.default_vbox() {
+                Some(vbox) => builder.build_gtk(vbox),
+                None => return Err("the window has no container".to_string()),
+            }
+        };
+        #[cfg(not(target_os = "linux"))]
+        let webview = builder.build(&window);
+        let webview = webview.map_err(|error| error.to_string())?;
+
+        #[cfg(feature = "tray")]
+        let _tray = match &settings.tray {
+            Some(tray) => {
+                let messages = Mutex::new(event_loop.create_proxy());
+                tray::forward(move |message| {
+                    let messages = messages
+                        .lock()
+                        .unwrap_or_else(|error| error.into_inner());
+                    let _ = messages.send_event(WryEvent::Message(message));
+                });
+                Some(tray.display()?)
+            }
+            None => None,
+        };
+
+        let evals = Mutex::new(event_loop.create_proxy());
+        start(Proxy::new(move |js| {
+            let evals = evals.lock().unwrap_or_else(|error| error.into_inner());
+            evals.send_event(WryEvent::Eval(js)).is_ok()
+        }));
+
+        let mut result = Ok(());
+        event_loop.run_return(|event, _, control_flow| {
+            *control_flow = ControlFlow::Wait;
+            let message = match event {
+                LoopEvent::UserEvent(WryEvent::Message(message)) => message,
+                LoopEvent::UserEvent(WryEvent::Eval(js)) => {
+                    let _ = webview.evaluate_script(&js);
+                    return;
+                }
+                LoopEvent::WindowEvent {
+                    event: WindowEvent::CloseRequested,
+                    ..
+                } => {
+                    *control_flow = ControlFlow::Exit;
+                    return;
+                }
+                _ => return,
+            };
+
+            let mut view = WryView {
+                window: &window,
+                webview: &webview,
+                terminated: false,
+            };
+            if let Err(error) = handler(&mut view, &message) {
+                result = Err(error);
+            }
+            if view.terminated || result.is_err() {
+                *control_flow = ControlFlow::Exit;
+            }
+        });
+        result
+    }
+}
@@ -32,125 +32,740 @@
 //!
 //! ![](https://raw.githubusercontent.com/wiki/alexislozano/neutrino/images/styling/4.png)
 
-use web_view::*;
+use std::fmt::Display;
+use std::fs;
+use std::future::Future;
+use std::io;
+use std::path::{Path, PathBuf};
 
+pub mod backend;
+#[cfg(feature = "scripting")]
+pub mod scripting;
+#[cfg(feature = "server")]
+pub mod server;
+pub mod testing;
+#[cfg(feature = "tray")]
+pub mod tray;
 pub mod utils;
+#[cfg(feature = "webdriver")]
+pub mod webdriver;
 pub mod widgets;
 
-use utils::event::{Event, Key};
+#[cfg(all(feature = "web-view", not(feature = "wry")))]
+use backend::WebViewBackend;
+#[cfg(feature = "wry")]
+use backend::WryBackend;
+use backend::{Backend, Proxy, Settings, View};
+use utils::accessibility::{self, Issue};
+use utils::animation::{self, Animation};
+use utils::audit;
+use utils::clipboard::{self, ClipboardData};
+use utils::csp::{self, Handlers};
+use utils::document::{self, Action, Document};
+use utils::event::{self, Event, Key};
+use utils::export;
+use utils::geometry::Bounds;
+use utils::graphviz;
+use utils::history::{self, Command};
+use utils::html::escape;
+use utils::hook::{EventHook, Propagation};
+use utils::i18n::{self, Catalog};
+use utils::instance;
+use utils::launch::Launch;
+use utils::layout::{self, Bindings};
+use utils::lookup;
+use utils::metrics;
+use utils::monitor::Monitor;
+use utils::observe::{self, Job, Status};
+use utils::plugin::{self, WidgetPlugin};
+use utils::queue::{self, Queue};
+use utils::settings;
+use utils::store;
 use utils::theme::Theme;
+use utils::updater::{self, Release, Updater};
+use utils::watcher;
 use widgets::menubar::MenuBar;
-use widgets::widget::Widget;
+use widgets::widget::{self, Downcast, Widget};
 
 use json;
+use json::JsonValue;
+
+/// The maximal number of updates triggered by an event, while the stores are
+/// changed by the listeners updating
+const MAX_UPDATES: u32 = 8;
 
 /// # An abstract application
 ///
+/// The application keeps a History of commands, shared by all the
+/// listeners: a listener executes a command with `execute`, and an Edit menu
+/// or a toolbar calls `undo` and `redo`.
+///
 /// ## Example
 ///
 /// ```text
+/// App::execute(Box::new(my_command));
+/// App::undo();
 /// App::run(my_window);
 /// ```
 pub struct App;
 
 impl App {
-    /// Run the application
-    pub fn run(mut window: Window) {
-        let title = &window.title.to_owned();
-        let width = window.width;
-        let height = window.height;
-        let resizable = window.resizable;
-        let debug = window.debug;
+    /// Execute a command and push it onto the History of the application
+    pub fn execute(command: Box<dyn Command>) {
+        history::with_app(|history| history.execute(command));
+        document::set_modified(true);
+    }
 
-        let context = if debug {
-            ""
-        } else {
-            r#"(function() { event.preventDefault(); } )()"#
-        };
+    /// Push a command which has already been executed onto the History of
+    /// the application
+    pub fn push(command: Box<dyn Command>) {
+        history::with_app(|history| history.push(command));
+        document::set_modified(true);
+    }
 
-        let html = format!(
-            r#"
-            <!doctype html>
-            <html>
-                <head>
-                    <meta charset="UTF-8">
-                    {styles}
-                </head>
-                <body onkeydown="{key}" onmousedown="{click}" oncontextmenu="{context}">
-                    <div id="app"></div>
-                    {scripts}
-                </body>
-            </html>
-            "#,
-            styles = format!(
-                "{}\n{}\n{}\n",
-                inline_style(include_str!(concat!(
-                    env!("OUT_DIR"),
-                    "/app.css"
-                ))),
-                inline_style(&window.theme.css()),
-                inline_style(&window.custom_css),
-            ),
-            scripts = format!(
-                "{}\n{}\n",
-                inline_script(include_str!("www/app/morphdom.min.js")),
-                inline_script(include_str!("www/app/app.js"))
-            ),
-            key = Event::key_js(),
-            click = Event::undefined_js(),
-            context = context,
-        );
+    /// Undo the last command of the application. Return false if there is
+    /// none.
+    pub fn undo() -> bool {
+        let undone = history::with_app(|history| history.undo());
+        if undone {
+            document::set_modified(true);
+        }
+        undone
+    }
 
-        let webview = web_view::builder()
-            .title(title)
-            .content(Content::Html(html))
-            .size(width, height)
-            .resizable(resizable)
-            .user_data("")
-            .debug(debug)
-            .invoke_handler(|webview, arg| {
-                let event: Event = match json::parse(arg) {
-                    Ok(value) => match value["type"].as_str().unwrap() {
-                        "Update" => Event::Update,
-                        "Key" => match Key::new(value["key"].as_str().unwrap())
-                        {
-                            Some(key) => Event::Key { key },
-                            None => Event::Undefined,
-                        },
-                        "Change" => Event::Change {
-                            source: value["source"]
-                                .as_str()
-                                .unwrap()
-                                .to_string(),
-                            value: value["value"].as_str().unwrap().to_string(),
-                        },
-                        _ => Event::Undefined,
-                    },
-                    Err(_) => Event::Undefined,
-                };
-                window.trigger(&event);
-                match event {
-                    Event::Undefined => (),
-                    _ => window.trigger(&Event::Update),
-                };
-                window.render(webview)
-            })
-            .build()
-            .unwrap();
+    /// Redo the last undone command of the application. Return false if
+    /// there is none.
+    pub fn redo() -> bool {
+        let redone = history::with_app(|history| history.redo());
+        if redone {
+            document::set_modified(true);
+        }
+        redone
+    }
+
+    /// Return true if a command of the application can be undone
+    pub fn can_undo() -> bool {
+        history::with_app(|history| history.can_undo())
+    }
+
+    /// Return true if a command of the application can be redone
+    pub fn can_redo() -> bool {
+        history::with_app(|history| history.can_redo())
+    }
+
+    /// Get the name of the command of the application which would be undone
+    pub fn undo_name() -> Option<String> {
+        history::with_app(|history| history.undo_name())
+    }
+
+    /// Get the name of the command of the application which would be redone
+    pub fn redo_name() -> Option<String> {
+        history::with_app(|history| history.redo_name())
+    }
+
+    /// Set the maximal number of commands of the application which can be
+    /// undone
+    pub fn set_history_limit(limit: Option<usize>) {
+        history::with_app(|history| history.set_limit(limit));
+    }
+
+    /// Remove all the commands of the application, for instance when a new
+    /// document is opened
+    pub fn clear_history() {
+        history::with_app(|history| history.clear());
+    }
+
+    /// Set the document edited by the application (see the `document`
+    /// module of `utils`)
+    pub fn set_document(document: Document) {
+        document::set(document);
+    }
+
+    /// Set the modified flag of the document
+    pub fn set_modified() {
+        document::set_modified(true);
+    }
+
+    /// Return true if the document is modified
+    pub fn is_modified() -> bool {
+        document::with(|document| document.modified()).unwrap_or(false)
+    }
+
+    /// Get the path of the document, None for a new document
+    pub fn document_path() -> Option<PathBuf> {
+        document::with(|document| document.path().map(Path::to_path_buf))
+            .flatten()
+    }
+
+    /// Get the recent files, most recent first
+    pub fn recent_documents() -> Vec<PathBuf> {
+        document::with(|document| document.recent().clone()).unwrap_or_default()
+    }
+
+    /// Start a new document, once the modified document has been saved or
+    /// its changes discarded
+    pub fn new_document() {
+        document::request(Action::New);
+    }
+
+    /// Open the document at the given path, once the modified document has
+    /// been saved or its changes discarded
+    pub fn open_document<P: AsRef<Path>>(path: P) {
+        document::request(Action::Open(path.as_ref().to_path_buf()));
+    }
+
+    /// Save the document at its path
+    pub fn save_document() -> Result<(), String> {
+        document::save()
+    }
+
+    /// Save the document at the given path
+    pub fn save_document_as<P: AsRef<Path>>(path: P) -> Result<(), String> {
+        document::save_as(path.as_ref())
+    }
+
+    /// Close the window once the current event has been processed, and the
+    /// modified document saved or its changes discarded
+    pub fn quit() {
+        document::request(Action::Quit);
+    }
+
+    /// Hide the window once the current event has been processed, the
+    /// application going on in the background (see the `tray` module)
+    #[cfg(feature = "tray")]
+    pub fn hide() {
+        tray::set_visible(false);
+    }
+
+    /// Show the window hidden by `App::hide` once the current event has been
+    /// processed
+    #[cfg(feature = "tray")]
+    pub fn show() {
+        tray::set_visible(true);
+    }
+
+    /// Set the sync queue of the application (see the `queue` module of
+    /// `utils`)
+    pub fn set_queue(queue: Queue) {
+        queue::set(queue);
+    }
+
+    /// Record a job in the sync queue, run in the background once the
+    /// network is online. Return the identifier of the job.
+    pub fn enqueue(kind: &str, payload: JsonValue) -> Result<u64, String> {
+        queue::enqueue(kind, payload)
+    }
+
+    /// Get the number of jobs of the sync queue waiting to be run
+    pub fn queue_length() -> usize {
+        queue::length()
+    }
+
+    /// Run the jobs of the sync queue waiting to be run, the failed ones
+    /// included, if the network is online
+    pub fn sync() {
+        queue::sync();
+    }
+
+    /// Return true if the page reports the network as online
+    pub fn is_online() -> bool {
+        queue::online()
+    }
+
+    /// Export the window into a static HTML page once the current event has
+    /// been processed (see the `export` module of `utils`)
+    pub fn export_html<P: AsRef<Path>>(path: P) {
+        export::export_html(path.as_ref().to_path_buf());
+    }
+
+    /// Open the print dialog once the page has been rendered, to print the
+    /// window or save it as a PDF file
+    pub fn print() {
+        export::print();
+    }
+
+    /// Run a future off the UI thread for the widget with the given name,
+    /// rendered as a loading placeholder until the output of the future is
+    /// given to `apply` on the UI thread (see the `observe` module of
+    /// `utils`)
+    pub fn observe<F, T, A>(name: &str, future: F, apply: A)
+    where
+        F: Future<Output = T> + Send + 'static,
+        T: Send + 'static,
+        A: FnOnce(T) + 'static,
+    {
+        observe::observe(name, future, apply);
+    }
+
+    /// Run a future returning a result off the UI thread for the widget
+    /// with the given name, like `observe`, the widget failing if the future
+    /// returns an error
+    pub fn observe_result<F, T, E, A>(name: &str, future: F, apply: A)
+    where
+        F: Future<Output = Result<T, E>> + Send + 'static,
+        T: Send + 'static,
+        E: Display + Send + 'static,
+        A: FnOnce(T) + 'static,
+    {
+        observe::observe_result(name, future, apply);
+    }
+
+    /// Abandon the future observed by the widget with the given name, whose
+    /// output is dropped, and forget its failure
+    pub fn unobserve(name: &str) {
+        observe::unobserve(name);
+    }
+
+    /// Get the status of the future observed by the widget with the given
+    /// name, None if it is not running and did not fail
+    pub fn observe_status(name: &str) -> Option<Status> {
+        observe::status(name)
+    }
+
+    /// Run the observed futures with the given spawner instead of the
+    /// worker pool of neutrino, such as the one of the async runtime their
+    /// sockets and timers need (see the `observe` module of `utils`)
+    pub fn set_spawner<S>(spawner: S)
+    where
+        S: Fn(Job) + Send + Sync + 'static,
+    {
+        observe::set_spawner(spawner);
+    }
+
+    /// Set the settings of the application, keeping its preferences and the
+    /// state of its window between two runs (see the `settings` module of
+    /// `utils`)
+    pub fn set_settings(settings: settings::Settings) {
+        settings::set(settings);
+    }
+
+    /// Get a preference of the application, None if it is not set
+    pub fn setting(key: &str) -> Option<JsonValue> {
+        settings::get(key)
+    }
+
+    /// Set a preference of the application, written into the file of the
+    /// settings
+    pub fn set_setting<V: Into<JsonValue>>(
+        key: &str,
+        value: V,
+    ) -> Result<(), String> {
+        settings::set_value(key, value.into())
+    }
+
+    /// Animate a style property of a widget once the page has been rendered
+    /// (see the `animation` module of `utils`)
+    pub fn animate(animation: Animation) {
+        animation::animate(animation);
+    }
+
+    /// Write a content into the clipboard once the page has been rendered
+    /// (see the `clipboard` module of `utils`)
+    pub fn copy(data: ClipboardData) {
+        clipboard::copy(data);
+    }
+
+    /// Change the widget with the given name and type once the current event
+    /// has been processed (see the `lookup` module of `utils`)
+    pub fn widget_mut<T, F>(name: &str, change: F)
+    where
+        T: Widget + 'static,
+        F: FnOnce(&mut T) + 'static,
+    {
+        lookup::change(name, change);
+    }
+
+    /// Register a widget pack, bringing its widgets, themes, icons, CSS and
+    /// javascript to the application (see the `plugin` module of `utils`)
+    pub fn register(plugin: Box<dyn WidgetPlugin>) {
+        plugin::register(plugin);
+    }
+
+    /// Add a catalog of messages, replacing the one of the same language
+    /// (see the `i18n` module of `utils`)
+    pub fn add_catalog(catalog: Catalog) {
+        i18n::add_catalog(catalog);
+    }
+
+    /// Set the locale of the application, such as `fr` or `pt-BR`. The
+    /// texts set with a key are translated at the next render.
+    pub fn set_locale(locale: &str) {
+        i18n::set_locale(locale);
+    }
+
+    /// Get the locale of the application, `en` by default
+    pub fn locale() -> String {
+        i18n::locale()
+    }
+
+    /// Format the message with the given key in the locale of the
+    /// application
+    pub fn text(key: &str, args: &[(&str, &str)]) -> String {
+        i18n::translate(key, args)
+    }
+
+    /// Make sure that only one instance of the application with the given
+    /// identifier runs. Return false if another instance runs: the arguments
+    /// have been sent to it and this one should exit (see the `instance`
+    /// module of `utils`).
+    pub fn single_instance(id: &str) -> bool {
+        instance::guard(id)
+    }
+
+    /// Load the window described by the layout at the given path, in JSON,
+    /// RON or YAML according to its extension, binding the listeners to its
+    /// widgets by name
+    pub fn from_file<P: AsRef<Path>>(
+        path: P,
+        bindings: Bindings,
+    ) -> Result<Window, String> {
+        layout::load_file(path, bindings)
+    }
 
-        webview.run().unwrap();
+    /// Serve the application over HTTP instead of running it in a webview,
+    /// until it quits (see the `server` module)
+    #[cfg(feature = "server")]
+    pub fn serve(window: Window, server: server::Server) -> Result<(), String> {
+        server::serve(window, server)
+    }
+
+    /// Expose the window run by `App::run` or `App::run_with` to WebDriver
+    /// clients (see the `webdriver` module)
+    #[cfg(feature = "webdriver")]
+    pub fn set_webdriver(driver: webdriver::WebDriver) {
+        webdriver::set(driver);
+    }
+
+    /// Run the application with the wry backend with the `wry` feature,
+    /// and with the web-view backend otherwise
+    #[cfg(any(feature = "web-view", feature = "wry"))]
+    pub fn run(window: Window) {
+        #[cfg(feature = "wry")]
+        App::run_with(window, WryBackend).unwrap();
+        #[cfg(not(feature = "wry"))]
+        App::run_with(window, WebViewBackend).unwrap();
         std::process::exit(0);
     }
+
+    /// Run the application with the given backend (see the `backend`
+    /// module)
+    pub fn run_with<B: Backend>(
+        mut window: Window,
+        mut backend: B,
+    ) -> Result<(), String> {
+        let debug = !window.kiosk
+            && (window.debug || (window.devtools && cfg!(debug_assertions)));
+
+        if debug {
+            if let Some(inspector) = &window.inspector {
+                std::env::set_var("WEBKIT_INSPECTOR_SERVER", inspector);
+            }
+        }
+
+        if debug {
+            for issue in window.accessibility() {
+                event::report_error("accessibility", &issue.message());
+            }
+        }
+
+        if settings::is_set() {
+            window.process(&Event::Restore);
+        }
+
+        let settings = Settings {
+            title: window.title(),
+            html: window.page(debug, ""),
+            width: window.width,
+            height: window.height,
+            resizable: window.resizable && !window.kiosk,
+            fullscreen: window.kiosk,
+            debug,
+            #[cfg(feature = "tray")]
+            tray: window.tray.clone(),
+        };
+
+        let mut watched = vec![];
+        if let Some((path, _)) = &window.layout_file {
+            watched.push(path.to_owned());
+        }
+        if let Some(path) = &window.css_file {
+            watched.push(path.to_owned());
+        }
+
+        let args = std::env::args().skip(1).collect::<Vec<String>>();
+        let mut launch = Some(Launch::parse(&args, window.scheme()));
+
+        let updater = window.updater.clone();
+        let start = Box::new(move |proxy: Proxy| {
+            if let Some(updater) = updater {
+                updater::check(updater, proxy.clone());
+            }
+
+            instance::listen(proxy.clone());
+
+            document::start_autosave(proxy.clone());
+
+            queue::start(proxy.clone());
+
+            observe::start(proxy.clone());
+
+            #[cfg(feature = "webdriver")]
+            webdriver::start(proxy.clone());
+
+            if cfg!(debug_assertions) && !watched.is_empty() {
+                watcher::watch(watched, proxy);
+            }
+        });
+
+        backend.run(settings, start, &mut |view, arg| {
+            let event: Event = match json::parse(arg) {
+                Ok(value) => match value["type"].as_str() {
+                    Some("Reload") => {
+                        window.reload(view)?;
+                        Event::Update
+                    }
+                    #[cfg(feature = "webdriver")]
+                    Some("Driver") => {
+                        webdriver::reply(&value);
+                        return Ok(());
+                    }
+                    _ => Event::from_json(&value),
+                },
+                Err(error) => Event::Error {
+                    source: "".to_string(),
+                    message: format!("invalid event {}: {}", arg, error),
+                },
+            };
+            match updater::received() {
+                Some(Ok(release)) => {
+                    window.process(&Event::UpdateAvailable { release })
+                }
+                Some(Err(error)) => {
+                    event::report_error(updater::UPDATER, &error)
+                }
+                None => (),
+            }
+            window.process(&event);
+            match event {
+                Event::Geometry { .. } => Ok(()),
+                _ => window.render(view),
+            }?;
+            match launch.take() {
+                Some(launch) if !launch.is_empty() => {
+                    window.process(&Event::Launch { launch });
+                    window.render(view)
+                }
+                _ => Ok(()),
+            }?;
+            #[cfg(feature = "tray")]
+            if let Some(visible) = tray::take_visible() {
+                if let Err(error) = view.set_visible(visible) {
+                    event::report_error("tray", &error);
+                }
+            }
+            if document::quitting() {
+                view.terminate();
+            }
+            Ok(())
+        })
+    }
+}
+
+/// # The state of a Window
+///
+/// ## Fields
+///
+/// ```text
+/// focus: Option<String>
+/// tab_order: Vec<String>
+/// size: Option<(i32, i32)>
+/// pointer: Option<(f64, f64)>
+/// bounds: Vec<(String, Bounds)>
+/// monitors: Vec<Monitor>
+/// monitor: Option<usize>
+/// ```
+pub struct WindowState {
+    focus: Option<String>,
+    tab_order: Vec<String>,
+    size: Option<(i32, i32)>,
+    pointer: Option<(f64, f64)>,
+    bounds: Vec<(String, Bounds)>,
+    monitors: Vec<Monitor>,
+    monitor: Option<usize>,
+}
+
+impl WindowState {
+    /// Get the name of the focused widget
+    pub fn focus(&self) -> Option<&str> {
+        self.focus.as_ref().map(String::as_ref)
+    }
+
+    /// Get the tab order
+    pub fn tab_order(&self) -> &Vec<String> {
+        &self.tab_order
+    }
+
+    /// Get the size of the page in pixels, as (width, height), known once
+    /// it has been displayed and updated when the window is resized
+    pub fn size(&self) -> Option<(i32, i32)> {
+        self.size
+    }
+
+    /// Get the position of the pointer in the page in pixels, as (x, y),
+    /// None if it is outside of the page
+    pub fn pointer(&self) -> Option<(f64, f64)> {
+        self.pointer
+    }
+
+    /// Get the bounds of an element of the page, as last reported by the
+    /// page
+    pub fn bounds(&self, name: &str) -> Option<Bounds> {
+        self.bounds
+            .iter()
+            .find(|(element, _)| element == name)
+            .map(|(_, bounds)| *bounds)
+    }
+
+    /// Get the monitors of the system, known once the page has been
+    /// displayed
+    pub fn monitors(&self) -> &Vec<Monitor> {
+        &self.monitors
+    }
+
+    /// Get the index of the monitor the window is placed on
+    pub fn monitor(&self) -> Option<usize> {
+        self.monitor
+    }
+
+    /// Set the name of the widget to focus
+    pub fn set_focus(&mut self, focus: Option<&str>) {
+        self.focus = focus.map(str::to_string);
+    }
+
+    /// Set the names of the widgets in the order they are focused when the
+    /// Tab key is pressed. An empty list keeps the order of the document.
+    pub fn set_tab_order(&mut self, tab_order: Vec<&str>) {
+        self.tab_order = tab_order.iter().map(|t| t.to_string()).collect();
+    }
+
+    /// Set the index of the monitor to place the window on, in the list of
+    /// the monitors
+    pub fn set_monitor(&mut self, monitor: Option<usize>) {
+        self.monitor = monitor;
+    }
 }
 
 /// # The listener of a Window
 pub trait WindowListener {
     /// Function triggered on key event
     fn on_key(&self, _key: Key);
+
+    /// Function triggered on update event
+    fn on_update(&self, _state: &mut WindowState) {}
+
+    /// Function triggered when a widget gains the focus
+    fn on_focus(&self, _source: &str) {}
+
+    /// Function triggered when a widget loses the focus
+    fn on_blur(&self, _source: &str) {}
+
+    /// Function triggered when the window is resized, and once when it is
+    /// displayed, with the size of the page in pixels
+    fn on_resize(&self, _width: i32, _height: i32) {}
+
+    /// Function triggered when an animation started with `App::animate` is
+    /// over, with the name of the animated widget and of the animation
+    fn on_animation_end(&self, _source: &str, _name: &str) {}
+
+    /// Function triggered when something is pasted in the window, with the
+    /// name of the focused widget, empty if there is none
+    fn on_paste(&self, _source: &str, _data: &ClipboardData) {}
+
+    /// Function triggered when the javascript of the application sends a
+    /// custom event with `neutrino.emit(name, value)`
+    fn on_custom(&self, _name: &str, _value: &JsonValue) {}
+
+    /// Function triggered when the updater of the window has found a newer
+    /// release of the application
+    fn on_update_available(&self, _release: &Release) {}
+
+    /// Function triggered when another instance of the application is
+    /// started, with its command-line arguments, the program name excluded
+    fn on_instance(&self, _args: &[String]) {}
+
+    /// Function triggered when the icon of the tray is clicked, without an
+    /// item, or when an item of its menu is chosen, with the name of the
+    /// item. Clicking the icon shows the window by default.
+    #[cfg(feature = "tray")]
+    fn on_tray(&self, item: Option<&str>) {
+        if item.is_none() {
+            App::show();
+        }
+    }
+
+    /// Function triggered after the first render with the command-line
+    /// arguments of the application, and with the ones of another instance
+    /// when they are not empty
+    fn on_launch(&self, _launch: &Launch) {}
+
+    /// Function triggered before the first render when the application has
+    /// settings, to restore its preferences with `App::setting`
+    fn on_restore(&self) {}
+
+    /// Function triggered when the network goes online or offline, and once
+    /// when the window is displayed
+    fn on_network(&self, _online: bool) {}
+
+    /// Function triggered on error event, when a message of the page cannot
+    /// be read or a widget reports a value it cannot handle. The error is
+    /// printed on the standard error by default.
+    fn on_error(&self, source: &str, message: &str) {
+        eprintln!("{}: {}", source, message);
+    }
 }
 
 /// # A window containing the widgets
 ///
+/// The focused widget is tracked through the focus and blur events and
+/// restored after each render. A widget can be focused from Rust with
+/// `set_focus`, or through the state given to the `on_update` function of the
+/// listener.
+///
+/// To inspect the generated DOM and CSS, `set_devtools` enables the developer
+/// tools of the webview in debug builds, and `set_inspector` serves them to a
+/// remote browser. `set_debug` enables them in release builds too.
+///
+/// `set_strict_csp` generates the page with a strict content-security policy,
+/// only allowing the scripts and the event handlers of neutrino (see the
+/// `csp` module of `utils`).
+///
+/// `set_monitor` centers the window on one of the monitors of the system,
+/// such as a secondary display, once the page has reported them (see the
+/// `monitor` module of `utils`).
+///
+/// `set_history_shortcuts` makes `Ctrl + Z` undo and `Ctrl + Y` redo the
+/// commands of the application, before the key event reaches the listener.
+///
+/// When the application has settings, a resizable window is given the size
+/// it had when it was last resized (see the `settings` module of `utils`).
+///
+/// `set_updater` checks a release feed once the window is displayed (see the
+/// `updater` module of `utils`).
+///
+/// `set_scheme` sets the URL scheme of the links given to the application,
+/// such as `myapp://item/42`, which reach the listener with the other
+/// command-line arguments after the first render (see the `launch` module of
+/// `utils`).
+///
+/// `set_kiosk` runs the window fullscreen for point-of-sale and signage
+/// applications: it cannot be resized, and the context menu and the
+/// developer tools are disabled, even with `set_debug`. `set_block_shortcuts`
+/// also swallows the shortcuts of the webview, such as reloading, zooming,
+/// printing or opening the developer tools. The webview cannot remove the
+/// buttons of the window nor catch the shortcuts of the system, such as
+/// `Alt + Tab` or `Alt + F4`, which have to be disabled in the session of
+/// the kiosk.
+///
 /// ## Fields
 ///
 /// ```text
@@ -159,10 +774,26 @@ pub trait WindowListener {
 /// height: i32
 /// resizable: bool
 /// debug: bool
+/// devtools: bool
+/// inspector: Option<String>
+/// strict_csp: bool
+/// history_shortcuts: bool
+/// kiosk: bool
+/// block_shortcuts: bool
 /// theme: Theme
+/// plugin_theme: Option<String>
 /// custom_css: String
+/// css_file: Option<PathBuf>
+/// layout_file: Option<(PathBuf, Box<dyn Fn() -> Bindings>)>
+/// updater: Option<Updater>
+/// tray: Option<Tray>
+/// scheme: Option<String>
 /// child: Option<Box<dyn Widget>>
 /// menubar: Option<MenuBar>
+/// state: WindowState
+/// placed: Option<usize>
+/// displayed_title: Option<String>
+/// hooks: Vec<Box<dyn EventHook>>
 /// listener: Option<Box<dyn WindowListener>>
 /// ```
 ///
@@ -174,10 +805,32 @@ pub trait WindowListener {
 /// height: 480
 /// resizable: false
 /// debug: false
+/// devtools: false
+/// inspector: None
+/// strict_csp: false
+/// history_shortcuts: false
+/// kiosk: false
+/// block_shortcuts: false
 /// theme: Theme::Default
+/// plugin_theme: None
 /// custom_css: "".to_string()
+/// css_file: None
+/// layout_file: None
+/// updater: None
+/// tray: None
+/// scheme: None
 /// child: None
 /// menubar: None
+/// state:
+///     focus: None
+///     tab_order: vec![]
+///     size: None
+///     pointer: None
+///     bounds: vec![]
+///     monitors: vec![]
+///     monitor: None
+/// placed: None
+/// displayed_title: None
 /// listener: None
 /// ```
 ///
@@ -191,6 +844,11 @@ pub trait WindowListener {
 ///     my_window.set_title("Title");
 ///     my_window.set_size(800, 600);
 ///     my_window.set_resizable();
+///     my_window.set_focus("username");
+///     my_window.set_tab_order(vec!["username", "password", "submit"]);
+///     my_window.set_devtools();
+///     my_window.set_strict_csp();
+///     my_window.set_history_shortcuts();
 ///
 ///     // App::run(window);
 /// }
@@ -201,10 +859,28 @@ pub struct Window {
     height: i32,
     resizable: bool,
     debug: bool,
+    devtools: bool,
+    inspector: Option<String>,
+    strict_csp: bool,
+    history_shortcuts: bool,
+    kiosk: bool,
+    block_shortcuts: bool,
+    handlers: Handlers,
     theme: Theme,
+    plugin_theme: Option<String>,
     custom_css: String,
+    css_file: Option<PathBuf>,
+    layout_file: Option<(PathBuf, Box<dyn Fn() -> Bindings>)>,
+    updater: Option<Updater>,
+    #[cfg(feature = "tray")]
+    tray: Option<tray::Tray>,
+    scheme: Option<String>,
     child: Option<Box<dyn Widget>>,
     menubar: Option<MenuBar>,
+    state: WindowState,
+    placed: Option<usize>,
+    displayed_title: Option<String>,
+    hooks: Vec<Box<dyn EventHook>>,
     listener: Option<Box<dyn WindowListener>>,
 }
 
@@ -217,19 +893,54 @@ impl Window {
             height: 480,
             resizable: false,
             debug: false,
+            devtools: false,
+            inspector: None,
+            strict_csp: false,
+            history_shortcuts: false,
+            kiosk: false,
+            block_shortcuts: false,
+            handlers: Handlers::new(),
             theme: Theme::Default,
+            plugin_theme: None,
             custom_css: "".to_string(),
+            css_file: None,
+            layout_file: None,
+            updater: None,
+            #[cfg(feature = "tray")]
+            tray: None,
+            scheme: None,
             child: None,
             menubar: None,
+            state: WindowState {
+                focus: None,
+                tab_order: vec![],
+                size: None,
+                pointer: None,
+                bounds: vec![],
+                monitors: vec![],
+                monitor: None,
+            },
+            placed: None,
+            displayed_title: None,
+            hooks: vec![],
             listener: None,
         }
     }
 
-    /// Set the child
-    pub fn set_child(&mut self, widget: Box<dyn Widget>) {
+    /// Set the child, telling it the size of the page if it is known
+    pub fn set_child(&mut self, mut widget: Box<dyn Widget>) {
+        if let Some((width, height)) = self.state.size {
+            widget.trigger(&Event::Resize { width, height });
+        }
         self.child = Some(widget);
     }
 
+    /// Add a hook called for each event sent by the page, before and after
+    /// the widgets see it
+    pub fn add_hook(&mut self, hook: Box<dyn EventHook>) {
+        self.hooks.push(hook);
+    }
+
     /// Set the menubar
     pub fn set_menubar(&mut self, menubar: MenuBar) {
         self.menubar = Some(menubar);
@@ -256,9 +967,58 @@ impl Window {
         self.debug = true;
     }
 
+    /// Set the devtools flag to true: the developer tools of the webview,
+    /// opened from its context menu, are enabled in debug builds only
+    pub fn set_devtools(&mut self) {
+        self.devtools = true;
+    }
+
+    /// Set the address (such as "127.0.0.1:9222") of the remote inspector,
+    /// served when the developer tools are enabled. Only WebKitGTK, on Linux,
+    /// supports remote debugging.
+    pub fn set_inspector(&mut self, address: &str) {
+        self.inspector = Some(address.to_string());
+    }
+
+    /// Set the strict_csp flag to true: the page forbids any script which is
+    /// not written by neutrino, such as a handler injected through a text
+    pub fn set_strict_csp(&mut self) {
+        self.strict_csp = true;
+    }
+
+    /// Set the history_shortcuts flag to true: `Ctrl + Z` and `Ctrl + Y`
+    /// undo and redo the commands of the application
+    pub fn set_history_shortcuts(&mut self) {
+        self.history_shortcuts = true;
+    }
+
+    /// Set the kiosk flag to true: the window runs fullscreen, without
+    /// resizing, context menu nor developer tools
+    pub fn set_kiosk(&mut self) {
+        self.kiosk = true;
+    }
+
+    /// Set the block_shortcuts flag to true: the page swallows the shortcuts
+    /// of the webview, the key events still reaching the application
+    pub fn set_block_shortcuts(&mut self) {
+        self.block_shortcuts = true;
+    }
+
     /// Set the theme
     pub fn set_theme(&mut self, theme: Theme) {
         self.theme = theme;
+        self.plugin_theme = None;
+    }
+
+    /// Set the theme to the theme of a registered plugin with the given name
+    pub fn set_plugin_theme(&mut self, name: &str) -> Result<(), String> {
+        match plugin::theme(name) {
+            Some(_) => {
+                self.plugin_theme = Some(name.to_string());
+                Ok(())
+            }
+            None => Err(format!("no plugin has a theme named {}", name)),
+        }
     }
 
     /// Set the custom CSS
@@ -266,49 +1026,519 @@ impl Window {
         self.custom_css = css.to_string();
     }
 
+    /// Set the custom CSS from the file at the given path, reloaded in debug
+    /// builds when it changes on disk
+    pub fn set_custom_css_file<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+    ) -> io::Result<()> {
+        let path = path.as_ref().to_path_buf();
+        self.custom_css = fs::read_to_string(&path)?;
+        self.css_file = Some(path);
+        Ok(())
+    }
+
+    /// Set the layout file the window has been loaded from, reloaded in debug
+    /// builds when it changes on disk. The listeners are bound to the new
+    /// widgets by the given function.
+    pub fn set_layout_file<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+        bindings: Box<dyn Fn() -> Bindings>,
+    ) {
+        self.layout_file = Some((path.as_ref().to_path_buf(), bindings));
+    }
+
+    /// Set the updater, checking its release feed once the window is
+    /// displayed
+    pub fn set_updater(&mut self, updater: Updater) {
+        self.updater = Some(updater);
+    }
+
+    /// Set the icon of the window in the system tray, displayed by the
+    /// backends which support it (see the `tray` module)
+    #[cfg(feature = "tray")]
+    pub fn set_tray(&mut self, tray: tray::Tray) {
+        self.tray = Some(tray);
+    }
+
+    /// Set the URL scheme of the links given to the application, such as
+    /// `myapp` for `myapp://item/42`
+    pub fn set_scheme(&mut self, scheme: &str) {
+        self.scheme = Some(scheme.to_string());
+    }
+
+    /// Get the URL scheme of the links given to the application
+    pub(crate) fn scheme(&self) -> Option<&str> {
+        self.scheme.as_deref()
+    }
+
+    /// Set the name of the widget to focus
+    pub fn set_focus(&mut self, name: &str) {
+        self.state.set_focus(Some(name));
+    }
+
+    /// Set the names of the widgets in the order they are focused when the
+    /// Tab key is pressed
+    pub fn set_tab_order(&mut self, tab_order: Vec<&str>) {
+        self.state.set_tab_order(tab_order);
+    }
+
+    /// Set the index of the monitor to place the window on
+    pub fn set_monitor(&mut self, monitor: usize) {
+        self.state.set_monitor(Some(monitor));
+    }
+
     /// Set the listener
     pub fn set_listener(&mut self, listener: Box<dyn WindowListener>) {
         self.listener = Some(listener);
     }
 
+    /// Get the title displayed by the window, showing the name of the
+    /// document of the application if any (see the `document` module of
+    /// `utils`)
+    pub fn title(&self) -> String {
+        document::title(&self.title)
+    }
+
+    /// Get the position of the pointer in the page in pixels, as (x, y),
+    /// None if it is outside of the page
+    pub fn pointer(&self) -> Option<(f64, f64)> {
+        self.state.pointer()
+    }
+
+    /// Get the monitors of the system, known once the page has been
+    /// displayed
+    pub fn monitors(&self) -> &Vec<Monitor> {
+        self.state.monitors()
+    }
+
+    /// Get the widget with the given name and type
+    pub fn widget<T: Widget + 'static>(&self, name: &str) -> Option<&T> {
+        let child = self.child.as_ref()?;
+        lookup::find(child.as_ref(), name)?.downcast_ref::<T>()
+    }
+
+    /// Get the widget with the given name and type, to change it
+    pub fn widget_mut<T: Widget + 'static>(
+        &mut self,
+        name: &str,
+    ) -> Option<&mut T> {
+        let child = self.child.as_mut()?;
+        lookup::find_mut(child.as_mut(), name)?.downcast_mut::<T>()
+    }
+
+    /// Get the name of the innermost widget at a position of the page, in
+    /// pixels, from the bounds last reported by the page
+    pub fn widget_at(&self, x: f64, y: f64) -> Option<String> {
+        let mut names = vec![];
+        if let Some(child) = &self.child {
+            widget_names(child.as_ref(), &mut names);
+        }
+        self.state
+            .bounds
+            .iter()
+            .rev()
+            .find(|(name, bounds)| {
+                names.contains(name) && bounds.contains(x, y)
+            })
+            .map(|(name, _)| name.to_string())
+    }
+
+    /// Process an event sent by the page: pass it to the hooks and, unless
+    /// one of them stops it, trigger it in the widget tree then, unless
+    /// nothing happened, update the widget tree
+    pub(crate) fn process(&mut self, event: &Event) {
+        audit::observe(event);
+        metrics::observe(event);
+        let propagation = self
+            .hooks
+            .iter()
+            .map(|hook| hook.before(event))
+            .find(|propagation| *propagation == Propagation::Stop)
+            .unwrap_or(Propagation::Continue);
+        if propagation == Propagation::Continue {
+            self.trigger(event);
+        }
+        self.trigger_errors();
+        let changed = store::take_changed();
+        let update = match (propagation, event) {
+            (Propagation::Stop, _)
+            | (_, Event::Undefined)
+            | (_, Event::Geometry { .. }) => changed,
+            _ => true,
+        };
+        if update {
+            self.trigger(&Event::Update);
+            let mut updates = 1;
+            while store::take_changed() && updates < MAX_UPDATES {
+                self.trigger(&Event::Update);
+                updates += 1;
+            }
+            self.trigger_errors();
+        }
+        self.apply_changes();
+        self.export();
+        if propagation == Propagation::Continue {
+            for hook in &self.hooks {
+                hook.after(event);
+            }
+        }
+    }
+
+    /// Apply the changes of widgets queued with `App::widget_mut`
+    fn apply_changes(&mut self) {
+        let changes = lookup::take();
+        if changes.is_empty() {
+            return;
+        }
+        for (name, change) in changes {
+            let widget = match &mut self.child {
+                Some(child) => lookup::find_mut(child.as_mut(), &name),
+                None => None,
+            };
+            match widget {
+                Some(widget) => change(widget),
+                None => event::report_error(&name, "no widget has this name"),
+            }
+        }
+        self.trigger_errors();
+    }
+
+    /// Write the static HTML pages queued with `App::export_html`
+    fn export(&mut self) {
+        for path in export::take() {
+            if let Err(error) = fs::write(&path, self.snapshot()) {
+                let message = format!("{}: {}", path.display(), error);
+                event::report_error("export", &message);
+            }
+        }
+        self.trigger_errors();
+    }
+
+    /// Trigger the errors reported by the widgets
+    fn trigger_errors(&mut self) {
+        for error in event::take_errors() {
+            self.trigger(&error);
+        }
+    }
+
+    /// Audit the accessibility of the menubar, of the widget tree, of the
+    /// theme and of the custom CSS
+    pub fn accessibility(&self) -> Vec<Issue> {
+        let mut issues = accessibility::audit_html(&self.eval());
+        issues.extend(accessibility::contrast(&self.theme_css()));
+        issues.extend(accessibility::contrast(&self.custom_css));
+        issues
+    }
+
+    /// Describe the menubar and the widget tree in the DOT language of
+    /// Graphviz, under a node for the window labelled with its title
+    pub fn to_dot(&self) -> String {
+        let mut lines = vec![format!(
+            r#"n0 [label="{}\nWindow"];"#,
+            graphviz::quote(&self.title)
+        )];
+        let mut count = 1;
+        if self.menubar.is_some() {
+            lines.push(r#"n1 [label="menubar\nMenuBar"];"#.to_string());
+            lines.push("n0 -> n1;".to_string());
+            count += 1;
+        }
+        if let Some(child) = &self.child {
+            let id = graphviz::node(child.as_ref(), &mut lines, &mut count);
+            lines.push(format!("n0 -> n{};", id));
+        }
+        graphviz::graph(&lines)
+    }
+
+    /// Get the CSS of the theme, the theme of a plugin replacing the one of
+    /// neutrino if it is set
+    fn theme_css(&self) -> String {
+        let plugin_theme = self.plugin_theme.as_ref();
+        match plugin_theme.and_then(|name| plugin::theme(name)) {
+            Some(css) => css,
+            None => self.theme.css().to_string(),
+        }
+    }
+
+    /// Return the HTML of the page, running the given javascript before the
+    /// one of the application
+    pub(crate) fn page(&mut self, debug: bool, script: &str) -> String {
+        let nonce = if self.strict_csp {
+            csp::enable();
+            Some(csp::nonce())
+        } else {
+            None
+        };
+
+        let context = if debug {
+            "".to_string()
+        } else {
+            Event::prevent_default_js()
+        };
+
+        let mut body = format!(
+            r#"<body data-block-shortcuts="{block}" onkeydown="{key}" onmousedown="{click}" oncontextmenu="{context}">"#,
+            block = self.block_shortcuts,
+            key = Event::key_js(),
+            click = Event::undefined_js(),
+            context = context,
+        );
+        let mut handlers = "".to_string();
+        if self.strict_csp {
+            let (strict_body, definitions) = self.handlers.delegate(&body);
+            body = strict_body.replace("<body", r#"<body data-strict="true""#);
+            handlers = definitions;
+        }
+
+        format!(
+            r#"
+            <!doctype html>
+            <html>
+                <head>
+                    <meta charset="UTF-8">
+                    {policy}
+                    {styles}
+                </head>
+                {body}
+                    <div id="app"></div>
+                    {morphdom}
+                    {script}
+                    {app}
+                    {plugins}
+                    {handlers}
+                </body>
+            </html>
+            "#,
+            policy = match &nonce {
+                Some(nonce) => csp::policy(nonce),
+                None => "".to_string(),
+            },
+            body = body,
+            styles = self.styles(),
+            morphdom =
+                inline_script(include_str!("www/app/morphdom.min.js"), &nonce),
+            script = if script.is_empty() {
+                "".to_string()
+            } else {
+                inline_script(script, &nonce)
+            },
+            app = inline_script(include_str!("www/app/app.js"), &nonce),
+            plugins = inline_script(&plugin::js(), &nonce),
+            handlers = inline_script(&handlers, &nonce),
+        )
+    }
+
+    /// Return the HTML style tags of the page: the base style, the style of
+    /// the plugins, the theme and the custom CSS
+    fn styles(&self) -> String {
+        format!(
+            "{}\n{}\n{}\n{}\n",
+            inline_style(include_str!(concat!(env!("OUT_DIR"), "/app.css"))),
+            inline_style(&plugin::css()),
+            named_style("theme", &self.theme_css()),
+            named_style("custom", &self.custom_css),
+        )
+    }
+
+    /// Return a static HTML page showing the menubar and the widget tree as
+    /// they are, with their styles and without the scripts
+    pub fn snapshot(&self) -> String {
+        format!(
+            r#"<!doctype html>
+<html>
+    <head>
+        <meta charset="UTF-8">
+        <meta http-equiv="Content-Security-Policy" content="script-src 'none'">
+        <title>{title}</title>
+        {styles}
+    </head>
+    <body>
+        <div id="app">{app}</div>
+    </body>
+</html>
+"#,
+            title = escape(&self.title()),
+            styles = self.styles(),
+            app = self.eval(),
+        )
+    }
+
     /// Render the menubar and widget tree
-    fn render(&self, webview: &mut WebView<&str>) -> WVResult {
-        let rendered = format!(
-            r#"render("<div id=\"app\">{}</div>")"#,
-            self.eval().replace(r#"""#, r#"\""#)
+    fn render(&mut self, view: &mut dyn View) -> Result<(), String> {
+        self.render_title(view)?;
+        view.eval(&self.render_js())
+    }
+
+    /// Return the javascript rendering the menubar and the widget tree, and
+    /// running the pending animations and clipboard writes
+    pub(crate) fn render_js(&mut self) -> String {
+        let placement = self.placement_js();
+        let html = format!(
+            r#"<div id="app" data-focus="{}" data-tab-order="{}">{}</div>"#,
+            self.state.focus().unwrap_or(""),
+            self.state.tab_order().join(","),
+            self.eval()
         );
-        webview.eval(&rendered)
+        let (html, definitions) = if self.strict_csp {
+            self.handlers.delegate(&html)
+        } else {
+            (html, "".to_string())
+        };
+        format!(
+            "{}render({});{}{}",
+            definitions,
+            json::stringify(html),
+            pending_js(),
+            placement
+        )
+    }
+
+    /// Set the title of the window if it changed since it was last set
+    fn render_title(&mut self, view: &mut dyn View) -> Result<(), String> {
+        let title = self.title();
+        if self.displayed_title.as_ref() == Some(&title) {
+            return Ok(());
+        }
+        view.set_title(&title)?;
+        self.displayed_title = Some(title);
+        Ok(())
+    }
+
+    /// Return the javascript placing the window on its monitor, if it has
+    /// not been placed on it yet
+    fn placement_js(&mut self) -> String {
+        let monitor = match self.state.monitor() {
+            Some(monitor) if self.placed != Some(monitor) => monitor,
+            _ => return "".to_string(),
+        };
+        match self.state.monitors().get(monitor) {
+            Some(placement) => {
+                self.placed = Some(monitor);
+                placement.js()
+            }
+            None => "".to_string(),
+        }
+    }
+
+    /// Reload the layout file and the custom CSS file, replacing the widget
+    /// tree, the theme and the custom CSS
+    fn reload(&mut self, view: &mut dyn View) -> Result<(), String> {
+        if let Some((path, bindings)) = &self.layout_file {
+            let path = path.to_owned();
+            let bindings = bindings();
+            if let Err(error) = layout::reload(&path, bindings, self) {
+                event::report_error("reload", &error);
+            }
+        }
+        if let Some(path) = &self.css_file {
+            match fs::read_to_string(path) {
+                Ok(css) => self.custom_css = css,
+                Err(error) => event::report_error(
+                    "reload",
+                    &format!("{}: {}", path.display(), error),
+                ),
+            }
+        }
+        view.eval(&format!(
+            "document.getElementById('theme-css').textContent = {};\
+             document.getElementById('custom-css').textContent = {};",
+            json::stringify(self.theme_css()),
+            json::stringify(self.custom_css.as_str()),
+        ))
     }
 
     /// Return the HTML representation of the menubar and the widget tree
     fn eval(&self) -> String {
-        match (&self.menubar, &self.child) {
+        let html = match (&self.menubar, &self.child) {
             (Some(menubar), Some(child)) => {
-                format!("{}{}", menubar.eval(), child.eval())
+                format!("{}{}", menubar.eval(), widget::eval(child.as_ref()))
             }
-            (None, Some(child)) => child.eval().to_string(),
-            (Some(menubar), None) => menubar.eval().to_string(),
+            (None, Some(child)) => widget::eval(child.as_ref()),
+            (Some(menubar), None) => menubar.eval(),
             (None, None) => "".to_string(),
-        }
+        };
+        format!("{}{}", html, document::eval())
     }
 
     /// Trigger the events in the widget tree
     fn trigger(&mut self, event: &Event) {
         match event {
-            Event::Change { .. }
-            | Event::Update
-            | Event::Undefined => {
+            Event::Update => {
+                match &self.listener {
+                    None => (),
+                    Some(listener) => {
+                        listener.on_update(&mut self.state);
+                    }
+                };
                 match (&mut self.menubar, &mut self.child) {
                     (Some(menubar), Some(child)) => {
                         menubar.trigger(event);
-                        child.trigger(event);
+                        widget::trigger(child.as_mut(), event);
+                    }
+                    (None, Some(child)) => {
+                        widget::trigger(child.as_mut(), event)
                     }
-                    (None, Some(child)) => child.trigger(event),
                     (Some(menubar), None) => menubar.trigger(event),
                     (None, None) => (),
                 };
             }
+            Event::Focus { source } => {
+                self.state.set_focus(Some(source.as_str()));
+                match &self.listener {
+                    None => (),
+                    Some(listener) => {
+                        listener.on_focus(source);
+                    }
+                };
+                if let Some(child) = &mut self.child {
+                    widget::trigger(child.as_mut(), event);
+                }
+            }
+            Event::Blur { source } => {
+                if self.state.focus() == Some(source.as_str()) {
+                    self.state.set_focus(None);
+                }
+                match &self.listener {
+                    None => (),
+                    Some(listener) => {
+                        listener.on_blur(source);
+                    }
+                };
+                if let Some(child) = &mut self.child {
+                    widget::trigger(child.as_mut(), event);
+                }
+            }
+            Event::Change { .. }
+            | Event::CanvasClick { .. }
+            | Event::Hover { .. }
+            | Event::DoubleClick { .. }
+            | Event::MouseUp { .. }
+            | Event::Dismiss { .. }
+            | Event::Undefined => {
+                document::trigger(event);
+                if let Some(menubar) = &mut self.menubar {
+                    menubar.trigger(event);
+                }
+                if let Some(child) = &mut self.child {
+                    if widget::reaches(child.as_ref(), event) {
+                        widget::trigger(child.as_mut(), event);
+                    }
+                }
+            }
             Event::Key { key } => {
+                if self.history_shortcuts {
+                    match key {
+                        Key::Z => {
+                            App::undo();
+                        }
+                        Key::Y => {
+                            App::redo();
+                        }
+                        _ => (),
+                    }
+                }
                 match &self.listener {
                     None => (),
                     Some(listener) => {
@@ -318,23 +1548,225 @@ impl Window {
                 match (&mut self.menubar, &mut self.child) {
                     (Some(menubar), Some(child)) => {
                         menubar.trigger(event);
-                        child.trigger(event);
+                        widget::trigger(child.as_mut(), event);
+                    }
+                    (None, Some(child)) => {
+                        widget::trigger(child.as_mut(), event)
                     }
-                    (None, Some(child)) => child.trigger(event),
                     (Some(menubar), None) => menubar.trigger(event),
                     (None, None) => (),
                 };
             }
+            Event::Resize { width, height } => {
+                self.state.size = Some((*width, *height));
+                if self.resizable && !self.kiosk {
+                    let size = JsonValue::from(vec![*width, *height]);
+                    settings::save("window.size", size);
+                }
+                match &self.listener {
+                    None => (),
+                    Some(listener) => {
+                        listener.on_resize(*width, *height);
+                    }
+                };
+                if let Some(child) = &mut self.child {
+                    widget::trigger(child.as_mut(), event);
+                }
+            }
+            Event::AnimationEnd { source, name } => {
+                match &self.listener {
+                    None => (),
+                    Some(listener) => {
+                        listener.on_animation_end(source, name);
+                    }
+                };
+                if let Some(child) = &mut self.child {
+                    widget::trigger(child.as_mut(), event);
+                }
+            }
+            Event::Paste { source, data } => {
+                match &self.listener {
+                    None => (),
+                    Some(listener) => {
+                        listener.on_paste(source, data);
+                    }
+                };
+                if let Some(child) = &mut self.child {
+                    widget::trigger(child.as_mut(), event);
+                }
+            }
+            Event::UpdateAvailable { release } => {
+                match &self.listener {
+                    None => (),
+                    Some(listener) => {
+                        listener.on_update_available(release);
+                    }
+                };
+                if let Some(child) = &mut self.child {
+                    widget::trigger(child.as_mut(), event);
+                }
+            }
+            Event::Instance { args } => {
+                match &self.listener {
+                    None => (),
+                    Some(listener) => {
+                        listener.on_instance(args);
+                        let launch = Launch::parse(args, self.scheme());
+                        if !launch.is_empty() {
+                            listener.on_launch(&launch);
+                        }
+                    }
+                };
+                if let Some(child) = &mut self.child {
+                    widget::trigger(child.as_mut(), event);
+                }
+            }
+            Event::Autosave => {
+                document::autosave();
+                if let Some(child) = &mut self.child {
+                    widget::trigger(child.as_mut(), event);
+                }
+            }
+            #[cfg(feature = "tray")]
+            Event::Tray { item } => {
+                match &self.listener {
+                    None => App::show(),
+                    Some(listener) => {
+                        listener.on_tray(item.as_deref());
+                    }
+                };
+                if let Some(child) = &mut self.child {
+                    widget::trigger(child.as_mut(), event);
+                }
+            }
+            Event::Network { online } => {
+                queue::set_online(*online);
+                match &self.listener {
+                    None => (),
+                    Some(listener) => {
+                        listener.on_network(*online);
+                    }
+                };
+                if let Some(child) = &mut self.child {
+                    widget::trigger(child.as_mut(), event);
+                }
+            }
+            Event::Job { id, error } => {
+                queue::finish(*id, error.as_deref());
+                if let Some(child) = &mut self.child {
+                    widget::trigger(child.as_mut(), event);
+                }
+            }
+            Event::Observed { .. } => {
+                observe::finish();
+                if let Some(child) = &mut self.child {
+                    widget::trigger(child.as_mut(), event);
+                }
+            }
+            Event::Launch { launch } => {
+                match &self.listener {
+                    None => (),
+                    Some(listener) => {
+                        listener.on_launch(launch);
+                    }
+                };
+                if let Some(child) = &mut self.child {
+                    widget::trigger(child.as_mut(), event);
+                }
+            }
+            Event::Restore => {
+                if let Some(size) = settings::get("window.size") {
+                    if let (Some(width), Some(height)) =
+                        (size[0].as_i32(), size[1].as_i32())
+                    {
+                        if self.resizable && !self.kiosk {
+                            self.set_size(width, height);
+                        }
+                    }
+                }
+                match &self.listener {
+                    None => (),
+                    Some(listener) => {
+                        listener.on_restore();
+                    }
+                };
+                if let Some(child) = &mut self.child {
+                    widget::trigger(child.as_mut(), event);
+                }
+            }
+            Event::Custom { name, value } => {
+                match &self.listener {
+                    None => (),
+                    Some(listener) => {
+                        listener.on_custom(name, value);
+                    }
+                };
+                if let Some(child) = &mut self.child {
+                    widget::trigger(child.as_mut(), event);
+                }
+            }
+            Event::Geometry { pointer, bounds } => {
+                self.state.pointer = *pointer;
+                if let Some(bounds) = bounds {
+                    self.state.bounds = bounds.clone();
+                }
+            }
+            Event::Monitors { monitors } => {
+                self.state.monitors = monitors.clone();
+                self.placed = None;
+            }
+            Event::Error { source, message } => match &self.listener {
+                None => eprintln!("{}: {}", source, message),
+                Some(listener) => {
+                    listener.on_error(source, message);
+                }
+            },
         }
     }
 }
 
+/// Return the javascript starting the animations and writing the clipboard
+/// contents queued since the last render
+fn pending_js() -> String {
+    let mut js = animation::take()
+        .iter()
+        .map(Animation::js)
+        .collect::<Vec<String>>()
+        .join("");
+    for data in clipboard::take() {
+        js.push_str(&data.js());
+    }
+    js.push_str(&export::print_js());
+    js
+}
+
+/// Add the names of a widget and of its descendants
+fn widget_names(widget: &dyn Widget, names: &mut Vec<String>) {
+    names.push(widget.name());
+    for child in widget.children() {
+        widget_names(child, names);
+    }
+}
+
 /// Return the HTML style tag
 fn inline_style(s: &str) -> String {
     format!(r#"<style type="text/css">{}</style>"#, s)
 }
 
-/// Return the HTML script tag
-fn inline_script(s: &str) -> String {
-    format!(r#"<script type="text/javascript">{}</script>"#, s)
+/// Return the HTML style tag with the given id, so its content can be
+/// replaced
+fn named_style(id: &str, s: &str) -> String {
+    format!(r#"<style type="text/css" id="{}-css">{}</style>"#, id, s)
+}
+
+/// Return the HTML script tag, with the nonce of the content-security policy
+/// if any
+fn inline_script(s: &str, nonce: &Option<String>) -> String {
+    match nonce {
+        Some(nonce) => format!(
+            r#"<script type="text/javascript" nonce="{}">{}</script>"#,
+            nonce, s
+        ),
+        None => format!(r#"<script type="text/javascript">{}</script>"#, s),
+    }
 }
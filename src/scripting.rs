@@ -0,0 +1,400 @@
+//! # Scripting
+//!
+//! The `scripting` feature embeds the Rhai scripting language
+//! (<https://rhai.rs>) to automate a Window: listing its widgets, sending
+//! events to them as the page would, and reading or checking their state.
+//! Power users can drive an application from a script, and QA can script
+//! the flows of an interface and run them with the headless `Harness`.
+//!
+//! `run` runs a Rhai script, with its variables, conditions, loops and
+//! functions, on a Window. The engine has the functions of the bridge,
+//! `print` adding a line to the output of the script:
+//!
+//! ```text
+//! widgets()                 the widgets, depth first, as maps with their
+//!                           depth, name and type
+//! dispatch(event)           send an event written as the page would, such
+//!                           as #{type: "Change", source: "name", value: "x"}
+//! value(name)               the value of a form field
+//! update()                  send an update event
+//! change(source, value)     send a change event, such as a click
+//! focus(source)             send a focus event
+//! blur(source)              send a blur event
+//! dismiss(source)           send a dismiss event
+//! key(key)                  send a Ctrl + KEY event
+//! custom(name, value)       send a custom event, the value being in JSON
+//! expect(name, value)       fail unless the form field has this value
+//! contains(text)            fail unless the rendered HTML has this text
+//! ```
+//!
+//! `run_commands` runs a simpler language on top of it, for the scripts of
+//! the people who do not program: one command per line, which calls the
+//! function of the same name with the words of the line, the words with
+//! spaces being written between double quotes. `widgets` and `value` print
+//! their result. The empty lines and the lines starting with `#` are
+//! ignored.
+//!
+//! ## Example
+//!
+//! ```
+//! use neutrino::scripting;
+//! use neutrino::utils::event::Event;
+//! use neutrino::widgets::container::Container;
+//! use neutrino::widgets::textinput::TextInput;
+//! use neutrino::Window;
+//!
+//! let mut my_container = Container::new("my_container");
+//! my_container.add(Box::new(TextInput::new("my_textinput")));
+//!
+//! let mut my_window = Window::new();
+//! my_window.set_child(Box::new(my_container));
+//!
+//! let output = scripting::run(&mut my_window, r#"
+//!     let names = ["Tom", "Huck"];
+//!     for name in names {
+//!         change("my_textinput", name);
+//!         if value("my_textinput") != name {
+//!             throw "the value was not changed";
+//!         }
+//!     }
+//!     dispatch(#{type: "Change", source: "my_textinput", value: "Becky"});
+//!     for widget in widgets() {
+//!         if widget.type == "TextInput" {
+//!             print(widget.name + " is " + value(widget.name));
+//!         }
+//!     }
+//! "#).unwrap();
+//! assert_eq!(output, vec!["my_textinput is Becky"]);
+//!
+//! let output = scripting::run_commands(&mut my_window, r#"
+//!     change my_textinput "Tom Sawyer"
+//!     expect my_textinput "Tom Sawyer"
+//!     widgets
+//! "#).unwrap();
+//! assert_eq!(output, vec!["my_container Container", "  my_textinput TextInput"]);
+//!
+//! scripting::dispatch(&mut my_window, &Event::Change {
+//!     source: "my_textinput".to_string(),
+//!     value: "Huckleberry Finn".to_string(),
+//! });
+//! assert_eq!(scripting::value(&my_window, "my_textinput").unwrap(), "Huckleberry Finn");
+//! assert_eq!(scripting::widgets(&my_window)[1], (1, "my_textinput".to_string(), "TextInput".to_string()));
+//! ```
+
+use std::cell::RefCell;
+use std::mem;
+use std::rc::Rc;
+
+use rhai::{Array, Dynamic, Engine, EvalAltResult, Map, Scope};
+
+use crate::utils::event::{Event, Key};
+use crate::widgets::widget::Widget;
+use crate::Window;
+
+/// The commands, with their number of arguments
+const COMMANDS: [(&str, usize); 11] = [
+    ("update", 0),
+    ("change", 2),
+    ("focus", 1),
+    ("blur", 1),
+    ("dismiss", 1),
+    ("key", 1),
+    ("custom", 2),
+    ("widgets", 0),
+    ("value", 1),
+    ("expect", 2),
+    ("contains", 1),
+];
+
+/// A Window shared with the functions of the engine
+type Shared = Rc<RefCell<Window>>;
+
+/// The result of a function of the engine
+type Returned<T> = Result<T, Box<EvalAltResult>>;
+
+/// Run a Rhai script on a Window, returning the printed lines, or the error
+/// of the script with its position
+pub fn run(window: &mut Window, script: &str) -> Result<Vec<String>, String> {
+    with_engine(window, |engine, _| {
+        engine
+            .run_with_scope(&mut Scope::new(), script)
+            .map_err(|error| error.to_string())
+    })
+}
+
+/// Run a script of commands on a Window, returning the printed lines, or
+/// the first failing command with its line number
+pub fn run_commands(
+    window: &mut Window,
+    script: &str,
+) -> Result<Vec<String>, String> {
+    with_engine(window, |engine, output| {
+        for (i, line) in script.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            command(engine, &words(line), output)
+                .map_err(|error| format!("line {}: {}", i + 1, error))?;
+        }
+        Ok(())
+    })
+}
+
+/// Lend a Window to an engine having the functions of the bridge, and
+/// return the lines printed by the engine
+fn with_engine<F>(window: &mut Window, f: F) -> Result<Vec<String>, String>
+where
+    F: FnOnce(&Engine, &RefCell<Vec<String>>) -> Result<(), String>,
+{
+    let shared = Rc::new(RefCell::new(mem::replace(window, Window::new())));
+    let output = Rc::new(RefCell::new(vec![]));
+    let result = f(&engine(&shared, &output), &output);
+    *window = shared.replace(Window::new());
+    result?;
+    Ok(output.take())
+}
+
+/// Create an engine having the functions of the bridge on a Window
+fn engine(window: &Shared, output: &Rc<RefCell<Vec<String>>>) -> Engine {
+    let mut engine = Engine::new();
+    let printed = Rc::clone(output);
+    engine.on_print(move |line| printed.borrow_mut().push(line.to_string()));
+
+    let shared = Rc::clone(window);
+    engine.register_fn("widgets", move || -> Array {
+        widgets(&shared.borrow())
+            .into_iter()
+            .map(|(depth, name, kind)| {
+                let mut widget = Map::new();
+                widget.insert("depth".into(), (depth as i64).into());
+                widget.insert("name".into(), name.into());
+                widget.insert("type".into(), kind.into());
+                Dynamic::from_map(widget)
+            })
+            .collect()
+    });
+
+    let shared = Rc::clone(window);
+    engine.register_fn("dispatch", move |event: Map| -> Returned<()> {
+        let event = json::parse(&rhai::format_map_as_json(&event))
+            .map_err(|error| error.to_string())?;
+        match Event::from_json(&event) {
+            Event::Undefined => Err(format!("unknown event {}", event).into()),
+            Event::Error { message, .. } => Err(message.into()),
+            event => {
+                dispatch(&mut shared.borrow_mut(), &event);
+                Ok(())
+            }
+        }
+    });
+
+    let shared = Rc::clone(window);
+    engine.register_fn("value", move |name: &str| -> Returned<String> {
+        Ok(value(&shared.borrow(), name)?)
+    });
+
+    let shared = Rc::clone(window);
+    engine.register_fn("update", move || {
+        dispatch(&mut shared.borrow_mut(), &Event::Update)
+    });
+
+    let shared = Rc::clone(window);
+    engine.register_fn("change", move |source: &str, value: &str| {
+        let event = Event::Change {
+            source: source.to_string(),
+            value: value.to_string(),
+        };
+        dispatch(&mut shared.borrow_mut(), &event)
+    });
+
+    let shared = Rc::clone(window);
+    engine.register_fn("focus", move |source: &str| {
+        let source = source.to_string();
+        dispatch(&mut shared.borrow_mut(), &Event::Focus { source })
+    });
+
+    let shared = Rc::clone(window);
+    engine.register_fn("blur", move |source: &str| {
+        let source = source.to_string();
+        dispatch(&mut shared.borrow_mut(), &Event::Blur { source })
+    });
+
+    let shared = Rc::clone(window);
+    engine.register_fn("dismiss", move |source: &str| {
+        let source = source.to_string();
+        dispatch(&mut shared.borrow_mut(), &Event::Dismiss { source })
+    });
+
+    let shared = Rc::clone(window);
+    engine.register_fn("key", move |key: &str| -> Returned<()> {
+        match Key::new(key) {
+            Some(key) => {
+                dispatch(&mut shared.borrow_mut(), &Event::Key { key });
+                Ok(())
+            }
+            None => Err(format!("unknown key {}", key).into()),
+        }
+    });
+
+    let shared = Rc::clone(window);
+    engine.register_fn(
+        "custom",
+        move |name: &str, value: &str| -> Returned<()> {
+            let value =
+                json::parse(value).map_err(|error| error.to_string())?;
+            let name = name.to_string();
+            dispatch(&mut shared.borrow_mut(), &Event::Custom { name, value });
+            Ok(())
+        },
+    );
+
+    let shared = Rc::clone(window);
+    engine.register_fn(
+        "expect",
+        move |name: &str, expected: &str| -> Returned<()> {
+            let actual = value(&shared.borrow(), name)?;
+            if actual != expected {
+                return Err(format!(
+                    "{} is {:?}, not {:?}",
+                    name, actual, expected
+                )
+                .into());
+            }
+            Ok(())
+        },
+    );
+
+    let shared = Rc::clone(window);
+    engine.register_fn("contains", move |text: &str| -> Returned<()> {
+        if !shared.borrow().eval().contains(text) {
+            return Err(format!("{:?} is not rendered", text).into());
+        }
+        Ok(())
+    });
+
+    engine
+}
+
+/// Run a command, given as its words, by calling the function of the
+/// engine with the same name
+fn command(
+    engine: &Engine,
+    words: &[String],
+    output: &RefCell<Vec<String>>,
+) -> Result<(), String> {
+    let arguments = match COMMANDS.iter().find(|(name, _)| *name == words[0]) {
+        Some((_, arguments)) => *arguments,
+        None => return Err(format!("unknown command {}", words[0])),
+    };
+    if words.len() <= arguments {
+        return Err(format!("{}: missing argument", words[0]));
+    }
+    let mut scope = Scope::new();
+    let mut names = vec![];
+    for (i, word) in words[1..=arguments].iter().enumerate() {
+        names.push(format!("argument{}", i));
+        scope.push(names[i].as_str(), word.to_string());
+    }
+    let call = format!("{}({})", words[0], names.join(", "));
+    let error = |error: Box<EvalAltResult>| format!("{}: {}", words[0], error);
+    match words[0].as_str() {
+        "widgets" => {
+            let widgets = engine
+                .eval_expression_with_scope::<Array>(&mut scope, &call)
+                .map_err(error)?;
+            for widget in widgets {
+                let widget = widget.cast::<Map>();
+                let field = |key: &str| widget[key].to_string();
+                let depth = widget["depth"].as_int().unwrap_or(0) as usize;
+                output.borrow_mut().push(format!(
+                    "{}{} {}",
+                    "  ".repeat(depth),
+                    field("name"),
+                    field("type")
+                ));
+            }
+        }
+        "value" => {
+            let value = engine
+                .eval_expression_with_scope::<String>(&mut scope, &call)
+                .map_err(error)?;
+            output.borrow_mut().push(value);
+        }
+        _ => engine
+            .eval_expression_with_scope::<()>(&mut scope, &call)
+            .map_err(error)?,
+    }
+    Ok(())
+}
+
+/// Send an event to the Window, as the page would
+pub fn dispatch(window: &mut Window, event: &Event) {
+    window.process(event);
+}
+
+/// Get the widgets of the Window, depth first, as their depth, their name
+/// and their type
+pub fn widgets(window: &Window) -> Vec<(usize, String, String)> {
+    let mut widgets = vec![];
+    if let Some(child) = &window.child {
+        list(child.as_ref(), 0, &mut widgets);
+    }
+    widgets
+}
+
+/// Get the value of a form field of the Window
+pub fn value(window: &Window, name: &str) -> Result<String, String> {
+    let values = match &window.child {
+        Some(child) => child.values(),
+        None => vec![],
+    };
+    match values.into_iter().find(|(field, _)| field == name) {
+        Some((_, value)) => Ok(value),
+        None => Err(format!("no form field is named {}", name)),
+    }
+}
+
+/// List a widget and its children with their depth
+fn list(
+    widget: &dyn Widget,
+    depth: usize,
+    widgets: &mut Vec<(usize, String, String)>,
+) {
+    widgets.push((depth, widget.name().to_string(), widget.kind().to_string()));
+    for child in widget.children() {
+        list(child, depth + 1, widgets);
+    }
+}
+
+/// Split a line into words, the words between double quotes keeping their
+/// spaces
+fn words(line: &str) -> Vec<String> {
+    let mut words = vec![];
+    let mut word = String::new();
+    let mut quoted = false;
+    let mut started = false;
+    for c in line.chars() {
+        match c {
+            '"' => {
+                quoted = !quoted;
+                started = true;
+            }
+            c if c.is_whitespace() && !quoted => {
+                if started {
+                    words.push(word.clone());
+                    word.clear();
+                    started = false;
+                }
+            }
+            c => {
+                word.push(c);
+                started = true;
+            }
+        }
+    }
+    if started {
+        words.push(word);
+    }
+    words
+}
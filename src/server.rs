@@ -0,0 +1,567 @@
+//! # Server mode
+//!
+//! With the `server` feature, `App::serve` runs a Window without a webview:
+//! the same page is served over HTTP, so the application can be used from a
+//! browser on another machine, such as the control panel of a headless
+//! appliance. The events of the page are sent over a WebSocket and the
+//! widget tree is rendered in every connected browser after each event.
+//!
+//! Anyone who can reach the address controls the application. A Server bound
+//! to another interface than the loopback one should have a token, given in
+//! the address of the page, such as `http://192.168.1.20:8080/?token=...`,
+//! and be used on a trusted network or behind a reverse proxy serving HTTPS.
+//! The WebSocket is refused to the pages of other sites.
+//!
+//! Only the events of the user interface are taken from a page: update,
+//! change, focus, blur, key, canvas click, hover, double click, mouse up,
+//! dismiss, geometry and custom events. The others, such as the job or the
+//! error events, are internal to the application and dropped.
+//!
+//! The connections are limited in number, the requests in time and size,
+//! and the messages of a page in size. Each page is written by its own
+//! thread from a queue of renders, and a page which does not keep up with
+//! its queue is disconnected, so that it does not slow down the others.
+//!
+//! The features of the webview are not available: the strict
+//! content-security policy, the devtools, the updater, the single instance
+//! guard, the autosave of the document, the sync queue, the WebDriver
+//! adapter and the reload of the layout. The title of the window is the one
+//! of the browser tab, and `App::quit` stops the server.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use neutrino::server::Server;
+//! use neutrino::{App, Window};
+//!
+//! let mut my_server = Server::new("127.0.0.1:8080");
+//! my_server.set_token("d2f1c0e7a9b4");
+//!
+//! let my_window = Window::new();
+//! App::serve(my_window, my_server).unwrap();
+//! ```
+
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{Shutdown, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Sender, SyncSender};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use crate::utils::crypto::{equal, sha1};
+use crate::utils::document;
+use crate::utils::event::Event;
+use crate::utils::launch::Launch;
+use crate::utils::settings;
+use crate::Window;
+
+/// The types of the events taken from a page
+const PAGE_EVENTS: [&str; 12] = [
+    "Update",
+    "Change",
+    "Focus",
+    "Blur",
+    "Key",
+    "CanvasClick",
+    "Hover",
+    "DoubleClick",
+    "MouseUp",
+    "Dismiss",
+    "Geometry",
+    "Custom",
+];
+
+/// The GUID of the WebSocket handshake
+const GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+
+/// The largest message accepted from a page, its continuation frames
+/// included
+const MAX_MESSAGE: u64 = 16 * 1024 * 1024;
+
+/// The largest line of a request
+const MAX_LINE: u64 = 8 * 1024;
+
+/// The largest number of headers of a request
+const MAX_HEADERS: usize = 100;
+
+/// The largest number of connections open at the same time
+const MAX_CONNECTIONS: usize = 64;
+
+/// The number of frames waiting to be written to a page before it is
+/// disconnected
+const QUEUE: usize = 64;
+
+/// The time given to a client to send its request
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The time given to a write to a client
+const WRITE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The status code closing a WebSocket whose page broke the protocol, such
+/// as with an unmasked frame
+const PROTOCOL_ERROR: u16 = 1002;
+
+/// The status code closing a WebSocket whose page sent a too big message
+const TOO_BIG: u16 = 1009;
+
+/// The javascript sending the events of the page over the WebSocket and
+/// running the renders it receives
+const BRIDGE_JS: &str = r#"(function() {
+    let scheme = location.protocol === "https:" ? "wss://" : "ws://";
+    let socket = new WebSocket(scheme + location.host + "/socket" + location.search);
+    let queue = [];
+    socket.onopen = function() {
+        queue.forEach(function(message) { socket.send(message); });
+        queue = [];
+    };
+    socket.onmessage = function(message) {
+        (0, eval)(message.data);
+    };
+    socket.onclose = function() {
+        document.body.dataset.disconnected = "true";
+    };
+    window.external = {
+        invoke: function(message) {
+            if (socket.readyState === 1) {
+                socket.send(message);
+            } else {
+                queue.push(message);
+            }
+        }
+    };
+})();"#;
+
+/// # The address of a served application
+///
+/// ## Fields
+///
+/// ```text
+/// address: String
+/// token: Option<String>
+/// ```
+///
+/// ## Default values
+///
+/// ```text
+/// address: address.to_string()
+/// token: None
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Server {
+    address: String,
+    token: Option<String>,
+}
+
+impl Server {
+    /// Create a Server listening on the given address, such as
+    /// `127.0.0.1:8080`
+    pub fn new(address: &str) -> Self {
+        Self {
+            address: address.to_string(),
+            token: None,
+        }
+    }
+
+    /// Get the address
+    pub fn address(&self) -> &str {
+        &self.address
+    }
+
+    /// Get the token
+    pub fn token(&self) -> Option<&str> {
+        self.token.as_deref()
+    }
+
+    /// Set the token required in the query of the page, as `token=...`
+    pub fn set_token(&mut self, token: &str) {
+        self.token = Some(token.to_string());
+    }
+
+    /// Return true if a request target has the token, if one is required
+    fn allows(&self, target: &str) -> bool {
+        let token = match &self.token {
+            Some(token) => token,
+            None => return true,
+        };
+        let query = target.split_once('?').map(|(_, query)| query);
+        query
+            .unwrap_or("")
+            .split('&')
+            .filter_map(|pair| pair.split_once('='))
+            .any(|(name, value)| {
+                name == "token" && equal(value.as_bytes(), token.as_bytes())
+            })
+    }
+}
+
+/// The queue of the frames written to a page by its thread
+type Queue = SyncSender<Arc<Vec<u8>>>;
+
+/// A message of a connection to the window
+enum Message {
+    Open(usize, Queue),
+    Event(String),
+    Close(usize),
+}
+
+/// The count of the open connections, decremented when it is dropped
+struct Connection(Arc<AtomicUsize>);
+
+impl Drop for Connection {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Serve a window until the application quits
+pub(crate) fn serve(mut window: Window, server: Server) -> Result<(), String> {
+    let listener = TcpListener::bind(&server.address)
+        .map_err(|error| format!("{}: {}", server.address, error))?;
+    window.strict_csp = false;
+    if settings::is_set() {
+        window.process(&Event::Restore);
+    }
+    let page = Arc::new(window.page(false, BRIDGE_JS));
+    let server = Arc::new(server);
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let count = Arc::new(AtomicUsize::new(0));
+        for (id, mut stream) in listener.incoming().flatten().enumerate() {
+            if count.fetch_add(1, Ordering::SeqCst) >= MAX_CONNECTIONS {
+                count.fetch_sub(1, Ordering::SeqCst);
+                let _ = stream.set_write_timeout(Some(WRITE_TIMEOUT));
+                let _ = respond(
+                    &mut stream,
+                    "503 Service Unavailable",
+                    "text/plain",
+                    "Too many connections",
+                );
+                continue;
+            }
+            let connection = Connection(Arc::clone(&count));
+            let (page, server) = (Arc::clone(&page), Arc::clone(&server));
+            let sender = sender.clone();
+            thread::spawn(move || {
+                connect(id, stream, &page, &server, sender);
+                drop(connection);
+            });
+        }
+    });
+
+    let args = std::env::args().skip(1).collect::<Vec<String>>();
+    let mut launch = Some(Launch::parse(&args, window.scheme()));
+    let mut clients: HashMap<usize, Queue> = HashMap::new();
+    for message in receiver {
+        match message {
+            Message::Open(id, queue) => {
+                let js = render_js(&mut window);
+                let render = Arc::new(frame(0x1, js.as_bytes()));
+                if queue.try_send(render).is_ok() {
+                    clients.insert(id, queue);
+                }
+            }
+            Message::Event(message) => {
+                let event = match json::parse(&message) {
+                    Ok(value) => match value["type"].as_str() {
+                        Some(kind) if PAGE_EVENTS.contains(&kind) => {
+                            Event::from_json(&value)
+                        }
+                        _ => continue,
+                    },
+                    Err(error) => Event::Error {
+                        source: "".to_string(),
+                        message: format!(
+                            "invalid event {}: {}",
+                            message, error
+                        ),
+                    },
+                };
+                window.process(&event);
+                if let Event::Geometry { .. } = event {
+                    continue;
+                }
+                if let Some(launch) = launch.take() {
+                    if !launch.is_empty() {
+                        window.process(&Event::Launch { launch });
+                    }
+                }
+                broadcast(&mut clients, &render_js(&mut window));
+                if document::quitting() {
+                    return Ok(());
+                }
+            }
+            Message::Close(id) => {
+                clients.remove(&id);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Return the javascript rendering the window in a browser, its title
+/// included
+fn render_js(window: &mut Window) -> String {
+    format!(
+        "document.title = {};{}",
+        json::stringify(window.title()),
+        window.render_js()
+    )
+}
+
+/// Queue a render for every connected page, dropping the closed
+/// connections and the pages whose queue is full
+fn broadcast(clients: &mut HashMap<usize, Queue>, js: &str) {
+    let render = Arc::new(frame(0x1, js.as_bytes()));
+    clients.retain(|_, queue| queue.try_send(Arc::clone(&render)).is_ok());
+}
+
+/// Answer a request: the page, or the WebSocket relaying its events
+fn connect(
+    id: usize,
+    stream: TcpStream,
+    page: &str,
+    server: &Server,
+    sender: Sender<Message>,
+) {
+    let timeouts = stream
+        .set_read_timeout(Some(REQUEST_TIMEOUT))
+        .and_then(|_| stream.set_write_timeout(Some(WRITE_TIMEOUT)));
+    if timeouts.is_err() {
+        return;
+    }
+    let mut writer = match stream.try_clone() {
+        Ok(writer) => writer,
+        Err(_) => return,
+    };
+    let mut reader = BufReader::new(stream);
+    let (target, headers) = match read_request(&mut reader) {
+        Some(request) => request,
+        None => return,
+    };
+    let header = |name: &str| {
+        headers
+            .iter()
+            .find(|(header, _)| header.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    };
+    let path = target.split('?').next().unwrap_or("");
+    if !server.allows(&target) {
+        let _ =
+            respond(&mut writer, "403 Forbidden", "text/plain", "Forbidden");
+        return;
+    }
+    match (path, header("Sec-WebSocket-Key")) {
+        ("/", _) => {
+            let _ = respond(&mut writer, "200 OK", "text/html", page);
+        }
+        ("/socket", Some(key)) => {
+            if !same_origin(header("Origin"), header("Host")) {
+                let _ = respond(
+                    &mut writer,
+                    "403 Forbidden",
+                    "text/plain",
+                    "Forbidden",
+                );
+                return;
+            }
+            let accept =
+                base64::encode(&sha1(format!("{}{}", key, GUID).as_bytes()));
+            let handshake = format!(
+                "HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\nConnection: Upgrade\r\nSec-WebSocket-Accept: {}\r\n\r\n",
+                accept
+            );
+            if writer.write_all(handshake.as_bytes()).is_err() {
+                return;
+            }
+            if reader.get_ref().set_read_timeout(None).is_err() {
+                return;
+            }
+            let (queue, frames): (Queue, _) = mpsc::sync_channel(QUEUE);
+            thread::spawn(move || {
+                for frame in frames {
+                    if writer.write_all(&frame).is_err() {
+                        let _ = writer.shutdown(Shutdown::Both);
+                        return;
+                    }
+                }
+            });
+            if sender.send(Message::Open(id, queue.clone())).is_ok() {
+                let _ = relay(&mut reader, &queue, &sender);
+            }
+            let _ = sender.send(Message::Close(id));
+        }
+        _ => {
+            let _ = respond(
+                &mut writer,
+                "404 Not Found",
+                "text/plain",
+                "Not found",
+            );
+        }
+    }
+}
+
+/// Read the target and the headers of a request
+fn read_request(
+    reader: &mut BufReader<TcpStream>,
+) -> Option<(String, Vec<(String, String)>)> {
+    let line = read_line(reader)?;
+    let mut words = line.split_whitespace();
+    if words.next() != Some("GET") {
+        return None;
+    }
+    let target = words.next()?.to_string();
+    let mut headers = vec![];
+    loop {
+        let line = read_line(reader)?;
+        let line = line.trim_end();
+        if line.is_empty() {
+            return Some((target, headers));
+        }
+        if headers.len() == MAX_HEADERS {
+            return None;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            headers.push((name.trim().to_string(), value.trim().to_string()));
+        }
+    }
+}
+
+/// Read a line of a request, None if it is too long or the connection is
+/// closed
+fn read_line(reader: &mut BufReader<TcpStream>) -> Option<String> {
+    let mut line = String::new();
+    reader.by_ref().take(MAX_LINE).read_line(&mut line).ok()?;
+    if line.ends_with('\n') {
+        Some(line)
+    } else {
+        None
+    }
+}
+
+/// Write a response and close the connection
+fn respond(
+    stream: &mut TcpStream,
+    status: &str,
+    kind: &str,
+    body: &str,
+) -> std::io::Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: {}; charset=utf-8\r\nContent-Length: {}\r\nCache-Control: no-store\r\nConnection: close\r\n\r\n{}",
+        status,
+        kind,
+        body.len(),
+        body
+    )
+}
+
+/// Return true if a WebSocket is opened by a page of the server, the
+/// browsers giving the origin of the page
+fn same_origin(origin: Option<&str>, host: Option<&str>) -> bool {
+    match (origin, host) {
+        (None, _) => true,
+        (Some(origin), Some(host)) => {
+            origin.split_once("://").map(|(_, origin)| origin) == Some(host)
+        }
+        (Some(_), None) => false,
+    }
+}
+
+/// Send the events of a page to the window until the WebSocket closes, the
+/// answers to the page going through its queue
+fn relay(
+    reader: &mut BufReader<TcpStream>,
+    queue: &Queue,
+    sender: &Sender<Message>,
+) -> Option<()> {
+    let mut message = vec![];
+    loop {
+        let limit = MAX_MESSAGE - message.len() as u64;
+        let (fin, opcode, payload) = match read_frame(reader, limit) {
+            Ok(frame) => frame,
+            Err(Some(code)) => {
+                let close = Arc::new(frame(0x8, &code.to_be_bytes()));
+                let _ = queue.try_send(close);
+                return None;
+            }
+            Err(None) => return None,
+        };
+        match opcode {
+            0x0 | 0x1 => {
+                message.extend(payload);
+                if fin {
+                    let text = String::from_utf8_lossy(&message).to_string();
+                    message.clear();
+                    sender.send(Message::Event(text)).ok()?;
+                }
+            }
+            0x8 => {
+                let _ = queue.try_send(Arc::new(frame(0x8, &payload)));
+                return Some(());
+            }
+            0x9 => queue.try_send(Arc::new(frame(0xA, &payload))).ok()?,
+            _ => (),
+        }
+    }
+}
+
+/// Read a frame sent by a page, as its final flag, its opcode and its
+/// unmasked payload, whose length must not exceed the limit. The error is
+/// the status code closing the WebSocket, None if the connection is lost.
+fn read_frame(
+    reader: &mut impl Read,
+    limit: u64,
+) -> Result<(bool, u8, Vec<u8>), Option<u16>> {
+    let mut head = [0; 2];
+    reader.read_exact(&mut head).map_err(|_| None)?;
+    let fin = head[0] & 0x80 != 0;
+    let opcode = head[0] & 0x0F;
+    if head[1] & 0x80 == 0 {
+        return Err(Some(PROTOCOL_ERROR));
+    }
+    let length = match head[1] & 0x7F {
+        126 => {
+            let mut length = [0; 2];
+            reader.read_exact(&mut length).map_err(|_| None)?;
+            u64::from(u16::from_be_bytes(length))
+        }
+        127 => {
+            let mut length = [0; 8];
+            reader.read_exact(&mut length).map_err(|_| None)?;
+            u64::from_be_bytes(length)
+        }
+        length => u64::from(length),
+    };
+    if length > limit {
+        return Err(Some(TOO_BIG));
+    }
+    let mut mask = [0; 4];
+    reader.read_exact(&mut mask).map_err(|_| None)?;
+    let mut payload = vec![0; length as usize];
+    reader.read_exact(&mut payload).map_err(|_| None)?;
+    for (i, byte) in payload.iter_mut().enumerate() {
+        *byte ^= mask[i % 4];
+    }
+    Ok((fin, opcode, payload))
+}
+
+/// Encode an unmasked frame
+fn frame(opcode: u8, payload: &[u8]) -> Vec<u8> {
+    let mut frame = vec![0x80 | opcode];
+    match payload.len() {
+        length if length < 126 => frame.push(length as u8),
+        length if length < 65536 => {
+            frame.push(126);
+            frame.extend(&(length as u16).to_be_bytes());
+        }
+        length => {
+            frame.push(127);
+            frame.extend(&(length as u64).to_be_bytes());
+        }
+    }
+    frame.extend(payload);
+    frame
+}
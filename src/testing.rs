@@ -0,0 +1,349 @@
+//! # Headless testing
+//!
+//! A `Harness` runs a Window without a webview, so the widgets and the
+//! listeners of an application can be tested automatically. The events are
+//! processed as they would be in `App::run`, including the update of the
+//! widget tree which follows them, and the HTML which would be rendered can
+//! be asserted on.
+//!
+//! ## Example
+//!
+//! ```
+//! use std::cell::RefCell;
+//! use std::rc::Rc;
+//!
+//! use neutrino::testing::Harness;
+//! use neutrino::widgets::button::{Button, ButtonListener, ButtonState};
+//! use neutrino::widgets::container::Container;
+//! use neutrino::widgets::label::{Label, LabelListener, LabelState};
+//! use neutrino::Window;
+//!
+//!
+//! struct MyButtonListener {
+//!     clicks: Rc<RefCell<u32>>,
+//! }
+//!
+//! impl ButtonListener for MyButtonListener {
+//!     fn on_change(&self, _state: &ButtonState) {
+//!         *self.clicks.borrow_mut() += 1;
+//!     }
+//!
+//!     fn on_update(&self, _state: &mut ButtonState) {}
+//! }
+//!
+//!
+//! struct MyLabelListener {
+//!     clicks: Rc<RefCell<u32>>,
+//! }
+//!
+//! impl LabelListener for MyLabelListener {
+//!     fn on_update(&self, state: &mut LabelState) {
+//!         state.set_text(&format!("{} clicks", self.clicks.borrow()));
+//!     }
+//! }
+//!
+//!
+//! let clicks = Rc::new(RefCell::new(0));
+//!
+//! let mut my_button = Button::new("my_button");
+//! my_button.set_listener(Box::new(MyButtonListener { clicks: Rc::clone(&clicks) }));
+//!
+//! let mut my_label = Label::new("my_label");
+//! my_label.set_listener(Box::new(MyLabelListener { clicks: Rc::clone(&clicks) }));
+//!
+//! let mut my_container = Container::new("my_container");
+//! my_container.add(Box::new(my_button));
+//! my_container.add(Box::new(my_label));
+//!
+//! let mut my_window = Window::new();
+//! my_window.set_child(Box::new(my_container));
+//!
+//! let mut harness = Harness::new(my_window);
+//! assert!(harness.element("my_label").unwrap().contains("0 clicks"));
+//!
+//! harness.change("my_button", "");
+//! harness.change("my_button", "");
+//! assert!(harness.element("my_label").unwrap().contains("2 clicks"));
+//! ```
+
+use std::time::Duration;
+
+use json::JsonValue;
+
+use crate::utils::animation::{self, Animation};
+use crate::utils::clipboard::{self, ClipboardData};
+use crate::utils::csp::tag_end;
+use crate::utils::event::{Event, Key};
+use crate::utils::export;
+use crate::utils::geometry::Bounds;
+use crate::utils::launch::Launch;
+use crate::utils::monitor::Monitor;
+use crate::utils::observe;
+use crate::utils::settings;
+use crate::utils::updater::Release;
+use crate::Window;
+
+/// # A Window run without a webview
+///
+/// The Harness sends the first update on creation, as the page does when it
+/// is loaded, after the restore event if the application has settings.
+///
+/// ## Fields
+///
+/// ```text
+/// window: Window
+/// ```
+pub struct Harness {
+    window: Window,
+}
+
+impl Harness {
+    /// Create a Harness
+    pub fn new(window: Window) -> Self {
+        let mut harness = Self { window };
+        if settings::is_set() {
+            harness.send(Event::Restore);
+        }
+        harness.update();
+        harness
+    }
+
+    /// Get the window
+    pub fn window(&self) -> &Window {
+        &self.window
+    }
+
+    /// Get the window mutably
+    pub fn window_mut(&mut self) -> &mut Window {
+        &mut self.window
+    }
+
+    /// Send an event, as if it was sent by the page
+    pub fn send(&mut self, event: Event) {
+        self.window.process(&event);
+    }
+
+    /// Send an update event
+    pub fn update(&mut self) {
+        self.send(Event::Update);
+    }
+
+    /// Send a change event, sent by most widgets when they are used: the
+    /// value of a Button is empty, the one of a TextInput is the typed text
+    pub fn change(&mut self, source: &str, value: &str) {
+        self.send(Event::Change {
+            source: source.to_string(),
+            value: value.to_string(),
+        });
+    }
+
+    /// Send a `Ctrl + Key` event
+    pub fn key(&mut self, key: Key) {
+        self.send(Event::Key { key });
+    }
+
+    /// Send a focus event
+    pub fn focus(&mut self, source: &str) {
+        self.send(Event::Focus {
+            source: source.to_string(),
+        });
+    }
+
+    /// Send a blur event
+    pub fn blur(&mut self, source: &str) {
+        self.send(Event::Blur {
+            source: source.to_string(),
+        });
+    }
+
+    /// Send a resize event, sent when the window is resized
+    pub fn resize(&mut self, width: i32, height: i32) {
+        self.send(Event::Resize { width, height });
+    }
+
+    /// Send a dismiss event, sent when an overlay is closed by a click
+    /// outside of it or by the Escape key
+    pub fn dismiss(&mut self, source: &str) {
+        self.send(Event::Dismiss {
+            source: source.to_string(),
+        });
+    }
+
+    /// Send an animation end event, sent when an animation started with
+    /// `App::animate` is over
+    pub fn animation_end(&mut self, source: &str, name: &str) {
+        self.send(Event::AnimationEnd {
+            source: source.to_string(),
+            name: name.to_string(),
+        });
+    }
+
+    /// Take the animations started with `App::animate`, which a render would
+    /// play
+    pub fn animations(&mut self) -> Vec<Animation> {
+        animation::take()
+    }
+
+    /// Send a paste event, sent when something is pasted in the window
+    pub fn paste(&mut self, source: &str, data: ClipboardData) {
+        self.send(Event::Paste {
+            source: source.to_string(),
+            data,
+        });
+    }
+
+    /// Take the contents written into the clipboard with `App::copy`
+    pub fn copied(&mut self) -> Vec<ClipboardData> {
+        clipboard::take()
+    }
+
+    /// Return true if `App::print` was called since the last call, the
+    /// print dialog being opened by a render
+    pub fn printed(&mut self) -> bool {
+        export::take_print()
+    }
+
+    /// Wait until the futures observed by the widgets complete, and send the
+    /// observed events giving their outputs to the widgets. The futures which
+    /// have not completed within 10 seconds are left loading.
+    pub fn settle(&mut self) {
+        self.settle_within(Duration::from_secs(10));
+    }
+
+    /// Wait until the futures observed by the widgets complete, or until the
+    /// timeout is over, and send the observed events giving the outputs of
+    /// the completed ones to the widgets
+    pub fn settle_within(&mut self, timeout: Duration) {
+        for id in observe::wait(timeout) {
+            self.send(Event::Observed { id });
+        }
+    }
+
+    /// Send a geometry event, sent when the pointer moves and after the page
+    /// is rendered, with the position of the pointer and the bounds of the
+    /// widgets in the order of the document
+    pub fn geometry(
+        &mut self,
+        pointer: Option<(f64, f64)>,
+        bounds: Vec<(&str, Bounds)>,
+    ) {
+        self.send(Event::Geometry {
+            pointer,
+            bounds: Some(
+                bounds
+                    .into_iter()
+                    .map(|(name, bounds)| (name.to_string(), bounds))
+                    .collect(),
+            ),
+        });
+    }
+
+    /// Send a custom event, sent by the javascript of the application with
+    /// `neutrino.emit(name, value)`
+    pub fn custom(&mut self, name: &str, value: JsonValue) {
+        self.send(Event::Custom {
+            name: name.to_string(),
+            value,
+        });
+    }
+
+    /// Send an update event, sent when the updater of the window has found a
+    /// newer release
+    pub fn update_available(&mut self, release: Release) {
+        self.send(Event::UpdateAvailable { release });
+    }
+
+    /// Send an instance event, sent when another instance of the application
+    /// is started with the given arguments
+    pub fn instance(&mut self, args: &[&str]) {
+        self.send(Event::Instance {
+            args: args.iter().map(|arg| arg.to_string()).collect(),
+        });
+    }
+
+    /// Send a launch event with the given command-line arguments, sent after
+    /// the first render
+    pub fn launch(&mut self, args: &[&str]) {
+        let launch = Launch::parse(args, self.window.scheme());
+        self.send(Event::Launch { launch });
+    }
+
+    /// Send a monitors event, sent when the page is displayed and when the
+    /// monitors of the system change
+    pub fn monitors(&mut self, monitors: Vec<Monitor>) {
+        self.send(Event::Monitors { monitors });
+    }
+
+    /// Get the name of the focused widget
+    pub fn focused(&self) -> Option<&str> {
+        self.window.state.focus()
+    }
+
+    /// Get the HTML of the menubar and of the widget tree
+    pub fn html(&self) -> String {
+        self.window.eval()
+    }
+
+    /// Return true if the HTML contains the given text
+    pub fn contains(&self, text: &str) -> bool {
+        self.html().contains(text)
+    }
+
+    /// Get the HTML of the element with the given id, such as the name of a
+    /// widget
+    pub fn element(&self, id: &str) -> Option<String> {
+        let html = self.html();
+        let attribute = format!(r#"id="{}""#, id);
+        let mut position = 0;
+        while let Some(offset) = html[position..].find('<') {
+            let start = position + offset;
+            let end = start + tag_end(&html[start..]);
+            let tag = &html[start..end];
+            position = end;
+            if tag.starts_with("</") || !tag.contains(&attribute) {
+                continue;
+            }
+            let name: String = tag[1..]
+                .chars()
+                .take_while(|c| c.is_ascii_alphanumeric())
+                .collect();
+            if tag.ends_with("/>") || name == "input" || name == "img" {
+                return Some(tag.to_string());
+            }
+            return Some(
+                html[start..element_end(&html, end, &name)].to_string(),
+            );
+        }
+        None
+    }
+}
+
+/// Return the position after the end tag of the element named `name` whose
+/// content starts at `position`
+fn element_end(html: &str, mut position: usize, name: &str) -> usize {
+    let mut depth = 1;
+    while let Some(offset) = html[position..].find('<') {
+        let start = position + offset;
+        let end = start + tag_end(&html[start..]);
+        let tag = &html[start..end];
+        position = end;
+        let tag_name: String = tag
+            .trim_start_matches("</")
+            .trim_start_matches('<')
+            .chars()
+            .take_while(|c| c.is_ascii_alphanumeric())
+            .collect();
+        if tag_name != name {
+            continue;
+        }
+        if tag.starts_with("</") {
+            depth -= 1;
+            if depth == 0 {
+                return end;
+            }
+        } else if !tag.ends_with("/>") {
+            depth += 1;
+        }
+    }
+    html.len()
+}
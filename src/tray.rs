@@ -0,0 +1,509 @@
+//! # System tray
+//!
+//! With the `tray` feature, a Window can have an icon in the system tray or
+//! in the menu bar of macOS, with a menu, so that an application such as a
+//! sync client or a monitor can run in the background with its window
+//! hidden.
+//!
+//! The tray is displayed by the backend (see the `backend` module), which
+//! finds it in its `Settings` and sends a tray event to the handler when the
+//! icon is clicked, as `{"type": "Tray"}`, or when an item of the menu is
+//! chosen, as `{"type": "Tray", "item": "NAME"}`. The event is received by
+//! `WindowListener::on_tray`, which restores the window when the icon is
+//! clicked by default. `App::hide` and `App::show` hide and restore the
+//! window once the current event has been processed, through
+//! `View::set_visible`.
+//!
+//! The backends of neutrino display the tray with the `tray-icon` crate.
+//! The icon is a PNG image. On Linux, the tray is an application indicator,
+//! which only sends the events of its menu: a menu item restoring the
+//! window should be added.
+//!
+//! ## Example
+//!
+//! ```
+//! use std::cell::Cell;
+//! use std::rc::Rc;
+//!
+//! use neutrino::backend::{Backend, Proxy, Settings, View};
+//! use neutrino::tray::Tray;
+//! use neutrino::utils::event::Key;
+//! use neutrino::{App, Window, WindowListener};
+//!
+//!
+//! struct MyWindowListener;
+//!
+//! impl WindowListener for MyWindowListener {
+//!     fn on_key(&self, _key: Key) {}
+//!
+//!     fn on_tray(&self, item: Option<&str>) {
+//!         match item {
+//!             Some("hide") => App::hide(),
+//!             Some("quit") => App::quit(),
+//!             _ => App::show(),
+//!         }
+//!     }
+//! }
+//!
+//!
+//! struct TrayView {
+//!     visible: Rc<Cell<bool>>,
+//! }
+//!
+//! impl View for TrayView {
+//!     fn eval(&mut self, _js: &str) -> Result<(), String> {
+//!         Ok(())
+//!     }
+//!
+//!     fn set_title(&mut self, _title: &str) -> Result<(), String> {
+//!         Ok(())
+//!     }
+//!
+//!     fn terminate(&mut self) {}
+//!
+//!     fn set_visible(&mut self, visible: bool) -> Result<(), String> {
+//!         self.visible.set(visible);
+//!         Ok(())
+//!     }
+//! }
+//!
+//!
+//! struct TrayBackend {
+//!     visible: Rc<Cell<bool>>,
+//! }
+//!
+//! impl Backend for TrayBackend {
+//!     fn run(
+//!         &mut self,
+//!         settings: Settings,
+//!         start: Box<dyn FnOnce(Proxy)>,
+//!         handler: &mut dyn FnMut(&mut dyn View, &str) -> Result<(), String>,
+//!     ) -> Result<(), String> {
+//!         let tray = settings.tray().unwrap();
+//!         assert_eq!(tray.items()[0].name(), "hide");
+//!
+//!         let mut view = TrayView { visible: self.visible.clone() };
+//!         start(Proxy::new(|_js| false));
+//!         handler(&mut view, r#"{"type": "Update"}"#)?;
+//!         handler(&mut view, r#"{"type": "Tray", "item": "hide"}"#)?;
+//!         assert!(!self.visible.get());
+//!         handler(&mut view, r#"{"type": "Tray"}"#)
+//!     }
+//! }
+//!
+//!
+//! let mut my_tray = Tray::new("icons/tray.png");
+//! my_tray.set_tooltip("My sync client");
+//! my_tray.add("hide", "Hide the window");
+//! my_tray.add_separator();
+//! my_tray.add("quit", "Quit");
+//!
+//! let mut my_window = Window::new();
+//! my_window.set_tray(my_tray);
+//! my_window.set_listener(Box::new(MyWindowListener));
+//!
+//! let visible = Rc::new(Cell::new(true));
+//! App::run_with(my_window, TrayBackend { visible: visible.clone() }).unwrap();
+//! assert!(visible.get());
+//! ```
+
+use std::cell::Cell;
+#[cfg(any(feature = "web-view", feature = "wry"))]
+use std::fmt::Display;
+#[cfg(any(feature = "web-view", feature = "wry"))]
+use std::fs::File;
+
+#[cfg(any(feature = "web-view", feature = "wry"))]
+use json::JsonValue;
+#[cfg(any(feature = "web-view", feature = "wry"))]
+use tray_icon::menu::{Menu, MenuEvent, MenuItem, PredefinedMenuItem};
+#[cfg(any(feature = "web-view", feature = "wry"))]
+use tray_icon::{
+    MouseButton, MouseButtonState, TrayIcon, TrayIconBuilder, TrayIconEvent,
+};
+
+thread_local! {
+    static VISIBLE: Cell<Option<bool>> = const { Cell::new(None) };
+}
+
+/// # An item of the menu of a tray
+///
+/// An item without a name is a separator.
+///
+/// ## Fields
+///
+/// ```text
+/// name: String
+/// label: String
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrayItem {
+    name: String,
+    label: String,
+}
+
+impl TrayItem {
+    /// Get the name, sent in the tray event when the item is chosen
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the label
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    /// Return true if the item is a separator
+    pub fn is_separator(&self) -> bool {
+        self.name.is_empty()
+    }
+}
+
+/// # An icon in the system tray, with a menu
+///
+/// ## Fields
+///
+/// ```text
+/// icon: String
+/// tooltip: String
+/// items: Vec<TrayItem>
+/// ```
+///
+/// ## Default values
+///
+/// ```text
+/// icon: icon.to_string()
+/// tooltip: "".to_string()
+/// items: vec![]
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tray {
+    icon: String,
+    tooltip: String,
+    items: Vec<TrayItem>,
+}
+
+impl Tray {
+    /// Create a Tray from the path of its icon
+    pub fn new(icon: &str) -> Self {
+        Self {
+            icon: icon.to_string(),
+            tooltip: "".to_string(),
+            items: vec![],
+        }
+    }
+
+    /// Get the path of the icon
+    pub fn icon(&self) -> &str {
+        &self.icon
+    }
+
+    /// Get the tooltip
+    pub fn tooltip(&self) -> &str {
+        &self.tooltip
+    }
+
+    /// Get the items of the menu
+    pub fn items(&self) -> &Vec<TrayItem> {
+        &self.items
+    }
+
+    /// Set the tooltip
+    pub fn set_tooltip(&mut self, tooltip: &str) {
+        self.tooltip = tooltip.to_string();
+    }
+
+    /// Add an item to the menu, with the name sent in the tray event and its
+    /// label
+    pub fn add(&mut self, name: &str, label: &str) {
+        self.items.push(TrayItem {
+            name: name.to_string(),
+            label: label.to_string(),
+        });
+    }
+
+    /// Add a separator to the menu
+    pub fn add_separator(&mut self) {
+        self.items.push(TrayItem {
+            name: "".to_string(),
+            label: "".to_string(),
+        });
+    }
+
+    /// Display the tray, on the thread of the window. It stays in the tray
+    /// until the returned icon is dropped.
+    #[cfg(any(feature = "web-view", feature = "wry"))]
+    pub(crate) fn display(&self) -> Result<TrayIcon, String> {
+        #[cfg(target_os = "linux")]
+        gtk::init().map_err(|error| error.to_string())?;
+
+        let menu = Menu::new();
+        for item in &self.items {
+            if item.is_separator() {
+                menu.append(&PredefinedMenuItem::separator())
+            } else {
+                menu.append(&MenuItem::with_id(
+                    item.name.as_str(),
+                    &item.label,
+                    true,
+                    None,
+                ))
+            }
+            .map_err(|error| error.to_string())?;
+        }
+
+        let mut builder = TrayIconBuilder::new()
+            .with_icon(icon(&self.icon)?)
+            .with_menu(Box::new(menu))
+            .with_menu_on_left_click(false);
+        if !self.tooltip.is_empty() {
+            builder = builder.with_tooltip(&self.tooltip);
+        }
+        builder.build().map_err(|error| error.to_string())
+    }
+}
+
+/// Read the PNG icon at the given path
+#[cfg(any(feature = "web-view", feature = "wry"))]
+fn icon(path: &str) -> Result<tray_icon::Icon, String> {
+    let error = |error: &dyn Display| format!("{}: {}", path, error);
+
+    let file = File::open(path).map_err(|e| error(&e))?;
+    let mut decoder = png::Decoder::new(file);
+    decoder.set_transformations(
+        png::Transformations::normalize_to_color8()
+            | png::Transformations::ALPHA,
+    );
+    let mut reader = decoder.read_info().map_err(|e| error(&e))?;
+    let mut pixels = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut pixels).map_err(|e| error(&e))?;
+    pixels.truncate(info.buffer_size());
+
+    let rgba = match info.color_type {
+        png::ColorType::Rgba => pixels,
+        png::ColorType::GrayscaleAlpha => pixels
+            .chunks(2)
+            .flat_map(|pixel| vec![pixel[0], pixel[0], pixel[0], pixel[1]])
+            .collect(),
+        _ => return Err(error(&"unsupported color type")),
+    };
+    tray_icon::Icon::from_rgba(rgba, info.width, info.height)
+        .map_err(|e| error(&e))
+}
+
+/// Get the message sent to the handler when an item of the menu is chosen
+#[cfg(any(feature = "web-view", feature = "wry"))]
+pub(crate) fn menu_message(event: MenuEvent) -> String {
+    let mut message = JsonValue::new_object();
+    message["type"] = "Tray".into();
+    message["item"] = event.id.0.into();
+    message.dump()
+}
+
+/// Get the message sent to the handler when the icon is clicked, None for
+/// the other events of the icon
+#[cfg(any(feature = "web-view", feature = "wry"))]
+pub(crate) fn icon_message(event: TrayIconEvent) -> Option<String> {
+    match event {
+        TrayIconEvent::Click {
+            button: MouseButton::Left,
+            button_state: MouseButtonState::Up,
+            ..
+        } => Some(r#"{"type": "Tray"}"#.to_string()),
+        _ => None,
+    }
+}
+
+/// Send the messages of the tray with the given function as soon as they
+/// are received, instead of keeping them for `messages`
+#[cfg(feature = "wry")]
+pub(crate) fn forward<F>(send: F)
+where
+    F: Fn(String) + Send + Sync + 'static,
+{
+    let send = std::sync::Arc::new(send);
+    let menu = send.clone();
+    MenuEvent::set_event_handler(Some(move |event| menu(menu_message(event))));
+    TrayIconEvent::set_event_handler(Some(move |event| {
+        if let Some(message) = icon_message(event) {
+            send(message);
+        }
+    }));
+}
+
+/// Take the messages of the tray received since the last call
+#[cfg(feature = "web-view")]
+pub(crate) fn messages() -> Vec<String> {
+    let mut messages = vec![];
+    while let Ok(event) = MenuEvent::receiver().try_recv() {
+        messages.push(menu_message(event));
+    }
+    while let Ok(event) = TrayIconEvent::receiver().try_recv() {
+        messages.extend(icon_message(event));
+    }
+    messages
+}
+
+/// Hide or show the windows of the thread, for the backend built on
+/// web-view which does not give access to its native window
+#[cfg(feature = "web-view")]
+pub(crate) fn set_windows_visible(visible: bool) -> Result<(), String> {
+    native::set_visible(visible)
+}
+
+#[cfg(all(feature = "web-view", target_os = "linux"))]
+mod native {
+    use std::cell::RefCell;
+
+    use gtk::prelude::*;
+
+    thread_local! {
+        static HIDDEN: RefCell<Vec<gtk::Window>> = const { RefCell::new(vec![]) };
+    }
+
+    pub(super) fn set_visible(visible: bool) -> Result<(), String> {
+        gtk::init().map_err(|error| error.to_string())?;
+        if visible {
+            for window in HIDDEN.with(|hidden| hidden.take()) {
+                window.present();
+            }
+        } else {
+            let windows = gtk::Window::list_toplevels()
+                .into_iter()
+                .filter_map(|widget| widget.downcast::<gtk::Window>().ok())
+                .filter(|window| {
+                    window.window_type() == gtk::WindowType::Toplevel
+                        && window.is_visible()
+                })
+                .collect::<Vec<gtk::Window>>();
+            for window in &windows {
+                window.hide();
+            }
+            HIDDEN.with(|hidden| hidden.borrow_mut().extend(windows));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(all(feature = "web-view", target_os = "windows"))]
+mod native {
+    use std::cell::RefCell;
+
+    type Hwnd = isize;
+
+    const SW_HIDE: i32 = 0;
+    const SW_SHOW: i32 = 5;
+
+    #[link(name = "user32")]
+    extern "system" {
+        fn EnumThreadWindows(
+            thread: u32,
+            callback: extern "system" fn(Hwnd, isize) -> i32,
+            data: isize,
+        ) -> i32;
+        fn IsWindowVisible(window: Hwnd) -> i32;
+        fn ShowWindow(window: Hwnd, command: i32) -> i32;
+        fn SetForegroundWindow(window: Hwnd) -> i32;
+    }
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn GetCurrentThreadId() -> u32;
+    }
+
+    thread_local! {
+        static HIDDEN: RefCell<Vec<Hwnd>> = const { RefCell::new(vec![]) };
+    }
+
+    extern "system" fn collect(window: Hwnd, data: isize) -> i32 {
+        let windows = unsafe { &mut *(data as *mut Vec<Hwnd>) };
+        if unsafe { IsWindowVisible(window) } != 0 {
+            windows.push(window);
+        }
+        1
+    }
+
+    pub(super) fn set_visible(visible: bool) -> Result<(), String> {
+        if visible {
+            for window in HIDDEN.with(|hidden| hidden.take()) {
+                unsafe {
+                    ShowWindow(window, SW_SHOW);
+                    SetForegroundWindow(window);
+                }
+            }
+        } else {
+            let mut windows: Vec<Hwnd> = vec![];
+            unsafe {
+                EnumThreadWindows(
+                    GetCurrentThreadId(),
+                    collect,
+                    &mut windows as *mut Vec<Hwnd> as isize,
+                );
+            }
+            for window in &windows {
+                unsafe { ShowWindow(*window, SW_HIDE) };
+            }
+            HIDDEN.with(|hidden| hidden.borrow_mut().extend(windows));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(all(feature = "web-view", target_os = "macos"))]
+mod native {
+    use std::os::raw::{c_char, c_void};
+    use std::ptr;
+
+    type Id = *mut c_void;
+
+    #[link(name = "AppKit", kind = "framework")]
+    extern "C" {}
+
+    #[link(name = "objc")]
+    extern "C" {
+        fn objc_getClass(name: *const c_char) -> Id;
+        fn sel_registerName(name: *const c_char) -> Id;
+        fn objc_msgSend();
+    }
+
+    pub(super) fn set_visible(visible: bool) -> Result<(), String> {
+        let selector: &[u8] = if visible { b"unhide:\0" } else { b"hide:\0" };
+        unsafe {
+            let send: unsafe extern "C" fn(Id, Id, Id) -> Id =
+                std::mem::transmute(objc_msgSend as unsafe extern "C" fn());
+            let application = send(
+                objc_getClass(b"NSApplication\0".as_ptr() as *const c_char),
+                sel_registerName(
+                    b"sharedApplication\0".as_ptr() as *const c_char
+                ),
+                ptr::null_mut(),
+            );
+            send(
+                application,
+                sel_registerName(selector.as_ptr() as *const c_char),
+                ptr::null_mut(),
+            );
+        }
+        Ok(())
+    }
+}
+
+#[cfg(all(
+    feature = "web-view",
+    not(any(target_os = "linux", target_os = "windows", target_os = "macos"))
+))]
+mod native {
+    pub(super) fn set_visible(_visible: bool) -> Result<(), String> {
+        Err("the backend cannot hide the window on this platform".to_string())
+    }
+}
+
+/// Ask to hide or show the window once the current event is processed
+pub(crate) fn set_visible(visible: bool) {
+    VISIBLE.with(|current| current.set(Some(visible)));
+}
+
+/// Take the visibility asked since the last call, if any
+pub(crate) fn take_visible() -> Option<bool> {
+    VISIBLE.with(|current| current.take())
+}
@@ -0,0 +1,446 @@
+//! # Accessibility audit
+//!
+//! A diagnostic walking the HTML of a widget tree and the CSS of a theme,
+//! reporting the fields and images without a label, the clickable elements
+//! which cannot be reached with the keyboard, and the pairs of colors whose
+//! contrast is too low to be read comfortably (under the 4.5:1 ratio of the
+//! WCAG). As in the WCAG, the disabled elements are not checked.
+//!
+//! `audit` and `contrast` can be run in the tests of an application, and
+//! `Window::accessibility` audits a whole window. When the developer tools
+//! are enabled, the issues of the window are reported at startup as error
+//! events with the `accessibility` source, received by
+//! `WindowListener::on_error`.
+//!
+//! The widgets give their ARIA roles and states to screen readers, such as
+//! the checked state of a CheckBox or the value of a ProgressBar. The label
+//! read in place of their visible text, or the label of a field without
+//! text, is set with `set_aria_label`. The interactive widgets can be used
+//! with the keyboard once they are reached with `Tab`:
+//!
+//! * `Enter` and `Space` press a Button, `Space` toggles a CheckBox
+//! * the arrows select the previous or next choice of a Radio, a Combo and a
+//!   ListBox, and the previous or next tab of a Tabs, `Home` and `End`
+//!   selecting its first and last tabs
+//! * `Enter` and `Space` open a Combo, and `Escape` closes it
+//! * `Enter`, `Space` and `Down` open a menu of the MenuBar, the arrows
+//!   moving through its functions
+//!
+//! ## Example
+//!
+//! ```
+//! use neutrino::utils::accessibility::{self, Issue};
+//! use neutrino::widgets::textinput::TextInput;
+//! use neutrino::widgets::widget::Widget;
+//!
+//! let mut my_textinput = TextInput::new("my_textinput");
+//!
+//! assert!(accessibility::audit(&my_textinput).iter().any(|issue| match issue {
+//!     Issue::MissingLabel { source, .. } => source == "my_textinput",
+//!     _ => false,
+//! }));
+//!
+//! my_textinput.set_aria_label("Title");
+//! assert!(my_textinput.eval().contains(r#"aria-label="Title""#));
+//! assert!(accessibility::audit(&my_textinput).is_empty());
+//!
+//! let issues = accessibility::contrast(".hint{color:#aaaaaa;background-color:white}");
+//! assert_eq!(issues.len(), 1);
+//! ```
+
+use crate::utils::csp::{attributes, tag_end};
+use crate::widgets::widget::Widget;
+
+/// The minimal contrast ratio between a text and its background
+const MIN_CONTRAST: f64 = 4.5;
+
+/// The elements without content nor end tag
+const VOID: [&str; 6] = ["br", "hr", "img", "input", "link", "meta"];
+
+/// # An accessibility issue
+///
+/// The source is the name of the widget, or of the nearest widget holding
+/// the element, and the element is its HTML tag.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Issue {
+    MissingLabel {
+        source: String,
+        element: String,
+    },
+    Unreachable {
+        source: String,
+        element: String,
+    },
+    LowContrast {
+        selector: String,
+        foreground: String,
+        background: String,
+        ratio: f64,
+    },
+}
+
+impl Issue {
+    /// Get a message describing the issue
+    pub fn message(&self) -> String {
+        match self {
+            Issue::MissingLabel { source, element } => format!(
+                "{}: the <{}> has no label read by screen readers",
+                source, element
+            ),
+            Issue::Unreachable { source, element } => format!(
+                "{}: the <{}> handles clicks but cannot be reached with the keyboard",
+                source, element
+            ),
+            Issue::LowContrast {
+                selector,
+                foreground,
+                background,
+                ratio,
+            } => format!(
+                "{}: the contrast between {} and {} is {:.2}:1, under {}:1",
+                selector, foreground, background, ratio, MIN_CONTRAST
+            ),
+        }
+    }
+}
+
+/// # An element of the audited HTML
+///
+/// ## Fields
+///
+/// ```text
+/// tag: String
+/// attributes: Vec<(String, String)>
+/// parent: Option<usize>
+/// text: String
+/// ```
+struct Element {
+    tag: String,
+    attributes: Vec<(String, String)>,
+    parent: Option<usize>,
+    text: String,
+}
+
+impl Element {
+    /// Get the value of an attribute
+    fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(attribute, _)| attribute == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Return true if the element can be focused with the keyboard
+    fn focusable(&self) -> bool {
+        match self.attribute("tabindex") {
+            Some(tabindex) => tabindex != "-1",
+            None => {
+                ["input", "select", "textarea", "button"]
+                    .contains(&self.tag.as_str())
+                    || (self.tag == "a" && self.attribute("href").is_some())
+            }
+        }
+    }
+
+    /// Return true if the element is an item reached with the arrows from
+    /// the focused item of its group, such as a tab or a radio
+    fn navigable(&self) -> bool {
+        let roles = [
+            "tab",
+            "radio",
+            "option",
+            "menuitem",
+            "menuitemcheckbox",
+            "menuitemradio",
+        ];
+        match self.attribute("role") {
+            Some(role) => roles.contains(&role),
+            None => false,
+        }
+    }
+
+    /// Return true if the element handles clicks
+    fn clickable(&self) -> bool {
+        ["onclick", "onmousedown", "onmouseup", "ondblclick"]
+            .iter()
+            .any(|handler| self.attribute(handler).is_some())
+    }
+
+    /// Return true if the element has a label given by an attribute
+    fn labelled(&self) -> bool {
+        ["aria-label", "aria-labelledby", "title"]
+            .iter()
+            .any(|attribute| match self.attribute(attribute) {
+                Some(label) => !label.trim().is_empty(),
+                None => false,
+            })
+    }
+}
+
+/// Audit the HTML of a widget tree
+pub fn audit(widget: &dyn Widget) -> Vec<Issue> {
+    audit_html(&widget.eval())
+}
+
+/// Audit the colors of a stylesheet, such as the CSS of a theme
+pub fn contrast(css: &str) -> Vec<Issue> {
+    let mut issues = vec![];
+    for rule in css.split('}') {
+        let (selector, declarations) = match rule.rfind('{') {
+            Some(i) => (rule[..i].trim(), &rule[i + 1..]),
+            None => continue,
+        };
+        if selector.split(',').all(|part| part.contains(".disabled")) {
+            continue;
+        }
+        let mut foreground = None;
+        let mut background = None;
+        for declaration in declarations.split(';') {
+            let mut parts = declaration.splitn(2, ':');
+            let property = parts.next().unwrap_or("").trim();
+            let value = parts.next().unwrap_or("").trim();
+            match property {
+                "color" => foreground = first_color(value),
+                "background" | "background-color" => {
+                    background = first_color(value)
+                }
+                _ => (),
+            }
+        }
+        if let (Some((fg, fg_rgb)), Some((bg, bg_rgb))) =
+            (foreground, background)
+        {
+            let ratio = ratio(fg_rgb, bg_rgb);
+            if ratio < MIN_CONTRAST {
+                issues.push(Issue::LowContrast {
+                    selector: selector.to_string(),
+                    foreground: fg,
+                    background: bg,
+                    ratio,
+                });
+            }
+        }
+    }
+    issues
+}
+
+/// Audit an HTML string
+pub(crate) fn audit_html(html: &str) -> Vec<Issue> {
+    let elements = parse(html);
+    let mut focusable_inside = vec![false; elements.len()];
+    for element in elements.iter() {
+        if element.focusable() {
+            let mut parent = element.parent;
+            while let Some(p) = parent {
+                focusable_inside[p] = true;
+                parent = elements[p].parent;
+            }
+        }
+    }
+    let ancestors = |i: usize| {
+        let mut ancestors = vec![];
+        let mut parent = elements[i].parent;
+        while let Some(p) = parent {
+            ancestors.push(p);
+            parent = elements[p].parent;
+        }
+        ancestors
+    };
+    let source = |i: usize| {
+        let mut current = Some(i);
+        while let Some(c) = current {
+            if let Some(id) = elements[c].attribute("id") {
+                return id.to_string();
+            }
+            current = elements[c].parent;
+        }
+        "".to_string()
+    };
+    let mut issues = vec![];
+    for (i, element) in elements.iter().enumerate() {
+        let tag = element.tag.as_str();
+        let missing_label = match tag {
+            "input" => {
+                let kind = element.attribute("type").unwrap_or("text");
+                !["hidden", "submit", "button", "reset"].contains(&kind)
+                    && !element.labelled()
+                    && element
+                        .attribute("placeholder")
+                        .unwrap_or("")
+                        .trim()
+                        .is_empty()
+                    && !ancestors(i).iter().any(|&a| elements[a].tag == "label")
+                    && !elements.iter().any(|label| {
+                        label.tag == "label"
+                            && label.attribute("for").is_some()
+                            && label.attribute("for") == element.attribute("id")
+                    })
+            }
+            "select" | "textarea" => !element.labelled(),
+            "img" => element.attribute("alt").is_none() && !element.labelled(),
+            _ => {
+                element.clickable()
+                    && !element.labelled()
+                    && element.text.trim().is_empty()
+                    && !focusable_inside[i]
+            }
+        };
+        if missing_label {
+            push(
+                &mut issues,
+                Issue::MissingLabel {
+                    source: source(i),
+                    element: tag.to_string(),
+                },
+            );
+        }
+        if element.clickable()
+            && !element.focusable()
+            && !element.navigable()
+            && !focusable_inside[i]
+            && !ancestors(i).iter().any(|&a| elements[a].focusable())
+        {
+            push(
+                &mut issues,
+                Issue::Unreachable {
+                    source: source(i),
+                    element: tag.to_string(),
+                },
+            );
+        }
+    }
+    issues
+}
+
+/// Push an issue, once per widget
+fn push(issues: &mut Vec<Issue>, issue: Issue) {
+    if !issues.contains(&issue) {
+        issues.push(issue);
+    }
+}
+
+/// Parse an HTML string into a flat list of elements
+fn parse(html: &str) -> Vec<Element> {
+    let mut elements: Vec<Element> = vec![];
+    let mut open: Vec<usize> = vec![];
+    let mut rest = html;
+    loop {
+        let start = rest.find('<').unwrap_or(rest.len());
+        let text = &rest[..start];
+        for &i in open.iter() {
+            elements[i].text.push_str(text);
+        }
+        rest = &rest[start..];
+        if rest.is_empty() {
+            break;
+        }
+        let end = tag_end(rest);
+        let tag = &rest[..end];
+        rest = &rest[end..];
+        if tag.starts_with("<!") {
+            continue;
+        }
+        if let Some(name) = tag.strip_prefix("</") {
+            let name = name.trim_end_matches('>').trim().to_ascii_lowercase();
+            if let Some(position) =
+                open.iter().rposition(|&i| elements[i].tag == name)
+            {
+                open.truncate(position);
+            }
+            continue;
+        }
+        let name: String = tag[1..]
+            .chars()
+            .take_while(|c| c.is_ascii_alphanumeric())
+            .collect::<String>()
+            .to_ascii_lowercase();
+        let element = Element {
+            attributes: attributes(tag)
+                .into_iter()
+                .filter_map(|(_, attribute)| attribute)
+                .collect(),
+            parent: open.last().cloned(),
+            text: "".to_string(),
+            tag: name,
+        };
+        let void = VOID.contains(&element.tag.as_str())
+            || tag.trim_end_matches('>').ends_with('/');
+        elements.push(element);
+        if !void {
+            open.push(elements.len() - 1);
+        }
+    }
+    elements
+}
+
+/// Find the first color of a CSS value, with its RGB components
+fn first_color(value: &str) -> Option<(String, [f64; 3])> {
+    let value = value.to_ascii_lowercase();
+    for (i, _) in value.char_indices() {
+        let rest = &value[i..];
+        let boundary = i == 0
+            || !value[..i]
+                .chars()
+                .last()
+                .is_some_and(|c| c.is_ascii_alphanumeric() || c == '-');
+        if let Some(hex) = rest.strip_prefix('#') {
+            let hex: String =
+                hex.chars().take_while(|c| c.is_ascii_hexdigit()).collect();
+            let hex = match hex.len() {
+                3 => hex.chars().flat_map(|c| vec![c, c]).collect(),
+                6 => hex,
+                _ => continue,
+            };
+            let component = |j: usize| {
+                u8::from_str_radix(&hex[j..j + 2], 16).unwrap_or(0) as f64
+            };
+            return Some((
+                format!("#{}", hex),
+                [component(0), component(2), component(4)],
+            ));
+        } else if boundary && rest.starts_with("rgb") {
+            let inside = match (rest.find('('), rest.find(')')) {
+                (Some(open), Some(close)) if open < close => {
+                    &rest[open + 1..close]
+                }
+                _ => continue,
+            };
+            let components: Vec<f64> = inside
+                .split(',')
+                .take(3)
+                .filter_map(|c| c.trim().parse::<f64>().ok())
+                .collect();
+            if components.len() == 3 {
+                return Some((
+                    format!("rgb({})", inside),
+                    [components[0], components[1], components[2]],
+                ));
+            }
+        } else if boundary && rest.starts_with("white") {
+            return Some(("white".to_string(), [255.0, 255.0, 255.0]));
+        } else if boundary && rest.starts_with("black") {
+            return Some(("black".to_string(), [0.0, 0.0, 0.0]));
+        }
+    }
+    None
+}
+
+/// Get the contrast ratio of two colors
+fn ratio(a: [f64; 3], b: [f64; 3]) -> f64 {
+    let (a, b) = (luminance(a), luminance(b));
+    let (light, dark) = if a > b { (a, b) } else { (b, a) };
+    (light + 0.05) / (dark + 0.05)
+}
+
+/// Get the relative luminance of a color
+fn luminance(rgb: [f64; 3]) -> f64 {
+    let linear = |c: f64| {
+        let c = c / 255.0;
+        if c <= 0.03928 {
+            c / 12.92
+        } else {
+            ((c + 0.055) / 1.055).powf(2.4)
+        }
+    };
+    0.2126 * linear(rgb[0]) + 0.7152 * linear(rgb[1]) + 0.0722 * linear(rgb[2])
+}
@@ -0,0 +1,206 @@
+//! # Animations
+//!
+//! A widget can play a Transition when it is shown or hidden: it fades or
+//! slides in when it is added to the page or when its Container stops being
+//! hidden, and out when it is removed. The transition of a widget is one of
+//! its layout options, and the Tabs play theirs when the selected tab
+//! changes.
+//!
+//! A style property of a widget can also be animated from Rust with
+//! `App::animate`: the Animation starts once the page has been rendered, the
+//! widget keeping the final value of the property until it is rendered
+//! again, and an `AnimationEnd` event holding the name of the animation is
+//! sent when it is over (see `WindowListener::on_animation_end`).
+//!
+//! ## Example
+//!
+//! ```
+//! use neutrino::utils::animation::{Animation, Easing, Transition};
+//! use neutrino::utils::layoutoptions::LayoutOptions;
+//! use neutrino::widgets::label::Label;
+//! use neutrino::App;
+//!
+//! let mut layout = LayoutOptions::new();
+//! layout.set_transition(Transition::Fade);
+//!
+//! let mut my_label = Label::new("my_label");
+//! my_label.set_layout(layout);
+//!
+//! let mut flash = Animation::new("flash", "my_label", "background-color");
+//! flash.set_values("#ffeb3b", "transparent");
+//! flash.set_duration(600);
+//! flash.set_easing(Easing::EaseOut);
+//! App::animate(flash);
+//! ```
+
+use std::cell::RefCell;
+
+thread_local! {
+    static PENDING: RefCell<Vec<Animation>> = const { RefCell::new(Vec::new()) };
+}
+
+/// # The transition of a widget when it is shown or hidden
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Transition {
+    Fade,
+    Slide,
+}
+
+impl Transition {
+    /// Return the CSS class of the transition
+    pub(crate) fn css(self) -> &'static str {
+        match self {
+            Transition::Fade => "transition-fade",
+            Transition::Slide => "transition-slide",
+        }
+    }
+}
+
+/// # The timing function of an Animation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Easing {
+    Linear,
+    EaseIn,
+    EaseOut,
+    EaseInOut,
+}
+
+impl Easing {
+    /// Return the CSS name of the timing function
+    fn css(self) -> &'static str {
+        match self {
+            Easing::Linear => "linear",
+            Easing::EaseIn => "ease-in",
+            Easing::EaseOut => "ease-out",
+            Easing::EaseInOut => "ease-in-out",
+        }
+    }
+}
+
+/// # The animation of a style property of a widget
+///
+/// ## Fields
+///
+/// ```text
+/// name: String
+/// target: String
+/// property: String
+/// from: String
+/// to: String
+/// duration: u32
+/// easing: Easing
+/// ```
+///
+/// ## Default values
+///
+/// ```text
+/// name: name.to_string()
+/// target: target.to_string()
+/// property: property.to_string()
+/// from: "".to_string()
+/// to: "".to_string()
+/// duration: 300
+/// easing: Easing::EaseInOut
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Animation {
+    name: String,
+    target: String,
+    property: String,
+    from: String,
+    to: String,
+    duration: u32,
+    easing: Easing,
+}
+
+impl Animation {
+    /// Create an Animation of a CSS property, such as `opacity` or
+    /// `background-color`, of the widget with the given name
+    pub fn new(name: &str, target: &str, property: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            target: target.to_string(),
+            property: property.to_string(),
+            from: "".to_string(),
+            to: "".to_string(),
+            duration: 300,
+            easing: Easing::EaseInOut,
+        }
+    }
+
+    /// Get the name, sent back with the AnimationEnd event
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the name of the animated widget
+    pub fn target(&self) -> &str {
+        &self.target
+    }
+
+    /// Get the animated property
+    pub fn property(&self) -> &str {
+        &self.property
+    }
+
+    /// Get the initial value
+    pub fn from(&self) -> &str {
+        &self.from
+    }
+
+    /// Get the final value
+    pub fn to(&self) -> &str {
+        &self.to
+    }
+
+    /// Get the duration, in milliseconds
+    pub fn duration(&self) -> u32 {
+        self.duration
+    }
+
+    /// Get the timing function
+    pub fn easing(&self) -> Easing {
+        self.easing
+    }
+
+    /// Set the initial and final values, such as `0` and `1` for the
+    /// opacity
+    pub fn set_values(&mut self, from: &str, to: &str) {
+        self.from = from.to_string();
+        self.to = to.to_string();
+    }
+
+    /// Set the duration, in milliseconds
+    pub fn set_duration(&mut self, duration: u32) {
+        self.duration = duration;
+    }
+
+    /// Set the timing function
+    pub fn set_easing(&mut self, easing: Easing) {
+        self.easing = easing;
+    }
+
+    /// Return the javascript starting the animation
+    pub(crate) fn js(&self) -> String {
+        format!(
+            "animate({}, {}, {}, {}, {}, {}, {});",
+            json::stringify(self.target.as_str()),
+            json::stringify(self.name.as_str()),
+            json::stringify(self.property.as_str()),
+            json::stringify(self.from.as_str()),
+            json::stringify(self.to.as_str()),
+            self.duration,
+            json::stringify(self.easing.css()),
+        )
+    }
+}
+
+/// Queue an animation, started after the next render
+pub(crate) fn animate(animation: Animation) {
+    PENDING.with(|pending| pending.borrow_mut().push(animation));
+}
+
+/// Take the animations queued since the last call
+pub(crate) fn take() -> Vec<Animation> {
+    PENDING.with(|pending| pending.borrow_mut().drain(..).collect())
+}
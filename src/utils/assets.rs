@@ -0,0 +1,58 @@
+//! # Embedded assets
+//!
+//! The stylesheets, the themes, the scripts and the icons used by neutrino
+//! are compiled into the binary, so an application ships as a single
+//! executable without any asset folder. The only files read at runtime are
+//! the ones given by the application itself, such as the path of a
+//! `Pixmap::from_path` or an `Image::from_path`.
+//!
+//! These lookups are recorded, so an application which must not depend on
+//! files next to its executable can check at startup that its images are
+//! embedded too, for instance with `include_bytes!` and
+//! `Pixmap::from_bytes`.
+//!
+//! ## Example
+//!
+//! ```
+//! use neutrino::utils::assets;
+//! use neutrino::utils::pixmap::Pixmap;
+//!
+//! let logo = Pixmap::from_bytes(b"\x89PNG\r\n\x1a\n");
+//! assert!(assets::verify().is_ok());
+//!
+//! let photo = Pixmap::from_path("photos/holidays.jpg");
+//! assert_eq!(assets::verify(), Err(vec!["photos/holidays.jpg".to_string()]));
+//! ```
+
+use std::sync::Mutex;
+
+static LOOKUPS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+/// Get the paths of the files read at runtime, in the order they were read
+pub fn lookups() -> Vec<String> {
+    match LOOKUPS.lock() {
+        Ok(lookups) => lookups.clone(),
+        Err(poisoned) => poisoned.into_inner().clone(),
+    }
+}
+
+/// Return an error listing the paths of the files read at runtime, if any
+pub fn verify() -> Result<(), Vec<String>> {
+    let lookups = lookups();
+    if lookups.is_empty() {
+        Ok(())
+    } else {
+        Err(lookups)
+    }
+}
+
+/// Record a file read at runtime
+pub(crate) fn record(path: &str) {
+    let mut lookups = match LOOKUPS.lock() {
+        Ok(lookups) => lookups,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    if !lookups.iter().any(|lookup| lookup == path) {
+        lookups.push(path.to_string());
+    }
+}
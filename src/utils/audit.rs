@@ -0,0 +1,256 @@
+//! # Audit trail
+//!
+//! An opt-in log of the significant interactions of the users, for
+//! applications which must be able to tell who did what and when. Once
+//! `start` has been called, each event whose source is a widget marked as
+//! relevant with `mark` is appended to the log file, with the time, the user
+//! set by `set_user`, the name of the widget, the kind of the event and its
+//! value. The application can append its own entries with `record`.
+//!
+//! The file is only opened in append mode, and each entry is a line of JSON
+//! signed with an HMAC-SHA256 of the entry and of the signature of the
+//! previous entry, using the key given to `start`. `verify` recomputes the
+//! chain, so a modified, inserted, removed or reordered entry is detected.
+//! Removing the last entries is only detected by comparing the number of
+//! entries with the one expected.
+//!
+//! The value of an event is logged as it is sent by the widget, so the
+//! widgets typing secrets, such as a password input, should not be marked.
+//!
+//! ## Example
+//!
+//! ```
+//! use neutrino::utils::audit;
+//!
+//! let path = std::env::temp_dir().join("neutrino-audit-example.log");
+//! let _ = std::fs::remove_file(&path);
+//!
+//! audit::start(&path, b"secret key").unwrap();
+//! audit::set_user("ada");
+//! audit::mark("delete_button");
+//! audit::record("session", "login").unwrap();
+//! audit::record("session", "logout").unwrap();
+//! audit::stop();
+//!
+//! assert_eq!(audit::verify(&path, b"secret key"), Ok(2));
+//!
+//! let log = std::fs::read_to_string(&path).unwrap();
+//! let mut lines: Vec<String> = log.lines().map(str::to_string).collect();
+//! lines[1] = lines[1].replace("\"ada\"", "\"eve\"");
+//! std::fs::write(&path, lines.join("\n") + "\n").unwrap();
+//!
+//! assert_eq!(audit::verify(&path, b"secret key"), Err(2));
+//! ```
+
+use std::cell::RefCell;
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use json::{self, JsonValue};
+
+use crate::utils::crypto::{equal, hex, hmac_sha256};
+use crate::utils::event::Event;
+
+/// # The state of the audit trail
+///
+/// ## Fields
+///
+/// ```text
+/// path: PathBuf
+/// key: Vec<u8>
+/// user: String
+/// marked: Vec<String>
+/// last: String
+/// error: Option<String>
+/// ```
+struct Audit {
+    path: PathBuf,
+    key: Vec<u8>,
+    user: String,
+    marked: Vec<String>,
+    last: String,
+    error: Option<String>,
+}
+
+thread_local! {
+    static AUDIT: RefCell<Option<Audit>> = const { RefCell::new(None) };
+}
+
+/// Start the audit trail, appending to the file at the given path. The chain
+/// of signatures goes on from the last entry of the file, if any.
+pub fn start<P: AsRef<Path>>(path: P, key: &[u8]) -> io::Result<()> {
+    let path = path.as_ref().to_path_buf();
+    let last = match File::open(&path) {
+        Ok(file) => {
+            let mut last = "".to_string();
+            for line in BufReader::new(file).lines() {
+                let line = line?;
+                if let Ok(entry) = json::parse(&line) {
+                    if let Some(signature) = entry["signature"].as_str() {
+                        last = signature.to_string();
+                    }
+                }
+            }
+            last
+        }
+        Err(ref error) if error.kind() == io::ErrorKind::NotFound => {
+            "".to_string()
+        }
+        Err(error) => return Err(error),
+    };
+    OpenOptions::new().create(true).append(true).open(&path)?;
+    AUDIT.with(|audit| {
+        *audit.borrow_mut() = Some(Audit {
+            path,
+            key: key.to_vec(),
+            user: "".to_string(),
+            marked: vec![],
+            last,
+            error: None,
+        });
+    });
+    Ok(())
+}
+
+/// Stop the audit trail
+pub fn stop() {
+    AUDIT.with(|audit| *audit.borrow_mut() = None);
+}
+
+/// Return true if the audit trail has been started
+pub fn started() -> bool {
+    AUDIT.with(|audit| audit.borrow().is_some())
+}
+
+/// Set the user written in the next entries
+pub fn set_user(user: &str) {
+    with_audit(|audit| audit.user = user.to_string());
+}
+
+/// Mark the widget with the given name as relevant: its events are logged
+pub fn mark(name: &str) {
+    with_audit(|audit| {
+        if !audit.marked.iter().any(|marked| marked == name) {
+            audit.marked.push(name.to_string());
+        }
+    });
+}
+
+/// Unmark the widget with the given name
+pub fn unmark(name: &str) {
+    with_audit(|audit| audit.marked.retain(|marked| marked != name));
+}
+
+/// Return true if the widget with the given name is marked as relevant
+pub fn marked(name: &str) -> bool {
+    AUDIT.with(|audit| match &*audit.borrow() {
+        Some(audit) => audit.marked.iter().any(|marked| marked == name),
+        None => false,
+    })
+}
+
+/// Append an entry written by the application, such as a login
+pub fn record(source: &str, action: &str) -> io::Result<()> {
+    AUDIT.with(|audit| match &mut *audit.borrow_mut() {
+        Some(audit) => audit.append(source, "Record", action),
+        None => Ok(()),
+    })
+}
+
+/// Get the last error which occured while an event was logged
+pub fn error() -> Option<String> {
+    AUDIT.with(|audit| match &*audit.borrow() {
+        Some(audit) => audit.error.clone(),
+        None => None,
+    })
+}
+
+/// Verify the signatures of the log at the given path. Return the number of
+/// entries, or the line number of the first invalid entry.
+pub fn verify<P: AsRef<Path>>(path: P, key: &[u8]) -> Result<usize, usize> {
+    let file = File::open(path).map_err(|_| 0usize)?;
+    let mut last = "".to_string();
+    let mut count = 0;
+    for (i, line) in BufReader::new(file).lines().enumerate() {
+        let line = line.map_err(|_| i + 1)?;
+        let mut entry = json::parse(&line).map_err(|_| i + 1)?;
+        let signature = match entry["signature"].as_str() {
+            Some(signature) => signature.to_string(),
+            None => return Err(i + 1),
+        };
+        entry.remove("signature");
+        if !equal(sign(key, &last, &entry).as_bytes(), signature.as_bytes()) {
+            return Err(i + 1);
+        }
+        last = signature;
+        count += 1;
+    }
+    Ok(count)
+}
+
+/// Log an event if its source is marked as relevant
+pub(crate) fn observe(event: &Event) {
+    let (source, kind, value) = match event {
+        Event::Change { source, value } => (source, "Change", value.clone()),
+        Event::CanvasClick { source, x, y } => {
+            (source, "CanvasClick", format!("{},{}", x, y))
+        }
+        Event::DoubleClick { source, x, y, .. } => {
+            (source, "DoubleClick", format!("{},{}", x, y))
+        }
+        Event::Dismiss { source } => (source, "Dismiss", "".to_string()),
+        _ => return,
+    };
+    with_audit(|audit| {
+        if audit.marked.iter().any(|marked| marked == source) {
+            if let Err(error) = audit.append(source, kind, &value) {
+                audit.error = Some(error.to_string());
+            }
+        }
+    });
+}
+
+/// Run a function on the audit trail, if it has been started
+fn with_audit(f: impl FnOnce(&mut Audit)) {
+    AUDIT.with(|audit| {
+        if let Some(audit) = &mut *audit.borrow_mut() {
+            f(audit);
+        }
+    });
+}
+
+impl Audit {
+    /// Append a signed entry to the log
+    fn append(
+        &mut self,
+        source: &str,
+        event: &str,
+        value: &str,
+    ) -> io::Result<()> {
+        let time = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_millis() as u64)
+            .unwrap_or(0);
+        let mut entry = json::object! {
+            "time" => time,
+            "user" => self.user.as_str(),
+            "source" => source,
+            "event" => event,
+            "value" => value,
+        };
+        let signature = sign(&self.key, &self.last, &entry);
+        entry["signature"] = signature.as_str().into();
+        let mut file = OpenOptions::new().append(true).open(&self.path)?;
+        writeln!(file, "{}", entry.dump())?;
+        self.last = signature;
+        Ok(())
+    }
+}
+
+/// Return the signature of an entry chained to the previous signature
+fn sign(key: &[u8], last: &str, entry: &JsonValue) -> String {
+    let message = format!("{}\n{}", last, entry.dump());
+    hex(&hmac_sha256(key, message.as_bytes()))
+}
@@ -0,0 +1,285 @@
+//! # Clipboard
+//!
+//! The clipboard is reached through the page, as the webview does not give
+//! access to the one of the system.
+//!
+//! When something is pasted in the window, the page reads the clipboard and
+//! sends a `Paste` event holding its content: the text, the images, such as
+//! a screenshot, the files, with their names and data, and the paths of the
+//! files copied from a file manager. The event is received by
+//! `WindowListener::on_paste` with the name of the focused widget, the text
+//! still being pasted in the focused input as usual.
+//!
+//! The content of the clipboard is replaced with `App::copy` once the page
+//! has been rendered: a text, an image and a list of file paths, written as
+//! a list of `file://` URIs which file managers paste as files. Writing an
+//! image needs the asynchronous clipboard of the webview, and only the first
+//! image is written.
+//!
+//! ## Example
+//!
+//! ```
+//! use neutrino::utils::clipboard::ClipboardData;
+//! use neutrino::utils::pixmap::Pixmap;
+//! use neutrino::App;
+//!
+//! let mut data = ClipboardData::new();
+//! data.set_text("report.png");
+//! data.add_image(Pixmap::from_bytes(b"\x89PNG\r\n\x1a\n"));
+//! data.add_path("/home/user/report.png");
+//! App::copy(data);
+//! ```
+
+use std::cell::RefCell;
+
+use json::JsonValue;
+
+use crate::utils::pixmap::Pixmap;
+
+thread_local! {
+    static PENDING: RefCell<Vec<ClipboardData>> = const { RefCell::new(Vec::new()) };
+}
+
+/// # A file pasted in the window
+///
+/// ## Fields
+///
+/// ```text
+/// name: String
+/// mime: String
+/// data: String
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClipboardFile {
+    name: String,
+    mime: String,
+    data: String,
+}
+
+impl ClipboardFile {
+    /// Create a ClipboardFile from its name, its MIME type and its base64
+    /// encoded data
+    pub fn new(name: &str, mime: &str, data: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            mime: mime.to_string(),
+            data: data.to_string(),
+        }
+    }
+
+    /// Get the name
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the MIME type, empty if it is unknown
+    pub fn mime(&self) -> &str {
+        &self.mime
+    }
+
+    /// Get the base64 encoded data
+    pub fn data(&self) -> &str {
+        &self.data
+    }
+
+    /// Get the decoded data
+    pub fn bytes(&self) -> Result<Vec<u8>, String> {
+        base64::decode(&self.data).map_err(|error| error.to_string())
+    }
+}
+
+/// # The content of the clipboard
+///
+/// ## Fields
+///
+/// ```text
+/// text: Option<String>
+/// images: Vec<Pixmap>
+/// files: Vec<ClipboardFile>
+/// paths: Vec<String>
+/// ```
+///
+/// ## Default values
+///
+/// ```text
+/// text: None
+/// images: vec![]
+/// files: vec![]
+/// paths: vec![]
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ClipboardData {
+    text: Option<String>,
+    images: Vec<Pixmap>,
+    files: Vec<ClipboardFile>,
+    paths: Vec<String>,
+}
+
+impl ClipboardData {
+    /// Create a ClipboardData
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Read the content of a paste event sent by the page
+    pub(crate) fn from_json(value: &JsonValue) -> Self {
+        let text = value["text"].as_str().map(str::to_string);
+        let images = value["images"]
+            .members()
+            .filter_map(JsonValue::as_str)
+            .map(Pixmap::from_data_uri)
+            .collect();
+        let files = value["files"]
+            .members()
+            .map(|file| {
+                ClipboardFile::new(
+                    file["name"].as_str().unwrap_or(""),
+                    file["mime"].as_str().unwrap_or(""),
+                    file["data"].as_str().unwrap_or(""),
+                )
+            })
+            .collect();
+        let paths = value["uris"]
+            .as_str()
+            .unwrap_or("")
+            .lines()
+            .filter_map(path)
+            .collect();
+        Self {
+            text,
+            images,
+            files,
+            paths,
+        }
+    }
+
+    /// Get the text
+    pub fn text(&self) -> Option<&str> {
+        self.text.as_deref()
+    }
+
+    /// Get the images
+    pub fn images(&self) -> &Vec<Pixmap> {
+        &self.images
+    }
+
+    /// Get the files
+    pub fn files(&self) -> &Vec<ClipboardFile> {
+        &self.files
+    }
+
+    /// Get the paths of the files copied from a file manager
+    pub fn paths(&self) -> &Vec<String> {
+        &self.paths
+    }
+
+    /// Return true if the clipboard holds nothing
+    pub fn is_empty(&self) -> bool {
+        self.text.is_none()
+            && self.images.is_empty()
+            && self.files.is_empty()
+            && self.paths.is_empty()
+    }
+
+    /// Set the text
+    pub fn set_text(&mut self, text: &str) {
+        self.text = Some(text.to_string());
+    }
+
+    /// Add an image
+    pub fn add_image(&mut self, image: Pixmap) {
+        self.images.push(image);
+    }
+
+    /// Add a file
+    pub fn add_file(&mut self, file: ClipboardFile) {
+        self.files.push(file);
+    }
+
+    /// Add the path of a file, copied as a `file://` URI
+    pub fn add_path(&mut self, path: &str) {
+        self.paths.push(path.to_string());
+    }
+
+    /// Return the javascript writing the content into the clipboard
+    pub(crate) fn js(&self) -> String {
+        let text = match &self.text {
+            Some(text) => json::stringify(text.as_str()),
+            None => "null".to_string(),
+        };
+        let images: Vec<String> = self
+            .images
+            .iter()
+            .map(|image| {
+                format!(
+                    "data:image/{};base64,{}",
+                    image.extension(),
+                    image.data()
+                )
+            })
+            .collect();
+        let uris: Vec<String> =
+            self.paths.iter().map(|path| uri(path)).collect();
+        format!(
+            "copyToClipboard({}, {}, {});",
+            text,
+            json::stringify(images),
+            json::stringify(uris.join("\r\n")),
+        )
+    }
+}
+
+/// Queue a content to write into the clipboard after the next render
+pub(crate) fn copy(data: ClipboardData) {
+    PENDING.with(|pending| pending.borrow_mut().push(data));
+}
+
+/// Take the contents queued since the last call
+pub(crate) fn take() -> Vec<ClipboardData> {
+    PENDING.with(|pending| pending.borrow_mut().drain(..).collect())
+}
+
+/// Return the `file://` URI of a path, its special characters being
+/// percent-encoded
+fn uri(path: &str) -> String {
+    let mut uri = "file://".to_string();
+    for byte in path.bytes() {
+        match byte {
+            b'A'..=b'Z'
+            | b'a'..=b'z'
+            | b'0'..=b'9'
+            | b'/'
+            | b'-'
+            | b'_'
+            | b'.'
+            | b'~' => uri.push(byte as char),
+            byte => uri.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    uri
+}
+
+/// Return the path of a `file://` URI of a URI list, skipping the comments
+/// and the other URIs
+fn path(uri: &str) -> Option<String> {
+    let uri = uri.trim();
+    let encoded = uri.strip_prefix("file://")?;
+    let encoded = &encoded[encoded.find('/')?..];
+    let bytes = encoded.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = encoded.get(i + 1..i + 3);
+        match hex.map(|hex| u8::from_str_radix(hex, 16)) {
+            Some(Ok(byte)) if bytes[i] == b'%' => {
+                decoded.push(byte);
+                i += 3;
+            }
+            _ => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8(decoded).ok()
+}
@@ -0,0 +1,162 @@
+//! # Cryptographic primitives
+//!
+//! The primitives used by the framework, in one place, on top of the audited
+//! crates of the RustCrypto project:
+//!
+//! * `sha1`: the SHA-1 digest, only for the handshake of the WebSocket, as
+//!   it is broken for the other uses (`sha1` crate)
+//! * `sha256`: the SHA-256 digest (`sha2` crate)
+//! * `hmac_sha256`: the HMAC-SHA256 of a message (`hmac` crate)
+//! * `pbkdf2_sha256`: a key derived from a password (`pbkdf2` crate)
+//! * `seal` and `open`: the authenticated encryption of data with
+//!   ChaCha20-Poly1305 (`chacha20poly1305` crate)
+//! * `equal`: the comparison of two digests in constant time (`subtle`
+//!   crate)
+//!
+//! They are not meant to be used beyond what the framework needs from them.
+
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use hmac::{Hmac, Mac};
+use sha1::Sha1;
+use sha2::{Digest, Sha256};
+use subtle::ConstantTimeEq;
+
+/// Write bytes in hexadecimal
+///
+/// # Example
+///
+/// ```
+/// use neutrino::utils::crypto::hex;
+///
+/// assert_eq!(hex(&[0x00, 0xab, 0x10]), "00ab10");
+/// ```
+pub fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Compute the SHA-1 digest of data, as required by the handshake of the
+/// WebSocket (RFC 6455)
+///
+/// # Example
+///
+/// ```
+/// use neutrino::utils::crypto::{hex, sha1};
+///
+/// assert_eq!(hex(&sha1(b"abc")), "a9993e364706816aba3e25717850c26c9cd0d89d");
+/// ```
+pub fn sha1(data: &[u8]) -> [u8; 20] {
+    Sha1::digest(data).into()
+}
+
+/// Compute the SHA-256 digest of data
+///
+/// # Example
+///
+/// ```
+/// use neutrino::utils::crypto::{hex, sha256};
+///
+/// assert_eq!(
+///     hex(&sha256(b"abc")),
+///     "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+/// );
+/// ```
+pub fn sha256(data: &[u8]) -> [u8; 32] {
+    Sha256::digest(data).into()
+}
+
+/// Compute the HMAC-SHA256 of data
+///
+/// # Example
+///
+/// The test case 2 of RFC 4231:
+///
+/// ```
+/// use neutrino::utils::crypto::{hex, hmac_sha256};
+///
+/// assert_eq!(
+///     hex(&hmac_sha256(b"Jefe", b"what do ya want for nothing?")),
+///     "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+/// );
+/// ```
+pub fn hmac_sha256(key: &[u8], data: &[u8]) -> [u8; 32] {
+    let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(key)
+        .expect("HMAC accepts keys of any length");
+    mac.update(data);
+    mac.finalize().into_bytes().into()
+}
+
+/// Derive a 32 bytes key from a password with PBKDF2-HMAC-SHA256
+///
+/// # Example
+///
+/// ```
+/// use neutrino::utils::crypto::{hex, pbkdf2_sha256};
+///
+/// assert_eq!(
+///     hex(&pbkdf2_sha256(b"password", b"salt", 1)),
+///     "120fb6cffcf8b32c43e7225256c4f837a86548c92ccc35480805987cb70be17b"
+/// );
+/// ```
+pub fn pbkdf2_sha256(
+    password: &[u8],
+    salt: &[u8],
+    iterations: u32,
+) -> [u8; 32] {
+    pbkdf2::pbkdf2_hmac_array::<Sha256, 32>(password, salt, iterations)
+}
+
+/// Encrypt and authenticate data with ChaCha20-Poly1305, with a 12 bytes
+/// nonce which must never be used twice with the same key
+///
+/// # Panics
+///
+/// If the nonce is not 12 bytes long.
+///
+/// # Example
+///
+/// ```
+/// use neutrino::utils::crypto::{open, seal};
+///
+/// let key = [7u8; 32];
+/// let nonce = [1u8; 12];
+///
+/// let mut sealed = seal(&key, &nonce, b"d2f1c0e7a9b4");
+/// assert_eq!(open(&key, &nonce, &sealed).unwrap(), b"d2f1c0e7a9b4");
+/// assert!(open(&[8u8; 32], &nonce, &sealed).is_none());
+///
+/// sealed[0] ^= 1;
+/// assert!(open(&key, &nonce, &sealed).is_none());
+/// ```
+pub fn seal(key: &[u8; 32], nonce: &[u8], data: &[u8]) -> Vec<u8> {
+    ChaCha20Poly1305::new(Key::from_slice(key))
+        .encrypt(Nonce::from_slice(nonce), data)
+        .expect("the data is not too long to be encrypted")
+}
+
+/// Decrypt data sealed with `seal`, None if it was modified or sealed with
+/// another key or nonce
+///
+/// # Panics
+///
+/// If the nonce is not 12 bytes long.
+pub fn open(key: &[u8; 32], nonce: &[u8], sealed: &[u8]) -> Option<Vec<u8>> {
+    ChaCha20Poly1305::new(Key::from_slice(key))
+        .decrypt(Nonce::from_slice(nonce), sealed)
+        .ok()
+}
+
+/// Compare two digests in a time which does not depend on their contents
+///
+/// # Example
+///
+/// ```
+/// use neutrino::utils::crypto::equal;
+///
+/// assert!(equal(b"digest", b"digest"));
+/// assert!(!equal(b"digest", b"digesT"));
+/// assert!(!equal(b"digest", b"diges"));
+/// ```
+pub fn equal(a: &[u8], b: &[u8]) -> bool {
+    a.ct_eq(b).into()
+}
@@ -0,0 +1,242 @@
+//! # Strict content-security policy
+//!
+//! By default, the widgets send their events from inline handlers, such as
+//! `onmousedown="..."` attributes, which a content-security policy forbidding
+//! inline scripts would block. With `Window::set_strict_csp`, the page is
+//! generated with a policy only allowing the scripts of neutrino, and the
+//! inline handlers are moved to listeners delegated from the document:
+//!
+//! - the handlers written by the `Event` functions are the only ones kept,
+//!   each attribute being replaced by a `data-on-<event>` attribute holding
+//!   the identifier of its handler
+//! - any other inline handler, such as one injected through a text displayed
+//!   by a widget, is removed from the page
+//! - the handlers are defined from the application, never from the page, so
+//!   the policy does not need to allow `eval`
+//!
+//! The style elements and attributes are still allowed, the widgets setting
+//! their sizes and positions inline.
+
+use std::cell::RefCell;
+use std::collections::hash_map::RandomState;
+use std::collections::{HashMap, HashSet};
+use std::hash::{BuildHasher, Hasher};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// The names of the events which can be delegated
+const EVENTS: [&str; 13] = [
+    "mousedown",
+    "mouseover",
+    "mousemove",
+    "mouseup",
+    "dblclick",
+    "contextmenu",
+    "input",
+    "change",
+    "keydown",
+    "scroll",
+    "touchstart",
+    "touchmove",
+    "touchend",
+];
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+    static ALLOWED: RefCell<HashSet<String>> = RefCell::new(HashSet::new());
+}
+
+/// Enable the strict policy: the handlers returned by the `Event` functions
+/// are recorded from now on
+pub(crate) fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+/// Record a handler written by neutrino, the only ones kept in the page
+pub(crate) fn allow(js: &str) {
+    if ENABLED.load(Ordering::Relaxed) {
+        ALLOWED.with(|allowed| allowed.borrow_mut().insert(js.to_string()));
+    }
+}
+
+/// Return true if the handler has been written by neutrino
+fn allowed(js: &str) -> bool {
+    ALLOWED.with(|allowed| allowed.borrow().contains(js))
+}
+
+/// Return a random nonce for the scripts of the page
+pub(crate) fn nonce() -> String {
+    let first = RandomState::new().build_hasher().finish();
+    let second = RandomState::new().build_hasher().finish();
+    format!("{:016x}{:016x}", first, second)
+}
+
+/// Return the HTML meta tag of the policy
+pub(crate) fn policy(nonce: &str) -> String {
+    format!(
+        r#"<meta http-equiv="Content-Security-Policy" content="default-src 'none'; script-src 'nonce-{}'; style-src 'unsafe-inline'; img-src data:; font-src data:">"#,
+        nonce
+    )
+}
+
+/// # The delegated handlers of a page
+///
+/// ## Fields
+///
+/// ```text
+/// ids: HashMap<String, usize>
+/// ```
+pub(crate) struct Handlers {
+    ids: HashMap<String, usize>,
+}
+
+impl Handlers {
+    /// Create the Handlers
+    pub(crate) fn new() -> Self {
+        Self {
+            ids: HashMap::new(),
+        }
+    }
+
+    /// Replace the allowed inline handlers of the HTML by the identifiers of
+    /// delegated handlers and remove the others. Return the new HTML and the
+    /// javascript defining the handlers which were not defined yet.
+    pub(crate) fn delegate(&mut self, html: &str) -> (String, String) {
+        let mut out = String::with_capacity(html.len());
+        let mut definitions = String::new();
+        let mut rest = html;
+        while let Some(start) = rest.find('<') {
+            out.push_str(&rest[..start]);
+            rest = &rest[start..];
+            let end = tag_end(rest);
+            let tag = &rest[..end];
+            rest = &rest[end..];
+            if tag.starts_with("<!") || tag.starts_with("</") {
+                out.push_str(tag);
+            } else {
+                out.push_str(&self.delegate_tag(tag, &mut definitions));
+            }
+        }
+        out.push_str(rest);
+        (out, definitions)
+    }
+
+    /// Replace the inline handlers of a start tag
+    fn delegate_tag(&mut self, tag: &str, definitions: &mut String) -> String {
+        let mut out = String::with_capacity(tag.len());
+        for (text, attribute) in attributes(tag) {
+            match attribute {
+                Some((name, value)) if name.starts_with("on") => {
+                    let event = &name[2..];
+                    if EVENTS.contains(&event) && allowed(&value) {
+                        let id = self.id(&value, definitions);
+                        out.push_str(&format!(
+                            r#" data-on-{}="{}""#,
+                            event, id
+                        ));
+                    }
+                }
+                _ => out.push_str(text),
+            }
+        }
+        out
+    }
+
+    /// Get the identifier of a handler, defining it if needed
+    fn id(&mut self, value: &str, definitions: &mut String) -> usize {
+        if let Some(id) = self.ids.get(value) {
+            return *id;
+        }
+        let id = self.ids.len();
+        self.ids.insert(value.to_string(), id);
+        definitions.push_str(&format!(
+            "defineHandler({}, function(event, element) {{ with (document) with (element.form || {{}}) with (element) {{ {} }} }});\n",
+            id,
+            unescape(value)
+        ));
+        id
+    }
+}
+
+/// Return the length of the tag at the start of the HTML, quoted values
+/// included
+pub(crate) fn tag_end(html: &str) -> usize {
+    let mut quote = None;
+    for (i, c) in html.char_indices() {
+        match (quote, c) {
+            (None, '"') | (None, '\'') => quote = Some(c),
+            (Some(q), _) if q == c => quote = None,
+            (None, '>') => return i + 1,
+            _ => (),
+        }
+    }
+    html.len()
+}
+
+/// Split a start tag into its text pieces, each one being either an
+/// attribute with its name and unquoted value, or the text between them
+#[allow(clippy::type_complexity)]
+pub(crate) fn attributes(tag: &str) -> Vec<(&str, Option<(String, String)>)> {
+    let bytes = tag.as_bytes();
+    let separator = |b: u8| b.is_ascii_whitespace() || b"=>/".contains(&b);
+    let mut pieces = vec![];
+    let mut i = 1;
+    while i < bytes.len() && !separator(bytes[i]) {
+        i += 1;
+    }
+    let mut text = 0;
+    loop {
+        while i < bytes.len()
+            && (bytes[i].is_ascii_whitespace() || bytes[i] == b'/')
+        {
+            i += 1;
+        }
+        if i >= bytes.len() || bytes[i] == b'>' {
+            break;
+        }
+        let start = i;
+        i += 1;
+        while i < bytes.len() && !separator(bytes[i]) {
+            i += 1;
+        }
+        let name = tag[start..i].to_ascii_lowercase();
+        let mut value = String::new();
+        if i < bytes.len() && bytes[i] == b'=' {
+            i += 1;
+            if i < bytes.len() && (bytes[i] == b'"' || bytes[i] == b'\'') {
+                let quote = bytes[i];
+                let value_start = i + 1;
+                i = value_start;
+                while i < bytes.len() && bytes[i] != quote {
+                    i += 1;
+                }
+                value = tag[value_start..i].to_string();
+                i = (i + 1).min(bytes.len());
+            } else {
+                let value_start = i;
+                while i < bytes.len()
+                    && !bytes[i].is_ascii_whitespace()
+                    && bytes[i] != b'>'
+                {
+                    i += 1;
+                }
+                value = tag[value_start..i].to_string();
+            }
+        }
+        pieces.push((&tag[text..start], None));
+        pieces.push((&tag[start..i], Some((name, value))));
+        text = i;
+    }
+    pieces.push((&tag[text..], None));
+    pieces
+}
+
+/// Decode the character references of an attribute value
+fn unescape(value: &str) -> String {
+    value
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+}
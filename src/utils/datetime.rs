@@ -0,0 +1,387 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const MONTHS: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+/// # A date of the proleptic Gregorian calendar
+///
+/// Dates are formatted and parsed with a format string in which `%Y` is the
+/// year, `%m` the month number, `%d` the day, `%B` the month name and `%b`
+/// its first three letters. Other characters must be matched literally.
+///
+/// ## Example
+///
+/// ```
+/// use neutrino::utils::datetime::Date;
+///
+/// let date = Date::parse_iso("2026-03-05").unwrap();
+/// assert_eq!(date.format("%d/%m/%Y"), "05/03/2026");
+/// assert_eq!(date.format("%B %d, %Y"), "March 05, 2026");
+///
+/// assert_eq!(Date::parse("5/3/2026", "%d/%m/%Y"), Some(date));
+/// assert_eq!(Date::parse("31/02/2026", "%d/%m/%Y"), None);
+///
+/// assert_eq!(date.add_months(-3).iso(), "2025-12-05");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Date {
+    year: i32,
+    month: u32,
+    day: u32,
+}
+
+impl Date {
+    /// Create a Date, returning None if the day does not exist
+    pub fn new(year: i32, month: u32, day: u32) -> Option<Self> {
+        if !(1..=12).contains(&month) || day < 1 {
+            return None;
+        }
+        if day > Date::days_in_month(year, month) {
+            return None;
+        }
+        Some(Self { year, month, day })
+    }
+
+    /// Get the current date, in UTC
+    pub fn today() -> Self {
+        let seconds = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs() as i64)
+            .unwrap_or(0);
+        Date::from_days(seconds.div_euclid(86400))
+    }
+
+    /// Get the year
+    pub fn year(&self) -> i32 {
+        self.year
+    }
+
+    /// Get the month, from 1 to 12
+    pub fn month(&self) -> u32 {
+        self.month
+    }
+
+    /// Get the day of the month, from 1 to 31
+    pub fn day(&self) -> u32 {
+        self.day
+    }
+
+    /// Get the day of the week, from 0 (Monday) to 6 (Sunday)
+    pub fn weekday(&self) -> u32 {
+        (self.days() + 3).rem_euclid(7) as u32
+    }
+
+    /// Return true if the year is a leap year
+    pub fn is_leap_year(year: i32) -> bool {
+        (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+    }
+
+    /// Get the number of days of a month
+    pub fn days_in_month(year: i32, month: u32) -> u32 {
+        match month {
+            2 if Date::is_leap_year(year) => 29,
+            2 => 28,
+            4 | 6 | 9 | 11 => 30,
+            _ => 31,
+        }
+    }
+
+    /// Get the name of a month, from 1 to 12
+    pub fn month_name(month: u32) -> &'static str {
+        MONTHS[(month.clamp(1, 12) - 1) as usize]
+    }
+
+    /// Return the date a number of days later (or earlier if negative)
+    pub fn add_days(&self, days: i64) -> Self {
+        Date::from_days(self.days() + days)
+    }
+
+    /// Return the date a number of months later (or earlier if negative),
+    /// keeping the day within the resulting month
+    pub fn add_months(&self, months: i32) -> Self {
+        let index = self.year * 12 + self.month as i32 - 1 + months;
+        let year = index.div_euclid(12);
+        let month = index.rem_euclid(12) as u32 + 1;
+        let day = self.day.min(Date::days_in_month(year, month));
+        Self { year, month, day }
+    }
+
+    /// Return the date formatted as an ISO 8601 string (YYYY-MM-DD)
+    pub fn iso(&self) -> String {
+        self.format("%Y-%m-%d")
+    }
+
+    /// Parse an ISO 8601 string (YYYY-MM-DD)
+    pub fn parse_iso(value: &str) -> Option<Self> {
+        Date::parse(value, "%Y-%m-%d")
+    }
+
+    /// Return the date formatted with the given format
+    pub fn format(&self, format: &str) -> String {
+        let mut s = String::new();
+        let mut chars = format.chars();
+        while let Some(c) = chars.next() {
+            if c != '%' {
+                s.push(c);
+                continue;
+            }
+            match chars.next() {
+                Some('Y') => s.push_str(&format!("{:04}", self.year)),
+                Some('m') => s.push_str(&format!("{:02}", self.month)),
+                Some('d') => s.push_str(&format!("{:02}", self.day)),
+                Some('B') => s.push_str(Date::month_name(self.month)),
+                Some('b') => s.push_str(&Date::month_name(self.month)[..3]),
+                Some(c) => {
+                    s.push('%');
+                    s.push(c);
+                }
+                None => s.push('%'),
+            }
+        }
+        s
+    }
+
+    /// Parse a string written with the given format
+    pub fn parse(value: &str, format: &str) -> Option<Self> {
+        let fields = scan(value, format)?;
+        let year = field(&fields, &['Y'])?;
+        let month = field(&fields, &['m', 'B', 'b'])?;
+        let day = field(&fields, &['d'])?;
+        Date::new(year as i32, month as u32, day as u32)
+    }
+
+    fn days(&self) -> i64 {
+        let year = if self.month <= 2 {
+            self.year as i64 - 1
+        } else {
+            self.year as i64
+        };
+        let era = year.div_euclid(400);
+        let yoe = year - era * 400;
+        let month = self.month as i64;
+        let mp = if month > 2 { month - 3 } else { month + 9 };
+        let doy = (153 * mp + 2) / 5 + self.day as i64 - 1;
+        let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+        era * 146_097 + doe - 719_468
+    }
+
+    fn from_days(days: i64) -> Self {
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let doe = z - era * 146_097;
+        let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+        let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+        Self {
+            year: year as i32,
+            month,
+            day,
+        }
+    }
+}
+
+/// # A time of the day
+///
+/// Times are formatted and parsed with a format string in which `%H` is the
+/// hour from 00 to 23, `%I` the hour from 01 to 12, `%p` AM or PM, `%M` the
+/// minutes and `%S` the seconds. Other characters must be matched literally.
+///
+/// ## Example
+///
+/// ```
+/// use neutrino::utils::datetime::Time;
+///
+/// let time = Time::parse_iso("14:30").unwrap();
+/// assert_eq!(time.iso(), "14:30:00");
+/// assert_eq!(time.format("%I:%M %p"), "02:30 PM");
+///
+/// assert_eq!(Time::parse("2:30 pm", "%I:%M %p"), Some(time));
+/// assert_eq!(Time::parse("25:00", "%H:%M"), None);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Time {
+    hour: u32,
+    minute: u32,
+    second: u32,
+}
+
+impl Time {
+    /// Create a Time, returning None if it does not exist
+    pub fn new(hour: u32, minute: u32, second: u32) -> Option<Self> {
+        if hour > 23 || minute > 59 || second > 59 {
+            return None;
+        }
+        Some(Self {
+            hour,
+            minute,
+            second,
+        })
+    }
+
+    /// Create a Time from the number of seconds since midnight, wrapping
+    /// around a day
+    pub fn from_seconds(seconds: u32) -> Self {
+        let seconds = seconds % 86400;
+        Self {
+            hour: seconds / 3600,
+            minute: seconds / 60 % 60,
+            second: seconds % 60,
+        }
+    }
+
+    /// Get the hour, from 0 to 23
+    pub fn hour(&self) -> u32 {
+        self.hour
+    }
+
+    /// Get the minutes
+    pub fn minute(&self) -> u32 {
+        self.minute
+    }
+
+    /// Get the seconds
+    pub fn second(&self) -> u32 {
+        self.second
+    }
+
+    /// Get the number of seconds since midnight
+    pub fn seconds(&self) -> u32 {
+        self.hour * 3600 + self.minute * 60 + self.second
+    }
+
+    /// Return the time formatted as an ISO 8601 string (HH:MM:SS)
+    pub fn iso(&self) -> String {
+        self.format("%H:%M:%S")
+    }
+
+    /// Parse an ISO 8601 string (HH:MM:SS or HH:MM)
+    pub fn parse_iso(value: &str) -> Option<Self> {
+        Time::parse(value, "%H:%M:%S").or_else(|| Time::parse(value, "%H:%M"))
+    }
+
+    /// Return the time formatted with the given format
+    pub fn format(&self, format: &str) -> String {
+        let mut s = String::new();
+        let mut chars = format.chars();
+        while let Some(c) = chars.next() {
+            if c != '%' {
+                s.push(c);
+                continue;
+            }
+            match chars.next() {
+                Some('H') => s.push_str(&format!("{:02}", self.hour)),
+                Some('I') => {
+                    let hour = match self.hour % 12 {
+                        0 => 12,
+                        hour => hour,
+                    };
+                    s.push_str(&format!("{:02}", hour));
+                }
+                Some('M') => s.push_str(&format!("{:02}", self.minute)),
+                Some('S') => s.push_str(&format!("{:02}", self.second)),
+                Some('p') => {
+                    s.push_str(if self.hour < 12 { "AM" } else { "PM" })
+                }
+                Some(c) => {
+                    s.push('%');
+                    s.push(c);
+                }
+                None => s.push('%'),
+            }
+        }
+        s
+    }
+
+    /// Parse a string written with the given format
+    pub fn parse(value: &str, format: &str) -> Option<Self> {
+        let fields = scan(value, format)?;
+        let hour = match (field(&fields, &['H']), field(&fields, &['I'])) {
+            (Some(hour), _) => hour,
+            (None, Some(hour)) if (1..=12).contains(&hour) => {
+                hour % 12 + field(&fields, &['p']).unwrap_or(0) * 12
+            }
+            _ => return None,
+        };
+        let minute = field(&fields, &['M'])?;
+        let second = field(&fields, &['S']).unwrap_or(0);
+        Time::new(hour as u32, minute as u32, second as u32)
+    }
+}
+
+fn field(fields: &[(char, i64)], specs: &[char]) -> Option<i64> {
+    fields
+        .iter()
+        .find(|(spec, _)| specs.contains(spec))
+        .map(|(_, value)| *value)
+}
+
+fn prefix_len(value: &str, prefix: &str) -> Option<usize> {
+    match value.get(..prefix.len()) {
+        Some(start) if start.eq_ignore_ascii_case(prefix) => Some(prefix.len()),
+        _ => None,
+    }
+}
+
+fn scan(value: &str, format: &str) -> Option<Vec<(char, i64)>> {
+    let mut fields = vec![];
+    let mut value = value.trim();
+    let mut chars = format.chars();
+    while let Some(c) = chars.next() {
+        if c.is_whitespace() {
+            value = value.trim_start();
+            continue;
+        }
+        if c != '%' {
+            value = value.strip_prefix(c)?;
+            continue;
+        }
+        let spec = chars.next()?;
+        let (number, length) = match spec {
+            'B' | 'b' => MONTHS
+                .iter()
+                .enumerate()
+                .filter_map(|(i, name)| {
+                    prefix_len(value, name)
+                        .or_else(|| prefix_len(value, &name[..3]))
+                        .map(|length| (i as i64 + 1, length))
+                })
+                .next()?,
+            'p' => match (prefix_len(value, "AM"), prefix_len(value, "PM")) {
+                (Some(length), _) => (0, length),
+                (_, Some(length)) => (1, length),
+                _ => return None,
+            },
+            _ => {
+                let width = if spec == 'Y' { 4 } else { 2 };
+                let length = value
+                    .chars()
+                    .take(width)
+                    .take_while(char::is_ascii_digit)
+                    .count();
+                (value[..length].parse::<i64>().ok()?, length)
+            }
+        };
+        fields.push((spec, number));
+        value = &value[length..];
+    }
+    if value.trim().is_empty() {
+        Some(fields)
+    } else {
+        None
+    }
+}
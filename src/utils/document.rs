@@ -0,0 +1,484 @@
+//! # Documents
+//!
+//! A `Document` gathers what an editor does around the file it edits, so
+//! that each application does not glue it together again. It is set with
+//! `App::set_document`, and a `DocumentListener` reads and writes the file.
+//!
+//! * The document is modified when `App::set_modified` is called, and when a
+//!   command of the history of the application is executed, undone or
+//!   redone. Saving it clears the modified flag.
+//! * The title of the window shows the name of the file, with an asterisk
+//!   when the document is modified, such as `*notes.txt - Editor`.
+//! * `App::new_document`, `App::open_document` and `App::quit` ask whether
+//!   to save the modified document first, in a prompt displayed over the
+//!   window: saving, discarding the changes or cancelling. A document
+//!   without a path is saved at the path given by
+//!   `DocumentListener::save_path`, such as the one chosen in a dialog.
+//! * With `Document::set_autosave`, the modified document is saved at the
+//!   given interval if it has a path.
+//! * The files opened and saved are kept as the recent files, most recent
+//!   first, in the file set with `Document::set_recent_file` if any, so that
+//!   a menu can list them.
+//!
+//! Closing the window with its close button is not reported by the webview,
+//! so it cannot be cancelled: an application with a File menu should offer
+//! a Quit function calling `App::quit`, and set an autosave interval.
+//!
+//! The texts of the prompt can be translated with the `document.unsaved`,
+//! `document.save`, `document.discard` and `document.cancel` keys (see
+//! `i18n`), the first one taking the `name` of the file.
+//!
+//! ## Example
+//!
+//! ```
+//! use std::cell::RefCell;
+//! use std::path::Path;
+//! use std::rc::Rc;
+//!
+//! use neutrino::testing::Harness;
+//! use neutrino::utils::document::{Document, DocumentListener};
+//! use neutrino::{App, Window};
+//!
+//!
+//! struct MyDocumentListener {
+//!     saved: Rc<RefCell<Vec<String>>>,
+//! }
+//!
+//! impl DocumentListener for MyDocumentListener {
+//!     fn on_new(&self) {}
+//!
+//!     fn on_open(&self, _path: &Path) -> Result<(), String> {
+//!         Ok(())
+//!     }
+//!
+//!     fn on_save(&self, path: &Path) -> Result<(), String> {
+//!         self.saved.borrow_mut().push(path.display().to_string());
+//!         Ok(())
+//!     }
+//! }
+//!
+//!
+//! let saved = Rc::new(RefCell::new(vec![]));
+//!
+//! let mut my_document = Document::new();
+//! my_document.set_listener(Box::new(MyDocumentListener { saved: saved.clone() }));
+//! App::set_document(my_document);
+//!
+//! let mut my_window = Window::new();
+//! my_window.set_title("Editor");
+//!
+//! let mut harness = Harness::new(my_window);
+//! App::open_document("notes.txt");
+//! App::set_modified();
+//! assert_eq!(harness.window().title(), "*notes.txt - Editor");
+//!
+//! App::new_document();
+//! assert!(harness.contains("Save the changes to notes.txt?"));
+//!
+//! harness.change("document", "save");
+//! assert_eq!(*saved.borrow(), vec!["notes.txt"]);
+//! assert_eq!(harness.window().title(), "Untitled - Editor");
+//! assert_eq!(App::recent_documents()[0].to_str(), Some("notes.txt"));
+//! ```
+
+use std::cell::{Cell, RefCell};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::thread;
+use std::time::Duration;
+
+use crate::backend::Proxy;
+use crate::utils::event::{self, Event};
+use crate::utils::html::escape;
+use crate::utils::i18n;
+
+/// The name of the prompt, used as the source of its events
+const PROMPT: &str = "document";
+
+/// The default number of recent files
+const RECENT_LIMIT: usize = 10;
+
+thread_local! {
+    static DOCUMENT: RefCell<Option<Document>> = const { RefCell::new(None) };
+    static QUITTING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// # The listener of a Document
+pub trait DocumentListener {
+    /// Function triggered to start a new document
+    fn on_new(&self);
+
+    /// Function triggered to read the document at the given path
+    fn on_open(&self, path: &Path) -> Result<(), String>;
+
+    /// Function triggered to write the document at the given path
+    fn on_save(&self, path: &Path) -> Result<(), String>;
+
+    /// Function returning the path where a document without one is saved,
+    /// None to cancel the save
+    fn save_path(&self) -> Option<PathBuf> {
+        None
+    }
+}
+
+/// # An action waiting for the modified document to be saved
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Action {
+    New,
+    Open(PathBuf),
+    Quit,
+}
+
+/// # The document edited by the application
+///
+/// ## Fields
+///
+/// ```text
+/// path: Option<PathBuf>
+/// modified: bool
+/// autosave: Option<Duration>
+/// recent: Vec<PathBuf>
+/// recent_limit: usize
+/// recent_file: Option<PathBuf>
+/// pending: Option<Action>
+/// listener: Option<Rc<dyn DocumentListener>>
+/// ```
+///
+/// ## Default values
+///
+/// ```text
+/// path: None
+/// modified: false
+/// autosave: None
+/// recent: vec![]
+/// recent_limit: 10
+/// recent_file: None
+/// pending: None
+/// listener: None
+/// ```
+pub struct Document {
+    path: Option<PathBuf>,
+    modified: bool,
+    autosave: Option<Duration>,
+    recent: Vec<PathBuf>,
+    recent_limit: usize,
+    recent_file: Option<PathBuf>,
+    pending: Option<Action>,
+    listener: Option<Rc<dyn DocumentListener>>,
+}
+
+impl Document {
+    /// Create a Document
+    pub fn new() -> Self {
+        Self {
+            path: None,
+            modified: false,
+            autosave: None,
+            recent: vec![],
+            recent_limit: RECENT_LIMIT,
+            recent_file: None,
+            pending: None,
+            listener: None,
+        }
+    }
+
+    /// Get the path, None for a new document
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// Get the name of the file, `Untitled` for a new document
+    pub fn name(&self) -> String {
+        self.path
+            .as_ref()
+            .and_then(|path| path.file_name())
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| "Untitled".to_string())
+    }
+
+    /// Get the modified flag
+    pub fn modified(&self) -> bool {
+        self.modified
+    }
+
+    /// Get the autosave interval
+    pub fn autosave(&self) -> Option<Duration> {
+        self.autosave
+    }
+
+    /// Get the recent files, most recent first
+    pub fn recent(&self) -> &Vec<PathBuf> {
+        &self.recent
+    }
+
+    /// Get the action waiting for the modified document to be saved
+    pub fn pending(&self) -> Option<&Action> {
+        self.pending.as_ref()
+    }
+
+    /// Set the path
+    pub fn set_path<P: AsRef<Path>>(&mut self, path: P) {
+        self.path = Some(path.as_ref().to_path_buf());
+    }
+
+    /// Set the interval at which the modified document is saved
+    pub fn set_autosave(&mut self, interval: Duration) {
+        self.autosave = Some(interval);
+    }
+
+    /// Set the maximum number of recent files
+    pub fn set_recent_limit(&mut self, limit: usize) {
+        self.recent_limit = limit;
+        self.recent.truncate(limit);
+    }
+
+    /// Set the file keeping the recent files, one per line, and read them
+    /// from it if it exists
+    pub fn set_recent_file<P: AsRef<Path>>(&mut self, path: P) {
+        let path = path.as_ref().to_path_buf();
+        if let Ok(content) = fs::read_to_string(&path) {
+            self.recent = content
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .map(PathBuf::from)
+                .take(self.recent_limit)
+                .collect();
+        }
+        self.recent_file = Some(path);
+    }
+
+    /// Set the listener
+    pub fn set_listener(&mut self, listener: Box<dyn DocumentListener>) {
+        self.listener = Some(Rc::from(listener));
+    }
+
+    /// Put a file at the top of the recent files and write them into the
+    /// recent file if any
+    fn add_recent(&mut self, path: &Path) {
+        self.recent.retain(|recent| recent != path);
+        self.recent.insert(0, path.to_path_buf());
+        self.recent.truncate(self.recent_limit);
+        if let Some(file) = &self.recent_file {
+            let content = self
+                .recent
+                .iter()
+                .map(|path| format!("{}\n", path.display()))
+                .collect::<String>();
+            if let Err(error) = fs::write(file, content) {
+                event::report_error(
+                    PROMPT,
+                    &format!("{}: {}", file.display(), error),
+                );
+            }
+        }
+    }
+}
+
+impl Default for Document {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Set the document of the application
+pub(crate) fn set(document: Document) {
+    DOCUMENT.with(|current| *current.borrow_mut() = Some(document));
+}
+
+/// Run a function on the document of the application, None if there is
+/// none. The listener must not be called from the function.
+pub(crate) fn with<R>(f: impl FnOnce(&mut Document) -> R) -> Option<R> {
+    DOCUMENT.with(|document| document.borrow_mut().as_mut().map(f))
+}
+
+/// Get the listener of the document of the application
+fn listener() -> Option<Rc<dyn DocumentListener>> {
+    with(|document| document.listener.clone()).flatten()
+}
+
+/// Set the modified flag of the document of the application
+pub(crate) fn set_modified(modified: bool) {
+    with(|document| document.modified = modified);
+}
+
+/// Get the title of a window with the given title, showing the name of the
+/// document of the application
+pub(crate) fn title(window: &str) -> String {
+    with(|document| {
+        let modified = if document.modified { "*" } else { "" };
+        format!("{}{} - {}", modified, document.name(), window)
+    })
+    .unwrap_or_else(|| window.to_string())
+}
+
+/// Run an action, once the modified document of the application, if any,
+/// has been saved or its changes discarded in the prompt
+pub(crate) fn request(action: Action) {
+    let modified = with(|document| {
+        if document.modified {
+            document.pending = Some(action.clone());
+        }
+        document.modified
+    });
+    if modified != Some(true) {
+        run(action);
+    }
+}
+
+/// Run an action without prompting
+fn run(action: Action) {
+    if action == Action::Quit {
+        QUITTING.with(|quitting| quitting.set(true));
+        return;
+    }
+    let listener = match listener() {
+        Some(listener) => listener,
+        None => return,
+    };
+    match action {
+        Action::New => {
+            listener.on_new();
+            with(|document| {
+                document.path = None;
+                document.modified = false;
+            });
+        }
+        Action::Open(path) => match listener.on_open(&path) {
+            Ok(()) => {
+                with(|document| {
+                    document.add_recent(&path);
+                    document.path = Some(path);
+                    document.modified = false;
+                });
+            }
+            Err(error) => event::report_error(PROMPT, &error),
+        },
+        Action::Quit => (),
+    }
+}
+
+/// Save the document of the application at its path, or at the path given
+/// by the listener if it has none
+pub(crate) fn save() -> Result<(), String> {
+    let listener = listener().ok_or("the document has no listener")?;
+    let path = match with(|document| document.path.clone()).flatten() {
+        Some(path) => path,
+        None => listener.save_path().ok_or("the save was cancelled")?,
+    };
+    save_as(&path)
+}
+
+/// Save the document of the application at the given path
+pub(crate) fn save_as(path: &Path) -> Result<(), String> {
+    let listener = listener().ok_or("the document has no listener")?;
+    listener.on_save(path)?;
+    with(|document| {
+        document.add_recent(path);
+        document.path = Some(path.to_path_buf());
+        document.modified = false;
+    });
+    Ok(())
+}
+
+/// Save the modified document of the application if it has a path,
+/// reporting the errors
+pub(crate) fn autosave() {
+    let due = with(|document| document.modified && document.path.is_some());
+    if due == Some(true) {
+        if let Err(error) = save() {
+            event::report_error(PROMPT, &error);
+        }
+    }
+}
+
+/// Send an autosave event at the interval of the document of the
+/// application, in a thread
+pub(crate) fn start_autosave(proxy: Proxy) {
+    let interval = match with(|document| document.autosave).flatten() {
+        Some(interval) => interval,
+        None => return,
+    };
+    thread::spawn(move || loop {
+        thread::sleep(interval);
+        let js = r#"emit({"type":"Autosave"});"#;
+        if !proxy.eval(js) {
+            return;
+        }
+    });
+}
+
+/// Return true once the application has been asked to quit
+pub(crate) fn quitting() -> bool {
+    QUITTING.with(Cell::get)
+}
+
+/// Handle the choice made in the prompt
+pub(crate) fn trigger(event: &Event) {
+    let choice = match event {
+        Event::Change { source, value } if source == PROMPT => value.as_str(),
+        Event::Dismiss { source } if source == PROMPT => "cancel",
+        _ => return,
+    };
+    let action = match with(|document| document.pending.take()).flatten() {
+        Some(action) => action,
+        None => return,
+    };
+    match choice {
+        "save" => match save() {
+            Ok(()) => run(action),
+            Err(error) => event::report_error(PROMPT, &error),
+        },
+        "discard" => {
+            set_modified(false);
+            run(action);
+        }
+        _ => (),
+    }
+}
+
+/// Return the HTML representation of the prompt, empty if no action is
+/// waiting
+pub(crate) fn eval() -> String {
+    let name = match with(|document| {
+        document.pending.as_ref().map(|_| document.name())
+    })
+    .flatten()
+    {
+        Some(name) => name,
+        None => return "".to_string(),
+    };
+    let button = |choice: &str, text: String| {
+        format!(
+            r#"<div class="saveprompt-button saveprompt-{}" role="button" tabindex="0" onmousedown="{}" onkeydown="{}">{}</div>"#,
+            choice,
+            Event::change_js(PROMPT, &format!("'{}'", choice)),
+            Event::keys_js(
+                PROMPT,
+                &[("Enter", choice.to_string()), (" ", choice.to_string())]
+            ),
+            escape(&text),
+        )
+    };
+    format!(
+        r#"<div id="{}" class="saveprompt" data-overlay="{}" data-modal="true" role="alertdialog" aria-modal="true" aria-labelledby="{}-message"><div class="saveprompt-dialog"><div id="{}-message" class="saveprompt-message">{}</div><div class="saveprompt-buttons">{}{}{}</div></div></div>"#,
+        PROMPT,
+        PROMPT,
+        PROMPT,
+        PROMPT,
+        escape(&i18n::translate_or(
+            "document.unsaved",
+            "Save the changes to {name}?",
+            &[("name", &name)],
+        )),
+        button("save", i18n::translate_or("document.save", "Save", &[])),
+        button(
+            "discard",
+            i18n::translate_or("document.discard", "Don't save", &[])
+        ),
+        button(
+            "cancel",
+            i18n::translate_or("document.cancel", "Cancel", &[])
+        ),
+    )
+}
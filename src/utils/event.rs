@@ -1,38 +1,570 @@
+use std::cell::RefCell;
+
+use json::JsonValue;
+
+use crate::utils::clipboard::ClipboardData;
+use crate::utils::csp;
+use crate::utils::geometry::{self, Elements};
+use crate::utils::instance;
+use crate::utils::launch::Launch;
+use crate::utils::monitor::Monitor;
+use crate::utils::updater::Release;
+
+/// The types of the events which are not sent by a widget
+const GLOBAL_EVENTS: [&str; 13] = [
+    "Update",
+    "Key",
+    "Resize",
+    "Geometry",
+    "Monitors",
+    "Custom",
+    "Instance",
+    "Autosave",
+    "Network",
+    "Job",
+    "Observed",
+    "Tray",
+    "Undefined",
+];
+
+thread_local! {
+    static ERRORS: RefCell<Vec<Event>> = const { RefCell::new(Vec::new()) };
+}
+
 /// # An equivalent of Javascript events
+///
+/// The custom event is sent by the javascript of the application, such as
+/// the one of a plugin, with `neutrino.emit(name, value)`, the value being
+/// any value which can be written in JSON.
+///
+/// The launch event is not sent by the page: it is sent to the window with
+/// the arguments of the application after the first render.
+///
+/// The restore event is not sent by the page: it is sent to the window
+/// before the first render when the application has settings, so that the
+/// window and the widgets restore their state (see the `settings` module).
+///
+/// The error event is sent to the window when a message of the page cannot
+/// be read, or when a widget reports a value it cannot handle with
+/// `report_error`. The threads of the framework, such as the one of the
+/// single instance guard, ask the page to send it.
+///
+/// The update available event is not sent by the page: the window processes
+/// it when the thread of its updater hands it a newer release (see
+/// `updater`).
+///
+/// The tray event is not sent by the page: it is sent by the backend when
+/// the icon of the tray or an item of its menu is clicked, with the `tray`
+/// feature (see the `tray` module).
 #[derive(Debug)]
 pub enum Event {
     Undefined,
     Update,
     Change { source: String, value: String },
     Key { key: Key },
+    CanvasClick { source: String, x: f64, y: f64 },
+    Focus { source: String },
+    Blur { source: String },
+    Hover { source: String, x: f64, y: f64 },
+    DoubleClick { source: String, button: MouseButton, x: f64, y: f64 },
+    MouseUp { source: String, button: MouseButton, x: f64, y: f64 },
+    Dismiss { source: String },
+    Resize { width: i32, height: i32 },
+    AnimationEnd { source: String, name: String },
+    Paste { source: String, data: ClipboardData },
+    Geometry { pointer: Option<(f64, f64)>, bounds: Option<Elements> },
+    Monitors { monitors: Vec<Monitor> },
+    Custom { name: String, value: JsonValue },
+    UpdateAvailable { release: Release },
+    Instance { args: Vec<String> },
+    Autosave,
+    Network { online: bool },
+    Job { id: u64, error: Option<String> },
+    Observed { id: u64 },
+    #[cfg(feature = "tray")]
+    Tray { item: Option<String> },
+    Launch { launch: Launch },
+    Restore,
+    Error { source: String, message: String },
 }
 
 impl Event {
+    /// Read an event from a message of the page, returning an error event if
+    /// the message is malformed
+    pub(crate) fn from_json(value: &JsonValue) -> Self {
+        let kind = match value["type"].as_str() {
+            Some(kind) => kind,
+            None => return invalid(value, "the type is missing"),
+        };
+        let source = match value["source"].as_str() {
+            Some(source) => source.to_string(),
+            None if GLOBAL_EVENTS.contains(&kind) => "".to_string(),
+            None => return invalid(value, "the source is missing"),
+        };
+        let x = value["x"].as_f64().unwrap_or(0.0);
+        let y = value["y"].as_f64().unwrap_or(0.0);
+        let button = || MouseButton::new(value["button"].as_i64().unwrap_or(0));
+        match kind {
+            "Update" => Event::Update,
+            "Key" => match value["key"].as_str().and_then(Key::new) {
+                Some(key) => Event::Key { key },
+                None => Event::Undefined,
+            },
+            "Change" => match value["value"].as_str() {
+                Some(v) => Event::Change {
+                    source,
+                    value: v.to_string(),
+                },
+                None => invalid(value, "the value is missing"),
+            },
+            "CanvasClick" => Event::CanvasClick { source, x, y },
+            "Focus" => Event::Focus { source },
+            "Blur" => Event::Blur { source },
+            "Dismiss" => Event::Dismiss { source },
+            "Hover" => Event::Hover { source, x, y },
+            "AnimationEnd" => match value["name"].as_str() {
+                Some(name) => Event::AnimationEnd {
+                    source,
+                    name: name.to_string(),
+                },
+                None => invalid(value, "the name is missing"),
+            },
+            "Paste" => Event::Paste {
+                source,
+                data: ClipboardData::from_json(value),
+            },
+            "Geometry" => Event::Geometry {
+                pointer: geometry::pointer(value),
+                bounds: geometry::bounds(value),
+            },
+            "Custom" => match value["name"].as_str() {
+                Some(name) => Event::Custom {
+                    name: name.to_string(),
+                    value: value["value"].clone(),
+                },
+                None => invalid(value, "the name is missing"),
+            },
+            "Instance" => Event::Instance {
+                args: instance::args(value),
+            },
+            "Autosave" => Event::Autosave,
+            "Network" => Event::Network {
+                online: value["online"].as_bool().unwrap_or(true),
+            },
+            "Job" => match value["id"].as_u64() {
+                Some(id) => Event::Job {
+                    id,
+                    error: value["error"].as_str().map(str::to_string),
+                },
+                None => invalid(value, "the id is missing"),
+            },
+            "Error" => Event::Error {
+                source,
+                message: value["message"].as_str().unwrap_or("").to_string(),
+            },
+            #[cfg(feature = "tray")]
+            "Tray" => Event::Tray {
+                item: value["item"].as_str().map(str::to_string),
+            },
+            "Observed" => match value["id"].as_u64() {
+                Some(id) => Event::Observed { id },
+                None => invalid(value, "the id is missing"),
+            },
+            "Monitors" => Event::Monitors {
+                monitors: Monitor::from_json(value),
+            },
+            "Resize" => Event::Resize {
+                width: value["width"].as_i32().unwrap_or(0),
+                height: value["height"].as_i32().unwrap_or(0),
+            },
+            "DoubleClick" => match button() {
+                Some(button) => Event::DoubleClick {
+                    source,
+                    button,
+                    x,
+                    y,
+                },
+                None => Event::Undefined,
+            },
+            "MouseUp" => match button() {
+                Some(button) => Event::MouseUp {
+                    source,
+                    button,
+                    x,
+                    y,
+                },
+                None => Event::Undefined,
+            },
+            _ => Event::Undefined,
+        }
+    }
+
+    /// Get the name of the widget an event is sent to, None if the event is
+    /// sent to the whole widget tree. Only this widget and the widgets
+    /// holding it see the event.
+    pub fn target(&self) -> Option<&str> {
+        match self {
+            Event::Change { source, .. }
+            | Event::CanvasClick { source, .. }
+            | Event::Hover { source, .. }
+            | Event::DoubleClick { source, .. }
+            | Event::MouseUp { source, .. }
+            | Event::Dismiss { source } => Some(source),
+            _ => None,
+        }
+    }
+
     /// Return an one-line function sending a change event from javascript
     pub fn change_js(source: &str, value: &str) -> String {
-        format!(
+        handler(format!(
             r#"(function(){{ emit( {{ type: 'Change', source: '{}', value: {} }} ); event.stopPropagation(); }})()"#,
-            source, value
-        )
+            quote(source),
+            value
+        ))
+    }
+
+    /// Return an one-line function sending a change event from javascript
+    /// on input, held back while an IME composition is in progress and sent
+    /// once the composed text is committed
+    pub fn input_js(source: &str, value: &str) -> String {
+        handler(format!(
+            r#"(function(e){{ if (!composing(e)) {{ emit( {{ type: 'Change', source: '{}', value: {} }} ); }} event.stopPropagation(); }})(event.currentTarget)"#,
+            quote(source),
+            value
+        ))
     }
 
     /// Return an one-line function sending a key event from javascript
     pub fn key_js() -> String {
-        r#"(function() { if (event.ctrlKey && event.key !== 'Control') { emit( { type: 'Key', key: event.key } ); } event.stopPropagation(); } )()"#
-            .to_string()
+        handler(
+            r#"(function() { if (event.ctrlKey && event.key !== 'Control') { emit( { type: 'Key', key: event.key } ); } event.stopPropagation(); } )()"#
+                .to_string(),
+        )
+    }
+
+    /// Return an one-line function sending a canvas click event from
+    /// javascript, with the coordinates relative to the canvas
+    pub fn canvas_click_js(source: &str) -> String {
+        handler(format!(
+            r#"(function(e){{ emit( {{ type: 'CanvasClick', source: '{}', x: event.offsetX, y: event.offsetY }} ); marquee(e, event); event.stopPropagation(); }})(event.currentTarget)"#,
+            quote(source)
+        ))
+    }
+
+    /// Return an one-line function sending a `load-more` change event from
+    /// javascript when a scrollable element is scrolled near its bottom
+    pub fn load_more_js(source: &str) -> String {
+        handler(format!(
+            r#"(function(e){{ if (e.getAttribute('data-loading') !== 'true' && e.scrollTop + e.clientHeight >= e.scrollHeight - 40) {{ e.setAttribute('data-loading', 'true'); emit( {{ type: 'Change', source: '{}', value: 'load-more' }} ); }} }})(event.currentTarget)"#,
+            quote(source)
+        ))
+    }
+
+    /// Return an one-line function sending the value of a range input, after
+    /// the given prefix, and moving its value bubble along
+    pub fn range_js(source: &str, prefix: &str) -> String {
+        handler(format!(
+            r#"(function(e){{ emit( {{ type: 'Change', source: '{}', value: '{}' + rangeInput(e) }} ); }})(event.currentTarget)"#,
+            quote(source),
+            quote(prefix)
+        ))
+    }
+
+    /// Return an one-line function starting a pull-to-refresh gesture
+    pub fn pull_start_js() -> String {
+        handler(
+            r#"(function(e){ pullStart(e, event); })(event.currentTarget)"#
+                .to_string(),
+        )
+    }
+
+    /// Return an one-line function following a pull-to-refresh gesture
+    pub fn pull_move_js() -> String {
+        handler(
+            r#"(function(e){ pullMove(e, event); })(event.currentTarget)"#
+                .to_string(),
+        )
+    }
+
+    /// Return an one-line function ending a pull-to-refresh gesture and
+    /// sending a `refresh` change event from javascript if the element has
+    /// been pulled far enough
+    pub fn pull_end_js(source: &str) -> String {
+        handler(format!(
+            r#"(function(e){{ if (pullEnd(e)) {{ emit( {{ type: 'Change', source: '{}', value: 'refresh' }} ); }} }})(event.currentTarget)"#,
+            quote(source)
+        ))
+    }
+
+    /// Return an one-line function centering the target viewport of a
+    /// minimap on the pointed location and sending its new transform as a
+    /// change event of the viewport
+    pub fn minimap_navigate_js() -> String {
+        handler(
+            r#"(function(e){ minimapNavigate(e, event); event.stopPropagation(); })(event.currentTarget)"#
+                .to_string(),
+        )
+    }
+
+    /// Return an one-line function sending a hover event from javascript,
+    /// with the coordinates relative to the element. The events are sent at
+    /// most every 50 milliseconds.
+    pub fn hover_js(source: &str) -> String {
+        handler(format!(
+            r#"(function(e){{ hover('{}', e, event); }})(event.currentTarget)"#,
+            quote(source)
+        ))
+    }
+
+    /// Return an one-line function sending a double click event from
+    /// javascript, with the button and the coordinates relative to the element
+    pub fn double_click_js(source: &str) -> String {
+        handler(format!(
+            r#"(function(e){{ mouse('DoubleClick', '{}', e, event); event.stopPropagation(); }})(event.currentTarget)"#,
+            quote(source)
+        ))
+    }
+
+    /// Return an one-line function sending a mouse up event from javascript,
+    /// with the button and the coordinates relative to the element
+    pub fn mouse_up_js(source: &str) -> String {
+        handler(format!(
+            r#"(function(e){{ mouse('MouseUp', '{}', e, event); event.stopPropagation(); }})(event.currentTarget)"#,
+            quote(source)
+        ))
+    }
+
+    /// Return an one-line function dragging a guide of a canvas, or creating
+    /// one if no index is given, and sending the new guides as a `guides:`
+    /// change event of the canvas from javascript
+    pub fn guide_drag_js(orientation: &str, index: Option<usize>) -> String {
+        let index = match index {
+            Some(index) => index as i64,
+            None => -1,
+        };
+        handler(format!(
+            r#"(function(e){{ guideDrag(e.closest('.canvas'), event, '{}', {}); event.stopPropagation(); }})(event.currentTarget)"#,
+            orientation, index
+        ))
+    }
+
+    /// Return an one-line function applying a mask to the value of an input
+    pub fn mask_js(mask: &str) -> String {
+        handler(format!(
+            r#"(function(e){{ if (!composing(e)) {{ applyMask(e, '{}'); }} }})(event.currentTarget)"#,
+            quote(mask)
+        ))
+    }
+
+    /// Return an one-line function masking the value of a text input and
+    /// queueing a change event from javascript on input, sent right away,
+    /// once the typing pauses or at most once per delay depending on the
+    /// mode, except while an IME composition is in progress
+    pub fn queue_change_js(
+        source: &str,
+        mask: Option<&str>,
+        mode: &str,
+        delay: u32,
+    ) -> String {
+        let mask = match mask {
+            Some(mask) => format!("'{}'", quote(mask)),
+            None => "null".to_string(),
+        };
+        handler(format!(
+            r#"(function(e){{ if (!composing(e)) {{ queueChange(e, '{}', {}, '{}', {}); }} event.stopPropagation(); }})(event.currentTarget)"#,
+            quote(source),
+            mask,
+            mode,
+            delay
+        ))
+    }
+
+    /// Return an one-line function sending the queued change event of a
+    /// text input from javascript, if its value was not sent yet
+    pub fn send_change_js(source: &str) -> String {
+        handler(format!(
+            r#"(function(e){{ sendChange(e, '{}'); event.stopPropagation(); }})(event.currentTarget)"#,
+            quote(source)
+        ))
+    }
+
+    /// Return an one-line function sending the navigation keys of an
+    /// autocomplete input as `key:` change events from javascript, except
+    /// while an IME composition is in progress
+    pub fn autocomplete_key_js(source: &str) -> String {
+        handler(format!(
+            r#"(function(){{ if (composingKey(event)) {{ return; }} let keys = {{ ArrowDown: 'down', ArrowUp: 'up', Enter: 'enter', Escape: 'escape' }}; if (keys[event.key] !== undefined) {{ event.preventDefault(); emit( {{ type: 'Change', source: '{}', value: 'key:' + keys[event.key] }} ); event.stopPropagation(); }} }})()"#,
+            quote(source)
+        ))
+    }
+
+    /// Return an one-line function sending the given keys, such as `Enter`,
+    /// `" "` for the space bar or `ArrowDown`, as change events with the
+    /// value associated with the key from javascript, except while an IME
+    /// composition is in progress
+    pub fn keys_js(source: &str, keys: &[(&str, String)]) -> String {
+        let keys = keys
+            .iter()
+            .map(|(key, value)| format!("'{}': '{}'", quote(key), quote(value)))
+            .collect::<Vec<String>>()
+            .join(", ");
+        handler(format!(
+            r#"(function(){{ if (composingKey(event) || event.ctrlKey || event.altKey || event.metaKey) {{ return; }} let keys = {{ {} }}; if (keys[event.key] !== undefined) {{ event.preventDefault(); emit( {{ type: 'Change', source: '{}', value: keys[event.key] }} ); event.stopPropagation(); }} }})()"#,
+            keys,
+            quote(source)
+        ))
+    }
+
+    /// Return an one-line function moving a draggable element and sending
+    /// its new position as a change event from javascript
+    pub fn drag_js() -> String {
+        handler(
+            r#"(function(e){ dragStart(e.closest('.draggable'), event); })(event.currentTarget)"#
+                .to_string(),
+        )
+    }
+
+    /// Return an one-line function selecting a tab, and moving it by
+    /// dragging its title as a `move:` change event from javascript
+    pub fn tab_drag_js(source: &str, index: usize) -> String {
+        handler(format!(
+            r#"(function(e){{ tabDragStart(e, event, '{}', {}); }})(event.currentTarget)"#,
+            quote(source),
+            index
+        ))
+    }
+
+    /// Return an one-line function sending the Up and Down keys of a spin box
+    /// as `increment` and `decrement` change events from javascript, except
+    /// while an IME composition is in progress
+    pub fn spin_key_js(source: &str) -> String {
+        handler(format!(
+            r#"(function(){{ if (composingKey(event)) {{ return; }} let keys = {{ ArrowUp: 'increment', ArrowDown: 'decrement' }}; if (keys[event.key] !== undefined) {{ event.preventDefault(); emit( {{ type: 'Change', source: '{}', value: keys[event.key] }} ); event.stopPropagation(); }} }})()"#,
+            quote(source)
+        ))
+    }
+
+    /// Return an one-line function resizing a container by dragging one of its
+    /// edges and sending its new size as a `resize:` change event from
+    /// javascript
+    pub fn resize_js(edge: &str, min: f64, max: f64) -> String {
+        let max = if max.is_finite() {
+            max.to_string()
+        } else {
+            "Infinity".to_string()
+        };
+        handler(format!(
+            r#"(function(e){{ resizeStart(e.parentNode, event, '{}', {}, {}); event.stopPropagation(); }})(event.currentTarget)"#,
+            edge, min, max
+        ))
+    }
+
+    /// Return an one-line function sending a change event from javascript
+    /// when the Enter key is pressed, unless it commits an IME composition
+    pub fn enter_js(source: &str, value: &str) -> String {
+        handler(format!(
+            r#"(function(){{ if (event.key === 'Enter' && !composingKey(event)) {{ event.preventDefault(); emit( {{ type: 'Change', source: '{}', value: {} }} ); event.stopPropagation(); }} }})()"#,
+            quote(source),
+            value
+        ))
+    }
+
+    /// Return an one-line function stopping the propagation of the event
+    pub fn stop_js() -> String {
+        handler(r#"(function() { event.stopPropagation(); } )()"#.to_string())
+    }
+
+    /// Return an one-line function preventing the default behavior of the
+    /// event
+    pub fn prevent_default_js() -> String {
+        handler(r#"(function() { event.preventDefault(); } )()"#.to_string())
     }
 
     /// Return an one-line function sending a undefined event from javascript
     pub fn undefined_js() -> String {
-        r#"(function() { emit( { type: 'Undefined' } ); event.stopPropagation(); } )()"#
-            .to_string()
+        handler(
+            r#"(function() { emit( { type: 'Undefined' } ); event.stopPropagation(); } )()"#
+                .to_string(),
+        )
+    }
+}
+
+/// Report a value a widget cannot handle, such as an index which is not a
+/// number. The error is sent to the window as an `Event::Error` once the
+/// current event has been processed, the widget keeping its state.
+pub fn report_error(source: &str, message: &str) {
+    ERRORS.with(|errors| {
+        errors.borrow_mut().push(Event::Error {
+            source: source.to_string(),
+            message: message.to_string(),
+        })
+    });
+}
+
+/// Return the javascript asking the page to send an error event, for the
+/// errors of the threads which cannot call `report_error`
+pub(crate) fn error_js(source: &str, message: &str) -> String {
+    let mut event = JsonValue::new_object();
+    event["type"] = "Error".into();
+    event["source"] = source.into();
+    event["message"] = message.into();
+    format!("emit({});", event.dump())
+}
+
+/// Take the errors reported since the last call
+pub(crate) fn take_errors() -> Vec<Event> {
+    ERRORS.with(|errors| errors.borrow_mut().drain(..).collect())
+}
+
+/// Return an error event for a malformed message of the page
+fn invalid(value: &JsonValue, reason: &str) -> Event {
+    Event::Error {
+        source: value["source"].as_str().unwrap_or("").to_string(),
+        message: format!("invalid event {}: {}", value.dump(), reason),
+    }
+}
+
+/// Record a handler, so that it is kept in a page with a strict
+/// content-security policy
+fn handler(js: String) -> String {
+    csp::allow(&js);
+    js
+}
+
+/// Escape a string written between single quotes in a handler
+fn quote(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace('\'', "\\'")
+        .replace('&', "&amp;")
+        .replace('"', "&quot;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// # An enum holding a mouse button
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MouseButton {
+    Left,
+    Middle,
+    Right,
+}
+
+impl MouseButton {
+    /// Return the MouseButton corresponding with the javascript button number
+    pub fn new(button: i64) -> Option<Self> {
+        match button {
+            0 => Some(MouseButton::Left),
+            1 => Some(MouseButton::Middle),
+            2 => Some(MouseButton::Right),
+            _ => None,
+        }
     }
 }
 
 /// # An enum holding a keyboard key
 ///
 /// The key event is triggered with `Ctrl + Key`.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Key {
     A,
     B,
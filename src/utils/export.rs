@@ -0,0 +1,99 @@
+//! # Export and print
+//!
+//! `App::export_html` writes the window as it is displayed into a static
+//! HTML page: the menubar and the widget tree, with the theme and the custom
+//! CSS, and without the scripts, so that a report shown on screen can be
+//! archived, sent, or opened in a browser. The page is written once the
+//! current event has been processed and the widgets updated, and an error
+//! is sent to the window if it cannot be written. A canvas, painted by the
+//! scripts, is exported empty.
+//!
+//! `App::print` opens the print dialog of the webview once the page has
+//! been rendered, from which the window can be printed, or saved as a PDF
+//! file where the system offers it. The menubar, the toolbars, the status
+//! bars and the overlays are not printed, and a widget can be left out with
+//! a custom CSS rule in a `@media print` block.
+//!
+//! ## Example
+//!
+//! ```
+//! use neutrino::testing::Harness;
+//! use neutrino::widgets::button::{Button, ButtonListener, ButtonState};
+//! use neutrino::widgets::container::Container;
+//! use neutrino::widgets::label::Label;
+//! use neutrino::{App, Window};
+//!
+//!
+//! struct ExportListener;
+//!
+//! impl ButtonListener for ExportListener {
+//!     fn on_change(&self, _state: &ButtonState) {
+//!         let path = std::env::temp_dir().join("neutrino-report.html");
+//!         App::export_html(path);
+//!         App::print();
+//!     }
+//!
+//!     fn on_update(&self, _state: &mut ButtonState) {}
+//! }
+//!
+//!
+//! let mut total = Label::new("total");
+//! total.set_text("Total: 1 024 kg");
+//!
+//! let mut export = Button::new("export");
+//! export.set_listener(Box::new(ExportListener));
+//!
+//! let mut report = Container::new("report");
+//! report.add(Box::new(total));
+//! report.add(Box::new(export));
+//!
+//! let mut my_window = Window::new();
+//! my_window.set_child(Box::new(report));
+//!
+//! let mut harness = Harness::new(my_window);
+//! harness.change("export", "");
+//! assert!(harness.printed());
+//!
+//! let path = std::env::temp_dir().join("neutrino-report.html");
+//! let page = std::fs::read_to_string(path).unwrap();
+//! assert!(page.contains("Total: 1 024 kg"));
+//! assert!(!page.contains("<script"));
+//! ```
+
+use std::cell::{Cell, RefCell};
+use std::path::PathBuf;
+
+thread_local! {
+    static EXPORTS: RefCell<Vec<PathBuf>> = const { RefCell::new(Vec::new()) };
+    static PRINT: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Queue the export of the window into a static HTML page
+pub(crate) fn export_html(path: PathBuf) {
+    EXPORTS.with(|exports| exports.borrow_mut().push(path));
+}
+
+/// Take the paths of the exports queued since the last event
+pub(crate) fn take() -> Vec<PathBuf> {
+    EXPORTS.with(|exports| exports.borrow_mut().drain(..).collect())
+}
+
+/// Ask for the print dialog to be opened once the page has been rendered
+pub(crate) fn print() {
+    PRINT.with(|print| print.set(true));
+}
+
+/// Return true if the print dialog was asked for since the last render,
+/// clearing the request
+pub(crate) fn take_print() -> bool {
+    PRINT.with(|print| print.replace(false))
+}
+
+/// Return the javascript opening the print dialog, if it was asked for
+pub(crate) fn print_js() -> String {
+    if take_print() {
+        "setTimeout(function() { window.print(); }, 0);".to_string()
+    } else {
+        "".to_string()
+    }
+}
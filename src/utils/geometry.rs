@@ -0,0 +1,116 @@
+//! # Geometry of the page
+//!
+//! The page reports the position of the pointer and the bounds of the
+//! elements of the widgets, in pixels relative to the top left corner of the
+//! page, through `Geometry` events. The bounds are sent after each render and
+//! when the window is resized or scrolled, and the position of the pointer
+//! when it moves, at most every 50 milliseconds. They are kept in the state
+//! of the Window, so custom drag-and-drop logic or overlays can look them up
+//! from Rust with `Window::pointer` and `Window::widget_at`, without waiting
+//! for an event of the widget under the pointer.
+//!
+//! ## Example
+//!
+//! ```
+//! use neutrino::testing::Harness;
+//! use neutrino::utils::geometry::Bounds;
+//! use neutrino::widgets::button::Button;
+//! use neutrino::widgets::container::Container;
+//! use neutrino::Window;
+//!
+//! let mut my_container = Container::new("my_container");
+//! my_container.add(Box::new(Button::new("my_button")));
+//!
+//! let mut my_window = Window::new();
+//! my_window.set_child(Box::new(my_container));
+//!
+//! let mut harness = Harness::new(my_window);
+//! harness.geometry(
+//!     Some((20.0, 10.0)),
+//!     vec![
+//!         ("my_container", Bounds::new(0.0, 0.0, 640.0, 480.0)),
+//!         ("my_button", Bounds::new(10.0, 5.0, 80.0, 24.0)),
+//!     ],
+//! );
+//!
+//! assert_eq!(harness.window().pointer(), Some((20.0, 10.0)));
+//! assert_eq!(harness.window().widget_at(20.0, 10.0).as_deref(), Some("my_button"));
+//! assert_eq!(harness.window().widget_at(200.0, 100.0).as_deref(), Some("my_container"));
+//! ```
+
+use json::JsonValue;
+
+/// # The bounds of an element of the page, in pixels
+///
+/// ## Fields
+///
+/// ```text
+/// x: f64
+/// y: f64
+/// width: f64
+/// height: f64
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bounds {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+impl Bounds {
+    /// Create a Bounds from the position of its top left corner and its size
+    pub fn new(x: f64, y: f64, width: f64, height: f64) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// Return true if the bounds contain the point
+    pub fn contains(&self, x: f64, y: f64) -> bool {
+        x >= self.x
+            && x < self.x + self.width
+            && y >= self.y
+            && y < self.y + self.height
+    }
+}
+
+/// The bounds of the elements of the page by name, in the order of the
+/// document
+pub type Elements = Vec<(String, Bounds)>;
+
+/// Read the position of the pointer of a geometry event, None if the
+/// pointer has not entered the page
+pub(crate) fn pointer(value: &JsonValue) -> Option<(f64, f64)> {
+    let pointer = &value["pointer"];
+    match (pointer["x"].as_f64(), pointer["y"].as_f64()) {
+        (Some(x), Some(y)) => Some((x, y)),
+        _ => None,
+    }
+}
+
+/// Read the bounds of the elements of a geometry event, in the order of the
+/// document, None if they did not change since the last event
+pub(crate) fn bounds(value: &JsonValue) -> Option<Elements> {
+    let elements = &value["elements"];
+    if !elements.is_array() {
+        return None;
+    }
+    let bounds = elements
+        .members()
+        .filter_map(|element| {
+            let name = element["name"].as_str()?;
+            let bounds = Bounds::new(
+                element["x"].as_f64()?,
+                element["y"].as_f64()?,
+                element["width"].as_f64()?,
+                element["height"].as_f64()?,
+            );
+            Some((name.to_string(), bounds))
+        })
+        .collect();
+    Some(bounds)
+}
@@ -0,0 +1,72 @@
+//! # Graphviz export
+//!
+//! A description of a widget tree in the DOT language of Graphviz, with a
+//! node per widget labelled with its name and its type, for documenting
+//! complex interfaces and checking how the containers are nested. The
+//! description can be rendered with `dot -Tsvg tree.dot -o tree.svg`.
+//!
+//! `Window::to_dot` describes a whole window, the menubar and the widget
+//! tree being the children of a node for the window itself.
+//!
+//! ## Example
+//!
+//! ```
+//! use neutrino::utils::graphviz;
+//! use neutrino::widgets::button::Button;
+//! use neutrino::widgets::container::Container;
+//!
+//! let mut my_container = Container::new("my_container");
+//! my_container.add(Box::new(Button::new("my_button")));
+//!
+//! let dot = graphviz::to_dot(&my_container);
+//!
+//! assert!(dot.contains(r#"n0 [label="my_container\nContainer"];"#));
+//! assert!(dot.contains(r#"n1 [label="my_button\nButton"];"#));
+//! assert!(dot.contains("n0 -> n1;"));
+//! ```
+
+use crate::widgets::widget::Widget;
+
+/// Describe a widget tree in the DOT language
+pub fn to_dot(widget: &dyn Widget) -> String {
+    let mut lines = vec![];
+    node(widget, &mut lines, &mut 0);
+    graph(&lines)
+}
+
+/// Wrap the nodes and edges of a tree in a directed graph
+pub(crate) fn graph(lines: &[String]) -> String {
+    let mut s = "digraph widgets {\n    node [shape=box];\n".to_string();
+    for line in lines {
+        s.push_str(&format!("    {}\n", line));
+    }
+    s.push_str("}\n");
+    s
+}
+
+/// Add a node for the widget, and the nodes of its children with the
+/// edges leading to them, naming the nodes by their order of visit
+pub(crate) fn node(
+    widget: &dyn Widget,
+    lines: &mut Vec<String>,
+    count: &mut usize,
+) -> usize {
+    let id = *count;
+    *count += 1;
+    lines.push(format!(
+        r#"n{} [label="{}\n{}"];"#,
+        id,
+        quote(&widget.name()),
+        quote(widget.kind())
+    ));
+    for child in widget.children() {
+        let child_id = node(child, lines, count);
+        lines.push(format!("n{} -> n{};", id, child_id));
+    }
+    id
+}
+
+/// Escape a text inserted in a quoted DOT string
+pub(crate) fn quote(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
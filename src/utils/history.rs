@@ -0,0 +1,209 @@
+//! # Undo and redo
+//!
+//! A `Command` is a reversible change of the model of the application, such
+//! as typing a word or deleting a shape. Pushed onto a `History`, the
+//! commands can be undone and redone in order.
+//!
+//! The application keeps its own History, managed through `App::execute`,
+//! `App::undo` and `App::redo`, so the listeners of an Edit menu, of a
+//! toolbar and of the `Ctrl + Z` and `Ctrl + Y` shortcuts (enabled by
+//! `Window::set_history_shortcuts`) all share the same stack.
+//!
+//! ## Example
+//!
+//! ```
+//! use std::cell::RefCell;
+//! use std::rc::Rc;
+//!
+//! use neutrino::utils::history::{Command, History};
+//!
+//!
+//! struct Append {
+//!     text: Rc<RefCell<String>>,
+//!     word: String,
+//! }
+//!
+//! impl Command for Append {
+//!     fn execute(&mut self) {
+//!         self.text.borrow_mut().push_str(&self.word);
+//!     }
+//!
+//!     fn undo(&mut self) {
+//!         let mut text = self.text.borrow_mut();
+//!         let length = text.len() - self.word.len();
+//!         text.truncate(length);
+//!     }
+//!
+//!     fn name(&self) -> String {
+//!         "Typing".to_string()
+//!     }
+//! }
+//!
+//!
+//! let text = Rc::new(RefCell::new("".to_string()));
+//! let mut history = History::new();
+//!
+//! history.execute(Box::new(Append { text: Rc::clone(&text), word: "Hello".to_string() }));
+//! history.execute(Box::new(Append { text: Rc::clone(&text), word: " world".to_string() }));
+//! assert_eq!(*text.borrow(), "Hello world");
+//!
+//! history.undo();
+//! assert_eq!(*text.borrow(), "Hello");
+//! assert_eq!(history.redo_name(), Some("Typing".to_string()));
+//!
+//! history.redo();
+//! assert_eq!(*text.borrow(), "Hello world");
+//! ```
+
+use std::cell::RefCell;
+
+/// # A reversible change of the model
+pub trait Command {
+    /// Apply the change
+    fn execute(&mut self);
+
+    /// Revert the change
+    fn undo(&mut self);
+
+    /// Get the name, displayed in menus such as "Undo Typing"
+    fn name(&self) -> String {
+        "".to_string()
+    }
+}
+
+/// # A stack of commands which can be undone and redone
+///
+/// Executing or pushing a command clears the commands which were undone.
+/// When a limit is set, the oldest commands are dropped.
+///
+/// ## Fields
+///
+/// ```text
+/// done: Vec<Box<dyn Command>>
+/// undone: Vec<Box<dyn Command>>
+/// limit: Option<usize>
+/// ```
+///
+/// ## Default values
+///
+/// ```text
+/// done: vec![]
+/// undone: vec![]
+/// limit: None
+/// ```
+pub struct History {
+    done: Vec<Box<dyn Command>>,
+    undone: Vec<Box<dyn Command>>,
+    limit: Option<usize>,
+}
+
+impl History {
+    /// Create a History
+    pub fn new() -> Self {
+        Self {
+            done: vec![],
+            undone: vec![],
+            limit: None,
+        }
+    }
+
+    /// Get the maximal number of commands which can be undone
+    pub fn limit(&self) -> Option<usize> {
+        self.limit
+    }
+
+    /// Set the maximal number of commands which can be undone
+    pub fn set_limit(&mut self, limit: Option<usize>) {
+        self.limit = limit;
+        self.trim();
+    }
+
+    /// Return true if a command can be undone
+    pub fn can_undo(&self) -> bool {
+        !self.done.is_empty()
+    }
+
+    /// Return true if a command can be redone
+    pub fn can_redo(&self) -> bool {
+        !self.undone.is_empty()
+    }
+
+    /// Get the name of the command which would be undone
+    pub fn undo_name(&self) -> Option<String> {
+        self.done.last().map(|command| command.name())
+    }
+
+    /// Get the name of the command which would be redone
+    pub fn redo_name(&self) -> Option<String> {
+        self.undone.last().map(|command| command.name())
+    }
+
+    /// Execute a command and push it
+    pub fn execute(&mut self, mut command: Box<dyn Command>) {
+        command.execute();
+        self.push(command);
+    }
+
+    /// Push a command which has already been executed
+    pub fn push(&mut self, command: Box<dyn Command>) {
+        self.done.push(command);
+        self.undone.clear();
+        self.trim();
+    }
+
+    /// Undo the last command. Return false if there is none.
+    pub fn undo(&mut self) -> bool {
+        match self.done.pop() {
+            Some(mut command) => {
+                command.undo();
+                self.undone.push(command);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Redo the last undone command. Return false if there is none.
+    pub fn redo(&mut self) -> bool {
+        match self.undone.pop() {
+            Some(mut command) => {
+                command.execute();
+                self.done.push(command);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Remove all the commands
+    pub fn clear(&mut self) {
+        self.done.clear();
+        self.undone.clear();
+    }
+
+    /// Drop the oldest commands over the limit
+    fn trim(&mut self) {
+        if let Some(limit) = self.limit {
+            if self.done.len() > limit {
+                let excess = self.done.len() - limit;
+                self.done.drain(..excess);
+            }
+        }
+    }
+}
+
+impl Default for History {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+thread_local! {
+    static APP_HISTORY: RefCell<History> = RefCell::new(History::new());
+}
+
+/// Run a function on the History of the application. The commands must not
+/// use the History of the application while they are executed or undone.
+pub(crate) fn with_app<R>(f: impl FnOnce(&mut History) -> R) -> R {
+    APP_HISTORY.with(|history| f(&mut history.borrow_mut()))
+}
@@ -0,0 +1,94 @@
+//! # Event hooks
+//!
+//! The events sent by the page go through the hooks added to the Window
+//! with `Window::add_hook` before the widgets see them, so an application
+//! can log all the events, handle global shortcuts or cancel an event, and
+//! after the widget tree has been updated.
+//!
+//! The hooks are called in the order they were added. `before` returns
+//! `Propagation::Stop` to cancel an event: the next hooks, the listener of
+//! the window and the widgets do not see it, and the widget tree is only
+//! updated if the store changed. `after` is only called for the events
+//! which were not cancelled.
+//!
+//! The events sent by a widget, such as a change event, only reach this
+//! widget and the widgets holding it, such as its container, instead of
+//! the whole widget tree. The other events, such as the key events, reach
+//! every widget.
+//!
+//! ## Example
+//!
+//! ```
+//! use std::cell::Cell;
+//! use std::rc::Rc;
+//!
+//! use neutrino::testing::Harness;
+//! use neutrino::utils::event::Event;
+//! use neutrino::utils::hook::{EventHook, Propagation};
+//! use neutrino::widgets::checkbox::CheckBox;
+//! use neutrino::widgets::container::Container;
+//! use neutrino::Window;
+//!
+//!
+//! struct ReadOnlyHook {
+//!     seen: Rc<Cell<usize>>,
+//! }
+//!
+//! impl EventHook for ReadOnlyHook {
+//!     fn before(&self, event: &Event) -> Propagation {
+//!         match event {
+//!             Event::Change { source, .. } if source == "locked" => {
+//!                 Propagation::Stop
+//!             }
+//!             _ => Propagation::Continue,
+//!         }
+//!     }
+//!
+//!     fn after(&self, event: &Event) {
+//!         if let Event::Change { .. } = event {
+//!             self.seen.set(self.seen.get() + 1);
+//!         }
+//!     }
+//! }
+//!
+//!
+//! let mut my_container = Container::new("my_container");
+//! my_container.add(Box::new(CheckBox::new("locked")));
+//! my_container.add(Box::new(CheckBox::new("free")));
+//!
+//! let seen = Rc::new(Cell::new(0));
+//!
+//! let mut my_window = Window::new();
+//! my_window.set_child(Box::new(my_container));
+//! my_window.add_hook(Box::new(ReadOnlyHook { seen: seen.clone() }));
+//!
+//! let mut harness = Harness::new(my_window);
+//! harness.change("locked", "");
+//! harness.change("free", "");
+//!
+//! let window = harness.window();
+//! assert!(!window.widget::<CheckBox>("locked").unwrap().state().checked());
+//! assert!(window.widget::<CheckBox>("free").unwrap().state().checked());
+//! assert_eq!(seen.get(), 1);
+//! ```
+
+use crate::utils::event::Event;
+
+/// # Whether an event goes on to the widgets
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Propagation {
+    Continue,
+    Stop,
+}
+
+/// # A hook called for each event sent by the page
+pub trait EventHook {
+    /// Function called before the widgets see the event, returning
+    /// `Propagation::Stop` to cancel it
+    fn before(&self, _event: &Event) -> Propagation {
+        Propagation::Continue
+    }
+
+    /// Function called once the widget tree has been updated
+    fn after(&self, _event: &Event) {}
+}
@@ -0,0 +1,46 @@
+//! # HTML escaping
+//!
+//! The widgets escape the texts they are given, such as the text of a Label
+//! or the choices of a Combo, before inserting them into their HTML, so a
+//! text coming from the model is displayed as it is instead of breaking the
+//! page or injecting markup and scripts. The Label can display raw HTML
+//! when it is explicitly asked to with `set_raw`.
+//!
+//! The label read by screen readers for a widget, set with
+//! `set_aria_label`, is escaped the same way.
+//!
+//! ## Example
+//!
+//! ```
+//! use neutrino::utils::html;
+//!
+//! assert_eq!(
+//!     html::escape(r#"<b onclick="alert('1')">Tom & Jerry</b>"#),
+//!     "&lt;b onclick=&quot;alert(&#39;1&#39;)&quot;&gt;Tom &amp; Jerry&lt;/b&gt;"
+//! );
+//! ```
+
+/// Escape a text inserted in HTML, between tags or in a quoted attribute
+pub fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Return the `aria-label` attribute of an optional label, empty if there
+/// is no label
+pub fn aria_label(label: Option<&str>) -> String {
+    match label {
+        Some(label) => format!(r#"aria-label="{}""#, escape(label)),
+        None => "".to_string(),
+    }
+}
@@ -0,0 +1,565 @@
+//! # Message formatting
+//!
+//! The texts of an application are written as messages in the ICU syntax,
+//! so that their grammar can follow the count or the gender of their
+//! arguments in each language:
+//!
+//! - `{name}` is replaced by the argument `name`
+//! - `{count, plural, =0 {no files} one {# file} other {# files}}` chooses
+//!   the branch matching the number, exactly (`=0`) or through the plural
+//!   category of the language (`zero`, `one`, `two`, `few`, `many` or
+//!   `other`), `#` being replaced by the number
+//! - `{gender, select, female {she} male {he} other {they}}` chooses the
+//!   branch matching the argument
+//!
+//! The `other` branch is required. The branches are messages themselves, so
+//! they can hold other arguments. A quote escapes the braces and the `#`
+//! following it until the next quote, and two quotes give one quote.
+//!
+//! A `Catalog` holds the messages of a language by key. The catalogs of an
+//! application are added with `App::add_catalog`, from the code or from a
+//! JSON file whose nested objects give dotted keys, such as `menu.save`.
+//! `App::set_locale` chooses the catalog used by the widgets whose text is
+//! set with `set_text_key`, such as the Button, the CheckBox, the Label and
+//! the functions of the MenuBar: their text is looked up each time the page
+//! is rendered, so switching the locale from a listener translates the whole
+//! window at the next render. A locale without a catalog of its own, such as
+//! `fr-CA`, uses the one of its language, and a missing message shows its
+//! key.
+//!
+//! `format_number` and `format_date` write numbers and dates the way the
+//! locale does, and `parse_number` reads the numbers back. The SpinBox and
+//! the DatePicker use them with `set_localized`.
+//!
+//! ## Example
+//!
+//! ```
+//! use neutrino::testing::Harness;
+//! use neutrino::utils::datetime::Date;
+//! use neutrino::utils::i18n::{self, Catalog};
+//! use neutrino::widgets::button::Button;
+//! use neutrino::{App, Window};
+//!
+//!
+//! let french = Catalog::from_json(
+//!     "fr",
+//!     r#"{ "menu": { "save": "Enregistrer" } }"#,
+//! ).unwrap();
+//! App::add_catalog(french);
+//!
+//! let mut save = Button::new("save");
+//! save.set_text_key("menu.save");
+//!
+//! let mut my_window = Window::new();
+//! my_window.set_child(Box::new(save));
+//!
+//! App::set_locale("fr-CA");
+//! let harness = Harness::new(my_window);
+//! assert!(harness.contains("Enregistrer"));
+//!
+//! App::set_locale("en");
+//! assert!(harness.contains("menu.save"));
+//!
+//! assert_eq!(i18n::format_number("de", 1234.5, 2), "1.234,50");
+//! assert_eq!(i18n::parse_number("de", "1.234,5"), Some(1234.5));
+//!
+//! let date = Date::new(2024, 3, 9).unwrap();
+//! assert_eq!(i18n::format_date("en-US", date), "03/09/2024");
+//! assert_eq!(i18n::format_date("fr", date), "09/03/2024");
+//! ```
+//!
+//! ```
+//! use neutrino::utils::i18n::{self, Catalog};
+//!
+//! let message = "{count, plural, =0 {No file} one {# file} other {# files}} selected";
+//!
+//! assert_eq!(i18n::format("en", message, &[("count", "1")]).unwrap(), "1 file selected");
+//! assert_eq!(i18n::format("en", message, &[("count", "3")]).unwrap(), "3 files selected");
+//!
+//! let mut catalog = Catalog::new("pl");
+//! catalog.add(
+//!     "selected",
+//!     "{count, plural, one {# plik} few {# pliki} other {# plików}}",
+//! );
+//!
+//! assert_eq!(catalog.format("selected", &[("count", "3")]), "3 pliki");
+//! assert_eq!(catalog.format("selected", &[("count", "5")]), "5 plików");
+//! ```
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::path::Path;
+
+use json::JsonValue;
+
+use crate::utils::datetime::Date;
+
+thread_local! {
+    static CATALOGS: RefCell<Vec<Catalog>> = const { RefCell::new(Vec::new()) };
+    static LOCALE: RefCell<String> = RefCell::new("en".to_string());
+}
+
+/// # A plural category of the CLDR
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Plural {
+    Zero,
+    One,
+    Two,
+    Few,
+    Many,
+    Other,
+}
+
+impl Plural {
+    /// Return the name of the category used in the messages
+    pub fn name(&self) -> &str {
+        match &self {
+            Plural::Zero => "zero",
+            Plural::One => "one",
+            Plural::Two => "two",
+            Plural::Few => "few",
+            Plural::Many => "many",
+            Plural::Other => "other",
+        }
+    }
+}
+
+/// # The messages of a language
+///
+/// ## Fields
+///
+/// ```text
+/// language: String
+/// messages: HashMap<String, String>
+/// ```
+pub struct Catalog {
+    language: String,
+    messages: HashMap<String, String>,
+}
+
+impl Catalog {
+    /// Create a Catalog for the language with the given code, such as `en`
+    /// or `pt-BR`
+    pub fn new(language: &str) -> Self {
+        Self {
+            language: language.to_string(),
+            messages: HashMap::new(),
+        }
+    }
+
+    /// Create a Catalog from a JSON object of messages, the keys of nested
+    /// objects being joined with dots
+    pub fn from_json(language: &str, messages: &str) -> Result<Self, String> {
+        let messages = json::parse(messages)
+            .map_err(|error| format!("{}: {}", language, error))?;
+        if !messages.is_object() {
+            return Err(format!(
+                "{}: the messages must be an object",
+                language
+            ));
+        }
+        let mut catalog = Self::new(language);
+        catalog.add_json("", &messages)?;
+        Ok(catalog)
+    }
+
+    /// Create a Catalog from a JSON file of messages
+    pub fn from_file<P: AsRef<Path>>(
+        language: &str,
+        path: P,
+    ) -> Result<Self, String> {
+        let path = path.as_ref();
+        let messages = fs::read_to_string(path)
+            .map_err(|error| format!("{}: {}", path.display(), error))?;
+        Self::from_json(language, &messages)
+    }
+
+    /// Get the language
+    pub fn language(&self) -> &str {
+        &self.language
+    }
+
+    /// Add the message with the given key
+    pub fn add(&mut self, key: &str, message: &str) {
+        self.messages.insert(key.to_string(), message.to_string());
+    }
+
+    /// Format the message with the given key. The key is returned if there
+    /// is no such message, and the message as it is if it is malformed.
+    pub fn format(&self, key: &str, args: &[(&str, &str)]) -> String {
+        match self.messages.get(key) {
+            Some(message) => format(&self.language, message, args)
+                .unwrap_or_else(|_| message.to_string()),
+            None => key.to_string(),
+        }
+    }
+
+    /// Return true if the catalog has a message with the given key
+    pub fn contains(&self, key: &str) -> bool {
+        self.messages.contains_key(key)
+    }
+
+    /// Add the messages of a JSON object, their keys starting with a prefix
+    fn add_json(
+        &mut self,
+        prefix: &str,
+        value: &JsonValue,
+    ) -> Result<(), String> {
+        for (key, value) in value.entries() {
+            let key = if prefix.is_empty() {
+                key.to_string()
+            } else {
+                format!("{}.{}", prefix, key)
+            };
+            match value.as_str() {
+                Some(message) => self.add(&key, message),
+                None if value.is_object() => self.add_json(&key, value)?,
+                None => {
+                    return Err(format!(
+                        "{}: {}: the message must be a string",
+                        self.language, key
+                    ))
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Add a catalog to the application, replacing the one of the same language
+pub(crate) fn add_catalog(catalog: Catalog) {
+    CATALOGS.with(|catalogs| {
+        let mut catalogs = catalogs.borrow_mut();
+        catalogs.retain(|other| other.language != catalog.language);
+        catalogs.push(catalog);
+    });
+}
+
+/// Set the locale of the application
+pub(crate) fn set_locale(locale: &str) {
+    LOCALE.with(|current| *current.borrow_mut() = locale.to_string());
+}
+
+/// Get the locale of the application, `en` by default
+pub fn locale() -> String {
+    LOCALE.with(|locale| locale.borrow().to_string())
+}
+
+/// Get the locale of the user from the environment, such as `fr-FR` for
+/// `LANG=fr_FR.UTF-8`
+pub fn system_locale() -> Option<String> {
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .filter_map(|name| env::var(name).ok())
+        .map(|value| value.split(['.', '@']).next().unwrap_or("").to_string())
+        .find(|value| !value.is_empty() && value != "C" && value != "POSIX")
+        .map(|value| value.replace('_', "-"))
+}
+
+/// Get the message with the given key in the locale of the application
+pub fn text(key: &str) -> String {
+    translate(key, &[])
+}
+
+/// Format the message with the given key in the locale of the application,
+/// from the catalog of the locale or else of its language. The key is
+/// returned if there is no such message.
+pub fn translate(key: &str, args: &[(&str, &str)]) -> String {
+    lookup(key, args).unwrap_or_else(|| key.to_string())
+}
+
+/// Format the message with the given key in the locale of the application,
+/// or the given message if there is no such message
+pub(crate) fn translate_or(
+    key: &str,
+    message: &str,
+    args: &[(&str, &str)],
+) -> String {
+    lookup(key, args).unwrap_or_else(|| {
+        format(&locale(), message, args).unwrap_or_else(|_| message.to_string())
+    })
+}
+
+/// Format the message with the given key in the locale of the application,
+/// None if no catalog of the locale or of its language has it
+fn lookup(key: &str, args: &[(&str, &str)]) -> Option<String> {
+    let locale = locale();
+    let language = code(&locale);
+    CATALOGS.with(|catalogs| {
+        let catalogs = catalogs.borrow();
+        let with_key =
+            || catalogs.iter().filter(|catalog| catalog.contains(key));
+        with_key()
+            .find(|catalog| catalog.language == locale)
+            .or_else(|| with_key().find(|catalog| catalog.language == language))
+            .map(|catalog| catalog.format(key, args))
+    })
+}
+
+/// Get the decimal and the group separators of a locale
+pub fn separators(locale: &str) -> (char, char) {
+    match code(locale) {
+        "de" | "es" | "it" | "nl" | "pt" | "id" | "tr" | "da" | "el" => {
+            (',', '.')
+        }
+        "fr" | "ru" | "pl" | "cs" | "sk" | "uk" | "fi" | "sv" | "nb" => {
+            (',', '\u{a0}')
+        }
+        _ => ('.', ','),
+    }
+}
+
+/// Format a number with the given number of decimals in a locale, grouping
+/// the digits of its integer part by three
+pub fn format_number(locale: &str, value: f64, decimals: u32) -> String {
+    let (decimal, group) = separators(locale);
+    let s = format!("{:.*}", decimals as usize, value.abs());
+    let (integer, fraction) = match s.split_once('.') {
+        Some((integer, fraction)) => (integer, Some(fraction)),
+        None => (s.as_str(), None),
+    };
+    let mut formatted = String::new();
+    if value < 0.0 && s.chars().any(|c| c != '0' && c != '.') {
+        formatted.push('-');
+    }
+    for (i, digit) in integer.chars().enumerate() {
+        if i > 0 && (integer.len() - i) % 3 == 0 {
+            formatted.push(group);
+        }
+        formatted.push(digit);
+    }
+    if let Some(fraction) = fraction {
+        formatted.push(decimal);
+        formatted.push_str(fraction);
+    }
+    formatted
+}
+
+/// Read a number written in a locale, with or without the group separators
+pub fn parse_number(locale: &str, text: &str) -> Option<f64> {
+    let (decimal, group) = separators(locale);
+    let text: String = text
+        .trim()
+        .chars()
+        .filter(|&c| c != group && !(group == '\u{a0}' && c == ' '))
+        .map(|c| if c == decimal { '.' } else { c })
+        .collect();
+    text.parse().ok()
+}
+
+/// Get the format of the dates in a locale, in the syntax of
+/// `Date::format`
+pub fn date_format(locale: &str) -> &'static str {
+    let region = locale.split(['-', '_']).nth(1);
+    match code(locale) {
+        "en" if region.is_none() || region == Some("US") => "%m/%d/%Y",
+        "en" | "fr" | "es" | "it" | "pt" | "el" => "%d/%m/%Y",
+        "nl" => "%d-%m-%Y",
+        "de" | "ru" | "pl" | "cs" | "sk" | "uk" | "fi" | "nb" | "tr" | "da" => {
+            "%d.%m.%Y"
+        }
+        "ja" | "zh" => "%Y/%m/%d",
+        _ => "%Y-%m-%d",
+    }
+}
+
+/// Format a date in a locale
+pub fn format_date(locale: &str, date: Date) -> String {
+    date.format(date_format(locale))
+}
+
+/// Get the language of a locale, such as `pt` for `pt-BR`
+fn code(locale: &str) -> &str {
+    locale.split(['-', '_']).next().unwrap_or("")
+}
+
+/// Format a message in the given language
+pub fn format(
+    language: &str,
+    message: &str,
+    args: &[(&str, &str)],
+) -> Result<String, String> {
+    let chars: Vec<char> = message.chars().collect();
+    format_chars(language, &chars, args, None)
+}
+
+/// Get the plural category of an integer in the given language, the
+/// languages without a rule of their own using those of English
+pub fn plural(language: &str, n: i64) -> Plural {
+    let n = n.unsigned_abs();
+    let (n10, n100) = (n % 10, n % 100);
+    match code(language) {
+        "ja" | "ko" | "zh" | "th" | "vi" | "id" | "ms" | "tr" => Plural::Other,
+        "fr" | "pt" if n <= 1 => Plural::One,
+        "fr" | "pt" => Plural::Other,
+        "ru" | "uk" | "be" | "sr" | "hr" | "bs" => {
+            if n10 == 1 && n100 != 11 {
+                Plural::One
+            } else if (2..=4).contains(&n10) && !(12..=14).contains(&n100) {
+                Plural::Few
+            } else {
+                Plural::Many
+            }
+        }
+        "pl" => {
+            if n == 1 {
+                Plural::One
+            } else if (2..=4).contains(&n10) && !(12..=14).contains(&n100) {
+                Plural::Few
+            } else {
+                Plural::Many
+            }
+        }
+        "cs" | "sk" => match n {
+            1 => Plural::One,
+            2..=4 => Plural::Few,
+            _ => Plural::Other,
+        },
+        "ar" => match (n, n100) {
+            (0, _) => Plural::Zero,
+            (1, _) => Plural::One,
+            (2, _) => Plural::Two,
+            (_, 3..=10) => Plural::Few,
+            (_, 11..=99) => Plural::Many,
+            _ => Plural::Other,
+        },
+        _ if n == 1 => Plural::One,
+        _ => Plural::Other,
+    }
+}
+
+/// Format a message, `number` being the value replacing `#` in a plural
+/// branch
+fn format_chars(
+    language: &str,
+    chars: &[char],
+    args: &[(&str, &str)],
+    number: Option<&str>,
+) -> Result<String, String> {
+    let mut s = String::new();
+    let mut i = 0;
+    while i < chars.len() {
+        match chars[i] {
+            '\'' if chars.get(i + 1) == Some(&'\'') => {
+                s.push('\'');
+                i += 2;
+            }
+            '\'' if matches!(chars.get(i + 1), Some('{' | '}' | '#')) => {
+                let end = chars[i + 1..]
+                    .iter()
+                    .position(|&c| c == '\'')
+                    .map(|p| i + 1 + p)
+                    .unwrap_or(chars.len());
+                s.extend(&chars[i + 1..end]);
+                i = end + 1;
+            }
+            '#' if number.is_some() => {
+                s.push_str(number.unwrap_or(""));
+                i += 1;
+            }
+            '{' => {
+                let end = closing(chars, i)?;
+                s.push_str(&argument(language, &chars[i + 1..end], args)?);
+                i = end + 1;
+            }
+            '}' => return Err("unexpected }".to_string()),
+            c => {
+                s.push(c);
+                i += 1;
+            }
+        }
+    }
+    Ok(s)
+}
+
+/// Format an argument, given as the text between its braces
+fn argument(
+    language: &str,
+    chars: &[char],
+    args: &[(&str, &str)],
+) -> Result<String, String> {
+    let text: String = chars.iter().collect();
+    let mut parts = text.splitn(3, ',');
+    let name = parts.next().unwrap_or("").trim();
+    let value = match args.iter().find(|(arg, _)| *arg == name) {
+        Some((_, value)) => *value,
+        None => return Err(format!("missing argument {}", name)),
+    };
+    let kind = match parts.next() {
+        Some(kind) => kind.trim(),
+        None => return Ok(value.to_string()),
+    };
+    let branches =
+        branches(&parts.next().unwrap_or("").chars().collect::<Vec<char>>())?;
+    let find = |selector: &str| {
+        branches
+            .iter()
+            .find(|(key, _)| key == selector)
+            .map(|(_, branch)| branch)
+    };
+    let (branch, number) = match kind {
+        "plural" => {
+            let n = value
+                .trim()
+                .parse::<i64>()
+                .map_err(|_| format!("{} must be an integer", name))?;
+            let branch = find(&format!("={}", n))
+                .or_else(|| find(plural(language, n).name()));
+            (branch, Some(value))
+        }
+        "select" => (find(value), None),
+        kind => return Err(format!("unknown argument type {}", kind)),
+    };
+    match branch.or_else(|| find("other")) {
+        Some(branch) => format_chars(language, branch, args, number),
+        None => Err(format!("{}: the other branch is missing", name)),
+    }
+}
+
+/// Read the branches of a plural or select argument, as (selector, message)
+fn branches(chars: &[char]) -> Result<Vec<(String, Vec<char>)>, String> {
+    let mut branches = vec![];
+    let mut i = 0;
+    loop {
+        while i < chars.len() && chars[i].is_whitespace() {
+            i += 1;
+        }
+        if i == chars.len() {
+            return Ok(branches);
+        }
+        let start = i;
+        while i < chars.len() && chars[i] != '{' && !chars[i].is_whitespace() {
+            i += 1;
+        }
+        let selector: String = chars[start..i].iter().collect();
+        while i < chars.len() && chars[i].is_whitespace() {
+            i += 1;
+        }
+        if i == chars.len() {
+            return Err(format!("{}: the branch has no message", selector));
+        }
+        let end = closing(chars, i)?;
+        branches.push((selector, chars[i + 1..end].to_vec()));
+        i = end + 1;
+    }
+}
+
+/// Find the brace closing the one at the given position
+fn closing(chars: &[char], open: usize) -> Result<usize, String> {
+    let mut depth = 0;
+    for (i, &c) in chars.iter().enumerate().skip(open) {
+        match c {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Ok(i);
+                }
+            }
+            _ => (),
+        }
+    }
+    Err("missing }".to_string())
+}
@@ -0,0 +1,422 @@
+//! # Single instance
+//!
+//! `App::single_instance` makes sure that only one instance of an
+//! application runs at a time. It is called at the start of `main`, with an
+//! identifier of the application such as `com.example.notes`: the first
+//! instance takes a lock file and listens on a channel only open to the
+//! user, and the next ones send it their command-line arguments and exit.
+//!
+//! * On Unix, the lock file and the Unix domain socket are kept in
+//!   `$XDG_RUNTIME_DIR`, or else in a `neutrino` directory only accessible
+//!   by the user, in the temporary directory of the user on macOS and in
+//!   `~/.cache` elsewhere.
+//! * On Windows, the lock file is kept in `%LOCALAPPDATA%\neutrino`, and
+//!   the channel is a named pipe of the user, rejecting remote clients.
+//!
+//! The lock is released by the system when the instance exits, so a socket
+//! left by an instance which crashed is replaced by the next one.
+//!
+//! The running instance receives the arguments through an `Instance` event,
+//! handled by `WindowListener::on_instance`, for instance to open the file
+//! given to the new invocation, and the page asks the webview to bring its
+//! window to the front, where the webview lets it.
+//!
+//! If the guard cannot be set up, for instance if the directory is
+//! accessible by other users, it is skipped: the application runs as usual
+//! and the error is reported to the window.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use neutrino::{App, Window};
+//!
+//! if !App::single_instance("com.example.notes") {
+//!     return;
+//! }
+//!
+//! let my_window = Window::new();
+//! # #[cfg(feature = "web-view")]
+//! App::run(my_window);
+//! ```
+
+use std::cell::RefCell;
+use std::env;
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use fs2::FileExt;
+use json::JsonValue;
+
+use crate::backend::Proxy;
+use crate::utils::event;
+
+/// The name of the guard, used as the source of its errors
+const INSTANCE: &str = "instance";
+
+/// The time given to the running instance to answer
+const TIMEOUT: Duration = Duration::from_secs(2);
+
+thread_local! {
+    static GUARD: RefCell<Option<(String, channel::Listener)>> = const { RefCell::new(None) };
+    static LOCK: RefCell<Option<File>> = const { RefCell::new(None) };
+}
+
+/// Start the guard of the application with the given identifier. Return
+/// false if another instance runs, the arguments having been sent to it.
+pub(crate) fn guard(id: &str) -> bool {
+    match lock(id) {
+        Ok(true) => true,
+        Ok(false) => match forward(id) {
+            Ok(()) => false,
+            Err(error) => {
+                event::report_error(INSTANCE, &error);
+                true
+            }
+        },
+        Err(error) => {
+            event::report_error(INSTANCE, &error);
+            true
+        }
+    }
+}
+
+/// Take the lock of the application and listen on its channel, returning
+/// false if another instance holds the lock
+fn lock(id: &str) -> Result<bool, String> {
+    let name = file_name(id);
+    let dir = channel::dir().ok_or("no directory for the lock file")?;
+    let path = dir.join(format!("{}.lock", name));
+    let error = |error: io::Error| format!("{}: {}", path.display(), error);
+    let file = OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(&path)
+        .map_err(error)?;
+    match file.try_lock_exclusive() {
+        Ok(()) => (),
+        Err(cause)
+            if cause.raw_os_error()
+                == fs2::lock_contended_error().raw_os_error() =>
+        {
+            return Ok(false)
+        }
+        Err(cause) => return Err(error(cause)),
+    }
+    let listener = channel::Listener::bind(&dir, &name)?;
+    LOCK.with(|lock| *lock.borrow_mut() = Some(file));
+    GUARD.with(|guard| *guard.borrow_mut() = Some((id.to_string(), listener)));
+    Ok(true)
+}
+
+/// Receive the arguments of the next instances in a thread, asking the page
+/// to send an instance event for each of them
+pub(crate) fn listen(proxy: Proxy) {
+    let (id, mut listener) = match GUARD.with(|guard| guard.borrow_mut().take())
+    {
+        Some(guard) => guard,
+        None => return,
+    };
+    thread::spawn(move || loop {
+        let args = match listener.accept() {
+            Ok(stream) => match receive(&id, stream) {
+                Some(args) => args,
+                None => continue,
+            },
+            Err(error) => {
+                proxy.eval(&event::error_js(INSTANCE, &error.to_string()));
+                return;
+            }
+        };
+        let mut event = JsonValue::new_object();
+        event["type"] = "Instance".into();
+        event["args"] = args.into();
+        let js = format!("window.focus(); emit({});", event.dump());
+        if !proxy.eval(&js) {
+            return;
+        }
+    });
+}
+
+/// Read the arguments of an instance event sent by the page
+pub(crate) fn args(value: &JsonValue) -> Vec<String> {
+    value["args"]
+        .members()
+        .filter_map(JsonValue::as_str)
+        .map(str::to_string)
+        .collect()
+}
+
+/// Get the name of the files of an application, keeping the letters, the
+/// digits, the dots, the dashes and the underscores of its identifier
+fn file_name(id: &str) -> String {
+    id.chars()
+        .filter(|c| c.is_ascii_alphanumeric() || ".-_".contains(*c))
+        .collect()
+}
+
+/// Send the arguments of this instance to the running one, which answers
+/// with the identifier of the application. The running instance may be
+/// starting, so the channel is tried again until the time is up.
+fn forward(id: &str) -> Result<(), String> {
+    let mut message = JsonValue::new_object();
+    message["id"] = id.into();
+    message["args"] = env::args().skip(1).collect::<Vec<String>>().into();
+    let start = Instant::now();
+    let mut stream = loop {
+        match channel::connect(&file_name(id)) {
+            Ok(stream) => break stream,
+            Err(_) if start.elapsed() < TIMEOUT => {
+                thread::sleep(Duration::from_millis(50))
+            }
+            Err(error) => return Err(format!("{}: {}", id, error)),
+        }
+    };
+    writeln!(stream, "{}", message.dump())
+        .map_err(|error| format!("{}: {}", id, error))?;
+    let mut answer = String::new();
+    BufReader::new(&mut stream)
+        .read_line(&mut answer)
+        .map_err(|error| format!("{}: {}", id, error))?;
+    if answer.trim_end() == id {
+        Ok(())
+    } else {
+        Err(format!("{}: the running instance did not answer", id))
+    }
+}
+
+/// Read the arguments sent by another instance of the application, None if
+/// the message does not come from an instance of the application
+fn receive<S: Read + Write>(id: &str, mut stream: S) -> Option<Vec<String>> {
+    let mut line = String::new();
+    BufReader::new(&mut stream).read_line(&mut line).ok()?;
+    let message = json::parse(&line).ok()?;
+    if message["id"].as_str() != Some(id) {
+        return None;
+    }
+    writeln!(stream, "{}", id).ok()?;
+    Some(args(&message))
+}
+
+/// The channel of the instances: a Unix domain socket
+#[cfg(unix)]
+mod channel {
+    use std::env;
+    use std::fs;
+    use std::io;
+    use std::os::unix::fs::{DirBuilderExt, PermissionsExt};
+    use std::os::unix::net::{UnixListener, UnixStream};
+    use std::path::{Path, PathBuf};
+
+    use super::TIMEOUT;
+
+    /// # A listener of the instances
+    pub struct Listener(UnixListener);
+
+    impl Listener {
+        /// Listen on the socket with the given name in the directory,
+        /// replacing the one left by an instance which crashed
+        pub fn bind(dir: &Path, name: &str) -> Result<Self, String> {
+            let path = dir.join(format!("{}.sock", name));
+            let error =
+                |error: io::Error| format!("{}: {}", path.display(), error);
+            match fs::remove_file(&path) {
+                Err(cause) if cause.kind() != io::ErrorKind::NotFound => {
+                    return Err(error(cause))
+                }
+                _ => (),
+            }
+            let listener = UnixListener::bind(&path).map_err(error)?;
+            fs::set_permissions(&path, fs::Permissions::from_mode(0o600))
+                .map_err(error)?;
+            Ok(Self(listener))
+        }
+
+        /// Wait for the next instance
+        pub fn accept(&mut self) -> io::Result<UnixStream> {
+            let (stream, _) = self.0.accept()?;
+            stream.set_read_timeout(Some(TIMEOUT))?;
+            Ok(stream)
+        }
+    }
+
+    /// Connect to the running instance
+    pub fn connect(name: &str) -> io::Result<UnixStream> {
+        let dir = dir().ok_or(io::ErrorKind::NotFound)?;
+        let stream = UnixStream::connect(dir.join(format!("{}.sock", name)))?;
+        stream.set_read_timeout(Some(TIMEOUT))?;
+        Ok(stream)
+    }
+
+    /// Get the directory of the lock file and of the socket, creating it
+    /// if needed, None if it cannot be created or is accessible by other
+    /// users
+    pub fn dir() -> Option<PathBuf> {
+        if let Some(dir) = env::var_os("XDG_RUNTIME_DIR") {
+            if !dir.is_empty() {
+                return Some(PathBuf::from(dir));
+            }
+        }
+        let parent = if cfg!(target_os = "macos") {
+            env::temp_dir()
+        } else {
+            PathBuf::from(env::var_os("HOME")?).join(".cache")
+        };
+        let dir = parent.join("neutrino");
+        fs::create_dir_all(&parent).ok()?;
+        match fs::DirBuilder::new().mode(0o700).create(&dir) {
+            Err(error) if error.kind() != io::ErrorKind::AlreadyExists => {
+                return None
+            }
+            _ => (),
+        }
+        let mode = fs::symlink_metadata(&dir).ok()?.permissions().mode();
+        if mode & 0o077 == 0 {
+            Some(dir)
+        } else {
+            None
+        }
+    }
+}
+
+/// The channel of the instances: a named pipe of the user, rejecting the
+/// remote clients
+#[cfg(windows)]
+mod channel {
+    use std::env;
+    use std::ffi::OsStr;
+    use std::fs::{self, File, OpenOptions};
+    use std::io;
+    use std::mem;
+    use std::os::raw::c_void;
+    use std::os::windows::ffi::OsStrExt;
+    use std::os::windows::io::{AsRawHandle, FromRawHandle, RawHandle};
+    use std::path::{Path, PathBuf};
+    use std::ptr;
+
+    const PIPE_ACCESS_DUPLEX: u32 = 0x0000_0003;
+    const FILE_FLAG_FIRST_PIPE_INSTANCE: u32 = 0x0008_0000;
+    const PIPE_REJECT_REMOTE_CLIENTS: u32 = 0x0000_0008;
+    const PIPE_UNLIMITED_INSTANCES: u32 = 255;
+    const ERROR_PIPE_CONNECTED: i32 = 535;
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn CreateNamedPipeW(
+            name: *const u16,
+            open_mode: u32,
+            pipe_mode: u32,
+            max_instances: u32,
+            out_buffer_size: u32,
+            in_buffer_size: u32,
+            default_timeout: u32,
+            security_attributes: *mut c_void,
+        ) -> RawHandle;
+        fn ConnectNamedPipe(pipe: RawHandle, overlapped: *mut c_void) -> i32;
+    }
+
+    /// # A listener of the instances
+    ///
+    /// An instance of the pipe is always open, so that no other program
+    /// can take its name.
+    pub struct Listener {
+        name: Vec<u16>,
+        next: File,
+    }
+
+    impl Listener {
+        /// Create the first instance of the pipe with the given name,
+        /// failing if another program created it
+        pub fn bind(_dir: &Path, name: &str) -> Result<Self, String> {
+            let name: Vec<u16> = OsStr::new(&pipe(name))
+                .encode_wide()
+                .chain(Some(0))
+                .collect();
+            let next = create(&name, FILE_FLAG_FIRST_PIPE_INSTANCE)
+                .map_err(|error| error.to_string())?;
+            Ok(Self { name, next })
+        }
+
+        /// Wait for the next instance, opening the next instance of the
+        /// pipe before returning the connected one
+        pub fn accept(&mut self) -> io::Result<File> {
+            let handle = self.next.as_raw_handle();
+            if unsafe { ConnectNamedPipe(handle, ptr::null_mut()) } == 0 {
+                let error = io::Error::last_os_error();
+                if error.raw_os_error() != Some(ERROR_PIPE_CONNECTED) {
+                    return Err(error);
+                }
+            }
+            let next = create(&self.name, 0)?;
+            Ok(mem::replace(&mut self.next, next))
+        }
+    }
+
+    /// Create an instance of the pipe with the given name
+    fn create(name: &[u16], flags: u32) -> io::Result<File> {
+        let handle = unsafe {
+            CreateNamedPipeW(
+                name.as_ptr(),
+                PIPE_ACCESS_DUPLEX | flags,
+                PIPE_REJECT_REMOTE_CLIENTS,
+                PIPE_UNLIMITED_INSTANCES,
+                4096,
+                4096,
+                0,
+                ptr::null_mut(),
+            )
+        };
+        if handle as isize == -1 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(unsafe { File::from_raw_handle(handle) })
+    }
+
+    /// Connect to the running instance
+    pub fn connect(name: &str) -> io::Result<File> {
+        OpenOptions::new().read(true).write(true).open(pipe(name))
+    }
+
+    /// Get the name of the pipe of the user
+    fn pipe(name: &str) -> String {
+        let user = env::var("USERNAME").unwrap_or_default();
+        format!(r"\\.\pipe\neutrino-{}-{}", user, name)
+    }
+
+    /// Get the directory of the lock file, creating it if needed
+    pub fn dir() -> Option<PathBuf> {
+        let dir = PathBuf::from(env::var_os("LOCALAPPDATA")?).join("neutrino");
+        fs::create_dir_all(&dir).ok()?;
+        Some(dir)
+    }
+}
+
+/// The channel of the instances, missing on the other platforms
+#[cfg(not(any(unix, windows)))]
+mod channel {
+    use std::io;
+    use std::path::{Path, PathBuf};
+
+    /// # A listener of the instances
+    pub struct Listener;
+
+    impl Listener {
+        pub fn bind(_dir: &Path, _name: &str) -> Result<Self, String> {
+            Err("no channel between the instances".to_string())
+        }
+
+        pub fn accept(&mut self) -> io::Result<io::Empty> {
+            Err(io::ErrorKind::Unsupported.into())
+        }
+    }
+
+    pub fn connect(_name: &str) -> io::Result<io::Empty> {
+        Err(io::ErrorKind::Unsupported.into())
+    }
+
+    pub fn dir() -> Option<PathBuf> {
+        None
+    }
+}
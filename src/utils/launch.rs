@@ -0,0 +1,260 @@
+//! # Launch arguments
+//!
+//! The command-line arguments of the application are read when it starts
+//! and sent to the window as a `Launch` event right after the first render,
+//! once the widget tree is displayed, so the listener can open the files
+//! given by the file manager or follow a link to the right screen. The event
+//! is handled by `WindowListener::on_launch`, which is also called with the
+//! arguments forwarded by another instance when the application runs as a
+//! single instance (see `instance`).
+//!
+//! The arguments are sorted out as follows:
+//!
+//! * `--name` and `--name=value` are options, as well as `-n`
+//! * the arguments starting with the URL scheme of the window, set with
+//!   `Window::set_scheme`, are links, such as `myapp://item/42?tab=notes`
+//! * the other arguments, and all the arguments after `--`, are files
+//!
+//! Registering the URL scheme with the system, so a link starts the
+//! application with the link as argument, is done by its installer: a key
+//! of the registry on Windows, the `Info.plist` of the bundle on macOS and
+//! the `.desktop` file on Linux.
+//!
+//! ## Example
+//!
+//! ```
+//! use neutrino::testing::Harness;
+//! use neutrino::utils::launch::Launch;
+//! use neutrino::widgets::label::Label;
+//! use neutrino::widgets::tabs::Tabs;
+//! use neutrino::{App, Window, WindowListener};
+//! use neutrino::utils::event::Key;
+//!
+//!
+//! struct MyWindowListener;
+//!
+//! impl WindowListener for MyWindowListener {
+//!     fn on_key(&self, _key: Key) {}
+//!
+//!     fn on_launch(&self, launch: &Launch) {
+//!         for link in launch.links() {
+//!             if link.segment(0) == Some("notes") {
+//!                 App::widget_mut::<Tabs, _>("tabs", |tabs| {
+//!                     tabs.state_mut().set_selected(1);
+//!                 });
+//!             }
+//!         }
+//!     }
+//! }
+//!
+//!
+//! let mut tabs = Tabs::new("tabs");
+//! tabs.add("Items", Box::new(Label::new("items")));
+//! tabs.add("Notes", Box::new(Label::new("notes")));
+//!
+//! let mut my_window = Window::new();
+//! my_window.set_scheme("myapp");
+//! my_window.set_child(Box::new(tabs));
+//! my_window.set_listener(Box::new(MyWindowListener));
+//!
+//! let mut harness = Harness::new(my_window);
+//! harness.launch(&["--verbose", "myapp://notes/42?sort=date", "todo.txt"]);
+//!
+//! let tabs = harness.window().widget::<Tabs>("tabs").unwrap();
+//! assert_eq!(tabs.state().selected(), 1);
+//!
+//! let launch = Launch::parse(&["myapp://notes/42?sort=date"], Some("myapp"));
+//! let link = &launch.links()[0];
+//! assert_eq!(link.route(), "notes/42");
+//! assert_eq!(link.query("sort"), Some("date"));
+//! ```
+
+use std::path::PathBuf;
+
+/// # A link given to the application
+///
+/// ## Fields
+///
+/// ```text
+/// url: String
+/// segments: Vec<String>
+/// query: Vec<(String, String)>
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Link {
+    url: String,
+    segments: Vec<String>,
+    query: Vec<(String, String)>,
+}
+
+impl Link {
+    /// Read a link with the given scheme, None if the URL has another scheme
+    pub fn parse(url: &str, scheme: &str) -> Option<Self> {
+        let prefix = format!("{}:", scheme);
+        let head = url.get(..prefix.len())?;
+        if !head.eq_ignore_ascii_case(&prefix) {
+            return None;
+        }
+        let rest = url[prefix.len()..].trim_start_matches('/');
+        let rest = rest.split('#').next().unwrap_or("");
+        let (path, query) = match rest.split_once('?') {
+            Some((path, query)) => (path, query),
+            None => (rest, ""),
+        };
+        let segments = path
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .map(decode)
+            .collect();
+        let query = query
+            .split('&')
+            .filter(|pair| !pair.is_empty())
+            .map(|pair| match pair.split_once('=') {
+                Some((name, value)) => (decode(name), decode(value)),
+                None => (decode(pair), "".to_string()),
+            })
+            .collect();
+        Some(Self {
+            url: url.to_string(),
+            segments,
+            query,
+        })
+    }
+
+    /// Get the URL
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Get the segments of the path, the host being the first one
+    pub fn segments(&self) -> &Vec<String> {
+        &self.segments
+    }
+
+    /// Get a segment of the path
+    pub fn segment(&self, index: usize) -> Option<&str> {
+        self.segments.get(index).map(String::as_str)
+    }
+
+    /// Get the path, such as `item/42` for `myapp://item/42?tab=notes`
+    pub fn route(&self) -> String {
+        self.segments.join("/")
+    }
+
+    /// Get the parameters of the query
+    pub fn parameters(&self) -> &Vec<(String, String)> {
+        &self.query
+    }
+
+    /// Get the value of a parameter of the query
+    pub fn query(&self, name: &str) -> Option<&str> {
+        self.query
+            .iter()
+            .find(|(parameter, _)| parameter == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// # The arguments given to the application
+///
+/// ## Fields
+///
+/// ```text
+/// options: Vec<(String, Option<String>)>
+/// links: Vec<Link>
+/// files: Vec<PathBuf>
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Launch {
+    options: Vec<(String, Option<String>)>,
+    links: Vec<Link>,
+    files: Vec<PathBuf>,
+}
+
+impl Launch {
+    /// Sort out the arguments, the program name excluded, the links having
+    /// the given scheme
+    pub fn parse<S: AsRef<str>>(args: &[S], scheme: Option<&str>) -> Self {
+        let mut launch = Self::default();
+        let mut options = true;
+        for arg in args {
+            let arg = arg.as_ref();
+            if options && arg == "--" {
+                options = false;
+                continue;
+            }
+            if options && arg.len() > 1 && arg.starts_with('-') {
+                let option = arg.trim_start_matches('-');
+                launch.options.push(match option.split_once('=') {
+                    Some((name, value)) => {
+                        (name.to_string(), Some(value.to_string()))
+                    }
+                    None => (option.to_string(), None),
+                });
+                continue;
+            }
+            match scheme.and_then(|scheme| Link::parse(arg, scheme)) {
+                Some(link) => launch.links.push(link),
+                None => launch.files.push(PathBuf::from(arg)),
+            }
+        }
+        launch
+    }
+
+    /// Get the options, with their values
+    pub fn options(&self) -> &Vec<(String, Option<String>)> {
+        &self.options
+    }
+
+    /// Return true if the option is given
+    pub fn has_option(&self, name: &str) -> bool {
+        self.options.iter().any(|(option, _)| option == name)
+    }
+
+    /// Get the value of an option
+    pub fn option(&self, name: &str) -> Option<&str> {
+        self.options
+            .iter()
+            .find(|(option, _)| option == name)
+            .and_then(|(_, value)| value.as_deref())
+    }
+
+    /// Get the links
+    pub fn links(&self) -> &Vec<Link> {
+        &self.links
+    }
+
+    /// Get the files
+    pub fn files(&self) -> &Vec<PathBuf> {
+        &self.files
+    }
+
+    /// Return true if no argument is given
+    pub fn is_empty(&self) -> bool {
+        self.options.is_empty()
+            && self.links.is_empty()
+            && self.files.is_empty()
+    }
+}
+
+/// Decode the percent-encoded characters of a part of a URL, a `+` being a
+/// space
+fn decode(part: &str) -> String {
+    let bytes = part.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = part.get(i + 1..i + 3);
+        match hex.map(|hex| u8::from_str_radix(hex, 16)) {
+            Some(Ok(byte)) if bytes[i] == b'%' => {
+                decoded.push(byte);
+                i += 3;
+            }
+            _ => {
+                decoded.push(if bytes[i] == b'+' { b' ' } else { bytes[i] });
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).to_string()
+}
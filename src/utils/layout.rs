@@ -0,0 +1,1129 @@
+//! # Declarative layouts
+//!
+//! A layout describes a window and its tree of widgets, so it can be tweaked
+//! without recompiling the application. It is loaded at startup by
+//! `App::from_file`, which returns the Window to run. The listeners are
+//! written in Rust and bound to the widgets by name beforehand, through
+//! `Bindings`.
+//!
+//! A layout is written in JSON, RON or YAML, the format of a file being
+//! given by its extension: `.ron` for RON, `.yaml` or `.yml` for YAML, and
+//! JSON otherwise. The three formats describe the same objects: a RON layout
+//! uses maps or structs whose names are ignored, such as
+//! `(type: "Button", name: "save")`, and a YAML layout uses mappings.
+//!
+//! The window has the following properties, all optional:
+//!
+//! * `title`, `width` and `height`
+//! * the `resizable`, `kiosk` and `block_shortcuts` flags
+//! * a `theme`: Adwaita, Breeze, Default, OSX or a theme of a plugin
+//! * a `child`, the root widget
+//!
+//! Each widget is an object with a `type`, a `name` and the properties of
+//! its builder. Every widget may have a `layout` object with the options of
+//! `LayoutOptions`: a `grow` and a `shrink` factor, an `align` (Start,
+//! Center, End or Stretch), a `padding` and a `margin`, given as one value or
+//! as a list of 2 or 4 values, a fixed `width` and `height`, and a
+//! `transition` (Fade or Slide). Most widgets also accept an `aria_label`
+//! and a `stretched` flag. The other properties are:
+//!
+//! * Button: `text`, `text_key` and the `disabled` flag
+//! * CheckBox: `text`, `text_key` and the `checked` flag
+//! * Combo: the `choices`, strings or `{ "value": ..., "text": ... }`
+//!   objects with an optional `group` and a `disabled` flag, and the index
+//!   of the `selected` one
+//! * Container: a `direction` (Horizontal or Vertical), a `position`
+//!   (Start, Center, End, Between or Around), an `alignment` (None, Start,
+//!   Center or End), the `scrollable` flag, a resizable `size`, the `wrap`
+//!   flag, a `hide_below` width, a list of `{ "width": ..., "direction": ...
+//!   }` `breakpoints` and a list of `children`
+//! * Label: `text` and `text_key`
+//! * ListBox: the `items`, the `multiple` flag and the list of the indexes
+//!   of the `selected` items
+//! * ProgressBar: `min`, `max`, `value` and the `indeterminate` flag
+//! * Radio: the `choices` and the index of the `selected` one
+//! * Range: `min`, `max`, `step`, `value`, the `high` value of a double
+//!   range, and the `vertical` and `bubble` flags
+//! * SpinBox: `min`, `max`, `step`, `decimals`, `value` and the `localized`
+//!   flag, writing the value the way the locale does
+//! * Spinner: `text` and the `inactive` flag
+//! * Tabs: a list of `{ "title": ..., "child": ... }` `tabs` with an
+//!   optional `badge`, the index of the `selected` one, a `transition` and
+//!   the `closable` and `reorderable` flags
+//! * TextInput: `value`, `size`, an `input_type` (Text, Password, Email or
+//!   Number), a `pattern`, a `mask`, and the `change` mode: sending the
+//!   value on Blur by default or on each Input, or when the typing pauses
+//!   for a `debounce` delay in milliseconds, or at most once per `throttle`
+//!   delay
+//!
+//! A `text_key` is translated (see `i18n`). The widgets and themes of the
+//! registered plugins are supported too (see `plugin`). The menubar, the
+//! icons and the listener of the window are set on the loaded Window. In
+//! debug builds, the layout can be reloaded when it changes on disk (see
+//! `watcher`).
+//!
+//! ## Example
+//!
+//! ```
+//! use neutrino::utils::layout::{self, Bindings};
+//! use neutrino::widgets::button::{ButtonListener, ButtonState};
+//!
+//!
+//! struct MyButtonListener;
+//!
+//! impl ButtonListener for MyButtonListener {
+//!     fn on_change(&self, _state: &ButtonState) {
+//!         println!("Saved");
+//!     }
+//!
+//!     fn on_update(&self, _state: &mut ButtonState) {}
+//! }
+//!
+//!
+//! let description = r#"{
+//!     "title": "Editor",
+//!     "width": 320,
+//!     "height": 240,
+//!     "theme": "Breeze",
+//!     "child": {
+//!         "type": "Container",
+//!         "name": "root",
+//!         "direction": "Vertical",
+//!         "children": [
+//!             {
+//!                 "type": "TextInput",
+//!                 "name": "title",
+//!                 "value": "Untitled",
+//!                 "layout": { "grow": 2, "padding": [4, 8] }
+//!             },
+//!             { "type": "Button", "name": "save", "text": "Save", "stretched": true }
+//!         ]
+//!     }
+//! }"#;
+//!
+//! let mut bindings = Bindings::new();
+//! bindings.bind::<dyn ButtonListener>("save", Box::new(MyButtonListener));
+//!
+//! let window = layout::load(description, bindings).unwrap();
+//! ```
+//!
+//! The same kind of layout in RON and in YAML:
+//!
+//! ```
+//! use neutrino::utils::layout::{self, Bindings, Format};
+//!
+//! let ron = r#"(
+//!     title: "Editor",
+//!     child: (
+//!         type: "Container",
+//!         name: "root",
+//!         children: [
+//!             (type: "TextInput", name: "title", value: "Untitled"),
+//!             (type: "Button", name: "save", text: "Save"),
+//!         ],
+//!     ),
+//! )"#;
+//!
+//! let yaml = r#"
+//! title: Editor
+//! child:
+//!   type: Container
+//!   name: root
+//!   children:
+//!     - { type: TextInput, name: title, value: Untitled }
+//!     - { type: Button, name: save, text: Save }
+//! "#;
+//!
+//! let window = layout::load_as(ron, Format::Ron, Bindings::new()).unwrap();
+//! assert_eq!(window.title(), "Editor");
+//!
+//! let window = layout::load_as(yaml, Format::Yaml, Bindings::new()).unwrap();
+//! assert_eq!(window.title(), "Editor");
+//!
+//! assert_eq!(Format::of("ui.ron".as_ref()), Format::Ron);
+//! ```
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use json::{self, JsonValue};
+
+use crate::utils::animation::Transition;
+use crate::utils::layoutoptions::{Align, LayoutOptions, Spacing};
+use crate::utils::plugin;
+use crate::utils::theme::Theme;
+use crate::widgets::button::{Button, ButtonListener};
+use crate::widgets::checkbox::{CheckBox, CheckBoxListener};
+use crate::widgets::combo::{Combo, ComboListener};
+use crate::widgets::container::{
+    Alignment, Container, ContainerListener, Direction, Position,
+};
+use crate::widgets::label::{Label, LabelListener};
+use crate::widgets::listbox::{ListBox, ListBoxListener};
+use crate::widgets::progressbar::{ProgressBar, ProgressBarListener};
+use crate::widgets::radio::{Radio, RadioListener};
+use crate::widgets::range::{Range, RangeListener};
+use crate::widgets::spinbox::{SpinBox, SpinBoxListener};
+use crate::widgets::spinner::{Spinner, SpinnerListener};
+use crate::widgets::tabs::{Tabs, TabsListener};
+use crate::widgets::textinput::{
+    ChangeMode, InputType, TextInput, TextInputListener,
+};
+use crate::widgets::widget::Widget;
+use crate::Window;
+
+/// # The listeners bound to the widgets of a layout by name
+///
+/// A listener is bound with the type of the listener of its widget, such as
+/// `bindings.bind::<dyn ButtonListener>("save", Box::new(listener))`.
+///
+/// ## Fields
+///
+/// ```text
+/// listeners: HashMap<String, Box<dyn Any>>
+/// ```
+pub struct Bindings {
+    listeners: HashMap<String, Box<dyn Any>>,
+}
+
+impl Bindings {
+    /// Create a Bindings
+    pub fn new() -> Self {
+        Self {
+            listeners: HashMap::new(),
+        }
+    }
+
+    /// Bind a listener to the widget with the given name
+    pub fn bind<L: ?Sized + 'static>(&mut self, name: &str, listener: Box<L>) {
+        self.listeners.insert(name.to_string(), Box::new(listener));
+    }
+
+    /// Take the listener bound to the widget with the given name, failing if
+    /// it is not a listener of this kind of widget
+    pub fn take<L: ?Sized + 'static>(
+        &mut self,
+        name: &str,
+    ) -> Result<Option<Box<L>>, String> {
+        match self.listeners.remove(name) {
+            None => Ok(None),
+            Some(listener) => match listener.downcast::<Box<L>>() {
+                Ok(listener) => Ok(Some(*listener)),
+                Err(_) => Err(format!(
+                    "{}: the bound listener does not match the widget",
+                    name
+                )),
+            },
+        }
+    }
+}
+
+impl Default for Bindings {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// # The format of a layout
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Json,
+    Ron,
+    Yaml,
+}
+
+impl Format {
+    /// Get the format of a file from its extension: RON for `.ron`, YAML
+    /// for `.yaml` and `.yml`, and JSON otherwise
+    pub fn of(path: &Path) -> Self {
+        let extension = path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .unwrap_or("")
+            .to_lowercase();
+        match extension.as_str() {
+            "ron" => Format::Ron,
+            "yaml" | "yml" => Format::Yaml,
+            _ => Format::Json,
+        }
+    }
+
+    /// Parse a description written in this format into the JSON value of
+    /// the same objects
+    fn parse(self, description: &str) -> Result<JsonValue, String> {
+        match self {
+            Format::Json => {
+                json::parse(description).map_err(|error| error.to_string())
+            }
+            Format::Ron => ron::from_str::<ron::Value>(description)
+                .map_err(|error| error.to_string())
+                .and_then(|value| from_ron(&value)),
+            Format::Yaml => {
+                serde_yaml::from_str::<serde_yaml::Value>(description)
+                    .map_err(|error| error.to_string())
+                    .and_then(|value| from_yaml(&value))
+            }
+        }
+    }
+}
+
+/// Load the window described by the file at the given path, in the format
+/// given by its extension
+pub fn load_file<P: AsRef<Path>>(
+    path: P,
+    bindings: Bindings,
+) -> Result<Window, String> {
+    let mut window = Window::new();
+    apply(&read(path.as_ref())?, bindings, &mut window)?;
+    Ok(window)
+}
+
+/// Load the window described by a JSON string. Every listener must be bound
+/// to a widget of the layout.
+pub fn load(description: &str, bindings: Bindings) -> Result<Window, String> {
+    load_as(description, Format::Json, bindings)
+}
+
+/// Load the window described by a string in the given format
+pub fn load_as(
+    description: &str,
+    format: Format,
+    bindings: Bindings,
+) -> Result<Window, String> {
+    let mut window = Window::new();
+    apply(&format.parse(description)?, bindings, &mut window)?;
+    Ok(window)
+}
+
+/// Replace the widget tree and the theme of a running window by those of the
+/// file at the given path. The window is left untouched on error.
+pub(crate) fn reload(
+    path: &Path,
+    bindings: Bindings,
+    window: &mut Window,
+) -> Result<(), String> {
+    apply(&read(path)?, bindings, window)
+}
+
+/// Read a layout file, in the format given by its extension
+fn read(path: &Path) -> Result<JsonValue, String> {
+    let error = |error: String| format!("{}: {}", path.display(), error);
+    let description =
+        fs::read_to_string(path).map_err(|e| error(e.to_string()))?;
+    Format::of(path).parse(&description).map_err(error)
+}
+
+/// Convert a RON value into a JSON value
+fn from_ron(value: &ron::Value) -> Result<JsonValue, String> {
+    Ok(match value {
+        ron::Value::Bool(value) => (*value).into(),
+        ron::Value::Char(value) => value.to_string().into(),
+        ron::Value::String(value) => value.as_str().into(),
+        ron::Value::Number(ron::Number::Integer(value)) => (*value).into(),
+        ron::Value::Number(ron::Number::Float(value)) => value.get().into(),
+        ron::Value::Option(Some(value)) => from_ron(value)?,
+        ron::Value::Option(None) | ron::Value::Unit => JsonValue::Null,
+        ron::Value::Seq(values) => JsonValue::Array(
+            values.iter().map(from_ron).collect::<Result<_, _>>()?,
+        ),
+        ron::Value::Map(map) => {
+            let mut object = JsonValue::new_object();
+            for (key, value) in map.iter() {
+                match key {
+                    ron::Value::String(key) => {
+                        object[key.as_str()] = from_ron(value)?
+                    }
+                    _ => return Err("the keys must be strings".to_string()),
+                }
+            }
+            object
+        }
+    })
+}
+
+/// Convert a YAML value into a JSON value
+fn from_yaml(value: &serde_yaml::Value) -> Result<JsonValue, String> {
+    Ok(match value {
+        serde_yaml::Value::Null => JsonValue::Null,
+        serde_yaml::Value::Bool(value) => (*value).into(),
+        serde_yaml::Value::String(value) => value.as_str().into(),
+        serde_yaml::Value::Number(number) => match number.as_i64() {
+            Some(value) => value.into(),
+            None => number.as_f64().unwrap_or(0.0).into(),
+        },
+        serde_yaml::Value::Sequence(values) => JsonValue::Array(
+            values.iter().map(from_yaml).collect::<Result<_, _>>()?,
+        ),
+        serde_yaml::Value::Mapping(mapping) => {
+            let mut object = JsonValue::new_object();
+            for (key, value) in mapping {
+                match key {
+                    serde_yaml::Value::String(key) => {
+                        object[key.as_str()] = from_yaml(value)?
+                    }
+                    _ => return Err("the keys must be strings".to_string()),
+                }
+            }
+            object
+        }
+        serde_yaml::Value::Tagged(tagged) => from_yaml(&tagged.value)?,
+    })
+}
+
+/// Apply a description to a window, once it has been fully read
+fn apply(
+    root: &JsonValue,
+    mut bindings: Bindings,
+    window: &mut Window,
+) -> Result<(), String> {
+    let properties = Properties {
+        name: "window",
+        value: root,
+    };
+    let title = properties.string("title")?;
+    let width = properties.integer("width")?;
+    let height = properties.integer("height")?;
+    let resizable = properties.flag("resizable")?;
+    let kiosk = properties.flag("kiosk")?;
+    let block_shortcuts = properties.flag("block_shortcuts")?;
+    let theme = match properties.string("theme")? {
+        None => None,
+        Some("Adwaita") => Some(Ok(Theme::Adwaita)),
+        Some("Breeze") => Some(Ok(Theme::Breeze)),
+        Some("Default") => Some(Ok(Theme::Default)),
+        Some("OSX") => Some(Ok(Theme::OSX)),
+        Some(theme) if plugin::theme(theme).is_some() => Some(Err(theme)),
+        Some(theme) => {
+            return Err(format!("window: unknown theme {}", theme));
+        }
+    };
+    let child = if root["child"].is_null() {
+        None
+    } else {
+        Some(widget(&root["child"], &mut bindings)?)
+    };
+    let mut unbound: Vec<&String> = bindings.listeners.keys().collect();
+    unbound.sort();
+    if let Some(name) = unbound.first() {
+        return Err(format!("{}: no widget has this name", name));
+    }
+    if let Some(title) = title {
+        window.set_title(title);
+    }
+    if width.is_some() || height.is_some() {
+        window.set_size(width.unwrap_or(640), height.unwrap_or(480));
+    }
+    if resizable {
+        window.set_resizable();
+    }
+    if kiosk {
+        window.set_kiosk();
+    }
+    if block_shortcuts {
+        window.set_block_shortcuts();
+    }
+    match theme {
+        None => (),
+        Some(Ok(theme)) => window.set_theme(theme),
+        Some(Err(plugin_theme)) => window.set_plugin_theme(plugin_theme)?,
+    }
+    if let Some(child) = child {
+        window.set_child(child);
+    }
+    Ok(())
+}
+
+/// Build a widget and its children, for instance the children of a
+/// container of a plugin
+pub fn widget(
+    value: &JsonValue,
+    bindings: &mut Bindings,
+) -> Result<Box<dyn Widget>, String> {
+    if !value.is_object() {
+        return Err("a widget must be an object".to_string());
+    }
+    let name = match value["name"].as_str() {
+        Some(name) => name,
+        None => return Err("a widget has no name".to_string()),
+    };
+    let properties = Properties { name, value };
+    let kind = match properties.string("type")? {
+        Some(kind) => kind,
+        None => return Err(format!("{}: the widget has no type", name)),
+    };
+    match kind {
+        "Button" => {
+            let mut button = Button::new(name);
+            if let Some(text) = properties.string("text")? {
+                button.set_text(text);
+            }
+            if let Some(key) = properties.string("text_key")? {
+                button.set_text_key(key);
+            }
+            if properties.flag("disabled")? {
+                button.set_disabled();
+            }
+            if let Some(label) = properties.string("aria_label")? {
+                button.set_aria_label(label);
+            }
+            if let Some(layout) = layout_options(&properties)? {
+                button.set_layout(layout);
+            }
+            if properties.flag("stretched")? {
+                button.set_stretched();
+            }
+            if let Some(listener) = bindings.take::<dyn ButtonListener>(name)? {
+                button.set_listener(listener);
+            }
+            Ok(Box::new(button))
+        }
+        "CheckBox" => {
+            let mut checkbox = CheckBox::new(name);
+            if let Some(text) = properties.string("text")? {
+                checkbox.set_text(text);
+            }
+            if let Some(key) = properties.string("text_key")? {
+                checkbox.set_text_key(key);
+            }
+            if properties.flag("checked")? {
+                checkbox.set_checked();
+            }
+            if let Some(label) = properties.string("aria_label")? {
+                checkbox.set_aria_label(label);
+            }
+            if let Some(layout) = layout_options(&properties)? {
+                checkbox.set_layout(layout);
+            }
+            if properties.flag("stretched")? {
+                checkbox.set_stretched();
+            }
+            if let Some(listener) =
+                bindings.take::<dyn CheckBoxListener>(name)?
+            {
+                checkbox.set_listener(listener);
+            }
+            Ok(Box::new(checkbox))
+        }
+        "Combo" => {
+            let mut combo = Combo::new(name);
+            let choices = properties.list("choices")?;
+            if choices.iter().all(|choice| choice.is_string()) {
+                if let Some(choices) = properties.strings("choices")? {
+                    combo.set_choices(choices);
+                }
+            } else {
+                combo.set_choices(vec![]);
+                for (i, value) in choices.into_iter().enumerate() {
+                    if let Some(text) = value.as_str() {
+                        combo.add_choice(text, text);
+                        continue;
+                    }
+                    let choice = Properties { name, value };
+                    let text = choice.string("text")?.ok_or(format!(
+                        "{}: a choice must have a text",
+                        name
+                    ))?;
+                    let value = choice.string("value")?.unwrap_or(text);
+                    combo.add_choice(value, text);
+                    let state = combo.state_mut();
+                    state.set_group(i as u32, choice.string("group")?);
+                    state.set_disabled(i as u32, choice.flag("disabled")?);
+                }
+            }
+            if let Some(selected) = properties.integer("selected")? {
+                combo.set_selected(selected as u32);
+            }
+            if let Some(label) = properties.string("aria_label")? {
+                combo.set_aria_label(label);
+            }
+            if let Some(layout) = layout_options(&properties)? {
+                combo.set_layout(layout);
+            }
+            if properties.flag("stretched")? {
+                combo.set_stretched();
+            }
+            if let Some(listener) = bindings.take::<dyn ComboListener>(name)? {
+                combo.set_listener(listener);
+            }
+            Ok(Box::new(combo))
+        }
+        "Container" => {
+            let mut container = Container::new(name);
+            if let Some(direction) = properties.string("direction")? {
+                container.set_direction(self::direction(name, direction)?);
+            }
+            match properties.string("position")? {
+                None => (),
+                Some("Center") => container.set_position(Position::Center),
+                Some("Start") => container.set_position(Position::Start),
+                Some("End") => container.set_position(Position::End),
+                Some("Between") => container.set_position(Position::Between),
+                Some("Around") => container.set_position(Position::Around),
+                Some(position) => {
+                    return Err(format!(
+                        "{}: unknown position {}",
+                        name, position
+                    ));
+                }
+            }
+            match properties.string("alignment")? {
+                None => (),
+                Some("None") => container.set_alignment(Alignment::None),
+                Some("Center") => container.set_alignment(Alignment::Center),
+                Some("Start") => container.set_alignment(Alignment::Start),
+                Some("End") => container.set_alignment(Alignment::End),
+                Some(alignment) => {
+                    return Err(format!(
+                        "{}: unknown alignment {}",
+                        name, alignment
+                    ));
+                }
+            }
+            if let Some(layout) = layout_options(&properties)? {
+                container.set_layout(layout);
+            }
+            if properties.flag("stretched")? {
+                container.set_stretched();
+            }
+            if properties.flag("scrollable")? {
+                container.set_scrollable();
+            }
+            if let Some(size) = properties.number("size")? {
+                container.set_size(size);
+            }
+            if properties.flag("wrap")? {
+                container.set_wrap();
+            }
+            if let Some(width) = properties.integer("hide_below")? {
+                container.set_hide_below(width);
+            }
+            for breakpoint in properties.list("breakpoints")? {
+                let breakpoint = Properties {
+                    name,
+                    value: breakpoint,
+                };
+                match (
+                    breakpoint.integer("width")?,
+                    breakpoint.string("direction")?,
+                ) {
+                    (Some(width), Some(direction)) => container.add_breakpoint(
+                        width,
+                        self::direction(name, direction)?,
+                    ),
+                    _ => {
+                        return Err(format!(
+                            "{}: a breakpoint needs a width and a direction",
+                            name
+                        ));
+                    }
+                }
+            }
+            for child in properties.list("children")? {
+                container.add(widget(child, bindings)?);
+            }
+            if let Some(listener) =
+                bindings.take::<dyn ContainerListener>(name)?
+            {
+                container.set_listener(listener);
+            }
+            Ok(Box::new(container))
+        }
+        "Label" => {
+            let mut label = Label::new(name);
+            if let Some(text) = properties.string("text")? {
+                label.set_text(text);
+            }
+            if let Some(key) = properties.string("text_key")? {
+                label.set_text_key(key);
+            }
+            if let Some(layout) = layout_options(&properties)? {
+                label.set_layout(layout);
+            }
+            if properties.flag("stretched")? {
+                label.set_stretched();
+            }
+            if let Some(listener) = bindings.take::<dyn LabelListener>(name)? {
+                label.set_listener(listener);
+            }
+            Ok(Box::new(label))
+        }
+        "ListBox" => {
+            let mut listbox = ListBox::new(name);
+            if let Some(items) = properties.strings("items")? {
+                listbox.set_items(items);
+            }
+            if properties.flag("multiple")? {
+                listbox.set_multiple();
+            }
+            let selected = properties.list("selected")?;
+            if !selected.is_empty() {
+                let selected = selected
+                    .iter()
+                    .map(|index| index.as_u32())
+                    .collect::<Option<Vec<u32>>>()
+                    .ok_or(format!(
+                        "{}: selected must be a list of integers",
+                        name
+                    ))?;
+                listbox.set_selected(selected);
+            }
+            if let Some(label) = properties.string("aria_label")? {
+                listbox.set_aria_label(label);
+            }
+            if let Some(layout) = layout_options(&properties)? {
+                listbox.set_layout(layout);
+            }
+            if properties.flag("stretched")? {
+                listbox.set_stretched();
+            }
+            if let Some(listener) =
+                bindings.take::<dyn ListBoxListener>(name)?
+            {
+                listbox.set_listener(listener);
+            }
+            Ok(Box::new(listbox))
+        }
+        "ProgressBar" => {
+            let mut progressbar = ProgressBar::new(name);
+            if let Some(min) = properties.integer("min")? {
+                progressbar.set_min(min);
+            }
+            if let Some(max) = properties.integer("max")? {
+                progressbar.set_max(max);
+            }
+            if let Some(value) = properties.integer("value")? {
+                progressbar.set_value(value);
+            }
+            if properties.flag("indeterminate")? {
+                progressbar.set_indeterminate();
+            }
+            if let Some(label) = properties.string("aria_label")? {
+                progressbar.set_aria_label(label);
+            }
+            if let Some(layout) = layout_options(&properties)? {
+                progressbar.set_layout(layout);
+            }
+            if properties.flag("stretched")? {
+                progressbar.set_stretched();
+            }
+            if let Some(listener) =
+                bindings.take::<dyn ProgressBarListener>(name)?
+            {
+                progressbar.set_listener(listener);
+            }
+            Ok(Box::new(progressbar))
+        }
+        "Radio" => {
+            let mut radio = Radio::new(name);
+            if let Some(choices) = properties.strings("choices")? {
+                radio.set_choices(choices);
+            }
+            if let Some(selected) = properties.integer("selected")? {
+                radio.set_selected(selected as u32);
+            }
+            if let Some(label) = properties.string("aria_label")? {
+                radio.set_aria_label(label);
+            }
+            if let Some(layout) = layout_options(&properties)? {
+                radio.set_layout(layout);
+            }
+            if properties.flag("stretched")? {
+                radio.set_stretched();
+            }
+            if let Some(listener) = bindings.take::<dyn RadioListener>(name)? {
+                radio.set_listener(listener);
+            }
+            Ok(Box::new(radio))
+        }
+        "Range" => {
+            let mut range = Range::new(name);
+            if let Some(min) = properties.integer("min")? {
+                range.set_min(min);
+            }
+            if let Some(max) = properties.integer("max")? {
+                range.set_max(max);
+            }
+            if let Some(step) = properties.integer("step")? {
+                range.set_step(step);
+            }
+            if let Some(value) = properties.integer("value")? {
+                range.set_value(value);
+            }
+            if let Some(high) = properties.integer("high")? {
+                range.state_mut().set_high(Some(high));
+            }
+            if properties.flag("vertical")? {
+                range.set_vertical();
+            }
+            if properties.flag("bubble")? {
+                range.set_bubble();
+            }
+            if let Some(label) = properties.string("aria_label")? {
+                range.set_aria_label(label);
+            }
+            if let Some(layout) = layout_options(&properties)? {
+                range.set_layout(layout);
+            }
+            if properties.flag("stretched")? {
+                range.set_stretched();
+            }
+            if let Some(listener) = bindings.take::<dyn RangeListener>(name)? {
+                range.set_listener(listener);
+            }
+            Ok(Box::new(range))
+        }
+        "SpinBox" => {
+            let mut spinbox = SpinBox::new(name);
+            if let Some(min) = properties.number("min")? {
+                spinbox.set_min(min);
+            }
+            if let Some(max) = properties.number("max")? {
+                spinbox.set_max(max);
+            }
+            if let Some(step) = properties.number("step")? {
+                spinbox.set_step(step);
+            }
+            if let Some(decimals) = properties.integer("decimals")? {
+                spinbox.set_decimals(decimals as u32);
+            }
+            if let Some(value) = properties.number("value")? {
+                spinbox.set_value(value);
+            }
+            if properties.flag("localized")? {
+                spinbox.set_localized();
+            }
+            if let Some(layout) = layout_options(&properties)? {
+                spinbox.set_layout(layout);
+            }
+            if properties.flag("stretched")? {
+                spinbox.set_stretched();
+            }
+            if let Some(listener) =
+                bindings.take::<dyn SpinBoxListener>(name)?
+            {
+                spinbox.set_listener(listener);
+            }
+            Ok(Box::new(spinbox))
+        }
+        "Spinner" => {
+            let mut spinner = Spinner::new(name);
+            if let Some(text) = properties.string("text")? {
+                spinner.set_text(text);
+            }
+            if properties.flag("inactive")? {
+                spinner.set_inactive();
+            }
+            if let Some(label) = properties.string("aria_label")? {
+                spinner.set_aria_label(label);
+            }
+            if let Some(layout) = layout_options(&properties)? {
+                spinner.set_layout(layout);
+            }
+            if let Some(listener) =
+                bindings.take::<dyn SpinnerListener>(name)?
+            {
+                spinner.set_listener(listener);
+            }
+            Ok(Box::new(spinner))
+        }
+        "Tabs" => {
+            let mut tabs = Tabs::new(name);
+            let list = properties.list("tabs")?;
+            for (index, tab) in list.into_iter().enumerate() {
+                let tab = Properties { name, value: tab };
+                let title = match tab.string("title")? {
+                    Some(title) => title,
+                    None => {
+                        return Err(format!("{}: a tab has no title", name));
+                    }
+                };
+                if tab.value["child"].is_null() {
+                    return Err(format!("{}: a tab has no child", name));
+                }
+                tabs.add(title, widget(&tab.value["child"], bindings)?);
+                if let Some(badge) = tab.string("badge")? {
+                    tabs.set_badge(index as u32, badge);
+                }
+            }
+            if let Some(selected) = properties.integer("selected")? {
+                tabs.set_selected(selected as u32);
+            }
+            if let Some(transition) = properties.string("transition")? {
+                tabs.set_transition(self::transition(name, transition)?);
+            }
+            if let Some(label) = properties.string("aria_label")? {
+                tabs.set_aria_label(label);
+            }
+            if let Some(layout) = layout_options(&properties)? {
+                tabs.set_layout(layout);
+            }
+            if properties.flag("stretched")? {
+                tabs.set_stretched();
+            }
+            if properties.flag("closable")? {
+                tabs.set_closable();
+            }
+            if properties.flag("reorderable")? {
+                tabs.set_reorderable();
+            }
+            if let Some(listener) = bindings.take::<dyn TabsListener>(name)? {
+                tabs.set_listener(listener);
+            }
+            Ok(Box::new(tabs))
+        }
+        "TextInput" => {
+            let mut textinput = TextInput::new(name);
+            if let Some(value) = properties.string("value")? {
+                textinput.set_value(value);
+            }
+            if let Some(size) = properties.integer("size")? {
+                textinput.set_size(size as u32);
+            }
+            match properties.string("input_type")? {
+                None => (),
+                Some("Text") => textinput.set_input_type(InputType::Text),
+                Some("Password") => {
+                    textinput.set_input_type(InputType::Password)
+                }
+                Some("Email") => textinput.set_input_type(InputType::Email),
+                Some("Number") => textinput.set_input_type(InputType::Number),
+                Some(input_type) => {
+                    return Err(format!(
+                        "{}: unknown input type {}",
+                        name, input_type
+                    ));
+                }
+            }
+            if let Some(pattern) = properties.string("pattern")? {
+                textinput.set_pattern(pattern);
+            }
+            if let Some(mask) = properties.string("mask")? {
+                textinput.set_mask(mask);
+            }
+            match properties.string("change")? {
+                None => (),
+                Some("Blur") => textinput.set_change_mode(ChangeMode::Blur),
+                Some("Input") => textinput.set_change_mode(ChangeMode::Input),
+                Some(change) => {
+                    return Err(format!(
+                        "{}: unknown change mode {}",
+                        name, change
+                    ));
+                }
+            }
+            if let Some(delay) = properties.integer("debounce")? {
+                textinput.set_change_mode(ChangeMode::Debounce(delay as u32));
+            }
+            if let Some(delay) = properties.integer("throttle")? {
+                textinput.set_change_mode(ChangeMode::Throttle(delay as u32));
+            }
+            if let Some(label) = properties.string("aria_label")? {
+                textinput.set_aria_label(label);
+            }
+            if let Some(layout) = layout_options(&properties)? {
+                textinput.set_layout(layout);
+            }
+            if properties.flag("stretched")? {
+                textinput.set_stretched();
+            }
+            if let Some(listener) =
+                bindings.take::<dyn TextInputListener>(name)?
+            {
+                textinput.set_listener(listener);
+            }
+            Ok(Box::new(textinput))
+        }
+        kind => match plugin::factory(kind) {
+            Some(factory) => factory(name, value, bindings),
+            None => Err(format!("{}: unknown widget type {}", name, kind)),
+        },
+    }
+}
+
+/// Read the layout options of a widget, if it has a `layout` object
+fn layout_options(
+    properties: &Properties,
+) -> Result<Option<LayoutOptions>, String> {
+    let value = &properties.value["layout"];
+    if value.is_null() {
+        return Ok(None);
+    }
+    if !value.is_object() {
+        return Err(format!("{}: layout must be an object", properties.name));
+    }
+    let layout = Properties {
+        name: properties.name,
+        value,
+    };
+    let mut options = LayoutOptions::new();
+    if let Some(grow) = layout.integer("grow")? {
+        options.set_grow(grow as u32);
+    }
+    if let Some(shrink) = layout.integer("shrink")? {
+        options.set_shrink(shrink as u32);
+    }
+    match layout.string("align")? {
+        None => (),
+        Some("Start") => options.set_align(Align::Start),
+        Some("Center") => options.set_align(Align::Center),
+        Some("End") => options.set_align(Align::End),
+        Some("Stretch") => options.set_align(Align::Stretch),
+        Some(align) => {
+            return Err(format!("{}: unknown align {}", layout.name, align));
+        }
+    }
+    if let Some(padding) = spacing(&layout, "padding")? {
+        options.set_padding(padding);
+    }
+    if let Some(margin) = spacing(&layout, "margin")? {
+        options.set_margin(margin);
+    }
+    if let Some(width) = layout.integer("width")? {
+        options.set_width(width as u32);
+    }
+    if let Some(height) = layout.integer("height")? {
+        options.set_height(height as u32);
+    }
+    if let Some(transition) = layout.string("transition")? {
+        options.set_transition(self::transition(layout.name, transition)?);
+    }
+    Ok(Some(options))
+}
+
+/// Read a spacing of the layout options, either a single value or a list of
+/// two (vertical, horizontal) or four (top, right, bottom, left) values
+fn spacing(layout: &Properties, key: &str) -> Result<Option<Spacing>, String> {
+    if layout.value[key].is_null() {
+        return Ok(None);
+    }
+    if let Some(value) = layout.value[key].as_u32() {
+        return Ok(Some(Spacing::all(value)));
+    }
+    let values = layout
+        .list(key)?
+        .iter()
+        .map(|value| value.as_u32())
+        .collect::<Option<Vec<u32>>>();
+    match values.as_deref() {
+        Some(&[vertical, horizontal]) => {
+            Ok(Some(Spacing::symmetric(vertical, horizontal)))
+        }
+        Some(&[top, right, bottom, left]) => {
+            Ok(Some(Spacing::new(top, right, bottom, left)))
+        }
+        _ => Err(format!(
+            "{}: {} must be an integer or a list of 2 or 4 integers",
+            layout.name, key
+        )),
+    }
+}
+
+/// Read a transition
+fn transition(name: &str, transition: &str) -> Result<Transition, String> {
+    match transition {
+        "Fade" => Ok(Transition::Fade),
+        "Slide" => Ok(Transition::Slide),
+        transition => {
+            Err(format!("{}: unknown transition {}", name, transition))
+        }
+    }
+}
+
+/// Read the direction of a Container
+fn direction(name: &str, direction: &str) -> Result<Direction, String> {
+    match direction {
+        "Horizontal" => Ok(Direction::Horizontal),
+        "Vertical" => Ok(Direction::Vertical),
+        direction => Err(format!("{}: unknown direction {}", name, direction)),
+    }
+}
+
+/// # The properties of a widget, read with the name of the widget in the
+/// errors
+///
+/// ## Fields
+///
+/// ```text
+/// name: &str
+/// value: &JsonValue
+/// ```
+struct Properties<'a> {
+    name: &'a str,
+    value: &'a JsonValue,
+}
+
+impl<'a> Properties<'a> {
+    /// Get a string property
+    fn string(&self, key: &str) -> Result<Option<&'a str>, String> {
+        let value = &self.value[key];
+        if value.is_null() {
+            return Ok(None);
+        }
+        value
+            .as_str()
+            .map(Some)
+            .ok_or(format!("{}: {} must be a string", self.name, key))
+    }
+
+    /// Get a boolean property, false if it is missing
+    fn flag(&self, key: &str) -> Result<bool, String> {
+        let value = &self.value[key];
+        if value.is_null() {
+            return Ok(false);
+        }
+        value
+            .as_bool()
+            .ok_or(format!("{}: {} must be a boolean", self.name, key))
+    }
+
+    /// Get an integer property
+    fn integer(&self, key: &str) -> Result<Option<i32>, String> {
+        let value = &self.value[key];
+        if value.is_null() {
+            return Ok(None);
+        }
+        value
+            .as_i32()
+            .map(Some)
+            .ok_or(format!("{}: {} must be an integer", self.name, key))
+    }
+
+    /// Get a number property
+    fn number(&self, key: &str) -> Result<Option<f64>, String> {
+        let value = &self.value[key];
+        if value.is_null() {
+            return Ok(None);
+        }
+        value
+            .as_f64()
+            .map(Some)
+            .ok_or(format!("{}: {} must be a number", self.name, key))
+    }
+
+    /// Get a list property, empty if it is missing
+    fn list(&self, key: &str) -> Result<Vec<&'a JsonValue>, String> {
+        let value = &self.value[key];
+        if value.is_null() {
+            return Ok(vec![]);
+        }
+        if !value.is_array() {
+            return Err(format!("{}: {} must be a list", self.name, key));
+        }
+        Ok(value.members().collect())
+    }
+
+    /// Get a list of strings property
+    fn strings(&self, key: &str) -> Result<Option<Vec<&'a str>>, String> {
+        if self.value[key].is_null() {
+            return Ok(None);
+        }
+        self.list(key)?
+            .iter()
+            .map(|value| value.as_str())
+            .collect::<Option<Vec<&str>>>()
+            .map(Some)
+            .ok_or(format!("{}: {} must be a list of strings", self.name, key))
+    }
+}
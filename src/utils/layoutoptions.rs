@@ -0,0 +1,263 @@
+//! # Layout options of a widget
+//!
+//! Every widget placed in a Container has LayoutOptions describing how it
+//! fits in the flex layout of its parent: how it grows and shrinks, how it is
+//! aligned on the cross axis, its padding and margin, an optional fixed
+//! width or height in pixels, and the transition played when it is shown or
+//! hidden (see `animation`). The options are set on the builder of the
+//! widget with `set_layout`, `set_stretched` being a shortcut for a grow of 1.
+//!
+//! ## Example
+//!
+//! ```
+//! use neutrino::utils::layoutoptions::{Align, LayoutOptions, Spacing};
+//! use neutrino::widgets::button::Button;
+//!
+//! let mut layout = LayoutOptions::new();
+//! layout.set_grow(2);
+//! layout.set_align(Align::Center);
+//! layout.set_padding(Spacing::symmetric(4, 8));
+//! layout.set_width(120);
+//!
+//! let mut button = Button::new("my_button");
+//! button.set_layout(layout);
+//! ```
+
+use crate::utils::animation::Transition;
+
+/// # The alignment of a widget on the cross axis of its Container
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Align {
+    Start,
+    Center,
+    End,
+    Stretch,
+}
+
+impl Align {
+    /// Return the CSS class of the alignment
+    fn css(self) -> &'static str {
+        match self {
+            Align::Start => "align-self-start",
+            Align::Center => "align-self-center",
+            Align::End => "align-self-end",
+            Align::Stretch => "align-self-stretch",
+        }
+    }
+}
+
+/// # The spacing around the four sides of a widget, in pixels
+///
+/// ## Fields
+///
+/// ```text
+/// top: u32
+/// right: u32
+/// bottom: u32
+/// left: u32
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Spacing {
+    pub top: u32,
+    pub right: u32,
+    pub bottom: u32,
+    pub left: u32,
+}
+
+impl Spacing {
+    /// Create a Spacing from its four sides
+    pub fn new(top: u32, right: u32, bottom: u32, left: u32) -> Self {
+        Self {
+            top,
+            right,
+            bottom,
+            left,
+        }
+    }
+
+    /// Create a Spacing with the same value on every side
+    pub fn all(value: u32) -> Self {
+        Self::new(value, value, value, value)
+    }
+
+    /// Create a Spacing from its vertical and horizontal values
+    pub fn symmetric(vertical: u32, horizontal: u32) -> Self {
+        Self::new(vertical, horizontal, vertical, horizontal)
+    }
+
+    /// Return the CSS value of the spacing
+    fn css(self) -> String {
+        format!(
+            "{}px {}px {}px {}px",
+            self.top, self.right, self.bottom, self.left
+        )
+    }
+}
+
+/// # The layout options of a widget
+///
+/// ## Fields
+///
+/// ```text
+/// grow: u32
+/// shrink: Option<u32>
+/// align: Option<Align>
+/// padding: Option<Spacing>
+/// margin: Option<Spacing>
+/// width: Option<u32>
+/// height: Option<u32>
+/// transition: Option<Transition>
+/// ```
+///
+/// ## Default values
+///
+/// ```text
+/// grow: 0
+/// shrink: None
+/// align: None
+/// padding: None
+/// margin: None
+/// width: None
+/// height: None
+/// transition: None
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct LayoutOptions {
+    grow: u32,
+    shrink: Option<u32>,
+    align: Option<Align>,
+    padding: Option<Spacing>,
+    margin: Option<Spacing>,
+    width: Option<u32>,
+    height: Option<u32>,
+    transition: Option<Transition>,
+}
+
+impl LayoutOptions {
+    /// Create a LayoutOptions
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the flex grow factor
+    pub fn grow(&self) -> u32 {
+        self.grow
+    }
+
+    /// Get the flex shrink factor
+    pub fn shrink(&self) -> Option<u32> {
+        self.shrink
+    }
+
+    /// Get the alignment
+    pub fn align(&self) -> Option<Align> {
+        self.align
+    }
+
+    /// Get the padding
+    pub fn padding(&self) -> Option<Spacing> {
+        self.padding
+    }
+
+    /// Get the margin
+    pub fn margin(&self) -> Option<Spacing> {
+        self.margin
+    }
+
+    /// Get the fixed width
+    pub fn width(&self) -> Option<u32> {
+        self.width
+    }
+
+    /// Get the fixed height
+    pub fn height(&self) -> Option<u32> {
+        self.height
+    }
+
+    /// Get the transition played when the widget is shown or hidden
+    pub fn transition(&self) -> Option<Transition> {
+        self.transition
+    }
+
+    /// Get the stretched flag, true if the widget grows
+    pub fn stretched(&self) -> bool {
+        self.grow > 0
+    }
+
+    /// Set the flex grow factor
+    pub fn set_grow(&mut self, grow: u32) {
+        self.grow = grow;
+    }
+
+    /// Set the flex shrink factor
+    pub fn set_shrink(&mut self, shrink: u32) {
+        self.shrink = Some(shrink);
+    }
+
+    /// Set the alignment
+    pub fn set_align(&mut self, align: Align) {
+        self.align = Some(align);
+    }
+
+    /// Set the padding
+    pub fn set_padding(&mut self, padding: Spacing) {
+        self.padding = Some(padding);
+    }
+
+    /// Set the margin
+    pub fn set_margin(&mut self, margin: Spacing) {
+        self.margin = Some(margin);
+    }
+
+    /// Set the fixed width
+    pub fn set_width(&mut self, width: u32) {
+        self.width = Some(width);
+    }
+
+    /// Set the fixed height
+    pub fn set_height(&mut self, height: u32) {
+        self.height = Some(height);
+    }
+
+    /// Set the transition played when the widget is shown or hidden
+    pub fn set_transition(&mut self, transition: Transition) {
+        self.transition = Some(transition);
+    }
+
+    /// Set the stretched flag: a grow factor of 1, or 0 to stop growing
+    pub fn set_stretched(&mut self, stretched: bool) {
+        self.grow = if stretched { 1 } else { 0 };
+    }
+
+    /// Return the CSS classes of the options
+    pub fn class(&self) -> String {
+        let stretched = if self.stretched() { "stretched" } else { "" };
+        let align = self.align.map(Align::css).unwrap_or("");
+        let transition = self.transition.map(Transition::css).unwrap_or("");
+        format!("{} {} {}", stretched, align, transition)
+    }
+
+    /// Return the inline CSS style of the options
+    pub fn style(&self) -> String {
+        let mut s = "".to_string();
+        if self.grow > 1 {
+            s.push_str(&format!("flex-grow: {}; ", self.grow));
+        }
+        if let Some(shrink) = self.shrink {
+            s.push_str(&format!("flex-shrink: {}; ", shrink));
+        }
+        if let Some(padding) = self.padding {
+            s.push_str(&format!("padding: {}; ", padding.css()));
+        }
+        if let Some(margin) = self.margin {
+            s.push_str(&format!("margin: {}; ", margin.css()));
+        }
+        if let Some(width) = self.width {
+            s.push_str(&format!("width: {}px; ", width));
+        }
+        if let Some(height) = self.height {
+            s.push_str(&format!("height: {}px; ", height));
+        }
+        s
+    }
+}
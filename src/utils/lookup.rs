@@ -0,0 +1,125 @@
+//! # Widget lookup
+//!
+//! The widgets of a Window can be looked up by name and type once the tree
+//! is built, with `Window::widget` and `Window::widget_mut`, their state
+//! being read and changed through `state` and `state_mut`.
+//!
+//! A listener cannot borrow the window it is called from, so it changes
+//! another widget with `App::widget_mut`: the change is queued and applied
+//! once the current event has been processed and the widget tree updated,
+//! right before the page is rendered. The listener of the changed widget
+//! still has the last word at the next update, if it sets the same value.
+//!
+//! ## Example
+//!
+//! ```
+//! use neutrino::testing::Harness;
+//! use neutrino::widgets::button::Button;
+//! use neutrino::widgets::checkbox::{CheckBox, CheckBoxListener, CheckBoxState};
+//! use neutrino::widgets::container::Container;
+//! use neutrino::{App, Window};
+//!
+//!
+//! struct AgreeListener;
+//!
+//! impl CheckBoxListener for AgreeListener {
+//!     fn on_change(&self, state: &CheckBoxState) {
+//!         let agreed = state.checked();
+//!         App::widget_mut::<Button, _>("submit", move |button| {
+//!             button.state_mut().set_disabled(!agreed);
+//!         });
+//!     }
+//!
+//!     fn on_update(&self, _state: &mut CheckBoxState) {}
+//! }
+//!
+//!
+//! let mut agree = CheckBox::new("agree");
+//! agree.set_listener(Box::new(AgreeListener));
+//!
+//! let mut submit = Button::new("submit");
+//! submit.set_disabled();
+//!
+//! let mut my_container = Container::new("my_container");
+//! my_container.add(Box::new(agree));
+//! my_container.add(Box::new(submit));
+//!
+//! let mut my_window = Window::new();
+//! my_window.set_child(Box::new(my_container));
+//!
+//! let mut harness = Harness::new(my_window);
+//! harness.change("agree", "");
+//!
+//! let submit = harness.window().widget::<Button>("submit").unwrap();
+//! assert!(!submit.state().disabled());
+//! ```
+
+use std::cell::RefCell;
+
+use crate::utils::event;
+use crate::widgets::widget::{Downcast, Widget};
+
+/// A change of a widget, queued by name
+type Change = (String, Box<dyn FnOnce(&mut (dyn Widget + 'static))>);
+
+thread_local! {
+    static CHANGES: RefCell<Vec<Change>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Find a widget by name in a widget tree
+pub fn find<'a>(
+    widget: &'a (dyn Widget + 'static),
+    name: &str,
+) -> Option<&'a (dyn Widget + 'static)> {
+    if widget.name() == name {
+        return Some(widget);
+    }
+    widget
+        .children()
+        .into_iter()
+        .find_map(|child| find(child, name))
+}
+
+/// Find a widget by name in a widget tree, to change it
+pub fn find_mut<'a>(
+    widget: &'a mut (dyn Widget + 'static),
+    name: &str,
+) -> Option<&'a mut (dyn Widget + 'static)> {
+    if widget.name() == name {
+        return Some(widget);
+    }
+    widget
+        .children_mut()
+        .into_iter()
+        .find_map(|child| find_mut(child, name))
+}
+
+/// Queue a change of the widget with the given name and type, reporting an
+/// error if the widget has another type
+pub(crate) fn change<T, F>(name: &str, change: F)
+where
+    T: Widget + 'static,
+    F: FnOnce(&mut T) + 'static,
+{
+    let source = name.to_string();
+    let change = move |widget: &mut (dyn Widget + 'static)| {
+        let kind = widget.kind().to_string();
+        match widget.downcast_mut::<T>() {
+            Some(widget) => change(widget),
+            None => event::report_error(
+                &source,
+                &format!("the widget is a {}", kind),
+            ),
+        }
+    };
+    CHANGES.with(|changes| {
+        changes
+            .borrow_mut()
+            .push((name.to_string(), Box::new(change)))
+    });
+}
+
+/// Take the changes queued since the last call
+pub(crate) fn take() -> Vec<Change> {
+    CHANGES.with(|changes| changes.borrow_mut().drain(..).collect())
+}
@@ -0,0 +1,244 @@
+//! # Usage metrics
+//!
+//! Local counters of the use of the widgets, for applications which want to
+//! know which features are used without sending anything anywhere. Once
+//! `enable` has been called, the events sent by each widget (clicks, changes
+//! and double clicks) are counted under its name, and the time spent on each
+//! tab of the Tabs is measured. The application can count its own actions,
+//! such as the functions of a menu, with `record`.
+//!
+//! The metrics stay in memory, are queried by the application, and are only
+//! written to the file given to `save`, from which `load` reads them back on
+//! the next start.
+//!
+//! ## Example
+//!
+//! ```
+//! use neutrino::utils::metrics;
+//!
+//! metrics::enable();
+//! metrics::record("export");
+//! metrics::record("export");
+//!
+//! assert_eq!(metrics::count("export"), 2);
+//! assert_eq!(metrics::counts(), vec![("export".to_string(), 2)]);
+//! ```
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use json::{self, JsonValue};
+
+use crate::utils::event::Event;
+
+/// # The usage metrics
+///
+/// ## Fields
+///
+/// ```text
+/// counts: HashMap<String, u64>
+/// times: HashMap<(String, String), Duration>
+/// shown: HashMap<String, (String, Instant)>
+/// ```
+struct Metrics {
+    counts: HashMap<String, u64>,
+    times: HashMap<(String, String), Duration>,
+    shown: HashMap<String, (String, Instant)>,
+}
+
+thread_local! {
+    static METRICS: RefCell<Option<Metrics>> = const { RefCell::new(None) };
+}
+
+/// Enable the metrics, starting from zero
+pub fn enable() {
+    METRICS.with(|metrics| {
+        *metrics.borrow_mut() = Some(Metrics {
+            counts: HashMap::new(),
+            times: HashMap::new(),
+            shown: HashMap::new(),
+        });
+    });
+}
+
+/// Disable the metrics, dropping them
+pub fn disable() {
+    METRICS.with(|metrics| *metrics.borrow_mut() = None);
+}
+
+/// Return true if the metrics are enabled
+pub fn enabled() -> bool {
+    METRICS.with(|metrics| metrics.borrow().is_some())
+}
+
+/// Count an action of the application
+pub fn record(action: &str) {
+    with_metrics(|metrics| {
+        *metrics.counts.entry(action.to_string()).or_insert(0) += 1;
+    });
+}
+
+/// Get the number of events of a widget, or of actions of the application,
+/// with the given name
+pub fn count(name: &str) -> u64 {
+    METRICS.with(|metrics| match &*metrics.borrow() {
+        Some(metrics) => metrics.counts.get(name).cloned().unwrap_or(0),
+        None => 0,
+    })
+}
+
+/// Get the counts, the most used first
+pub fn counts() -> Vec<(String, u64)> {
+    METRICS.with(|metrics| match &*metrics.borrow() {
+        Some(metrics) => {
+            let mut counts: Vec<(String, u64)> = metrics
+                .counts
+                .iter()
+                .map(|(name, count)| (name.to_string(), *count))
+                .collect();
+            counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+            counts
+        }
+        None => vec![],
+    })
+}
+
+/// Get the time spent on the tab with the given title of the Tabs with the
+/// given name
+pub fn time(tabs: &str, title: &str) -> Duration {
+    METRICS.with(|metrics| match &*metrics.borrow() {
+        Some(metrics) => metrics
+            .all_times()
+            .remove(&(tabs.to_string(), title.to_string()))
+            .unwrap_or_default(),
+        None => Duration::default(),
+    })
+}
+
+/// Get the times spent on the tabs, as (tabs, title, time), the longest
+/// first
+pub fn times() -> Vec<(String, String, Duration)> {
+    METRICS.with(|metrics| match &*metrics.borrow() {
+        Some(metrics) => {
+            let mut times: Vec<(String, String, Duration)> = metrics
+                .all_times()
+                .into_iter()
+                .map(|((tabs, title), time)| (tabs, title, time))
+                .collect();
+            times.sort_by(|a, b| {
+                b.2.cmp(&a.2)
+                    .then_with(|| a.0.cmp(&b.0))
+                    .then_with(|| a.1.cmp(&b.1))
+            });
+            times
+        }
+        None => vec![],
+    })
+}
+
+/// Reset the metrics to zero
+pub fn reset() {
+    if enabled() {
+        enable();
+    }
+}
+
+/// Save the metrics to the file at the given path
+pub fn save<P: AsRef<Path>>(path: P) -> io::Result<()> {
+    let mut saved_counts = JsonValue::new_object();
+    for (name, count) in counts() {
+        saved_counts[name.as_str()] = count.into();
+    }
+    let mut saved_times = JsonValue::new_array();
+    for (tabs, title, time) in times() {
+        let _ = saved_times.push(json::object! {
+            "tabs" => tabs,
+            "title" => title,
+            "milliseconds" => time.as_millis() as u64,
+        });
+    }
+    let content = json::object! {
+        "counts" => saved_counts,
+        "times" => saved_times,
+    };
+    fs::write(path, content.dump())
+}
+
+/// Add the metrics saved in the file at the given path to the current ones
+pub fn load<P: AsRef<Path>>(path: P) -> io::Result<()> {
+    let content = fs::read_to_string(path)?;
+    let content = json::parse(&content)
+        .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+    with_metrics(|metrics| {
+        for (name, count) in content["counts"].entries() {
+            *metrics.counts.entry(name.to_string()).or_insert(0) +=
+                count.as_u64().unwrap_or(0);
+        }
+        for time in content["times"].members() {
+            if let (Some(tabs), Some(title), Some(milliseconds)) = (
+                time["tabs"].as_str(),
+                time["title"].as_str(),
+                time["milliseconds"].as_u64(),
+            ) {
+                *metrics
+                    .times
+                    .entry((tabs.to_string(), title.to_string()))
+                    .or_default() += Duration::from_millis(milliseconds);
+            }
+        }
+    });
+    Ok(())
+}
+
+/// Count an event sent by a widget
+pub(crate) fn observe(event: &Event) {
+    let source = match event {
+        Event::Change { source, .. }
+        | Event::CanvasClick { source, .. }
+        | Event::DoubleClick { source, .. } => source,
+        _ => return,
+    };
+    record(source);
+}
+
+/// Tell which tab of a Tabs is shown, the time since the last call being
+/// spent on the previous one
+pub(crate) fn show(tabs: &str, title: &str) {
+    with_metrics(|metrics| {
+        let now = Instant::now();
+        if let Some((shown, since)) = metrics.shown.remove(tabs) {
+            *metrics.times.entry((tabs.to_string(), shown)).or_default() +=
+                now - since;
+        }
+        metrics
+            .shown
+            .insert(tabs.to_string(), (title.to_string(), now));
+    });
+}
+
+/// Run a function on the metrics, if they are enabled
+fn with_metrics(f: impl FnOnce(&mut Metrics)) {
+    METRICS.with(|metrics| {
+        if let Some(metrics) = &mut *metrics.borrow_mut() {
+            f(metrics);
+        }
+    });
+}
+
+impl Metrics {
+    /// Get the times, including the time spent on the tabs shown now
+    fn all_times(&self) -> HashMap<(String, String), Duration> {
+        let mut times = self.times.clone();
+        let now = Instant::now();
+        for (tabs, (title, since)) in self.shown.iter() {
+            *times
+                .entry((tabs.to_string(), title.to_string()))
+                .or_default() += now - *since;
+        }
+        times
+    }
+}
@@ -1,4 +1,39 @@
+pub mod accessibility;
+pub mod animation;
+pub mod assets;
+pub mod audit;
+pub mod clipboard;
+pub mod crypto;
+pub mod csp;
+pub mod datetime;
+pub mod document;
 pub mod event;
+pub mod export;
+pub mod geometry;
+pub mod graphviz;
+pub mod history;
+pub mod hook;
+pub mod i18n;
+pub mod html;
 pub mod icon;
+pub mod instance;
+pub mod launch;
+pub mod layout;
+pub mod layoutoptions;
+pub mod lookup;
+pub mod metrics;
+pub mod monitor;
+pub mod observe;
+pub mod palette;
 pub mod pixmap;
+pub mod platform;
+pub mod plugin;
+pub mod queue;
+pub mod secrets;
+pub mod settings;
+pub mod store;
+pub mod style;
 pub mod theme;
+pub mod updater;
+pub mod validator;
+pub mod watcher;
@@ -0,0 +1,135 @@
+//! # Monitors
+//!
+//! The page reports the monitors of the system through a `Monitors` event
+//! once it is displayed, and again when they change where the webview
+//! supports the multi-screen window placement API. Otherwise only the
+//! monitor holding the window is known. The monitors are kept in the state
+//! of the Window, with the part of their screen available to the windows,
+//! in pixels, the primary monitor usually being at (0, 0).
+//!
+//! A Window is placed on a monitor with `set_monitor`, or from the
+//! `on_update` function of its listener with `WindowState::set_monitor`: it
+//! is centered on the monitor once the monitors are known, where the
+//! webview lets the page move its window.
+//!
+//! ## Example
+//!
+//! ```
+//! use neutrino::testing::Harness;
+//! use neutrino::utils::monitor::Monitor;
+//! use neutrino::Window;
+//!
+//! let mut my_window = Window::new();
+//! my_window.set_monitor(1);
+//!
+//! let mut harness = Harness::new(my_window);
+//! harness.monitors(vec![
+//!     Monitor::new("eDP-1", 0, 0, 1920, 1080, true),
+//!     Monitor::new("HDMI-1", 1920, 0, 2560, 1440, false),
+//! ]);
+//!
+//! let monitors = harness.window().monitors();
+//! assert_eq!(monitors[1].name(), "HDMI-1");
+//! assert_eq!(monitors[1].x(), 1920);
+//! ```
+
+use json::JsonValue;
+
+/// # A monitor of the system
+///
+/// ## Fields
+///
+/// ```text
+/// name: String
+/// x: i32
+/// y: i32
+/// width: i32
+/// height: i32
+/// primary: bool
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Monitor {
+    name: String,
+    x: i32,
+    y: i32,
+    width: i32,
+    height: i32,
+    primary: bool,
+}
+
+impl Monitor {
+    /// Create a Monitor from its name, the position and the size of its
+    /// available screen, and its primary flag
+    pub fn new(
+        name: &str,
+        x: i32,
+        y: i32,
+        width: i32,
+        height: i32,
+        primary: bool,
+    ) -> Self {
+        Self {
+            name: name.to_string(),
+            x,
+            y,
+            width,
+            height,
+            primary,
+        }
+    }
+
+    /// Read the monitors of a monitors event sent by the page
+    pub(crate) fn from_json(value: &JsonValue) -> Vec<Self> {
+        value["monitors"]
+            .members()
+            .map(|monitor| {
+                Self::new(
+                    monitor["name"].as_str().unwrap_or(""),
+                    monitor["x"].as_i32().unwrap_or(0),
+                    monitor["y"].as_i32().unwrap_or(0),
+                    monitor["width"].as_i32().unwrap_or(0),
+                    monitor["height"].as_i32().unwrap_or(0),
+                    monitor["primary"].as_bool().unwrap_or(false),
+                )
+            })
+            .collect()
+    }
+
+    /// Get the name, empty if the webview does not give it
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the horizontal position of the available screen
+    pub fn x(&self) -> i32 {
+        self.x
+    }
+
+    /// Get the vertical position of the available screen
+    pub fn y(&self) -> i32 {
+        self.y
+    }
+
+    /// Get the width of the available screen
+    pub fn width(&self) -> i32 {
+        self.width
+    }
+
+    /// Get the height of the available screen
+    pub fn height(&self) -> i32 {
+        self.height
+    }
+
+    /// Get the primary flag
+    pub fn primary(&self) -> bool {
+        self.primary
+    }
+
+    /// Return the javascript centering the window on the monitor
+    pub(crate) fn js(&self) -> String {
+        format!(
+            "placeWindow({}, {}, {}, {});",
+            self.x, self.y, self.width, self.height
+        )
+    }
+}
@@ -0,0 +1,515 @@
+//! # Async observers
+//!
+//! `App::observe` runs a future off the UI thread for a widget which
+//! displays its output, such as the `async fn` fetching a forecast from a
+//! server or reading a large file, so that the window does not freeze and
+//! the application does not spawn and join threads itself.
+//!
+//! * While the future runs, the widget is rendered as a loading placeholder
+//!   in its place, and the rest of the window is used as usual. Its text can
+//!   be translated with the `widget.loading` key (see `i18n`).
+//! * Once the future completes, its output is given to a function run on
+//!   the UI thread, which stores it in the model of the application, and
+//!   the window is updated: the listener of the widget reads the data as
+//!   usual.
+//! * A widget observes one future at a time: observing another one, such as
+//!   the next page of a list, abandons the previous one, whose output is
+//!   dropped, and so does `App::unobserve`.
+//! * With `App::observe_result`, a future returning an error, like a future
+//!   which panics, fails: the error is reported as an error of the widget,
+//!   which is rendered as an error placeholder until it observes another
+//!   future.
+//!
+//! The status of a widget, loading or failed, is given by
+//! `App::observe_status`. A data widget, such as a ListView, renders its own
+//! placeholders for these statuses (see `Widget::status_placeholder`).
+//!
+//! The futures are polled by a pool of worker threads, one per processor,
+//! so they have to be `Send`. The pool is not an async runtime: a future
+//! can await other futures, or block its worker, but not the ones which need
+//! the reactor of a runtime, such as the timers and sockets of tokio, used
+//! by reqwest. An application observing such futures gives the spawner of
+//! its runtime to `App::set_spawner`, which then runs all the observed
+//! futures instead of the pool:
+//!
+//! ```text
+//! let runtime = tokio::runtime::Runtime::new().unwrap();
+//! let handle = runtime.handle().clone();
+//! App::set_spawner(move |future| {
+//!     handle.spawn(future);
+//! });
+//! ```
+//!
+//! An abandoned future is cancelled the next time it is polled: it is
+//! dropped instead. A future which blocks its worker, such as a synchronous
+//! download, cannot be interrupted and keeps the worker from polling the
+//! other futures until it completes, its output being dropped.
+//!
+//! The outputs are given back to the thread which observed the futures, so
+//! several windows, such as the ones of tests run in parallel, each receive
+//! their own.
+//!
+//! ## Example
+//!
+//! ```
+//! use std::cell::RefCell;
+//! use std::rc::Rc;
+//!
+//! use neutrino::testing::Harness;
+//! use neutrino::widgets::label::{Label, LabelListener, LabelState};
+//! use neutrino::{App, Window};
+//!
+//!
+//! async fn fetch_forecast(city: String) -> String {
+//!     format!("{}: sunny, 24°C", city)
+//! }
+//!
+//!
+//! struct MyLabelListener {
+//!     forecast: Rc<RefCell<String>>,
+//! }
+//!
+//! impl LabelListener for MyLabelListener {
+//!     fn on_update(&self, state: &mut LabelState) {
+//!         state.set_text(&self.forecast.borrow());
+//!     }
+//! }
+//!
+//!
+//! let forecast = Rc::new(RefCell::new("".to_string()));
+//!
+//! let mut my_label = Label::new("forecast");
+//! my_label.set_listener(Box::new(MyLabelListener {
+//!     forecast: Rc::clone(&forecast),
+//! }));
+//!
+//! let mut my_window = Window::new();
+//! my_window.set_child(Box::new(my_label));
+//!
+//! let model = Rc::clone(&forecast);
+//! App::observe(
+//!     "forecast",
+//!     fetch_forecast("Lyon".to_string()),
+//!     move |text| *model.borrow_mut() = text,
+//! );
+//!
+//! let mut harness = Harness::new(my_window);
+//! assert!(harness.element("forecast").unwrap().contains("widget-loading"));
+//!
+//! harness.settle();
+//! assert!(harness.contains("Lyon: sunny, 24°C"));
+//! ```
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::fmt::Display;
+use std::future::Future;
+use std::panic::{self, AssertUnwindSafe};
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Condvar, Mutex, Once};
+use std::task::{Context, Poll, Wake, Waker};
+use std::thread::{self, ThreadId};
+use std::time::{Duration, Instant};
+
+use json::JsonValue;
+
+use crate::backend::Proxy;
+use crate::utils::event;
+use crate::utils::html::escape;
+use crate::utils::i18n;
+
+/// The output of a future, or the message of its panic
+type Output = Result<Box<dyn Any + Send>, String>;
+
+/// A future spawned to be polled until it completes
+pub type Job = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+/// The function spawning the jobs, set by the application
+type Spawner = Arc<dyn Fn(Job) + Send + Sync>;
+
+/// A completed future, with the thread which observed it
+struct Done {
+    owner: ThreadId,
+    id: u64,
+    output: Output,
+}
+
+static NEXT: AtomicU64 = AtomicU64::new(1);
+static PROXIES: Mutex<Vec<(ThreadId, Proxy)>> = Mutex::new(Vec::new());
+static DONE: Mutex<Vec<Done>> = Mutex::new(Vec::new());
+static COMPLETED: Condvar = Condvar::new();
+static SPAWNER: Mutex<Option<Spawner>> = Mutex::new(None);
+static QUEUE: Mutex<VecDeque<Arc<Task>>> = Mutex::new(VecDeque::new());
+static QUEUED: Condvar = Condvar::new();
+static WORKERS: Once = Once::new();
+
+thread_local! {
+    static OBSERVERS: RefCell<Vec<Observer>> = const { RefCell::new(Vec::new()) };
+    static FAILURES: RefCell<Vec<(String, String)>> = const { RefCell::new(Vec::new()) };
+}
+
+/// # The status of the future observed by a widget
+#[derive(Debug, Clone, PartialEq)]
+pub enum Status {
+    /// The future is running
+    Loading,
+    /// The future failed, with its error
+    Failed(String),
+}
+
+/// A future observed by a widget, waiting for its output
+struct Observer {
+    id: u64,
+    name: String,
+    cancelled: Arc<AtomicBool>,
+    apply: Box<dyn FnOnce(Box<dyn Any + Send>)>,
+}
+
+/// A job of the worker pool, queued again when it is woken
+struct Task {
+    job: Mutex<Option<Job>>,
+}
+
+impl Wake for Task {
+    fn wake(self: Arc<Self>) {
+        queue(self);
+    }
+}
+
+/// Queue a task of the worker pool, starting the workers the first time
+fn queue(task: Arc<Task>) {
+    WORKERS.call_once(|| {
+        let count = thread::available_parallelism().map_or(4, |n| n.get());
+        for _ in 0..count {
+            thread::spawn(work);
+        }
+    });
+    if let Ok(mut queue) = QUEUE.lock() {
+        queue.push_back(task);
+    }
+    QUEUED.notify_one();
+}
+
+/// Poll the queued tasks, on a worker of the pool
+fn work() {
+    loop {
+        let task = {
+            let mut queue = match QUEUE.lock() {
+                Ok(queue) => queue,
+                Err(_) => return,
+            };
+            loop {
+                match queue.pop_front() {
+                    Some(task) => break task,
+                    None => {
+                        queue = match QUEUED.wait(queue) {
+                            Ok(queue) => queue,
+                            Err(_) => return,
+                        }
+                    }
+                }
+            }
+        };
+        let waker = Waker::from(Arc::clone(&task));
+        let mut context = Context::from_waker(&waker);
+        let mut job = match task.job.lock() {
+            Ok(job) => job,
+            Err(_) => continue,
+        };
+        if let Some(future) = job.as_mut() {
+            if future.as_mut().poll(&mut context).is_ready() {
+                *job = None;
+            }
+        }
+    }
+}
+
+/// Run a job with the spawner of the application, or on the worker pool
+fn spawn(job: Job) {
+    let spawner = SPAWNER.lock().ok().and_then(|spawner| spawner.clone());
+    match spawner {
+        Some(spawner) => spawner(job),
+        None => queue(Arc::new(Task {
+            job: Mutex::new(Some(job)),
+        })),
+    }
+}
+
+/// Run the observed futures with the given function instead of the worker
+/// pool
+pub(crate) fn set_spawner<S>(spawner: S)
+where
+    S: Fn(Job) + Send + Sync + 'static,
+{
+    if let Ok(mut current) = SPAWNER.lock() {
+        *current = Some(Arc::new(spawner));
+    }
+}
+
+/// A future observed by a widget: it is dropped once it is cancelled, and
+/// its output, or the message of its panic, is given back to the thread
+/// observing it
+struct Observed<F: Future> {
+    future: Pin<Box<F>>,
+    owner: ThreadId,
+    id: u64,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl<F> Future for Observed<F>
+where
+    F: Future,
+    F::Output: Send + 'static,
+{
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, context: &mut Context) -> Poll<()> {
+        let observed = self.get_mut();
+        if observed.cancelled.load(Ordering::Relaxed) {
+            return Poll::Ready(());
+        }
+        let future = &mut observed.future;
+        let output = match panic::catch_unwind(AssertUnwindSafe(|| {
+            future.as_mut().poll(context)
+        })) {
+            Ok(Poll::Pending) => return Poll::Pending,
+            Ok(Poll::Ready(output)) => {
+                Ok(Box::new(output) as Box<dyn Any + Send>)
+            }
+            Err(payload) => Err(match payload.downcast::<String>() {
+                Ok(message) => *message,
+                Err(payload) => match payload.downcast::<&str>() {
+                    Ok(message) => message.to_string(),
+                    Err(_) => "the future panicked".to_string(),
+                },
+            }),
+        };
+        complete(observed.owner, observed.id, &observed.cancelled, output);
+        Poll::Ready(())
+    }
+}
+
+/// Give the output of a completed future back to the thread observing it,
+/// unless it was cancelled
+fn complete(owner: ThreadId, id: u64, cancelled: &AtomicBool, output: Output) {
+    if cancelled.load(Ordering::Relaxed) {
+        return;
+    }
+    if let Ok(mut done) = DONE.lock() {
+        done.push(Done { owner, id, output });
+    }
+    COMPLETED.notify_all();
+    if let Ok(proxies) = PROXIES.lock() {
+        if let Some((_, proxy)) = proxies.iter().find(|(t, _)| *t == owner) {
+            emit(proxy, id);
+        }
+    }
+}
+
+/// Run a future for the widget with the given name, which is loading until
+/// its output is given to `apply` on the UI thread
+pub(crate) fn observe<F, T, A>(name: &str, future: F, apply: A)
+where
+    F: Future<Output = T> + Send + 'static,
+    T: Send + 'static,
+    A: FnOnce(T) + 'static,
+{
+    let id = NEXT.fetch_add(1, Ordering::Relaxed);
+    let owner = thread::current().id();
+    let cancelled = Arc::new(AtomicBool::new(false));
+    let cancel = Arc::clone(&cancelled);
+    unobserve(name);
+    OBSERVERS.with(|observers| {
+        observers.borrow_mut().push(Observer {
+            id,
+            name: name.to_string(),
+            cancelled,
+            apply: Box::new(move |output| {
+                if let Ok(output) = output.downcast::<T>() {
+                    apply(*output);
+                }
+            }),
+        });
+    });
+    spawn(Box::pin(Observed {
+        future: Box::pin(future),
+        owner,
+        id,
+        cancelled: cancel,
+    }));
+}
+
+/// Run a future returning a result for the widget with the given name,
+/// which fails if the future returns an error
+pub(crate) fn observe_result<F, T, E, A>(name: &str, future: F, apply: A)
+where
+    F: Future<Output = Result<T, E>> + Send + 'static,
+    T: Send + 'static,
+    E: Display + Send + 'static,
+    A: FnOnce(T) + 'static,
+{
+    let widget = name.to_string();
+    observe(name, future, move |output| match output {
+        Ok(output) => apply(output),
+        Err(error) => fail(&widget, &error.to_string()),
+    });
+}
+
+/// Abandon the future observed by the widget with the given name, which is
+/// cancelled, and forget its failure
+pub(crate) fn unobserve(name: &str) {
+    OBSERVERS.with(|observers| {
+        observers.borrow_mut().retain(|observer| {
+            if observer.name == name {
+                observer.cancelled.store(true, Ordering::Relaxed);
+            }
+            observer.name != name
+        })
+    });
+    clear(name);
+}
+
+/// Record the failure of the future observed by a widget, and report it
+fn fail(name: &str, message: &str) {
+    FAILURES.with(|failures| {
+        failures
+            .borrow_mut()
+            .push((name.to_string(), message.to_string()))
+    });
+    event::report_error(name, message);
+}
+
+/// Forget the failure of the future observed by a widget
+fn clear(name: &str) {
+    FAILURES.with(|failures| {
+        failures.borrow_mut().retain(|(failed, _)| failed != name)
+    });
+}
+
+/// Get the status of the widget with the given name, None if it does not
+/// wait for a future and the last one did not fail
+pub(crate) fn status(name: &str) -> Option<Status> {
+    let loading = OBSERVERS.with(|observers| {
+        observers
+            .borrow()
+            .iter()
+            .any(|observer| observer.name == name)
+    });
+    if loading {
+        return Some(Status::Loading);
+    }
+    FAILURES.with(|failures| {
+        failures
+            .borrow()
+            .iter()
+            .find(|(failed, _)| failed == name)
+            .map(|(_, message)| Status::Failed(message.to_string()))
+    })
+}
+
+/// Return true if a widget waits for a future or failed
+pub(crate) fn any_status() -> bool {
+    OBSERVERS.with(|observers| !observers.borrow().is_empty())
+        || FAILURES.with(|failures| !failures.borrow().is_empty())
+}
+
+/// Return the loading placeholder rendered in place of a widget
+pub(crate) fn placeholder(name: &str) -> String {
+    format!(
+        r#"<div id="{}" class="spinner-box widget-loading" role="status" aria-live="polite"><div class="spinner" aria-hidden="true"></div><span class="spinner-text">{}</span></div>"#,
+        escape(name),
+        escape(&i18n::translate_or("widget.loading", "Loading", &[]))
+    )
+}
+
+/// Send an observed event to the page, which sends it back to the window
+fn emit(proxy: &Proxy, id: u64) {
+    let mut event = JsonValue::new_object();
+    event["type"] = "Observed".into();
+    event["id"] = id.into();
+    proxy.eval(&format!("emit({});", event.dump()));
+}
+
+/// Take the completed futures observed by the current thread, leaving the
+/// ones of the other threads
+fn take_done() -> Vec<Done> {
+    let owner = thread::current().id();
+    let mut done = match DONE.lock() {
+        Ok(done) => done,
+        Err(_) => return vec![],
+    };
+    let (mine, others) = done.drain(..).partition(|d| d.owner == owner);
+    *done = others;
+    mine
+}
+
+/// Give the outputs of the completed futures to the widgets still observing
+/// them, on the UI thread
+pub(crate) fn finish() {
+    for Done { id, output, .. } in take_done() {
+        let observer = OBSERVERS.with(|observers| {
+            let mut observers = observers.borrow_mut();
+            let index = observers.iter().position(|o| o.id == id)?;
+            Some(observers.remove(index))
+        });
+        let observer = match observer {
+            Some(observer) => observer,
+            None => continue,
+        };
+        match output {
+            Ok(output) => (observer.apply)(output),
+            Err(message) => fail(&observer.name, &message),
+        }
+    }
+}
+
+/// Wait until the futures observed by the widgets of the current thread
+/// complete, or until the timeout is over, returning the identifiers of the
+/// completed ones
+pub(crate) fn wait(timeout: Duration) -> Vec<u64> {
+    let ids = OBSERVERS.with(|observers| {
+        observers
+            .borrow()
+            .iter()
+            .map(|o| o.id)
+            .collect::<Vec<u64>>()
+    });
+    let deadline = Instant::now() + timeout;
+    let mut done = match DONE.lock() {
+        Ok(done) => done,
+        Err(_) => return vec![],
+    };
+    loop {
+        let completed = ids
+            .iter()
+            .filter(|id| done.iter().any(|d| d.id == **id))
+            .copied()
+            .collect::<Vec<u64>>();
+        let now = Instant::now();
+        if completed.len() == ids.len() || now >= deadline {
+            return completed;
+        }
+        done = match COMPLETED.wait_timeout(done, deadline - now) {
+            Ok((done, _)) => done,
+            Err(_) => return completed,
+        };
+    }
+}
+
+/// Send the outputs of the futures observed by the current thread to its
+/// window through the proxy, including the ones completed before the window
+/// was displayed
+pub(crate) fn start(proxy: Proxy) {
+    let owner = thread::current().id();
+    if let Ok(mut proxies) = PROXIES.lock() {
+        proxies.retain(|(thread, _)| *thread != owner);
+        proxies.push((owner, proxy.clone()));
+    }
+    if let Ok(done) = DONE.lock() {
+        for d in done.iter().filter(|d| d.owner == owner) {
+            emit(&proxy, d.id);
+        }
+    }
+}
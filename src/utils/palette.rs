@@ -0,0 +1,164 @@
+//! # Colour palettes for data visualization
+//!
+//! The palettes remain distinguishable with the common colour-vision
+//! deficiencies (protanopia, deuteranopia and tritanopia):
+//!
+//! * `Palette::okabe_ito` is a categorical palette of 8 colours, for the
+//!   series of a chart or the categories of a legend. It is the default
+//!   palette of a Canvas.
+//! * `Palette::viridis` and `Palette::cividis` are sequential palettes, from
+//!   dark to light, for the values of a heatmap or a gradient.
+//!
+//! A palette can also be made of custom colours with `Palette::categorical`
+//! and `Palette::sequential`, to follow the colours of a brand.
+//!
+//! `Palette::color` returns the colour of a series, going back to the first
+//! colour after the last one, and `Palette::scale` the colour of a value
+//! between 0 and 1, interpolated between the colours of the palette.
+//!
+//! ## Example
+//!
+//! ```
+//! use neutrino::utils::palette::Palette;
+//! use neutrino::widgets::canvas::Canvas;
+//!
+//!
+//! let sales = vec![12.0, 30.0, 21.0];
+//!
+//! let mut my_canvas = Canvas::new("sales");
+//! my_canvas.set_size(120, 60);
+//!
+//! let state = my_canvas.state_mut();
+//! for (i, value) in sales.iter().enumerate() {
+//!     let color = state.palette().color(i);
+//!     state.fill_rect(i as f64 * 40.0, 60.0 - value, 30.0, *value, &color);
+//! }
+//!
+//! assert_eq!(Palette::okabe_ito().color(8), "#e69f00");
+//! assert_eq!(Palette::viridis().scale(0.0), "#440154");
+//! assert_eq!(Palette::viridis().scale(1.0), "#fde725");
+//! ```
+
+/// # The kind of a palette
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PaletteKind {
+    /// Distinct colours for unordered categories
+    Categorical,
+    /// Ordered colours for values from low to high
+    Sequential,
+}
+
+/// # A colour palette
+///
+/// The colours are CSS colours. The colours of a sequential palette are
+/// interpolated by `scale` if they are written as `#rrggbb`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Palette {
+    kind: PaletteKind,
+    colors: Vec<String>,
+}
+
+impl Palette {
+    /// Create a categorical palette with custom colours
+    pub fn categorical(colors: Vec<&str>) -> Self {
+        Self {
+            kind: PaletteKind::Categorical,
+            colors: colors.iter().map(|c| c.to_string()).collect(),
+        }
+    }
+
+    /// Create a sequential palette with custom colours, from low to high
+    pub fn sequential(colors: Vec<&str>) -> Self {
+        Self {
+            kind: PaletteKind::Sequential,
+            colors: colors.iter().map(|c| c.to_string()).collect(),
+        }
+    }
+
+    /// The categorical palette of Okabe and Ito
+    pub fn okabe_ito() -> Self {
+        Self::categorical(vec![
+            "#e69f00", "#56b4e9", "#009e73", "#f0e442", "#0072b2", "#d55e00",
+            "#cc79a7", "#000000",
+        ])
+    }
+
+    /// The sequential viridis palette, from dark purple to yellow
+    pub fn viridis() -> Self {
+        Self::sequential(vec![
+            "#440154", "#472d7b", "#3b528b", "#2c728e", "#21918c", "#28ae80",
+            "#5ec962", "#addc30", "#fde725",
+        ])
+    }
+
+    /// The sequential cividis palette, from dark blue to yellow, designed
+    /// for the deuteranopes and protanopes
+    pub fn cividis() -> Self {
+        Self::sequential(vec![
+            "#00204d", "#31446b", "#666970", "#958f78", "#cbba69", "#ffea46",
+        ])
+    }
+
+    /// Get the kind
+    pub fn kind(&self) -> PaletteKind {
+        self.kind
+    }
+
+    /// Get the colours
+    pub fn colors(&self) -> &Vec<String> {
+        &self.colors
+    }
+
+    /// Get the colour of the series with the given index, going back to the
+    /// first colour after the last one
+    pub fn color(&self, index: usize) -> String {
+        if self.colors.is_empty() {
+            return "black".to_string();
+        }
+        self.colors[index % self.colors.len()].to_string()
+    }
+
+    /// Get the colour of a value between 0 and 1, interpolated between the
+    /// two nearest colours of the palette, or the nearest one if they are not
+    /// written as `#rrggbb`
+    pub fn scale(&self, value: f64) -> String {
+        if self.colors.is_empty() {
+            return "black".to_string();
+        }
+        let value = if value.is_nan() {
+            0.0
+        } else {
+            value.clamp(0.0, 1.0)
+        };
+        let position = value * (self.colors.len() - 1) as f64;
+        let low = position.floor() as usize;
+        let high = position.ceil() as usize;
+        let ratio = position - low as f64;
+        match (rgb(&self.colors[low]), rgb(&self.colors[high])) {
+            (Some(from), Some(to)) => {
+                let mix = |i: usize| {
+                    (from[i] as f64 + (to[i] as f64 - from[i] as f64) * ratio)
+                        .round() as u8
+                };
+                format!("#{:02x}{:02x}{:02x}", mix(0), mix(1), mix(2))
+            }
+            _ => self.colors[position.round() as usize].to_string(),
+        }
+    }
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Self::okabe_ito()
+    }
+}
+
+/// Parse a colour written as `#rrggbb`
+fn rgb(color: &str) -> Option<[u8; 3]> {
+    let hex = color.strip_prefix('#')?;
+    if hex.len() != 6 {
+        return None;
+    }
+    let channel = |i: usize| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok();
+    Some([channel(0)?, channel(2)?, channel(4)?])
+}
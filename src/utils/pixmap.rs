@@ -1,3 +1,4 @@
+use crate::utils::assets;
 use crate::utils::icon::Icon;
 use base64::encode;
 use std::fs;
@@ -14,6 +15,7 @@ use std::path::Path;
 /// data: String
 /// extension: String
 /// ```
+#[derive(Debug, Clone, PartialEq)]
 pub struct Pixmap {
     data: String,
     extension: String,
@@ -28,8 +30,10 @@ impl Pixmap {
         }
     }
 
-    /// Create a Pixmap from a file path
+    /// Create a Pixmap from a file path. The file is read at runtime, see
+    /// `utils::assets` to embed it instead.
     pub fn from_path(path: &str) -> Self {
+        assets::record(path);
         let extension = match Path::new(path).extension() {
             Some(ext) => ext.to_str().unwrap().to_string(),
             None => "".to_string(),
@@ -41,6 +45,51 @@ impl Pixmap {
         Self { data, extension }
     }
 
+    /// Create a Pixmap from raw bytes. The image format is detected from the
+    /// first bytes of the data (PNG, JPEG, GIF, BMP, WebP, SVG).
+    pub fn from_bytes(bytes: &[u8]) -> Self {
+        let extension = if bytes.starts_with(b"\x89PNG") {
+            "png"
+        } else if bytes.starts_with(b"\xff\xd8\xff") {
+            "jpeg"
+        } else if bytes.starts_with(b"GIF8") {
+            "gif"
+        } else if bytes.starts_with(b"BM") {
+            "bmp"
+        } else if bytes.len() > 11
+            && &bytes[0..4] == b"RIFF"
+            && &bytes[8..12] == b"WEBP"
+        {
+            "webp"
+        } else if String::from_utf8_lossy(&bytes[..bytes.len().min(256)])
+            .contains("<svg")
+        {
+            "svg"
+        } else {
+            ""
+        };
+        Self {
+            data: encode(bytes),
+            extension: extension.to_string(),
+        }
+    }
+
+    /// Create a Pixmap from a data URI
+    /// (`data:image/png;base64,iVBORw0KGgo...`)
+    pub fn from_data_uri(uri: &str) -> Self {
+        let (header, data) = match uri.find(',') {
+            Some(index) => (&uri[..index], &uri[index + 1..]),
+            None => ("", uri),
+        };
+        let extension = header
+            .trim_start_matches("data:image/")
+            .trim_end_matches(";base64");
+        Self {
+            data: data.to_string(),
+            extension: extension.to_string(),
+        }
+    }
+
     /// Create a Pixmap from an Icon
     pub fn from_icon(icon: Box<dyn Icon>) -> Self {
         let extension = icon.extension();
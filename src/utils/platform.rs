@@ -0,0 +1,140 @@
+use std::env;
+use std::path::Path;
+
+/// # An operating system
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Os {
+    Linux,
+    Windows,
+    MacOS,
+    Other,
+}
+
+/// # A display server
+///
+/// Windows and macOS only have their native one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DisplayServer {
+    Wayland,
+    X11,
+    Native,
+    Unknown,
+}
+
+/// # An optional capability of the platform
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Capability {
+    Tray,
+    GlobalShortcuts,
+    Notifications,
+    Frameless,
+}
+
+impl Capability {
+    /// Get all the capabilities
+    pub fn all() -> Vec<Capability> {
+        vec![
+            Capability::Tray,
+            Capability::GlobalShortcuts,
+            Capability::Notifications,
+            Capability::Frameless,
+        ]
+    }
+}
+
+/// Get the operating system the application is running on
+pub fn os() -> Os {
+    if cfg!(target_os = "windows") {
+        Os::Windows
+    } else if cfg!(target_os = "macos") {
+        Os::MacOS
+    } else if cfg!(target_os = "linux") {
+        Os::Linux
+    } else {
+        Os::Other
+    }
+}
+
+/// Get the display server the application is running on. On Linux, it is
+/// guessed from the environment of the session.
+pub fn display_server() -> DisplayServer {
+    match os() {
+        Os::Windows | Os::MacOS => DisplayServer::Native,
+        _ => {
+            let wayland = env::var_os("WAYLAND_DISPLAY").is_some()
+                || env::var("XDG_SESSION_TYPE")
+                    .map(|session| session == "wayland")
+                    .unwrap_or(false);
+            if wayland {
+                DisplayServer::Wayland
+            } else if env::var_os("DISPLAY").is_some() {
+                DisplayServer::X11
+            } else {
+                DisplayServer::Unknown
+            }
+        }
+    }
+}
+
+/// Return true if the capability is available at runtime
+///
+/// Windows and macOS provide all the capabilities. On Linux, they depend on
+/// the session, read from its environment:
+///
+/// * the tray needs a display, and a desktop showing the icons of the
+///   applications on Wayland, such as KDE, as GNOME does not
+/// * the global shortcuts need X11, as Wayland does not let an application
+///   grab the keys
+/// * the notifications need the session bus of D-Bus
+/// * the frameless windows need a display
+///
+/// A capability of the platform is not necessarily used by the backend: a
+/// backend given to `App::run_with`, for instance, may not display a tray
+/// (see the `tray` module).
+pub fn supports(capability: Capability) -> bool {
+    if let Os::Windows | Os::MacOS = os() {
+        return true;
+    }
+    let server = display_server();
+    match capability {
+        Capability::Tray => match server {
+            DisplayServer::X11 => true,
+            DisplayServer::Wayland => env::var("XDG_CURRENT_DESKTOP")
+                .map(|desktop| !desktop.to_uppercase().contains("GNOME"))
+                .unwrap_or(false),
+            _ => false,
+        },
+        Capability::GlobalShortcuts => server == DisplayServer::X11,
+        Capability::Notifications => {
+            env::var_os("DBUS_SESSION_BUS_ADDRESS").is_some()
+                || env::var_os("XDG_RUNTIME_DIR")
+                    .map(|dir| Path::new(&dir).join("bus").exists())
+                    .unwrap_or(false)
+        }
+        Capability::Frameless => server != DisplayServer::Unknown,
+    }
+}
+
+/// Get the capabilities available at runtime
+///
+/// ## Example
+///
+/// ```
+/// use neutrino::utils::platform::{self, Capability, DisplayServer, Os};
+///
+/// let tray = platform::capabilities().contains(&Capability::Tray);
+/// assert_eq!(tray, platform::supports(Capability::Tray));
+///
+/// if let Os::Windows | Os::MacOS = platform::os() {
+///     assert_eq!(platform::capabilities(), Capability::all());
+/// }
+/// if platform::display_server() == DisplayServer::Unknown {
+///     assert!(!platform::supports(Capability::Frameless));
+/// }
+/// ```
+pub fn capabilities() -> Vec<Capability> {
+    Capability::all()
+        .into_iter()
+        .filter(|&capability| supports(capability))
+        .collect()
+}
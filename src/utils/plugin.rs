@@ -0,0 +1,207 @@
+//! # Widget packs
+//!
+//! A widget pack is a crate implementing `WidgetPlugin`, registered by the
+//! application with `App::register` before running its window. A plugin
+//! can bring:
+//!
+//! - widgets, which are created from Rust as any other widget and, through
+//!   a factory, from the JSON layouts (see `layout`) under their type name
+//! - themes, chosen with `Window::set_plugin_theme` or by their name in a
+//!   layout
+//! - icons, found by name with `icon`
+//! - the CSS and the javascript used by its widgets, added to the page
+//!   after the ones of neutrino
+//!
+//! The javascript of a plugin talks to Rust with `neutrino.emit(name,
+//! value)`, which sends an `Event::Custom` holding the name and the value
+//! in JSON. It reaches `WindowListener::on_custom`, then the widgets.
+//!
+//! The plugins are kept by the thread running the application, in the order
+//! of their registration. A plugin registered with the name of another one
+//! replaces it.
+//!
+//! ## Example
+//!
+//! ```
+//! use neutrino::utils::layout::{self, Bindings};
+//! use neutrino::utils::plugin::{self, Factory, WidgetPlugin};
+//! use neutrino::widgets::label::Label;
+//! use neutrino::widgets::widget::Widget;
+//! use neutrino::App;
+//!
+//! use json::JsonValue;
+//!
+//!
+//! fn badge(
+//!     name: &str,
+//!     properties: &JsonValue,
+//!     _bindings: &mut Bindings,
+//! ) -> Result<Box<dyn Widget>, String> {
+//!     let mut badge = Label::new(name);
+//!     badge.set_text(properties["text"].as_str().unwrap_or(""));
+//!     Ok(Box::new(badge))
+//! }
+//!
+//!
+//! struct MyPlugin;
+//!
+//! impl WidgetPlugin for MyPlugin {
+//!     fn name(&self) -> &str {
+//!         "my_plugin"
+//!     }
+//!
+//!     fn widgets(&self) -> Vec<(String, Factory)> {
+//!         vec![("Badge".to_string(), badge)]
+//!     }
+//!
+//!     fn themes(&self) -> Vec<(String, String)> {
+//!         vec![("Solarized".to_string(), "#app{background:#fdf6e3}".to_string())]
+//!     }
+//! }
+//!
+//!
+//! App::register(Box::new(MyPlugin));
+//!
+//! assert_eq!(plugin::names(), vec!["my_plugin"]);
+//!
+//! let description = r#"{
+//!     "theme": "Solarized",
+//!     "child": { "type": "Badge", "name": "my_badge", "text": "New" }
+//! }"#;
+//!
+//! let window = layout::load(description, Bindings::new()).unwrap();
+//! ```
+
+use std::cell::RefCell;
+
+use json::JsonValue;
+
+use crate::utils::icon::Icon;
+use crate::utils::layout::Bindings;
+use crate::widgets::widget::Widget;
+
+/// Build a widget of a plugin from a layout, given its name, its JSON
+/// description and the bindings, from which it takes its listener
+pub type Factory =
+    fn(&str, &JsonValue, &mut Bindings) -> Result<Box<dyn Widget>, String>;
+
+/// # Trait that any of the widget packs have to implement
+pub trait WidgetPlugin {
+    /// Get the name of the plugin
+    fn name(&self) -> &str;
+
+    /// Get the factories of the widgets, by type name
+    fn widgets(&self) -> Vec<(String, Factory)> {
+        vec![]
+    }
+
+    /// Get the CSS of the themes, by theme name
+    fn themes(&self) -> Vec<(String, String)> {
+        vec![]
+    }
+
+    /// Get the icons, by icon name
+    fn icons(&self) -> Vec<(String, Box<dyn Icon>)> {
+        vec![]
+    }
+
+    /// Get the CSS styling the widgets, whatever the theme
+    fn css(&self) -> String {
+        "".to_string()
+    }
+
+    /// Get the javascript used by the widgets
+    fn js(&self) -> String {
+        "".to_string()
+    }
+}
+
+thread_local! {
+    static PLUGINS: RefCell<Vec<Box<dyn WidgetPlugin>>> =
+        const { RefCell::new(Vec::new()) };
+}
+
+/// Register a plugin, replacing the one with the same name if any
+pub(crate) fn register(plugin: Box<dyn WidgetPlugin>) {
+    PLUGINS.with(|plugins| {
+        let mut plugins = plugins.borrow_mut();
+        match plugins.iter().position(|p| p.name() == plugin.name()) {
+            Some(i) => plugins[i] = plugin,
+            None => plugins.push(plugin),
+        }
+    });
+}
+
+/// Get the names of the registered plugins
+pub fn names() -> Vec<String> {
+    PLUGINS.with(|plugins| {
+        plugins
+            .borrow()
+            .iter()
+            .map(|p| p.name().to_string())
+            .collect()
+    })
+}
+
+/// Get the factory of the widget type with the given name, taken from the
+/// last plugin providing it
+pub fn factory(kind: &str) -> Option<Factory> {
+    PLUGINS.with(|plugins| {
+        plugins.borrow().iter().rev().find_map(|p| {
+            p.widgets()
+                .into_iter()
+                .find(|(name, _)| name == kind)
+                .map(|(_, factory)| factory)
+        })
+    })
+}
+
+/// Get the CSS of the theme with the given name, taken from the last plugin
+/// providing it
+pub fn theme(name: &str) -> Option<String> {
+    PLUGINS.with(|plugins| {
+        plugins.borrow().iter().rev().find_map(|p| {
+            p.themes()
+                .into_iter()
+                .find(|(theme, _)| theme == name)
+                .map(|(_, css)| css)
+        })
+    })
+}
+
+/// Get the icon with the given name, taken from the last plugin providing
+/// it
+pub fn icon(name: &str) -> Option<Box<dyn Icon>> {
+    PLUGINS.with(|plugins| {
+        plugins.borrow().iter().rev().find_map(|p| {
+            p.icons()
+                .into_iter()
+                .find(|(icon, _)| icon == name)
+                .map(|(_, icon)| icon)
+        })
+    })
+}
+
+/// Get the CSS of all the plugins
+pub(crate) fn css() -> String {
+    PLUGINS.with(|plugins| {
+        plugins
+            .borrow()
+            .iter()
+            .map(|p| p.css())
+            .collect::<Vec<String>>()
+            .join("\n")
+    })
+}
+
+/// Get the javascript of all the plugins
+pub(crate) fn js() -> String {
+    PLUGINS.with(|plugins| {
+        plugins
+            .borrow()
+            .iter()
+            .map(|p| p.js())
+            .collect::<Vec<String>>()
+            .join("\n")
+    })
+}
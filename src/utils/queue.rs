@@ -0,0 +1,394 @@
+//! # Sync queue
+//!
+//! A `Queue` records the write operations of an application which works
+//! offline, such as the forms filled on the field, and runs them in the
+//! background once the network is available. It is set with
+//! `App::set_queue`, and a `JobRunner` runs each job, such as sending it to
+//! a server.
+//!
+//! * `App::enqueue` records a job, with a kind and a payload written in
+//!   JSON, in the file set with `Queue::set_file` if any, so that the jobs
+//!   survive a restart of the application.
+//! * The page reports whether the network is available with a network
+//!   event, handled by `WindowListener::on_network`. While it is online, the
+//!   jobs are sent to a thread which runs them one after the other.
+//! * A job which succeeds is removed from the queue. A job which fails
+//!   stays in the queue with its error, and is retried when the network is
+//!   back online, when another job is recorded, or with `App::sync`.
+//! * A `StatusBar` with a sync indicator shows the number of jobs waiting
+//!   to be run.
+//!
+//! The jobs are only run by `App::run` and `App::run_with`: the runner
+//! must be sent to the thread, so it cannot hold the `Rc` of the
+//! application.
+//!
+//! ## Example
+//!
+//! ```
+//! use json::JsonValue;
+//!
+//! use neutrino::testing::Harness;
+//! use neutrino::utils::event::Event;
+//! use neutrino::utils::queue::{Job, JobRunner, Queue};
+//! use neutrino::widgets::statusbar::StatusBar;
+//! use neutrino::{App, Window};
+//!
+//!
+//! struct MyJobRunner;
+//!
+//! impl JobRunner for MyJobRunner {
+//!     fn run(&self, job: &Job) -> Result<(), String> {
+//!         println!("POST /{} {}", job.kind(), job.payload().dump());
+//!         Ok(())
+//!     }
+//! }
+//!
+//!
+//! let mut my_queue = Queue::new();
+//! my_queue.set_runner(Box::new(MyJobRunner));
+//! App::set_queue(my_queue);
+//!
+//! let mut my_statusbar = StatusBar::new("my_statusbar");
+//! my_statusbar.set_sync();
+//!
+//! let mut my_window = Window::new();
+//! my_window.set_child(Box::new(my_statusbar));
+//!
+//! let mut harness = Harness::new(my_window);
+//! harness.send(Event::Network { online: false });
+//!
+//! let mut reading = JsonValue::new_object();
+//! reading["well"] = "W-12".into();
+//! reading["level"] = 4.2.into();
+//! let id = App::enqueue("readings", reading).unwrap();
+//! harness.send(Event::Update);
+//! assert_eq!(App::queue_length(), 1);
+//! assert!(harness.contains("Offline, 1 change waiting to sync"));
+//!
+//! harness.send(Event::Job { id, error: None });
+//! assert_eq!(App::queue_length(), 0);
+//! ```
+
+use std::cell::{Cell, RefCell};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Sender};
+use std::thread;
+
+use json::JsonValue;
+
+use crate::backend::Proxy;
+use crate::utils::event;
+
+/// The name of the queue, used as the source of its errors
+const QUEUE: &str = "queue";
+
+thread_local! {
+    static JOBS: RefCell<Option<Queue>> = const { RefCell::new(None) };
+    static ONLINE: Cell<bool> = const { Cell::new(true) };
+}
+
+/// # A write operation waiting to be run
+///
+/// ## Fields
+///
+/// ```text
+/// id: u64
+/// kind: String
+/// payload: JsonValue
+/// attempts: u32
+/// error: Option<String>
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Job {
+    id: u64,
+    kind: String,
+    payload: JsonValue,
+    attempts: u32,
+    error: Option<String>,
+}
+
+impl Job {
+    /// Get the identifier
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// Get the kind, such as the resource the job writes
+    pub fn kind(&self) -> &str {
+        &self.kind
+    }
+
+    /// Get the payload
+    pub fn payload(&self) -> &JsonValue {
+        &self.payload
+    }
+
+    /// Get the number of failed attempts
+    pub fn attempts(&self) -> u32 {
+        self.attempts
+    }
+
+    /// Get the error of the last failed attempt
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    /// Read a job written in JSON, None if it is malformed
+    fn from_json(value: &JsonValue) -> Option<Self> {
+        Some(Self {
+            id: value["id"].as_u64()?,
+            kind: value["kind"].as_str()?.to_string(),
+            payload: value["payload"].clone(),
+            attempts: value["attempts"].as_u32().unwrap_or(0),
+            error: value["error"].as_str().map(str::to_string),
+        })
+    }
+
+    /// Write the job in JSON
+    fn to_json(&self) -> JsonValue {
+        let mut value = JsonValue::new_object();
+        value["id"] = self.id.into();
+        value["kind"] = self.kind.as_str().into();
+        value["payload"] = self.payload.clone();
+        value["attempts"] = self.attempts.into();
+        value["error"] = self.error.clone().into();
+        value
+    }
+}
+
+/// # The runner of the jobs of a Queue
+///
+/// The runner is called in a thread, one job after the other.
+pub trait JobRunner: Send {
+    /// Function running a job, such as sending it to a server
+    fn run(&self, job: &Job) -> Result<(), String>;
+}
+
+/// # A queue of jobs run in the background when the network is online
+///
+/// ## Fields
+///
+/// ```text
+/// jobs: Vec<Job>
+/// next: u64
+/// file: Option<PathBuf>
+/// runner: Option<Box<dyn JobRunner>>
+/// sender: Option<Sender<Job>>
+/// running: Vec<u64>
+/// ```
+///
+/// ## Default values
+///
+/// ```text
+/// jobs: vec![]
+/// next: 1
+/// file: None
+/// runner: None
+/// sender: None
+/// running: vec![]
+/// ```
+pub struct Queue {
+    jobs: Vec<Job>,
+    next: u64,
+    file: Option<PathBuf>,
+    runner: Option<Box<dyn JobRunner>>,
+    sender: Option<Sender<Job>>,
+    running: Vec<u64>,
+}
+
+impl Queue {
+    /// Create a Queue
+    pub fn new() -> Self {
+        Self {
+            jobs: vec![],
+            next: 1,
+            file: None,
+            runner: None,
+            sender: None,
+            running: vec![],
+        }
+    }
+
+    /// Get the jobs waiting to be run, oldest first
+    pub fn jobs(&self) -> &Vec<Job> {
+        &self.jobs
+    }
+
+    /// Get the number of jobs waiting to be run
+    pub fn len(&self) -> usize {
+        self.jobs.len()
+    }
+
+    /// Return true if no job is waiting to be run
+    pub fn is_empty(&self) -> bool {
+        self.jobs.is_empty()
+    }
+
+    /// Set the file keeping the jobs, reading the ones it holds. A file
+    /// which cannot be read is reported to the window.
+    pub fn set_file<P: AsRef<Path>>(&mut self, path: P) {
+        let path = path.as_ref().to_path_buf();
+        if let Ok(content) = fs::read_to_string(&path) {
+            match json::parse(&content) {
+                Ok(value) => {
+                    self.jobs =
+                        value.members().filter_map(Job::from_json).collect();
+                }
+                Err(error) => event::report_error(
+                    QUEUE,
+                    &format!("{}: {}", path.display(), error),
+                ),
+            }
+        }
+        self.next = self.jobs.iter().map(|job| job.id + 1).max().unwrap_or(1);
+        self.file = Some(path);
+    }
+
+    /// Set the runner
+    pub fn set_runner(&mut self, runner: Box<dyn JobRunner>) {
+        self.runner = Some(runner);
+    }
+
+    /// Write the jobs into the file if any
+    fn write(&self) -> Result<(), String> {
+        let file = match &self.file {
+            Some(file) => file,
+            None => return Ok(()),
+        };
+        let jobs = self.jobs.iter().map(Job::to_json).collect::<Vec<_>>();
+        fs::write(file, JsonValue::from(jobs).pretty(2))
+            .map_err(|error| format!("{}: {}", file.display(), error))
+    }
+
+    /// Send the jobs which are not running to the thread
+    fn flush(&mut self) {
+        let sender = match &self.sender {
+            Some(sender) => sender,
+            None => return,
+        };
+        for job in &self.jobs {
+            if self.running.contains(&job.id) {
+                continue;
+            }
+            if sender.send(job.clone()).is_err() {
+                return;
+            }
+            self.running.push(job.id);
+        }
+    }
+}
+
+impl Default for Queue {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Set the queue of the application
+pub(crate) fn set(queue: Queue) {
+    JOBS.with(|current| *current.borrow_mut() = Some(queue));
+}
+
+/// Run a function on the queue of the application, None if there is none
+pub(crate) fn with<R>(f: impl FnOnce(&mut Queue) -> R) -> Option<R> {
+    JOBS.with(|queue| queue.borrow_mut().as_mut().map(f))
+}
+
+/// Record a job in the queue of the application, created if there is none,
+/// and run it if the network is online. Return the identifier of the job.
+pub(crate) fn enqueue(kind: &str, payload: JsonValue) -> Result<u64, String> {
+    JOBS.with(|queue| {
+        let mut queue = queue.borrow_mut();
+        let queue = queue.get_or_insert_with(Queue::new);
+        let id = queue.next;
+        queue.jobs.push(Job {
+            id,
+            kind: kind.to_string(),
+            payload,
+            attempts: 0,
+            error: None,
+        });
+        if let Err(error) = queue.write() {
+            queue.jobs.pop();
+            return Err(error);
+        }
+        queue.next += 1;
+        if online() {
+            queue.flush();
+        }
+        Ok(id)
+    })
+}
+
+/// Get the number of jobs waiting to be run
+pub(crate) fn length() -> usize {
+    with(|queue| queue.len()).unwrap_or(0)
+}
+
+/// Return true if the page reports the network as online
+pub(crate) fn online() -> bool {
+    ONLINE.with(Cell::get)
+}
+
+/// Set the network status reported by the page, running the jobs waiting
+/// when it is online
+pub(crate) fn set_online(online: bool) {
+    ONLINE.with(|current| current.set(online));
+    if online {
+        sync();
+    }
+}
+
+/// Run the jobs waiting in the queue of the application, the failed ones
+/// included, if the network is online
+pub(crate) fn sync() {
+    if online() {
+        with(Queue::flush);
+    }
+}
+
+/// Handle the result of a job run by the thread: the job is removed if it
+/// succeeded, and kept with its error otherwise
+pub(crate) fn finish(id: u64, error: Option<&str>) {
+    let result = with(|queue| {
+        queue.running.retain(|running| *running != id);
+        match error {
+            None => queue.jobs.retain(|job| job.id != id),
+            Some(error) => {
+                if let Some(job) = queue.jobs.iter_mut().find(|j| j.id == id) {
+                    job.attempts += 1;
+                    job.error = Some(error.to_string());
+                }
+            }
+        }
+        queue.write()
+    });
+    if let Some(Err(error)) = result {
+        event::report_error(QUEUE, &error);
+    }
+}
+
+/// Run the jobs of the queue of the application in a thread, which sends a
+/// job event with the result of each of them
+pub(crate) fn start(proxy: Proxy) {
+    let runner = match with(|queue| queue.runner.take()).flatten() {
+        Some(runner) => runner,
+        None => return,
+    };
+    let (sender, receiver) = mpsc::channel::<Job>();
+    thread::spawn(move || {
+        for job in receiver {
+            let mut event = JsonValue::new_object();
+            event["type"] = "Job".into();
+            event["id"] = job.id.into();
+            event["error"] = runner.run(&job).err().into();
+            let js = format!("emit({});", event.dump());
+            if !proxy.eval(&js) {
+                return;
+            }
+        }
+    });
+    with(|queue| queue.sender = Some(sender));
+    sync();
+}
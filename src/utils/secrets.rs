@@ -0,0 +1,373 @@
+//! # Secrets
+//!
+//! `Secrets` keeps the tokens and credentials of an application, such as
+//! the API token used by a listener, in an encrypted file instead of a
+//! plain configuration file. The file holds named values, encrypted and
+//! authenticated with ChaCha20-Poly1305, and is written each time a value is
+//! set or removed.
+//!
+//! The key of the file is either:
+//!
+//! * a random key kept in the keyring of the system, under the given
+//!   service name, with `Secrets::open`: the Secret Service through
+//!   `secret-tool` on Linux, and the Keychain through `security` on macOS.
+//!   Where there is no keyring, such as on Windows, `Secrets::open` returns
+//!   an error rather than keeping the key next to the file it protects.
+//! * a key derived from a password typed by the user, with
+//!   `Secrets::open_with_password`, which works everywhere.
+//!
+//! A file which was modified, or opened with another key, cannot be read.
+//! The file is replaced at once when it is written, only readable by the
+//! user on Unix, and an error reading it, other than a missing file, is
+//! returned instead of starting from an empty store.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use std::cell::RefCell;
+//! use std::rc::Rc;
+//!
+//! use neutrino::utils::secrets::Secrets;
+//!
+//!
+//! let secrets = Secrets::open("secrets.json", "com.example.notes").unwrap();
+//! let secrets = Rc::new(RefCell::new(secrets));
+//!
+//! if secrets.borrow().get("token").is_none() {
+//!     secrets.borrow_mut().set("token", "d2f1c0e7a9b4").unwrap();
+//! }
+//! ```
+
+use std::fs;
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use json::JsonValue;
+
+use crate::utils::crypto::{self, hex, pbkdf2_sha256};
+
+/// The version of the format of the file
+const VERSION: u32 = 1;
+
+/// The number of iterations deriving a key from a password
+const ITERATIONS: u32 = 100_000;
+
+/// The account under which the key is kept in the keyring
+const ACCOUNT: &str = "secrets";
+
+/// # An encrypted file of named secrets
+///
+/// ## Fields
+///
+/// ```text
+/// path: PathBuf
+/// key: [u8; 32]
+/// salt: Option<Vec<u8>>
+/// entries: Vec<(String, String)>
+/// ```
+///
+/// ## Example
+///
+/// ```
+/// use neutrino::utils::secrets::Secrets;
+///
+/// let path = std::env::temp_dir().join("neutrino-secrets-example.json");
+/// let _ = std::fs::remove_file(&path);
+///
+/// let mut secrets = Secrets::open_with_password(&path, "correct horse").unwrap();
+/// secrets.set("token", "d2f1c0e7a9b4").unwrap();
+///
+/// let secrets = Secrets::open_with_password(&path, "correct horse").unwrap();
+/// assert_eq!(secrets.get("token"), Some("d2f1c0e7a9b4"));
+/// assert!(Secrets::open_with_password(&path, "battery staple").is_err());
+///
+/// let content = std::fs::read_to_string(&path).unwrap();
+/// assert!(!content.contains("d2f1c0e7a9b4"));
+/// ```
+pub struct Secrets {
+    path: PathBuf,
+    key: [u8; 32],
+    salt: Option<Vec<u8>>,
+    entries: Vec<(String, String)>,
+}
+
+impl Secrets {
+    /// Open the secrets at the given path, with a key kept in the keyring
+    /// under the given service name, such as `com.example.notes`. The key
+    /// is created the first time. Where there is no keyring, an error is
+    /// returned, and the secrets should be opened with
+    /// `open_with_password`.
+    pub fn open<P: AsRef<Path>>(
+        path: P,
+        service: &str,
+    ) -> Result<Self, String> {
+        let path = path.as_ref().to_path_buf();
+        let key = match keyring_get(service).and_then(|hex| decode_key(&hex)) {
+            Some(key) => key,
+            None => {
+                let key = random_key()?;
+                if !keyring_set(service, &encode_key(&key)) {
+                    return Err(format!(
+                        "{}: no keyring to keep the key, open the secrets \
+                         with a password instead",
+                        path.display()
+                    ));
+                }
+                key
+            }
+        };
+        Self::read(path, key, None)
+    }
+
+    /// Open the secrets at the given path, with a key derived from the
+    /// given password
+    pub fn open_with_password<P: AsRef<Path>>(
+        path: P,
+        password: &str,
+    ) -> Result<Self, String> {
+        let path = path.as_ref().to_path_buf();
+        let salt = match fs::read_to_string(&path) {
+            Ok(content) => {
+                let file = json::parse(&content).map_err(|error| {
+                    format!("{}: {}", path.display(), error)
+                })?;
+                field(&file, "salt").ok_or_else(|| {
+                    format!("{}: the salt is missing", path.display())
+                })?
+            }
+            Err(error) if error.kind() == ErrorKind::NotFound => random(16)?,
+            Err(error) => return Err(format!("{}: {}", path.display(), error)),
+        };
+        let key = pbkdf2_sha256(password.as_bytes(), &salt, ITERATIONS);
+        Self::read(path, key, Some(salt))
+    }
+
+    /// Get the value of a secret
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|(entry, _)| entry == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Get the names of the secrets
+    pub fn names(&self) -> Vec<&str> {
+        self.entries.iter().map(|(name, _)| name.as_str()).collect()
+    }
+
+    /// Set the value of a secret and write the file
+    pub fn set(&mut self, name: &str, value: &str) -> Result<(), String> {
+        match self.entries.iter_mut().find(|(entry, _)| entry == name) {
+            Some(entry) => entry.1 = value.to_string(),
+            None => self.entries.push((name.to_string(), value.to_string())),
+        }
+        self.write()
+    }
+
+    /// Remove a secret and write the file
+    pub fn remove(&mut self, name: &str) -> Result<(), String> {
+        self.entries.retain(|(entry, _)| entry != name);
+        self.write()
+    }
+
+    /// Read the secrets from the file at the given path, empty if it does
+    /// not exist
+    fn read(
+        path: PathBuf,
+        key: [u8; 32],
+        salt: Option<Vec<u8>>,
+    ) -> Result<Self, String> {
+        let mut secrets = Self {
+            path,
+            key,
+            salt,
+            entries: vec![],
+        };
+        let content = match fs::read_to_string(&secrets.path) {
+            Ok(content) => content,
+            Err(error) if error.kind() == ErrorKind::NotFound => {
+                return Ok(secrets)
+            }
+            Err(error) => {
+                return Err(format!("{}: {}", secrets.path.display(), error))
+            }
+        };
+        let error =
+            |message: &str| format!("{}: {}", secrets.path.display(), message);
+        let file = json::parse(&content).map_err(|e| error(&e.to_string()))?;
+        if file["version"].as_u32() != Some(VERSION) {
+            return Err(error("unknown version"));
+        }
+        let nonce = field(&file, "nonce").filter(|nonce| nonce.len() == 12);
+        let (nonce, data) = match (nonce, field(&file, "data")) {
+            (Some(nonce), Some(data)) => (nonce, data),
+            _ => return Err(error("the file is damaged")),
+        };
+        let plain = crypto::open(&key, &nonce, &data)
+            .ok_or_else(|| error("the key is wrong or the file is damaged"))?;
+        let plain =
+            String::from_utf8(plain).map_err(|_| error("invalid text"))?;
+        let entries = json::parse(&plain).map_err(|e| error(&e.to_string()))?;
+        secrets.entries = entries
+            .entries()
+            .filter_map(|(name, value)| {
+                value
+                    .as_str()
+                    .map(|value| (name.to_string(), value.to_string()))
+            })
+            .collect();
+        Ok(secrets)
+    }
+
+    /// Encrypt the secrets into the file, replacing it at once so that it
+    /// is never left half written
+    fn write(&self) -> Result<(), String> {
+        let mut entries = JsonValue::new_object();
+        for (name, value) in &self.entries {
+            entries[name.as_str()] = value.as_str().into();
+        }
+        let nonce = random(12)?;
+        let data = crypto::seal(&self.key, &nonce, entries.dump().as_bytes());
+        let mut file = JsonValue::new_object();
+        file["version"] = VERSION.into();
+        if let Some(salt) = &self.salt {
+            file["salt"] = base64::encode(salt).into();
+        }
+        file["nonce"] = base64::encode(&nonce).into();
+        file["data"] = base64::encode(&data).into();
+        write_private(&self.path, file.pretty(2).as_bytes())
+    }
+}
+
+/// Get the key kept in the keyring under the given service name, None
+/// if there is none or no keyring
+fn keyring_get(service: &str) -> Option<String> {
+    let output = if cfg!(target_os = "macos") {
+        Command::new("security")
+            .args(["find-generic-password", "-s", service])
+            .args(["-a", ACCOUNT, "-w"])
+            .stderr(Stdio::null())
+            .output()
+    } else if cfg!(unix) {
+        Command::new("secret-tool")
+            .args(["lookup", "service", service, "account", ACCOUNT])
+            .stderr(Stdio::null())
+            .output()
+    } else {
+        return None;
+    };
+    let output = output.ok().filter(|output| output.status.success())?;
+    Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Keep a key in the keyring under the given service name, returning
+/// false if there is no keyring. The key is given on the standard input
+/// of the command, so that it does not appear in the list of processes.
+fn keyring_set(service: &str, key: &str) -> bool {
+    let (mut command, input) = if cfg!(target_os = "macos") {
+        let mut command = Command::new("security");
+        command.arg("-i");
+        let input = format!(
+            "add-generic-password -U -s \"{}\" -a {} -w {}\n",
+            service.replace('"', ""),
+            ACCOUNT,
+            key
+        );
+        (command, input)
+    } else if cfg!(unix) {
+        let mut command = Command::new("secret-tool");
+        command
+            .args(["store", &format!("--label={}", service)])
+            .args(["service", service, "account", ACCOUNT]);
+        (command, key.to_string())
+    } else {
+        return false;
+    };
+    let child = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn();
+    let mut child = match child {
+        Ok(child) => child,
+        Err(_) => return false,
+    };
+    if let Some(mut stdin) = child.stdin.take() {
+        if stdin.write_all(input.as_bytes()).is_err() {
+            return false;
+        }
+    }
+    let stored = child.wait().map(|status| status.success());
+    stored.unwrap_or(false) && keyring_get(service).as_deref() == Some(key)
+}
+
+/// Replace the content of a file: the content is written into a new file
+/// of the same directory, only readable by the user on Unix, synced to the
+/// disk, and then renamed over the file
+fn write_private(path: &Path, content: &[u8]) -> Result<(), String> {
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    let temporary =
+        path.with_file_name(format!(".{}.{}.tmp", name, hex(&random(8)?)));
+    let written = create_private(&temporary)
+        .and_then(|mut file| {
+            file.write_all(content)?;
+            file.sync_all()
+        })
+        .and_then(|_| fs::rename(&temporary, path));
+    written.map_err(|error| {
+        let _ = fs::remove_file(&temporary);
+        format!("{}: {}", path.display(), error)
+    })
+}
+
+/// Create a new file, only readable by the user on Unix
+fn create_private(path: &Path) -> std::io::Result<fs::File> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    options.open(path)
+}
+
+/// Read a base64 field of a file
+fn field(file: &JsonValue, name: &str) -> Option<Vec<u8>> {
+    file[name]
+        .as_str()
+        .and_then(|value| base64::decode(value).ok())
+}
+
+/// Write a key in hexadecimal
+fn encode_key(key: &[u8; 32]) -> String {
+    hex(key)
+}
+
+/// Read a key written in hexadecimal
+fn decode_key(hex: &str) -> Option<[u8; 32]> {
+    let hex = hex.trim();
+    if hex.len() != 64 {
+        return None;
+    }
+    let mut key = [0u8; 32];
+    for (i, byte) in key.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(hex.get(2 * i..2 * i + 2)?, 16).ok()?;
+    }
+    Some(key)
+}
+
+/// Get random bytes from the system
+fn random(length: usize) -> Result<Vec<u8>, String> {
+    let mut bytes = vec![0u8; length];
+    getrandom::getrandom(&mut bytes).map_err(|error| error.to_string())?;
+    Ok(bytes)
+}
+
+/// Get a random key from the system
+fn random_key() -> Result<[u8; 32], String> {
+    let mut key = [0u8; 32];
+    key.copy_from_slice(&random(32)?);
+    Ok(key)
+}
@@ -0,0 +1,212 @@
+//! # Persisted settings
+//!
+//! `Settings` keeps the preferences of an application and the state of its
+//! window between two runs, in a JSON file of the configuration directory of
+//! the platform: `$XDG_CONFIG_HOME` or `~/.config` on Linux,
+//! `~/Library/Application Support` on macOS and `%APPDATA%` on Windows. It is
+//! set with `App::set_settings`, and the file is written each time a value
+//! is set.
+//!
+//! * `App::setting` and `App::set_setting` read and write the preferences of
+//!   the application, such as the unit of the temperatures, with any value
+//!   which can be written in JSON.
+//! * The size of the window is saved when it is resized, the selected tab of
+//!   the Tabs when it changes, and the size of the resizable Containers
+//!   when they are resized, under the name of the widget.
+//! * At start-up, the window and the widgets restore their state from the
+//!   settings on a restore event, before the first render, and the
+//!   `on_restore` function of the listener of the window reads the
+//!   preferences of the application.
+//!
+//! An error writing the file is reported to the window.
+//!
+//! ## Example
+//!
+//! ```
+//! use neutrino::testing::Harness;
+//! use neutrino::utils::settings::Settings;
+//! use neutrino::widgets::label::Label;
+//! use neutrino::widgets::tabs::Tabs;
+//! use neutrino::{App, Window};
+//!
+//!
+//! fn window() -> Window {
+//!     let mut inbox = Label::new("inbox");
+//!     inbox.set_text("No new message");
+//!
+//!     let mut archive = Label::new("archive");
+//!     archive.set_text("42 archived messages");
+//!
+//!     let mut my_tabs = Tabs::new("folders");
+//!     my_tabs.add("Inbox", Box::new(inbox));
+//!     my_tabs.add("Archive", Box::new(archive));
+//!
+//!     let mut my_window = Window::new();
+//!     my_window.set_child(Box::new(my_tabs));
+//!     my_window
+//! }
+//!
+//!
+//! let path = std::env::temp_dir().join("neutrino-settings-example.json");
+//! let _ = std::fs::remove_file(&path);
+//!
+//! App::set_settings(Settings::open_file(&path).unwrap());
+//! App::set_setting("unit", "celsius").unwrap();
+//!
+//! let mut harness = Harness::new(window());
+//! harness.change("folders", "1");
+//!
+//! App::set_settings(Settings::open_file(&path).unwrap());
+//! assert_eq!(App::setting("unit").unwrap(), "celsius");
+//!
+//! let harness = Harness::new(window());
+//! assert!(harness.contains("42 archived messages"));
+//! ```
+
+use std::cell::RefCell;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use json::JsonValue;
+
+use crate::utils::event;
+use crate::utils::platform::{self, Os};
+
+/// The name of the settings, used as the source of their errors
+const SETTINGS: &str = "settings";
+
+thread_local! {
+    static CURRENT: RefCell<Option<Settings>> = const { RefCell::new(None) };
+}
+
+/// # A JSON file of named values
+///
+/// ## Fields
+///
+/// ```text
+/// path: PathBuf
+/// values: JsonValue
+/// ```
+pub struct Settings {
+    path: PathBuf,
+    values: JsonValue,
+}
+
+impl Settings {
+    /// Open the settings of the application with the given identifier, such
+    /// as `com.example.notes`, in the configuration directory of the
+    /// platform
+    pub fn open(app: &str) -> Result<Self, String> {
+        match config_dir() {
+            Some(dir) => Self::open_file(dir.join(app).join("settings.json")),
+            None => Err("no configuration directory".to_string()),
+        }
+    }
+
+    /// Open the settings kept in the file at the given path, empty if it
+    /// does not exist yet
+    pub fn open_file<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        let path = path.as_ref().to_path_buf();
+        let values = match fs::read_to_string(&path) {
+            Ok(content) => match json::parse(&content) {
+                Ok(values) if values.is_object() => values,
+                Ok(_) => {
+                    return Err(format!("{}: not an object", path.display()))
+                }
+                Err(error) => {
+                    return Err(format!("{}: {}", path.display(), error))
+                }
+            },
+            Err(_) => JsonValue::new_object(),
+        };
+        Ok(Self { path, values })
+    }
+
+    /// Get the path of the file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Get a value, None if it is not set
+    pub fn get(&self, key: &str) -> Option<&JsonValue> {
+        match &self.values[key] {
+            JsonValue::Null => None,
+            value => Some(value),
+        }
+    }
+
+    /// Set a value and write the file
+    pub fn set<V: Into<JsonValue>>(
+        &mut self,
+        key: &str,
+        value: V,
+    ) -> Result<(), String> {
+        self.values[key] = value.into();
+        self.write()
+    }
+
+    /// Remove a value and write the file
+    pub fn remove(&mut self, key: &str) -> Result<(), String> {
+        self.values.remove(key);
+        self.write()
+    }
+
+    /// Write the values into the file, creating its directory
+    fn write(&self) -> Result<(), String> {
+        let error = |error: std::io::Error| {
+            format!("{}: {}", self.path.display(), error)
+        };
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir).map_err(error)?;
+        }
+        fs::write(&self.path, self.values.pretty(2)).map_err(error)
+    }
+}
+
+/// Get the configuration directory of the platform
+pub fn config_dir() -> Option<PathBuf> {
+    let home = || env::var_os("HOME").map(PathBuf::from);
+    match platform::os() {
+        Os::Windows => env::var_os("APPDATA").map(PathBuf::from),
+        Os::MacOS => Some(home()?.join("Library/Application Support")),
+        _ => match env::var_os("XDG_CONFIG_HOME") {
+            Some(dir) if !dir.is_empty() => Some(PathBuf::from(dir)),
+            _ => Some(home()?.join(".config")),
+        },
+    }
+}
+
+/// Set the settings of the application
+pub(crate) fn set(settings: Settings) {
+    CURRENT.with(|current| *current.borrow_mut() = Some(settings));
+}
+
+/// Return true if the application has settings
+pub(crate) fn is_set() -> bool {
+    CURRENT.with(|settings| settings.borrow().is_some())
+}
+
+/// Get a value of the settings of the application
+pub(crate) fn get(key: &str) -> Option<JsonValue> {
+    CURRENT.with(|settings| settings.borrow().as_ref()?.get(key).cloned())
+}
+
+/// Set a value of the settings of the application, if it has settings
+pub(crate) fn set_value(key: &str, value: JsonValue) -> Result<(), String> {
+    CURRENT.with(|settings| match settings.borrow_mut().as_mut() {
+        Some(settings) => settings.set(key, value),
+        None => Err("the application has no settings".to_string()),
+    })
+}
+
+/// Save a value of the state of the window or of a widget, if the
+/// application has settings, reporting the errors
+pub(crate) fn save(key: &str, value: JsonValue) {
+    if !is_set() || get(key).as_ref() == Some(&value) {
+        return;
+    }
+    if let Err(error) = set_value(key, value) {
+        event::report_error(SETTINGS, &error);
+    }
+}
@@ -0,0 +1,164 @@
+//! # State management
+//!
+//! A `Store` holds the state of the application, or of a part of it, and
+//! changes it only through actions: the listeners holding the store
+//! dispatch actions, which are reduced by the state, and read the state or a
+//! value derived from it on update.
+//!
+//! The Store is a handle which can be cloned into as many listeners as
+//! needed, all the clones sharing the same state. When an action has been
+//! dispatched, the window is updated even if the event which triggered it
+//! does not update it by itself, and it is updated again if other actions
+//! are dispatched while the listeners are updating.
+//!
+//! ## Example
+//!
+//! ```
+//! use neutrino::utils::store::{Reducer, Store};
+//!
+//!
+//! struct Counter {
+//!     count: i32,
+//! }
+//!
+//! enum Action {
+//!     Increment,
+//!     Reset,
+//! }
+//!
+//! impl Reducer for Counter {
+//!     type Action = Action;
+//!
+//!     fn reduce(&mut self, action: Action) {
+//!         match action {
+//!             Action::Increment => self.count += 1,
+//!             Action::Reset => self.count = 0,
+//!         }
+//!     }
+//! }
+//!
+//!
+//! let store = Store::new(Counter { count: 0 });
+//! let observer = store.clone();
+//!
+//! store.dispatch(Action::Increment);
+//! store.dispatch(Action::Increment);
+//! assert_eq!(observer.select(|counter| counter.count * 10), 20);
+//!
+//! observer.dispatch(Action::Reset);
+//! assert_eq!(store.state().count, 0);
+//! ```
+
+use std::cell::{Cell, Ref, RefCell};
+use std::collections::VecDeque;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static CHANGED: AtomicBool = AtomicBool::new(false);
+
+/// Return true if an action has been dispatched to a store since the last
+/// call
+pub(crate) fn take_changed() -> bool {
+    CHANGED.swap(false, Ordering::Relaxed)
+}
+
+/// # A state changed by actions
+pub trait Reducer {
+    /// The type of the actions
+    type Action;
+
+    /// Apply an action to the state
+    fn reduce(&mut self, action: Self::Action);
+}
+
+/// # A function called with the new state after each action
+type Subscriber<T> = Box<dyn Fn(&T)>;
+
+/// # The shared content of the clones of a Store
+struct Shared<T: Reducer> {
+    state: RefCell<T>,
+    queue: RefCell<VecDeque<T::Action>>,
+    dispatching: Cell<bool>,
+    version: Cell<u64>,
+    subscribers: RefCell<Vec<Subscriber<T>>>,
+}
+
+/// # A store holding a state changed by actions
+///
+/// An action dispatched while another one is being reduced, for instance by
+/// a subscriber, is queued and reduced right after it.
+///
+/// ## Fields
+///
+/// ```text
+/// shared: Rc<Shared<T>>
+/// ```
+pub struct Store<T: Reducer> {
+    shared: Rc<Shared<T>>,
+}
+
+impl<T: Reducer> Store<T> {
+    /// Create a Store
+    pub fn new(state: T) -> Self {
+        Self {
+            shared: Rc::new(Shared {
+                state: RefCell::new(state),
+                queue: RefCell::new(VecDeque::new()),
+                dispatching: Cell::new(false),
+                version: Cell::new(0),
+                subscribers: RefCell::new(vec![]),
+            }),
+        }
+    }
+
+    /// Get the state
+    pub fn state(&self) -> Ref<'_, T> {
+        self.shared.state.borrow()
+    }
+
+    /// Get a value derived from the state
+    pub fn select<R>(&self, selector: impl FnOnce(&T) -> R) -> R {
+        selector(&self.shared.state.borrow())
+    }
+
+    /// Get the number of actions reduced since the store has been created
+    pub fn version(&self) -> u64 {
+        self.shared.version.get()
+    }
+
+    /// Add a function called with the new state after each action
+    pub fn subscribe(&self, subscriber: Box<dyn Fn(&T)>) {
+        self.shared.subscribers.borrow_mut().push(subscriber);
+    }
+
+    /// Dispatch an action, reducing it and notifying the subscribers
+    pub fn dispatch(&self, action: T::Action) {
+        self.shared.queue.borrow_mut().push_back(action);
+        if self.shared.dispatching.get() {
+            return;
+        }
+        self.shared.dispatching.set(true);
+        loop {
+            let action = match self.shared.queue.borrow_mut().pop_front() {
+                Some(action) => action,
+                None => break,
+            };
+            self.shared.state.borrow_mut().reduce(action);
+            self.shared.version.set(self.shared.version.get() + 1);
+            CHANGED.store(true, Ordering::Relaxed);
+            let state = self.shared.state.borrow();
+            for subscriber in self.shared.subscribers.borrow().iter() {
+                subscriber(&state);
+            }
+        }
+        self.shared.dispatching.set(false);
+    }
+}
+
+impl<T: Reducer> Clone for Store<T> {
+    fn clone(&self) -> Self {
+        Self {
+            shared: Rc::clone(&self.shared),
+        }
+    }
+}
@@ -0,0 +1,180 @@
+//! # Inline style of a widget
+//!
+//! A Style overrides the look given by the theme to a single widget: its
+//! background and foreground colors, the size and weight of its font, its
+//! border and the radius of its corners. It is set on the builder of the
+//! widget with `set_style`, and can be changed at runtime from the
+//! `on_update` function of the listener, for instance to turn a Label red on
+//! error. The colors and borders are CSS values, such as `#ff0000` or
+//! `1px solid red`.
+//!
+//! ## Example
+//!
+//! ```
+//! use neutrino::utils::style::{FontWeight, Style};
+//! use neutrino::widgets::label::{LabelListener, LabelState};
+//!
+//!
+//! struct ErrorListener;
+//!
+//! impl LabelListener for ErrorListener {
+//!     fn on_update(&self, state: &mut LabelState) {
+//!         let mut style = Style::new();
+//!         style.set_foreground("red");
+//!         style.set_font_weight(FontWeight::Bold);
+//!         state.set_style(style);
+//!     }
+//! }
+//! ```
+
+use crate::utils::html::escape;
+
+/// # The weight of a font
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FontWeight {
+    Normal,
+    Bold,
+    Light,
+    Value(u32),
+}
+
+impl FontWeight {
+    /// Return the CSS value of the weight
+    fn css(self) -> String {
+        match self {
+            FontWeight::Normal => "normal".to_string(),
+            FontWeight::Bold => "bold".to_string(),
+            FontWeight::Light => "lighter".to_string(),
+            FontWeight::Value(value) => value.to_string(),
+        }
+    }
+}
+
+/// # The inline style of a widget
+///
+/// ## Fields
+///
+/// ```text
+/// background: Option<String>
+/// foreground: Option<String>
+/// font_size: Option<u32>
+/// font_weight: Option<FontWeight>
+/// border: Option<String>
+/// radius: Option<u32>
+/// ```
+///
+/// ## Default values
+///
+/// ```text
+/// background: None
+/// foreground: None
+/// font_size: None
+/// font_weight: None
+/// border: None
+/// radius: None
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Style {
+    background: Option<String>,
+    foreground: Option<String>,
+    font_size: Option<u32>,
+    font_weight: Option<FontWeight>,
+    border: Option<String>,
+    radius: Option<u32>,
+}
+
+impl Style {
+    /// Create a Style
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the background color
+    pub fn background(&self) -> Option<&str> {
+        self.background.as_deref()
+    }
+
+    /// Get the foreground color
+    pub fn foreground(&self) -> Option<&str> {
+        self.foreground.as_deref()
+    }
+
+    /// Get the font size, in pixels
+    pub fn font_size(&self) -> Option<u32> {
+        self.font_size
+    }
+
+    /// Get the font weight
+    pub fn font_weight(&self) -> Option<FontWeight> {
+        self.font_weight
+    }
+
+    /// Get the border
+    pub fn border(&self) -> Option<&str> {
+        self.border.as_deref()
+    }
+
+    /// Get the radius of the corners, in pixels
+    pub fn radius(&self) -> Option<u32> {
+        self.radius
+    }
+
+    /// Set the background color
+    pub fn set_background(&mut self, background: &str) {
+        self.background = Some(background.to_string());
+    }
+
+    /// Set the foreground color
+    pub fn set_foreground(&mut self, foreground: &str) {
+        self.foreground = Some(foreground.to_string());
+    }
+
+    /// Set the font size, in pixels
+    pub fn set_font_size(&mut self, font_size: u32) {
+        self.font_size = Some(font_size);
+    }
+
+    /// Set the font weight
+    pub fn set_font_weight(&mut self, font_weight: FontWeight) {
+        self.font_weight = Some(font_weight);
+    }
+
+    /// Set the border, such as `1px solid red`
+    pub fn set_border(&mut self, border: &str) {
+        self.border = Some(border.to_string());
+    }
+
+    /// Set the radius of the corners, in pixels
+    pub fn set_radius(&mut self, radius: u32) {
+        self.radius = Some(radius);
+    }
+
+    /// Return the inline CSS of the style, escaped for a style attribute
+    pub fn css(&self) -> String {
+        let mut s = "".to_string();
+        if let Some(background) = &self.background {
+            s.push_str(&format!("background: {}; ", value(background)));
+        }
+        if let Some(foreground) = &self.foreground {
+            s.push_str(&format!("color: {}; ", value(foreground)));
+        }
+        if let Some(font_size) = self.font_size {
+            s.push_str(&format!("font-size: {}px; ", font_size));
+        }
+        if let Some(font_weight) = self.font_weight {
+            s.push_str(&format!("font-weight: {}; ", font_weight.css()));
+        }
+        if let Some(border) = &self.border {
+            s.push_str(&format!("border: {}; ", value(border)));
+        }
+        if let Some(radius) = self.radius {
+            s.push_str(&format!("border-radius: {}px; ", radius));
+        }
+        s
+    }
+}
+
+/// Escape a CSS value, which cannot end its declaration
+fn value(text: &str) -> String {
+    escape(&text.replace(';', ""))
+}
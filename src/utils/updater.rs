@@ -0,0 +1,391 @@
+//! # Update checks
+//!
+//! An Updater checks a release feed when the window is displayed, in a
+//! thread, and hands the Release to the window over a channel when its
+//! version is newer than the one of the application. The window then
+//! processes an `UpdateAvailable` event, received by
+//! `WindowListener::on_update_available`, which can tell the user, for
+//! instance with a Snackbar. The release never goes through the page, so a
+//! script of the page or a client of the server cannot forge it.
+//!
+//! The feed is a JSON document fetched over HTTPS with the `curl` command,
+//! shipped with Windows 10, macOS and most Linux distributions. It is either
+//! an object with a `version`, the `url` of the installer, its `sha256`
+//! digest in hexadecimal and optional `notes`, or the latest release of a
+//! GitHub repository, from
+//! `https://api.github.com/repos/OWNER/REPO/releases/latest`, whose tag is
+//! the version and whose asset ending with the suffix set by `set_asset` is
+//! the installer, with the digest given by GitHub.
+//!
+//! `Release::install` downloads the installer over HTTPS into a new
+//! directory only readable by the user, checks its digest against the one
+//! of the feed, and launches it: directly on Windows, with `msiexec` for a
+//! `.msi`, and with the opener of the system elsewhere. A release without
+//! a digest, or whose installer is not served over HTTPS, is not installed.
+//!
+//! ## Example
+//!
+//! ```
+//! use neutrino::utils::updater::{self, Release, Updater};
+//! use neutrino::{Window, WindowListener};
+//! use neutrino::utils::event::Key;
+//!
+//!
+//! struct MyWindowListener;
+//!
+//! impl WindowListener for MyWindowListener {
+//!     fn on_key(&self, _key: Key) {}
+//!
+//!     fn on_update_available(&self, release: &Release) {
+//!         println!("Version {} is available", release.version());
+//!     }
+//! }
+//!
+//!
+//! let mut my_updater = Updater::new(
+//!     "https://example.com/my_app/latest.json",
+//!     env!("CARGO_PKG_VERSION"),
+//! );
+//! my_updater.set_asset(".msi");
+//!
+//! let mut my_window = Window::new();
+//! my_window.set_updater(my_updater);
+//! my_window.set_listener(Box::new(MyWindowListener));
+//!
+//! assert!(updater::newer("v1.10.0", "1.9.2"));
+//! assert!(!updater::newer("1.2.0", "1.2.0"));
+//!
+//! let unsigned = Release::new("1.2.0", "https://example.com/setup.msi", "", "");
+//! assert!(unsigned.install().is_err());
+//! ```
+
+use std::cell::RefCell;
+use std::cmp::Ordering;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+
+use crate::backend::Proxy;
+use crate::utils::crypto::{equal, hex, sha256};
+use json::JsonValue;
+
+/// The name of the updater, used as the source of its errors
+pub(crate) const UPDATER: &str = "updater";
+
+/// The javascript asking the page to send an update event, so that the
+/// window takes the result of the check
+const WAKE_JS: &str = r#"emit({"type": "Update"});"#;
+
+thread_local! {
+    static RESULT: RefCell<Option<Receiver<Result<Release, String>>>> =
+        const { RefCell::new(None) };
+}
+
+/// The arguments of curl restricting it to HTTPS, redirections included
+const HTTPS_ONLY: [&str; 4] = ["--proto", "=https", "--proto-redir", "=https"];
+
+/// # A release of the application
+///
+/// ## Fields
+///
+/// ```text
+/// version: String
+/// url: String
+/// sha256: String
+/// notes: String
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Release {
+    version: String,
+    url: String,
+    sha256: String,
+    notes: String,
+}
+
+impl Release {
+    /// Create a Release from its version, the URL of its installer, the
+    /// SHA-256 digest of the installer in hexadecimal and its notes
+    pub fn new(version: &str, url: &str, sha256: &str, notes: &str) -> Self {
+        Self {
+            version: version.to_string(),
+            url: url.to_string(),
+            sha256: sha256.trim().to_lowercase(),
+            notes: notes.to_string(),
+        }
+    }
+
+    /// Get the version
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+
+    /// Get the URL of the installer, empty if the feed does not give one
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Get the SHA-256 digest of the installer in hexadecimal, empty if the
+    /// feed does not give one
+    pub fn sha256(&self) -> &str {
+        &self.sha256
+    }
+
+    /// Get the notes
+    pub fn notes(&self) -> &str {
+        &self.notes
+    }
+
+    /// Download the installer into a new private directory, check its
+    /// digest and launch it, returning its path
+    pub fn install(&self) -> Result<PathBuf, String> {
+        let error = |message: &str| format!("{}: {}", self.version, message);
+        if self.url.is_empty() {
+            return Err(error("the release has no installer"));
+        }
+        if !self.url.starts_with("https://") {
+            return Err(error("the installer is not served over HTTPS"));
+        }
+        if self.sha256.len() != 64 {
+            return Err(error("the release has no SHA-256 digest"));
+        }
+        let path = private_dir()?.join(installer_name(&self.url));
+        let downloaded = Command::new("curl")
+            .args(HTTPS_ONLY)
+            .args(["-fsSL", "-o"])
+            .arg(&path)
+            .arg(&self.url)
+            .status()
+            .map_err(|error| format!("curl: {}", error))?;
+        if !downloaded.success() {
+            return Err(format!("{}: the download failed", self.url));
+        }
+        let content = fs::read(&path)
+            .map_err(|error| format!("{}: {}", path.display(), error))?;
+        let digest = hex(&sha256(&content));
+        if !equal(digest.as_bytes(), self.sha256.as_bytes()) {
+            let _ = fs::remove_file(&path);
+            return Err(error("the digest of the installer does not match"));
+        }
+        launch(&path)
+            .map_err(|error| format!("{}: {}", path.display(), error))?;
+        Ok(path)
+    }
+}
+
+/// # A checker of a release feed
+///
+/// ## Fields
+///
+/// ```text
+/// feed: String
+/// version: String
+/// asset: Option<String>
+/// ```
+///
+/// ## Default values
+///
+/// ```text
+/// feed: feed.to_string()
+/// version: version.to_string()
+/// asset: None
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Updater {
+    feed: String,
+    version: String,
+    asset: Option<String>,
+}
+
+impl Updater {
+    /// Create an Updater from the URL of the feed and the version of the
+    /// application
+    pub fn new(feed: &str, version: &str) -> Self {
+        Self {
+            feed: feed.to_string(),
+            version: version.to_string(),
+            asset: None,
+        }
+    }
+
+    /// Get the URL of the feed
+    pub fn feed(&self) -> &str {
+        &self.feed
+    }
+
+    /// Get the version of the application
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+
+    /// Get the suffix of the installer among the assets of a GitHub release
+    pub fn asset(&self) -> Option<&str> {
+        self.asset.as_deref()
+    }
+
+    /// Set the suffix of the installer among the assets of a GitHub release,
+    /// such as `.msi`, the first asset being taken by default
+    pub fn set_asset(&mut self, asset: &str) {
+        self.asset = Some(asset.to_string());
+    }
+
+    /// Fetch the feed over HTTPS and return the latest release if it is
+    /// newer than the application
+    pub fn check(&self) -> Result<Option<Release>, String> {
+        if !self.feed.starts_with("https://") {
+            return Err(format!(
+                "{}: the feed is not served over HTTPS",
+                self.feed
+            ));
+        }
+        let output = Command::new("curl")
+            .args(HTTPS_ONLY)
+            .args(["-fsSL", "-H", "Accept: application/json"])
+            .arg(&self.feed)
+            .output()
+            .map_err(|error| format!("curl: {}", error))?;
+        if !output.status.success() {
+            return Err(format!("{}: the feed cannot be fetched", self.feed));
+        }
+        let body = String::from_utf8_lossy(&output.stdout);
+        let feed = json::parse(&body)
+            .map_err(|error| format!("{}: {}", self.feed, error))?;
+        let release = self.release(&feed)?;
+        if newer(release.version(), &self.version) {
+            Ok(Some(release))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Read the latest release of a feed
+    fn release(&self, feed: &JsonValue) -> Result<Release, String> {
+        if let Some(version) = feed["version"].as_str() {
+            return Ok(Release::new(
+                version,
+                feed["url"].as_str().unwrap_or(""),
+                feed["sha256"].as_str().unwrap_or(""),
+                feed["notes"].as_str().unwrap_or(""),
+            ));
+        }
+        let version = match feed["tag_name"].as_str() {
+            Some(version) => version,
+            None => {
+                return Err(format!("{}: the version is missing", self.feed))
+            }
+        };
+        let asset = feed["assets"].members().find(|asset| {
+            let name = asset["name"].as_str().unwrap_or("");
+            match &self.asset {
+                Some(suffix) => name.ends_with(suffix.as_str()),
+                None => true,
+            }
+        });
+        let (url, digest) = match asset {
+            Some(asset) => (
+                asset["browser_download_url"].as_str().unwrap_or(""),
+                asset["digest"].as_str().unwrap_or(""),
+            ),
+            None => ("", ""),
+        };
+        Ok(Release::new(
+            version,
+            url,
+            digest.strip_prefix("sha256:").unwrap_or(""),
+            feed["body"].as_str().unwrap_or(""),
+        ))
+    }
+}
+
+/// Return true if a version is newer than another one, comparing their
+/// numbers one by one. A `v` prefix and a suffix such as `-beta` are
+/// ignored.
+pub fn newer(version: &str, current: &str) -> bool {
+    numbers(version).cmp(&numbers(current)) == Ordering::Greater
+}
+
+/// Get the numbers of a version
+fn numbers(version: &str) -> Vec<u64> {
+    let version = version.trim().trim_start_matches(['v', 'V']);
+    let version = version.split(['-', '+']).next().unwrap_or("");
+    let mut numbers: Vec<u64> = version
+        .split('.')
+        .map(|number| number.parse().unwrap_or(0))
+        .collect();
+    while numbers.last() == Some(&0) {
+        numbers.pop();
+    }
+    numbers
+}
+
+/// Create a new directory only readable by the user on Unix, in the
+/// temporary directory
+fn private_dir() -> Result<PathBuf, String> {
+    let mut suffix = [0u8; 8];
+    getrandom::getrandom(&mut suffix).map_err(|error| error.to_string())?;
+    let dir = env::temp_dir().join(format!("neutrino-update-{}", hex(&suffix)));
+    let mut builder = fs::DirBuilder::new();
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::DirBuilderExt;
+        builder.mode(0o700);
+    }
+    builder
+        .create(&dir)
+        .map_err(|error| format!("{}: {}", dir.display(), error))?;
+    Ok(dir)
+}
+
+/// Get the name of the downloaded installer: `installer`, with the
+/// extension of the URL so that the system knows how to open it
+fn installer_name(url: &str) -> String {
+    let file = url.rsplit('/').next().unwrap_or("");
+    let file = file.split(['?', '#']).next().unwrap_or("");
+    match file.rsplit_once('.') {
+        Some((_, extension))
+            if !extension.is_empty()
+                && extension.chars().all(|c| c.is_ascii_alphanumeric()) =>
+        {
+            format!("installer.{}", extension.to_lowercase())
+        }
+        _ => "installer".to_string(),
+    }
+}
+
+/// Launch an installer without going through a shell
+fn launch(path: &Path) -> std::io::Result<std::process::Child> {
+    let msi = path.extension().is_some_and(|extension| extension == "msi");
+    if cfg!(target_os = "windows") && msi {
+        Command::new("msiexec").arg("/i").arg(path).spawn()
+    } else if cfg!(target_os = "windows") {
+        Command::new(path).spawn()
+    } else if cfg!(target_os = "macos") {
+        Command::new("open").arg(path).spawn()
+    } else {
+        Command::new("xdg-open").arg(path).spawn()
+    }
+}
+
+/// Check the feed in a thread, which sends the newer release or the error
+/// over a channel to the current thread and wakes the page
+pub(crate) fn check(updater: Updater, proxy: Proxy) {
+    let (sender, receiver) = mpsc::channel();
+    RESULT.with(|result| *result.borrow_mut() = Some(receiver));
+    thread::spawn(move || {
+        let result = match updater.check() {
+            Ok(Some(release)) => Ok(release),
+            Ok(None) => return,
+            Err(error) => Err(error),
+        };
+        if sender.send(result).is_ok() {
+            proxy.eval(WAKE_JS);
+        }
+    });
+}
+
+/// Take the result of the check, once the thread has sent it
+pub(crate) fn received() -> Option<Result<Release, String>> {
+    RESULT.with(|result| result.borrow().as_ref()?.try_recv().ok())
+}
@@ -0,0 +1,161 @@
+/// A function checking a value, returning an error message if it is invalid
+pub type ValidatorFn = Box<dyn Fn(&str) -> Result<(), String>>;
+
+/// # A rule checking the value of a form widget
+///
+/// The message of the first rule the value breaks is displayed under the
+/// widget when its value changes.
+///
+/// `Email` checks the shape of an email address: a local part, an `@` and a
+/// domain of dot-separated labels made of letters, digits and hyphens.
+/// Regular expressions can be checked with a `Custom` validator wrapping the
+/// regex engine of your choice, such as the regex crate:
+///
+/// ```text
+/// let postcode = Regex::new(r"^\d{5}$").unwrap();
+/// let rule = Validator::custom(move |value| match postcode.is_match(value) {
+///     true => Ok(()),
+///     false => Err("Must be a postcode of 5 digits".to_string()),
+/// });
+/// ```
+///
+/// ## Example
+///
+/// ```
+/// use neutrino::utils::validator::Validator;
+///
+/// let required = Validator::Required;
+/// assert!(required.validate("").is_err());
+///
+/// let age = Validator::Range { min: 0.0, max: 130.0 };
+/// assert!(age.validate("42").is_ok());
+/// assert!(age.validate("-1").is_err());
+///
+/// let even = Validator::custom(|value| match value.parse::<i32>() {
+///     Ok(n) if n % 2 == 0 => Ok(()),
+///     _ => Err("Must be an even number".to_string()),
+/// });
+/// assert!(even.validate("3").is_err());
+///
+/// assert!(Validator::Email.validate("jane.doe@example.com").is_ok());
+/// assert!(Validator::Email.validate("jane.doe@example").is_err());
+/// ```
+///
+/// A field failing a rule displays its message:
+///
+/// ```
+/// use neutrino::testing::Harness;
+/// use neutrino::utils::validator::Validator;
+/// use neutrino::widgets::textinput::TextInput;
+/// use neutrino::Window;
+///
+/// let mut email = TextInput::new("email");
+/// email.add_validator(Validator::Required);
+/// email.add_validator(Validator::Email);
+///
+/// let mut my_window = Window::new();
+/// my_window.set_child(Box::new(email));
+///
+/// let mut harness = Harness::new(my_window);
+/// harness.change("email", "jane.doe@");
+/// assert!(harness.contains("Must be a valid email address"));
+///
+/// harness.change("email", "jane.doe@example.com");
+/// assert!(!harness.contains("Must be a valid email address"));
+/// ```
+pub enum Validator {
+    Required,
+    MinLength(usize),
+    MaxLength(usize),
+    Range { min: f64, max: f64 },
+    Email,
+    Custom(ValidatorFn),
+}
+
+impl Validator {
+    /// Create a custom validator from a closure returning an error message
+    /// when the value is invalid
+    pub fn custom<F>(f: F) -> Self
+    where
+        F: Fn(&str) -> Result<(), String> + 'static,
+    {
+        Validator::Custom(Box::new(f))
+    }
+
+    /// Check the value, returning an error message if it is invalid
+    pub fn validate(&self, value: &str) -> Result<(), String> {
+        match self {
+            Validator::Required => {
+                if value.trim().is_empty() {
+                    Err("This field is required".to_string())
+                } else {
+                    Ok(())
+                }
+            }
+            Validator::MinLength(length) => {
+                if value.chars().count() < *length {
+                    Err(format!("At least {} characters are required", length))
+                } else {
+                    Ok(())
+                }
+            }
+            Validator::MaxLength(length) => {
+                if value.chars().count() > *length {
+                    Err(format!("At most {} characters are allowed", length))
+                } else {
+                    Ok(())
+                }
+            }
+            Validator::Range { min, max } => {
+                match value.trim().parse::<f64>() {
+                    Ok(number) => {
+                        if number < *min || number > *max {
+                            Err(format!(
+                                "The value must be between {} and {}",
+                                min, max
+                            ))
+                        } else {
+                            Ok(())
+                        }
+                    }
+                    Err(_) => Err("The value must be a number".to_string()),
+                }
+            }
+            Validator::Email => {
+                if is_email(value.trim()) {
+                    Ok(())
+                } else {
+                    Err("Must be a valid email address".to_string())
+                }
+            }
+            Validator::Custom(f) => f(value),
+        }
+    }
+}
+
+/// Check a value against a list of validators, returning the first error
+/// message
+pub fn validate(validators: &[Validator], value: &str) -> Option<String> {
+    validators
+        .iter()
+        .filter_map(|validator| validator.validate(value).err())
+        .next()
+}
+
+/// Return true if a value has the shape of an email address
+fn is_email(value: &str) -> bool {
+    let (local, domain) = match value.rsplit_once('@') {
+        Some(parts) => parts,
+        None => return false,
+    };
+    let label = |label: &str| {
+        !label.is_empty()
+            && !label.starts_with('-')
+            && !label.ends_with('-')
+            && label.chars().all(|c| c.is_alphanumeric() || c == '-')
+    };
+    !local.is_empty()
+        && !local.contains(|c: char| c.is_whitespace() || c == '@')
+        && domain.contains('.')
+        && domain.split('.').all(label)
+}
@@ -0,0 +1,78 @@
+//! # Hot reload
+//!
+//! In debug builds, the layout file set by `Window::set_layout_file` and the
+//! style file set by `Window::set_custom_css_file` are watched while the
+//! application runs. When one of them changes on disk, the widget tree is
+//! rebuilt from the layout, with the listeners bound again, and the theme
+//! and the custom CSS are replaced, without restarting the application.
+//!
+//! The model of the application, held by the listeners, is kept: the new
+//! widgets get their state from it on the update which follows the reload.
+//! The state which only lives in the widgets, such as a text typed in an
+//! input which is not saved by its listener, is lost.
+//!
+//! A file which cannot be read or parsed, for instance while it is being
+//! written, is reported as an error event with the `reload` source, received
+//! by `WindowListener::on_error`, and the window is kept as it is until the
+//! next change.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use neutrino::utils::layout::Bindings;
+//! use neutrino::App;
+//!
+//! fn bindings() -> Bindings {
+//!     let mut bindings = Bindings::new();
+//!     // bindings.bind::<dyn ButtonListener>("save", Box::new(...));
+//!     bindings
+//! }
+//!
+//! let mut window = App::from_file("ui.json", bindings()).unwrap();
+//! window.set_layout_file("ui.json", Box::new(bindings));
+//! window.set_custom_css_file("ui.css").unwrap();
+//!
+//! # #[cfg(feature = "web-view")]
+//! App::run(window);
+//! ```
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use crate::backend::Proxy;
+
+/// The message sent by the page when a watched file has changed
+const RELOAD: &str = r#"emit({"type": "Reload"})"#;
+
+/// The interval between two checks of the watched files
+const INTERVAL: Duration = Duration::from_millis(500);
+
+/// Watch the given files in a thread, asking the page to send a Reload event
+/// when one of them has been modified. The thread stops with the window.
+pub(crate) fn watch(paths: Vec<PathBuf>, proxy: Proxy) {
+    thread::spawn(move || {
+        let mut modified: Vec<Option<SystemTime>> =
+            paths.iter().map(|path| modification(path)).collect();
+        loop {
+            thread::sleep(INTERVAL);
+            let current: Vec<Option<SystemTime>> =
+                paths.iter().map(|path| modification(path)).collect();
+            if current == modified {
+                continue;
+            }
+            modified = current;
+            if !proxy.eval(RELOAD) {
+                break;
+            }
+        }
+    });
+}
+
+/// Get the time of the last modification of a file, if it exists
+fn modification(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
@@ -0,0 +1,703 @@
+//! # WebDriver adapter
+//!
+//! With the `webdriver` feature, `App::set_webdriver` exposes the window run
+//! by `App::run` or `App::run_with` to WebDriver clients, such as Selenium or
+//! WebdriverIO, so that acceptance tests can drive a real build of an
+//! application: finding its widgets, clicking them, typing into them and
+//! reading their text. The adapter listens on the given address and speaks
+//! the following subset of the W3C WebDriver protocol:
+//!
+//! ```text
+//! GET    /status
+//! POST   /session
+//! DELETE /session/{session}
+//! GET    /session/{session}/timeouts
+//! POST   /session/{session}/timeouts
+//! GET    /session/{session}/title
+//! GET    /session/{session}/source
+//! POST   /session/{session}/element
+//! POST   /session/{session}/elements
+//! POST   /session/{session}/element/{element}/element
+//! POST   /session/{session}/element/{element}/elements
+//! POST   /session/{session}/element/{element}/click
+//! POST   /session/{session}/element/{element}/clear
+//! POST   /session/{session}/element/{element}/value
+//! GET    /session/{session}/element/{element}/text
+//! GET    /session/{session}/element/{element}/name
+//! GET    /session/{session}/element/{element}/attribute/{name}
+//! GET    /session/{session}/element/{element}/property/{name}
+//! GET    /session/{session}/element/{element}/selected
+//! GET    /session/{session}/element/{element}/enabled
+//! GET    /session/{session}/element/{element}/displayed
+//! ```
+//!
+//! The elements are found with the `css selector`, `tag name` and `xpath`
+//! strategies, and with the `testid` strategy, which matches the element of
+//! the widget of the given name, or the element with the given `data-testid`
+//! attribute. The commands are run in the page: a click sends the mouse
+//! events to the element at the center of the found one, and the typed text
+//! sends the key and input events of each character, followed by a change
+//! event, so the events reach the listeners as they do for a user. A command
+//! answers once the events it sent have been processed.
+//!
+//! There is one session at a time, and deleting it does not close the
+//! window. Anyone who can reach the address controls the application: the
+//! adapter should listen on the loopback interface, in the builds used for
+//! testing only.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use neutrino::webdriver::WebDriver;
+//! use neutrino::{App, Window};
+//!
+//! if std::env::var("MY_APP_WEBDRIVER").is_ok() {
+//!     App::set_webdriver(WebDriver::new("127.0.0.1:4444"));
+//! }
+//!
+//! let my_window = Window::new();
+//! # #[cfg(feature = "web-view")]
+//! App::run(my_window);
+//! ```
+
+use std::cell::RefCell;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use json::JsonValue;
+
+use crate::backend::Proxy;
+use crate::utils::event;
+
+/// The name of the adapter, used as the source of its errors
+const WEBDRIVER: &str = "webdriver";
+
+/// The key of an element reference in the WebDriver protocol
+const ELEMENT: &str = "element-6066-11e4-a52e-4f735466cecf";
+
+/// The longest a command waits for the page
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+/// The largest request body accepted from a client
+const MAX_BODY: usize = 16 * 1024 * 1024;
+
+/// The javascript of the helpers running the commands in the page
+const DRIVER_JS: &str = r#"{
+    elements: [],
+    keys: {
+        "\uE003": "Backspace", "\uE004": "Tab", "\uE006": "Enter",
+        "\uE007": "Enter", "\uE00C": "Escape", "\uE00D": " ",
+        "\uE010": "End", "\uE011": "Home", "\uE012": "ArrowLeft",
+        "\uE013": "ArrowUp", "\uE014": "ArrowRight", "\uE015": "ArrowDown",
+        "\uE017": "Delete"
+    },
+    fail: function(error, message) {
+        let exception = new Error(message);
+        exception.driverError = error;
+        throw exception;
+    },
+    reference: function(element) {
+        let index = this.elements.indexOf(element);
+        if (index < 0) {
+            index = this.elements.push(element) - 1;
+        }
+        return "e" + index;
+    },
+    element: function(reference) {
+        let element = this.elements[Number(String(reference).slice(1))];
+        if (element === undefined) {
+            this.fail("no such element", "unknown element " + reference);
+        }
+        if (!document.contains(element)) {
+            this.fail("stale element reference", "the element " + reference + " is no longer in the page");
+        }
+        return element;
+    },
+    find: function(root, using, value) {
+        switch (using) {
+            case "testid":
+                return Array.from(root.querySelectorAll("[id], [data-testid]")).filter(function(element) {
+                    return element.id === value || element.getAttribute("data-testid") === value;
+                });
+            case "css selector":
+                return Array.from(root.querySelectorAll(value));
+            case "tag name":
+                return Array.from(root.getElementsByTagName(value));
+            case "xpath": {
+                let nodes = document.evaluate(value, root, null, XPathResult.ORDERED_NODE_SNAPSHOT_TYPE, null);
+                let elements = [];
+                for (let i = 0; i < nodes.snapshotLength; i++) {
+                    if (nodes.snapshotItem(i).nodeType === Node.ELEMENT_NODE) {
+                        elements.push(nodes.snapshotItem(i));
+                    }
+                }
+                return elements;
+            }
+            default:
+                this.fail("invalid argument", "unsupported strategy " + using);
+        }
+    },
+    click: function(element) {
+        element.scrollIntoView({ block: "nearest", inline: "nearest" });
+        let rect = element.getBoundingClientRect();
+        let x = rect.left + rect.width / 2;
+        let y = rect.top + rect.height / 2;
+        let target = document.elementFromPoint(x, y);
+        if (target === null || !element.contains(target)) {
+            target = element;
+        }
+        let init = { bubbles: true, cancelable: true, view: window, button: 0, clientX: x, clientY: y };
+        target.dispatchEvent(new MouseEvent("mousedown", init));
+        if (typeof target.focus === "function") {
+            target.focus();
+        }
+        target.dispatchEvent(new MouseEvent("mouseup", init));
+        target.dispatchEvent(new MouseEvent("click", init));
+    },
+    type: function(element, text) {
+        let target = element.matches("input, textarea, select") ? element : (element.querySelector("input, textarea, select") || element);
+        target.focus();
+        for (let character of text) {
+            let key = this.keys[character] || character;
+            let init = { key: key, bubbles: true, cancelable: true };
+            if (!target.dispatchEvent(new KeyboardEvent("keydown", init)) || !("value" in target)) {
+                target.dispatchEvent(new KeyboardEvent("keyup", init));
+                continue;
+            }
+            if (key.length === 1) {
+                target.value += key;
+                target.dispatchEvent(new InputEvent("input", { data: key, bubbles: true }));
+            } else if (key === "Backspace") {
+                target.value = target.value.slice(0, -1);
+                target.dispatchEvent(new InputEvent("input", { bubbles: true }));
+            }
+            target.dispatchEvent(new KeyboardEvent("keyup", init));
+        }
+        target.dispatchEvent(new Event("change", { bubbles: true }));
+    },
+    clear: function(element) {
+        let target = element.matches("input, textarea, select") ? element : (element.querySelector("input, textarea, select") || element);
+        if (!("value" in target)) {
+            this.fail("invalid element state", "the element cannot be cleared");
+        }
+        target.focus();
+        target.value = "";
+        target.dispatchEvent(new InputEvent("input", { bubbles: true }));
+        target.dispatchEvent(new Event("change", { bubbles: true }));
+    },
+    selected: function(element) {
+        return element.checked === true || element.selected === true ||
+            element.getAttribute("aria-selected") === "true" ||
+            element.getAttribute("aria-checked") === "true" ||
+            element.classList.contains("selected");
+    },
+    enabled: function(element) {
+        return !element.disabled && element.closest("[aria-disabled='true'], .disabled") === null;
+    },
+    displayed: function(element) {
+        let style = getComputedStyle(element);
+        return element.getClientRects().length > 0 && style.visibility !== "hidden" && style.display !== "none";
+    }
+}"#;
+
+thread_local! {
+    static DRIVER: RefCell<Option<WebDriver>> = const { RefCell::new(None) };
+}
+
+/// The commands waiting for the page, with the channel of their answer
+static PENDING: Mutex<Vec<(u64, Sender<JsonValue>)>> = Mutex::new(Vec::new());
+
+/// # The address of the WebDriver adapter
+///
+/// ## Fields
+///
+/// ```text
+/// address: String
+/// ```
+///
+/// ## Default values
+///
+/// ```text
+/// address: address.to_string()
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WebDriver {
+    address: String,
+}
+
+impl WebDriver {
+    /// Create a WebDriver listening on the given address, such as
+    /// `127.0.0.1:4444`
+    pub fn new(address: &str) -> Self {
+        Self {
+            address: address.to_string(),
+        }
+    }
+
+    /// Get the address
+    pub fn address(&self) -> &str {
+        &self.address
+    }
+}
+
+/// A WebDriver session
+struct Session {
+    id: String,
+    implicit: u64,
+}
+
+/// The state shared by the connections of the adapter
+struct Driver {
+    proxy: Proxy,
+    session: Mutex<Option<Session>>,
+    next: AtomicU64,
+}
+
+/// An error of the WebDriver protocol, as its code and its message
+struct Error {
+    code: String,
+    message: String,
+}
+
+impl Error {
+    /// Create an Error
+    fn new(code: &str, message: &str) -> Self {
+        Self {
+            code: code.to_string(),
+            message: message.to_string(),
+        }
+    }
+
+    /// Get the HTTP status of the error
+    fn status(&self) -> &'static str {
+        match self.code.as_str() {
+            "invalid argument" | "invalid element state" => "400 Bad Request",
+            "no such element"
+            | "stale element reference"
+            | "invalid session id"
+            | "no such window"
+            | "unknown command" => "404 Not Found",
+            "unknown method" => "405 Method Not Allowed",
+            _ => "500 Internal Server Error",
+        }
+    }
+}
+
+/// Set the WebDriver adapter of the application
+pub(crate) fn set(driver: WebDriver) {
+    DRIVER.with(|current| *current.borrow_mut() = Some(driver));
+}
+
+/// Listen to the WebDriver clients in a thread, if an adapter is set
+pub(crate) fn start(proxy: Proxy) {
+    let driver = match DRIVER.with(|driver| driver.borrow_mut().take()) {
+        Some(driver) => driver,
+        None => return,
+    };
+    let listener = match TcpListener::bind(&driver.address) {
+        Ok(listener) => listener,
+        Err(error) => {
+            let message = format!("{}: {}", driver.address, error);
+            event::report_error(WEBDRIVER, &message);
+            return;
+        }
+    };
+    let driver = Arc::new(Driver {
+        proxy,
+        session: Mutex::new(None),
+        next: AtomicU64::new(1),
+    });
+    thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            let driver = Arc::clone(&driver);
+            thread::spawn(move || connect(stream, &driver));
+        }
+    });
+}
+
+/// Hand the answer of the page to the command waiting for it
+pub(crate) fn reply(message: &JsonValue) {
+    let id = match message["id"].as_u64() {
+        Some(id) => id,
+        None => return,
+    };
+    let mut pending = lock(&PENDING);
+    if let Some(i) = pending.iter().position(|(pending, _)| *pending == id) {
+        let (_, sender) = pending.remove(i);
+        let _ = sender.send(message["result"].clone());
+    }
+}
+
+/// Answer the requests of a client
+fn connect(stream: TcpStream, driver: &Driver) {
+    let mut writer = match stream.try_clone() {
+        Ok(writer) => writer,
+        Err(_) => return,
+    };
+    let mut reader = BufReader::new(stream);
+    while let Some((method, path, body)) = read_request(&mut reader) {
+        let (status, value) = match command(driver, &method, &path, &body) {
+            Ok(value) => ("200 OK", value),
+            Err(error) => {
+                let mut value = JsonValue::new_object();
+                value["error"] = error.code.as_str().into();
+                value["message"] = error.message.as_str().into();
+                value["stacktrace"] = "".into();
+                (error.status(), value)
+            }
+        };
+        let mut response = JsonValue::new_object();
+        response["value"] = value;
+        if respond(&mut writer, status, &response.dump()).is_err() {
+            return;
+        }
+    }
+}
+
+/// Read the method, the path and the body of a request
+fn read_request(
+    reader: &mut BufReader<TcpStream>,
+) -> Option<(String, String, JsonValue)> {
+    let mut line = String::new();
+    if reader.read_line(&mut line).ok()? == 0 {
+        return None;
+    }
+    let mut words = line.split_whitespace();
+    let method = words.next()?.to_string();
+    let path = words.next()?.split('?').next()?.to_string();
+    let mut length = 0;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line).ok()? == 0 {
+            return None;
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("Content-Length") {
+                length = value.trim().parse::<usize>().ok()?;
+            }
+        }
+    }
+    if length > MAX_BODY {
+        return None;
+    }
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body).ok()?;
+    let body = match String::from_utf8_lossy(&body).trim() {
+        "" => JsonValue::new_object(),
+        body => json::parse(body).unwrap_or(JsonValue::Null),
+    };
+    Some((method, path, body))
+}
+
+/// Write a response
+fn respond(
+    stream: &mut TcpStream,
+    status: &str,
+    body: &str,
+) -> std::io::Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: application/json; charset=utf-8\r\nContent-Length: {}\r\nCache-Control: no-cache\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    )
+}
+
+/// Run a command of a client
+fn command(
+    driver: &Driver,
+    method: &str,
+    path: &str,
+    body: &JsonValue,
+) -> Result<JsonValue, Error> {
+    if !body.is_object() {
+        return Err(Error::new(
+            "invalid argument",
+            "the body is not an object",
+        ));
+    }
+    let parts = path
+        .trim_matches('/')
+        .split('/')
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<&str>>();
+    match (method, parts.as_slice()) {
+        ("GET", ["status"]) => {
+            let (ready, message) = match *lock(&driver.session) {
+                Some(_) => (false, "a session is running"),
+                None => (true, "ready"),
+            };
+            let mut value = JsonValue::new_object();
+            value["ready"] = ready.into();
+            value["message"] = message.into();
+            Ok(value)
+        }
+        ("POST", ["session"]) => new_session(driver),
+        ("DELETE", ["session", id]) => {
+            check_session(driver, id)?;
+            *lock(&driver.session) = None;
+            Ok(JsonValue::Null)
+        }
+        (_, ["session", id, ..]) => {
+            check_session(driver, id)?;
+            session_command(driver, method, &parts[2..], body)
+        }
+        ("GET", _) | ("POST", _) | ("DELETE", _) => {
+            Err(Error::new("unknown command", path))
+        }
+        _ => Err(Error::new("unknown method", method)),
+    }
+}
+
+/// Run a command of the current session, given the parts of the path
+/// following its identifier
+fn session_command(
+    driver: &Driver,
+    method: &str,
+    parts: &[&str],
+    body: &JsonValue,
+) -> Result<JsonValue, Error> {
+    match (method, parts) {
+        ("GET", ["timeouts"]) => Ok(timeouts(driver)),
+        ("POST", ["timeouts"]) => {
+            if let Some(implicit) = body["implicit"].as_u64() {
+                if let Some(session) = lock(&driver.session).as_mut() {
+                    session.implicit = implicit;
+                }
+            }
+            Ok(JsonValue::Null)
+        }
+        ("GET", ["title"]) => page(driver, "return document.title;"),
+        ("GET", ["source"]) => {
+            page(driver, "return document.documentElement.outerHTML;")
+        }
+        ("POST", ["element"]) => find(driver, None, body, false),
+        ("POST", ["elements"]) => find(driver, None, body, true),
+        ("POST", ["element", element, "element"]) => {
+            find(driver, Some(element), body, false)
+        }
+        ("POST", ["element", element, "elements"]) => {
+            find(driver, Some(element), body, true)
+        }
+        ("POST", ["element", element, "click"]) => {
+            element_js(driver, element, "driver.click(element);")
+        }
+        ("POST", ["element", element, "clear"]) => {
+            element_js(driver, element, "driver.clear(element);")
+        }
+        ("POST", ["element", element, "value"]) => {
+            let text = match body["text"].as_str() {
+                Some(text) => text.to_string(),
+                None => body["value"]
+                    .members()
+                    .filter_map(JsonValue::as_str)
+                    .collect::<String>(),
+            };
+            let js =
+                format!("driver.type(element, {});", json::stringify(text));
+            element_js(driver, element, &js)
+        }
+        ("GET", ["element", element, "text"]) => {
+            element_js(driver, element, "return element.innerText;")
+        }
+        ("GET", ["element", element, "name"]) => {
+            element_js(driver, element, "return element.tagName.toLowerCase();")
+        }
+        ("GET", ["element", element, "attribute", name]) => {
+            let js = format!(
+                "return element.getAttribute({});",
+                json::stringify(*name)
+            );
+            element_js(driver, element, &js)
+        }
+        ("GET", ["element", element, "property", name]) => {
+            let js = format!(
+                "let value = element[{}]; return value === undefined ? null : value;",
+                json::stringify(*name)
+            );
+            element_js(driver, element, &js)
+        }
+        ("GET", ["element", element, "selected"]) => {
+            element_js(driver, element, "return driver.selected(element);")
+        }
+        ("GET", ["element", element, "enabled"]) => {
+            element_js(driver, element, "return driver.enabled(element);")
+        }
+        ("GET", ["element", element, "displayed"]) => {
+            element_js(driver, element, "return driver.displayed(element);")
+        }
+        ("GET", _) | ("POST", _) | ("DELETE", _) => {
+            Err(Error::new("unknown command", &parts.join("/")))
+        }
+        _ => Err(Error::new("unknown method", method)),
+    }
+}
+
+/// Lock a mutex, even if a connection panicked while holding it
+fn lock<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|error| error.into_inner())
+}
+
+/// Create a session, if none is running
+fn new_session(driver: &Driver) -> Result<JsonValue, Error> {
+    let mut session = lock(&driver.session);
+    if session.is_some() {
+        return Err(Error::new(
+            "session not created",
+            "a session is already running",
+        ));
+    }
+    let mut bytes = [0; 16];
+    getrandom::getrandom(&mut bytes).map_err(|error| {
+        Error::new("session not created", &error.to_string())
+    })?;
+    let id = bytes.iter().map(|byte| format!("{:02x}", byte)).collect();
+    let platform = match std::env::consts::OS {
+        "macos" => "mac",
+        os => os,
+    };
+    let mut capabilities = JsonValue::new_object();
+    capabilities["browserName"] = "neutrino".into();
+    capabilities["browserVersion"] = env!("CARGO_PKG_VERSION").into();
+    capabilities["platformName"] = platform.into();
+    let mut value = JsonValue::new_object();
+    value["sessionId"] = JsonValue::from(&id as &str);
+    value["capabilities"] = capabilities;
+    *session = Some(Session { id, implicit: 0 });
+    Ok(value)
+}
+
+/// Fail unless the identifier is the one of the current session
+fn check_session(driver: &Driver, id: &str) -> Result<(), Error> {
+    match lock(&driver.session).as_ref() {
+        Some(session) if session.id == id => Ok(()),
+        _ => Err(Error::new("invalid session id", id)),
+    }
+}
+
+/// Get the timeouts of the current session
+fn timeouts(driver: &Driver) -> JsonValue {
+    let implicit = lock(&driver.session)
+        .as_ref()
+        .map(|session| session.implicit)
+        .unwrap_or(0);
+    let mut value = JsonValue::new_object();
+    value["implicit"] = implicit.into();
+    value["pageLoad"] = 300_000.into();
+    value["script"] = 30_000.into();
+    value
+}
+
+/// Find the elements matching a locator, in the page or in an element,
+/// waiting for them up to the implicit timeout of the session
+fn find(
+    driver: &Driver,
+    root: Option<&str>,
+    body: &JsonValue,
+    all: bool,
+) -> Result<JsonValue, Error> {
+    let (using, value) = match (body["using"].as_str(), body["value"].as_str())
+    {
+        (Some(using), Some(value)) => (using, value),
+        _ => {
+            return Err(Error::new(
+                "invalid argument",
+                "a locator has a using and a value",
+            ))
+        }
+    };
+    let root = match root {
+        Some(root) => format!("driver.element({})", json::stringify(root)),
+        None => "document".to_string(),
+    };
+    let js = format!(
+        "return driver.find({}, {}, {}).map(function(element) {{ return driver.reference(element); }});",
+        root,
+        json::stringify(using),
+        json::stringify(value)
+    );
+    let implicit = timeouts(driver)["implicit"].as_u64().unwrap_or(0);
+    let deadline = Instant::now() + Duration::from_millis(implicit);
+    let references = loop {
+        let references = page(driver, &js)?;
+        if !references.is_empty() || Instant::now() >= deadline {
+            break references;
+        }
+        thread::sleep(Duration::from_millis(100));
+    };
+    let mut elements = references.members().map(|reference| {
+        let mut element = JsonValue::new_object();
+        element[ELEMENT] = reference.clone();
+        element
+    });
+    match (all, elements.next()) {
+        (true, first) => {
+            Ok(first.into_iter().chain(elements).collect::<Vec<_>>().into())
+        }
+        (false, Some(element)) => Ok(element),
+        (false, None) => Err(Error::new("no such element", value)),
+    }
+}
+
+/// Run javascript on an element of the page, given as `element`
+fn element_js(
+    driver: &Driver,
+    reference: &str,
+    js: &str,
+) -> Result<JsonValue, Error> {
+    page(
+        driver,
+        &format!(
+            "let element = driver.element({}); {}",
+            json::stringify(reference),
+            js
+        ),
+    )
+}
+
+/// Run the body of a javascript function in the page, given the helpers as
+/// `driver`, and return its result once the events it sent have been
+/// processed
+fn page(driver: &Driver, body: &str) -> Result<JsonValue, Error> {
+    let id = driver.next.fetch_add(1, Ordering::SeqCst);
+    let (sender, receiver) = mpsc::channel();
+    lock(&PENDING).push((id, sender));
+    let js = format!(
+        r#"(function() {{
+    let driver = window.neutrinoDriver || (window.neutrinoDriver = {});
+    let result;
+    try {{
+        result = {{ value: (function(driver) {{ {} }})(driver) }};
+    }} catch (error) {{
+        result = {{ error: error.driverError || "javascript error", message: String(error.message || error) }};
+    }}
+    emit({{ type: "Driver", id: {}, result: result }});
+}})();"#,
+        DRIVER_JS, body, id
+    );
+    let result = if driver.proxy.eval(&js) {
+        receiver
+            .recv_timeout(TIMEOUT)
+            .map_err(|_| Error::new("timeout", "the page did not answer"))
+    } else {
+        Err(Error::new("no such window", "the window is closed"))
+    };
+    lock(&PENDING).retain(|(pending, _)| *pending != id);
+    let result = result?;
+    match result["error"].as_str() {
+        Some(error) => {
+            Err(Error::new(error, result["message"].as_str().unwrap_or("")))
+        }
+        None => Ok(result["value"].clone()),
+    }
+}
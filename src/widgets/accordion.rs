@@ -0,0 +1,410 @@
+use crate::utils::event::Event;
+use crate::utils::html::escape;
+use crate::utils::layoutoptions::LayoutOptions;
+use crate::utils::style::Style;
+use crate::widgets::widget::{self, Widget};
+
+/// # The state of an Accordion
+///
+/// ## Fields
+///
+/// ```text
+/// titles: Vec<String>
+/// children: Vec<Box<dyn Widget>>
+/// expanded: Vec<bool>
+/// exclusive: bool
+/// layout: LayoutOptions
+/// style: Style
+/// ```
+pub struct AccordionState {
+    titles: Vec<String>,
+    children: Vec<Box<dyn Widget>>,
+    expanded: Vec<bool>,
+    exclusive: bool,
+    layout: LayoutOptions,
+    style: Style,
+}
+
+impl AccordionState {
+    /// Get the titles
+    pub fn titles(&self) -> &Vec<String> {
+        &self.titles
+    }
+
+    /// Get the children
+    pub fn children(&self) -> &Vec<Box<dyn Widget>> {
+        &self.children
+    }
+
+    /// Get the expanded flags of the sections
+    pub fn expanded(&self) -> &Vec<bool> {
+        &self.expanded
+    }
+
+    /// Return true if the section at the given index is expanded
+    pub fn is_expanded(&self, index: u32) -> bool {
+        self.expanded.get(index as usize).cloned().unwrap_or(false)
+    }
+
+    /// Get the exclusive flag
+    pub fn exclusive(&self) -> bool {
+        self.exclusive
+    }
+
+    /// Get the layout options
+    pub fn layout(&self) -> &LayoutOptions {
+        &self.layout
+    }
+
+    /// Get the style
+    pub fn style(&self) -> &Style {
+        &self.style
+    }
+
+    /// Get the stretched flag
+    pub fn stretched(&self) -> bool {
+        self.layout.stretched()
+    }
+
+    /// Set the titles
+    pub fn set_titles(&mut self, titles: Vec<&str>) {
+        self.titles = titles
+            .iter()
+            .map(|t| t.to_string())
+            .collect::<Vec<String>>();
+    }
+
+    /// Set the children, collapsing all the sections
+    pub fn set_children(&mut self, children: Vec<Box<dyn Widget>>) {
+        self.expanded = vec![false; children.len()];
+        self.children = children;
+    }
+
+    /// Set the expanded flag of the section at the given index, collapsing
+    /// the other sections if the exclusive flag is true
+    pub fn set_expanded(&mut self, index: u32, expanded: bool) {
+        if index as usize >= self.expanded.len() {
+            return;
+        }
+        if expanded && self.exclusive {
+            self.collapse_all();
+        }
+        self.expanded[index as usize] = expanded;
+    }
+
+    /// Set the exclusive flag, keeping only the first expanded section if it
+    /// is true
+    pub fn set_exclusive(&mut self, exclusive: bool) {
+        self.exclusive = exclusive;
+        if exclusive {
+            if let Some(index) = self.expanded.iter().position(|&e| e) {
+                self.collapse_all();
+                self.expanded[index] = true;
+            }
+        }
+    }
+
+    /// Set the layout options
+    pub fn set_layout(&mut self, layout: LayoutOptions) {
+        self.layout = layout;
+    }
+
+    /// Set the style
+    pub fn set_style(&mut self, style: Style) {
+        self.style = style;
+    }
+
+    /// Set the stretched flag
+    pub fn set_stretched(&mut self, stretched: bool) {
+        self.layout.set_stretched(stretched);
+    }
+
+    /// Collapse all the sections
+    pub fn collapse_all(&mut self) {
+        for expanded in self.expanded.iter_mut() {
+            *expanded = false;
+        }
+    }
+
+    /// Add a section
+    fn add(&mut self, title: &str, child: Box<dyn Widget>) {
+        self.titles.push(title.to_string());
+        self.children.push(child);
+        self.expanded.push(false);
+    }
+}
+
+/// # The listener of an Accordion
+pub trait AccordionListener {
+    /// Function triggered on update event
+    fn on_update(&self, state: &mut AccordionState);
+
+    /// Function triggered when a section has been expanded or collapsed by
+    /// the user
+    fn on_change(&self, state: &AccordionState);
+}
+
+/// # A list of collapsible sections
+///
+/// A click on the title of a section expands or collapses it. If the
+/// exclusive flag is set, expanding a section collapses the others.
+///
+/// ## Fields
+///
+/// ```text
+/// name: String
+/// state: AccordionState
+/// listener: Option<Box<dyn AccordionListener>>
+/// ```
+///
+/// ## Default values
+///
+/// ```text
+/// name: name.to_string()
+/// state:
+///     titles: vec![]
+///     children: vec![]
+///     expanded: vec![]
+///     exclusive: false
+///     layout: LayoutOptions::new()
+///     style: Style::new()
+/// listener: None
+/// ```
+///
+/// ## Example
+///
+/// ```
+/// use std::cell::RefCell;
+/// use std::rc::Rc;
+///
+/// use neutrino::widgets::accordion::{
+///     Accordion, AccordionListener, AccordionState,
+/// };
+/// use neutrino::widgets::checkbox::CheckBox;
+/// use neutrino::widgets::label::Label;
+/// use neutrino::{App, Window};
+///
+///
+/// struct Settings {
+///     section: Option<u32>,
+/// }
+///
+///
+/// struct MyAccordionListener {
+///     settings: Rc<RefCell<Settings>>,
+/// }
+///
+/// impl AccordionListener for MyAccordionListener {
+///     fn on_update(&self, state: &mut AccordionState) {
+///         state.collapse_all();
+///         if let Some(section) = self.settings.borrow().section {
+///             state.set_expanded(section, true);
+///         }
+///     }
+///
+///     fn on_change(&self, state: &AccordionState) {
+///         self.settings.borrow_mut().section =
+///             state.expanded().iter().position(|&e| e).map(|i| i as u32);
+///     }
+/// }
+///
+///
+/// fn main() {
+///     let settings = Rc::new(RefCell::new(Settings { section: Some(0) }));
+///
+///     let mut my_label = Label::new("my_label");
+///     my_label.set_text("Version 1.0");
+///
+///     let mut my_checkbox = CheckBox::new("my_checkbox");
+///     my_checkbox.set_text("Send usage statistics");
+///
+///     let mut my_accordion = Accordion::new("my_accordion");
+///     my_accordion.add("About", Box::new(my_label));
+///     my_accordion.add("Privacy", Box::new(my_checkbox));
+///     my_accordion.set_exclusive();
+///     my_accordion.set_listener(Box::new(MyAccordionListener { settings }));
+/// }
+/// ```
+pub struct Accordion {
+    name: String,
+    state: AccordionState,
+    listener: Option<Box<dyn AccordionListener>>,
+}
+
+impl Accordion {
+    /// Create an Accordion
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            state: AccordionState {
+                titles: vec![],
+                children: vec![],
+                expanded: vec![],
+                exclusive: false,
+                layout: LayoutOptions::new(),
+                style: Style::new(),
+            },
+            listener: None,
+        }
+    }
+
+    /// Get the state
+    pub fn state(&self) -> &AccordionState {
+        &self.state
+    }
+
+    /// Get the state, to change it from outside of the listener
+    pub fn state_mut(&mut self) -> &mut AccordionState {
+        &mut self.state
+    }
+
+    /// Expand the section at the given index
+    pub fn set_expanded(&mut self, index: u32) {
+        self.state.set_expanded(index, true);
+    }
+
+    /// Set the exclusive flag to true
+    pub fn set_exclusive(&mut self) {
+        self.state.set_exclusive(true);
+    }
+
+    /// Set the layout options
+    pub fn set_layout(&mut self, layout: LayoutOptions) {
+        self.state.set_layout(layout);
+    }
+
+    /// Set the style
+    pub fn set_style(&mut self, style: Style) {
+        self.state.set_style(style);
+    }
+
+    /// Set the stretched flag to true
+    pub fn set_stretched(&mut self) {
+        self.state.set_stretched(true);
+    }
+
+    /// Set the listener
+    pub fn set_listener(&mut self, listener: Box<dyn AccordionListener>) {
+        self.listener = Some(listener);
+    }
+
+    /// Add a section
+    pub fn add(&mut self, title: &str, child: Box<dyn Widget>) {
+        self.state.add(title, child);
+    }
+}
+
+impl Widget for Accordion {
+    fn eval(&self) -> String {
+        let layout = self.state.layout();
+        let mut s = format!(
+            r#"<div id="{}" class="accordion {}" style="{}{}">"#,
+            self.name,
+            layout.class(),
+            layout.style(),
+            self.state.style().css(),
+        );
+        for (i, title) in self.state.titles().iter().enumerate() {
+            let expanded = self.state.is_expanded(i as u32);
+            s.push_str(&format!(
+                r#"<div class="accordion-section {}"><div class="accordion-title" onmousedown="{}"><span class="accordion-arrow"></span>{}</div>"#,
+                if expanded { "expanded" } else { "" },
+                Event::change_js(&self.name, &format!("'{}'", i)),
+                escape(title)
+            ));
+            if expanded {
+                if let Some(child) = self.state.children().get(i) {
+                    s.push_str(&format!(
+                        r#"<div class="accordion-content">{}</div>"#,
+                        widget::eval(child.as_ref())
+                    ));
+                }
+            }
+            s.push_str("</div>");
+        }
+        s.push_str("</div>");
+        s
+    }
+
+    fn name(&self) -> String {
+        self.name.to_string()
+    }
+
+    fn trigger(&mut self, event: &Event) {
+        match event {
+            Event::Update => self.on_update(),
+            Event::Change { source, value } => {
+                if source == &self.name {
+                    self.on_change(value);
+                    return;
+                }
+            }
+            _ => (),
+        }
+        for child in self.state.children.iter_mut() {
+            if widget::reaches(child.as_ref(), event) {
+                widget::trigger(child.as_mut(), event);
+            }
+        }
+    }
+
+    fn on_update(&mut self) {
+        match &self.listener {
+            None => (),
+            Some(listener) => {
+                listener.on_update(&mut self.state);
+            }
+        }
+    }
+
+    fn on_change(&mut self, value: &str) {
+        if let Ok(index) = value.parse::<u32>() {
+            let expanded = self.state.is_expanded(index);
+            self.state.set_expanded(index, !expanded);
+            match &self.listener {
+                None => (),
+                Some(listener) => {
+                    listener.on_change(&self.state);
+                }
+            }
+        }
+    }
+
+    fn values(&self) -> Vec<(String, String)> {
+        self.state
+            .children
+            .iter()
+            .flat_map(|widget| widget.values())
+            .collect()
+    }
+
+    fn is_valid(&self) -> bool {
+        self.state.children.iter().all(|widget| widget.is_valid())
+    }
+
+    fn validate(&mut self) -> bool {
+        let mut valid = true;
+        for widget in self.state.children.iter_mut() {
+            if !widget.validate() {
+                valid = false;
+            }
+        }
+        valid
+    }
+
+    fn children(&self) -> Vec<&(dyn Widget + 'static)> {
+        self.state
+            .children
+            .iter()
+            .map(|widget| widget.as_ref())
+            .collect()
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut (dyn Widget + 'static)> {
+        self.state
+            .children
+            .iter_mut()
+            .map(|widget| widget.as_mut() as &mut dyn Widget)
+            .collect()
+    }
+}
@@ -0,0 +1,533 @@
+use crate::utils::event::Event;
+use crate::utils::html::escape;
+use crate::utils::layoutoptions::LayoutOptions;
+use crate::utils::style::Style;
+use crate::widgets::widget::Widget;
+
+/// # The state of an AutoComplete
+///
+/// ## Fields
+///
+/// ```text
+/// query: String
+/// suggestions: Vec<String>
+/// highlighted: Option<u32>
+/// selected: Option<String>
+/// opened: bool
+/// loading: bool
+/// filtered: bool
+/// min_chars: u32
+/// max_suggestions: u32
+/// placeholder: String
+/// layout: LayoutOptions
+/// style: Style
+/// ```
+pub struct AutoCompleteState {
+    query: String,
+    suggestions: Vec<String>,
+    highlighted: Option<u32>,
+    selected: Option<String>,
+    opened: bool,
+    loading: bool,
+    filtered: bool,
+    min_chars: u32,
+    max_suggestions: u32,
+    placeholder: String,
+    layout: LayoutOptions,
+    style: Style,
+}
+
+impl AutoCompleteState {
+    /// Get the query
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    /// Get the suggestions
+    pub fn suggestions(&self) -> &Vec<String> {
+        &self.suggestions
+    }
+
+    /// Get the index of the highlighted suggestion among the displayed ones
+    pub fn highlighted(&self) -> Option<u32> {
+        self.highlighted
+    }
+
+    /// Get the selected suggestion
+    pub fn selected(&self) -> Option<&str> {
+        self.selected.as_ref().map(String::as_ref)
+    }
+
+    /// Get the opened flag
+    pub fn opened(&self) -> bool {
+        self.opened
+    }
+
+    /// Get the loading flag
+    pub fn loading(&self) -> bool {
+        self.loading
+    }
+
+    /// Get the filtered flag
+    pub fn filtered(&self) -> bool {
+        self.filtered
+    }
+
+    /// Get the minimum number of characters needed to display suggestions
+    pub fn min_chars(&self) -> u32 {
+        self.min_chars
+    }
+
+    /// Get the maximum number of displayed suggestions
+    pub fn max_suggestions(&self) -> u32 {
+        self.max_suggestions
+    }
+
+    /// Get the placeholder
+    pub fn placeholder(&self) -> &str {
+        &self.placeholder
+    }
+
+    /// Get the layout options
+    pub fn layout(&self) -> &LayoutOptions {
+        &self.layout
+    }
+
+    /// Get the style
+    pub fn style(&self) -> &Style {
+        &self.style
+    }
+
+    /// Get the stretched flag
+    pub fn stretched(&self) -> bool {
+        self.layout.stretched()
+    }
+
+    /// Return the displayed suggestions. If the filtered flag is set, only
+    /// the suggestions containing the query, ignoring case, are kept.
+    pub fn visible_suggestions(&self) -> Vec<&str> {
+        let query = self.query.to_lowercase();
+        self.suggestions
+            .iter()
+            .filter(|s| !self.filtered || s.to_lowercase().contains(&query))
+            .take(self.max_suggestions as usize)
+            .map(String::as_ref)
+            .collect()
+    }
+
+    /// Set the query
+    pub fn set_query(&mut self, query: &str) {
+        self.query = query.to_string();
+    }
+
+    /// Set the suggestions and reset the loading flag
+    pub fn set_suggestions(&mut self, suggestions: Vec<&str>) {
+        self.suggestions = suggestions.iter().map(|s| s.to_string()).collect();
+        self.loading = false;
+        self.highlighted = None;
+    }
+
+    /// Set the selected suggestion
+    pub fn set_selected(&mut self, selected: Option<&str>) {
+        self.selected = selected.map(str::to_string);
+    }
+
+    /// Set the opened flag
+    pub fn set_opened(&mut self, opened: bool) {
+        self.opened = opened;
+    }
+
+    /// Set the loading flag
+    pub fn set_loading(&mut self, loading: bool) {
+        self.loading = loading;
+    }
+
+    /// Set the filtered flag
+    pub fn set_filtered(&mut self, filtered: bool) {
+        self.filtered = filtered;
+    }
+
+    /// Set the minimum number of characters needed to display suggestions
+    pub fn set_min_chars(&mut self, min_chars: u32) {
+        self.min_chars = min_chars;
+    }
+
+    /// Set the maximum number of displayed suggestions
+    pub fn set_max_suggestions(&mut self, max_suggestions: u32) {
+        self.max_suggestions = max_suggestions;
+    }
+
+    /// Set the placeholder
+    pub fn set_placeholder(&mut self, placeholder: &str) {
+        self.placeholder = placeholder.to_string();
+    }
+
+    /// Set the layout options
+    pub fn set_layout(&mut self, layout: LayoutOptions) {
+        self.layout = layout;
+    }
+
+    /// Set the style
+    pub fn set_style(&mut self, style: Style) {
+        self.style = style;
+    }
+
+    /// Set the stretched flag
+    pub fn set_stretched(&mut self, stretched: bool) {
+        self.layout.set_stretched(stretched);
+    }
+
+    /// Move the highlight by the given offset among the displayed
+    /// suggestions
+    fn move_highlight(&mut self, offset: i32) {
+        let count = self.visible_suggestions().len() as i32;
+        if count == 0 {
+            self.highlighted = None;
+            return;
+        }
+        let next = match self.highlighted {
+            Some(highlighted) => highlighted as i32 + offset,
+            None if offset > 0 => 0,
+            None => count - 1,
+        };
+        self.highlighted = Some(next.max(0).min(count - 1) as u32);
+    }
+
+    /// Select a displayed suggestion, copying it into the query. Return
+    /// false if there is no such suggestion.
+    fn select(&mut self, index: u32) -> bool {
+        let suggestion = match self.visible_suggestions().get(index as usize) {
+            Some(suggestion) => suggestion.to_string(),
+            None => return false,
+        };
+        self.query = suggestion.clone();
+        self.selected = Some(suggestion);
+        self.opened = false;
+        self.highlighted = None;
+        true
+    }
+}
+
+/// # The listener of an AutoComplete
+pub trait AutoCompleteListener {
+    /// Function triggered on update event
+    fn on_update(&self, state: &mut AutoCompleteState);
+
+    /// Function triggered when the query is modified. The suggestions can be
+    /// given through the state on this or any later update.
+    fn on_query(&self, state: &AutoCompleteState);
+
+    /// Function triggered when a suggestion is selected
+    fn on_select(&self, state: &AutoCompleteState);
+}
+
+/// # A text input suggesting values while typing
+///
+/// Each modification of the query triggers the `on_query` function of the
+/// listener. The suggestions can then be loaded in the background and given
+/// to the state on a later update. The loading flag, displaying a spinner
+/// below the suggestions, is reset before each update, so a listener waiting
+/// for suggestions has to set it again until they arrive. With the filtered
+/// flag set, the suggestions are also filtered by the query, so a fixed list
+/// of hundreds of values can be given once.
+///
+/// The suggestions can be browsed with the Up and Down keys, selected with
+/// the Enter key or a click, and hidden with the Escape key.
+///
+/// ## Fields
+///
+/// ```text
+/// name: String
+/// state: AutoCompleteState
+/// listener: Option<Box<dyn AutoCompleteListener>>
+/// ```
+///
+/// ## Default values
+///
+/// ```text
+/// name: name.to_string()
+/// state:
+///     query: "".to_string()
+///     suggestions: vec![]
+///     highlighted: None
+///     selected: None
+///     opened: false
+///     loading: false
+///     filtered: false
+///     min_chars: 1
+///     max_suggestions: 10
+///     placeholder: "".to_string()
+///     layout: LayoutOptions::new()
+///     style: Style::new()
+/// listener: None
+/// ```
+///
+/// ## Example
+///
+/// ```
+/// use std::cell::RefCell;
+/// use std::rc::Rc;
+///
+/// use neutrino::widgets::autocomplete::{
+///     AutoComplete, AutoCompleteListener, AutoCompleteState,
+/// };
+/// use neutrino::{App, Window};
+///
+///
+/// struct City {
+///     name: String,
+/// }
+///
+///
+/// struct MyAutoCompleteListener {
+///     city: Rc<RefCell<City>>,
+/// }
+///
+/// impl AutoCompleteListener for MyAutoCompleteListener {
+///     fn on_update(&self, _state: &mut AutoCompleteState) {}
+///
+///     fn on_query(&self, _state: &AutoCompleteState) {}
+///
+///     fn on_select(&self, state: &AutoCompleteState) {
+///         self.city.borrow_mut().name = state.query().to_string();
+///     }
+/// }
+///
+///
+/// fn main() {
+///     let city = Rc::new(RefCell::new(City { name: "".to_string() }));
+///
+///     let mut my_autocomplete = AutoComplete::new("my_autocomplete");
+///     my_autocomplete.set_suggestions(vec!["Berlin", "Lisbon", "London"]);
+///     my_autocomplete.set_filtered();
+///     my_autocomplete.set_listener(Box::new(MyAutoCompleteListener { city }));
+/// }
+/// ```
+pub struct AutoComplete {
+    name: String,
+    state: AutoCompleteState,
+    listener: Option<Box<dyn AutoCompleteListener>>,
+}
+
+impl AutoComplete {
+    /// Create an AutoComplete
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            state: AutoCompleteState {
+                query: "".to_string(),
+                suggestions: vec![],
+                highlighted: None,
+                selected: None,
+                opened: false,
+                loading: false,
+                filtered: false,
+                min_chars: 1,
+                max_suggestions: 10,
+                placeholder: "".to_string(),
+                layout: LayoutOptions::new(),
+                style: Style::new(),
+            },
+            listener: None,
+        }
+    }
+
+    /// Get the state
+    pub fn state(&self) -> &AutoCompleteState {
+        &self.state
+    }
+
+    /// Get the state, to change it from outside of the listener
+    pub fn state_mut(&mut self) -> &mut AutoCompleteState {
+        &mut self.state
+    }
+
+    /// Set the query
+    pub fn set_query(&mut self, query: &str) {
+        self.state.set_query(query);
+    }
+
+    /// Set the suggestions
+    pub fn set_suggestions(&mut self, suggestions: Vec<&str>) {
+        self.state.set_suggestions(suggestions);
+    }
+
+    /// Set the filtered flag to true
+    pub fn set_filtered(&mut self) {
+        self.state.set_filtered(true);
+    }
+
+    /// Set the minimum number of characters needed to display suggestions
+    pub fn set_min_chars(&mut self, min_chars: u32) {
+        self.state.set_min_chars(min_chars);
+    }
+
+    /// Set the maximum number of displayed suggestions
+    pub fn set_max_suggestions(&mut self, max_suggestions: u32) {
+        self.state.set_max_suggestions(max_suggestions);
+    }
+
+    /// Set the placeholder
+    pub fn set_placeholder(&mut self, placeholder: &str) {
+        self.state.set_placeholder(placeholder);
+    }
+
+    /// Set the layout options
+    pub fn set_layout(&mut self, layout: LayoutOptions) {
+        self.state.set_layout(layout);
+    }
+
+    /// Set the style
+    pub fn set_style(&mut self, style: Style) {
+        self.state.set_style(style);
+    }
+
+    /// Set the stretched flag to true
+    pub fn set_stretched(&mut self) {
+        self.state.set_stretched(true);
+    }
+
+    /// Set the listener
+    pub fn set_listener(&mut self, listener: Box<dyn AutoCompleteListener>) {
+        self.listener = Some(listener);
+    }
+}
+
+impl Widget for AutoComplete {
+    fn eval(&self) -> String {
+        let layout = self.state.layout();
+        let mut s = format!(
+            r#"<div id="{}" class="autocomplete {}" style="{}{}"><input value="{}" placeholder="{}" autocomplete="off" oninput="{}" onkeydown="{}" />"#,
+            self.name,
+            layout.class(),
+            layout.style(),
+            self.state.style().css(),
+            escape(self.state.query()),
+            escape(self.state.placeholder()),
+            Event::input_js(&self.name, "'query:' + value"),
+            Event::autocomplete_key_js(&self.name),
+        );
+        if self.state.opened() {
+            s.push_str(&format!(
+                r#"<div class="autocomplete-suggestions" data-placement="bottom" data-overlay="{}">"#,
+                self.name
+            ));
+            for (i, suggestion) in
+                self.state.visible_suggestions().iter().enumerate()
+            {
+                let highlighted = if self.state.highlighted() == Some(i as u32)
+                {
+                    "highlighted"
+                } else {
+                    ""
+                };
+                s.push_str(&format!(
+                    r#"<div class="autocomplete-suggestion {}" onmousedown="{}">{}</div>"#,
+                    highlighted,
+                    Event::change_js(&self.name, &format!("'select:{}'", i)),
+                    escape(suggestion)
+                ));
+            }
+            if self.state.loading() {
+                s.push_str(
+                    r#"<div class="autocomplete-loading"><div class="spinner"></div></div>"#,
+                );
+            }
+            s.push_str("</div>");
+        }
+        s.push_str("</div>");
+        s
+    }
+
+    fn name(&self) -> String {
+        self.name.to_string()
+    }
+
+    fn trigger(&mut self, event: &Event) {
+        match event {
+            Event::Update => self.on_update(),
+            Event::Change { source, value } => {
+                if source == &self.name {
+                    self.on_change(value);
+                } else {
+                    self.state.set_opened(false);
+                }
+            }
+            Event::Focus { .. } | Event::Hover { .. } => (),
+            _ => self.state.set_opened(false),
+        }
+    }
+
+    fn on_update(&mut self) {
+        self.state.set_loading(false);
+        match &self.listener {
+            None => (),
+            Some(listener) => {
+                listener.on_update(&mut self.state);
+            }
+        }
+    }
+
+    fn on_change(&mut self, value: &str) {
+        if let Some(query) = value.strip_prefix("query:") {
+            self.state.set_query(query);
+            self.state.set_selected(None);
+            self.state.highlighted = None;
+            let long_enough =
+                query.chars().count() as u32 >= self.state.min_chars();
+            self.state.set_opened(long_enough);
+            if long_enough {
+                match &self.listener {
+                    None => (),
+                    Some(listener) => {
+                        listener.on_query(&self.state);
+                    }
+                }
+            }
+            return;
+        }
+        let selected = match value {
+            "key:down" => {
+                if self.state.opened() {
+                    self.state.move_highlight(1);
+                } else {
+                    self.state.set_opened(true);
+                }
+                false
+            }
+            "key:up" => {
+                self.state.move_highlight(-1);
+                false
+            }
+            "key:escape" => {
+                self.state.set_opened(false);
+                false
+            }
+            "key:enter" => match self.state.highlighted() {
+                Some(highlighted) if self.state.opened() => {
+                    self.state.select(highlighted)
+                }
+                _ => false,
+            },
+            _ => match value.strip_prefix("select:") {
+                Some(index) => match index.parse::<u32>() {
+                    Ok(index) => self.state.select(index),
+                    Err(_) => false,
+                },
+                None => false,
+            },
+        };
+        if selected {
+            match &self.listener {
+                None => (),
+                Some(listener) => {
+                    listener.on_select(&self.state);
+                }
+            }
+        }
+    }
+
+    fn values(&self) -> Vec<(String, String)> {
+        vec![(self.name.to_string(), self.state.query().to_string())]
+    }
+}
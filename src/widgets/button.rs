@@ -1,6 +1,10 @@
 use crate::utils::event::Event;
+use crate::utils::html::{aria_label, escape};
+use crate::utils::i18n;
 use crate::utils::icon::Icon;
+use crate::utils::layoutoptions::LayoutOptions;
 use crate::utils::pixmap::Pixmap;
+use crate::utils::style::Style;
 use crate::widgets::widget::Widget;
 
 /// # The state of a Button
@@ -9,15 +13,21 @@ use crate::widgets::widget::Widget;
 ///
 /// ```text
 /// text: String
+/// text_key: Option<String>
 /// disabled: bool
-/// stretched: bool
+/// layout: LayoutOptions
+/// style: Style
+/// aria_label: Option<String>
 /// ```
 pub struct ButtonState {
     text: Option<String>,
+    text_key: Option<String>,
     icon_data: Option<String>,
     icon_extension: Option<String>,
     disabled: bool,
-    stretched: bool,
+    layout: LayoutOptions,
+    style: Style,
+    aria_label: Option<String>,
 }
 
 impl ButtonState {
@@ -26,6 +36,11 @@ impl ButtonState {
         self.text.as_ref().map(String::as_ref)
     }
 
+    /// Get the key of the text in the catalogs
+    pub fn text_key(&self) -> Option<&str> {
+        self.text_key.as_deref()
+    }
+
     // Get the icon
     pub fn icon(&self) -> Option<Pixmap> {
         match (&self.icon_data, &self.icon_extension) {
@@ -39,9 +54,24 @@ impl ButtonState {
         self.disabled
     }
 
+    /// Get the layout options
+    pub fn layout(&self) -> &LayoutOptions {
+        &self.layout
+    }
+
+    /// Get the style
+    pub fn style(&self) -> &Style {
+        &self.style
+    }
+
+    /// Get the label read by screen readers
+    pub fn aria_label(&self) -> Option<&str> {
+        self.aria_label.as_deref()
+    }
+
     /// Get the stretched flag
     pub fn stretched(&self) -> bool {
-        self.stretched
+        self.layout.stretched()
     }
 
     /// Set the text
@@ -49,6 +79,12 @@ impl ButtonState {
         self.text = Some(text.to_string());
     }
 
+    /// Set the key of the text in the catalogs, the text being translated
+    /// in the locale of the application
+    pub fn set_text_key(&mut self, key: &str) {
+        self.text_key = Some(key.to_string());
+    }
+
     /// Set the icon
     pub fn set_icon(&mut self, icon: Box<dyn Icon>) {
         let pixmap = Pixmap::from_icon(icon);
@@ -61,9 +97,24 @@ impl ButtonState {
         self.disabled = disabled;
     }
 
-    /// Set the streched flag
+    /// Set the layout options
+    pub fn set_layout(&mut self, layout: LayoutOptions) {
+        self.layout = layout;
+    }
+
+    /// Set the style
+    pub fn set_style(&mut self, style: Style) {
+        self.style = style;
+    }
+
+    /// Set the label read by screen readers, in place of the visible text
+    pub fn set_aria_label(&mut self, label: &str) {
+        self.aria_label = Some(label.to_string());
+    }
+
+    /// Set the stretched flag
     pub fn set_stretched(&mut self, stretched: bool) {
-        self.stretched = stretched;
+        self.layout.set_stretched(stretched);
     }
 }
 
@@ -92,8 +143,11 @@ pub trait ButtonListener {
 /// name: name.to_string()
 /// state:
 ///     text: "Button".to_string()
+///     text_key: None
 ///     disabled: false
-///     stretched: false
+///     layout: LayoutOptions::new()
+///     style: Style::new()
+///     aria_label: None
 /// listener: None
 /// ```
 ///
@@ -171,20 +225,39 @@ impl Button {
             name: name.to_string(),
             state: ButtonState {
                 text: None,
+                text_key: None,
                 icon_data: None,
                 icon_extension: None,
                 disabled: false,
-                stretched: false,
+                layout: LayoutOptions::new(),
+                style: Style::new(),
+                aria_label: None,
             },
             listener: None,
         }
     }
 
+    /// Get the state
+    pub fn state(&self) -> &ButtonState {
+        &self.state
+    }
+
+    /// Get the state, to change it from outside of the listener
+    pub fn state_mut(&mut self) -> &mut ButtonState {
+        &mut self.state
+    }
+
     /// Set the text
     pub fn set_text(&mut self, text: &str) {
         self.state.set_text(text);
     }
 
+    /// Set the key of the text in the catalogs, the text being translated
+    /// in the locale of the application
+    pub fn set_text_key(&mut self, key: &str) {
+        self.state.set_text_key(key);
+    }
+
     /// Set the icon
     pub fn set_icon(&mut self, icon: Box<dyn Icon>) {
         self.state.set_icon(icon);
@@ -195,6 +268,21 @@ impl Button {
         self.state.set_disabled(true);
     }
 
+    /// Set the layout options
+    pub fn set_layout(&mut self, layout: LayoutOptions) {
+        self.state.set_layout(layout);
+    }
+
+    /// Set the style
+    pub fn set_style(&mut self, style: Style) {
+        self.state.set_style(style);
+    }
+
+    /// Set the label read by screen readers, in place of the visible text
+    pub fn set_aria_label(&mut self, label: &str) {
+        self.state.set_aria_label(label);
+    }
+
     /// Set the stretched flag to true
     pub fn set_stretched(&mut self) {
         self.state.set_stretched(true);
@@ -213,50 +301,76 @@ impl Widget for Button {
         } else {
             ""
         };
-        let stretched = if self.state.stretched() {
-            "stretched"
-        } else {
-            ""
+        let layout = self.state.layout();
+        let aria = format!(
+            r#"role="button" tabindex="{}" aria-disabled="{}" {} onkeydown="{}""#,
+            if self.state.disabled() { -1 } else { 0 },
+            self.state.disabled(),
+            aria_label(self.state.aria_label()),
+            Event::keys_js(
+                &self.name,
+                &[("Enter", "".to_string()), (" ", "".to_string())]
+            ),
+        );
+        let text = match self.state.text_key() {
+            Some(key) => Some(i18n::text(key)),
+            None => self.state.text().map(str::to_string),
         };
-        match (self.state.text(), self.state.icon()) {
+        match (text.as_deref(), self.state.icon()) {
             (Some(text), Some(icon)) => format!(
-                r#"<div id="{}" onmousedown="{}" class="button {} {}"><img src="data:image/{};base64,{}" /><span>{}</span></div>"#,
+                r#"<div id="{}" {} onmousedown="{}" class="button {} {}" style="{}{}"><img src="data:image/{};base64,{}" /><span>{}</span></div>"#,
                 self.name,
+                aria,
                 Event::change_js(&self.name, "''"),
                 disabled,
-                stretched,
+                layout.class(),
+                layout.style(),
+                self.state.style().css(),
                 icon.extension(),
                 icon.data(),
-                text,
+                escape(text),
             ),
             (Some(text), None) => format!(
-                r#"<div id="{}" onmousedown="{}" class="button {} {}">{}</div>"#,
+                r#"<div id="{}" {} onmousedown="{}" class="button {} {}" style="{}{}">{}</div>"#,
                 self.name,
+                aria,
                 Event::change_js(&self.name, "''"),
                 disabled,
-                stretched,
-                text,
+                layout.class(),
+                layout.style(),
+                self.state.style().css(),
+                escape(text),
             ),
             (None, Some(icon)) => format!(
-                r#"<div id="{}" onmousedown="{}" class="button {} {}"><img src="data:image/{};base64,{}" /></div>"#,
+                r#"<div id="{}" {} onmousedown="{}" class="button {} {}" style="{}{}"><img src="data:image/{};base64,{}" /></div>"#,
                 self.name,
+                aria,
                 Event::change_js(&self.name, "''"),
                 disabled,
-                stretched,
+                layout.class(),
+                layout.style(),
+                self.state.style().css(),
                 icon.extension(),
                 icon.data(),
             ),
             (None, None) => format!(
-                r#"<div id="{}" onmousedown="{}" class="button {} {}">{}</div>"#,
+                r#"<div id="{}" {} onmousedown="{}" class="button {} {}" style="{}{}">{}</div>"#,
                 self.name,
+                aria,
                 Event::change_js(&self.name, "''"),
                 disabled,
-                stretched,
+                layout.class(),
+                layout.style(),
+                self.state.style().css(),
                 "No text",
             ),
         }
     }
 
+    fn name(&self) -> String {
+        self.name.to_string()
+    }
+
     fn trigger(&mut self, event: &Event) {
         match event {
             Event::Update => self.on_update(),
@@ -0,0 +1,1026 @@
+use crate::utils::event::{Event, MouseButton};
+use crate::utils::html::escape;
+use crate::utils::layoutoptions::LayoutOptions;
+use crate::utils::palette::Palette;
+use crate::utils::pixmap::Pixmap;
+use crate::utils::style::Style;
+use crate::widgets::widget::Widget;
+
+use json::JsonValue;
+
+/// # A drawing command of a Canvas
+///
+/// Coordinates are expressed in pixels from the top left corner of the canvas.
+/// Colors are CSS colors (`"black"`, `"#ff0000"`, `"rgba(0,0,0,0.5)"`...).
+pub enum DrawCommand {
+    Line {
+        x1: f64,
+        y1: f64,
+        x2: f64,
+        y2: f64,
+        color: String,
+        width: f64,
+    },
+    Rect {
+        x: f64,
+        y: f64,
+        width: f64,
+        height: f64,
+        fill: Option<String>,
+        stroke: Option<String>,
+    },
+    Path {
+        points: Vec<(f64, f64)>,
+        closed: bool,
+        fill: Option<String>,
+        stroke: Option<String>,
+    },
+    Text {
+        x: f64,
+        y: f64,
+        text: String,
+        font: String,
+        color: String,
+    },
+    Image {
+        x: f64,
+        y: f64,
+        width: f64,
+        height: f64,
+        pixmap: Pixmap,
+    },
+}
+
+impl DrawCommand {
+    /// Return the bounding box of the command as (x, y, width, height). The
+    /// bounding box of a text is reduced to its anchor point.
+    pub fn bounds(&self) -> (f64, f64, f64, f64) {
+        let enclose = |points: &[(f64, f64)]| {
+            if points.is_empty() {
+                return (0.0, 0.0, 0.0, 0.0);
+            }
+            let (mut x1, mut y1) = points[0];
+            let (mut x2, mut y2) = points[0];
+            for (x, y) in points.iter() {
+                x1 = x1.min(*x);
+                y1 = y1.min(*y);
+                x2 = x2.max(*x);
+                y2 = y2.max(*y);
+            }
+            (x1, y1, x2 - x1, y2 - y1)
+        };
+        match self {
+            DrawCommand::Line { x1, y1, x2, y2, .. } => {
+                enclose(&[(*x1, *y1), (*x2, *y2)])
+            }
+            DrawCommand::Rect {
+                x,
+                y,
+                width,
+                height,
+                ..
+            }
+            | DrawCommand::Image {
+                x,
+                y,
+                width,
+                height,
+                ..
+            } => enclose(&[(*x, *y), (x + width, y + height)]),
+            DrawCommand::Path { points, .. } => enclose(points),
+            DrawCommand::Text { x, y, .. } => (*x, *y, 0.0, 0.0),
+        }
+    }
+
+    /// Return the JSON representation of the command, read by the javascript
+    /// side to paint the canvas
+    fn json(&self) -> JsonValue {
+        match self {
+            DrawCommand::Line {
+                x1,
+                y1,
+                x2,
+                y2,
+                color,
+                width,
+            } => json::object! {
+                "type" => "line",
+                "x1" => *x1,
+                "y1" => *y1,
+                "x2" => *x2,
+                "y2" => *y2,
+                "color" => color.as_str(),
+                "width" => *width,
+            },
+            DrawCommand::Rect {
+                x,
+                y,
+                width,
+                height,
+                fill,
+                stroke,
+            } => json::object! {
+                "type" => "rect",
+                "x" => *x,
+                "y" => *y,
+                "width" => *width,
+                "height" => *height,
+                "fill" => fill.clone(),
+                "stroke" => stroke.clone(),
+            },
+            DrawCommand::Path {
+                points,
+                closed,
+                fill,
+                stroke,
+            } => {
+                let mut pts = JsonValue::new_array();
+                for (x, y) in points.iter() {
+                    pts.push(json::array![*x, *y]).unwrap();
+                }
+                json::object! {
+                    "type" => "path",
+                    "points" => pts,
+                    "closed" => *closed,
+                    "fill" => fill.clone(),
+                    "stroke" => stroke.clone(),
+                }
+            }
+            DrawCommand::Text {
+                x,
+                y,
+                text,
+                font,
+                color,
+            } => json::object! {
+                "type" => "text",
+                "x" => *x,
+                "y" => *y,
+                "text" => text.as_str(),
+                "font" => font.as_str(),
+                "color" => color.as_str(),
+            },
+            DrawCommand::Image {
+                x,
+                y,
+                width,
+                height,
+                pixmap,
+            } => json::object! {
+                "type" => "image",
+                "x" => *x,
+                "y" => *y,
+                "width" => *width,
+                "height" => *height,
+                "src" => format!(
+                    "data:image/{};base64,{}",
+                    pixmap.extension(),
+                    pixmap.data()
+                ),
+            },
+        }
+    }
+}
+
+/// # The orientation of a Canvas guide
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GuideOrientation {
+    Horizontal,
+    Vertical,
+}
+
+/// # A guide line of a Canvas
+///
+/// The position of a horizontal guide is on the y axis, the position of a
+/// vertical guide is on the x axis.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Guide {
+    pub orientation: GuideOrientation,
+    pub position: f64,
+}
+
+impl Guide {
+    /// Create a horizontal guide at the given y coordinate
+    pub fn horizontal(position: f64) -> Self {
+        Self {
+            orientation: GuideOrientation::Horizontal,
+            position,
+        }
+    }
+
+    /// Create a vertical guide at the given x coordinate
+    pub fn vertical(position: f64) -> Self {
+        Self {
+            orientation: GuideOrientation::Vertical,
+            position,
+        }
+    }
+
+    /// Return the compact representation of the guide shared with the
+    /// javascript side (`h120`, `v40`...)
+    fn code(&self) -> String {
+        match self.orientation {
+            GuideOrientation::Horizontal => format!("h{}", self.position),
+            GuideOrientation::Vertical => format!("v{}", self.position),
+        }
+    }
+
+    /// Parse the compact representation of a guide
+    fn parse(code: &str) -> Option<Self> {
+        let (orientation, position) = code.split_at(
+            code.find(|c: char| c.is_ascii_digit() || c == '-' || c == '.')?,
+        );
+        match (orientation, position.parse::<f64>()) {
+            ("h", Ok(position)) => Some(Guide::horizontal(position)),
+            ("v", Ok(position)) => Some(Guide::vertical(position)),
+            _ => None,
+        }
+    }
+}
+
+/// # The state of a Canvas
+///
+/// ## Fields
+///
+/// ```text
+/// width: u32
+/// height: u32
+/// background: String
+/// commands: Vec<DrawCommand>
+/// hoverable: bool
+/// items: Vec<(String, usize)>
+/// rulers: bool
+/// guides: Vec<Guide>
+/// snap: Option<f64>
+/// selectable: bool
+/// region: Option<(f64, f64, f64, f64)>
+/// selection: Vec<String>
+/// palette: Palette
+/// layout: LayoutOptions
+/// style: Style
+/// ```
+pub struct CanvasState {
+    width: u32,
+    height: u32,
+    background: String,
+    commands: Vec<DrawCommand>,
+    items: Vec<(String, usize)>,
+    hoverable: bool,
+    rulers: bool,
+    guides: Vec<Guide>,
+    snap: Option<f64>,
+    selectable: bool,
+    region: Option<(f64, f64, f64, f64)>,
+    selection: Vec<String>,
+    palette: Palette,
+    layout: LayoutOptions,
+    style: Style,
+}
+
+impl CanvasState {
+    /// Get the width
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Get the height
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Get the background color
+    pub fn background(&self) -> &str {
+        &self.background
+    }
+
+    /// Get the drawing commands
+    pub fn commands(&self) -> &Vec<DrawCommand> {
+        &self.commands
+    }
+
+    /// Get the hoverable flag
+    pub fn hoverable(&self) -> bool {
+        self.hoverable
+    }
+
+    /// Get the rulers flag
+    pub fn rulers(&self) -> bool {
+        self.rulers
+    }
+
+    /// Get the guides
+    pub fn guides(&self) -> &Vec<Guide> {
+        &self.guides
+    }
+
+    /// Get the snap distance
+    pub fn snap_distance(&self) -> Option<f64> {
+        self.snap
+    }
+
+    /// Get the selectable flag
+    pub fn selectable(&self) -> bool {
+        self.selectable
+    }
+
+    /// Get the last region selected with the marquee, as
+    /// (x, y, width, height)
+    pub fn region(&self) -> Option<(f64, f64, f64, f64)> {
+        self.region
+    }
+
+    /// Get the ids of the items contained in the selected region
+    pub fn selection(&self) -> &Vec<String> {
+        &self.selection
+    }
+
+    /// Return the ids of the items entirely contained in a region
+    pub fn items_in(
+        &self,
+        x: f64,
+        y: f64,
+        width: f64,
+        height: f64,
+    ) -> Vec<&str> {
+        self.items
+            .iter()
+            .filter(|(_, index)| {
+                let (ix, iy, iw, ih) = self.commands[*index].bounds();
+                ix >= x
+                    && iy >= y
+                    && ix + iw <= x + width
+                    && iy + ih <= y + height
+            })
+            .map(|(id, _)| id.as_str())
+            .collect()
+    }
+
+    /// Get the palette of the charts drawn on the canvas
+    pub fn palette(&self) -> &Palette {
+        &self.palette
+    }
+
+    /// Get the layout options
+    pub fn layout(&self) -> &LayoutOptions {
+        &self.layout
+    }
+
+    /// Get the style
+    pub fn style(&self) -> &Style {
+        &self.style
+    }
+
+    /// Get the stretched flag
+    pub fn stretched(&self) -> bool {
+        self.layout.stretched()
+    }
+
+    /// Set the size (width and height)
+    pub fn set_size(&mut self, width: u32, height: u32) {
+        self.width = width;
+        self.height = height;
+    }
+
+    /// Set the background color
+    pub fn set_background(&mut self, background: &str) {
+        self.background = background.to_string();
+    }
+
+    /// Set the drawing commands
+    pub fn set_commands(&mut self, commands: Vec<DrawCommand>) {
+        self.commands = commands;
+        self.items.clear();
+    }
+
+    /// Set the hoverable flag
+    pub fn set_hoverable(&mut self, hoverable: bool) {
+        self.hoverable = hoverable;
+    }
+
+    /// Set the rulers flag
+    pub fn set_rulers(&mut self, rulers: bool) {
+        self.rulers = rulers;
+    }
+
+    /// Set the guides
+    pub fn set_guides(&mut self, guides: Vec<Guide>) {
+        self.guides = guides;
+    }
+
+    /// Add a guide
+    pub fn add_guide(&mut self, guide: Guide) {
+        self.guides.push(guide);
+    }
+
+    /// Set the snap distance. Pointer coordinates closer to a guide than
+    /// this distance are moved onto the guide.
+    pub fn set_snap_distance(&mut self, snap: Option<f64>) {
+        self.snap = snap;
+    }
+
+    /// Return the coordinates snapped to the closest guides, if a snap
+    /// distance is set
+    pub fn snap(&self, x: f64, y: f64) -> (f64, f64) {
+        let distance = match self.snap {
+            Some(distance) => distance,
+            None => return (x, y),
+        };
+        let closest = |value: f64, orientation: GuideOrientation| {
+            self.guides
+                .iter()
+                .filter(|guide| guide.orientation == orientation)
+                .map(|guide| guide.position)
+                .filter(|position| (position - value).abs() <= distance)
+                .min_by(|a, b| {
+                    (a - value).abs().partial_cmp(&(b - value).abs()).unwrap()
+                })
+                .unwrap_or(value)
+        };
+        (
+            closest(x, GuideOrientation::Vertical),
+            closest(y, GuideOrientation::Horizontal),
+        )
+    }
+
+    /// Set the selectable flag
+    pub fn set_selectable(&mut self, selectable: bool) {
+        self.selectable = selectable;
+    }
+
+    /// Set the selection
+    pub fn set_selection(&mut self, selection: Vec<&str>) {
+        self.selection = selection.iter().map(|s| s.to_string()).collect();
+    }
+
+    /// Set the selected region and select the items it contains
+    fn select(&mut self, x: f64, y: f64, width: f64, height: f64) {
+        let (x1, y1) = self.snap(x, y);
+        let (x2, y2) = self.snap(x + width, y + height);
+        self.region = Some((x1, y1, x2 - x1, y2 - y1));
+        self.selection = self
+            .items_in(x1, y1, x2 - x1, y2 - y1)
+            .iter()
+            .map(|id| id.to_string())
+            .collect();
+    }
+
+    /// Set the palette of the charts drawn on the canvas
+    pub fn set_palette(&mut self, palette: Palette) {
+        self.palette = palette;
+    }
+
+    /// Set the layout options
+    pub fn set_layout(&mut self, layout: LayoutOptions) {
+        self.layout = layout;
+    }
+
+    /// Set the style
+    pub fn set_style(&mut self, style: Style) {
+        self.style = style;
+    }
+
+    /// Set the stretched flag
+    pub fn set_stretched(&mut self, stretched: bool) {
+        self.layout.set_stretched(stretched);
+    }
+
+    /// Remove all the drawing commands
+    pub fn clear(&mut self) {
+        self.commands.clear();
+        self.items.clear();
+    }
+
+    /// Add a drawing command
+    pub fn draw(&mut self, command: DrawCommand) {
+        self.commands.push(command);
+    }
+
+    /// Add a drawing command identified by an id, reported in the selection
+    /// when its bounding box is contained in the marquee
+    pub fn draw_item(&mut self, id: &str, command: DrawCommand) {
+        self.items.push((id.to_string(), self.commands.len()));
+        self.commands.push(command);
+    }
+
+    /// Draw a line
+    pub fn line(&mut self, x1: f64, y1: f64, x2: f64, y2: f64, color: &str) {
+        self.draw(DrawCommand::Line {
+            x1,
+            y1,
+            x2,
+            y2,
+            color: color.to_string(),
+            width: 1.0,
+        });
+    }
+
+    /// Draw a filled rectangle
+    pub fn fill_rect(
+        &mut self,
+        x: f64,
+        y: f64,
+        width: f64,
+        height: f64,
+        color: &str,
+    ) {
+        self.draw(DrawCommand::Rect {
+            x,
+            y,
+            width,
+            height,
+            fill: Some(color.to_string()),
+            stroke: None,
+        });
+    }
+
+    /// Draw the outline of a rectangle
+    pub fn stroke_rect(
+        &mut self,
+        x: f64,
+        y: f64,
+        width: f64,
+        height: f64,
+        color: &str,
+    ) {
+        self.draw(DrawCommand::Rect {
+            x,
+            y,
+            width,
+            height,
+            fill: None,
+            stroke: Some(color.to_string()),
+        });
+    }
+
+    /// Draw a text
+    pub fn text(&mut self, x: f64, y: f64, text: &str, color: &str) {
+        self.draw(DrawCommand::Text {
+            x,
+            y,
+            text: text.to_string(),
+            font: "13px sans-serif".to_string(),
+            color: color.to_string(),
+        });
+    }
+
+    /// Return the JSON representation of the drawing commands
+    fn commands_json(&self) -> String {
+        let mut commands = JsonValue::new_array();
+        for command in self.commands.iter() {
+            commands.push(command.json()).unwrap();
+        }
+        commands.dump()
+    }
+}
+
+/// # The listener of a Canvas
+pub trait CanvasListener {
+    /// Function triggered on update event
+    fn on_update(&self, state: &mut CanvasState);
+
+    /// Function triggered on canvas click event, the coordinates being
+    /// relative to the top left corner of the canvas
+    fn on_click(&self, state: &CanvasState, x: f64, y: f64);
+
+    /// Function triggered when the pointer moves over a hoverable canvas
+    fn on_hover(&self, _state: &CanvasState, _x: f64, _y: f64) {}
+
+    /// Function triggered on canvas double click event
+    fn on_double_click(
+        &self,
+        _state: &CanvasState,
+        _button: MouseButton,
+        _x: f64,
+        _y: f64,
+    ) {
+    }
+
+    /// Function triggered when the guides are added, moved or removed by the
+    /// user
+    fn on_guides_change(&self, _state: &CanvasState) {}
+
+    /// Function triggered when a region is selected with the marquee of a
+    /// selectable canvas
+    fn on_select(&self, _state: &CanvasState) {}
+
+    /// Function triggered when a mouse button is released over the canvas
+    fn on_mouse_up(
+        &self,
+        _state: &CanvasState,
+        _button: MouseButton,
+        _x: f64,
+        _y: f64,
+    ) {
+    }
+}
+
+/// # A surface on which shapes, texts and images can be drawn
+///
+/// The drawing commands are sent to the javascript canvas at each update.
+///
+/// Hover events are only sent when the hoverable flag is set, as they are
+/// triggered each time the pointer moves.
+///
+/// Rulers can be displayed along the top and left sides. Dragging from a
+/// ruler creates a guide, which can then be moved or dropped back on the
+/// ruler to be removed. With a snap distance set, the coordinates given to the
+/// listener are snapped to the nearby guides.
+///
+/// On a selectable canvas, dragging the pointer draws a selection rectangle.
+/// When it is released, the region and the ids of the items it contains are
+/// stored in the state and the `on_select` function of the listener is
+/// triggered. Items are drawing commands added with `draw_item`.
+///
+/// The charts drawn on the canvas take the colours of their series from its
+/// palette, which is the colour-blind-safe `Palette::okabe_ito` by default
+/// (see `palette`).
+///
+/// ## Fields
+///
+/// ```text
+/// name: String
+/// state: CanvasState
+/// listener: Option<Box<dyn CanvasListener>>
+/// ```
+///
+/// ## Default values
+///
+/// ```text
+/// name: name.to_string()
+/// state:
+///     width: 300
+///     height: 150
+///     background: "white".to_string()
+///     commands: vec![]
+///     items: vec![]
+///     hoverable: false
+///     rulers: false
+///     guides: vec![]
+///     snap: None
+///     selectable: false
+///     region: None
+///     selection: vec![]
+///     palette: Palette::okabe_ito()
+///     layout: LayoutOptions::new()
+///     style: Style::new()
+/// listener: None
+/// ```
+///
+/// ## Example
+///
+/// ```
+/// use std::cell::RefCell;
+/// use std::rc::Rc;
+///
+/// use neutrino::widgets::canvas::{Canvas, CanvasListener, CanvasState};
+/// use neutrino::utils::theme::Theme;
+/// use neutrino::{App, Window};
+///
+///
+/// struct Points {
+///     values: Vec<(f64, f64)>,
+/// }
+///
+/// impl Points {
+///     fn new() -> Self {
+///         Self { values: vec![] }
+///     }
+///
+///     fn values(&self) -> &Vec<(f64, f64)> {
+///         &self.values
+///     }
+///
+///     fn add(&mut self, x: f64, y: f64) {
+///         self.values.push((x, y));
+///     }
+/// }
+///
+///
+/// struct MyCanvasListener {
+///     points: Rc<RefCell<Points>>,
+/// }
+///
+/// impl MyCanvasListener {
+///    pub fn new(points: Rc<RefCell<Points>>) -> Self {
+///        Self { points }
+///    }
+/// }
+///
+/// impl CanvasListener for MyCanvasListener {
+///     fn on_update(&self, state: &mut CanvasState) {
+///         state.clear();
+///         for (x, y) in self.points.borrow().values() {
+///             state.fill_rect(x - 2.0, y - 2.0, 4.0, 4.0, "black");
+///         }
+///     }
+///
+///     fn on_click(&self, _state: &CanvasState, x: f64, y: f64) {
+///         self.points.borrow_mut().add(x, y);
+///     }
+/// }
+///
+///
+/// fn main() {
+///     let points = Rc::new(RefCell::new(Points::new()));
+///
+///     let my_listener = MyCanvasListener::new(Rc::clone(&points));
+///
+///     let mut my_canvas = Canvas::new("my_canvas");
+///     my_canvas.set_size(400, 300);
+///     my_canvas.set_listener(Box::new(my_listener));
+/// }
+/// ```
+pub struct Canvas {
+    name: String,
+    state: CanvasState,
+    listener: Option<Box<dyn CanvasListener>>,
+}
+
+impl Canvas {
+    /// Create a Canvas
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            state: CanvasState {
+                width: 300,
+                height: 150,
+                background: "white".to_string(),
+                commands: vec![],
+                items: vec![],
+                hoverable: false,
+                rulers: false,
+                guides: vec![],
+                snap: None,
+                selectable: false,
+                region: None,
+                selection: vec![],
+                palette: Palette::okabe_ito(),
+                layout: LayoutOptions::new(),
+                style: Style::new(),
+            },
+            listener: None,
+        }
+    }
+
+    /// Get the state
+    pub fn state(&self) -> &CanvasState {
+        &self.state
+    }
+
+    /// Get the state, to change it from outside of the listener
+    pub fn state_mut(&mut self) -> &mut CanvasState {
+        &mut self.state
+    }
+
+    /// Set the size (width and height)
+    pub fn set_size(&mut self, width: u32, height: u32) {
+        self.state.set_size(width, height);
+    }
+
+    /// Set the background color
+    pub fn set_background(&mut self, background: &str) {
+        self.state.set_background(background);
+    }
+
+    /// Add a drawing command
+    pub fn draw(&mut self, command: DrawCommand) {
+        self.state.draw(command);
+    }
+
+    /// Add a drawing command identified by an id
+    pub fn draw_item(&mut self, id: &str, command: DrawCommand) {
+        self.state.draw_item(id, command);
+    }
+
+    /// Set the selectable flag to true
+    pub fn set_selectable(&mut self) {
+        self.state.set_selectable(true);
+    }
+
+    /// Set the hoverable flag to true
+    pub fn set_hoverable(&mut self) {
+        self.state.set_hoverable(true);
+    }
+
+    /// Set the rulers flag to true
+    pub fn set_rulers(&mut self) {
+        self.state.set_rulers(true);
+    }
+
+    /// Add a guide
+    pub fn add_guide(&mut self, guide: Guide) {
+        self.state.add_guide(guide);
+    }
+
+    /// Set the snap distance
+    pub fn set_snap_distance(&mut self, snap: f64) {
+        self.state.set_snap_distance(Some(snap));
+    }
+
+    /// Set the palette of the charts drawn on the canvas
+    pub fn set_palette(&mut self, palette: Palette) {
+        self.state.set_palette(palette);
+    }
+
+    /// Set the layout options
+    pub fn set_layout(&mut self, layout: LayoutOptions) {
+        self.state.set_layout(layout);
+    }
+
+    /// Set the style
+    pub fn set_style(&mut self, style: Style) {
+        self.state.set_style(style);
+    }
+
+    /// Set the stretched flag to true
+    pub fn set_stretched(&mut self) {
+        self.state.set_stretched(true);
+    }
+
+    /// Set the listener
+    pub fn set_listener(&mut self, listener: Box<dyn CanvasListener>) {
+        self.listener = Some(listener);
+    }
+}
+
+impl Widget for Canvas {
+    fn eval(&self) -> String {
+        let layout = self.state.layout();
+        let onmousemove = if self.state.hoverable() {
+            Event::hover_js(&self.name)
+        } else {
+            "".to_string()
+        };
+        let (ruled, rulers) = if self.state.rulers() {
+            let rulers = format!(
+                r#"<div class="canvas-corner"></div><canvas class="canvas-ruler horizontal" width="{}" height="20" data-ruler="horizontal" onmousedown="{}"></canvas><canvas class="canvas-ruler vertical" width="20" height="{}" data-ruler="vertical" onmousedown="{}"></canvas>"#,
+                self.state.width(),
+                Event::guide_drag_js("h", None),
+                self.state.height(),
+                Event::guide_drag_js("v", None),
+            );
+            ("rulers", rulers)
+        } else {
+            ("", "".to_string())
+        };
+        let mut guides = "".to_string();
+        for (i, guide) in self.state.guides().iter().enumerate() {
+            let (class, style, code) = match guide.orientation {
+                GuideOrientation::Horizontal => ("horizontal", "top", "h"),
+                GuideOrientation::Vertical => ("vertical", "left", "v"),
+            };
+            guides.push_str(&format!(
+                r#"<div class="canvas-guide {}" style="{}: {}px;" onmousedown="{}"></div>"#,
+                class,
+                style,
+                guide.position,
+                Event::guide_drag_js(code, Some(i)),
+            ));
+        }
+        let codes = self
+            .state
+            .guides()
+            .iter()
+            .map(Guide::code)
+            .collect::<Vec<String>>()
+            .join(",");
+        format!(
+            r#"<div id="{}" class="canvas {} {}" style="{}{}" data-guides="{}">{}<div class="canvas-area"><canvas width="{}" height="{}" style="background:{};" data-selectable="{}" onmousedown="{}" onmousemove="{}" ondblclick="{}" onmouseup="{}" data-commands="{}"></canvas>{}</div></div>"#,
+            self.name,
+            ruled,
+            layout.class(),
+            layout.style(),
+            self.state.style().css(),
+            codes,
+            rulers,
+            self.state.width(),
+            self.state.height(),
+            escape(self.state.background()),
+            self.state.selectable(),
+            Event::canvas_click_js(&self.name),
+            onmousemove,
+            Event::double_click_js(&self.name),
+            Event::mouse_up_js(&self.name),
+            escape(&self.state.commands_json()),
+            guides,
+        )
+    }
+
+    fn name(&self) -> String {
+        self.name.to_string()
+    }
+
+    fn trigger(&mut self, event: &Event) {
+        match event {
+            Event::Update => self.on_update(),
+            Event::Change { source, value } => {
+                if source == &self.name {
+                    self.on_change(value)
+                }
+            }
+            Event::CanvasClick { source, x, y } => {
+                if source == &self.name {
+                    let (x, y) = self.state.snap(*x, *y);
+                    self.on_click(x, y)
+                }
+            }
+            Event::Hover { source, x, y } => {
+                if source == &self.name {
+                    let (x, y) = self.state.snap(*x, *y);
+                    match &self.listener {
+                        None => (),
+                        Some(listener) => {
+                            listener.on_hover(&self.state, x, y);
+                        }
+                    }
+                }
+            }
+            Event::DoubleClick {
+                source,
+                button,
+                x,
+                y,
+            } => {
+                if source == &self.name {
+                    let (x, y) = self.state.snap(*x, *y);
+                    match &self.listener {
+                        None => (),
+                        Some(listener) => {
+                            listener.on_double_click(
+                                &self.state,
+                                *button,
+                                x,
+                                y,
+                            );
+                        }
+                    }
+                }
+            }
+            Event::MouseUp {
+                source,
+                button,
+                x,
+                y,
+            } => {
+                if source == &self.name {
+                    let (x, y) = self.state.snap(*x, *y);
+                    match &self.listener {
+                        None => (),
+                        Some(listener) => {
+                            listener.on_mouse_up(&self.state, *button, x, y);
+                        }
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+
+    fn on_update(&mut self) {
+        match &self.listener {
+            None => (),
+            Some(listener) => {
+                listener.on_update(&mut self.state);
+            }
+        }
+    }
+
+    fn on_change(&mut self, value: &str) {
+        if let Some(region) = value.strip_prefix("marquee:") {
+            let values = region
+                .split(';')
+                .map(|v| v.parse::<f64>())
+                .collect::<Vec<_>>();
+            if let [Ok(x), Ok(y), Ok(width), Ok(height)] = values.as_slice() {
+                self.state.select(*x, *y, *width, *height);
+                match &self.listener {
+                    None => (),
+                    Some(listener) => {
+                        listener.on_select(&self.state);
+                    }
+                }
+            }
+        } else if let Some(codes) = value.strip_prefix("guides:") {
+            let guides = codes.split(',').filter_map(Guide::parse).collect();
+            self.state.set_guides(guides);
+            match &self.listener {
+                None => (),
+                Some(listener) => {
+                    listener.on_guides_change(&self.state);
+                }
+            }
+        }
+    }
+}
+
+impl Canvas {
+    /// Function triggered on canvas click event
+    fn on_click(&mut self, x: f64, y: f64) {
+        match &self.listener {
+            None => (),
+            Some(listener) => {
+                listener.on_click(&self.state, x, y);
+            }
+        }
+    }
+}
@@ -1,4 +1,8 @@
 use crate::utils::event::Event;
+use crate::utils::html::{aria_label, escape};
+use crate::utils::i18n;
+use crate::utils::layoutoptions::LayoutOptions;
+use crate::utils::style::Style;
 use crate::widgets::widget::Widget;
 
 /// # The state of a CheckBox
@@ -7,13 +11,19 @@ use crate::widgets::widget::Widget;
 ///
 /// ```text
 /// text: String
+/// text_key: Option<String>
 /// checked: bool
-/// stretched: bool
+/// layout: LayoutOptions
+/// style: Style
+/// aria_label: Option<String>
 /// ```
 pub struct CheckBoxState {
     text: String,
+    text_key: Option<String>,
     checked: bool,
-    stretched: bool,
+    layout: LayoutOptions,
+    style: Style,
+    aria_label: Option<String>,
 }
 
 impl CheckBoxState {
@@ -22,14 +32,34 @@ impl CheckBoxState {
         &self.text
     }
 
+    /// Get the key of the text in the catalogs
+    pub fn text_key(&self) -> Option<&str> {
+        self.text_key.as_deref()
+    }
+
     /// Get the checked flag
     pub fn checked(&self) -> bool {
         self.checked
     }
 
+    /// Get the layout options
+    pub fn layout(&self) -> &LayoutOptions {
+        &self.layout
+    }
+
+    /// Get the style
+    pub fn style(&self) -> &Style {
+        &self.style
+    }
+
+    /// Get the label read by screen readers
+    pub fn aria_label(&self) -> Option<&str> {
+        self.aria_label.as_deref()
+    }
+
     /// Get the stretched flag
     pub fn stretched(&self) -> bool {
-        self.stretched
+        self.layout.stretched()
     }
 
     /// Set the text
@@ -37,14 +67,35 @@ impl CheckBoxState {
         self.text = text.to_string();
     }
 
+    /// Set the key of the text in the catalogs, the text being translated
+    /// in the locale of the application
+    pub fn set_text_key(&mut self, key: &str) {
+        self.text_key = Some(key.to_string());
+    }
+
     /// Set the checked flag
     pub fn set_checked(&mut self, checked: bool) {
         self.checked = checked;
     }
 
-    /// Set the streched flag
+    /// Set the layout options
+    pub fn set_layout(&mut self, layout: LayoutOptions) {
+        self.layout = layout;
+    }
+
+    /// Set the style
+    pub fn set_style(&mut self, style: Style) {
+        self.style = style;
+    }
+
+    /// Set the label read by screen readers, in place of the visible text
+    pub fn set_aria_label(&mut self, label: &str) {
+        self.aria_label = Some(label.to_string());
+    }
+
+    /// Set the stretched flag
     pub fn set_stretched(&mut self, stretched: bool) {
-        self.stretched = stretched;
+        self.layout.set_stretched(stretched);
     }
 }
 
@@ -73,8 +124,11 @@ pub trait CheckBoxListener {
 /// name: name.to_string()
 /// state:
 ///     text: "CheckBox".to_string()
+///     text_key: None
 ///     checked: false
-///     stretched: false
+///     layout: LayoutOptions::new()
+///     style: Style::new()
+///     aria_label: None
 /// listener: None
 /// ```
 ///
@@ -152,23 +206,57 @@ impl CheckBox {
             name: name.to_string(),
             state: CheckBoxState {
                 text: "CheckBox".to_string(),
+                text_key: None,
                 checked: false,
-                stretched: false,
+                layout: LayoutOptions::new(),
+                style: Style::new(),
+                aria_label: None,
             },
             listener: None,
         }
     }
 
+    /// Get the state
+    pub fn state(&self) -> &CheckBoxState {
+        &self.state
+    }
+
+    /// Get the state, to change it from outside of the listener
+    pub fn state_mut(&mut self) -> &mut CheckBoxState {
+        &mut self.state
+    }
+
     /// Set the text
     pub fn set_text(&mut self, text: &str) {
         self.state.set_text(text);
     }
 
+    /// Set the key of the text in the catalogs, the text being translated
+    /// in the locale of the application
+    pub fn set_text_key(&mut self, key: &str) {
+        self.state.set_text_key(key);
+    }
+
     /// Set the checked flag to true
     pub fn set_checked(&mut self) {
         self.state.set_checked(true);
     }
 
+    /// Set the layout options
+    pub fn set_layout(&mut self, layout: LayoutOptions) {
+        self.state.set_layout(layout);
+    }
+
+    /// Set the style
+    pub fn set_style(&mut self, style: Style) {
+        self.state.set_style(style);
+    }
+
+    /// Set the label read by screen readers, in place of the visible text
+    pub fn set_aria_label(&mut self, label: &str) {
+        self.state.set_aria_label(label);
+    }
+
     /// Set the stretched flag to true
     pub fn set_stretched(&mut self) {
         self.state.set_stretched(true);
@@ -183,22 +271,31 @@ impl CheckBox {
 impl Widget for CheckBox {
     fn eval(&self) -> String {
         let checked = if self.state.checked() { "checked" } else { "" };
-        let stretched = if self.state.stretched() {
-            "checked"
-        } else {
-            ""
+        let layout = self.state.layout();
+        let text = match self.state.text_key() {
+            Some(key) => i18n::text(key),
+            None => self.state.text().to_string(),
         };
         format!(
-            r#"<div id="{}" class="checkbox {}" onmousedown="{}"><div class="checkbox-outer {}"><div class="checkbox-inner {}"></div></div><label>{}</label></div>"#, 
+            r#"<div id="{}" class="checkbox {}" style="{}{}" role="checkbox" aria-checked="{}" tabindex="0" {} onmousedown="{}" onkeydown="{}"><div class="checkbox-outer {}"><div class="checkbox-inner {}"></div></div><label>{}</label></div>"#, 
             self.name,
-            stretched,
+            layout.class(),
+            layout.style(),
+            self.state.style().css(),
+            self.state.checked(),
+            aria_label(self.state.aria_label()),
             Event::change_js(&self.name, "''"), 
+            Event::keys_js(&self.name, &[(" ", "".to_string())]),
             checked,
             checked,
-            self.state.text,
+            escape(&text),
         )
     }
 
+    fn name(&self) -> String {
+        self.name.to_string()
+    }
+
     fn trigger(&mut self, event: &Event) {
         match event {
             Event::Update => self.on_update(),
@@ -229,4 +326,8 @@ impl Widget for CheckBox {
             }
         }
     }
+
+    fn values(&self) -> Vec<(String, String)> {
+        vec![(self.name.to_string(), self.state.checked().to_string())]
+    }
 }
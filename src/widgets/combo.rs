@@ -1,6 +1,10 @@
-use crate::utils::event::Event;
+use crate::utils::event::{self, Event};
+use crate::utils::html::{aria_label, escape};
 use crate::utils::icon::Icon;
+use crate::utils::layoutoptions::LayoutOptions;
 use crate::utils::pixmap::Pixmap;
+use crate::utils::style::Style;
+use crate::utils::validator::{self, Validator};
 use crate::widgets::widget::Widget;
 
 /// # The state of a Combo
@@ -9,19 +13,33 @@ use crate::widgets::widget::Widget;
 ///
 /// ```text
 /// choices: Vec<String>
+/// values: Vec<String>
+/// groups: Vec<Option<String>>
+/// disabled: Vec<bool>
 /// selected: u32
 /// opened: bool
-/// stretched: bool
+/// layout: LayoutOptions
+/// style: Style
+/// aria_label: Option<String>
 /// arrow_data: Option<String>
 /// arrow_extension: Option<String>
+/// validators: Vec<Validator>
+/// error: Option<String>
 /// ```
 pub struct ComboState {
     choices: Vec<String>,
+    values: Vec<String>,
+    groups: Vec<Option<String>>,
+    disabled: Vec<bool>,
     selected: u32,
     opened: bool,
-    stretched: bool,
+    layout: LayoutOptions,
+    style: Style,
+    aria_label: Option<String>,
     icon_data: Option<String>,
     icon_extension: Option<String>,
+    validators: Vec<Validator>,
+    error: Option<String>,
 }
 
 impl ComboState {
@@ -30,6 +48,25 @@ impl ComboState {
         &self.choices
     }
 
+    /// Get the values of the choices, such as stable keys which do not
+    /// change with the language of the texts
+    pub fn values(&self) -> &Vec<String> {
+        &self.values
+    }
+
+    /// Get the group of a choice
+    pub fn group(&self, index: u32) -> Option<&str> {
+        match self.groups.get(index as usize) {
+            Some(Some(group)) => Some(group),
+            _ => None,
+        }
+    }
+
+    /// Return true if a choice is disabled
+    pub fn is_disabled(&self, index: u32) -> bool {
+        self.disabled.get(index as usize).copied().unwrap_or(false)
+    }
+
     /// Get the selected flag
     pub fn selected(&self) -> u32 {
         self.selected
@@ -40,9 +77,24 @@ impl ComboState {
         self.opened
     }
 
+    /// Get the layout options
+    pub fn layout(&self) -> &LayoutOptions {
+        &self.layout
+    }
+
+    /// Get the style
+    pub fn style(&self) -> &Style {
+        &self.style
+    }
+
+    /// Get the label read by screen readers
+    pub fn aria_label(&self) -> Option<&str> {
+        self.aria_label.as_deref()
+    }
+
     /// Get the stretched flag
     pub fn stretched(&self) -> bool {
-        self.stretched
+        self.layout.stretched()
     }
 
     /// Get the icon
@@ -53,12 +105,72 @@ impl ComboState {
         }
     }
 
-    /// Set the choices
+    /// Get the validators
+    pub fn validators(&self) -> &Vec<Validator> {
+        &self.validators
+    }
+
+    /// Get the error message
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_ref().map(String::as_ref)
+    }
+
+    /// Get the text of the selected choice
+    pub fn selected_choice(&self) -> &str {
+        match self.choices.get(self.selected as usize) {
+            Some(choice) => choice,
+            None => "",
+        }
+    }
+
+    /// Get the value of the selected choice
+    pub fn selected_value(&self) -> &str {
+        match self.values.get(self.selected as usize) {
+            Some(value) => value,
+            None => "",
+        }
+    }
+
+    /// Set the choices, their values being their texts until they are set,
+    /// without group and enabled
     pub fn set_choices(&mut self, choices: Vec<&str>) {
         self.choices = choices
             .iter()
             .map(|c| c.to_string())
             .collect::<Vec<String>>();
+        self.values = self.choices.clone();
+        self.groups = vec![None; self.choices.len()];
+        self.disabled = vec![false; self.choices.len()];
+    }
+
+    /// Set the values of the choices, in the order of the choices
+    pub fn set_values(&mut self, values: Vec<&str>) {
+        for (slot, value) in self.values.iter_mut().zip(values) {
+            *slot = value.to_string();
+        }
+    }
+
+    /// Add a choice with its value and its text
+    pub fn add_choice(&mut self, value: &str, text: &str) {
+        self.choices.push(text.to_string());
+        self.values.push(value.to_string());
+        self.groups.push(None);
+        self.disabled.push(false);
+    }
+
+    /// Set the group of a choice. The consecutive choices of a group are
+    /// displayed under its label.
+    pub fn set_group(&mut self, index: u32, group: Option<&str>) {
+        if let Some(slot) = self.groups.get_mut(index as usize) {
+            *slot = group.map(str::to_string);
+        }
+    }
+
+    /// Set the disabled flag of a choice, which cannot be selected
+    pub fn set_disabled(&mut self, index: u32, disabled: bool) {
+        if let Some(slot) = self.disabled.get_mut(index as usize) {
+            *slot = disabled;
+        }
     }
 
     /// Set the selected flag
@@ -66,14 +178,41 @@ impl ComboState {
         self.selected = selected;
     }
 
+    /// Select the choice with the given value, returning false if there is
+    /// none
+    pub fn set_selected_value(&mut self, value: &str) -> bool {
+        match self.values.iter().position(|v| v == value) {
+            Some(index) => {
+                self.selected = index as u32;
+                true
+            }
+            None => false,
+        }
+    }
+
     /// Set the opened flag
     pub fn set_opened(&mut self, opened: bool) {
         self.opened = opened;
     }
 
+    /// Set the layout options
+    pub fn set_layout(&mut self, layout: LayoutOptions) {
+        self.layout = layout;
+    }
+
+    /// Set the style
+    pub fn set_style(&mut self, style: Style) {
+        self.style = style;
+    }
+
+    /// Set the label read by screen readers, in place of the visible text
+    pub fn set_aria_label(&mut self, label: &str) {
+        self.aria_label = Some(label.to_string());
+    }
+
     /// Set the stretched flag
     pub fn set_stretched(&mut self, stretched: bool) {
-        self.stretched = stretched;
+        self.layout.set_stretched(stretched);
     }
 
     /// Set the icon
@@ -82,6 +221,30 @@ impl ComboState {
         self.icon_data = Some(pixmap.data().to_string());
         self.icon_extension = Some(pixmap.extension().to_string());
     }
+
+    /// Set the error message
+    pub fn set_error(&mut self, error: Option<&str>) {
+        self.error = error.map(str::to_string);
+    }
+
+    /// Add a validator
+    pub fn add_validator(&mut self, validator: Validator) {
+        self.validators.push(validator);
+    }
+
+    /// Return true if the value of the selected choice satisfies all the
+    /// validators
+    pub fn is_valid(&self) -> bool {
+        validator::validate(&self.validators, self.selected_value()).is_none()
+    }
+
+    /// Check the value of the selected choice against the validators, set
+    /// the error message accordingly and return true if the value is valid
+    pub fn validate(&mut self) -> bool {
+        self.error =
+            validator::validate(&self.validators, self.selected_value());
+        self.error.is_none()
+    }
 }
 
 /// # The listener of a Combo
@@ -95,6 +258,13 @@ pub trait ComboListener {
 
 /// # A collapsible list of strings
 ///
+/// Each choice has a text, displayed, and a value, which is the text unless
+/// it is set. The value, such as a stable key, is what the listener should
+/// read with `selected_value` and what a form gets, so that the application
+/// does not depend on the texts, which change with the language. Consecutive
+/// choices can be grouped under a label, and a disabled choice is displayed
+/// but cannot be selected.
+///
 /// ## Fields
 ///
 /// ```text
@@ -109,11 +279,18 @@ pub trait ComboListener {
 /// name: name.to_string()
 /// state:
 ///     choices: vec!["Choice 1".to_string(), "Choice 2".to_string()],
+///     values: vec!["Choice 1".to_string(), "Choice 2".to_string()],
+///     groups: vec![None, None],
+///     disabled: vec![false, false],
 ///     selected: 0,
 ///     opened: false,
-///     stretched: false,
+///     layout: LayoutOptions::new(),
+///     style: Style::new(),
+///     aria_label: None
 ///     icon_data: None,
-///     icon_extension: None
+///     icon_extension: None,
+///     validators: vec![],
+///     error: None
 /// listener: None
 /// ```
 ///
@@ -129,26 +306,20 @@ pub trait ComboListener {
 ///
 ///
 /// struct Dessert {
-///     index: u32,
-///     value: String,
+///     key: String,
 /// }
 ///
 /// impl Dessert {
 ///     fn new() -> Self {
-///         Self { index: 0, value: "Cake".to_string() }
-///     }
-///
-///     fn index(&self) -> u32 {
-///         self.index
+///         Self { key: "cake".to_string() }
 ///     }
 ///
-///     fn value(&self) -> &str {
-///         &self.value
+///     fn key(&self) -> &str {
+///         &self.key
 ///     }
 ///
-///     fn set(&mut self, index: u32, value: &str) {
-///         self.index = index;
-///         self.value = value.to_string();
+///     fn set(&mut self, key: &str) {
+///         self.key = key.to_string();
 ///     }
 /// }
 ///
@@ -165,15 +336,11 @@ pub trait ComboListener {
 ///
 /// impl ComboListener for MyComboListener {
 ///     fn on_change(&self, state: &ComboState) {
-///         let index = state.selected();
-///         self.dessert.borrow_mut().set(
-///             index,
-///             &state.choices()[index as usize]
-///         );
+///         self.dessert.borrow_mut().set(state.selected_value());
 ///     }
 ///
 ///     fn on_update(&self, state: &mut ComboState) {
-///         state.set_selected(self.dessert.borrow().index());
+///         state.set_selected_value(self.dessert.borrow().key());
 ///     }
 /// }
 ///
@@ -184,7 +351,11 @@ pub trait ComboListener {
 ///     let my_listener = MyComboListener::new(Rc::clone(&dessert));
 ///
 ///     let mut my_combo = Combo::new("my_combo");
-///     my_combo.set_choices(vec!["Cake", "Ice Cream", "Pie"]);
+///     my_combo.set_choices(vec![]);
+///     my_combo.add_group("Baked", vec![("cake", "Cake"), ("pie", "Pie")]);
+///     my_combo.add_group("Frozen", vec![("ice", "Ice Cream")]);
+///     my_combo.add_choice("sorbet", "Sorbet");
+///     my_combo.set_disabled(3);
 ///     my_combo.set_listener(Box::new(my_listener));
 /// }
 /// ```
@@ -201,31 +372,92 @@ impl Combo {
             name: name.to_string(),
             state: ComboState {
                 choices: vec!["Choice 1".to_string(), "Choice 2".to_string()],
+                values: vec!["Choice 1".to_string(), "Choice 2".to_string()],
+                groups: vec![None, None],
+                disabled: vec![false, false],
                 selected: 0,
                 opened: false,
-                stretched: false,
+                layout: LayoutOptions::new(),
+                style: Style::new(),
+                aria_label: None,
                 icon_data: None,
                 icon_extension: None,
+                validators: vec![],
+                error: None,
             },
             listener: None,
         }
     }
 
+    /// Get the state
+    pub fn state(&self) -> &ComboState {
+        &self.state
+    }
+
+    /// Get the state, to change it from outside of the listener
+    pub fn state_mut(&mut self) -> &mut ComboState {
+        &mut self.state
+    }
+
     /// Set the choices
     pub fn set_choices(&mut self, choices: Vec<&str>) {
         self.state.set_choices(choices);
     }
 
+    /// Set the values of the choices, in the order of the choices
+    pub fn set_values(&mut self, values: Vec<&str>) {
+        self.state.set_values(values);
+    }
+
+    /// Add a choice with its value and its text
+    pub fn add_choice(&mut self, value: &str, text: &str) {
+        self.state.add_choice(value, text);
+    }
+
+    /// Add a group of choices, given as their values and their texts
+    pub fn add_group(&mut self, label: &str, choices: Vec<(&str, &str)>) {
+        for (value, text) in choices {
+            self.state.add_choice(value, text);
+            let index = self.state.choices().len() as u32 - 1;
+            self.state.set_group(index, Some(label));
+        }
+    }
+
+    /// Disable a choice, which cannot be selected
+    pub fn set_disabled(&mut self, index: u32) {
+        self.state.set_disabled(index, true);
+    }
+
     /// Set the index of the selected choice
     pub fn set_selected(&mut self, selected: u32) {
         self.state.set_selected(selected);
     }
 
+    /// Select the choice with the given value
+    pub fn set_selected_value(&mut self, value: &str) {
+        self.state.set_selected_value(value);
+    }
+
     /// Set the opened flag to true
     pub fn set_opened(&mut self) {
         self.state.set_opened(true);
     }
 
+    /// Set the layout options
+    pub fn set_layout(&mut self, layout: LayoutOptions) {
+        self.state.set_layout(layout);
+    }
+
+    /// Set the style
+    pub fn set_style(&mut self, style: Style) {
+        self.state.set_style(style);
+    }
+
+    /// Set the label read by screen readers, in place of the visible text
+    pub fn set_aria_label(&mut self, label: &str) {
+        self.state.set_aria_label(label);
+    }
+
     /// Set the stretched flag to true
     pub fn set_stretched(&mut self) {
         self.state.set_stretched(true);
@@ -236,6 +468,12 @@ impl Combo {
         self.state.set_icon(icon);
     }
 
+    /// Add a validator, checking the value of the selected choice each time
+    /// it changes
+    pub fn add_validator(&mut self, validator: Validator) {
+        self.state.add_validator(validator);
+    }
+
     /// Set the listener
     pub fn set_listener(&mut self, listener: Box<dyn ComboListener>) {
         self.listener = Some(listener);
@@ -244,57 +482,123 @@ impl Combo {
 
 impl Widget for Combo {
     fn eval(&self) -> String {
-        let stretched = if self.state.stretched() {
-            "stretched"
+        let layout = self.state.layout();
+        let opened = if self.state.opened() { "opened" } else { "" };
+        let invalid = if self.state.error().is_some() {
+            "invalid"
         } else {
             ""
         };
-        let opened = if self.state.opened() { "opened" } else { "" };
+        let choice =
+            match self.state.choices().get(self.state.selected() as usize) {
+                Some(choice) => escape(choice),
+                None => "".to_string(),
+            };
+        let mut keys = vec![
+            ("Enter", "-1".to_string()),
+            (" ", "-1".to_string()),
+            ("ArrowUp", "previous".to_string()),
+            ("ArrowDown", "next".to_string()),
+        ];
+        if self.state.opened() {
+            keys.push(("Escape", "-1".to_string()));
+        }
+        let aria = format!(
+            r#"role="combobox" aria-haspopup="listbox" aria-expanded="{}" tabindex="0" {} onkeydown="{}""#,
+            self.state.opened(),
+            aria_label(self.state.aria_label()),
+            Event::keys_js(&self.name, &keys),
+        );
         let mut s = match self.state.icon() {
             Some(icon) => {
                 format!(
-                    r#"<div id="{}" class="combo {}"><div onmousedown="{}" class="combo-button {}">{}<img src="data:image/{};base64,{}" /></div>"#,
+                    r#"<div id="{}" class="combo {} {}" style="{}{}"><div {} onmousedown="{}" class="combo-button {}">{}<img src="data:image/{};base64,{}" /></div>"#,
                     self.name,
-                    stretched,
+                    layout.class(),
+                    invalid,
+                    layout.style(),
+                    self.state.style().css(),
+                    aria,
                     Event::change_js(&self.name, "'-1'"),
                     opened,
-                    self.state.choices()[self.state.selected() as usize],
+                    choice,
                     icon.extension(),
                     icon.data(),
                 )
             },
             None => {
                 format!(
-                    r#"<div id="{}" class="combo {}"><div onmousedown="{}" class="combo-button">{}</div>"#,
+                    r#"<div id="{}" class="combo {} {}" style="{}{}"><div {} onmousedown="{}" class="combo-button">{}</div>"#,
                     self.name,
-                    stretched,
+                    layout.class(),
+                    invalid,
+                    layout.style(),
+                    self.state.style().css(),
+                    aria,
                     Event::change_js(&self.name, "'-1'"),
-                    self.state.choices()[self.state.selected() as usize],
+                    choice,
                 )
             }
         };
         if self.state.opened() {
-            s.push_str(r#"<div class="combo-choices">"#);
+            s.push_str(&format!(
+                r#"<div class="combo-choices" role="listbox" data-placement="bottom" data-overlay="{}">"#,
+                self.name
+            ));
             let combos_length = self.state.choices().len();
+            let mut group = None;
             for (i, choice) in self.state.choices().iter().enumerate() {
-                let last = if i == combos_length - 1 {
-                    "last"
-                } else {
-                    ""
-                };
+                let index = i as u32;
+                if self.state.group(index) != group {
+                    if group.is_some() {
+                        s.push_str("</div>");
+                    }
+                    group = self.state.group(index);
+                    if let Some(label) = group {
+                        s.push_str(&format!(
+                            r#"<div class="combo-group" role="group" aria-label="{}"><div class="combo-group-label" aria-hidden="true">{}</div>"#,
+                            escape(label),
+                            escape(label)
+                        ));
+                    }
+                }
+                let last = if i == combos_length - 1 { "last" } else { "" };
+                if self.state.is_disabled(index) {
+                    s.push_str(&format!(
+                        r#"<div class="combo-choice disabled {}" role="option" aria-selected="false" aria-disabled="true" onmousedown="{}">{}</div>"#,
+                        last,
+                        Event::stop_js(),
+                        escape(choice)
+                    ));
+                    continue;
+                }
                 s.push_str(&format!(
-                    r#"<div class="combo-choice {}" onmousedown="{}">{}</div>"#,
+                    r#"<div class="combo-choice {}" role="option" aria-selected="{}" onmousedown="{}">{}</div>"#,
                     last,
+                    self.state.selected() == index,
                     Event::change_js(&self.name, &format!("'{}'", i)),
-                    choice
+                    escape(choice)
                 ));
             }
+            if group.is_some() {
+                s.push_str("</div>");
+            }
             s.push_str(r#"</div>"#);
         }
+        if let Some(error) = self.state.error() {
+            s.push_str(&format!(
+                r#"<div class="combo-error">{}</div>"#,
+                escape(error)
+            ));
+        }
         s.push_str("</div>");
         s
     }
 
+    fn name(&self) -> String {
+        self.name.to_string()
+    }
+
     fn trigger(&mut self, event: &Event) {
         match event {
             Event::Update => self.on_update(),
@@ -305,6 +609,7 @@ impl Widget for Combo {
                     self.state.set_opened(false);
                 }
             }
+            Event::Focus { .. } | Event::Blur { .. } => (),
             _ => self.state.set_opened(false),
         }
     }
@@ -319,10 +624,57 @@ impl Widget for Combo {
     }
 
     fn on_change(&mut self, value: &str) {
+        let count = self.state.choices().len() as u32;
+        let selected = self.state.selected();
+        let step = match value {
+            "previous" => Some(
+                (0..selected.min(count))
+                    .rev()
+                    .find(|i| !self.state.is_disabled(*i))
+                    .unwrap_or(selected),
+            ),
+            "next" => Some(
+                (selected + 1..count)
+                    .find(|i| !self.state.is_disabled(*i))
+                    .unwrap_or(selected),
+            ),
+            _ => None,
+        };
+        if let Some(selected) = step {
+            self.state.set_selected(selected);
+            self.state.validate();
+            match &self.listener {
+                None => (),
+                Some(listener) => {
+                    listener.on_change(&self.state);
+                }
+            }
+            return;
+        }
+        let selected = match value.parse::<i32>() {
+            Ok(selected) => selected,
+            Err(_) => {
+                event::report_error(
+                    &self.name,
+                    &format!("invalid choice: {}", value),
+                );
+                return;
+            }
+        };
+        if selected < -1 || selected >= self.state.choices().len() as i32 {
+            event::report_error(
+                &self.name,
+                &format!("choice out of range: {}", value),
+            );
+            return;
+        }
+        if selected > -1 && self.state.is_disabled(selected as u32) {
+            return;
+        }
         self.state.set_opened(!self.state.opened());
-        let selected = value.parse::<i32>().unwrap();
         if selected > -1 {
             self.state.set_selected(selected as u32);
+            self.state.validate();
         }
         match &self.listener {
             None => (),
@@ -331,4 +683,19 @@ impl Widget for Combo {
             }
         }
     }
+
+    fn values(&self) -> Vec<(String, String)> {
+        vec![(
+            self.name.to_string(),
+            self.state.selected_value().to_string(),
+        )]
+    }
+
+    fn is_valid(&self) -> bool {
+        self.state.is_valid()
+    }
+
+    fn validate(&mut self) -> bool {
+        self.state.validate()
+    }
 }
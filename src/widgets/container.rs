@@ -1,5 +1,8 @@
 use crate::utils::event::Event;
-use crate::widgets::widget::Widget;
+use crate::utils::layoutoptions::LayoutOptions;
+use crate::utils::settings;
+use crate::utils::style::Style;
+use crate::widgets::widget::{self, Widget};
 
 /// # The state of a Container
 ///
@@ -7,16 +10,39 @@ use crate::widgets::widget::Widget;
 ///
 /// ```text
 /// children: Vec<Box<dyn Widget>>
+/// sticky: Vec<bool>
 /// direction: Direction
 /// position: Position
 /// alignment: Alignment
+/// layout: LayoutOptions
+/// style: Style
+/// scrollable: bool
+/// resizable: Option<Edge>
+/// size: Option<f64>
+/// min_size: f64
+/// max_size: f64
+/// wrap: bool
+/// breakpoints: Vec<(i32, Direction)>
+/// hide_below: Option<i32>
+/// window_width: Option<i32>
 /// ```
 pub struct ContainerState {
     children: Vec<Box<dyn Widget>>,
+    sticky: Vec<bool>,
     direction: Direction,
     position: Position,
     alignment: Alignment,
-    stretched: bool,
+    layout: LayoutOptions,
+    style: Style,
+    scrollable: bool,
+    resizable: Option<Edge>,
+    size: Option<f64>,
+    min_size: f64,
+    max_size: f64,
+    wrap: bool,
+    breakpoints: Vec<(i32, Direction)>,
+    hide_below: Option<i32>,
+    window_width: Option<i32>,
 }
 
 impl ContainerState {
@@ -25,6 +51,11 @@ impl ContainerState {
         &self.children
     }
 
+    /// Return true if the child at the given index is sticky
+    pub fn sticky(&self, index: usize) -> bool {
+        self.sticky.get(index).cloned().unwrap_or(false)
+    }
+
     /// Get the direction
     pub fn direction(&self) -> &Direction {
         &self.direction
@@ -40,16 +71,108 @@ impl ContainerState {
         &self.alignment
     }
 
+    /// Get the layout options
+    pub fn layout(&self) -> &LayoutOptions {
+        &self.layout
+    }
+
+    /// Get the style
+    pub fn style(&self) -> &Style {
+        &self.style
+    }
+
     /// Get the stretched flag
     pub fn stretched(&self) -> bool {
-        self.stretched
+        self.layout.stretched()
+    }
+
+    /// Get the scrollable flag
+    pub fn scrollable(&self) -> bool {
+        self.scrollable
+    }
+
+    /// Get the resizable edge
+    pub fn resizable(&self) -> Option<&Edge> {
+        self.resizable.as_ref()
+    }
+
+    /// Get the size in pixels, being the width if the resizable edge is on
+    /// the left or right side and the height otherwise
+    pub fn size(&self) -> Option<f64> {
+        self.size
+    }
+
+    /// Get the minimum size in pixels
+    pub fn min_size(&self) -> f64 {
+        self.min_size
+    }
+
+    /// Get the maximum size in pixels
+    pub fn max_size(&self) -> f64 {
+        self.max_size
+    }
+
+    /// Get the wrap flag
+    pub fn wrap(&self) -> bool {
+        self.wrap
+    }
+
+    /// Get the breakpoints, as (width, direction)
+    pub fn breakpoints(&self) -> &Vec<(i32, Direction)> {
+        &self.breakpoints
+    }
+
+    /// Get the window width in pixels under which the Container is hidden
+    pub fn hide_below(&self) -> Option<i32> {
+        self.hide_below
+    }
+
+    /// Get the width of the window in pixels, known once it has been
+    /// displayed
+    pub fn window_width(&self) -> Option<i32> {
+        self.window_width
+    }
+
+    /// Get the direction for the current window width: the direction of the
+    /// narrowest breakpoint wider than the window, or the direction of the
+    /// Container if there is none
+    pub fn current_direction(&self) -> &Direction {
+        let width = match self.window_width {
+            Some(width) => width,
+            None => return &self.direction,
+        };
+        match self
+            .breakpoints
+            .iter()
+            .filter(|(breakpoint, _)| width < *breakpoint)
+            .min_by_key(|(breakpoint, _)| *breakpoint)
+        {
+            Some((_, direction)) => direction,
+            None => &self.direction,
+        }
+    }
+
+    /// Return true if the window is narrower than the hide_below width
+    pub fn hidden(&self) -> bool {
+        match (self.window_width, self.hide_below) {
+            (Some(width), Some(hide_below)) => width < hide_below,
+            _ => false,
+        }
     }
 
     /// Set the children
     pub fn set_children(&mut self, children: Vec<Box<dyn Widget>>) {
+        self.sticky = vec![false; children.len()];
         self.children = children;
     }
 
+    /// Set the sticky flag of the child at the given index
+    pub fn set_sticky(&mut self, index: usize, sticky: bool) {
+        if let Some(flag) = self.sticky.get_mut(index) {
+            *flag = sticky;
+        }
+    }
+
     /// Set the direction
     pub fn set_direction(&mut self, direction: Direction) {
         self.direction = direction;
@@ -65,14 +188,68 @@ impl ContainerState {
         self.alignment = alignment;
     }
 
+    /// Set the layout options
+    pub fn set_layout(&mut self, layout: LayoutOptions) {
+        self.layout = layout;
+    }
+
+    /// Set the style
+    pub fn set_style(&mut self, style: Style) {
+        self.style = style;
+    }
+
     /// Set the stretched flag
     pub fn set_stretched(&mut self, stretched: bool) {
-        self.stretched = stretched;
+        self.layout.set_stretched(stretched);
+    }
+
+    /// Set the scrollable flag
+    pub fn set_scrollable(&mut self, scrollable: bool) {
+        self.scrollable = scrollable;
+    }
+
+    /// Set the resizable edge
+    pub fn set_resizable(&mut self, resizable: Option<Edge>) {
+        self.resizable = resizable;
+    }
+
+    /// Set the size in pixels, clamped between the minimum and maximum sizes
+    pub fn set_size(&mut self, size: Option<f64>) {
+        self.size = size.map(|s| s.max(self.min_size).min(self.max_size));
+    }
+
+    /// Set the minimum and maximum sizes in pixels
+    pub fn set_size_bounds(&mut self, min_size: f64, max_size: f64) {
+        self.min_size = min_size;
+        self.max_size = max_size;
+        self.set_size(self.size);
+    }
+
+    /// Set the wrap flag
+    pub fn set_wrap(&mut self, wrap: bool) {
+        self.wrap = wrap;
+    }
+
+    /// Set the breakpoints, as (width, direction)
+    pub fn set_breakpoints(&mut self, breakpoints: Vec<(i32, Direction)>) {
+        self.breakpoints = breakpoints;
+    }
+
+    /// Set the window width in pixels under which the Container is hidden
+    pub fn set_hide_below(&mut self, hide_below: Option<i32>) {
+        self.hide_below = hide_below;
     }
 
     /// Add a child
     fn add(&mut self, child: Box<dyn Widget>) {
         self.children.push(child);
+        self.sticky.push(false);
+    }
+
+    /// Add a sticky child
+    fn add_sticky(&mut self, child: Box<dyn Widget>) {
+        self.children.push(child);
+        self.sticky.push(true);
     }
 }
 
@@ -80,10 +257,30 @@ impl ContainerState {
 pub trait ContainerListener {
     /// Function triggered on update event
     fn on_update(&self, state: &mut ContainerState);
+
+    /// Function triggered when the Container has been resized by the user
+    fn on_resize(&self, _state: &ContainerState) {}
 }
 
 /// # A container for other widgets
 ///
+/// A scrollable Container scrolls its children along its direction when they
+/// overflow. Its sticky children, such as section headers, stay at the top
+/// (or on the left) while the children following them are scrolled, until
+/// the next sticky child pushes them away.
+///
+/// One edge of the Container can be made resizable. The user can then drag
+/// it to change the width (left and right edges) or the height (top and
+/// bottom edges) of the Container within the minimum and maximum sizes. The
+/// new size is reported to the `on_resize` function of the listener, and,
+/// when the application has settings, saved and restored at start-up (see
+/// the `settings` module of `utils`).
+///
+/// A Container can adapt to the width of the window: its children can wrap
+/// onto several lines, a breakpoint changes its direction when the window is
+/// narrower than its width (for instance a horizontal sidebar stacked over
+/// the content on small screens), and it can be hidden below a given width.
+///
 /// ## Fields
 ///
 /// ```text
@@ -98,9 +295,21 @@ pub trait ContainerListener {
 /// name: name.to_string()
 /// state:
 ///     children: vec![]
+///     sticky: vec![]
 ///     direction: Direction::Vertical
 ///     position: Position::Start
 ///     alignment: Alignment::None
+///     layout: LayoutOptions::new()
+///     style: Style::new()
+///     scrollable: false
+///     resizable: None
+///     size: None
+///     min_size: 0.0
+///     max_size: f64::INFINITY
+///     wrap: false
+///     breakpoints: vec![]
+///     hide_below: None
+///     window_width: None
 /// listener: None
 /// ```
 ///
@@ -177,15 +386,36 @@ impl Container {
             name: name.to_string(),
             state: ContainerState {
                 children: vec![],
+                sticky: vec![],
                 direction: Direction::Vertical,
                 position: Position::Start,
                 alignment: Alignment::None,
-                stretched: false,
+                layout: LayoutOptions::new(),
+                style: Style::new(),
+                scrollable: false,
+                resizable: None,
+                size: None,
+                min_size: 0.0,
+                max_size: f64::INFINITY,
+                wrap: false,
+                breakpoints: vec![],
+                hide_below: None,
+                window_width: None,
             },
             listener: None,
         }
     }
 
+    /// Get the state
+    pub fn state(&self) -> &ContainerState {
+        &self.state
+    }
+
+    /// Get the state, to change it from outside of the listener
+    pub fn state_mut(&mut self) -> &mut ContainerState {
+        &mut self.state
+    }
+
     /// Set the direction
     pub fn set_direction(&mut self, direction: Direction) {
         self.state.set_direction(direction);
@@ -201,40 +431,135 @@ impl Container {
         self.state.set_alignment(alignment);
     }
 
+    /// Set the layout options
+    pub fn set_layout(&mut self, layout: LayoutOptions) {
+        self.state.set_layout(layout);
+    }
+
+    /// Set the style
+    pub fn set_style(&mut self, style: Style) {
+        self.state.set_style(style);
+    }
+
     /// Set the stretched flag to true. Alignment needs to be set to
     /// Alignment::None (default) for the Container to stretch.
     pub fn set_stretched(&mut self) {
         self.state.set_stretched(true);
     }
 
+    /// Set the scrollable flag to true
+    pub fn set_scrollable(&mut self) {
+        self.state.set_scrollable(true);
+    }
+
+    /// Make an edge resizable by the user
+    pub fn set_resizable(&mut self, edge: Edge) {
+        self.state.set_resizable(Some(edge));
+    }
+
+    /// Set the size in pixels
+    pub fn set_size(&mut self, size: f64) {
+        self.state.set_size(Some(size));
+    }
+
+    /// Set the minimum and maximum sizes in pixels
+    pub fn set_size_bounds(&mut self, min_size: f64, max_size: f64) {
+        self.state.set_size_bounds(min_size, max_size);
+    }
+
+    /// Set the wrap flag to true: the children wrap onto several lines when
+    /// they overflow
+    pub fn set_wrap(&mut self) {
+        self.state.set_wrap(true);
+    }
+
+    /// Add a breakpoint: the Container takes the given direction when the
+    /// window is narrower than the given width in pixels
+    pub fn add_breakpoint(&mut self, width: i32, direction: Direction) {
+        self.state.breakpoints.push((width, direction));
+    }
+
+    /// Hide the Container when the window is narrower than the given width
+    /// in pixels
+    pub fn set_hide_below(&mut self, width: i32) {
+        self.state.set_hide_below(Some(width));
+    }
+
     /// Add a widget
     pub fn add(&mut self, widget: Box<dyn Widget>) {
         self.state.add(widget);
     }
+
+    /// Add a widget staying visible while the following ones are scrolled
+    pub fn add_sticky(&mut self, widget: Box<dyn Widget>) {
+        self.state.add_sticky(widget);
+    }
+
+    /// Set the listener
+    pub fn set_listener(&mut self, listener: Box<dyn ContainerListener>) {
+        self.listener = Some(listener);
+    }
 }
 
 impl Widget for Container {
     fn eval(&self) -> String {
-        let stretched = if self.state.stretched() {
-            "stretched"
+        let layout = self.state.layout();
+        let style = match (self.state.resizable(), self.state.size()) {
+            (Some(edge), Some(size)) => {
+                format!("{}: {}px; flex-shrink: 0;", edge.dimension(), size)
+            }
+            _ => "".to_string(),
+        };
+        let scrollable = if self.state.scrollable() {
+            "scrollable"
         } else {
             ""
         };
+        let wrap = if self.state.wrap() { "wrap" } else { "" };
+        let hidden = if self.state.hidden() { "hidden" } else { "" };
         let mut s = format!(
-            r#"<div id="{}" class="container {} {} {} {}">"#,
+            r#"<div id="{}" class="container {} {} {} {} {} {} {}" style="{}{}{}">"#,
             self.name,
             self.state.position().css(),
-            self.state.direction().css(),
+            self.state.current_direction().css(),
             self.state.alignment().css(),
-            stretched,
+            layout.class(),
+            scrollable,
+            wrap,
+            hidden,
+            layout.style(),
+            style,
+            self.state.style().css(),
         );
-        for widget in self.state.children.iter() {
-            s.push_str(&widget.eval());
+        for (i, widget) in self.state.children.iter().enumerate() {
+            if self.state.sticky(i) {
+                s.push_str(&format!(
+                    r#"<div class="container-sticky">{}</div>"#,
+                    widget::eval(widget.as_ref())
+                ));
+            } else {
+                s.push_str(&widget::eval(widget.as_ref()));
+            }
+        }
+        if let Some(edge) = self.state.resizable() {
+            s.push_str(&format!(
+                r#"<div class="container-handle {}" onmousedown="{}"></div>"#,
+                edge.css(),
+                Event::resize_js(
+                    edge.name(),
+                    self.state.min_size(),
+                    self.state.max_size()
+                ),
+            ));
         }
         s.push_str("</div>");
         s
     }
 
+    fn name(&self) -> String {
+        self.name.to_string()
+    }
+
     fn trigger(&mut self, event: &Event) {
         match event {
             Event::Update => self.on_update(),
@@ -243,10 +568,23 @@ impl Widget for Container {
                     self.on_change(value)
                 }
             }
+            Event::Resize { width, .. } => {
+                self.state.window_width = Some(*width);
+            }
+            Event::Restore => {
+                let key = format!("{}.size", self.name);
+                if let Some(size) = settings::get(&key) {
+                    if self.state.resizable().is_some() {
+                        self.state.set_size(size.as_f64());
+                    }
+                }
+            }
             _ => (),
         }
-        for widget in self.state.children.iter_mut() {
-            widget.trigger(event);
+        for child in self.state.children.iter_mut() {
+            if widget::reaches(child.as_ref(), event) {
+                widget::trigger(child.as_mut(), event);
+            }
         }
     }
 
@@ -259,7 +597,59 @@ impl Widget for Container {
         }
     }
 
-    fn on_change(&mut self, _value: &str) {}
+    fn on_change(&mut self, value: &str) {
+        if let Some(size) = value.strip_prefix("resize:") {
+            if let Ok(size) = size.parse::<f64>() {
+                self.state.set_size(Some(size));
+                let key = format!("{}.size", self.name);
+                settings::save(&key, size.into());
+                match &self.listener {
+                    None => (),
+                    Some(listener) => {
+                        listener.on_resize(&self.state);
+                    }
+                }
+            }
+        }
+    }
+
+    fn values(&self) -> Vec<(String, String)> {
+        self.state
+            .children
+            .iter()
+            .flat_map(|widget| widget.values())
+            .collect()
+    }
+
+    fn is_valid(&self) -> bool {
+        self.state.children.iter().all(|widget| widget.is_valid())
+    }
+
+    fn validate(&mut self) -> bool {
+        let mut valid = true;
+        for widget in self.state.children.iter_mut() {
+            if !widget.validate() {
+                valid = false;
+            }
+        }
+        valid
+    }
+
+    fn children(&self) -> Vec<&(dyn Widget + 'static)> {
+        self.state
+            .children
+            .iter()
+            .map(|widget| widget.as_ref())
+            .collect()
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut (dyn Widget + 'static)> {
+        self.state
+            .children
+            .iter_mut()
+            .map(|widget| widget.as_mut() as &mut dyn Widget)
+            .collect()
+    }
 }
 
 /// # The direction of a Container
@@ -270,7 +660,7 @@ pub enum Direction {
 
 impl Direction {
     // Return the CSS class corresponding to the direction
-    fn css(&self) -> &str {
+    pub(crate) fn css(&self) -> &str {
         match &self {
             Direction::Horizontal => "direction-horizontal",
             Direction::Vertical => "direction-vertical",
@@ -352,3 +742,41 @@ impl Alignment {
         }
     }
 }
+
+/// # An edge of a Container
+pub enum Edge {
+    Top,
+    Right,
+    Bottom,
+    Left,
+}
+
+impl Edge {
+    // Return the CSS class corresponding to the edge
+    fn css(&self) -> &str {
+        match &self {
+            Edge::Top => "edge-top",
+            Edge::Right => "edge-right",
+            Edge::Bottom => "edge-bottom",
+            Edge::Left => "edge-left",
+        }
+    }
+
+    // Return the name of the edge shared with the javascript side
+    fn name(&self) -> &str {
+        match &self {
+            Edge::Top => "top",
+            Edge::Right => "right",
+            Edge::Bottom => "bottom",
+            Edge::Left => "left",
+        }
+    }
+
+    // Return the CSS property changed when the edge is dragged
+    fn dimension(&self) -> &str {
+        match &self {
+            Edge::Top | Edge::Bottom => "height",
+            Edge::Right | Edge::Left => "width",
+        }
+    }
+}
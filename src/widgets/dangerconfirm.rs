@@ -0,0 +1,358 @@
+use crate::utils::event::Event;
+use crate::utils::html::escape;
+use crate::widgets::widget::Widget;
+
+/// # The state of a DangerConfirm
+///
+/// ## Fields
+///
+/// ```text
+/// title: String
+/// message: String
+/// phrase: String
+/// typed: String
+/// confirm: String
+/// cancel: String
+/// opened: bool
+/// ```
+pub struct DangerConfirmState {
+    title: String,
+    message: String,
+    phrase: String,
+    typed: String,
+    confirm: String,
+    cancel: String,
+    opened: bool,
+}
+
+impl DangerConfirmState {
+    /// Get the title
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    /// Get the message
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Get the phrase to type
+    pub fn phrase(&self) -> &str {
+        &self.phrase
+    }
+
+    /// Get the text typed by the user
+    pub fn typed(&self) -> &str {
+        &self.typed
+    }
+
+    /// Get the text of the confirm button
+    pub fn confirm(&self) -> &str {
+        &self.confirm
+    }
+
+    /// Get the text of the cancel button
+    pub fn cancel(&self) -> &str {
+        &self.cancel
+    }
+
+    /// Get the opened flag
+    pub fn opened(&self) -> bool {
+        self.opened
+    }
+
+    /// Return true if the typed text matches the phrase
+    pub fn matches(&self) -> bool {
+        self.typed == self.phrase
+    }
+
+    /// Set the title
+    pub fn set_title(&mut self, title: &str) {
+        self.title = title.to_string();
+    }
+
+    /// Set the message
+    pub fn set_message(&mut self, message: &str) {
+        self.message = message.to_string();
+    }
+
+    /// Set the phrase to type
+    pub fn set_phrase(&mut self, phrase: &str) {
+        self.phrase = phrase.to_string();
+    }
+
+    /// Set the text of the confirm button
+    pub fn set_confirm(&mut self, confirm: &str) {
+        self.confirm = confirm.to_string();
+    }
+
+    /// Set the text of the cancel button
+    pub fn set_cancel(&mut self, cancel: &str) {
+        self.cancel = cancel.to_string();
+    }
+
+    /// Set the opened flag, clearing the typed text when the dialog opens
+    pub fn set_opened(&mut self, opened: bool) {
+        if opened && !self.opened {
+            self.typed = "".to_string();
+        }
+        self.opened = opened;
+    }
+}
+
+/// # The listener of a DangerConfirm
+pub trait DangerConfirmListener {
+    /// Function triggered on update event
+    fn on_update(&self, state: &mut DangerConfirmState);
+
+    /// Function triggered when the user has confirmed the action
+    fn on_confirm(&self, state: &DangerConfirmState);
+
+    /// Function triggered when the user has cancelled the action
+    fn on_cancel(&self, _state: &DangerConfirmState) {}
+}
+
+/// # A dialog confirming a destructive action
+///
+/// The dialog asks the user to type a phrase, such as the name of the
+/// project to delete, and its confirm button stays disabled until the typed
+/// text matches the phrase exactly. The cancel button, a click outside of
+/// the dialog and the Escape key cancel the action.
+///
+/// Both outcomes close the dialog. The listener should keep its model in
+/// sync, so the dialog is not opened again by the next update.
+///
+/// ## Fields
+///
+/// ```text
+/// name: String
+/// state: DangerConfirmState
+/// listener: Option<Box<dyn DangerConfirmListener>>
+/// ```
+///
+/// ## Default values
+///
+/// ```text
+/// name: name.to_string()
+/// state:
+///     title: "".to_string()
+///     message: "".to_string()
+///     phrase: "".to_string()
+///     typed: "".to_string()
+///     confirm: "Delete".to_string()
+///     cancel: "Cancel".to_string()
+///     opened: false
+/// listener: None
+/// ```
+///
+/// ## Example
+///
+/// ```
+/// use std::cell::RefCell;
+/// use std::rc::Rc;
+///
+/// use neutrino::widgets::dangerconfirm::{
+///     DangerConfirm, DangerConfirmListener, DangerConfirmState,
+/// };
+/// use neutrino::{App, Window};
+///
+///
+/// struct Project {
+///     name: String,
+///     deleting: bool,
+///     deleted: bool,
+/// }
+///
+///
+/// struct MyDangerConfirmListener {
+///     project: Rc<RefCell<Project>>,
+/// }
+///
+/// impl DangerConfirmListener for MyDangerConfirmListener {
+///     fn on_update(&self, state: &mut DangerConfirmState) {
+///         let project = self.project.borrow();
+///         state.set_phrase(&project.name);
+///         state.set_opened(project.deleting);
+///     }
+///
+///     fn on_confirm(&self, _state: &DangerConfirmState) {
+///         let mut project = self.project.borrow_mut();
+///         project.deleting = false;
+///         project.deleted = true;
+///     }
+///
+///     fn on_cancel(&self, _state: &DangerConfirmState) {
+///         self.project.borrow_mut().deleting = false;
+///     }
+/// }
+///
+///
+/// fn main() {
+///     let project = Rc::new(RefCell::new(Project {
+///         name: "neutrino".to_string(),
+///         deleting: false,
+///         deleted: false,
+///     }));
+///
+///     let mut my_dialog = DangerConfirm::new("my_dialog");
+///     my_dialog.set_title("Delete this project?");
+///     my_dialog.set_message("This cannot be undone. Type the name of the project to confirm.");
+///     my_dialog.set_listener(Box::new(MyDangerConfirmListener { project }));
+/// }
+/// ```
+pub struct DangerConfirm {
+    name: String,
+    state: DangerConfirmState,
+    listener: Option<Box<dyn DangerConfirmListener>>,
+}
+
+impl DangerConfirm {
+    /// Create a DangerConfirm
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            state: DangerConfirmState {
+                title: "".to_string(),
+                message: "".to_string(),
+                phrase: "".to_string(),
+                typed: "".to_string(),
+                confirm: "Delete".to_string(),
+                cancel: "Cancel".to_string(),
+                opened: false,
+            },
+            listener: None,
+        }
+    }
+
+    /// Get the state
+    pub fn state(&self) -> &DangerConfirmState {
+        &self.state
+    }
+
+    /// Get the state, to change it from outside of the listener
+    pub fn state_mut(&mut self) -> &mut DangerConfirmState {
+        &mut self.state
+    }
+
+    /// Set the title
+    pub fn set_title(&mut self, title: &str) {
+        self.state.set_title(title);
+    }
+
+    /// Set the message
+    pub fn set_message(&mut self, message: &str) {
+        self.state.set_message(message);
+    }
+
+    /// Set the phrase to type
+    pub fn set_phrase(&mut self, phrase: &str) {
+        self.state.set_phrase(phrase);
+    }
+
+    /// Set the text of the confirm button
+    pub fn set_confirm(&mut self, confirm: &str) {
+        self.state.set_confirm(confirm);
+    }
+
+    /// Set the text of the cancel button
+    pub fn set_cancel(&mut self, cancel: &str) {
+        self.state.set_cancel(cancel);
+    }
+
+    /// Set the opened flag to true
+    pub fn set_opened(&mut self) {
+        self.state.set_opened(true);
+    }
+
+    /// Set the listener
+    pub fn set_listener(&mut self, listener: Box<dyn DangerConfirmListener>) {
+        self.listener = Some(listener);
+    }
+
+    fn on_cancel(&mut self) {
+        self.state.set_opened(false);
+        match &self.listener {
+            None => (),
+            Some(listener) => {
+                listener.on_cancel(&self.state);
+            }
+        }
+    }
+}
+
+impl Widget for DangerConfirm {
+    fn eval(&self) -> String {
+        if !self.state.opened() {
+            return format!(
+                r#"<div id="{}" class="dangerconfirm"></div>"#,
+                self.name
+            );
+        }
+        let disabled = if self.state.matches() { "" } else { "disabled" };
+        format!(
+            r#"<div id="{}" class="dangerconfirm opened" data-overlay="{}" data-modal="true"><div class="dangerconfirm-dialog"><div class="dangerconfirm-title">{}</div><div class="dangerconfirm-message">{}</div><div class="dangerconfirm-phrase">{}</div><input value="{}" autocomplete="off" spellcheck="false" oninput="{}" /><div class="dangerconfirm-buttons"><div class="dangerconfirm-cancel" onmousedown="{}">{}</div><div class="dangerconfirm-confirm {}" onmousedown="{}">{}</div></div></div></div>"#,
+            self.name,
+            self.name,
+            escape(self.state.title()),
+            escape(self.state.message()),
+            escape(self.state.phrase()),
+            escape(self.state.typed()),
+            Event::input_js(&self.name, "'text:' + value"),
+            Event::change_js(&self.name, "'cancel'"),
+            escape(self.state.cancel()),
+            disabled,
+            Event::change_js(&self.name, "'confirm'"),
+            escape(self.state.confirm()),
+        )
+    }
+
+    fn name(&self) -> String {
+        self.name.to_string()
+    }
+
+    fn trigger(&mut self, event: &Event) {
+        match event {
+            Event::Update => self.on_update(),
+            Event::Change { source, value } => {
+                if source == &self.name {
+                    self.on_change(value);
+                }
+            }
+            Event::Dismiss { source } => {
+                if source == &self.name {
+                    self.on_cancel();
+                }
+            }
+            _ => (),
+        }
+    }
+
+    fn on_update(&mut self) {
+        match &self.listener {
+            None => (),
+            Some(listener) => {
+                listener.on_update(&mut self.state);
+            }
+        }
+    }
+
+    fn on_change(&mut self, value: &str) {
+        if let Some(text) = value.strip_prefix("text:") {
+            self.state.typed = text.to_string();
+            return;
+        }
+        match value {
+            "cancel" => self.on_cancel(),
+            "confirm" if self.state.matches() => {
+                self.state.set_opened(false);
+                match &self.listener {
+                    None => (),
+                    Some(listener) => {
+                        listener.on_confirm(&self.state);
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+}
@@ -0,0 +1,520 @@
+use crate::utils::datetime::Date;
+use crate::utils::event::Event;
+use crate::utils::html::escape;
+use crate::utils::i18n;
+use crate::utils::layoutoptions::LayoutOptions;
+use crate::utils::style::Style;
+use crate::widgets::widget::Widget;
+
+/// # The state of a DatePicker
+///
+/// ## Fields
+///
+/// ```text
+/// date: Option<Date>
+/// min: Option<Date>
+/// max: Option<Date>
+/// format: String
+/// localized: bool
+/// month: Date
+/// opened: bool
+/// placeholder: String
+/// layout: LayoutOptions
+/// style: Style
+/// ```
+pub struct DatePickerState {
+    date: Option<Date>,
+    min: Option<Date>,
+    max: Option<Date>,
+    format: String,
+    localized: bool,
+    month: Date,
+    opened: bool,
+    placeholder: String,
+    layout: LayoutOptions,
+    style: Style,
+}
+
+impl DatePickerState {
+    /// Get the date
+    pub fn date(&self) -> Option<Date> {
+        self.date
+    }
+
+    /// Get the min date
+    pub fn min(&self) -> Option<Date> {
+        self.min
+    }
+
+    /// Get the max date
+    pub fn max(&self) -> Option<Date> {
+        self.max
+    }
+
+    /// Get the format, the one of the locale of the application if the
+    /// localized flag is set
+    pub fn format(&self) -> &str {
+        if self.localized {
+            i18n::date_format(&i18n::locale())
+        } else {
+            &self.format
+        }
+    }
+
+    /// Get the localized flag
+    pub fn localized(&self) -> bool {
+        self.localized
+    }
+
+    /// Get the first day of the month displayed by the calendar
+    pub fn month(&self) -> Date {
+        self.month
+    }
+
+    /// Get the opened flag
+    pub fn opened(&self) -> bool {
+        self.opened
+    }
+
+    /// Get the placeholder
+    pub fn placeholder(&self) -> &str {
+        &self.placeholder
+    }
+
+    /// Get the layout options
+    pub fn layout(&self) -> &LayoutOptions {
+        &self.layout
+    }
+
+    /// Get the style
+    pub fn style(&self) -> &Style {
+        &self.style
+    }
+
+    /// Get the stretched flag
+    pub fn stretched(&self) -> bool {
+        self.layout.stretched()
+    }
+
+    /// Return the date as an ISO 8601 string, or an empty string if no date
+    /// is set
+    pub fn value(&self) -> String {
+        match self.date {
+            Some(date) => date.iso(),
+            None => "".to_string(),
+        }
+    }
+
+    /// Return the date formatted with the format
+    pub fn text(&self) -> String {
+        match self.date {
+            Some(date) => date.format(self.format()),
+            None => "".to_string(),
+        }
+    }
+
+    /// Return true if the date is between the min and the max dates
+    pub fn allowed(&self, date: Date) -> bool {
+        self.min.iter().all(|min| date >= *min)
+            && self.max.iter().all(|max| date <= *max)
+    }
+
+    /// Set the date, clamped between the min and the max dates, and display
+    /// its month
+    pub fn set_date(&mut self, date: Option<Date>) {
+        self.date = date.map(|date| self.clamp(date));
+        if let Some(date) = self.date {
+            self.show_month(date);
+        }
+    }
+
+    /// Set the min date
+    pub fn set_min(&mut self, min: Option<Date>) {
+        self.min = min;
+        self.set_date(self.date);
+    }
+
+    /// Set the max date
+    pub fn set_max(&mut self, max: Option<Date>) {
+        self.max = max;
+        self.set_date(self.date);
+    }
+
+    /// Set the format
+    pub fn set_format(&mut self, format: &str) {
+        self.format = format.to_string();
+    }
+
+    /// Set the localized flag: the date is written with the format of the
+    /// locale of the application
+    pub fn set_localized(&mut self, localized: bool) {
+        self.localized = localized;
+    }
+
+    /// Set the month displayed by the calendar
+    pub fn show_month(&mut self, date: Date) {
+        self.month = date.add_days(1 - date.day() as i64);
+    }
+
+    /// Set the opened flag
+    pub fn set_opened(&mut self, opened: bool) {
+        self.opened = opened;
+    }
+
+    /// Set the placeholder
+    pub fn set_placeholder(&mut self, placeholder: &str) {
+        self.placeholder = placeholder.to_string();
+    }
+
+    /// Set the layout options
+    pub fn set_layout(&mut self, layout: LayoutOptions) {
+        self.layout = layout;
+    }
+
+    /// Set the style
+    pub fn set_style(&mut self, style: Style) {
+        self.style = style;
+    }
+
+    /// Set the stretched flag
+    pub fn set_stretched(&mut self, stretched: bool) {
+        self.layout.set_stretched(stretched);
+    }
+
+    fn clamp(&self, date: Date) -> Date {
+        let date = self.min.map_or(date, |min| date.max(min));
+        self.max.map_or(date, |max| date.min(max))
+    }
+}
+
+/// # The listener of a DatePicker
+pub trait DatePickerListener {
+    /// Function triggered on update event
+    fn on_update(&self, state: &mut DatePickerState);
+
+    /// Function triggered on change event
+    fn on_change(&self, state: &DatePickerState);
+}
+
+/// # A date input with a calendar popup
+///
+/// The date can be typed with the format, or picked in the calendar opened
+/// by the button. It is always kept between the min and the max dates, and
+/// the days out of this range cannot be picked. Typed values which do not
+/// match the format (nor ISO 8601) are ignored.
+///
+/// The value of the DatePicker is the date as an ISO 8601 string
+/// (YYYY-MM-DD), or an empty string if no date is set.
+///
+/// ## Fields
+///
+/// ```text
+/// name: String
+/// state: DatePickerState
+/// listener: Option<Box<dyn DatePickerListener>>
+/// ```
+///
+/// ## Default values
+///
+/// ```text
+/// name: name.to_string()
+/// state:
+///     date: None
+///     min: None
+///     max: None
+///     format: "%Y-%m-%d".to_string()
+///     localized: false
+///     month: the current month
+///     opened: false
+///     placeholder: "".to_string()
+///     layout: LayoutOptions::new()
+///     style: Style::new()
+/// listener: None
+/// ```
+///
+/// ## Example
+///
+/// ```
+/// use std::cell::RefCell;
+/// use std::rc::Rc;
+///
+/// use neutrino::utils::datetime::Date;
+/// use neutrino::widgets::datepicker::{
+///     DatePicker, DatePickerListener, DatePickerState,
+/// };
+/// use neutrino::{App, Window};
+///
+///
+/// struct Booking {
+///     arrival: Option<Date>,
+/// }
+///
+///
+/// struct MyDatePickerListener {
+///     booking: Rc<RefCell<Booking>>,
+/// }
+///
+/// impl DatePickerListener for MyDatePickerListener {
+///     fn on_update(&self, state: &mut DatePickerState) {
+///         state.set_date(self.booking.borrow().arrival);
+///     }
+///
+///     fn on_change(&self, state: &DatePickerState) {
+///         self.booking.borrow_mut().arrival = state.date();
+///     }
+/// }
+///
+///
+/// fn main() {
+///     let booking = Rc::new(RefCell::new(Booking { arrival: None }));
+///
+///     let mut my_datepicker = DatePicker::new("my_datepicker");
+///     my_datepicker.set_format("%d/%m/%Y");
+///     my_datepicker.set_min(Date::today());
+///     my_datepicker.set_placeholder("dd/mm/yyyy");
+///     my_datepicker.set_listener(Box::new(MyDatePickerListener { booking }));
+/// }
+/// ```
+pub struct DatePicker {
+    name: String,
+    state: DatePickerState,
+    listener: Option<Box<dyn DatePickerListener>>,
+}
+
+impl DatePicker {
+    /// Create a DatePicker
+    pub fn new(name: &str) -> Self {
+        let mut state = DatePickerState {
+            date: None,
+            min: None,
+            max: None,
+            format: "%Y-%m-%d".to_string(),
+            localized: false,
+            month: Date::today(),
+            opened: false,
+            placeholder: "".to_string(),
+            layout: LayoutOptions::new(),
+            style: Style::new(),
+        };
+        state.show_month(Date::today());
+        Self {
+            name: name.to_string(),
+            state,
+            listener: None,
+        }
+    }
+
+    /// Get the state
+    pub fn state(&self) -> &DatePickerState {
+        &self.state
+    }
+
+    /// Get the state, to change it from outside of the listener
+    pub fn state_mut(&mut self) -> &mut DatePickerState {
+        &mut self.state
+    }
+
+    /// Set the date
+    pub fn set_date(&mut self, date: Date) {
+        self.state.set_date(Some(date));
+    }
+
+    /// Set the min date
+    pub fn set_min(&mut self, min: Date) {
+        self.state.set_min(Some(min));
+    }
+
+    /// Set the max date
+    pub fn set_max(&mut self, max: Date) {
+        self.state.set_max(Some(max));
+    }
+
+    /// Set the format
+    pub fn set_format(&mut self, format: &str) {
+        self.state.set_format(format);
+    }
+
+    /// Set the localized flag to true: the date is written with the format
+    /// of the locale of the application, such as `03/09/2024` in American
+    /// English and `09.03.2024` in German
+    pub fn set_localized(&mut self) {
+        self.state.set_localized(true);
+    }
+
+    /// Set the placeholder
+    pub fn set_placeholder(&mut self, placeholder: &str) {
+        self.state.set_placeholder(placeholder);
+    }
+
+    /// Set the layout options
+    pub fn set_layout(&mut self, layout: LayoutOptions) {
+        self.state.set_layout(layout);
+    }
+
+    /// Set the style
+    pub fn set_style(&mut self, style: Style) {
+        self.state.set_style(style);
+    }
+
+    /// Set the stretched flag to true
+    pub fn set_stretched(&mut self) {
+        self.state.set_stretched(true);
+    }
+
+    /// Set the listener
+    pub fn set_listener(&mut self, listener: Box<dyn DatePickerListener>) {
+        self.listener = Some(listener);
+    }
+
+    fn calendar(&self) -> String {
+        let month = self.state.month();
+        let mut s = format!(
+            r#"<div class="datepicker-calendar" data-placement="bottom" data-overlay="{}"><div class="datepicker-header"><div class="datepicker-nav" onmousedown="{}">&lsaquo;</div><div class="datepicker-title">{}</div><div class="datepicker-nav" onmousedown="{}">&rsaquo;</div></div><div class="datepicker-days">"#,
+            self.name,
+            Event::change_js(&self.name, "'month:-1'"),
+            month.format("%B %Y"),
+            Event::change_js(&self.name, "'month:1'"),
+        );
+        for weekday in &["Mo", "Tu", "We", "Th", "Fr", "Sa", "Su"] {
+            s.push_str(&format!(
+                r#"<div class="datepicker-weekday">{}</div>"#,
+                weekday
+            ));
+        }
+        for _ in 0..month.weekday() {
+            s.push_str(r#"<div class="datepicker-blank"></div>"#);
+        }
+        let today = Date::today();
+        let days = Date::days_in_month(month.year(), month.month());
+        for i in 0..days {
+            let day = month.add_days(i as i64);
+            let mut classes = vec!["datepicker-day"];
+            if self.state.date() == Some(day) {
+                classes.push("selected");
+            }
+            if day == today {
+                classes.push("today");
+            }
+            if self.state.allowed(day) {
+                s.push_str(&format!(
+                    r#"<div class="{}" onmousedown="{}">{}</div>"#,
+                    classes.join(" "),
+                    Event::change_js(
+                        &self.name,
+                        &format!("'pick:{}'", day.iso())
+                    ),
+                    day.day(),
+                ));
+            } else {
+                classes.push("disabled");
+                s.push_str(&format!(
+                    r#"<div class="{}">{}</div>"#,
+                    classes.join(" "),
+                    day.day(),
+                ));
+            }
+        }
+        s.push_str("</div></div>");
+        s
+    }
+}
+
+impl Widget for DatePicker {
+    fn eval(&self) -> String {
+        let layout = self.state.layout();
+        let opened = if self.state.opened() { "opened" } else { "" };
+        let mut s = format!(
+            r#"<div id="{}" class="datepicker {}" style="{}{}"><div class="datepicker-field"><input value="{}" placeholder="{}" autocomplete="off" onchange="{}" /><div class="datepicker-button {}" onmousedown="{}">&#9662;</div></div>"#,
+            self.name,
+            layout.class(),
+            layout.style(),
+            self.state.style().css(),
+            escape(&self.state.text()),
+            escape(self.state.placeholder()),
+            Event::change_js(&self.name, "'text:' + value"),
+            opened,
+            Event::change_js(&self.name, "'toggle'"),
+        );
+        if self.state.opened() {
+            s.push_str(&self.calendar());
+        }
+        s.push_str("</div>");
+        s
+    }
+
+    fn name(&self) -> String {
+        self.name.to_string()
+    }
+
+    fn trigger(&mut self, event: &Event) {
+        match event {
+            Event::Update => self.on_update(),
+            Event::Change { source, value } => {
+                if source == &self.name {
+                    self.on_change(value);
+                } else {
+                    self.state.set_opened(false);
+                }
+            }
+            Event::Focus { .. } | Event::Hover { .. } => (),
+            _ => self.state.set_opened(false),
+        }
+    }
+
+    fn on_update(&mut self) {
+        match &self.listener {
+            None => (),
+            Some(listener) => {
+                listener.on_update(&mut self.state);
+            }
+        }
+    }
+
+    fn on_change(&mut self, value: &str) {
+        let date = if value == "toggle" {
+            let opened = self.state.opened();
+            if let Some(date) = self.state.date() {
+                self.state.show_month(date);
+            }
+            self.state.set_opened(!opened);
+            return;
+        } else if let Some(offset) = value.strip_prefix("month:") {
+            if let Ok(offset) = offset.parse::<i32>() {
+                let month = self.state.month().add_months(offset);
+                self.state.show_month(month);
+            }
+            return;
+        } else if let Some(date) = value.strip_prefix("pick:") {
+            self.state.set_opened(false);
+            match Date::parse_iso(date) {
+                Some(date) if self.state.allowed(date) => Some(date),
+                _ => return,
+            }
+        } else if let Some(text) = value.strip_prefix("text:") {
+            self.state.set_opened(false);
+            if text.trim().is_empty() {
+                None
+            } else {
+                match Date::parse(text, self.state.format())
+                    .or_else(|| Date::parse_iso(text))
+                {
+                    Some(date) => Some(date),
+                    None => return,
+                }
+            }
+        } else {
+            return;
+        };
+        self.state.set_date(date);
+        match &self.listener {
+            None => (),
+            Some(listener) => {
+                listener.on_change(&self.state);
+            }
+        }
+    }
+
+    fn values(&self) -> Vec<(String, String)> {
+        vec![(self.name.to_string(), self.state.value())]
+    }
+}
@@ -0,0 +1,295 @@
+use crate::utils::event::Event;
+use crate::utils::html::escape;
+use crate::widgets::widget::{self, Widget};
+
+/// # The state of a Draggable
+///
+/// ## Fields
+///
+/// ```text
+/// child: Option<Box<dyn Widget>>
+/// title: Option<String>
+/// x: f64
+/// y: f64
+/// ```
+pub struct DraggableState {
+    child: Option<Box<dyn Widget>>,
+    title: Option<String>,
+    x: f64,
+    y: f64,
+}
+
+impl DraggableState {
+    /// Get the child
+    pub fn child(&self) -> Option<&(dyn Widget + 'static)> {
+        self.child.as_ref().map(|child| child.as_ref())
+    }
+
+    /// Get the title
+    pub fn title(&self) -> Option<&str> {
+        self.title.as_ref().map(String::as_ref)
+    }
+
+    /// Get the horizontal position in pixels, relative to the parent
+    pub fn x(&self) -> f64 {
+        self.x
+    }
+
+    /// Get the vertical position in pixels, relative to the parent
+    pub fn y(&self) -> f64 {
+        self.y
+    }
+
+    /// Set the child
+    pub fn set_child(&mut self, child: Box<dyn Widget>) {
+        self.child = Some(child);
+    }
+
+    /// Set the title
+    pub fn set_title(&mut self, title: Option<&str>) {
+        self.title = title.map(str::to_string);
+    }
+
+    /// Set the position in pixels, relative to the parent
+    pub fn set_position(&mut self, x: f64, y: f64) {
+        self.x = x;
+        self.y = y;
+    }
+}
+
+/// # The listener of a Draggable
+pub trait DraggableListener {
+    /// Function triggered on update event
+    fn on_update(&self, state: &mut DraggableState);
+
+    /// Function triggered when the Draggable has been moved by the user
+    fn on_move(&self, state: &DraggableState);
+}
+
+/// # A floating element which can be moved around its parent
+///
+/// The Draggable is positioned over the other children of its parent, at the
+/// given coordinates. If a title is set, the Draggable can be moved by
+/// dragging its title bar. Otherwise it can be dragged from anywhere, except
+/// from its inner inputs. The position is kept within the parent and the new
+/// one is reported to the listener when the pointer is released.
+///
+/// ## Fields
+///
+/// ```text
+/// name: String
+/// state: DraggableState
+/// listener: Option<Box<dyn DraggableListener>>
+/// ```
+///
+/// ## Default values
+///
+/// ```text
+/// name: name.to_string()
+/// state:
+///     child: None
+///     title: None
+///     x: 0.0
+///     y: 0.0
+/// listener: None
+/// ```
+///
+/// ## Example
+///
+/// ```
+/// use std::cell::RefCell;
+/// use std::rc::Rc;
+///
+/// use neutrino::widgets::button::Button;
+/// use neutrino::widgets::draggable::{
+///     Draggable, DraggableListener, DraggableState,
+/// };
+/// use neutrino::{App, Window};
+///
+///
+/// struct Palette {
+///     x: f64,
+///     y: f64,
+/// }
+///
+///
+/// struct MyDraggableListener {
+///     palette: Rc<RefCell<Palette>>,
+/// }
+///
+/// impl DraggableListener for MyDraggableListener {
+///     fn on_update(&self, state: &mut DraggableState) {
+///         let palette = self.palette.borrow();
+///         state.set_position(palette.x, palette.y);
+///     }
+///
+///     fn on_move(&self, state: &DraggableState) {
+///         let mut palette = self.palette.borrow_mut();
+///         palette.x = state.x();
+///         palette.y = state.y();
+///     }
+/// }
+///
+///
+/// fn main() {
+///     let palette = Rc::new(RefCell::new(Palette { x: 20.0, y: 20.0 }));
+///
+///     let mut my_draggable = Draggable::new("my_draggable");
+///     my_draggable.set_title("Tools");
+///     my_draggable.set_child(Box::new(Button::new("my_button")));
+///     my_draggable.set_listener(Box::new(MyDraggableListener { palette }));
+/// }
+/// ```
+pub struct Draggable {
+    name: String,
+    state: DraggableState,
+    listener: Option<Box<dyn DraggableListener>>,
+}
+
+impl Draggable {
+    /// Create a Draggable
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            state: DraggableState {
+                child: None,
+                title: None,
+                x: 0.0,
+                y: 0.0,
+            },
+            listener: None,
+        }
+    }
+
+    /// Get the state
+    pub fn state(&self) -> &DraggableState {
+        &self.state
+    }
+
+    /// Get the state, to change it from outside of the listener
+    pub fn state_mut(&mut self) -> &mut DraggableState {
+        &mut self.state
+    }
+
+    /// Set the child
+    pub fn set_child(&mut self, child: Box<dyn Widget>) {
+        self.state.set_child(child);
+    }
+
+    /// Set the title, displayed in a bar used to drag the Draggable
+    pub fn set_title(&mut self, title: &str) {
+        self.state.set_title(Some(title));
+    }
+
+    /// Set the position in pixels, relative to the parent
+    pub fn set_position(&mut self, x: f64, y: f64) {
+        self.state.set_position(x, y);
+    }
+
+    /// Set the listener
+    pub fn set_listener(&mut self, listener: Box<dyn DraggableListener>) {
+        self.listener = Some(listener);
+    }
+}
+
+impl Widget for Draggable {
+    fn eval(&self) -> String {
+        let child = match self.state.child() {
+            Some(child) => widget::eval(child),
+            None => "".to_string(),
+        };
+        match self.state.title() {
+            Some(title) => format!(
+                r#"<div id="{}" class="draggable" style="left: {}px; top: {}px;"><div class="draggable-title" onmousedown="{}">{}</div>{}</div>"#,
+                self.name,
+                self.state.x(),
+                self.state.y(),
+                Event::drag_js(),
+                escape(title),
+                child,
+            ),
+            None => format!(
+                r#"<div id="{}" class="draggable" style="left: {}px; top: {}px;" onmousedown="{}">{}</div>"#,
+                self.name,
+                self.state.x(),
+                self.state.y(),
+                Event::drag_js(),
+                child,
+            ),
+        }
+    }
+
+    fn name(&self) -> String {
+        self.name.to_string()
+    }
+
+    fn trigger(&mut self, event: &Event) {
+        match event {
+            Event::Update => self.on_update(),
+            Event::Change { source, value } => {
+                if source == &self.name {
+                    self.on_change(value)
+                }
+            }
+            _ => (),
+        }
+        if let Some(child) = self.state.child.as_mut() {
+            if widget::reaches(child.as_ref(), event) {
+                widget::trigger(child.as_mut(), event);
+            }
+        }
+    }
+
+    fn on_update(&mut self) {
+        match &self.listener {
+            None => (),
+            Some(listener) => {
+                listener.on_update(&mut self.state);
+            }
+        }
+    }
+
+    fn on_change(&mut self, value: &str) {
+        let values = value
+            .split(';')
+            .map(|v| v.parse::<f64>())
+            .collect::<Vec<_>>();
+        if let [Ok(x), Ok(y)] = values.as_slice() {
+            self.state.set_position(*x, *y);
+            match &self.listener {
+                None => (),
+                Some(listener) => {
+                    listener.on_move(&self.state);
+                }
+            }
+        }
+    }
+
+    fn values(&self) -> Vec<(String, String)> {
+        match self.state.child() {
+            Some(child) => child.values(),
+            None => vec![],
+        }
+    }
+
+    fn is_valid(&self) -> bool {
+        match self.state.child() {
+            Some(child) => child.is_valid(),
+            None => true,
+        }
+    }
+
+    fn validate(&mut self) -> bool {
+        match self.state.child.as_mut() {
+            Some(child) => child.validate(),
+            None => true,
+        }
+    }
+
+    fn children(&self) -> Vec<&(dyn Widget + 'static)> {
+        match self.state.child() {
+            Some(child) => vec![child],
+            None => vec![],
+        }
+    }
+}
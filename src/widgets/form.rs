@@ -0,0 +1,379 @@
+use std::collections::HashMap;
+
+use crate::utils::event::Event;
+use crate::utils::html::escape;
+use crate::utils::layoutoptions::LayoutOptions;
+use crate::utils::style::Style;
+use crate::widgets::container::Direction;
+use crate::widgets::widget::{self, Widget};
+
+/// # The state of a Form
+///
+/// ## Fields
+///
+/// ```text
+/// children: Vec<Box<dyn Widget>>
+/// direction: Direction
+/// submit_text: String
+/// values: HashMap<String, String>
+/// valid: bool
+/// layout: LayoutOptions
+/// style: Style
+/// ```
+pub struct FormState {
+    children: Vec<Box<dyn Widget>>,
+    direction: Direction,
+    submit_text: String,
+    values: HashMap<String, String>,
+    valid: bool,
+    layout: LayoutOptions,
+    style: Style,
+}
+
+impl FormState {
+    /// Get the children
+    pub fn children(&self) -> &Vec<Box<dyn Widget>> {
+        &self.children
+    }
+
+    /// Get the direction
+    pub fn direction(&self) -> &Direction {
+        &self.direction
+    }
+
+    /// Get the text of the submit button
+    pub fn submit_text(&self) -> &str {
+        &self.submit_text
+    }
+
+    /// Get the values of the fields, indexed by widget name
+    pub fn values(&self) -> &HashMap<String, String> {
+        &self.values
+    }
+
+    /// Get the value of a field
+    pub fn value(&self, name: &str) -> Option<&str> {
+        self.values.get(name).map(String::as_ref)
+    }
+
+    /// Return true if all the fields are valid
+    pub fn is_valid(&self) -> bool {
+        self.valid
+    }
+
+    /// Get the layout options
+    pub fn layout(&self) -> &LayoutOptions {
+        &self.layout
+    }
+
+    /// Get the style
+    pub fn style(&self) -> &Style {
+        &self.style
+    }
+
+    /// Get the stretched flag
+    pub fn stretched(&self) -> bool {
+        self.layout.stretched()
+    }
+
+    /// Set the children
+    pub fn set_children(&mut self, children: Vec<Box<dyn Widget>>) {
+        self.children = children;
+    }
+
+    /// Set the direction
+    pub fn set_direction(&mut self, direction: Direction) {
+        self.direction = direction;
+    }
+
+    /// Set the text of the submit button
+    pub fn set_submit_text(&mut self, submit_text: &str) {
+        self.submit_text = submit_text.to_string();
+    }
+
+    /// Set the layout options
+    pub fn set_layout(&mut self, layout: LayoutOptions) {
+        self.layout = layout;
+    }
+
+    /// Set the style
+    pub fn set_style(&mut self, style: Style) {
+        self.style = style;
+    }
+
+    /// Set the stretched flag
+    pub fn set_stretched(&mut self, stretched: bool) {
+        self.layout.set_stretched(stretched);
+    }
+
+    /// Add a child
+    fn add(&mut self, child: Box<dyn Widget>) {
+        self.children.push(child);
+    }
+
+    /// Collect the values and the validity of the fields
+    fn collect(&mut self) {
+        self.values = self
+            .children
+            .iter()
+            .flat_map(|widget| widget.values())
+            .collect();
+        self.valid = self.children.iter().all(|widget| widget.is_valid());
+    }
+}
+
+/// # The listener of a Form
+pub trait FormListener {
+    /// Function triggered on update event
+    fn on_update(&self, state: &mut FormState);
+
+    /// Function triggered when the form is submitted with valid fields
+    fn on_submit(&self, state: &FormState);
+}
+
+/// # A container validating its fields and submitting their values
+///
+/// The fields are the TextInput, Combo, CheckBox, Radio and Range widgets
+/// found among the children, including the ones nested in Containers and
+/// Tabs. When the submit button is clicked, every field is validated and its
+/// error message displayed. The `on_submit` function of the listener is only
+/// triggered if all the fields are valid.
+///
+/// ## Fields
+///
+/// ```text
+/// name: String
+/// state: FormState
+/// listener: Option<Box<dyn FormListener>>
+/// ```
+///
+/// ## Default values
+///
+/// ```text
+/// name: name.to_string()
+/// state:
+///     children: vec![]
+///     direction: Direction::Vertical
+///     submit_text: "Submit".to_string()
+///     values: HashMap::new()
+///     valid: true
+///     layout: LayoutOptions::new()
+///     style: Style::new()
+/// listener: None
+/// ```
+///
+/// ## Example
+///
+/// ```
+/// use neutrino::widgets::form::{Form, FormListener, FormState};
+/// use neutrino::widgets::textinput::TextInput;
+/// use neutrino::utils::validator::Validator;
+/// use neutrino::{App, Window};
+///
+///
+/// struct MyFormListener;
+///
+/// impl FormListener for MyFormListener {
+///     fn on_update(&self, _state: &mut FormState) {}
+///
+///     fn on_submit(&self, state: &FormState) {
+///         println!("Welcome {}", state.value("username").unwrap());
+///     }
+/// }
+///
+///
+/// fn main() {
+///     let mut username = TextInput::new("username");
+///     username.set_value("");
+///     username.add_validator(Validator::Required);
+///     username.add_validator(Validator::MinLength(3));
+///
+///     let mut my_form = Form::new("my_form");
+///     my_form.set_submit_text("Sign in");
+///     my_form.add(Box::new(username));
+///     my_form.set_listener(Box::new(MyFormListener));
+/// }
+/// ```
+pub struct Form {
+    name: String,
+    state: FormState,
+    listener: Option<Box<dyn FormListener>>,
+}
+
+impl Form {
+    /// Create a Form
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            state: FormState {
+                children: vec![],
+                direction: Direction::Vertical,
+                submit_text: "Submit".to_string(),
+                values: HashMap::new(),
+                valid: true,
+                layout: LayoutOptions::new(),
+                style: Style::new(),
+            },
+            listener: None,
+        }
+    }
+
+    /// Get the state
+    pub fn state(&self) -> &FormState {
+        &self.state
+    }
+
+    /// Get the state, to change it from outside of the listener
+    pub fn state_mut(&mut self) -> &mut FormState {
+        &mut self.state
+    }
+
+    /// Set the direction
+    pub fn set_direction(&mut self, direction: Direction) {
+        self.state.set_direction(direction);
+    }
+
+    /// Set the text of the submit button
+    pub fn set_submit_text(&mut self, submit_text: &str) {
+        self.state.set_submit_text(submit_text);
+    }
+
+    /// Set the layout options
+    pub fn set_layout(&mut self, layout: LayoutOptions) {
+        self.state.set_layout(layout);
+    }
+
+    /// Set the style
+    pub fn set_style(&mut self, style: Style) {
+        self.state.set_style(style);
+    }
+
+    /// Set the stretched flag to true
+    pub fn set_stretched(&mut self) {
+        self.state.set_stretched(true);
+    }
+
+    /// Set the listener
+    pub fn set_listener(&mut self, listener: Box<dyn FormListener>) {
+        self.listener = Some(listener);
+    }
+
+    /// Add a widget
+    pub fn add(&mut self, widget: Box<dyn Widget>) {
+        self.state.add(widget);
+    }
+
+    /// Return true if all the fields are valid
+    pub fn is_valid(&self) -> bool {
+        self.state.children.iter().all(|widget| widget.is_valid())
+    }
+}
+
+impl Widget for Form {
+    fn eval(&self) -> String {
+        let layout = self.state.layout();
+        let mut s = format!(
+            r#"<div id="{}" class="form {} {}" style="{}{}">"#,
+            self.name,
+            self.state.direction().css(),
+            layout.class(),
+            layout.style(),
+            self.state.style().css(),
+        );
+        for widget in self.state.children.iter() {
+            s.push_str(&widget::eval(widget.as_ref()));
+        }
+        s.push_str(&format!(
+            r#"<div class="form-actions"><div class="button form-submit" onmousedown="{}">{}</div></div>"#,
+            Event::change_js(&self.name, "'submit'"),
+            escape(self.state.submit_text()),
+        ));
+        s.push_str("</div>");
+        s
+    }
+
+    fn name(&self) -> String {
+        self.name.to_string()
+    }
+
+    fn trigger(&mut self, event: &Event) {
+        for child in self.state.children.iter_mut() {
+            if widget::reaches(child.as_ref(), event) {
+                widget::trigger(child.as_mut(), event);
+            }
+        }
+        self.state.collect();
+        match event {
+            Event::Update => self.on_update(),
+            Event::Change { source, value } => {
+                if source == &self.name {
+                    self.on_change(value)
+                }
+            }
+            _ => (),
+        }
+    }
+
+    fn on_update(&mut self) {
+        match &self.listener {
+            None => (),
+            Some(listener) => {
+                listener.on_update(&mut self.state);
+            }
+        }
+    }
+
+    fn on_change(&mut self, value: &str) {
+        if value != "submit" {
+            return;
+        }
+        if !self.validate() {
+            return;
+        }
+        match &self.listener {
+            None => (),
+            Some(listener) => {
+                listener.on_submit(&self.state);
+            }
+        }
+    }
+
+    fn values(&self) -> Vec<(String, String)> {
+        self.state
+            .children
+            .iter()
+            .flat_map(|widget| widget.values())
+            .collect()
+    }
+
+    fn is_valid(&self) -> bool {
+        self.state.children.iter().all(|widget| widget.is_valid())
+    }
+
+    fn validate(&mut self) -> bool {
+        let mut valid = true;
+        for widget in self.state.children.iter_mut() {
+            if !widget.validate() {
+                valid = false;
+            }
+        }
+        self.state.collect();
+        valid
+    }
+
+    fn children(&self) -> Vec<&(dyn Widget + 'static)> {
+        self.state
+            .children
+            .iter()
+            .map(|widget| widget.as_ref())
+            .collect()
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut (dyn Widget + 'static)> {
+        self.state
+            .children
+            .iter_mut()
+            .map(|widget| widget.as_mut() as &mut dyn Widget)
+            .collect()
+    }
+}
@@ -1,6 +1,9 @@
 use crate::utils::event::Event;
+use crate::utils::html::escape;
 use crate::utils::icon::Icon;
+use crate::utils::layoutoptions::LayoutOptions;
 use crate::utils::pixmap::Pixmap;
+use crate::utils::style::Style;
 use crate::widgets::widget::Widget;
 
 /// # The state of an Image
@@ -12,14 +15,16 @@ use crate::widgets::widget::Widget;
 /// extension: String
 /// background: String
 /// keep_ratio_aspect: bool
-/// stretched: bool
+/// layout: LayoutOptions
+/// style: Style
 /// ```
 pub struct ImageState {
     data: String,
     extension: String,
     background: String,
     keep_ratio_aspect: bool,
-    stretched: bool,
+    layout: LayoutOptions,
+    style: Style,
 }
 
 impl ImageState {
@@ -43,9 +48,19 @@ impl ImageState {
         self.keep_ratio_aspect
     }
 
+    /// Get the layout options
+    pub fn layout(&self) -> &LayoutOptions {
+        &self.layout
+    }
+
+    /// Get the style
+    pub fn style(&self) -> &Style {
+        &self.style
+    }
+
     /// Get the stretched flag
     pub fn stretched(&self) -> bool {
-        self.stretched
+        self.layout.stretched()
     }
 
     /// Set the base64 encoded image data
@@ -63,14 +78,40 @@ impl ImageState {
         self.background = background.to_string();
     }
 
+    /// Set the data and the extension from raw bytes
+    pub fn set_bytes(&mut self, bytes: &[u8]) {
+        self.set_pixmap(&Pixmap::from_bytes(bytes));
+    }
+
+    /// Set the data and the extension from a data URI
+    pub fn set_data_uri(&mut self, uri: &str) {
+        self.set_pixmap(&Pixmap::from_data_uri(uri));
+    }
+
+    /// Set the data and the extension from a Pixmap
+    pub fn set_pixmap(&mut self, pixmap: &Pixmap) {
+        self.data = pixmap.data().to_string();
+        self.extension = pixmap.extension().to_string();
+    }
+
     /// Set the keep_ratio_aspect flag
     pub fn set_keep_ratio_aspect(&mut self, keep_ratio_aspect: bool) {
         self.keep_ratio_aspect = keep_ratio_aspect;
     }
 
+    /// Set the layout options
+    pub fn set_layout(&mut self, layout: LayoutOptions) {
+        self.layout = layout;
+    }
+
+    /// Set the style
+    pub fn set_style(&mut self, style: Style) {
+        self.style = style;
+    }
+
     /// Set the stretched flag
     pub fn set_stretched(&mut self, stretched: bool) {
-        self.stretched = stretched;
+        self.layout.set_stretched(stretched);
     }
 }
 
@@ -80,7 +121,7 @@ pub trait ImageListener {
     fn on_update(&self, state: &mut ImageState);
 }
 
-/// # An element able to display images from icons and path
+/// # An element able to display images from icons, paths and bytes
 ///
 /// ## Fields
 ///
@@ -92,8 +133,8 @@ pub trait ImageListener {
 ///
 /// ## Default values
 ///
-/// The variable `pixmap` is built in both constructors from the given Icon or
-/// path.
+/// The variable `pixmap` is built in the constructors from the given Icon,
+/// path, bytes or data URI.
 ///
 /// ```text
 /// name: name.to_string()
@@ -102,7 +143,8 @@ pub trait ImageListener {
 ///     extension: pixmap.extension().to_string(),
 ///     background: "black".to_string(),
 ///     keep_ratio_aspect: false,
-///     stretched: false,
+///     layout: LayoutOptions::new(),
+///     style: Style::new(),
 /// listener: None
 /// ```
 ///
@@ -170,7 +212,21 @@ pub struct Image {
 impl Image {
     /// Create an image from a path
     pub fn from_path(name: &str, path: &str) -> Self {
-        let pixmap = Pixmap::from_path(path);
+        Self::from_pixmap(name, Pixmap::from_path(path))
+    }
+
+    /// Create an image from raw bytes
+    pub fn from_bytes(name: &str, bytes: &[u8]) -> Self {
+        Self::from_pixmap(name, Pixmap::from_bytes(bytes))
+    }
+
+    /// Create an image from a data URI
+    pub fn from_data_uri(name: &str, uri: &str) -> Self {
+        Self::from_pixmap(name, Pixmap::from_data_uri(uri))
+    }
+
+    /// Create an image from a Pixmap
+    pub fn from_pixmap(name: &str, pixmap: Pixmap) -> Self {
         Self {
             name: name.to_string(),
             state: ImageState {
@@ -178,7 +234,8 @@ impl Image {
                 extension: pixmap.extension().to_string(),
                 background: "black".to_string(),
                 keep_ratio_aspect: false,
-                stretched: false,
+                layout: LayoutOptions::new(),
+                style: Style::new(),
             },
             listener: None,
         }
@@ -186,18 +243,17 @@ impl Image {
 
     /// Create an image from an icon
     pub fn from_icon(name: &str, icon: Box<dyn Icon>) -> Self {
-        let pixmap = Pixmap::from_icon(icon);
-        Self {
-            name: name.to_string(),
-            state: ImageState {
-                data: pixmap.data().to_string(),
-                extension: pixmap.extension().to_string(),
-                background: "black".to_string(),
-                keep_ratio_aspect: false,
-                stretched: false,
-            },
-            listener: None,
-        }
+        Self::from_pixmap(name, Pixmap::from_icon(icon))
+    }
+
+    /// Get the state
+    pub fn state(&self) -> &ImageState {
+        &self.state
+    }
+
+    /// Get the state, to change it from outside of the listener
+    pub fn state_mut(&mut self) -> &mut ImageState {
+        &mut self.state
     }
 
     /// Set the background color
@@ -210,6 +266,16 @@ impl Image {
         self.state.set_keep_ratio_aspect(true);
     }
 
+    /// Set the layout options
+    pub fn set_layout(&mut self, layout: LayoutOptions) {
+        self.state.set_layout(layout);
+    }
+
+    /// Set the style
+    pub fn set_style(&mut self, style: Style) {
+        self.state.set_style(style);
+    }
+
     /// Set the stretched flag to true
     pub fn set_stretched(&mut self) {
         self.state.set_stretched(true);
@@ -228,22 +294,24 @@ impl Widget for Image {
         } else {
             r#"width="100%" height="100%""#
         };
-        let stretched = if self.state.stretched() {
-            "stretched"
-        } else {
-            ""
-        };
+        let layout = self.state.layout();
         format!(
-            r#"<div id="{}" class="image {}" style="background:{};"><img {} src="data:image/{};base64,{}" /></div>"#, 
+            r#"<div id="{}" class="image {}" style="{}background:{};{}"><img {} src="data:image/{};base64,{}" /></div>"#, 
             self.name,
-            stretched,
-            self.state.background(),
+            layout.class(),
+            layout.style(),
+            escape(self.state.background()),
+            self.state.style().css(),
             ratio,
             self.state.extension(),
             self.state.data(),
         )
     }
 
+    fn name(&self) -> String {
+        self.name.to_string()
+    }
+
     fn trigger(&mut self, event: &Event) {
         match event {
             Event::Update => self.on_update(),
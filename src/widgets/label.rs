@@ -1,4 +1,8 @@
 use crate::utils::event::Event;
+use crate::utils::html::escape;
+use crate::utils::i18n;
+use crate::utils::layoutoptions::LayoutOptions;
+use crate::utils::style::Style;
 use crate::widgets::widget::Widget;
 
 /// # The state of a Label
@@ -7,11 +11,17 @@ use crate::widgets::widget::Widget;
 ///
 /// ```text
 /// text: String
-/// stretched: bool
+/// text_key: Option<String>
+/// layout: LayoutOptions
+/// style: Style
+/// raw: bool
 /// ```
 pub struct LabelState {
     text: String,
-    stretched: bool,
+    text_key: Option<String>,
+    layout: LayoutOptions,
+    style: Style,
+    raw: bool,
 }
 
 impl LabelState {
@@ -20,9 +30,29 @@ impl LabelState {
         &self.text
     }
 
+    /// Get the key of the text in the catalogs
+    pub fn text_key(&self) -> Option<&str> {
+        self.text_key.as_deref()
+    }
+
+    /// Get the layout options
+    pub fn layout(&self) -> &LayoutOptions {
+        &self.layout
+    }
+
+    /// Get the style
+    pub fn style(&self) -> &Style {
+        &self.style
+    }
+
     /// Get the stretched flag
     pub fn stretched(&self) -> bool {
-        self.stretched
+        self.layout.stretched()
+    }
+
+    /// Get the raw flag
+    pub fn raw(&self) -> bool {
+        self.raw
     }
 
     /// Set the text
@@ -30,9 +60,30 @@ impl LabelState {
         self.text = text.to_string();
     }
 
+    /// Set the key of the text in the catalogs, the text being translated
+    /// in the locale of the application
+    pub fn set_text_key(&mut self, key: &str) {
+        self.text_key = Some(key.to_string());
+    }
+
+    /// Set the layout options
+    pub fn set_layout(&mut self, layout: LayoutOptions) {
+        self.layout = layout;
+    }
+
+    /// Set the style
+    pub fn set_style(&mut self, style: Style) {
+        self.style = style;
+    }
+
     /// Set the stretched flag
     pub fn set_stretched(&mut self, stretched: bool) {
-        self.stretched = stretched;
+        self.layout.set_stretched(stretched);
+    }
+
+    /// Set the raw flag: the text is inserted as HTML, without being escaped
+    pub fn set_raw(&mut self, raw: bool) {
+        self.raw = raw;
     }
 }
 
@@ -58,7 +109,10 @@ pub trait LabelListener {
 /// name: name.to_string()
 /// state:
 ///     text: "Label".to_string()
-///     stretched: false,
+///     text_key: None,
+///     layout: LayoutOptions::new(),
+///     style: Style::new(),
+///     raw: false,
 /// listener: None
 /// ```
 ///
@@ -128,22 +182,57 @@ impl Label {
             name: name.to_string(),
             state: LabelState {
                 text: "Label".to_string(),
-                stretched: false,
+                text_key: None,
+                layout: LayoutOptions::new(),
+                style: Style::new(),
+                raw: false,
             },
             listener: None,
         }
     }
 
+    /// Get the state
+    pub fn state(&self) -> &LabelState {
+        &self.state
+    }
+
+    /// Get the state, to change it from outside of the listener
+    pub fn state_mut(&mut self) -> &mut LabelState {
+        &mut self.state
+    }
+
     /// Set the text
     pub fn set_text(&mut self, text: &str) {
         self.state.set_text(text);
     }
 
+    /// Set the key of the text in the catalogs, the text being translated
+    /// in the locale of the application
+    pub fn set_text_key(&mut self, key: &str) {
+        self.state.set_text_key(key);
+    }
+
+    /// Set the layout options
+    pub fn set_layout(&mut self, layout: LayoutOptions) {
+        self.state.set_layout(layout);
+    }
+
+    /// Set the style
+    pub fn set_style(&mut self, style: Style) {
+        self.state.set_style(style);
+    }
+
     /// Set the stretched flag to true
     pub fn set_stretched(&mut self) {
         self.state.set_stretched(true);
     }
 
+    /// Set the raw flag to true: the text is trusted HTML, such as
+    /// `<b>bold</b>`, inserted without being escaped
+    pub fn set_raw(&mut self) {
+        self.state.set_raw(true);
+    }
+
     /// Set the listener
     pub fn set_listener(&mut self, listener: Box<dyn LabelListener>) {
         self.listener = Some(listener);
@@ -152,19 +241,30 @@ impl Label {
 
 impl Widget for Label {
     fn eval(&self) -> String {
-        let stretched = if self.state.stretched() {
-            "stretched"
+        let layout = self.state.layout();
+        let text = match self.state.text_key() {
+            Some(key) => i18n::text(key),
+            None => self.state.text().to_string(),
+        };
+        let text = if self.state.raw() {
+            text
         } else {
-            ""
+            escape(&text)
         };
         format!(
-            r#"<div id="{}" class="label {}">{}</div>"#,
+            r#"<div id="{}" class="label {}" style="{}{}">{}</div>"#,
             self.name,
-            stretched,
-            self.state.text()
+            layout.class(),
+            layout.style(),
+            self.state.style().css(),
+            text
         )
     }
 
+    fn name(&self) -> String {
+        self.name.to_string()
+    }
+
     fn trigger(&mut self, event: &Event) {
         match event {
             Event::Update => self.on_update(),
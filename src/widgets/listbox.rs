@@ -0,0 +1,450 @@
+use crate::utils::event::Event;
+use crate::utils::html::{aria_label, escape};
+use crate::utils::icon::Icon;
+use crate::utils::layoutoptions::LayoutOptions;
+use crate::utils::pixmap::Pixmap;
+use crate::utils::style::Style;
+use crate::widgets::widget::Widget;
+
+/// # The state of a ListBox
+///
+/// ## Fields
+///
+/// ```text
+/// items: Vec<String>
+/// icons: Vec<Option<Pixmap>>
+/// selected: Vec<u32>
+/// anchor: Option<u32>
+/// multiple: bool
+/// layout: LayoutOptions
+/// style: Style
+/// aria_label: Option<String>
+/// ```
+pub struct ListBoxState {
+    items: Vec<String>,
+    icons: Vec<Option<Pixmap>>,
+    selected: Vec<u32>,
+    anchor: Option<u32>,
+    multiple: bool,
+    layout: LayoutOptions,
+    style: Style,
+    aria_label: Option<String>,
+}
+
+impl ListBoxState {
+    /// Get the items
+    pub fn items(&self) -> &Vec<String> {
+        &self.items
+    }
+
+    /// Get the icon of an item
+    pub fn icon(&self, index: u32) -> Option<&Pixmap> {
+        match self.icons.get(index as usize) {
+            Some(Some(icon)) => Some(icon),
+            _ => None,
+        }
+    }
+
+    /// Get the indices of the selected items, in ascending order
+    pub fn selected(&self) -> &Vec<u32> {
+        &self.selected
+    }
+
+    /// Get the selected items
+    pub fn selected_items(&self) -> Vec<&str> {
+        self.selected
+            .iter()
+            .filter_map(|&i| self.items.get(i as usize))
+            .map(String::as_ref)
+            .collect()
+    }
+
+    /// Get the multiple flag
+    pub fn multiple(&self) -> bool {
+        self.multiple
+    }
+
+    /// Get the layout options
+    pub fn layout(&self) -> &LayoutOptions {
+        &self.layout
+    }
+
+    /// Get the style
+    pub fn style(&self) -> &Style {
+        &self.style
+    }
+
+    /// Get the label read by screen readers
+    pub fn aria_label(&self) -> Option<&str> {
+        self.aria_label.as_deref()
+    }
+
+    /// Get the stretched flag
+    pub fn stretched(&self) -> bool {
+        self.layout.stretched()
+    }
+
+    /// Set the items, clearing their icons and the selection
+    pub fn set_items(&mut self, items: Vec<&str>) {
+        self.items = items.iter().map(|item| item.to_string()).collect();
+        self.icons = self.items.iter().map(|_| None).collect();
+        self.selected = vec![];
+        self.anchor = None;
+    }
+
+    /// Set the icon of an item
+    pub fn set_icon(&mut self, index: u32, icon: Box<dyn Icon>) {
+        if let Some(slot) = self.icons.get_mut(index as usize) {
+            *slot = Some(Pixmap::from_icon(icon));
+        }
+    }
+
+    /// Set the indices of the selected items, ignoring the ones out of range
+    /// and keeping only the first one if the multiple flag is false
+    pub fn set_selected(&mut self, selected: Vec<u32>) {
+        let length = self.items.len() as u32;
+        let mut selected: Vec<u32> =
+            selected.into_iter().filter(|&i| i < length).collect();
+        selected.sort_unstable();
+        selected.dedup();
+        if !self.multiple {
+            selected.truncate(1);
+        }
+        match self.anchor {
+            Some(anchor) if selected.contains(&anchor) => (),
+            _ => self.anchor = selected.first().cloned(),
+        }
+        self.selected = selected;
+    }
+
+    /// Set the multiple flag
+    pub fn set_multiple(&mut self, multiple: bool) {
+        self.multiple = multiple;
+        if !multiple {
+            self.selected.truncate(1);
+        }
+    }
+
+    /// Set the layout options
+    pub fn set_layout(&mut self, layout: LayoutOptions) {
+        self.layout = layout;
+    }
+
+    /// Set the style
+    pub fn set_style(&mut self, style: Style) {
+        self.style = style;
+    }
+
+    /// Set the label read by screen readers, in place of the visible text
+    pub fn set_aria_label(&mut self, label: &str) {
+        self.aria_label = Some(label.to_string());
+    }
+
+    /// Set the stretched flag
+    pub fn set_stretched(&mut self, stretched: bool) {
+        self.layout.set_stretched(stretched);
+    }
+
+    fn select(&mut self, index: u32, mode: &str) {
+        if index as usize >= self.items.len() {
+            return;
+        }
+        match (mode, self.anchor) {
+            ("toggle", _) if self.multiple => {
+                match self.selected.iter().position(|&i| i == index) {
+                    Some(position) => {
+                        self.selected.remove(position);
+                    }
+                    None => {
+                        self.selected.push(index);
+                        self.selected.sort_unstable();
+                    }
+                }
+                self.anchor = Some(index);
+            }
+            ("range", Some(anchor)) if self.multiple => {
+                self.selected =
+                    (anchor.min(index)..=anchor.max(index)).collect();
+            }
+            _ => {
+                self.selected = vec![index];
+                self.anchor = Some(index);
+            }
+        }
+    }
+}
+
+/// # The listener of a ListBox
+pub trait ListBoxListener {
+    /// Function triggered on update event
+    fn on_update(&self, state: &mut ListBoxState);
+
+    /// Function triggered on change event
+    fn on_change(&self, state: &ListBoxState);
+}
+
+/// # A list of items which can be selected
+///
+/// A click selects an item. If the multiple flag is set, Ctrl+click (or
+/// Cmd+click) adds an item to the selection or removes it, and Shift+click
+/// selects all the items between the last clicked one and the clicked one.
+///
+/// The value of the ListBox is the indices of the selected items, separated
+/// by commas.
+///
+/// ## Fields
+///
+/// ```text
+/// name: String
+/// state: ListBoxState
+/// listener: Option<Box<dyn ListBoxListener>>
+/// ```
+///
+/// ## Default values
+///
+/// ```text
+/// name: name.to_string()
+/// state:
+///     items: vec![]
+///     icons: vec![]
+///     selected: vec![]
+///     anchor: None
+///     multiple: false
+///     layout: LayoutOptions::new()
+///     style: Style::new()
+///     aria_label: None
+/// listener: None
+/// ```
+///
+/// ## Example
+///
+/// ```
+/// use std::cell::RefCell;
+/// use std::rc::Rc;
+///
+/// use neutrino::widgets::listbox::{ListBox, ListBoxListener, ListBoxState};
+/// use neutrino::{App, Window};
+///
+///
+/// struct Recipients {
+///     selected: Vec<u32>,
+/// }
+///
+///
+/// struct MyListBoxListener {
+///     recipients: Rc<RefCell<Recipients>>,
+/// }
+///
+/// impl ListBoxListener for MyListBoxListener {
+///     fn on_update(&self, state: &mut ListBoxState) {
+///         state.set_selected(self.recipients.borrow().selected.clone());
+///     }
+///
+///     fn on_change(&self, state: &ListBoxState) {
+///         self.recipients.borrow_mut().selected = state.selected().clone();
+///     }
+/// }
+///
+///
+/// fn main() {
+///     let recipients = Rc::new(RefCell::new(Recipients { selected: vec![] }));
+///
+///     let mut my_listbox = ListBox::new("my_listbox");
+///     my_listbox.set_items(vec!["Alice", "Bob", "Carol", "Dave"]);
+///     my_listbox.set_multiple();
+///     my_listbox.set_listener(Box::new(MyListBoxListener { recipients }));
+/// }
+/// ```
+pub struct ListBox {
+    name: String,
+    state: ListBoxState,
+    listener: Option<Box<dyn ListBoxListener>>,
+}
+
+impl ListBox {
+    /// Create a ListBox
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            state: ListBoxState {
+                items: vec![],
+                icons: vec![],
+                selected: vec![],
+                anchor: None,
+                multiple: false,
+                layout: LayoutOptions::new(),
+                style: Style::new(),
+                aria_label: None,
+            },
+            listener: None,
+        }
+    }
+
+    /// Get the state
+    pub fn state(&self) -> &ListBoxState {
+        &self.state
+    }
+
+    /// Get the state, to change it from outside of the listener
+    pub fn state_mut(&mut self) -> &mut ListBoxState {
+        &mut self.state
+    }
+
+    /// Set the items
+    pub fn set_items(&mut self, items: Vec<&str>) {
+        self.state.set_items(items);
+    }
+
+    /// Set the icon of an item
+    pub fn set_icon(&mut self, index: u32, icon: Box<dyn Icon>) {
+        self.state.set_icon(index, icon);
+    }
+
+    /// Set the indices of the selected items
+    pub fn set_selected(&mut self, selected: Vec<u32>) {
+        self.state.set_selected(selected);
+    }
+
+    /// Set the multiple flag to true
+    pub fn set_multiple(&mut self) {
+        self.state.set_multiple(true);
+    }
+
+    /// Set the layout options
+    pub fn set_layout(&mut self, layout: LayoutOptions) {
+        self.state.set_layout(layout);
+    }
+
+    /// Set the style
+    pub fn set_style(&mut self, style: Style) {
+        self.state.set_style(style);
+    }
+
+    /// Set the label read by screen readers, in place of the visible text
+    pub fn set_aria_label(&mut self, label: &str) {
+        self.state.set_aria_label(label);
+    }
+
+    /// Set the stretched flag to true
+    pub fn set_stretched(&mut self) {
+        self.state.set_stretched(true);
+    }
+
+    /// Set the listener
+    pub fn set_listener(&mut self, listener: Box<dyn ListBoxListener>) {
+        self.listener = Some(listener);
+    }
+}
+
+impl Widget for ListBox {
+    fn eval(&self) -> String {
+        let layout = self.state.layout();
+        let multiple = if self.state.multiple() {
+            "multiple"
+        } else {
+            ""
+        };
+        let last = self.state.items().len().saturating_sub(1) as u32;
+        let current = self.state.selected().last().copied();
+        let previous = current.map_or(0, |current| current.saturating_sub(1));
+        let next = current.map_or(0, |current| (current + 1).min(last));
+        let mut s = format!(
+            r#"<div id="{}" class="listbox {} {}" style="{}{}" role="listbox" aria-multiselectable="{}" tabindex="0" {} onkeydown="{}">"#,
+            self.name,
+            multiple,
+            layout.class(),
+            layout.style(),
+            self.state.style().css(),
+            self.state.multiple(),
+            aria_label(self.state.aria_label()),
+            Event::keys_js(
+                &self.name,
+                &[
+                    ("ArrowUp", format!("{};single", previous)),
+                    ("ArrowDown", format!("{};single", next)),
+                ]
+            ),
+        );
+        for (i, item) in self.state.items().iter().enumerate() {
+            let selected = if self.state.selected().contains(&(i as u32)) {
+                "selected"
+            } else {
+                ""
+            };
+            let icon = match self.state.icon(i as u32) {
+                Some(icon) => format!(
+                    r#"<img src="data:image/{};base64,{}" />"#,
+                    icon.extension(),
+                    icon.data()
+                ),
+                None => "".to_string(),
+            };
+            s.push_str(&format!(
+                r#"<div class="listbox-item {}" role="option" aria-selected="{}" onmousedown="{}">{}<span>{}</span></div>"#,
+                selected,
+                !selected.is_empty(),
+                Event::change_js(
+                    &self.name,
+                    &format!(
+                        "'{};' + (event.shiftKey ? 'range' : event.ctrlKey || event.metaKey ? 'toggle' : 'single')",
+                        i
+                    )
+                ),
+                icon,
+                escape(item)
+            ));
+        }
+        s.push_str("</div>");
+        s
+    }
+
+    fn name(&self) -> String {
+        self.name.to_string()
+    }
+
+    fn trigger(&mut self, event: &Event) {
+        match event {
+            Event::Update => self.on_update(),
+            Event::Change { source, value } => {
+                if source == &self.name {
+                    self.on_change(value);
+                }
+            }
+            _ => (),
+        }
+    }
+
+    fn on_update(&mut self) {
+        match &self.listener {
+            None => (),
+            Some(listener) => {
+                listener.on_update(&mut self.state);
+            }
+        }
+    }
+
+    fn on_change(&mut self, value: &str) {
+        let values = value.split(';').collect::<Vec<_>>();
+        if let [index, mode] = values.as_slice() {
+            if let Ok(index) = index.parse::<u32>() {
+                self.state.select(index, mode);
+                match &self.listener {
+                    None => (),
+                    Some(listener) => {
+                        listener.on_change(&self.state);
+                    }
+                }
+            }
+        }
+    }
+
+    fn values(&self) -> Vec<(String, String)> {
+        let selected = self
+            .state
+            .selected()
+            .iter()
+            .map(|i| i.to_string())
+            .collect::<Vec<_>>();
+        vec![(self.name.to_string(), selected.join(","))]
+    }
+}
@@ -0,0 +1,923 @@
+use crate::utils::event::Event;
+use crate::utils::html::escape;
+use crate::utils::i18n;
+use crate::utils::layoutoptions::LayoutOptions;
+use crate::utils::observe::Status;
+use crate::utils::style::Style;
+use crate::widgets::widget::{self, Placeholder, Widget};
+
+/// # The state of a ListView
+///
+/// ## Fields
+///
+/// ```text
+/// items: Vec<String>
+/// selected: Option<u32>
+/// infinite: bool
+/// has_more: bool
+/// loading: bool
+/// refreshable: bool
+/// refreshing: bool
+/// selectable: bool
+/// selecting: bool
+/// checked: Vec<u32>
+/// actions: Vec<(String, String)>
+/// error: Option<String>
+/// empty_placeholder: Option<Placeholder>
+/// loading_placeholder: Option<Placeholder>
+/// error_placeholder: Option<Placeholder>
+/// layout: LayoutOptions
+/// style: Style
+/// ```
+pub struct ListViewState {
+    items: Vec<String>,
+    selected: Option<u32>,
+    infinite: bool,
+    has_more: bool,
+    loading: bool,
+    refreshable: bool,
+    refreshing: bool,
+    selectable: bool,
+    selecting: bool,
+    checked: Vec<u32>,
+    actions: Vec<(String, String)>,
+    error: Option<String>,
+    empty_placeholder: Option<Placeholder>,
+    loading_placeholder: Option<Placeholder>,
+    error_placeholder: Option<Placeholder>,
+    layout: LayoutOptions,
+    style: Style,
+}
+
+impl ListViewState {
+    /// Get the items
+    pub fn items(&self) -> &Vec<String> {
+        &self.items
+    }
+
+    /// Get the selected index
+    pub fn selected(&self) -> Option<u32> {
+        self.selected
+    }
+
+    /// Get the infinite flag
+    pub fn infinite(&self) -> bool {
+        self.infinite
+    }
+
+    /// Get the has_more flag
+    pub fn has_more(&self) -> bool {
+        self.has_more
+    }
+
+    /// Get the loading flag
+    pub fn loading(&self) -> bool {
+        self.loading
+    }
+
+    /// Get the refreshable flag
+    pub fn refreshable(&self) -> bool {
+        self.refreshable
+    }
+
+    /// Get the refreshing flag
+    pub fn refreshing(&self) -> bool {
+        self.refreshing
+    }
+
+    /// Get the selectable flag
+    pub fn selectable(&self) -> bool {
+        self.selectable
+    }
+
+    /// Get the selecting flag, true while the selection mode is on
+    pub fn selecting(&self) -> bool {
+        self.selecting
+    }
+
+    /// Get the indexes of the checked items, in ascending order
+    pub fn checked(&self) -> &Vec<u32> {
+        &self.checked
+    }
+
+    /// Return true if an item is checked
+    pub fn is_checked(&self, index: u32) -> bool {
+        self.checked.contains(&index)
+    }
+
+    /// Get the bulk actions, as (action, text)
+    pub fn actions(&self) -> &Vec<(String, String)> {
+        &self.actions
+    }
+
+    /// Get the error of the data, displayed in place of the items
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    /// Get the placeholder displayed while there is no item
+    pub fn empty_placeholder(&self) -> Option<&Placeholder> {
+        self.empty_placeholder.as_ref()
+    }
+
+    /// Get the placeholder displayed while the first items are loading
+    pub fn loading_placeholder(&self) -> Option<&Placeholder> {
+        self.loading_placeholder.as_ref()
+    }
+
+    /// Get the placeholder displayed in place of the items on error
+    pub fn error_placeholder(&self) -> Option<&Placeholder> {
+        self.error_placeholder.as_ref()
+    }
+
+    /// Get the layout options
+    pub fn layout(&self) -> &LayoutOptions {
+        &self.layout
+    }
+
+    /// Get the style
+    pub fn style(&self) -> &Style {
+        &self.style
+    }
+
+    /// Get the stretched flag
+    pub fn stretched(&self) -> bool {
+        self.layout.stretched()
+    }
+
+    /// Set the items. The loading flag is reset if new items are given, and
+    /// the checked indexes past the end of the list are dropped.
+    pub fn set_items(&mut self, items: Vec<&str>) {
+        if items.len() > self.items.len() {
+            self.loading = false;
+        }
+        self.items = items
+            .iter()
+            .map(|i| i.to_string())
+            .collect::<Vec<String>>();
+        let length = self.items.len() as u32;
+        self.checked.retain(|index| *index < length);
+    }
+
+    /// Append items at the end of the list and reset the loading flag
+    pub fn add_items(&mut self, items: Vec<&str>) {
+        if !items.is_empty() {
+            self.loading = false;
+        }
+        self.items.extend(items.iter().map(|i| i.to_string()));
+    }
+
+    /// Set the selected index
+    pub fn set_selected(&mut self, selected: Option<u32>) {
+        self.selected = selected;
+    }
+
+    /// Set the infinite flag
+    pub fn set_infinite(&mut self, infinite: bool) {
+        self.infinite = infinite;
+    }
+
+    /// Set the has_more flag
+    pub fn set_has_more(&mut self, has_more: bool) {
+        self.has_more = has_more;
+    }
+
+    /// Set the loading flag
+    pub fn set_loading(&mut self, loading: bool) {
+        self.loading = loading;
+    }
+
+    /// Set the refreshable flag
+    pub fn set_refreshable(&mut self, refreshable: bool) {
+        self.refreshable = refreshable;
+    }
+
+    /// Set the refreshing flag
+    pub fn set_refreshing(&mut self, refreshing: bool) {
+        self.refreshing = refreshing;
+    }
+
+    /// Set the selectable flag
+    pub fn set_selectable(&mut self, selectable: bool) {
+        self.selectable = selectable;
+    }
+
+    /// Set the selecting flag, the checked items being cleared when the
+    /// selection mode is turned off
+    pub fn set_selecting(&mut self, selecting: bool) {
+        self.selecting = selecting;
+        if !selecting {
+            self.checked.clear();
+        }
+    }
+
+    /// Set the indexes of the checked items
+    pub fn set_checked(&mut self, checked: Vec<u32>) {
+        let length = self.items.len() as u32;
+        self.checked = checked;
+        self.checked.retain(|index| *index < length);
+        self.checked.sort_unstable();
+        self.checked.dedup();
+    }
+
+    /// Add a bulk action, with the action given to the listener and its
+    /// text
+    pub fn add_action(&mut self, action: &str, text: &str) {
+        self.actions.push((action.to_string(), text.to_string()));
+    }
+
+    /// Set the error of the data, None once the data is loaded
+    pub fn set_error(&mut self, error: Option<&str>) {
+        self.error = error.map(str::to_string);
+    }
+
+    /// Set the placeholder displayed while there is no item
+    pub fn set_empty_placeholder(&mut self, placeholder: Placeholder) {
+        self.empty_placeholder = Some(placeholder);
+    }
+
+    /// Set the placeholder displayed while the first items are loading
+    pub fn set_loading_placeholder(&mut self, placeholder: Placeholder) {
+        self.loading_placeholder = Some(placeholder);
+    }
+
+    /// Set the placeholder displayed in place of the items on error
+    pub fn set_error_placeholder(&mut self, placeholder: Placeholder) {
+        self.error_placeholder = Some(placeholder);
+    }
+
+    /// Return the placeholders
+    fn placeholders(&self) -> Vec<&Placeholder> {
+        vec![
+            &self.empty_placeholder,
+            &self.loading_placeholder,
+            &self.error_placeholder,
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+
+    /// Return the placeholders, to change them
+    fn placeholders_mut(&mut self) -> Vec<&mut Placeholder> {
+        vec![
+            &mut self.empty_placeholder,
+            &mut self.loading_placeholder,
+            &mut self.error_placeholder,
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+
+    /// Set the layout options
+    pub fn set_layout(&mut self, layout: LayoutOptions) {
+        self.layout = layout;
+    }
+
+    /// Set the style
+    pub fn set_style(&mut self, style: Style) {
+        self.style = style;
+    }
+
+    /// Set the stretched flag
+    pub fn set_stretched(&mut self, stretched: bool) {
+        self.layout.set_stretched(stretched);
+    }
+}
+
+/// # The listener of a ListView
+pub trait ListViewListener {
+    /// Function triggered on update event
+    fn on_update(&self, state: &mut ListViewState);
+
+    /// Function triggered on change event
+    fn on_change(&self, state: &ListViewState);
+
+    /// Function triggered when the bottom of an infinite list is reached
+    fn on_load_more(&self, state: &ListViewState);
+
+    /// Function triggered when a refreshable list is pulled down
+    fn on_refresh(&self, _state: &ListViewState) {}
+
+    /// Function triggered when an item is double clicked, the item being
+    /// the selected one
+    fn on_double_click(&self, _state: &ListViewState) {}
+
+    /// Function triggered when the selection mode is turned on or off, or
+    /// when an item is checked or unchecked
+    fn on_selection(&self, _state: &ListViewState) {}
+
+    /// Function triggered when a bulk action is chosen, the items it
+    /// applies to being the checked ones
+    fn on_action(&self, _action: &str, _state: &ListViewState) {}
+}
+
+/// # A scrollable list of items
+///
+/// In infinite mode, reaching the bottom of the list triggers the
+/// `on_load_more` function of the listener. The new items have to be appended
+/// through the state on the next update. As the list element is kept between
+/// renders, the scroll position is preserved.
+///
+/// In refreshable mode, pulling the top of the list down on a touch screen
+/// triggers the `on_refresh` function of the listener. A spinner is displayed
+/// while the refreshing flag is set. The flag is reset before each update, so
+/// a listener loading data asynchronously has to set it again until the data
+/// arrives.
+///
+/// In selectable mode, a toolbar above the items turns the selection mode on
+/// and off. While it is on, clicking an item checks or unchecks it, and the
+/// toolbar shows the number of checked items and the bulk actions, which
+/// trigger the `on_action` function of the listener with the checked items
+/// in the state. The selection mode is turned off once the action is
+/// handled. The texts of the toolbar can be translated with the
+/// `listview.select`, `listview.selected`, `listview.select_all` and
+/// `listview.done` keys (see `i18n`).
+///
+/// Placeholders, texts or widgets, are displayed in place of the items while
+/// there is none, while the first ones are loading, and while the state has
+/// an error. The loading and error placeholders are also displayed while the
+/// ListView observes a future and after it failed (see `observe`). Without
+/// placeholder, an empty ListView is blank, a loading one displays
+/// `Loading`, which can be translated with the `listview.loading` key, and a
+/// failed one its error.
+///
+/// ## Fields
+///
+/// ```text
+/// name: String
+/// state: ListViewState
+/// listener: Option<Box<dyn ListViewListener>>
+/// ```
+///
+/// ## Default values
+///
+/// ```text
+/// name: name.to_string()
+/// state:
+///     items: vec![]
+///     selected: None
+///     infinite: false
+///     has_more: true
+///     loading: false
+///     refreshable: false
+///     refreshing: false
+///     selectable: false
+///     selecting: false
+///     checked: vec![]
+///     actions: vec![]
+///     error: None
+///     empty_placeholder: None
+///     loading_placeholder: None
+///     error_placeholder: None
+///     layout: LayoutOptions::new()
+///     style: Style::new()
+/// listener: None
+/// ```
+///
+/// ## Example
+///
+/// ```
+/// use std::cell::RefCell;
+/// use std::rc::Rc;
+///
+/// use neutrino::widgets::listview::{ListView, ListViewListener, ListViewState};
+/// use neutrino::widgets::widget::Placeholder;
+/// use neutrino::utils::theme::Theme;
+/// use neutrino::{App, Window};
+///
+///
+/// struct Feed {
+///     posts: Vec<String>,
+/// }
+///
+/// impl Feed {
+///     fn new() -> Self {
+///         Self { posts: vec![] }
+///     }
+///
+///     fn posts(&self) -> Vec<&str> {
+///         self.posts.iter().map(|p| p.as_str()).collect()
+///     }
+///
+///     fn fetch(&mut self) {
+///         let start = self.posts.len();
+///         for i in start..start + 20 {
+///             self.posts.push(format!("Post {}", i));
+///         }
+///     }
+///
+///     fn archive(&mut self, indexes: &[u32]) {
+///         let mut index = 0;
+///         self.posts.retain(|_| {
+///             index += 1;
+///             !indexes.contains(&(index - 1))
+///         });
+///     }
+/// }
+///
+///
+/// struct MyListViewListener {
+///     feed: Rc<RefCell<Feed>>,
+/// }
+///
+/// impl MyListViewListener {
+///    pub fn new(feed: Rc<RefCell<Feed>>) -> Self {
+///        Self { feed }
+///    }
+/// }
+///
+/// impl ListViewListener for MyListViewListener {
+///     fn on_update(&self, state: &mut ListViewState) {
+///         state.set_items(self.feed.borrow().posts());
+///     }
+///
+///     fn on_change(&self, _state: &ListViewState) {}
+///
+///     fn on_load_more(&self, _state: &ListViewState) {
+///         self.feed.borrow_mut().fetch();
+///     }
+///
+///     fn on_action(&self, action: &str, state: &ListViewState) {
+///         if action == "archive" {
+///             self.feed.borrow_mut().archive(state.checked());
+///         }
+///     }
+/// }
+///
+///
+/// fn main() {
+///     let feed = Rc::new(RefCell::new(Feed::new()));
+///     feed.borrow_mut().fetch();
+///
+///     let my_listener = MyListViewListener::new(Rc::clone(&feed));
+///
+///     let mut my_listview = ListView::new("my_listview");
+///     my_listview.set_infinite();
+///     my_listview.add_action("archive", "Archive");
+///     my_listview.set_empty_placeholder(Placeholder::Text(
+///         "No post yet".to_string(),
+///     ));
+///     my_listview.set_listener(Box::new(my_listener));
+/// }
+/// ```
+pub struct ListView {
+    name: String,
+    state: ListViewState,
+    listener: Option<Box<dyn ListViewListener>>,
+}
+
+impl ListView {
+    /// Create a ListView
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            state: ListViewState {
+                items: vec![],
+                selected: None,
+                infinite: false,
+                has_more: true,
+                loading: false,
+                refreshable: false,
+                refreshing: false,
+                selectable: false,
+                selecting: false,
+                checked: vec![],
+                actions: vec![],
+                error: None,
+                empty_placeholder: None,
+                loading_placeholder: None,
+                error_placeholder: None,
+                layout: LayoutOptions::new(),
+                style: Style::new(),
+            },
+            listener: None,
+        }
+    }
+
+    /// Get the state
+    pub fn state(&self) -> &ListViewState {
+        &self.state
+    }
+
+    /// Get the state, to change it from outside of the listener
+    pub fn state_mut(&mut self) -> &mut ListViewState {
+        &mut self.state
+    }
+
+    /// Set the items
+    pub fn set_items(&mut self, items: Vec<&str>) {
+        self.state.set_items(items);
+    }
+
+    /// Set the selected index
+    pub fn set_selected(&mut self, selected: u32) {
+        self.state.set_selected(Some(selected));
+    }
+
+    /// Set the infinite flag to true
+    pub fn set_infinite(&mut self) {
+        self.state.set_infinite(true);
+    }
+
+    /// Set the refreshable flag to true
+    pub fn set_refreshable(&mut self) {
+        self.state.set_refreshable(true);
+    }
+
+    /// Set the selectable flag to true
+    pub fn set_selectable(&mut self) {
+        self.state.set_selectable(true);
+    }
+
+    /// Add a bulk action, with the action given to the listener and its
+    /// text, and set the selectable flag to true
+    pub fn add_action(&mut self, action: &str, text: &str) {
+        self.state.set_selectable(true);
+        self.state.add_action(action, text);
+    }
+
+    /// Set the placeholder displayed while there is no item
+    pub fn set_empty_placeholder(&mut self, placeholder: Placeholder) {
+        self.state.set_empty_placeholder(placeholder);
+    }
+
+    /// Set the placeholder displayed while the first items are loading
+    pub fn set_loading_placeholder(&mut self, placeholder: Placeholder) {
+        self.state.set_loading_placeholder(placeholder);
+    }
+
+    /// Set the placeholder displayed in place of the items on error
+    pub fn set_error_placeholder(&mut self, placeholder: Placeholder) {
+        self.state.set_error_placeholder(placeholder);
+    }
+
+    /// Set the layout options
+    pub fn set_layout(&mut self, layout: LayoutOptions) {
+        self.state.set_layout(layout);
+    }
+
+    /// Set the style
+    pub fn set_style(&mut self, style: Style) {
+        self.state.set_style(style);
+    }
+
+    /// Set the stretched flag to true
+    pub fn set_stretched(&mut self) {
+        self.state.set_stretched(true);
+    }
+
+    /// Set the listener
+    pub fn set_listener(&mut self, listener: Box<dyn ListViewListener>) {
+        self.listener = Some(listener);
+    }
+
+    /// Return the HTML of a button of the toolbar sending the given value
+    fn button(&self, text: &str, value: &str, disabled: bool) -> String {
+        let handlers = if disabled {
+            "".to_string()
+        } else {
+            format!(
+                r#"onmousedown="{}" onkeydown="{}""#,
+                Event::change_js(&self.name, &format!("'{}'", value)),
+                Event::keys_js(
+                    &self.name,
+                    &[("Enter", value.to_string()), (" ", value.to_string())]
+                )
+            )
+        };
+        format!(
+            r#"<div class="button listview-button {}" role="button" tabindex="{}" aria-disabled="{}" {}>{}</div>"#,
+            if disabled { "disabled" } else { "" },
+            if disabled { -1 } else { 0 },
+            disabled,
+            handlers,
+            escape(text)
+        )
+    }
+
+    /// Return the HTML of the toolbar of a selectable list
+    fn toolbar(&self) -> String {
+        if !self.state.selecting() {
+            return format!(
+                r#"<div class="listview-toolbar">{}</div>"#,
+                self.button(
+                    &i18n::translate_or("listview.select", "Select", &[]),
+                    "selection",
+                    false
+                )
+            );
+        }
+        let count = self.state.checked().len().to_string();
+        let mut s = format!(
+            r#"<div class="listview-toolbar"><span class="listview-count" role="status" aria-live="polite">{}</span>"#,
+            escape(&i18n::translate_or(
+                "listview.selected",
+                "{count, plural, =0 {No item selected} one {# item selected} other {# items selected}}",
+                &[("count", count.as_str())],
+            ))
+        );
+        s.push_str(&self.button(
+            &i18n::translate_or("listview.select_all", "Select all", &[]),
+            "all",
+            self.state.items().is_empty(),
+        ));
+        for (i, (_, text)) in self.state.actions().iter().enumerate() {
+            s.push_str(&self.button(
+                text,
+                &format!("action:{}", i),
+                self.state.checked().is_empty(),
+            ));
+        }
+        s.push_str(&self.button(
+            &i18n::translate_or("listview.done", "Done", &[]),
+            "selection",
+            false,
+        ));
+        s.push_str("</div>");
+        s
+    }
+
+    /// Return the HTML of the list, loading or failed with the given error
+    fn render(&self, loading: bool, error: Option<&str>) -> String {
+        let layout = self.state.layout();
+        let onscroll = if self.state.infinite() && self.state.has_more() {
+            Event::load_more_js(&self.name)
+        } else {
+            "".to_string()
+        };
+        let (ontouchstart, ontouchmove, ontouchend) =
+            if self.state.refreshable() {
+                (
+                    Event::pull_start_js(),
+                    Event::pull_move_js(),
+                    Event::pull_end_js(&self.name),
+                )
+            } else {
+                ("".to_string(), "".to_string(), "".to_string())
+            };
+        let selecting = if self.state.selecting() {
+            "selecting"
+        } else {
+            ""
+        };
+        let mut s = format!(
+            r#"<div id="{}" class="listview {} {}" style="{}{}" onscroll="{}" ontouchstart="{}" ontouchmove="{}" ontouchend="{}" data-loading="{}">"#,
+            self.name,
+            selecting,
+            layout.class(),
+            layout.style(),
+            self.state.style().css(),
+            onscroll,
+            ontouchstart,
+            ontouchmove,
+            ontouchend,
+            loading,
+        );
+        if self.state.refreshable() {
+            let refreshing = if self.state.refreshing() {
+                "refreshing"
+            } else {
+                ""
+            };
+            s.push_str(&format!(
+                r#"<div class="listview-refresh {}"><div class="spinner"></div></div>"#,
+                refreshing
+            ));
+        }
+        if self.state.selectable() {
+            s.push_str(&self.toolbar());
+        }
+        let placeholder = match error {
+            Some(error) => Some((
+                "error",
+                match self.state.error_placeholder() {
+                    Some(placeholder) => placeholder.eval(),
+                    None => Placeholder::Text(error.to_string()).eval(),
+                },
+            )),
+            None if self.state.items().is_empty() && loading => Some((
+                "loading",
+                match self.state.loading_placeholder() {
+                    Some(placeholder) => placeholder.eval(),
+                    None => format!(
+                        r#"<div class="spinner" aria-hidden="true"></div>{}"#,
+                        Placeholder::Text(i18n::translate_or(
+                            "listview.loading",
+                            "Loading",
+                            &[]
+                        ))
+                        .eval()
+                    ),
+                },
+            )),
+            None if self.state.items().is_empty() => self
+                .state
+                .empty_placeholder()
+                .map(|placeholder| ("empty", placeholder.eval())),
+            None => None,
+        };
+        if let Some((kind, content)) = placeholder {
+            s.push_str(&format!(
+                r#"<div class="listview-placeholder {}" role="{}">{}</div></div>"#,
+                kind,
+                if kind == "error" { "alert" } else { "status" },
+                content
+            ));
+            return s;
+        }
+        for (i, item) in self.state.items().iter().enumerate() {
+            if self.state.selecting() {
+                let checked = self.state.is_checked(i as u32);
+                s.push_str(&format!(
+                    r#"<div class="listview-item {}" onmousedown="{}"><span class="listview-check {}" role="checkbox" aria-checked="{}"></span><span>{}</span></div>"#,
+                    if checked { "checked" } else { "" },
+                    Event::change_js(&self.name, &format!("'check:{}'", i)),
+                    if checked { "checked" } else { "" },
+                    checked,
+                    escape(item)
+                ));
+                continue;
+            }
+            let selected = if self.state.selected() == Some(i as u32) {
+                "selected"
+            } else {
+                ""
+            };
+            s.push_str(&format!(
+                r#"<div class="listview-item {}" onmousedown="{}" ondblclick="{}">{}</div>"#,
+                selected,
+                Event::change_js(&self.name, &format!("'{}'", i)),
+                Event::double_click_js(&self.name),
+                escape(item)
+            ));
+        }
+        if loading {
+            s.push_str(r#"<div class="listview-loading">Loading...</div>"#);
+        }
+        s.push_str("</div>");
+        s
+    }
+
+    /// Trigger the on_selection function of the listener
+    fn on_selection(&self) {
+        match &self.listener {
+            None => (),
+            Some(listener) => {
+                listener.on_selection(&self.state);
+            }
+        }
+    }
+}
+
+impl Widget for ListView {
+    fn eval(&self) -> String {
+        self.render(self.state.loading(), self.state.error())
+    }
+
+    fn name(&self) -> String {
+        self.name.to_string()
+    }
+
+    fn trigger(&mut self, event: &Event) {
+        match event {
+            Event::Update => self.on_update(),
+            Event::Change { source, value } => {
+                if source == &self.name {
+                    self.on_change(value);
+                }
+            }
+            Event::DoubleClick { source, .. } => {
+                if source == &self.name && self.state.selected().is_some() {
+                    match &self.listener {
+                        None => (),
+                        Some(listener) => {
+                            listener.on_double_click(&self.state);
+                        }
+                    }
+                }
+            }
+            _ => (),
+        }
+        for placeholder in self.state.placeholders_mut() {
+            if let Some(child) = placeholder.widget_mut() {
+                if widget::reaches(child, event) {
+                    widget::trigger(child, event);
+                }
+            }
+        }
+    }
+
+    fn on_update(&mut self) {
+        self.state.set_refreshing(false);
+        match &self.listener {
+            None => (),
+            Some(listener) => {
+                listener.on_update(&mut self.state);
+            }
+        }
+    }
+
+    fn on_change(&mut self, value: &str) {
+        if value == "selection" {
+            if !self.state.selectable() {
+                return;
+            }
+            self.state.set_selecting(!self.state.selecting());
+            self.on_selection();
+        } else if value == "all" {
+            if !self.state.selecting() {
+                return;
+            }
+            let length = self.state.items().len() as u32;
+            if self.state.checked().len() as u32 == length {
+                self.state.set_checked(vec![]);
+            } else {
+                self.state.set_checked((0..length).collect());
+            }
+            self.on_selection();
+        } else if let Some(index) = value.strip_prefix("check:") {
+            let index = match index.parse::<u32>() {
+                Ok(index) if self.state.selecting() => index,
+                _ => return,
+            };
+            let mut checked = self.state.checked().clone();
+            match checked.iter().position(|i| *i == index) {
+                Some(position) => {
+                    checked.remove(position);
+                }
+                None => checked.push(index),
+            }
+            self.state.set_checked(checked);
+            self.on_selection();
+        } else if let Some(index) = value.strip_prefix("action:") {
+            let action = index
+                .parse::<usize>()
+                .ok()
+                .and_then(|index| self.state.actions().get(index))
+                .map(|(action, _)| action.to_string());
+            let action = match action {
+                Some(action) if !self.state.checked().is_empty() => action,
+                _ => return,
+            };
+            if let Some(listener) = &self.listener {
+                listener.on_action(&action, &self.state);
+            }
+            self.state.set_selecting(false);
+            self.on_selection();
+        } else if value == "refresh" {
+            self.state.set_refreshing(true);
+            match &self.listener {
+                None => (),
+                Some(listener) => {
+                    listener.on_refresh(&self.state);
+                }
+            }
+        } else if value == "load-more" {
+            if self.state.loading() || !self.state.has_more() {
+                return;
+            }
+            self.state.set_loading(true);
+            match &self.listener {
+                None => (),
+                Some(listener) => {
+                    listener.on_load_more(&self.state);
+                }
+            }
+        } else if let Ok(selected) = value.parse::<u32>() {
+            self.state.set_selected(Some(selected));
+            match &self.listener {
+                None => (),
+                Some(listener) => {
+                    listener.on_change(&self.state);
+                }
+            }
+        }
+    }
+
+    fn children(&self) -> Vec<&(dyn Widget + 'static)> {
+        self.state
+            .placeholders()
+            .into_iter()
+            .filter_map(Placeholder::widget)
+            .collect()
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut (dyn Widget + 'static)> {
+        self.state
+            .placeholders_mut()
+            .into_iter()
+            .filter_map(Placeholder::widget_mut)
+            .collect()
+    }
+
+    fn status_placeholder(&self, status: &Status) -> Option<String> {
+        Some(match status {
+            Status::Loading => self.render(true, None),
+            Status::Failed(error) => {
+                self.render(self.state.loading(), Some(error))
+            }
+        })
+    }
+}
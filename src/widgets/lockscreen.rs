@@ -0,0 +1,463 @@
+use crate::utils::event::{Event, Key};
+use crate::utils::html::escape;
+use crate::widgets::widget::Widget;
+
+/// # The secret asked by a LockScreen
+///
+/// A PIN only accepts digits.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LockPrompt {
+    Pin,
+    Password,
+}
+
+/// # The state of a LockScreen
+///
+/// ## Fields
+///
+/// ```text
+/// title: String
+/// message: String
+/// prompt: LockPrompt
+/// typed: String
+/// unlock: String
+/// failure: String
+/// attempts: u32
+/// idle: u32
+/// shortcut: Option<Key>
+/// locked: bool
+/// ```
+pub struct LockScreenState {
+    title: String,
+    message: String,
+    prompt: LockPrompt,
+    typed: String,
+    unlock: String,
+    failure: String,
+    attempts: u32,
+    idle: u32,
+    shortcut: Option<Key>,
+    locked: bool,
+}
+
+impl LockScreenState {
+    /// Get the title
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    /// Get the message
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Get the prompt
+    pub fn prompt(&self) -> LockPrompt {
+        self.prompt
+    }
+
+    /// Get the secret typed by the user
+    pub fn typed(&self) -> &str {
+        &self.typed
+    }
+
+    /// Get the text of the unlock button
+    pub fn unlock(&self) -> &str {
+        &self.unlock
+    }
+
+    /// Get the message displayed after a failed attempt
+    pub fn failure(&self) -> &str {
+        &self.failure
+    }
+
+    /// Get the number of failed attempts since the screen has been locked
+    pub fn attempts(&self) -> u32 {
+        self.attempts
+    }
+
+    /// Get the idle timeout in milliseconds
+    pub fn idle(&self) -> u32 {
+        self.idle
+    }
+
+    /// Get the shortcut
+    pub fn shortcut(&self) -> Option<Key> {
+        self.shortcut
+    }
+
+    /// Get the locked flag
+    pub fn locked(&self) -> bool {
+        self.locked
+    }
+
+    /// Set the title
+    pub fn set_title(&mut self, title: &str) {
+        self.title = title.to_string();
+    }
+
+    /// Set the message
+    pub fn set_message(&mut self, message: &str) {
+        self.message = message.to_string();
+    }
+
+    /// Set the prompt
+    pub fn set_prompt(&mut self, prompt: LockPrompt) {
+        self.prompt = prompt;
+    }
+
+    /// Set the text of the unlock button
+    pub fn set_unlock(&mut self, unlock: &str) {
+        self.unlock = unlock.to_string();
+    }
+
+    /// Set the message displayed after a failed attempt
+    pub fn set_failure(&mut self, failure: &str) {
+        self.failure = failure.to_string();
+    }
+
+    /// Set the idle timeout in milliseconds, 0 disabling it
+    pub fn set_idle(&mut self, idle: u32) {
+        self.idle = idle;
+    }
+
+    /// Set the shortcut
+    pub fn set_shortcut(&mut self, shortcut: Option<Key>) {
+        self.shortcut = shortcut;
+    }
+
+    /// Set the locked flag, clearing the typed secret and the failed attempts
+    /// when the screen locks
+    pub fn set_locked(&mut self, locked: bool) {
+        if locked && !self.locked {
+            self.typed = "".to_string();
+            self.attempts = 0;
+        }
+        self.locked = locked;
+    }
+
+    /// Set the typed secret, keeping only the digits of a PIN
+    fn set_typed(&mut self, typed: &str) {
+        self.typed = match self.prompt {
+            LockPrompt::Pin => {
+                typed.chars().filter(char::is_ascii_digit).collect()
+            }
+            LockPrompt::Password => typed.to_string(),
+        };
+    }
+}
+
+/// # The listener of a LockScreen
+pub trait LockScreenListener {
+    /// Function triggered on update event
+    fn on_update(&self, state: &mut LockScreenState);
+
+    /// Function triggered when the user submits the typed secret. Return true
+    /// if the secret is right, unlocking the screen.
+    fn on_submit(&self, state: &LockScreenState) -> bool;
+
+    /// Function triggered when the screen has been locked by the idle timeout
+    /// or by the shortcut
+    fn on_lock(&self, _state: &LockScreenState) {}
+
+    /// Function triggered when the screen has been unlocked
+    fn on_unlock(&self, _state: &LockScreenState) {}
+}
+
+/// # A screen locking the application
+///
+/// When it is locked, the LockScreen covers the whole window, blurring the
+/// widgets underneath, and asks for a PIN or a password. The widgets under
+/// it can neither be clicked nor receive the keyboard, and the `Ctrl + Key`
+/// events are not sent.
+///
+/// The screen is locked by the listener through the state, after the idle
+/// timeout during which the user has neither moved the mouse nor pressed a
+/// key, or by the `Ctrl + Key` shortcut. The verification of the secret is
+/// delegated to the listener, so it can be checked against any backend.
+///
+/// The listener should keep its model in sync on lock and on unlock, so the
+/// screen is not unlocked or locked again by the next update.
+///
+/// ## Fields
+///
+/// ```text
+/// name: String
+/// state: LockScreenState
+/// listener: Option<Box<dyn LockScreenListener>>
+/// ```
+///
+/// ## Default values
+///
+/// ```text
+/// name: name.to_string()
+/// state:
+///     title: "Locked".to_string()
+///     message: "".to_string()
+///     prompt: LockPrompt::Password
+///     typed: "".to_string()
+///     unlock: "Unlock".to_string()
+///     failure: "Wrong password".to_string()
+///     attempts: 0
+///     idle: 0
+///     shortcut: None
+///     locked: false
+/// listener: None
+/// ```
+///
+/// ## Example
+///
+/// ```
+/// use std::cell::RefCell;
+/// use std::rc::Rc;
+///
+/// use neutrino::utils::event::Key;
+/// use neutrino::widgets::lockscreen::{
+///     LockPrompt, LockScreen, LockScreenListener, LockScreenState,
+/// };
+/// use neutrino::{App, Window};
+///
+///
+/// struct Session {
+///     pin: String,
+///     locked: bool,
+/// }
+///
+///
+/// struct MyLockScreenListener {
+///     session: Rc<RefCell<Session>>,
+/// }
+///
+/// impl LockScreenListener for MyLockScreenListener {
+///     fn on_update(&self, state: &mut LockScreenState) {
+///         state.set_locked(self.session.borrow().locked);
+///     }
+///
+///     fn on_submit(&self, state: &LockScreenState) -> bool {
+///         state.typed() == self.session.borrow().pin
+///     }
+///
+///     fn on_lock(&self, _state: &LockScreenState) {
+///         self.session.borrow_mut().locked = true;
+///     }
+///
+///     fn on_unlock(&self, _state: &LockScreenState) {
+///         self.session.borrow_mut().locked = false;
+///     }
+/// }
+///
+///
+/// fn main() {
+///     let session = Rc::new(RefCell::new(Session {
+///         pin: "1234".to_string(),
+///         locked: false,
+///     }));
+///
+///     let mut my_lockscreen = LockScreen::new("my_lockscreen");
+///     my_lockscreen.set_prompt(LockPrompt::Pin);
+///     my_lockscreen.set_failure("Wrong PIN");
+///     my_lockscreen.set_idle(5 * 60 * 1000);
+///     my_lockscreen.set_shortcut(Key::L);
+///     my_lockscreen.set_listener(Box::new(MyLockScreenListener { session }));
+/// }
+/// ```
+pub struct LockScreen {
+    name: String,
+    state: LockScreenState,
+    listener: Option<Box<dyn LockScreenListener>>,
+}
+
+impl LockScreen {
+    /// Create a LockScreen
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            state: LockScreenState {
+                title: "Locked".to_string(),
+                message: "".to_string(),
+                prompt: LockPrompt::Password,
+                typed: "".to_string(),
+                unlock: "Unlock".to_string(),
+                failure: "Wrong password".to_string(),
+                attempts: 0,
+                idle: 0,
+                shortcut: None,
+                locked: false,
+            },
+            listener: None,
+        }
+    }
+
+    /// Get the state
+    pub fn state(&self) -> &LockScreenState {
+        &self.state
+    }
+
+    /// Get the state, to change it from outside of the listener
+    pub fn state_mut(&mut self) -> &mut LockScreenState {
+        &mut self.state
+    }
+
+    /// Set the title
+    pub fn set_title(&mut self, title: &str) {
+        self.state.set_title(title);
+    }
+
+    /// Set the message
+    pub fn set_message(&mut self, message: &str) {
+        self.state.set_message(message);
+    }
+
+    /// Set the prompt
+    pub fn set_prompt(&mut self, prompt: LockPrompt) {
+        self.state.set_prompt(prompt);
+    }
+
+    /// Set the text of the unlock button
+    pub fn set_unlock(&mut self, unlock: &str) {
+        self.state.set_unlock(unlock);
+    }
+
+    /// Set the message displayed after a failed attempt
+    pub fn set_failure(&mut self, failure: &str) {
+        self.state.set_failure(failure);
+    }
+
+    /// Set the idle timeout in milliseconds
+    pub fn set_idle(&mut self, idle: u32) {
+        self.state.set_idle(idle);
+    }
+
+    /// Set the shortcut
+    pub fn set_shortcut(&mut self, shortcut: Key) {
+        self.state.set_shortcut(Some(shortcut));
+    }
+
+    /// Set the locked flag to true
+    pub fn set_locked(&mut self) {
+        self.state.set_locked(true);
+    }
+
+    /// Set the listener
+    pub fn set_listener(&mut self, listener: Box<dyn LockScreenListener>) {
+        self.listener = Some(listener);
+    }
+
+    fn on_lock(&mut self) {
+        if self.state.locked() {
+            return;
+        }
+        self.state.set_locked(true);
+        match &self.listener {
+            None => (),
+            Some(listener) => {
+                listener.on_lock(&self.state);
+            }
+        }
+    }
+
+    fn on_submit(&mut self) {
+        if !self.state.locked() {
+            return;
+        }
+        let unlocked = match &self.listener {
+            None => false,
+            Some(listener) => listener.on_submit(&self.state),
+        };
+        self.state.typed = "".to_string();
+        if !unlocked {
+            self.state.attempts += 1;
+            return;
+        }
+        self.state.set_locked(false);
+        match &self.listener {
+            None => (),
+            Some(listener) => {
+                listener.on_unlock(&self.state);
+            }
+        }
+    }
+}
+
+impl Widget for LockScreen {
+    fn eval(&self) -> String {
+        if !self.state.locked() {
+            let idle = if self.state.idle() > 0 {
+                format!(r#"data-idle="{}""#, self.state.idle())
+            } else {
+                "".to_string()
+            };
+            return format!(
+                r#"<div id="{}" class="lockscreen" {}></div>"#,
+                self.name, idle
+            );
+        }
+        let inputmode = match self.state.prompt() {
+            LockPrompt::Pin => r#"inputmode="numeric""#,
+            LockPrompt::Password => "",
+        };
+        let failure = if self.state.attempts() > 0 {
+            format!(
+                r#"<div class="lockscreen-failure">{}</div>"#,
+                escape(self.state.failure())
+            )
+        } else {
+            "".to_string()
+        };
+        format!(
+            r#"<div id="{}" class="lockscreen locked"><div class="lockscreen-dialog"><div class="lockscreen-title">{}</div><div class="lockscreen-message">{}</div><input type="password" {} value="{}" autocomplete="off" oninput="{}" onkeydown="{}" />{}<div class="lockscreen-unlock" onmousedown="{}">{}</div></div></div>"#,
+            self.name,
+            escape(self.state.title()),
+            escape(self.state.message()),
+            inputmode,
+            escape(self.state.typed()),
+            Event::input_js(&self.name, "'text:' + value"),
+            Event::enter_js(&self.name, "'submit'"),
+            failure,
+            Event::change_js(&self.name, "'submit'"),
+            escape(self.state.unlock()),
+        )
+    }
+
+    fn name(&self) -> String {
+        self.name.to_string()
+    }
+
+    fn trigger(&mut self, event: &Event) {
+        match event {
+            Event::Update => self.on_update(),
+            Event::Change { source, value } => {
+                if source == &self.name {
+                    self.on_change(value);
+                }
+            }
+            Event::Key { key } => {
+                if self.state.shortcut() == Some(*key) {
+                    self.on_lock();
+                }
+            }
+            _ => (),
+        }
+    }
+
+    fn on_update(&mut self) {
+        match &self.listener {
+            None => (),
+            Some(listener) => {
+                listener.on_update(&mut self.state);
+            }
+        }
+    }
+
+    fn on_change(&mut self, value: &str) {
+        if let Some(text) = value.strip_prefix("text:") {
+            self.state.set_typed(text);
+            return;
+        }
+        match value {
+            "idle" if self.state.idle() > 0 => self.on_lock(),
+            "submit" => self.on_submit(),
+            _ => (),
+        }
+    }
+}
@@ -1,4 +1,8 @@
-use crate::utils::event::Event;
+use crate::utils::event::{self, Event, Key};
+use crate::utils::html::escape;
+use crate::utils::i18n;
+use crate::utils::icon::Icon;
+use crate::utils::pixmap::Pixmap;
 
 /// # The state of a MenuBar
 ///
@@ -6,11 +10,20 @@ use crate::utils::event::Event;
 ///
 /// ```text
 /// selected_item: Option<u32>
-/// selected_function: Option<u32
+/// selected_function: Option<u32>
+/// selected_path: Vec<u32>
+/// checked: Vec<Vec<u32>>
+/// disabled: Vec<Vec<u32>>
 /// ```
+///
+/// A function is identified by its path: the index of its MenuItem, then its
+/// index in each nested submenu. Separators are counted in the indices.
 pub struct MenuBarState {
     selected_item: Option<u32>,
     selected_function: Option<u32>,
+    selected_path: Vec<u32>,
+    checked: Vec<Vec<u32>>,
+    disabled: Vec<Vec<u32>>,
 }
 
 impl MenuBarState {
@@ -24,6 +37,25 @@ impl MenuBarState {
         self.selected_function
     }
 
+    /// Get the path of the selected function
+    pub fn selected_path(&self) -> &Vec<u32> {
+        &self.selected_path
+    }
+
+    /// Return true if the function at the given path is checked
+    pub fn is_checked(&self, path: &[u32]) -> bool {
+        self.checked
+            .iter()
+            .any(|checked| checked.as_slice() == path)
+    }
+
+    /// Return true if the function at the given path is disabled
+    pub fn is_disabled(&self, path: &[u32]) -> bool {
+        self.disabled
+            .iter()
+            .any(|disabled| disabled.as_slice() == path)
+    }
+
     /// Set selected item index
     pub fn set_selected_item(&mut self, selected_item: Option<u32>) {
         self.selected_item = selected_item;
@@ -33,16 +65,47 @@ impl MenuBarState {
     pub fn set_selected_function(&mut self, selected_function: Option<u32>) {
         self.selected_function = selected_function;
     }
+
+    /// Set the path of the selected function
+    pub fn set_selected_path(&mut self, selected_path: Vec<u32>) {
+        self.selected_path = selected_path;
+    }
+
+    /// Set the checked flag of the function at the given path
+    pub fn set_checked(&mut self, path: &[u32], checked: bool) {
+        self.checked.retain(|p| p.as_slice() != path);
+        if checked {
+            self.checked.push(path.to_vec());
+        }
+    }
+
+    /// Set the disabled flag of the function at the given path
+    pub fn set_disabled(&mut self, path: &[u32], disabled: bool) {
+        self.disabled.retain(|p| p.as_slice() != path);
+        if disabled {
+            self.disabled.push(path.to_vec());
+        }
+    }
 }
 
 /// # The listener of a MenuBar
 pub trait MenuBarListener {
     /// Function triggered on change event
     fn on_change(&self, state: &MenuBarState);
+
+    /// Function triggered on update event
+    fn on_update(&self, _state: &mut MenuBarState) {}
 }
 
 /// # A MenuBar
 ///
+/// A MenuFunction can be a check item, whose checked flag is switched when it
+/// is clicked, or a radio item, checking it and unchecking the radio items of
+/// the same group in its menu. It can also open a submenu, be disabled, or
+/// be triggered from the keyboard by its accelerator (`Ctrl + Key`), which
+/// is displayed next to it. The checked and disabled flags are kept in the
+/// state, so the listener can control them on update.
+///
 /// ## Fields
 ///
 /// ```text
@@ -58,6 +121,9 @@ pub trait MenuBarListener {
 /// state:
 ///     selected_item: None
 ///     selected_function: None
+///     selected_path: vec![]
+///     checked: vec![]
+///     disabled: vec![]
 /// listener: None
 /// ```
 ///
@@ -67,6 +133,7 @@ pub trait MenuBarListener {
 /// use std::cell::RefCell;
 /// use std::rc::Rc;
 ///
+/// use neutrino::utils::event::Key;
 /// use neutrino::widgets::menubar::{MenuBar, MenuBarState, MenuBarListener, MenuItem, MenuFunction};
 /// use neutrino::utils::theme::Theme;
 /// use neutrino::{App, Window};
@@ -117,10 +184,16 @@ pub trait MenuBarListener {
 ///     let document_list = Rc::new(RefCell::new(DocumentList::new()));
 ///     
 ///     let mut new = MenuFunction::new("New");
-///     new.set_shortcut("Ctrl-N");
-///     
+///     new.set_accelerator(Key::N);
+///
+///     let mut autosave = MenuFunction::new("Autosave");
+///     autosave.set_checkable();
+///     autosave.set_checked();
+///
 ///     let mut file = MenuItem::new("File");
 ///     file.add(new);
+///     file.add_separator();
+///     file.add(autosave);
 ///     
 ///     let my_menubarlistener = MyMenuBarListener::new(Rc::clone(&document_list));
 ///     
@@ -143,6 +216,9 @@ impl MenuBar {
             state: MenuBarState {
                 selected_item: None,
                 selected_function: None,
+                selected_path: vec![],
+                checked: vec![],
+                disabled: vec![],
             },
             listener: None,
         }
@@ -155,18 +231,22 @@ impl MenuBar {
 
     /// Add a MenuItem
     pub fn add(&mut self, item: MenuItem) {
+        let index = self.items.len() as u32;
+        for (i, function) in item.functions.iter().enumerate() {
+            function.init_state(&[index, i as u32], &mut self.state);
+        }
         self.items.push(item);
     }
 
     /// Return the HTML representation of the widget
     pub fn eval(&self) -> String {
-        let mut s = r#"<div class="menubar">"#.to_string();
+        let mut s = r#"<div class="menubar" role="menubar">"#.to_string();
         for (i, item) in self.items.iter().enumerate() {
             let selected_item = match self.state.selected_item {
                 None => false,
                 Some(selected_item) => selected_item == i as u32,
             };
-            s.push_str(&item.eval(i, selected_item));
+            s.push_str(&item.eval(i, selected_item, &self.state));
         }
         s.push_str(r#"</div>"#);
         s
@@ -175,7 +255,12 @@ impl MenuBar {
     /// Trigger functions depending on the event
     pub fn trigger(&mut self, event: &Event) {
         match event {
-            Event::Update => (),
+            Event::Update => match &self.listener {
+                None => (),
+                Some(listener) => {
+                    listener.on_update(&mut self.state);
+                }
+            },
             Event::Change { source, value } => {
                 if *source == "menuitem" {
                     self.on_item_change(value);
@@ -185,15 +270,30 @@ impl MenuBar {
                     self.state.set_selected_item(None);
                 }
             }
+            Event::Hover { .. } => (),
+            Event::Key { key } => {
+                self.on_key(*key);
+                self.state.set_selected_item(None);
+            }
             _ => self.state.set_selected_item(None),
         }
     }
 
     /// Function triggered on MenuItem change event
     fn on_item_change(&mut self, value: &str) {
-        let values = value.split(';').collect::<Vec<&str>>();
-        let e = values[0];
-        let index = values[1].parse::<u32>().unwrap();
+        let parsed = value
+            .split_once(';')
+            .map(|(e, index)| (e, index.parse::<u32>()));
+        let (e, index) = match parsed {
+            Some((e, Ok(index))) => (e, index),
+            _ => {
+                event::report_error(
+                    "menubar",
+                    &format!("invalid menu item: {}", value),
+                );
+                return;
+            }
+        };
         self.state
             .set_selected_item(match self.state.selected_item() {
                 Some(_) => match e {
@@ -209,15 +309,101 @@ impl MenuBar {
 
     /// Function triggered on MenuFunction change event
     fn on_function_change(&mut self, value: &str) {
-        self.state
-            .set_selected_function(Some(value.parse::<u32>().unwrap()));
+        let path = value
+            .split(',')
+            .map(|index| index.parse::<u32>())
+            .collect::<Result<Vec<u32>, _>>();
+        if let Ok(path) = path {
+            self.activate(path);
+        }
+        self.state.set_selected_item(None);
+    }
+
+    /// Function triggered on key event, activating the function with this
+    /// accelerator
+    fn on_key(&mut self, key: Key) {
+        let mut path = None;
+        for (i, item) in self.items.iter().enumerate() {
+            for (j, function) in item.functions.iter().enumerate() {
+                if path.is_none() {
+                    path = function
+                        .find_accelerator(key, vec![i as u32, j as u32]);
+                }
+            }
+        }
+        if let Some(path) = path {
+            self.activate(path);
+        }
+    }
+
+    /// Get the function at the given path
+    fn function(&self, path: &[u32]) -> Option<&MenuFunction> {
+        let (first, rest) = path.split_first()?;
+        let item = self.items.get(*first as usize)?;
+        let (second, rest) = rest.split_first()?;
+        let mut function = item.functions.get(*second as usize)?;
+        for index in rest {
+            function = function.functions.get(*index as usize)?;
+        }
+        Some(function)
+    }
+
+    /// Get the functions of the menu containing the function at the given
+    /// path
+    fn siblings(&self, path: &[u32]) -> Option<&Vec<MenuFunction>> {
+        match path {
+            [] | [_] => None,
+            [item, _] => Some(&self.items.get(*item as usize)?.functions),
+            _ => Some(&self.function(&path[..path.len() - 1])?.functions),
+        }
+    }
+
+    /// Switch the checked flag of the function at the given path if it is
+    /// checkable, then trigger the listener
+    fn activate(&mut self, path: Vec<u32>) {
+        let kind = match self.function(&path) {
+            Some(function)
+                if function.functions.is_empty()
+                    && !self.state.is_disabled(&path) =>
+            {
+                function.kind.clone()
+            }
+            _ => return,
+        };
+        match kind {
+            Kind::Separator => return,
+            Kind::Normal => (),
+            Kind::Check => {
+                let checked = self.state.is_checked(&path);
+                self.state.set_checked(&path, !checked);
+            }
+            Kind::Radio(group) => {
+                let last = path.len() - 1;
+                let radios = match self.siblings(&path) {
+                    Some(siblings) => siblings
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, f)| f.kind == Kind::Radio(group.clone()))
+                        .map(|(i, _)| i as u32)
+                        .collect::<Vec<u32>>(),
+                    None => vec![],
+                };
+                let mut sibling = path.clone();
+                for index in radios {
+                    sibling[last] = index;
+                    self.state.set_checked(&sibling, index == path[last]);
+                }
+            }
+        }
+        self.state.set_selected_item(Some(path[0]));
+        self.state.set_selected_function(Some(path[1]));
+        self.state.set_selected_path(path);
         match &self.listener {
             None => (),
             Some(listener) => {
                 listener.on_change(&self.state);
             }
         };
-        self.state.set_selected_item(None);
     }
 }
 
@@ -227,6 +413,8 @@ impl MenuBar {
 ///
 /// ```text
 /// name: String
+/// text_key: Option<String>
+/// icon: Option<Pixmap>
 /// functions: Vec<MenuFunction>
 /// ```
 ///
@@ -234,10 +422,14 @@ impl MenuBar {
 ///
 /// ```text
 /// name: name.to_string()
+/// text_key: None
+/// icon: None
 /// functions: vec![]
 /// ```
 pub struct MenuItem {
     name: String,
+    text_key: Option<String>,
+    icon: Option<Pixmap>,
     functions: Vec<MenuFunction>,
 }
 
@@ -246,30 +438,69 @@ impl MenuItem {
     pub fn new(name: &str) -> Self {
         Self {
             name: name.to_string(),
+            text_key: None,
+            icon: None,
             functions: vec![],
         }
     }
 
+    /// Set the key of the name in the catalogs, the name being translated
+    /// in the locale of the application
+    pub fn set_text_key(&mut self, key: &str) {
+        self.text_key = Some(key.to_string());
+    }
+
+    /// Set the icon
+    pub fn set_icon(&mut self, icon: Box<dyn Icon>) {
+        self.icon = Some(Pixmap::from_icon(icon));
+    }
+
     /// Add a MenuFunction
     pub fn add(&mut self, function: MenuFunction) {
         self.functions.push(function);
     }
 
+    /// Add a separator
+    pub fn add_separator(&mut self) {
+        self.functions.push(MenuFunction::separator());
+    }
+
     /// Return the HTML representation of the widget
-    fn eval(&self, index: usize, selected: bool) -> String {
+    fn eval(
+        &self,
+        index: usize,
+        selected: bool,
+        state: &MenuBarState,
+    ) -> String {
         let selected_str = if selected { "selected" } else { "" };
+        let click = format!("click;{}", index);
+        let mut keys =
+            vec![("Enter", click.to_string()), (" ", click.to_string())];
+        if !selected {
+            keys.push(("ArrowDown", click));
+        }
         let mut s = format!(
-            r#"<div class="menuitem"><div class="menuitem-title {}" onmousedown="{}" onmouseover="{}">{}</div>"#,
+            r#"<div class="menuitem"><div class="menuitem-title {}" role="menuitem" aria-haspopup="menu" aria-expanded="{}" tabindex="0" onmousedown="{}" onmouseover="{}" onkeydown="{}">{}{}</div>"#,
             selected_str,
+            selected,
             Event::change_js("menuitem", &format!("'click;{}'", index)), 
             Event::change_js("menuitem", &format!("'over;{}'", index)), 
-            self.name
+            Event::keys_js("menuitem", &keys),
+            icon_html(&self.icon),
+            escape(&title(&self.name, &self.text_key))
         );
         if selected {
-            s.push_str(r#"<div class="menufunctions">"#);
+            s.push_str(
+                r#"<div class="menufunctions" role="menu" data-overlay="menubar">"#,
+            );
             let functions_number = self.functions.len();
             for (i, function) in self.functions.iter().enumerate() {
-                s.push_str(&function.eval(i ,i == 0, i == functions_number - 1));
+                s.push_str(&function.eval(
+                    &[index as u32, i as u32],
+                    state,
+                    i == 0,
+                    i == functions_number - 1,
+                ));
             }
             s.push_str(r#"</div>"#);
         }
@@ -278,24 +509,54 @@ impl MenuItem {
     }
 }
 
+/// # The kind of a MenuFunction
+#[derive(Clone, PartialEq)]
+enum Kind {
+    Normal,
+    Check,
+    Radio(String),
+    Separator,
+}
+
 /// # A function of a MenuItem
 ///
 /// ## Fields
 ///
 /// ```text
 /// name: String
+/// text_key: Option<String>
+/// icon: Option<Pixmap>
 /// shortcut: Option<String>
+/// accelerator: Option<Key>
+/// kind: Kind
+/// checked: bool
+/// disabled: bool
+/// functions: Vec<MenuFunction>
 /// ```
 ///
 /// ## Default values
 ///
 /// ```text
 /// name: name.to_string()
+/// text_key: None
+/// icon: None
 /// shortcut: None
+/// accelerator: None
+/// kind: Kind::Normal
+/// checked: false
+/// disabled: false
+/// functions: vec![]
 /// ```
 pub struct MenuFunction {
     name: String,
+    text_key: Option<String>,
+    icon: Option<Pixmap>,
     shortcut: Option<String>,
+    accelerator: Option<Key>,
+    kind: Kind,
+    checked: bool,
+    disabled: bool,
+    functions: Vec<MenuFunction>,
 }
 
 impl MenuFunction {
@@ -303,26 +564,221 @@ impl MenuFunction {
     pub fn new(name: &str) -> Self {
         Self {
             name: name.to_string(),
+            text_key: None,
+            icon: None,
             shortcut: None,
+            accelerator: None,
+            kind: Kind::Normal,
+            checked: false,
+            disabled: false,
+            functions: vec![],
         }
     }
 
+    /// Create a separator
+    pub fn separator() -> Self {
+        let mut separator = Self::new("");
+        separator.kind = Kind::Separator;
+        separator
+    }
+
+    /// Set the icon
+    pub fn set_icon(&mut self, icon: Box<dyn Icon>) {
+        self.icon = Some(Pixmap::from_icon(icon));
+    }
+
+    /// Set the key of the name in the catalogs, the name being translated
+    /// in the locale of the application
+    pub fn set_text_key(&mut self, key: &str) {
+        self.text_key = Some(key.to_string());
+    }
+
     /// Set the shortcut
     pub fn set_shortcut(&mut self, shortcut: &str) {
         self.shortcut = Some(shortcut.to_string());
     }
 
+    /// Set the accelerator, triggering the function with `Ctrl + Key`. It is
+    /// displayed as the shortcut if none is set.
+    pub fn set_accelerator(&mut self, key: Key) {
+        self.accelerator = Some(key);
+    }
+
+    /// Make the function checkable
+    pub fn set_checkable(&mut self) {
+        self.kind = Kind::Check;
+    }
+
+    /// Make the function a radio item of the given group
+    pub fn set_radio(&mut self, group: &str) {
+        self.kind = Kind::Radio(group.to_string());
+    }
+
+    /// Set the checked flag to true
+    pub fn set_checked(&mut self) {
+        self.checked = true;
+    }
+
+    /// Set the disabled flag to true
+    pub fn set_disabled(&mut self) {
+        self.disabled = true;
+    }
+
+    /// Add a MenuFunction to the submenu
+    pub fn add(&mut self, function: MenuFunction) {
+        self.functions.push(function);
+    }
+
+    /// Add a separator to the submenu
+    pub fn add_separator(&mut self) {
+        self.functions.push(MenuFunction::separator());
+    }
+
+    /// Copy the initial checked and disabled flags into the state
+    fn init_state(&self, path: &[u32], state: &mut MenuBarState) {
+        if self.checked {
+            state.set_checked(path, true);
+        }
+        if self.disabled {
+            state.set_disabled(path, true);
+        }
+        for (i, function) in self.functions.iter().enumerate() {
+            let mut subpath = path.to_vec();
+            subpath.push(i as u32);
+            function.init_state(&subpath, state);
+        }
+    }
+
+    /// Get the path of the function with the given accelerator
+    fn find_accelerator(&self, key: Key, path: Vec<u32>) -> Option<Vec<u32>> {
+        if self.accelerator == Some(key) {
+            return Some(path);
+        }
+        for (i, function) in self.functions.iter().enumerate() {
+            let mut subpath = path.clone();
+            subpath.push(i as u32);
+            if let Some(found) = function.find_accelerator(key, subpath) {
+                return Some(found);
+            }
+        }
+        None
+    }
+
+    /// Return the text displayed next to the name
+    fn shortcut_text(&self) -> String {
+        if !self.functions.is_empty() {
+            return "&#9656;".to_string();
+        }
+        match (&self.shortcut, self.accelerator) {
+            (Some(shortcut), _) => escape(shortcut),
+            (None, Some(key)) => format!(
+                "Ctrl-{}",
+                format!("{:?}", key).trim_start_matches("Num")
+            ),
+            (None, None) => "".to_string(),
+        }
+    }
+
     /// Return the HTML representation of the widget
-    fn eval(&self, index: usize, first: bool, last: bool) -> String {
-        format!(
-            r#"<div class="menufunction {} {}" onmousedown="{}"><span class="title">{}</span><span class="shortcut">{}</span></div>"#,
+    fn eval(
+        &self,
+        path: &[u32],
+        state: &MenuBarState,
+        first: bool,
+        last: bool,
+    ) -> String {
+        if self.kind == Kind::Separator {
+            return r#"<div class="menuseparator" role="separator"></div>"#
+                .to_string();
+        }
+        let disabled = state.is_disabled(path);
+        let checked = state.is_checked(path);
+        let submenu = !self.functions.is_empty();
+        let (onmousedown, onkeydown) = if disabled || submenu {
+            (Event::stop_js(), "".to_string())
+        } else {
+            let path = path
+                .iter()
+                .map(|index| index.to_string())
+                .collect::<Vec<String>>()
+                .join(",");
+            (
+                Event::change_js("menufunction", &format!("'{}'", path)),
+                Event::keys_js(
+                    "menufunction",
+                    &[("Enter", path.to_string()), (" ", path)],
+                ),
+            )
+        };
+        let role = match &self.kind {
+            Kind::Check => format!(
+                r#"role="menuitemcheckbox" aria-checked="{}""#,
+                checked
+            ),
+            Kind::Radio(_) => {
+                format!(r#"role="menuitemradio" aria-checked="{}""#, checked)
+            }
+            _ => r#"role="menuitem""#.to_string(),
+        };
+        let check = match (&self.kind, checked) {
+            (Kind::Check, true) => "&#10003;",
+            (Kind::Radio(_), true) => "&#9679;",
+            _ => "",
+        };
+        let mut s = format!(
+            r#"<div class="menufunction {} {} {} {} {}" {} aria-disabled="{}" {} tabindex="-1" onmousedown="{}" onkeydown="{}"><span class="title"><span class="menucheck">{}</span>{}{}</span><span class="shortcut">{}</span>"#,
             if first { "first" } else { "" },
             if last { "last" } else { "" },
-            Event::change_js("menufunction", &format!("'{}'", index)),
-            self.name, match &self.shortcut {
-                None => "",
-                Some(shortcut) => shortcut,
-            },
-        )
+            if disabled { "disabled" } else { "" },
+            if checked { "checked" } else { "" },
+            if submenu { "submenu-parent" } else { "" },
+            role,
+            disabled,
+            if submenu { r#"aria-haspopup="menu""# } else { "" },
+            onmousedown,
+            onkeydown,
+            check,
+            icon_html(&self.icon),
+            escape(&title(&self.name, &self.text_key)),
+            self.shortcut_text(),
+        );
+        if submenu {
+            s.push_str(r#"<div class="menufunctions submenu" role="menu">"#);
+            let functions_number = self.functions.len();
+            for (i, function) in self.functions.iter().enumerate() {
+                let mut subpath = path.to_vec();
+                subpath.push(i as u32);
+                s.push_str(&function.eval(
+                    &subpath,
+                    state,
+                    i == 0,
+                    i == functions_number - 1,
+                ));
+            }
+            s.push_str("</div>");
+        }
+        s.push_str("</div>");
+        s
+    }
+}
+
+/// Return the name of a MenuItem or a MenuFunction, translated if it has a
+/// key
+fn title(name: &str, text_key: &Option<String>) -> String {
+    match text_key {
+        Some(key) => i18n::text(key),
+        None => name.to_string(),
+    }
+}
+
+/// Return the HTML representation of an optional icon
+fn icon_html(icon: &Option<Pixmap>) -> String {
+    match icon {
+        Some(icon) => format!(
+            r#"<img src="data:image/{};base64,{}" />"#,
+            icon.extension(),
+            icon.data()
+        ),
+        None => "".to_string(),
     }
 }
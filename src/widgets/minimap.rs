@@ -0,0 +1,250 @@
+use crate::utils::event::Event;
+use crate::utils::html::escape;
+use crate::utils::layoutoptions::LayoutOptions;
+use crate::utils::style::Style;
+use crate::widgets::widget::Widget;
+
+/// # The state of a Minimap
+///
+/// ## Fields
+///
+/// ```text
+/// target: String
+/// width: u32
+/// height: u32
+/// layout: LayoutOptions
+/// style: Style
+/// ```
+pub struct MinimapState {
+    target: String,
+    width: u32,
+    height: u32,
+    layout: LayoutOptions,
+    style: Style,
+}
+
+impl MinimapState {
+    /// Get the name of the target Viewport
+    pub fn target(&self) -> &str {
+        &self.target
+    }
+
+    /// Get the width in pixels
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Get the height in pixels
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Get the layout options
+    pub fn layout(&self) -> &LayoutOptions {
+        &self.layout
+    }
+
+    /// Get the style
+    pub fn style(&self) -> &Style {
+        &self.style
+    }
+
+    /// Get the stretched flag
+    pub fn stretched(&self) -> bool {
+        self.layout.stretched()
+    }
+
+    /// Set the name of the target Viewport
+    pub fn set_target(&mut self, target: &str) {
+        self.target = target.to_string();
+    }
+
+    /// Set the size (width and height) in pixels
+    pub fn set_size(&mut self, width: u32, height: u32) {
+        self.width = width;
+        self.height = height;
+    }
+
+    /// Set the layout options
+    pub fn set_layout(&mut self, layout: LayoutOptions) {
+        self.layout = layout;
+    }
+
+    /// Set the style
+    pub fn set_style(&mut self, style: Style) {
+        self.style = style;
+    }
+
+    /// Set the stretched flag
+    pub fn set_stretched(&mut self, stretched: bool) {
+        self.layout.set_stretched(stretched);
+    }
+}
+
+/// # The listener of a Minimap
+pub trait MinimapListener {
+    /// Function triggered on update event
+    fn on_update(&self, state: &mut MinimapState);
+}
+
+/// # A scaled overview of the content of a Viewport
+///
+/// The minimap displays a copy of the content of its target Viewport, scaled
+/// to fit, with a rectangle showing the visible area. Clicking or dragging on
+/// the minimap centers the Viewport on the pointed location by sending a
+/// change event to the Viewport, so its listener is informed of the new
+/// transform as if the user had panned it.
+///
+/// ## Fields
+///
+/// ```text
+/// name: String
+/// state: MinimapState
+/// listener: Option<Box<dyn MinimapListener>>
+/// ```
+///
+/// ## Default values
+///
+/// ```text
+/// name: name.to_string()
+/// state:
+///     target: "".to_string()
+///     width: 200
+///     height: 150
+///     layout: LayoutOptions::new()
+///     style: Style::new()
+/// listener: None
+/// ```
+///
+/// ## Example
+///
+/// ```
+/// use neutrino::widgets::canvas::Canvas;
+/// use neutrino::widgets::container::Container;
+/// use neutrino::widgets::minimap::Minimap;
+/// use neutrino::widgets::viewport::Viewport;
+/// use neutrino::{App, Window};
+///
+///
+/// fn main() {
+///     let mut my_canvas = Canvas::new("my_canvas");
+///     my_canvas.set_size(4000, 4000);
+///
+///     let mut my_viewport = Viewport::new("my_viewport");
+///     my_viewport.set_child(Box::new(my_canvas));
+///     my_viewport.set_stretched();
+///
+///     let mut my_minimap = Minimap::new("my_minimap");
+///     my_minimap.set_target("my_viewport");
+///
+///     let mut my_container = Container::new("my_container");
+///     my_container.add(Box::new(my_viewport));
+///     my_container.add(Box::new(my_minimap));
+/// }
+/// ```
+pub struct Minimap {
+    name: String,
+    state: MinimapState,
+    listener: Option<Box<dyn MinimapListener>>,
+}
+
+impl Minimap {
+    /// Create a Minimap
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            state: MinimapState {
+                target: "".to_string(),
+                width: 200,
+                height: 150,
+                layout: LayoutOptions::new(),
+                style: Style::new(),
+            },
+            listener: None,
+        }
+    }
+
+    /// Get the state
+    pub fn state(&self) -> &MinimapState {
+        &self.state
+    }
+
+    /// Get the state, to change it from outside of the listener
+    pub fn state_mut(&mut self) -> &mut MinimapState {
+        &mut self.state
+    }
+
+    /// Set the name of the target Viewport
+    pub fn set_target(&mut self, target: &str) {
+        self.state.set_target(target);
+    }
+
+    /// Set the size (width and height) in pixels
+    pub fn set_size(&mut self, width: u32, height: u32) {
+        self.state.set_size(width, height);
+    }
+
+    /// Set the layout options
+    pub fn set_layout(&mut self, layout: LayoutOptions) {
+        self.state.set_layout(layout);
+    }
+
+    /// Set the style
+    pub fn set_style(&mut self, style: Style) {
+        self.state.set_style(style);
+    }
+
+    /// Set the stretched flag to true
+    pub fn set_stretched(&mut self) {
+        self.state.set_stretched(true);
+    }
+
+    /// Set the listener
+    pub fn set_listener(&mut self, listener: Box<dyn MinimapListener>) {
+        self.listener = Some(listener);
+    }
+}
+
+impl Widget for Minimap {
+    fn eval(&self) -> String {
+        let layout = self.state.layout();
+        format!(
+            r#"<div id="{}" class="minimap {}" style="{}width: {}px; height: {}px;{}" data-target="{}" onmousedown="{}"><div class="minimap-content"></div><div class="minimap-view"></div></div>"#,
+            self.name,
+            layout.class(),
+            layout.style(),
+            self.state.width(),
+            self.state.height(),
+            self.state.style().css(),
+            escape(self.state.target()),
+            Event::minimap_navigate_js(),
+        )
+    }
+
+    fn name(&self) -> String {
+        self.name.to_string()
+    }
+
+    fn trigger(&mut self, event: &Event) {
+        match event {
+            Event::Update => self.on_update(),
+            Event::Change { source, value } => {
+                if source == &self.name {
+                    self.on_change(value)
+                }
+            }
+            _ => (),
+        }
+    }
+
+    fn on_update(&mut self) {
+        match &self.listener {
+            None => (),
+            Some(listener) => {
+                listener.on_update(&mut self.state);
+            }
+        }
+    }
+
+    fn on_change(&mut self, _value: &str) {}
+}
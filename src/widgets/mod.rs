@@ -1,13 +1,34 @@
+pub mod accordion;
+pub mod autocomplete;
 pub mod button;
+pub mod canvas;
 pub mod checkbox;
 pub mod combo;
 pub mod container;
+pub mod dangerconfirm;
+pub mod datepicker;
+pub mod draggable;
+pub mod form;
 pub mod image;
 pub mod label;
+pub mod listbox;
+pub mod listview;
+pub mod lockscreen;
 pub mod menubar;
+pub mod minimap;
+pub mod popover;
+pub mod profileswitcher;
 pub mod progressbar;
 pub mod radio;
+pub mod snackbar;
 pub mod range;
+pub mod spinbox;
+pub mod spinner;
+pub mod statusbar;
 pub mod tabs;
 pub mod textinput;
+pub mod timepicker;
+pub mod toolbar;
+pub mod viewport;
 pub mod widget;
+pub mod wizard;
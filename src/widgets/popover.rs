@@ -0,0 +1,362 @@
+use crate::utils::event::Event;
+use crate::utils::html::escape;
+use crate::widgets::widget::{self, Widget};
+
+/// # The preferred side of the anchor where a Popover is displayed
+pub enum Placement {
+    Top,
+    Right,
+    Bottom,
+    Left,
+}
+
+impl Placement {
+    fn name(&self) -> &str {
+        match self {
+            Placement::Top => "top",
+            Placement::Right => "right",
+            Placement::Bottom => "bottom",
+            Placement::Left => "left",
+        }
+    }
+}
+
+/// # The state of a Popover
+///
+/// ## Fields
+///
+/// ```text
+/// child: Option<Box<dyn Widget>>
+/// anchor: Option<String>
+/// placement: Placement
+/// opened: bool
+/// ```
+pub struct PopoverState {
+    child: Option<Box<dyn Widget>>,
+    anchor: Option<String>,
+    placement: Placement,
+    opened: bool,
+}
+
+impl PopoverState {
+    /// Get the child
+    pub fn child(&self) -> Option<&(dyn Widget + 'static)> {
+        self.child.as_ref().map(|child| child.as_ref())
+    }
+
+    /// Get the name of the anchor widget
+    pub fn anchor(&self) -> Option<&str> {
+        self.anchor.as_ref().map(String::as_ref)
+    }
+
+    /// Get the placement
+    pub fn placement(&self) -> &Placement {
+        &self.placement
+    }
+
+    /// Get the opened flag
+    pub fn opened(&self) -> bool {
+        self.opened
+    }
+
+    /// Set the child
+    pub fn set_child(&mut self, child: Box<dyn Widget>) {
+        self.child = Some(child);
+    }
+
+    /// Set the name of the anchor widget
+    pub fn set_anchor(&mut self, anchor: Option<&str>) {
+        self.anchor = anchor.map(str::to_string);
+    }
+
+    /// Set the placement
+    pub fn set_placement(&mut self, placement: Placement) {
+        self.placement = placement;
+    }
+
+    /// Set the opened flag
+    pub fn set_opened(&mut self, opened: bool) {
+        self.opened = opened;
+    }
+}
+
+/// # The listener of a Popover
+pub trait PopoverListener {
+    /// Function triggered on update event
+    fn on_update(&self, state: &mut PopoverState);
+
+    /// Function triggered when the Popover has been closed by a click outside
+    /// of it or by the Escape key
+    fn on_dismiss(&self, _state: &PopoverState) {}
+}
+
+/// # A floating element displayed next to an anchor widget
+///
+/// When opened, the child is displayed above the other widgets, on the
+/// preferred side of the anchor. If there is not enough room on this side of
+/// the window, the Popover flips to the opposite side, and it is shifted
+/// along the anchor to stay within the window. Without an anchor, the
+/// Popover is placed next to its parent.
+///
+/// Choices of a Combo and suggestions of an AutoComplete are placed the same
+/// way.
+///
+/// An opened Popover is an overlay: it is stacked above the overlays opened
+/// before it, and a click outside of it and of its anchor, or the Escape key,
+/// closes it and triggers `on_dismiss`, so the listener can keep its model in
+/// sync.
+///
+/// ## Fields
+///
+/// ```text
+/// name: String
+/// state: PopoverState
+/// listener: Option<Box<dyn PopoverListener>>
+/// ```
+///
+/// ## Default values
+///
+/// ```text
+/// name: name.to_string()
+/// state:
+///     child: None
+///     anchor: None
+///     placement: Placement::Bottom
+///     opened: false
+/// listener: None
+/// ```
+///
+/// ## Example
+///
+/// ```
+/// use std::cell::RefCell;
+/// use std::rc::Rc;
+///
+/// use neutrino::widgets::button::{Button, ButtonListener, ButtonState};
+/// use neutrino::widgets::container::Container;
+/// use neutrino::widgets::label::Label;
+/// use neutrino::widgets::popover::{
+///     Placement, Popover, PopoverListener, PopoverState,
+/// };
+/// use neutrino::{App, Window};
+///
+///
+/// struct Help {
+///     visible: bool,
+/// }
+///
+///
+/// struct MyButtonListener {
+///     help: Rc<RefCell<Help>>,
+/// }
+///
+/// impl ButtonListener for MyButtonListener {
+///     fn on_change(&self, _state: &ButtonState) {
+///         let visible = self.help.borrow().visible;
+///         self.help.borrow_mut().visible = !visible;
+///     }
+///
+///     fn on_update(&self, _state: &mut ButtonState) {}
+/// }
+///
+///
+/// struct MyPopoverListener {
+///     help: Rc<RefCell<Help>>,
+/// }
+///
+/// impl PopoverListener for MyPopoverListener {
+///     fn on_update(&self, state: &mut PopoverState) {
+///         state.set_opened(self.help.borrow().visible);
+///     }
+///
+///     fn on_dismiss(&self, _state: &PopoverState) {
+///         self.help.borrow_mut().visible = false;
+///     }
+/// }
+///
+///
+/// fn main() {
+///     let help = Rc::new(RefCell::new(Help { visible: false }));
+///
+///     let mut my_button = Button::new("my_button");
+///     my_button.set_text("?");
+///     my_button.set_listener(Box::new(MyButtonListener {
+///         help: Rc::clone(&help),
+///     }));
+///
+///     let mut my_label = Label::new("my_label");
+///     my_label.set_text("Fill in your name and press Enter");
+///
+///     let mut my_popover = Popover::new("my_popover");
+///     my_popover.set_anchor("my_button");
+///     my_popover.set_placement(Placement::Right);
+///     my_popover.set_child(Box::new(my_label));
+///     my_popover.set_listener(Box::new(MyPopoverListener { help }));
+///
+///     let mut my_container = Container::new("my_container");
+///     my_container.add(Box::new(my_button));
+///     my_container.add(Box::new(my_popover));
+/// }
+/// ```
+pub struct Popover {
+    name: String,
+    state: PopoverState,
+    listener: Option<Box<dyn PopoverListener>>,
+}
+
+impl Popover {
+    /// Create a Popover
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            state: PopoverState {
+                child: None,
+                anchor: None,
+                placement: Placement::Bottom,
+                opened: false,
+            },
+            listener: None,
+        }
+    }
+
+    /// Get the state
+    pub fn state(&self) -> &PopoverState {
+        &self.state
+    }
+
+    /// Get the state, to change it from outside of the listener
+    pub fn state_mut(&mut self) -> &mut PopoverState {
+        &mut self.state
+    }
+
+    /// Set the child
+    pub fn set_child(&mut self, child: Box<dyn Widget>) {
+        self.state.set_child(child);
+    }
+
+    /// Set the name of the anchor widget
+    pub fn set_anchor(&mut self, anchor: &str) {
+        self.state.set_anchor(Some(anchor));
+    }
+
+    /// Set the placement
+    pub fn set_placement(&mut self, placement: Placement) {
+        self.state.set_placement(placement);
+    }
+
+    /// Set the opened flag to true
+    pub fn set_opened(&mut self) {
+        self.state.set_opened(true);
+    }
+
+    /// Set the listener
+    pub fn set_listener(&mut self, listener: Box<dyn PopoverListener>) {
+        self.listener = Some(listener);
+    }
+
+    fn on_dismiss(&mut self) {
+        self.state.set_opened(false);
+        match &self.listener {
+            None => (),
+            Some(listener) => {
+                listener.on_dismiss(&self.state);
+            }
+        }
+    }
+}
+
+impl Widget for Popover {
+    fn eval(&self) -> String {
+        let opened = if self.state.opened() { "opened" } else { "" };
+        let child = match self.state.child() {
+            Some(child) if self.state.opened() => widget::eval(child),
+            _ => "".to_string(),
+        };
+        let overlay = if self.state.opened() {
+            format!(r#"data-overlay="{}""#, self.name)
+        } else {
+            "".to_string()
+        };
+        format!(
+            r#"<div id="{}" class="popover {}" data-anchor="{}" data-placement="{}" {}>{}</div>"#,
+            self.name,
+            opened,
+            escape(self.state.anchor().unwrap_or("")),
+            self.state.placement().name(),
+            overlay,
+            child,
+        )
+    }
+
+    fn name(&self) -> String {
+        self.name.to_string()
+    }
+
+    fn trigger(&mut self, event: &Event) {
+        match event {
+            Event::Update => self.on_update(),
+            Event::Change { source, value } => {
+                if source == &self.name {
+                    self.on_change(value)
+                }
+            }
+            Event::Dismiss { source } => {
+                if source == &self.name {
+                    self.on_dismiss();
+                }
+            }
+            _ => (),
+        }
+        if let Some(child) = self.state.child.as_mut() {
+            if widget::reaches(child.as_ref(), event) {
+                widget::trigger(child.as_mut(), event);
+            }
+        }
+    }
+
+    fn on_update(&mut self) {
+        match &self.listener {
+            None => (),
+            Some(listener) => {
+                listener.on_update(&mut self.state);
+            }
+        }
+    }
+
+    fn on_change(&mut self, _value: &str) {}
+
+    fn values(&self) -> Vec<(String, String)> {
+        match self.state.child() {
+            Some(child) => child.values(),
+            None => vec![],
+        }
+    }
+
+    fn is_valid(&self) -> bool {
+        match self.state.child() {
+            Some(child) => child.is_valid(),
+            None => true,
+        }
+    }
+
+    fn validate(&mut self) -> bool {
+        match self.state.child.as_mut() {
+            Some(child) => child.validate(),
+            None => true,
+        }
+    }
+
+    fn children(&self) -> Vec<&(dyn Widget + 'static)> {
+        match self.state.child() {
+            Some(child) => vec![child],
+            None => vec![],
+        }
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut (dyn Widget + 'static)> {
+        match &mut self.state.child {
+            Some(child) => vec![child.as_mut()],
+            None => vec![],
+        }
+    }
+}
@@ -0,0 +1,487 @@
+use crate::utils::event::Event;
+use crate::utils::html::escape;
+use crate::utils::pixmap::Pixmap;
+use crate::widgets::widget::Widget;
+
+/// # A profile of a ProfileSwitcher
+///
+/// A profile without an avatar is displayed with the initials of its name.
+///
+/// ## Fields
+///
+/// ```text
+/// id: String
+/// name: String
+/// detail: String
+/// avatar: Option<Pixmap>
+/// ```
+///
+/// ## Default values
+///
+/// ```text
+/// id: id.to_string()
+/// name: name.to_string()
+/// detail: "".to_string()
+/// avatar: None
+/// ```
+pub struct Profile {
+    id: String,
+    name: String,
+    detail: String,
+    avatar: Option<Pixmap>,
+}
+
+impl Profile {
+    /// Create a Profile
+    pub fn new(id: &str, name: &str) -> Self {
+        Self {
+            id: id.to_string(),
+            name: name.to_string(),
+            detail: "".to_string(),
+            avatar: None,
+        }
+    }
+
+    /// Get the id
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Get the name
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the detail, such as an email address
+    pub fn detail(&self) -> &str {
+        &self.detail
+    }
+
+    /// Get the avatar
+    pub fn avatar(&self) -> Option<&Pixmap> {
+        self.avatar.as_ref()
+    }
+
+    /// Set the detail, such as an email address
+    pub fn set_detail(&mut self, detail: &str) {
+        self.detail = detail.to_string();
+    }
+
+    /// Set the avatar
+    pub fn set_avatar(&mut self, avatar: Pixmap) {
+        self.avatar = Some(avatar);
+    }
+
+    /// Get the initials of the name
+    fn initials(&self) -> String {
+        self.name
+            .split_whitespace()
+            .filter_map(|word| word.chars().next())
+            .take(2)
+            .flat_map(char::to_uppercase)
+            .collect()
+    }
+
+    /// Return the HTML representation of the avatar
+    fn eval_avatar(&self) -> String {
+        match self.avatar() {
+            Some(avatar) => format!(
+                r#"<img class="profileswitcher-avatar" src="data:image/{};base64,{}" />"#,
+                avatar.extension(),
+                avatar.data()
+            ),
+            None => format!(
+                r#"<span class="profileswitcher-avatar">{}</span>"#,
+                escape(&self.initials())
+            ),
+        }
+    }
+}
+
+/// # The state of a ProfileSwitcher
+///
+/// ## Fields
+///
+/// ```text
+/// profiles: Vec<Profile>
+/// active: Option<String>
+/// selected: Option<String>
+/// add: String
+/// sign_out: String
+/// opened: bool
+/// ```
+pub struct ProfileSwitcherState {
+    profiles: Vec<Profile>,
+    active: Option<String>,
+    selected: Option<String>,
+    add: String,
+    sign_out: String,
+    opened: bool,
+}
+
+impl ProfileSwitcherState {
+    /// Get the profiles
+    pub fn profiles(&self) -> &Vec<Profile> {
+        &self.profiles
+    }
+
+    /// Get the id of the active profile
+    pub fn active(&self) -> Option<&str> {
+        self.active.as_ref().map(String::as_ref)
+    }
+
+    /// Get the id of the profile picked in the menu
+    pub fn selected(&self) -> Option<&str> {
+        self.selected.as_ref().map(String::as_ref)
+    }
+
+    /// Get the text of the add entry
+    pub fn add(&self) -> &str {
+        &self.add
+    }
+
+    /// Get the text of the sign out entry
+    pub fn sign_out(&self) -> &str {
+        &self.sign_out
+    }
+
+    /// Get the opened flag of the menu
+    pub fn opened(&self) -> bool {
+        self.opened
+    }
+
+    /// Get the profile with the given id
+    pub fn profile(&self, id: &str) -> Option<&Profile> {
+        self.profiles.iter().find(|profile| profile.id() == id)
+    }
+
+    /// Set the profiles
+    pub fn set_profiles(&mut self, profiles: Vec<Profile>) {
+        self.profiles = profiles;
+    }
+
+    /// Set the id of the active profile
+    pub fn set_active(&mut self, active: Option<&str>) {
+        self.active = active.map(str::to_string);
+    }
+
+    /// Set the text of the add entry
+    pub fn set_add(&mut self, add: &str) {
+        self.add = add.to_string();
+    }
+
+    /// Set the text of the sign out entry
+    pub fn set_sign_out(&mut self, sign_out: &str) {
+        self.sign_out = sign_out.to_string();
+    }
+
+    /// Set the opened flag of the menu
+    pub fn set_opened(&mut self, opened: bool) {
+        self.opened = opened;
+    }
+
+    /// Add a profile
+    fn add_profile(&mut self, profile: Profile) {
+        self.profiles.push(profile);
+    }
+}
+
+/// # The listener of a ProfileSwitcher
+pub trait ProfileSwitcherListener {
+    /// Function triggered on update event
+    fn on_update(&self, state: &mut ProfileSwitcherState);
+
+    /// Function triggered when another profile has been picked. The selected
+    /// profile is already the active one.
+    fn on_switch(&self, state: &ProfileSwitcherState);
+
+    /// Function triggered when the add entry has been clicked
+    fn on_add(&self, _state: &ProfileSwitcherState) {}
+
+    /// Function triggered when the sign out entry has been clicked, the
+    /// selected profile being the one signing out
+    fn on_sign_out(&self, _state: &ProfileSwitcherState) {}
+}
+
+/// # A menu switching between the profiles of the users
+///
+/// The ProfileSwitcher displays the avatar of the active profile. A click on
+/// it opens a menu listing the profiles, followed by an entry adding a
+/// profile and an entry signing the active profile out. The menu is an
+/// overlay closed by a click outside of it or by the Escape key.
+///
+/// Picking a profile makes it active and triggers the `on_switch` function
+/// of the listener, where the application swaps the settings and the stores
+/// of the users, for instance by replacing the model shared by its
+/// listeners.
+///
+/// ## Fields
+///
+/// ```text
+/// name: String
+/// state: ProfileSwitcherState
+/// listener: Option<Box<dyn ProfileSwitcherListener>>
+/// ```
+///
+/// ## Default values
+///
+/// ```text
+/// name: name.to_string()
+/// state:
+///     profiles: vec![]
+///     active: None
+///     selected: None
+///     add: "Add account".to_string()
+///     sign_out: "Sign out".to_string()
+///     opened: false
+/// listener: None
+/// ```
+///
+/// ## Example
+///
+/// ```
+/// use std::cell::RefCell;
+/// use std::collections::HashMap;
+/// use std::rc::Rc;
+///
+/// use neutrino::widgets::profileswitcher::{
+///     Profile, ProfileSwitcher, ProfileSwitcherListener, ProfileSwitcherState,
+/// };
+/// use neutrino::{App, Window};
+///
+///
+/// struct Settings {
+///     theme: String,
+/// }
+///
+/// struct Session {
+///     user: Option<String>,
+///     settings: HashMap<String, Settings>,
+/// }
+///
+///
+/// struct MyProfileSwitcherListener {
+///     session: Rc<RefCell<Session>>,
+/// }
+///
+/// impl ProfileSwitcherListener for MyProfileSwitcherListener {
+///     fn on_update(&self, state: &mut ProfileSwitcherState) {
+///         let session = self.session.borrow();
+///         state.set_active(session.user.as_ref().map(String::as_ref));
+///     }
+///
+///     fn on_switch(&self, state: &ProfileSwitcherState) {
+///         let mut session = self.session.borrow_mut();
+///         session.user = state.selected().map(str::to_string);
+///     }
+///
+///     fn on_sign_out(&self, _state: &ProfileSwitcherState) {
+///         self.session.borrow_mut().user = None;
+///     }
+/// }
+///
+///
+/// fn main() {
+///     let session = Rc::new(RefCell::new(Session {
+///         user: Some("ada".to_string()),
+///         settings: HashMap::new(),
+///     }));
+///
+///     let mut ada = Profile::new("ada", "Ada Lovelace");
+///     ada.set_detail("ada@example.com");
+///
+///     let mut my_switcher = ProfileSwitcher::new("my_switcher");
+///     my_switcher.add(ada);
+///     my_switcher.add(Profile::new("alan", "Alan Turing"));
+///     my_switcher.set_listener(Box::new(MyProfileSwitcherListener { session }));
+/// }
+/// ```
+pub struct ProfileSwitcher {
+    name: String,
+    state: ProfileSwitcherState,
+    listener: Option<Box<dyn ProfileSwitcherListener>>,
+}
+
+impl ProfileSwitcher {
+    /// Create a ProfileSwitcher
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            state: ProfileSwitcherState {
+                profiles: vec![],
+                active: None,
+                selected: None,
+                add: "Add account".to_string(),
+                sign_out: "Sign out".to_string(),
+                opened: false,
+            },
+            listener: None,
+        }
+    }
+
+    /// Get the state
+    pub fn state(&self) -> &ProfileSwitcherState {
+        &self.state
+    }
+
+    /// Get the state, to change it from outside of the listener
+    pub fn state_mut(&mut self) -> &mut ProfileSwitcherState {
+        &mut self.state
+    }
+
+    /// Add a profile
+    pub fn add(&mut self, profile: Profile) {
+        self.state.add_profile(profile);
+    }
+
+    /// Set the id of the active profile
+    pub fn set_active(&mut self, active: &str) {
+        self.state.set_active(Some(active));
+    }
+
+    /// Set the text of the add entry
+    pub fn set_add(&mut self, add: &str) {
+        self.state.set_add(add);
+    }
+
+    /// Set the text of the sign out entry
+    pub fn set_sign_out(&mut self, sign_out: &str) {
+        self.state.set_sign_out(sign_out);
+    }
+
+    /// Set the listener
+    pub fn set_listener(&mut self, listener: Box<dyn ProfileSwitcherListener>) {
+        self.listener = Some(listener);
+    }
+}
+
+impl Widget for ProfileSwitcher {
+    fn eval(&self) -> String {
+        let active = self.state.active().and_then(|id| self.state.profile(id));
+        let avatar = match active {
+            Some(profile) => profile.eval_avatar(),
+            None => {
+                r#"<span class="profileswitcher-avatar"></span>"#.to_string()
+            }
+        };
+        let title = match active {
+            Some(profile) => profile.name(),
+            None => "",
+        };
+        let mut s = format!(
+            r#"<div id="{}" class="profileswitcher"><div id="{}-button" class="profileswitcher-button" title="{}" onmousedown="{}">{}</div>"#,
+            self.name,
+            self.name,
+            escape(title),
+            Event::change_js(&self.name, "'toggle'"),
+            avatar
+        );
+        if self.state.opened() {
+            s.push_str(&format!(
+                r#"<div class="profileswitcher-menu" data-anchor="{}-button" data-placement="bottom" data-overlay="{}">"#,
+                self.name, self.name
+            ));
+            for (i, profile) in self.state.profiles().iter().enumerate() {
+                let active = if Some(profile.id()) == self.state.active() {
+                    "active"
+                } else {
+                    ""
+                };
+                s.push_str(&format!(
+                    r#"<div class="profileswitcher-profile {}" onmousedown="{}">{}<div class="profileswitcher-text"><span class="profileswitcher-name">{}</span><span class="profileswitcher-detail">{}</span></div></div>"#,
+                    active,
+                    Event::change_js(&self.name, &format!("'switch:{}'", i)),
+                    profile.eval_avatar(),
+                    escape(profile.name()),
+                    escape(profile.detail())
+                ));
+            }
+            s.push_str(&format!(
+                r#"<div class="profileswitcher-separator"></div><div class="profileswitcher-entry" onmousedown="{}">{}</div>"#,
+                Event::change_js(&self.name, "'add'"),
+                escape(self.state.add())
+            ));
+            if active.is_some() {
+                s.push_str(&format!(
+                    r#"<div class="profileswitcher-entry" onmousedown="{}">{}</div>"#,
+                    Event::change_js(&self.name, "'sign-out'"),
+                    escape(self.state.sign_out())
+                ));
+            }
+            s.push_str("</div>");
+        }
+        s.push_str("</div>");
+        s
+    }
+
+    fn name(&self) -> String {
+        self.name.to_string()
+    }
+
+    fn trigger(&mut self, event: &Event) {
+        match event {
+            Event::Update => self.on_update(),
+            Event::Change { source, value } => {
+                if source == &self.name {
+                    self.on_change(value);
+                }
+            }
+            Event::Dismiss { source } => {
+                if source == &self.name {
+                    self.state.set_opened(false);
+                }
+            }
+            _ => (),
+        }
+    }
+
+    fn on_update(&mut self) {
+        match &self.listener {
+            None => (),
+            Some(listener) => {
+                listener.on_update(&mut self.state);
+            }
+        }
+    }
+
+    fn on_change(&mut self, value: &str) {
+        if value == "toggle" {
+            let opened = self.state.opened();
+            self.state.set_opened(!opened);
+            return;
+        }
+        self.state.set_opened(false);
+        if let Some(index) = value.strip_prefix("switch:") {
+            let id = match index.parse::<usize>() {
+                Ok(index) => match self.state.profiles().get(index) {
+                    Some(profile) => profile.id().to_string(),
+                    None => return,
+                },
+                Err(_) => return,
+            };
+            if self.state.active() == Some(id.as_str()) {
+                return;
+            }
+            self.state.selected = Some(id.clone());
+            self.state.set_active(Some(&id));
+            match &self.listener {
+                None => (),
+                Some(listener) => {
+                    listener.on_switch(&self.state);
+                }
+            }
+            return;
+        }
+        match &self.listener {
+            None => (),
+            Some(listener) => match value {
+                "add" => listener.on_add(&self.state),
+                "sign-out" => {
+                    self.state.selected = self.state.active.clone();
+                    listener.on_sign_out(&self.state);
+                }
+                _ => (),
+            },
+        }
+    }
+}
@@ -1,4 +1,7 @@
 use crate::utils::event::Event;
+use crate::utils::html::aria_label;
+use crate::utils::layoutoptions::LayoutOptions;
+use crate::utils::style::Style;
 use crate::widgets::widget::Widget;
 
 /// # The state of a ProgressBar
@@ -9,13 +12,19 @@ use crate::widgets::widget::Widget;
 /// min: i32
 /// max: i32
 /// value: i32
-/// stretched: bool
+/// indeterminate: bool
+/// layout: LayoutOptions
+/// style: Style
+/// aria_label: Option<String>
 /// ```
 pub struct ProgressBarState {
     min: i32,
     max: i32,
     value: i32,
-    stretched: bool,
+    indeterminate: bool,
+    layout: LayoutOptions,
+    style: Style,
+    aria_label: Option<String>,
 }
 
 impl ProgressBarState {
@@ -34,9 +43,29 @@ impl ProgressBarState {
         self.value
     }
 
+    /// Get the indeterminate flag
+    pub fn indeterminate(&self) -> bool {
+        self.indeterminate
+    }
+
+    /// Get the layout options
+    pub fn layout(&self) -> &LayoutOptions {
+        &self.layout
+    }
+
+    /// Get the style
+    pub fn style(&self) -> &Style {
+        &self.style
+    }
+
+    /// Get the label read by screen readers
+    pub fn aria_label(&self) -> Option<&str> {
+        self.aria_label.as_deref()
+    }
+
     /// Get the stretched flag
     pub fn stretched(&self) -> bool {
-        self.stretched
+        self.layout.stretched()
     }
 
     /// Set the min
@@ -54,9 +83,29 @@ impl ProgressBarState {
         self.value = value;
     }
 
-    /// Set the stretched flqg
+    /// Set the indeterminate flag
+    pub fn set_indeterminate(&mut self, indeterminate: bool) {
+        self.indeterminate = indeterminate;
+    }
+
+    /// Set the layout options
+    pub fn set_layout(&mut self, layout: LayoutOptions) {
+        self.layout = layout;
+    }
+
+    /// Set the style
+    pub fn set_style(&mut self, style: Style) {
+        self.style = style;
+    }
+
+    /// Set the label read by screen readers, in place of the visible text
+    pub fn set_aria_label(&mut self, label: &str) {
+        self.aria_label = Some(label.to_string());
+    }
+
+    /// Set the stretched flag
     pub fn set_stretched(&mut self, stretched: bool) {
-        self.stretched = stretched;
+        self.layout.set_stretched(stretched);
     }
 }
 
@@ -68,6 +117,11 @@ pub trait ProgressBarListener {
 
 /// # A progress bar
 ///
+/// With the indeterminate flag, the bar shows that an operation is running
+/// without telling how far it is, for the operations whose duration is
+/// unknown. The listener can set the flag while the length of the operation
+/// is unknown, then set the value once it is.
+///
 /// ## Fields
 ///
 /// ```text
@@ -84,7 +138,10 @@ pub trait ProgressBarListener {
 ///     min: 0
 ///     max: 100
 ///     value: 0
-///     stretched: false
+///     indeterminate: false
+///     layout: LayoutOptions::new()
+///     style: Style::new()
+///     aria_label: None
 /// listener: None
 /// ```
 ///
@@ -155,12 +212,25 @@ impl ProgressBar {
                 min: 0,
                 max: 100,
                 value: 0,
-                stretched: false,
+                indeterminate: false,
+                layout: LayoutOptions::new(),
+                style: Style::new(),
+                aria_label: None,
             },
             listener: None,
         }
     }
 
+    /// Get the state
+    pub fn state(&self) -> &ProgressBarState {
+        &self.state
+    }
+
+    /// Get the state, to change it from outside of the listener
+    pub fn state_mut(&mut self) -> &mut ProgressBarState {
+        &mut self.state
+    }
+
     // Set the min
     pub fn set_min(&mut self, min: i32) {
         self.state.set_min(min);
@@ -176,6 +246,27 @@ impl ProgressBar {
         self.state.set_value(value);
     }
 
+    /// Set the indeterminate flag to true: the bar shows that an operation
+    /// is running without its value
+    pub fn set_indeterminate(&mut self) {
+        self.state.set_indeterminate(true);
+    }
+
+    /// Set the layout options
+    pub fn set_layout(&mut self, layout: LayoutOptions) {
+        self.state.set_layout(layout);
+    }
+
+    /// Set the style
+    pub fn set_style(&mut self, style: Style) {
+        self.state.set_style(style);
+    }
+
+    /// Set the label read by screen readers, in place of the visible text
+    pub fn set_aria_label(&mut self, label: &str) {
+        self.state.set_aria_label(label);
+    }
+
     // Set the stretched flag to true
     pub fn set_stretched(&mut self) {
         self.state.set_stretched(true);
@@ -189,21 +280,39 @@ impl ProgressBar {
 
 impl Widget for ProgressBar {
     fn eval(&self) -> String {
-        let stretched = if self.state.stretched() {
-            "stretched"
-        } else {
-            ""
-        };
+        let layout = self.state.layout();
+        if self.state.indeterminate() {
+            return format!(
+                r#"<div id="{}" class="progressbar indeterminate {}" style="{}{}" role="progressbar" aria-valuemin="{}" aria-valuemax="{}" {}><div class="inner-progressbar"></div></div>"#,
+                self.name,
+                layout.class(),
+                layout.style(),
+                self.state.style().css(),
+                self.state.min(),
+                self.state.max(),
+                aria_label(self.state.aria_label()),
+            );
+        }
         format!(
-            r#"<div id="{}" class="progressbar {}"><div class="inner-progressbar" style="width: {}%;"></div></div>"#, 
+            r#"<div id="{}" class="progressbar {}" style="{}{}" role="progressbar" aria-valuemin="{}" aria-valuemax="{}" aria-valuenow="{}" {}><div class="inner-progressbar" style="width: {}%;"></div></div>"#, 
             self.name,
-            stretched,
+            layout.class(),
+            layout.style(),
+            self.state.style().css(),
+            self.state.min(),
+            self.state.max(),
+            self.state.value(),
+            aria_label(self.state.aria_label()),
             f64::from(self.state.value() - self.state.min()) /
             f64::from(self.state.max() - self.state.min()) *
             100.0,
         )
     }
 
+    fn name(&self) -> String {
+        self.name.to_string()
+    }
+
     fn trigger(&mut self, event: &Event) {
         match event {
             Event::Update => self.on_update(),
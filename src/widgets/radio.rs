@@ -1,4 +1,7 @@
-use crate::utils::event::Event;
+use crate::utils::event::{self, Event};
+use crate::utils::html::{aria_label, escape};
+use crate::utils::layoutoptions::LayoutOptions;
+use crate::utils::style::Style;
 use crate::widgets::widget::Widget;
 
 /// # The state of a Radio
@@ -8,12 +11,16 @@ use crate::widgets::widget::Widget;
 /// ```text
 /// choices: Vec<String>
 /// selected: u32,
-/// stretched: bool
+/// layout: LayoutOptions
+/// style: Style
+/// aria_label: Option<String>
 /// ```
 pub struct RadioState {
     choices: Vec<String>,
     selected: u32,
-    stretched: bool,
+    layout: LayoutOptions,
+    style: Style,
+    aria_label: Option<String>,
 }
 
 impl RadioState {
@@ -27,9 +34,24 @@ impl RadioState {
         self.selected
     }
 
+    /// Get the layout options
+    pub fn layout(&self) -> &LayoutOptions {
+        &self.layout
+    }
+
+    /// Get the style
+    pub fn style(&self) -> &Style {
+        &self.style
+    }
+
+    /// Get the label read by screen readers
+    pub fn aria_label(&self) -> Option<&str> {
+        self.aria_label.as_deref()
+    }
+
     /// Get the stretched flag
     pub fn stretched(&self) -> bool {
-        self.stretched
+        self.layout.stretched()
     }
 
     /// Set the choices
@@ -45,9 +67,24 @@ impl RadioState {
         self.selected = selected;
     }
 
+    /// Set the layout options
+    pub fn set_layout(&mut self, layout: LayoutOptions) {
+        self.layout = layout;
+    }
+
+    /// Set the style
+    pub fn set_style(&mut self, style: Style) {
+        self.style = style;
+    }
+
+    /// Set the label read by screen readers, in place of the visible text
+    pub fn set_aria_label(&mut self, label: &str) {
+        self.aria_label = Some(label.to_string());
+    }
+
     /// Set the stretched flag
     pub fn set_stretched(&mut self, stretched: bool) {
-        self.stretched = stretched;
+        self.layout.set_stretched(stretched);
     }
 }
 
@@ -79,7 +116,9 @@ pub trait RadioListener {
 /// state:
 ///     choices: vec!["Choice 1".to_string(), "Choice 2".to_string()],
 ///     selected: 0
-///     stretched: false
+///     layout: LayoutOptions::new()
+///     style: Style::new()
+///     aria_label: None
 /// listener: None
 /// ```
 ///
@@ -168,12 +207,24 @@ impl Radio {
             state: RadioState {
                 choices: vec!["Choice 1".to_string(), "Choice 2".to_string()],
                 selected: 0,
-                stretched: false,
+                layout: LayoutOptions::new(),
+                style: Style::new(),
+                aria_label: None,
             },
             listener: None,
         }
     }
 
+    /// Get the state
+    pub fn state(&self) -> &RadioState {
+        &self.state
+    }
+
+    /// Get the state, to change it from outside of the listener
+    pub fn state_mut(&mut self) -> &mut RadioState {
+        &mut self.state
+    }
+
     /// Set the choices
     pub fn set_choices(&mut self, choices: Vec<&str>) {
         self.state.set_choices(choices);
@@ -184,6 +235,21 @@ impl Radio {
         self.state.set_selected(selected);
     }
 
+    /// Set the layout options
+    pub fn set_layout(&mut self, layout: LayoutOptions) {
+        self.state.set_layout(layout);
+    }
+
+    /// Set the style
+    pub fn set_style(&mut self, style: Style) {
+        self.state.set_style(style);
+    }
+
+    /// Set the label read by screen readers, in place of the visible text
+    pub fn set_aria_label(&mut self, label: &str) {
+        self.state.set_aria_label(label);
+    }
+
     /// Set the stretched flag
     pub fn set_stretched(&mut self) {
         self.state.set_stretched(true);
@@ -197,33 +263,52 @@ impl Radio {
 
 impl Widget for Radio {
     fn eval(&self) -> String {
-        let stretched = if self.state.stretched() {
-            "stretched"
-        } else {
-            ""
-        };
+        let layout = self.state.layout();
+        let count = self.state.choices().len();
         let mut s = "".to_string();
         for (i, choice) in self.state.choices().iter().enumerate() {
-            let selected = if self.state.selected() == i as u32 {
-                "selected"
-            } else {
-                ""
+            let checked = self.state.selected() == i as u32;
+            let selected = if checked { "selected" } else { "" };
+            let label = match self.state.aria_label() {
+                Some(label) => format!("{}, {}", label, choice),
+                None => choice.to_string(),
             };
+            let previous = ((i + count - 1) % count).to_string();
+            let next = ((i + 1) % count).to_string();
             s.push_str(
                 &format!(
-                    r#"<div id="{}" class="radio {}" onmousedown="{}"><div class="radio-outer {}"><div class="radio-inner {}"></div></div><label>{}</label></div>"#, 
+                    r#"<div id="{}" class="radio {}" style="{}{}" role="radio" aria-checked="{}" tabindex="{}" {} onmousedown="{}" onkeydown="{}"><div class="radio-outer {}"><div class="radio-inner {}"></div></div><label>{}</label></div>"#, 
                     self.name,
-                    stretched,
+                    layout.class(),
+                    layout.style(),
+                    self.state.style().css(),
+                    checked,
+                    if checked { 0 } else { -1 },
+                    aria_label(Some(&label)),
                     Event::change_js(&self.name, &format!("'{}'", i)), 
+                    Event::keys_js(
+                        &self.name,
+                        &[
+                            (" ", i.to_string()),
+                            ("ArrowUp", previous.clone()),
+                            ("ArrowLeft", previous),
+                            ("ArrowDown", next.clone()),
+                            ("ArrowRight", next),
+                        ]
+                    ),
                     selected,
                     selected,
-                    choice
+                    escape(choice)
                 )
             );
         }
         s
     }
 
+    fn name(&self) -> String {
+        self.name.to_string()
+    }
+
     fn trigger(&mut self, event: &Event) {
         match event {
             Event::Update => self.on_update(),
@@ -246,7 +331,16 @@ impl Widget for Radio {
     }
 
     fn on_change(&mut self, value: &str) {
-        self.state.set_selected(value.parse::<u32>().unwrap());
+        match value.parse::<u32>() {
+            Ok(selected) => self.state.set_selected(selected),
+            Err(_) => {
+                event::report_error(
+                    &self.name,
+                    &format!("invalid choice: {}", value),
+                );
+                return;
+            }
+        }
         match &self.listener {
             None => (),
             Some(listener) => {
@@ -254,4 +348,11 @@ impl Widget for Radio {
             }
         }
     }
+
+    fn values(&self) -> Vec<(String, String)> {
+        match self.state.choices().get(self.state.selected() as usize) {
+            Some(choice) => vec![(self.name.to_string(), choice.to_string())],
+            None => vec![],
+        }
+    }
 }
@@ -1,4 +1,8 @@
-use crate::utils::event::Event;
+use crate::utils::event::{self, Event};
+use crate::utils::html::aria_label;
+use crate::utils::i18n;
+use crate::utils::layoutoptions::LayoutOptions;
+use crate::utils::style::Style;
 use crate::widgets::widget::Widget;
 
 /// # The state of a Range
@@ -8,14 +12,26 @@ use crate::widgets::widget::Widget;
 /// ```text
 /// min: i32
 /// max: i32
+/// step: i32
 /// value: i32
-/// stretched: bool
+/// high: Option<i32>
+/// vertical: bool
+/// bubble: bool
+/// layout: LayoutOptions
+/// style: Style
+/// aria_label: Option<String>
 /// ```
 pub struct RangeState {
     min: i32,
     max: i32,
+    step: i32,
     value: i32,
-    stretched: bool,
+    high: Option<i32>,
+    vertical: bool,
+    bubble: bool,
+    layout: LayoutOptions,
+    style: Style,
+    aria_label: Option<String>,
 }
 
 impl RangeState {
@@ -29,14 +45,54 @@ impl RangeState {
         self.max
     }
 
-    /// Get the value
+    /// Get the step
+    pub fn step(&self) -> i32 {
+        self.step
+    }
+
+    /// Get the value, which is the lower end of an interval
     pub fn value(&self) -> i32 {
         self.value
     }
 
+    /// Get the upper end of an interval, None if the Range has one handle
+    pub fn high(&self) -> Option<i32> {
+        self.high
+    }
+
+    /// Get the interval as (low, high), None if the Range has one handle
+    pub fn interval(&self) -> Option<(i32, i32)> {
+        self.high.map(|high| (self.value, high))
+    }
+
+    /// Get the vertical flag
+    pub fn vertical(&self) -> bool {
+        self.vertical
+    }
+
+    /// Get the bubble flag
+    pub fn bubble(&self) -> bool {
+        self.bubble
+    }
+
+    /// Get the layout options
+    pub fn layout(&self) -> &LayoutOptions {
+        &self.layout
+    }
+
+    /// Get the style
+    pub fn style(&self) -> &Style {
+        &self.style
+    }
+
+    /// Get the label read by screen readers
+    pub fn aria_label(&self) -> Option<&str> {
+        self.aria_label.as_deref()
+    }
+
     /// Get the stretched flag
     pub fn stretched(&self) -> bool {
-        self.stretched
+        self.layout.stretched()
     }
 
     /// Set the min
@@ -49,14 +105,67 @@ impl RangeState {
         self.max = max;
     }
 
+    /// Set the step
+    pub fn set_step(&mut self, step: i32) {
+        self.step = step;
+    }
+
     /// Set the value
     pub fn set_value(&mut self, value: i32) {
         self.value = value;
     }
 
+    /// Set the upper end of an interval, giving the Range a second handle
+    pub fn set_high(&mut self, high: Option<i32>) {
+        self.high = high;
+    }
+
+    /// Set the interval, as the value and the upper end
+    pub fn set_interval(&mut self, low: i32, high: i32) {
+        self.value = low;
+        self.high = Some(high);
+    }
+
+    /// Set the vertical flag
+    pub fn set_vertical(&mut self, vertical: bool) {
+        self.vertical = vertical;
+    }
+
+    /// Set the bubble flag
+    pub fn set_bubble(&mut self, bubble: bool) {
+        self.bubble = bubble;
+    }
+
+    /// Return a value within the bounds, rounded to the nearest step
+    fn snap(&self, value: i32) -> i32 {
+        let step = self.step.max(1);
+        let value = value.max(self.min).min(self.max);
+        let snapped = self.min + (value - self.min + step / 2) / step * step;
+        if snapped > self.max {
+            snapped - step
+        } else {
+            snapped
+        }
+    }
+
+    /// Set the layout options
+    pub fn set_layout(&mut self, layout: LayoutOptions) {
+        self.layout = layout;
+    }
+
+    /// Set the style
+    pub fn set_style(&mut self, style: Style) {
+        self.style = style;
+    }
+
+    /// Set the label read by screen readers, in place of the visible text
+    pub fn set_aria_label(&mut self, label: &str) {
+        self.aria_label = Some(label.to_string());
+    }
+
     /// Set the stretched flag
     pub fn set_stretched(&mut self, stretched: bool) {
-        self.stretched = stretched;
+        self.layout.set_stretched(stretched);
     }
 }
 
@@ -71,6 +180,18 @@ pub trait RangeListener {
 
 /// # A progress bar with a handle
 ///
+/// The value moves by steps between the min and the max, horizontally or
+/// vertically. With the bubble flag, the value is displayed above the handle
+/// while it is dragged or focused. With an upper end, the Range has a second
+/// handle and selects an interval, such as the minimal and maximal prices
+/// of a search, the value being its lower end, and a form gets it as
+/// `low,high`. The listener reads the values as numbers, snapped to the
+/// steps, through the state.
+///
+/// The handles of an interval are read by screen readers as `Minimum` and
+/// `Maximum`, which can be translated with the `range.minimum` and
+/// `range.maximum` keys (see `i18n`).
+///
 /// ## Fields
 ///
 /// ```text
@@ -86,8 +207,14 @@ pub trait RangeListener {
 /// state:
 ///     min: 0
 ///     max: 100
+///     step: 1
 ///     value: 0
-///     stretched: false
+///     high: None
+///     vertical: false
+///     bubble: false
+///     layout: LayoutOptions::new()
+///     style: Style::new()
+///     aria_label: None
 /// listener: None
 /// ```
 ///
@@ -148,6 +275,8 @@ pub trait RangeListener {
 ///     let my_listener = MyRangeListener::new(Rc::clone(&counter));
 ///
 ///     let mut my_range = Range::new("my_range");
+///     my_range.set_step(5);
+///     my_range.set_bubble();
 ///     my_range.set_listener(Box::new(my_listener));
 /// }
 /// ```
@@ -165,13 +294,29 @@ impl Range {
             state: RangeState {
                 min: 0,
                 max: 100,
+                step: 1,
                 value: 0,
-                stretched: false,
+                high: None,
+                vertical: false,
+                bubble: false,
+                layout: LayoutOptions::new(),
+                style: Style::new(),
+                aria_label: None,
             },
             listener: None,
         }
     }
 
+    /// Get the state
+    pub fn state(&self) -> &RangeState {
+        &self.state
+    }
+
+    /// Get the state, to change it from outside of the listener
+    pub fn state_mut(&mut self) -> &mut RangeState {
+        &mut self.state
+    }
+
     /// Set the min
     pub fn set_min(&mut self, min: i32) {
         self.state.set_min(min);
@@ -182,11 +327,46 @@ impl Range {
         self.state.set_max(max);
     }
 
+    /// Set the step
+    pub fn set_step(&mut self, step: i32) {
+        self.state.set_step(step);
+    }
+
     /// Set the value
     pub fn set_value(&mut self, value: i32) {
         self.state.set_value(value);
     }
 
+    /// Set the interval, giving the Range a second handle
+    pub fn set_interval(&mut self, low: i32, high: i32) {
+        self.state.set_interval(low, high);
+    }
+
+    /// Set the vertical flag to true
+    pub fn set_vertical(&mut self) {
+        self.state.set_vertical(true);
+    }
+
+    /// Set the bubble flag to true
+    pub fn set_bubble(&mut self) {
+        self.state.set_bubble(true);
+    }
+
+    /// Set the layout options
+    pub fn set_layout(&mut self, layout: LayoutOptions) {
+        self.state.set_layout(layout);
+    }
+
+    /// Set the style
+    pub fn set_style(&mut self, style: Style) {
+        self.state.set_style(style);
+    }
+
+    /// Set the label read by screen readers, in place of the visible text
+    pub fn set_aria_label(&mut self, label: &str) {
+        self.state.set_aria_label(label);
+    }
+
     /// Set the stretched flag to true
     pub fn set_stretched(&mut self) {
         self.state.set_stretched(true);
@@ -196,26 +376,111 @@ impl Range {
     pub fn set_listener(&mut self, listener: Box<dyn RangeListener>) {
         self.listener = Some(listener);
     }
+
+    /// Return the HTML of a handle, followed by its bubble
+    fn handle(
+        &self,
+        class: &str,
+        prefix: &str,
+        value: i32,
+        label: &str,
+    ) -> String {
+        let state = &self.state;
+        let mut s = format!(
+            r#"<input oninput="{}" type="range" min="{}" max="{}" step="{}" value="{}" class="inner-range {}" aria-orientation="{}" {}>"#,
+            Event::range_js(&self.name, prefix),
+            state.min(),
+            state.max(),
+            state.step().max(1),
+            value,
+            class,
+            if state.vertical() {
+                "vertical"
+            } else {
+                "horizontal"
+            },
+            label,
+        );
+        if state.bubble() {
+            let position = if state.max() > state.min() {
+                f64::from(value - state.min())
+                    / f64::from(state.max() - state.min())
+            } else {
+                0.0
+            };
+            s.push_str(&format!(
+                r#"<output class="range-bubble" style="--position: {};" aria-hidden="true">{}</output>"#,
+                position, value
+            ));
+        }
+        s
+    }
 }
 
 impl Widget for Range {
     fn eval(&self) -> String {
-        let stretched = if self.state.stretched() {
-            "stretched"
-        } else {
-            ""
+        let layout = self.state.layout();
+        let handles = match self.state.high() {
+            None => self.handle(
+                "",
+                "",
+                self.state.value(),
+                &aria_label(self.state.aria_label()),
+            ),
+            Some(high) => {
+                let minimum =
+                    i18n::translate_or("range.minimum", "Minimum", &[]);
+                let maximum =
+                    i18n::translate_or("range.maximum", "Maximum", &[]);
+                format!(
+                    "{}{}",
+                    self.handle(
+                        "low",
+                        "",
+                        self.state.value(),
+                        &aria_label(Some(&minimum))
+                    ),
+                    self.handle(
+                        "high",
+                        "high:",
+                        high,
+                        &aria_label(Some(&maximum))
+                    )
+                )
+            }
+        };
+        let group = match self.state.high() {
+            Some(_) => format!(
+                r#"role="group" {}"#,
+                aria_label(self.state.aria_label())
+            ),
+            None => "".to_string(),
         };
         format!(
-            r#"<div id="{}" class="range {}"><input oninput="{}" type="range" min="{}" max="{}" value="{}" class="inner-range"></div>"#, 
+            r#"<div id="{}" class="range {} {} {}" style="{}{}" {}>{}</div>"#,
             self.name,
-            stretched,
-            Event::change_js(&self.name, "value"), 
-            self.state.min(),
-            self.state.max(),
-            self.state.value(),
+            if self.state.vertical() {
+                "vertical"
+            } else {
+                ""
+            },
+            if self.state.high().is_some() {
+                "dual"
+            } else {
+                ""
+            },
+            layout.class(),
+            layout.style(),
+            self.state.style().css(),
+            group,
+            handles,
         )
     }
 
+    fn name(&self) -> String {
+        self.name.to_string()
+    }
+
     fn trigger(&mut self, event: &Event) {
         match event {
             Event::Update => self.on_update(),
@@ -238,7 +503,29 @@ impl Widget for Range {
     }
 
     fn on_change(&mut self, value: &str) {
-        self.state.set_value(value.parse::<i32>().unwrap());
+        let (high, number) = match value.strip_prefix("high:") {
+            Some(number) => (true, number),
+            None => (false, value),
+        };
+        match number.parse::<i32>() {
+            Ok(number) => {
+                let number = self.state.snap(number);
+                match self.state.high() {
+                    Some(_) if high => self
+                        .state
+                        .set_high(Some(number.max(self.state.value()))),
+                    Some(upper) => self.state.set_value(number.min(upper)),
+                    None => self.state.set_value(number),
+                }
+            }
+            Err(_) => {
+                event::report_error(
+                    &self.name,
+                    &format!("invalid value: {}", value),
+                );
+                return;
+            }
+        }
         match &self.listener {
             None => (),
             Some(listener) => {
@@ -246,4 +533,12 @@ impl Widget for Range {
             }
         }
     }
+
+    fn values(&self) -> Vec<(String, String)> {
+        let value = match self.state.high() {
+            Some(high) => format!("{},{}", self.state.value(), high),
+            None => self.state.value().to_string(),
+        };
+        vec![(self.name.to_string(), value)]
+    }
 }
@@ -0,0 +1,289 @@
+use crate::utils::event::Event;
+use crate::utils::html::escape;
+use crate::widgets::widget::Widget;
+
+/// # The state of a Snackbar
+///
+/// ## Fields
+///
+/// ```text
+/// message: String
+/// action: Option<String>
+/// timeout: u32
+/// opened: bool
+/// serial: u32
+/// ```
+pub struct SnackbarState {
+    message: String,
+    action: Option<String>,
+    timeout: u32,
+    opened: bool,
+    serial: u32,
+}
+
+impl SnackbarState {
+    /// Get the message
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Get the text of the action button
+    pub fn action(&self) -> Option<&str> {
+        self.action.as_ref().map(String::as_ref)
+    }
+
+    /// Get the timeout in milliseconds
+    pub fn timeout(&self) -> u32 {
+        self.timeout
+    }
+
+    /// Get the opened flag
+    pub fn opened(&self) -> bool {
+        self.opened
+    }
+
+    /// Set the text of the action button
+    pub fn set_action(&mut self, action: Option<&str>) {
+        self.action = action.map(str::to_string);
+    }
+
+    /// Set the timeout in milliseconds, 0 keeping the Snackbar opened until
+    /// it is dismissed
+    pub fn set_timeout(&mut self, timeout: u32) {
+        self.timeout = timeout;
+    }
+
+    /// Open the Snackbar with a message, restarting its timeout
+    pub fn show(&mut self, message: &str) {
+        self.message = message.to_string();
+        self.opened = true;
+        self.serial = self.serial.wrapping_add(1);
+    }
+
+    /// Close the Snackbar
+    pub fn hide(&mut self) {
+        self.opened = false;
+    }
+}
+
+/// # The listener of a Snackbar
+pub trait SnackbarListener {
+    /// Function triggered on update event
+    fn on_update(&self, state: &mut SnackbarState);
+
+    /// Function triggered when the action button has been clicked
+    fn on_action(&self, state: &SnackbarState);
+
+    /// Function triggered when the Snackbar has been closed by its timeout
+    fn on_dismiss(&self, _state: &SnackbarState) {}
+}
+
+/// # A short message displayed at the bottom of the window
+///
+/// The Snackbar is opened by `show` and closes itself after its timeout. It
+/// can include an action button, such as "Undo": a click on it is sent to
+/// `on_action` and closes the Snackbar. Showing a new message while the
+/// Snackbar is opened replaces the message and restarts the timeout.
+///
+/// The Snackbar is stacked with the other overlays, but neither a click
+/// outside of it nor the Escape key closes it.
+///
+/// ## Fields
+///
+/// ```text
+/// name: String
+/// state: SnackbarState
+/// listener: Option<Box<dyn SnackbarListener>>
+/// ```
+///
+/// ## Default values
+///
+/// ```text
+/// name: name.to_string()
+/// state:
+///     message: "".to_string()
+///     action: None
+///     timeout: 4000
+///     opened: false
+///     serial: 0
+/// listener: None
+/// ```
+///
+/// ## Example
+///
+/// ```
+/// use std::cell::RefCell;
+/// use std::rc::Rc;
+///
+/// use neutrino::widgets::snackbar::{Snackbar, SnackbarListener, SnackbarState};
+/// use neutrino::{App, Window};
+///
+///
+/// struct Inbox {
+///     messages: Vec<String>,
+///     deleted: Option<String>,
+///     notify: bool,
+/// }
+///
+///
+/// struct MySnackbarListener {
+///     inbox: Rc<RefCell<Inbox>>,
+/// }
+///
+/// impl SnackbarListener for MySnackbarListener {
+///     fn on_update(&self, state: &mut SnackbarState) {
+///         let mut inbox = self.inbox.borrow_mut();
+///         if inbox.notify {
+///             inbox.notify = false;
+///             state.show("Message deleted");
+///         }
+///     }
+///
+///     fn on_action(&self, _state: &SnackbarState) {
+///         let mut inbox = self.inbox.borrow_mut();
+///         if let Some(message) = inbox.deleted.take() {
+///             inbox.messages.push(message);
+///         }
+///     }
+/// }
+///
+///
+/// fn main() {
+///     let inbox = Rc::new(RefCell::new(Inbox {
+///         messages: vec![],
+///         deleted: None,
+///         notify: false,
+///     }));
+///
+///     let mut my_snackbar = Snackbar::new("my_snackbar");
+///     my_snackbar.set_action("Undo");
+///     my_snackbar.set_listener(Box::new(MySnackbarListener { inbox }));
+/// }
+/// ```
+pub struct Snackbar {
+    name: String,
+    state: SnackbarState,
+    listener: Option<Box<dyn SnackbarListener>>,
+}
+
+impl Snackbar {
+    /// Create a Snackbar
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            state: SnackbarState {
+                message: "".to_string(),
+                action: None,
+                timeout: 4000,
+                opened: false,
+                serial: 0,
+            },
+            listener: None,
+        }
+    }
+
+    /// Get the state
+    pub fn state(&self) -> &SnackbarState {
+        &self.state
+    }
+
+    /// Get the state, to change it from outside of the listener
+    pub fn state_mut(&mut self) -> &mut SnackbarState {
+        &mut self.state
+    }
+
+    /// Set the text of the action button
+    pub fn set_action(&mut self, action: &str) {
+        self.state.set_action(Some(action));
+    }
+
+    /// Set the timeout in milliseconds
+    pub fn set_timeout(&mut self, timeout: u32) {
+        self.state.set_timeout(timeout);
+    }
+
+    /// Set the listener
+    pub fn set_listener(&mut self, listener: Box<dyn SnackbarListener>) {
+        self.listener = Some(listener);
+    }
+
+    fn on_dismiss(&mut self) {
+        self.state.hide();
+        match &self.listener {
+            None => (),
+            Some(listener) => {
+                listener.on_dismiss(&self.state);
+            }
+        }
+    }
+}
+
+impl Widget for Snackbar {
+    fn eval(&self) -> String {
+        if !self.state.opened() {
+            return format!(
+                r#"<div id="{}" class="snackbar"></div>"#,
+                self.name
+            );
+        }
+        let action = match self.state.action() {
+            Some(action) => format!(
+                r#"<div class="snackbar-action" onmousedown="{}">{}</div>"#,
+                Event::change_js(&self.name, "'action'"),
+                escape(action)
+            ),
+            None => "".to_string(),
+        };
+        format!(
+            r#"<div id="{}" class="snackbar opened" data-overlay="{}" data-toast="true" data-timeout="{}" data-serial="{}"><div class="snackbar-message">{}</div>{}</div>"#,
+            self.name,
+            self.name,
+            self.state.timeout(),
+            self.state.serial,
+            escape(self.state.message()),
+            action,
+        )
+    }
+
+    fn name(&self) -> String {
+        self.name.to_string()
+    }
+
+    fn trigger(&mut self, event: &Event) {
+        match event {
+            Event::Update => self.on_update(),
+            Event::Change { source, value } => {
+                if source == &self.name {
+                    self.on_change(value);
+                }
+            }
+            Event::Dismiss { source } => {
+                if source == &self.name {
+                    self.on_dismiss();
+                }
+            }
+            _ => (),
+        }
+    }
+
+    fn on_update(&mut self) {
+        match &self.listener {
+            None => (),
+            Some(listener) => {
+                listener.on_update(&mut self.state);
+            }
+        }
+    }
+
+    fn on_change(&mut self, value: &str) {
+        if value == "action" && self.state.opened() {
+            self.state.hide();
+            match &self.listener {
+                None => (),
+                Some(listener) => {
+                    listener.on_action(&self.state);
+                }
+            }
+        }
+    }
+}
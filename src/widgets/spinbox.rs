@@ -0,0 +1,385 @@
+use crate::utils::event::Event;
+use crate::utils::i18n;
+use crate::utils::layoutoptions::LayoutOptions;
+use crate::utils::style::Style;
+use crate::widgets::widget::Widget;
+
+/// # The state of a SpinBox
+///
+/// ## Fields
+///
+/// ```text
+/// value: f64
+/// min: f64
+/// max: f64
+/// step: f64
+/// decimals: u32
+/// localized: bool
+/// layout: LayoutOptions
+/// style: Style
+/// ```
+pub struct SpinBoxState {
+    value: f64,
+    min: f64,
+    max: f64,
+    step: f64,
+    decimals: u32,
+    localized: bool,
+    layout: LayoutOptions,
+    style: Style,
+}
+
+impl SpinBoxState {
+    /// Get the value
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    /// Get the min
+    pub fn min(&self) -> f64 {
+        self.min
+    }
+
+    /// Get the max
+    pub fn max(&self) -> f64 {
+        self.max
+    }
+
+    /// Get the step
+    pub fn step(&self) -> f64 {
+        self.step
+    }
+
+    /// Get the number of decimals
+    pub fn decimals(&self) -> u32 {
+        self.decimals
+    }
+
+    /// Get the localized flag
+    pub fn localized(&self) -> bool {
+        self.localized
+    }
+
+    /// Get the layout options
+    pub fn layout(&self) -> &LayoutOptions {
+        &self.layout
+    }
+
+    /// Get the style
+    pub fn style(&self) -> &Style {
+        &self.style
+    }
+
+    /// Get the stretched flag
+    pub fn stretched(&self) -> bool {
+        self.layout.stretched()
+    }
+
+    /// Set the value, clamped between the min and the max and rounded to the
+    /// number of decimals
+    pub fn set_value(&mut self, value: f64) {
+        let factor = 10f64.powi(self.decimals as i32);
+        let value = (value * factor).round() / factor;
+        self.value = value.max(self.min).min(self.max);
+    }
+
+    /// Set the min
+    pub fn set_min(&mut self, min: f64) {
+        self.min = min;
+    }
+
+    /// Set the max
+    pub fn set_max(&mut self, max: f64) {
+        self.max = max;
+    }
+
+    /// Set the step
+    pub fn set_step(&mut self, step: f64) {
+        self.step = step;
+    }
+
+    /// Set the number of decimals
+    pub fn set_decimals(&mut self, decimals: u32) {
+        self.decimals = decimals;
+    }
+
+    /// Set the localized flag: the value is written the way the locale of
+    /// the application does
+    pub fn set_localized(&mut self, localized: bool) {
+        self.localized = localized;
+    }
+
+    /// Set the layout options
+    pub fn set_layout(&mut self, layout: LayoutOptions) {
+        self.layout = layout;
+    }
+
+    /// Set the style
+    pub fn set_style(&mut self, style: Style) {
+        self.style = style;
+    }
+
+    /// Set the stretched flag
+    pub fn set_stretched(&mut self, stretched: bool) {
+        self.layout.set_stretched(stretched);
+    }
+
+    /// Increase the value by one step
+    pub fn increment(&mut self) {
+        self.set_value(self.value + self.step);
+    }
+
+    /// Decrease the value by one step
+    pub fn decrement(&mut self) {
+        self.set_value(self.value - self.step);
+    }
+
+    /// Return the value formatted with the number of decimals, in the
+    /// locale of the application if the localized flag is set
+    pub fn text(&self) -> String {
+        if self.localized {
+            i18n::format_number(&i18n::locale(), self.value, self.decimals)
+        } else {
+            format!("{:.*}", self.decimals as usize, self.value)
+        }
+    }
+
+    /// Read a typed value, in the locale of the application if the
+    /// localized flag is set
+    pub fn parse(&self, text: &str) -> Option<f64> {
+        if self.localized {
+            i18n::parse_number(&i18n::locale(), text)
+        } else {
+            text.trim().replace(',', ".").parse().ok()
+        }
+    }
+}
+
+/// # The listener of a SpinBox
+pub trait SpinBoxListener {
+    /// Function triggered on update event
+    fn on_update(&self, state: &mut SpinBoxState);
+
+    /// Function triggered on change event
+    fn on_change(&self, state: &SpinBoxState);
+}
+
+/// # A numeric input with increment and decrement buttons
+///
+/// The value can be typed, changed with the buttons or with the Up and Down
+/// keys. It is always kept between the min and the max. Typed values which
+/// are not numbers are ignored.
+///
+/// ## Fields
+///
+/// ```text
+/// name: String
+/// state: SpinBoxState
+/// listener: Option<Box<dyn SpinBoxListener>>
+/// ```
+///
+/// ## Default values
+///
+/// ```text
+/// name: name.to_string()
+/// state:
+///     value: 0.0
+///     min: 0.0
+///     max: 100.0
+///     step: 1.0
+///     decimals: 0
+///     localized: false
+///     layout: LayoutOptions::new()
+///     style: Style::new()
+/// listener: None
+/// ```
+///
+/// ## Example
+///
+/// ```
+/// use std::cell::RefCell;
+/// use std::rc::Rc;
+///
+/// use neutrino::widgets::spinbox::{SpinBox, SpinBoxListener, SpinBoxState};
+/// use neutrino::{App, Window};
+///
+///
+/// struct Order {
+///     quantity: f64,
+/// }
+///
+///
+/// struct MySpinBoxListener {
+///     order: Rc<RefCell<Order>>,
+/// }
+///
+/// impl SpinBoxListener for MySpinBoxListener {
+///     fn on_update(&self, state: &mut SpinBoxState) {
+///         state.set_value(self.order.borrow().quantity);
+///     }
+///
+///     fn on_change(&self, state: &SpinBoxState) {
+///         self.order.borrow_mut().quantity = state.value();
+///     }
+/// }
+///
+///
+/// fn main() {
+///     let order = Rc::new(RefCell::new(Order { quantity: 1.0 }));
+///
+///     let mut my_spinbox = SpinBox::new("my_spinbox");
+///     my_spinbox.set_min(1.0);
+///     my_spinbox.set_max(10.0);
+///     my_spinbox.set_listener(Box::new(MySpinBoxListener { order }));
+/// }
+/// ```
+pub struct SpinBox {
+    name: String,
+    state: SpinBoxState,
+    listener: Option<Box<dyn SpinBoxListener>>,
+}
+
+impl SpinBox {
+    /// Create a SpinBox
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            state: SpinBoxState {
+                value: 0.0,
+                min: 0.0,
+                max: 100.0,
+                step: 1.0,
+                decimals: 0,
+                localized: false,
+                layout: LayoutOptions::new(),
+                style: Style::new(),
+            },
+            listener: None,
+        }
+    }
+
+    /// Get the state
+    pub fn state(&self) -> &SpinBoxState {
+        &self.state
+    }
+
+    /// Get the state, to change it from outside of the listener
+    pub fn state_mut(&mut self) -> &mut SpinBoxState {
+        &mut self.state
+    }
+
+    /// Set the value
+    pub fn set_value(&mut self, value: f64) {
+        self.state.set_value(value);
+    }
+
+    /// Set the min
+    pub fn set_min(&mut self, min: f64) {
+        self.state.set_min(min);
+    }
+
+    /// Set the max
+    pub fn set_max(&mut self, max: f64) {
+        self.state.set_max(max);
+    }
+
+    /// Set the step
+    pub fn set_step(&mut self, step: f64) {
+        self.state.set_step(step);
+    }
+
+    /// Set the number of decimals
+    pub fn set_decimals(&mut self, decimals: u32) {
+        self.state.set_decimals(decimals);
+    }
+
+    /// Set the localized flag to true: the value is written the way the
+    /// locale of the application does, such as `1.234,5` in German
+    pub fn set_localized(&mut self) {
+        self.state.set_localized(true);
+    }
+
+    /// Set the layout options
+    pub fn set_layout(&mut self, layout: LayoutOptions) {
+        self.state.set_layout(layout);
+    }
+
+    /// Set the style
+    pub fn set_style(&mut self, style: Style) {
+        self.state.set_style(style);
+    }
+
+    /// Set the stretched flag to true
+    pub fn set_stretched(&mut self) {
+        self.state.set_stretched(true);
+    }
+
+    /// Set the listener
+    pub fn set_listener(&mut self, listener: Box<dyn SpinBoxListener>) {
+        self.listener = Some(listener);
+    }
+}
+
+impl Widget for SpinBox {
+    fn eval(&self) -> String {
+        let layout = self.state.layout();
+        format!(
+            r#"<div id="{}" class="spinbox {}" style="{}{}"><input value="{}" onchange="{}" onkeydown="{}" /><div class="spinbox-buttons"><div class="spinbox-button spinbox-increment" onmousedown="{}">+</div><div class="spinbox-button spinbox-decrement" onmousedown="{}">-</div></div></div>"#,
+            self.name,
+            layout.class(),
+            layout.style(),
+            self.state.style().css(),
+            self.state.text(),
+            Event::change_js(&self.name, "value"),
+            Event::spin_key_js(&self.name),
+            Event::change_js(&self.name, "'increment'"),
+            Event::change_js(&self.name, "'decrement'"),
+        )
+    }
+
+    fn name(&self) -> String {
+        self.name.to_string()
+    }
+
+    fn trigger(&mut self, event: &Event) {
+        match event {
+            Event::Update => self.on_update(),
+            Event::Change { source, value } => {
+                if source == &self.name {
+                    self.on_change(value);
+                }
+            }
+            _ => (),
+        }
+    }
+
+    fn on_update(&mut self) {
+        match &self.listener {
+            None => (),
+            Some(listener) => {
+                listener.on_update(&mut self.state);
+            }
+        }
+    }
+
+    fn on_change(&mut self, value: &str) {
+        match value {
+            "increment" => self.state.increment(),
+            "decrement" => self.state.decrement(),
+            _ => match self.state.parse(value) {
+                Some(value) if value.is_finite() => self.state.set_value(value),
+                _ => return,
+            },
+        }
+        match &self.listener {
+            None => (),
+            Some(listener) => {
+                listener.on_change(&self.state);
+            }
+        }
+    }
+
+    fn values(&self) -> Vec<(String, String)> {
+        vec![(self.name.to_string(), self.state.text())]
+    }
+}
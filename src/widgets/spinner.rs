@@ -0,0 +1,271 @@
+use crate::utils::event::Event;
+use crate::utils::html::{aria_label, escape};
+use crate::utils::i18n;
+use crate::utils::layoutoptions::LayoutOptions;
+use crate::utils::style::Style;
+use crate::widgets::widget::Widget;
+
+/// # The state of a Spinner
+///
+/// ## Fields
+///
+/// ```text
+/// active: bool
+/// text: String
+/// layout: LayoutOptions
+/// style: Style
+/// aria_label: Option<String>
+/// ```
+pub struct SpinnerState {
+    active: bool,
+    text: String,
+    layout: LayoutOptions,
+    style: Style,
+    aria_label: Option<String>,
+}
+
+impl SpinnerState {
+    /// Get the active flag
+    pub fn active(&self) -> bool {
+        self.active
+    }
+
+    /// Get the text
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Get the layout options
+    pub fn layout(&self) -> &LayoutOptions {
+        &self.layout
+    }
+
+    /// Get the style
+    pub fn style(&self) -> &Style {
+        &self.style
+    }
+
+    /// Get the label read by screen readers
+    pub fn aria_label(&self) -> Option<&str> {
+        self.aria_label.as_deref()
+    }
+
+    /// Set the active flag
+    pub fn set_active(&mut self, active: bool) {
+        self.active = active;
+    }
+
+    /// Set the text
+    pub fn set_text(&mut self, text: &str) {
+        self.text = text.to_string();
+    }
+
+    /// Set the layout options
+    pub fn set_layout(&mut self, layout: LayoutOptions) {
+        self.layout = layout;
+    }
+
+    /// Set the style
+    pub fn set_style(&mut self, style: Style) {
+        self.style = style;
+    }
+
+    /// Set the label read by screen readers, in place of the visible text
+    pub fn set_aria_label(&mut self, label: &str) {
+        self.aria_label = Some(label.to_string());
+    }
+}
+
+/// # The listener of a Spinner
+pub trait SpinnerListener {
+    /// Function triggered on update event
+    fn on_update(&self, state: &mut SpinnerState);
+}
+
+/// # A busy indicator
+///
+/// While it is active, the Spinner shows a spinning icon followed by its
+/// text, such as `Connecting…`, for the operations whose duration is
+/// unknown. It is empty while it is inactive, and screen readers announce
+/// its text when it becomes active. The listener sets the active flag while
+/// the operation runs.
+///
+/// Without a text nor a label, screen readers read `Loading`, which can be
+/// translated with the `spinner.loading` key (see `i18n`).
+///
+/// ## Fields
+///
+/// ```text
+/// name: String
+/// state: SpinnerState
+/// listener: Option<Box<dyn SpinnerListener>>
+/// ```
+///
+/// ## Default values
+///
+/// ```text
+/// name: name.to_string()
+/// state:
+///     active: true
+///     text: "".to_string()
+///     layout: LayoutOptions::new()
+///     style: Style::new()
+///     aria_label: None
+/// listener: None
+/// ```
+///
+/// ## Example
+///
+/// ```
+/// use std::cell::RefCell;
+/// use std::rc::Rc;
+///
+/// use neutrino::widgets::spinner::{Spinner, SpinnerListener, SpinnerState};
+///
+///
+/// struct Download {
+///     running: bool,
+/// }
+///
+///
+/// struct MySpinnerListener {
+///     download: Rc<RefCell<Download>>,
+/// }
+///
+/// impl SpinnerListener for MySpinnerListener {
+///     fn on_update(&self, state: &mut SpinnerState) {
+///         state.set_active(self.download.borrow().running);
+///     }
+/// }
+///
+///
+/// let download = Rc::new(RefCell::new(Download { running: true }));
+///
+/// let mut my_spinner = Spinner::new("my_spinner");
+/// my_spinner.set_text("Downloading the catalog…");
+/// my_spinner.set_listener(Box::new(MySpinnerListener {
+///     download: Rc::clone(&download),
+/// }));
+/// ```
+pub struct Spinner {
+    name: String,
+    state: SpinnerState,
+    listener: Option<Box<dyn SpinnerListener>>,
+}
+
+impl Spinner {
+    /// Create a Spinner
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            state: SpinnerState {
+                active: true,
+                text: "".to_string(),
+                layout: LayoutOptions::new(),
+                style: Style::new(),
+                aria_label: None,
+            },
+            listener: None,
+        }
+    }
+
+    /// Get the state
+    pub fn state(&self) -> &SpinnerState {
+        &self.state
+    }
+
+    /// Get the state, to change it from outside of the listener
+    pub fn state_mut(&mut self) -> &mut SpinnerState {
+        &mut self.state
+    }
+
+    /// Set the active flag to false: the Spinner is empty until the listener
+    /// activates it
+    pub fn set_inactive(&mut self) {
+        self.state.set_active(false);
+    }
+
+    /// Set the text
+    pub fn set_text(&mut self, text: &str) {
+        self.state.set_text(text);
+    }
+
+    /// Set the layout options
+    pub fn set_layout(&mut self, layout: LayoutOptions) {
+        self.state.set_layout(layout);
+    }
+
+    /// Set the style
+    pub fn set_style(&mut self, style: Style) {
+        self.state.set_style(style);
+    }
+
+    /// Set the label read by screen readers, in place of the visible text
+    pub fn set_aria_label(&mut self, label: &str) {
+        self.state.set_aria_label(label);
+    }
+
+    /// Set the listener
+    pub fn set_listener(&mut self, listener: Box<dyn SpinnerListener>) {
+        self.listener = Some(listener);
+    }
+}
+
+impl Widget for Spinner {
+    fn eval(&self) -> String {
+        let layout = self.state.layout();
+        let content = if !self.state.active() {
+            "".to_string()
+        } else if self.state.text().is_empty() {
+            let label = match self.state.aria_label() {
+                Some(label) => label.to_string(),
+                None => i18n::translate_or("spinner.loading", "Loading", &[]),
+            };
+            format!(
+                r#"<div class="spinner" role="img" aria-label="{}"></div>"#,
+                escape(&label)
+            )
+        } else {
+            format!(
+                r#"<div class="spinner" aria-hidden="true"></div><span class="spinner-text">{}</span>"#,
+                escape(self.state.text())
+            )
+        };
+        let label = if self.state.text().is_empty() {
+            "".to_string()
+        } else {
+            aria_label(self.state.aria_label())
+        };
+        format!(
+            r#"<div id="{}" class="spinner-box {} {}" style="{}{}" role="status" aria-live="polite" {}>{}</div>"#,
+            self.name,
+            if self.state.active() { "" } else { "inactive" },
+            layout.class(),
+            layout.style(),
+            self.state.style().css(),
+            label,
+            content
+        )
+    }
+
+    fn name(&self) -> String {
+        self.name.to_string()
+    }
+
+    fn trigger(&mut self, event: &Event) {
+        if let Event::Update = event {
+            self.on_update()
+        }
+    }
+
+    fn on_update(&mut self) {
+        match &self.listener {
+            None => (),
+            Some(listener) => {
+                listener.on_update(&mut self.state);
+            }
+        }
+    }
+
+    fn on_change(&mut self, _value: &str) {}
+}
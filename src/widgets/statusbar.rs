@@ -0,0 +1,306 @@
+use crate::utils::event::Event;
+use crate::utils::html::escape;
+use crate::utils::i18n;
+use crate::utils::layoutoptions::LayoutOptions;
+use crate::utils::queue;
+use crate::utils::style::Style;
+use crate::widgets::widget::Widget;
+
+/// # The state of a StatusBar
+///
+/// ## Fields
+///
+/// ```text
+/// text: String
+/// sections: Vec<String>
+/// sync: bool
+/// pending: usize
+/// online: bool
+/// layout: LayoutOptions
+/// style: Style
+/// ```
+pub struct StatusBarState {
+    text: String,
+    sections: Vec<String>,
+    sync: bool,
+    pending: usize,
+    online: bool,
+    layout: LayoutOptions,
+    style: Style,
+}
+
+impl StatusBarState {
+    /// Get the text
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Get the sections displayed after the text
+    pub fn sections(&self) -> &Vec<String> {
+        &self.sections
+    }
+
+    /// Get the sync flag
+    pub fn sync(&self) -> bool {
+        self.sync
+    }
+
+    /// Get the number of jobs of the sync queue waiting to be run
+    pub fn pending(&self) -> usize {
+        self.pending
+    }
+
+    /// Get the network status
+    pub fn online(&self) -> bool {
+        self.online
+    }
+
+    /// Get the layout options
+    pub fn layout(&self) -> &LayoutOptions {
+        &self.layout
+    }
+
+    /// Get the style
+    pub fn style(&self) -> &Style {
+        &self.style
+    }
+
+    /// Set the text
+    pub fn set_text(&mut self, text: &str) {
+        self.text = text.to_string();
+    }
+
+    /// Set the sections displayed after the text
+    pub fn set_sections(&mut self, sections: Vec<String>) {
+        self.sections = sections;
+    }
+
+    /// Set the sync flag
+    pub fn set_sync(&mut self, sync: bool) {
+        self.sync = sync;
+    }
+
+    /// Set the layout options
+    pub fn set_layout(&mut self, layout: LayoutOptions) {
+        self.layout = layout;
+    }
+
+    /// Set the style
+    pub fn set_style(&mut self, style: Style) {
+        self.style = style;
+    }
+
+    /// Get the text of the sync indicator
+    fn sync_text(&self) -> String {
+        let count = self.pending.to_string();
+        let args = [("count", count.as_str())];
+        if !self.online {
+            i18n::translate_or(
+                "statusbar.offline",
+                "Offline, {count, plural, =0 {all changes synced} one {# change waiting to sync} other {# changes waiting to sync}}",
+                &args,
+            )
+        } else if self.pending > 0 {
+            i18n::translate_or(
+                "statusbar.pending",
+                "{count, plural, one {# change} other {# changes}} waiting to sync",
+                &args,
+            )
+        } else {
+            i18n::translate_or("statusbar.synced", "All changes synced", &[])
+        }
+    }
+}
+
+/// # The listener of a StatusBar
+pub trait StatusBarListener {
+    /// Function triggered on update event
+    fn on_update(&self, state: &mut StatusBarState);
+}
+
+/// # A bar displaying the status of the application
+///
+/// A text and sections, such as the position of the cursor, are displayed
+/// from the left. With the sync flag, the bar ends with the number of jobs
+/// of the sync queue of the application waiting to be run, and whether the
+/// network is online (see the `queue` module of `utils`).
+///
+/// The texts of the sync indicator can be translated with the
+/// `statusbar.offline`, `statusbar.pending` and `statusbar.synced` keys (see
+/// `i18n`), the first two taking the `count` of jobs.
+///
+/// ## Fields
+///
+/// ```text
+/// name: String
+/// state: StatusBarState
+/// listener: Option<Box<dyn StatusBarListener>>
+/// ```
+///
+/// ## Default values
+///
+/// ```text
+/// name: name.to_string()
+/// state:
+///     text: "".to_string()
+///     sections: vec![]
+///     sync: false
+///     pending: 0
+///     online: true
+///     layout: LayoutOptions::new()
+///     style: Style::new()
+/// listener: None
+/// ```
+///
+/// ## Example
+///
+/// ```
+/// use neutrino::widgets::statusbar::{StatusBar, StatusBarListener, StatusBarState};
+///
+///
+/// struct MyStatusBarListener;
+///
+/// impl StatusBarListener for MyStatusBarListener {
+///     fn on_update(&self, state: &mut StatusBarState) {
+///         state.set_sections(vec!["Ln 1, Col 1".to_string()]);
+///     }
+/// }
+///
+///
+/// let mut my_statusbar = StatusBar::new("my_statusbar");
+/// my_statusbar.set_text("Ready");
+/// my_statusbar.set_sync();
+/// my_statusbar.set_listener(Box::new(MyStatusBarListener));
+/// ```
+pub struct StatusBar {
+    name: String,
+    state: StatusBarState,
+    listener: Option<Box<dyn StatusBarListener>>,
+}
+
+impl StatusBar {
+    /// Create a StatusBar
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            state: StatusBarState {
+                text: "".to_string(),
+                sections: vec![],
+                sync: false,
+                pending: 0,
+                online: true,
+                layout: LayoutOptions::new(),
+                style: Style::new(),
+            },
+            listener: None,
+        }
+    }
+
+    /// Get the state
+    pub fn state(&self) -> &StatusBarState {
+        &self.state
+    }
+
+    /// Get the state, to change it from outside of the listener
+    pub fn state_mut(&mut self) -> &mut StatusBarState {
+        &mut self.state
+    }
+
+    /// Set the text
+    pub fn set_text(&mut self, text: &str) {
+        self.state.set_text(text);
+    }
+
+    /// Set the sections displayed after the text
+    pub fn set_sections(&mut self, sections: Vec<String>) {
+        self.state.set_sections(sections);
+    }
+
+    /// Set the sync flag to true: the bar shows the sync queue
+    pub fn set_sync(&mut self) {
+        self.state.set_sync(true);
+    }
+
+    /// Set the layout options
+    pub fn set_layout(&mut self, layout: LayoutOptions) {
+        self.state.set_layout(layout);
+    }
+
+    /// Set the style
+    pub fn set_style(&mut self, style: Style) {
+        self.state.set_style(style);
+    }
+
+    /// Set the listener
+    pub fn set_listener(&mut self, listener: Box<dyn StatusBarListener>) {
+        self.listener = Some(listener);
+    }
+}
+
+impl Widget for StatusBar {
+    fn eval(&self) -> String {
+        let layout = self.state.layout();
+        let sections = self
+            .state
+            .sections()
+            .iter()
+            .map(|section| {
+                format!(
+                    r#"<span class="statusbar-section">{}</span>"#,
+                    escape(section)
+                )
+            })
+            .collect::<String>();
+        let sync = if self.state.sync() {
+            let mut class = "statusbar-sync".to_string();
+            if !self.state.online() {
+                class.push_str(" offline");
+            }
+            if self.state.pending() > 0 {
+                class.push_str(" pending");
+            }
+            format!(
+                r#"<span class="{}">{}</span>"#,
+                class,
+                escape(&self.state.sync_text())
+            )
+        } else {
+            "".to_string()
+        };
+        format!(
+            r#"<div id="{}" class="statusbar {}" role="status" style="{}{}"><span class="statusbar-text">{}</span>{}{}</div>"#,
+            self.name,
+            layout.class(),
+            layout.style(),
+            self.state.style().css(),
+            escape(self.state.text()),
+            sections,
+            sync
+        )
+    }
+
+    fn name(&self) -> String {
+        self.name.to_string()
+    }
+
+    fn trigger(&mut self, event: &Event) {
+        if let Event::Update = event {
+            self.on_update()
+        }
+    }
+
+    fn on_update(&mut self) {
+        if self.state.sync {
+            self.state.pending = queue::length();
+            self.state.online = queue::online();
+        }
+        match &self.listener {
+            None => (),
+            Some(listener) => {
+                listener.on_update(&mut self.state);
+            }
+        }
+    }
+
+    fn on_change(&mut self, _value: &str) {}
+}
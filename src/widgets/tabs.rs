@@ -1,5 +1,13 @@
-use crate::utils::event::Event;
-use crate::widgets::widget::Widget;
+use crate::utils::animation::Transition;
+use crate::utils::event::{self, Event};
+use crate::utils::html::{aria_label, escape};
+use crate::utils::icon::Icon;
+use crate::utils::layoutoptions::LayoutOptions;
+use crate::utils::metrics;
+use crate::utils::pixmap::Pixmap;
+use crate::utils::settings;
+use crate::utils::style::Style;
+use crate::widgets::widget::{self, Widget};
 
 /// # The state of a Tabs
 ///
@@ -7,15 +15,29 @@ use crate::widgets::widget::Widget;
 ///
 /// ```text
 /// titles: Vec<String>
+/// icons: Vec<Option<Pixmap>>
+/// badges: Vec<Option<String>>
 /// children: Vec<Box<dyn Widget>>
 /// selected: u32
-/// stretched: bool
+/// closable: bool
+/// reorderable: bool
+/// transition: Option<Transition>
+/// layout: LayoutOptions
+/// style: Style
+/// aria_label: Option<String>
 /// ```
 pub struct TabsState {
     titles: Vec<String>,
+    icons: Vec<Option<Pixmap>>,
+    badges: Vec<Option<String>>,
     children: Vec<Box<dyn Widget>>,
     selected: u32,
-    stretched: bool,
+    closable: bool,
+    reorderable: bool,
+    transition: Option<Transition>,
+    layout: LayoutOptions,
+    style: Style,
+    aria_label: Option<String>,
 }
 
 impl TabsState {
@@ -24,6 +46,22 @@ impl TabsState {
         &self.titles
     }
 
+    /// Get the icon of a tab
+    pub fn icon(&self, index: u32) -> Option<&Pixmap> {
+        match self.icons.get(index as usize) {
+            Some(Some(icon)) => Some(icon),
+            _ => None,
+        }
+    }
+
+    /// Get the badge of a tab, such as a number of unread items
+    pub fn badge(&self, index: u32) -> Option<&str> {
+        match self.badges.get(index as usize) {
+            Some(Some(badge)) => Some(badge),
+            _ => None,
+        }
+    }
+
     /// Get the children
     pub fn children(&self) -> &Vec<Box<dyn Widget>> {
         &self.children
@@ -34,9 +72,39 @@ impl TabsState {
         self.selected
     }
 
+    /// Get the closable flag
+    pub fn closable(&self) -> bool {
+        self.closable
+    }
+
+    /// Get the reorderable flag
+    pub fn reorderable(&self) -> bool {
+        self.reorderable
+    }
+
+    /// Get the transition played when the selected tab changes
+    pub fn transition(&self) -> Option<Transition> {
+        self.transition
+    }
+
+    /// Get the layout options
+    pub fn layout(&self) -> &LayoutOptions {
+        &self.layout
+    }
+
+    /// Get the style
+    pub fn style(&self) -> &Style {
+        &self.style
+    }
+
+    /// Get the label read by screen readers
+    pub fn aria_label(&self) -> Option<&str> {
+        self.aria_label.as_deref()
+    }
+
     /// Get the stretched flag
     pub fn stretched(&self) -> bool {
-        self.stretched
+        self.layout.stretched()
     }
 
     /// Set the titles
@@ -47,6 +115,20 @@ impl TabsState {
             .collect::<Vec<String>>();
     }
 
+    /// Set the icon of a tab
+    pub fn set_icon(&mut self, index: u32, icon: Box<dyn Icon>) {
+        if let Some(slot) = self.icons.get_mut(index as usize) {
+            *slot = Some(Pixmap::from_icon(icon));
+        }
+    }
+
+    /// Set the badge of a tab, None to remove it
+    pub fn set_badge(&mut self, index: u32, badge: Option<&str>) {
+        if let Some(slot) = self.badges.get_mut(index as usize) {
+            *slot = badge.map(str::to_string);
+        }
+    }
+
     /// Set the children
     pub fn set_children(&mut self, children: Vec<Box<dyn Widget>>) {
         self.children = children;
@@ -57,15 +139,102 @@ impl TabsState {
         self.selected = selected;
     }
 
+    /// Set the closable flag
+    pub fn set_closable(&mut self, closable: bool) {
+        self.closable = closable;
+    }
+
+    /// Set the reorderable flag
+    pub fn set_reorderable(&mut self, reorderable: bool) {
+        self.reorderable = reorderable;
+    }
+
+    /// Set the transition played when the selected tab changes
+    pub fn set_transition(&mut self, transition: Option<Transition>) {
+        self.transition = transition;
+    }
+
+    /// Set the layout options
+    pub fn set_layout(&mut self, layout: LayoutOptions) {
+        self.layout = layout;
+    }
+
+    /// Set the style
+    pub fn set_style(&mut self, style: Style) {
+        self.style = style;
+    }
+
+    /// Set the label read by screen readers, in place of the visible text
+    pub fn set_aria_label(&mut self, label: &str) {
+        self.aria_label = Some(label.to_string());
+    }
+
     /// Set the stretched flag
     pub fn set_stretched(&mut self, stretched: bool) {
-        self.stretched = stretched;
+        self.layout.set_stretched(stretched);
     }
 
-    /// Add a tab
-    fn add(&mut self, name: &str, child: Box<dyn Widget>) {
-        self.titles.push(name.to_string());
-        self.children.push(child);
+    /// Add a tab at the end
+    pub fn add(&mut self, name: &str, child: Box<dyn Widget>) {
+        self.insert(self.titles.len() as u32, name, child);
+    }
+
+    /// Insert a tab at the given index, the selected tab staying selected
+    pub fn insert(&mut self, index: u32, name: &str, child: Box<dyn Widget>) {
+        let index = (index as usize).min(self.titles.len());
+        self.titles.insert(index, name.to_string());
+        self.icons.insert(index, None);
+        self.badges.insert(index, None);
+        self.children.insert(index, child);
+        if self.titles.len() > 1 && index <= self.selected as usize {
+            self.selected += 1;
+        }
+    }
+
+    /// Remove the tab at the given index, returning its child. The next tab
+    /// is selected if the removed one was.
+    pub fn remove(&mut self, index: u32) -> Option<Box<dyn Widget>> {
+        let i = index as usize;
+        if i >= self.children.len() {
+            return None;
+        }
+        self.titles.remove(i);
+        self.icons.remove(i);
+        self.badges.remove(i);
+        let child = self.children.remove(i);
+        if index < self.selected
+            || (index == self.selected && i == self.titles.len() && i > 0)
+        {
+            self.selected -= 1;
+        }
+        Some(child)
+    }
+
+    /// Move the tab at the given index to another index, the selected tab
+    /// staying selected
+    pub fn move_tab(&mut self, from: u32, to: u32) {
+        let (from, to) = (from as usize, to as usize);
+        if from >= self.children.len() || to >= self.children.len() {
+            return;
+        }
+        let title = self.titles.remove(from);
+        self.titles.insert(to, title);
+        let icon = self.icons.remove(from);
+        self.icons.insert(to, icon);
+        let badge = self.badges.remove(from);
+        self.badges.insert(to, badge);
+        let child = self.children.remove(from);
+        self.children.insert(to, child);
+        let selected = self.selected as usize;
+        self.selected = if selected == from {
+            to
+        } else if from < selected && selected <= to {
+            selected - 1
+        } else if to <= selected && selected < from {
+            selected + 1
+        } else {
+            selected
+        } as u32;
     }
 }
 
@@ -76,10 +245,30 @@ pub trait TabsListener {
 
     /// Function triggered on change event
     fn on_change(&self, state: &TabsState);
+
+    /// Function triggered when the close button of a tab is clicked,
+    /// returning false to keep the tab open, for instance while its document
+    /// is not saved. The tab is closed by default.
+    fn on_close(&self, _index: u32, _state: &TabsState) -> bool {
+        true
+    }
+
+    /// Function triggered when a tab has been moved by dragging its title
+    fn on_move(&self, _from: u32, _to: u32, _state: &TabsState) {}
 }
 
 /// # A list of tabs
 ///
+/// Tabs can be added and removed at runtime, from the `on_update` function
+/// of the listener or with `App::widget_mut`. With the closable flag, each
+/// tab has a close button, also triggered by the Delete key, and the
+/// listener can keep the tab open in `on_close`. With the reorderable flag,
+/// the tabs can be moved by dragging their titles. A badge, such as a
+/// number of unread items, can be shown next to a title.
+///
+/// When the application has settings, the selected tab is saved and
+/// restored at start-up (see the `settings` module of `utils`).
+///
 /// ## Fields
 ///
 /// ```text
@@ -147,6 +336,10 @@ pub trait TabsListener {
 ///     fn on_update(&self, state: &mut TabsState) {
 ///         state.set_selected(self.dessert.borrow().index());
 ///     }
+///
+///     fn on_close(&self, _index: u32, state: &TabsState) -> bool {
+///         state.titles().len() > 1
+///     }
 /// }
 ///
 ///
@@ -160,6 +353,9 @@ pub trait TabsListener {
 ///
 ///     let mut my_tabs = Tabs::new("my_tabs");
 ///     my_tabs.add("Hello", Box::new(my_label));
+///     my_tabs.set_badge(0, "3");
+///     my_tabs.set_closable();
+///     my_tabs.set_reorderable();
 ///     my_tabs.set_listener(Box::new(my_listener));
 /// }
 /// ```
@@ -176,19 +372,67 @@ impl Tabs {
             name: name.to_string(),
             state: TabsState {
                 titles: vec![],
+                icons: vec![],
+                badges: vec![],
                 children: vec![],
                 selected: 0,
-                stretched: false,
+                closable: false,
+                reorderable: false,
+                transition: None,
+                layout: LayoutOptions::new(),
+                style: Style::new(),
+                aria_label: None,
             },
             listener: None,
         }
     }
 
+    /// Get the state
+    pub fn state(&self) -> &TabsState {
+        &self.state
+    }
+
+    /// Get the state, to change it from outside of the listener
+    pub fn state_mut(&mut self) -> &mut TabsState {
+        &mut self.state
+    }
+
     /// Set the selected index
     pub fn set_selected(&mut self, selected: u32) {
         self.state.set_selected(selected);
     }
 
+    /// Set the closable flag to true: each tab has a close button
+    pub fn set_closable(&mut self) {
+        self.state.set_closable(true);
+    }
+
+    /// Set the reorderable flag to true: the tabs can be moved by dragging
+    /// their titles
+    pub fn set_reorderable(&mut self) {
+        self.state.set_reorderable(true);
+    }
+
+    /// Set the transition played when the selected tab changes
+    pub fn set_transition(&mut self, transition: Transition) {
+        self.state.set_transition(Some(transition));
+    }
+
+    /// Set the layout options
+    pub fn set_layout(&mut self, layout: LayoutOptions) {
+        self.state.set_layout(layout);
+    }
+
+    /// Set the style
+    pub fn set_style(&mut self, style: Style) {
+        self.state.set_style(style);
+    }
+
+    /// Set the label read by screen readers, in place of the visible text
+    pub fn set_aria_label(&mut self, label: &str) {
+        self.state.set_aria_label(label);
+    }
+
     /// Set the stretched flag to true
     pub fn set_stretched(&mut self) {
         self.state.set_stretched(true);
@@ -199,22 +443,76 @@ impl Tabs {
         self.listener = Some(listener);
     }
 
+    /// Set the icon of a tab
+    pub fn set_icon(&mut self, index: u32, icon: Box<dyn Icon>) {
+        self.state.set_icon(index, icon);
+    }
+
+    /// Set the badge of a tab
+    pub fn set_badge(&mut self, index: u32, badge: &str) {
+        self.state.set_badge(index, Some(badge));
+    }
+
     /// Add a tab
     pub fn add(&mut self, name: &str, child: Box<dyn Widget>) {
         self.state.add(name, child);
     }
+
+    /// Close a tab, unless the listener keeps it open
+    fn close(&mut self, index: u32) {
+        if index as usize >= self.state.children.len() {
+            event::report_error(&self.name, &format!("invalid tab: {}", index));
+            return;
+        }
+        let closed = match &self.listener {
+            None => true,
+            Some(listener) => listener.on_close(index, &self.state),
+        };
+        if !closed {
+            return;
+        }
+        self.state.remove(index);
+        if let Some(listener) = &self.listener {
+            listener.on_change(&self.state);
+        }
+        self.show();
+    }
+
+    /// Move a tab dragged by its title
+    fn move_tab(&mut self, from: u32, to: u32) {
+        let count = self.state.children.len() as u32;
+        if from >= count || to >= count {
+            event::report_error(
+                &self.name,
+                &format!("invalid move: {} to {}", from, to),
+            );
+            return;
+        }
+        self.state.move_tab(from, to);
+        if let Some(listener) = &self.listener {
+            listener.on_move(from, to, &self.state);
+        }
+    }
+
+    /// Tell the usage metrics which tab is shown
+    fn show(&self) {
+        if let Some(title) = self.state.titles.get(self.state.selected as usize)
+        {
+            metrics::show(&self.name, title);
+        }
+    }
 }
 
 impl Widget for Tabs {
     fn eval(&self) -> String {
-        let stretched = if self.state.stretched() {
-            "stretched"
-        } else {
-            ""
-        };
+        let layout = self.state.layout();
         let mut s = format!(
-            r#"<div id="{}" class="tabs {}"><div class="tab-titles">"#,
-            self.name, stretched
+            r#"<div id="{}" class="tabs {}" style="{}{}"><div class="tab-titles" role="tablist" {}>"#,
+            self.name,
+            layout.class(),
+            layout.style(),
+            self.state.style().css(),
+            aria_label(self.state.aria_label()),
         );
         let tabs_number = self.state.titles.len();
         for (i, title) in self.state.titles.iter().enumerate() {
@@ -233,36 +531,132 @@ impl Widget for Tabs {
             } else {
                 ""
             };
+            let icon = match self.state.icon(i as u32) {
+                Some(icon) => format!(
+                    r#"<img src="data:image/{};base64,{}" />"#,
+                    icon.extension(),
+                    icon.data()
+                ),
+                None => "".to_string(),
+            };
+            let badge = match self.state.badge(i as u32) {
+                Some(badge) => {
+                    format!(
+                        r#"<span class="tab-badge">{}</span>"#,
+                        escape(badge)
+                    )
+                }
+                None => "".to_string(),
+            };
+            let close = if self.state.closable() {
+                format!(
+                    r#"<span class="tab-close" role="button" aria-label="Close {}" onmousedown="{}" onclick="{}">×</span>"#,
+                    escape(title),
+                    Event::stop_js(),
+                    Event::change_js(&self.name, &format!("'close:{}'", i))
+                )
+            } else {
+                "".to_string()
+            };
+            let mousedown = if self.state.reorderable() {
+                Event::tab_drag_js(&self.name, i)
+            } else {
+                Event::change_js(&self.name, &format!("'{}'", i))
+            };
+            let previous = ((i + tabs_number - 1) % tabs_number).to_string();
+            let next = ((i + 1) % tabs_number).to_string();
+            let mut keys = vec![
+                ("ArrowLeft", previous),
+                ("ArrowRight", next),
+                ("Home", "0".to_string()),
+                ("End", (tabs_number - 1).to_string()),
+            ];
+            if self.state.closable() {
+                keys.push(("Delete", format!("close:{}", i)));
+            }
             s.push_str(&format!(
-                r#"<div class="tab-title {} {} {}" onmousedown="{}">{}</div>"#,
+                r#"<div class="tab-title {} {} {}" role="tab" aria-selected="{}" tabindex="{}" onmousedown="{}" onkeydown="{}">{}{}{}{}</div>"#,
                 first,
                 last,
                 selected,
-                Event::change_js(&self.name, &format!("'{}'", i)),
-                title
+                !selected.is_empty(),
+                if selected.is_empty() { -1 } else { 0 },
+                mousedown,
+                Event::keys_js(&self.name, &keys),
+                icon,
+                escape(title),
+                badge,
+                close
             ));
         }
-        s.push_str(&format!(
-            r#"</div><div class="tab">{}</div>"#,
-            self.state.children[self.state.selected() as usize].eval()
-        ));
+        let child =
+            match self.state.children.get(self.state.selected() as usize) {
+                Some(child) => widget::eval(child.as_ref()),
+                None => "".to_string(),
+            };
+        match self.state.transition() {
+            Some(transition) => s.push_str(&format!(
+                r#"</div><div id="{}-tab-{}" class="tab {}" role="tabpanel">{}</div>"#,
+                self.name,
+                self.state.selected(),
+                transition.css(),
+                child
+            )),
+            None => {
+                s.push_str(&format!(
+                    r#"</div><div class="tab" role="tabpanel">{}</div>"#,
+                    child
+                ))
+            }
+        }
         s.push_str("</div>");
         s
     }
 
+    fn name(&self) -> String {
+        self.name.to_string()
+    }
+
     fn trigger(&mut self, event: &Event) {
         match event {
             Event::Update => self.on_update(),
             Event::Change { source, value } => {
                 if source == &self.name {
                     self.on_change(value);
-                } else {
-                    self.state.children[self.state.selected as usize]
-                        .trigger(event);
+                } else if let Some(child) =
+                    self.state.children.get_mut(self.state.selected as usize)
+                {
+                    if widget::reaches(child.as_ref(), event) {
+                        widget::trigger(child.as_mut(), event);
+                    }
                 };
             }
+            Event::Resize { .. } => {
+                for child in self.state.children.iter_mut() {
+                    widget::trigger(child.as_mut(), event);
+                }
+            }
+            Event::Restore => {
+                let key = format!("{}.selected", self.name);
+                let count = self.state.children.len() as u32;
+                match settings::get(&key).and_then(|value| value.as_u32()) {
+                    Some(selected) if selected < count => {
+                        self.state.set_selected(selected)
+                    }
+                    _ => (),
+                }
+                for child in self.state.children.iter_mut() {
+                    widget::trigger(child.as_mut(), event);
+                }
+            }
             _ => {
-                self.state.children[self.state.selected as usize].trigger(event)
+                if let Some(child) =
+                    self.state.children.get_mut(self.state.selected as usize)
+                {
+                    if widget::reaches(child.as_ref(), event) {
+                        widget::trigger(child.as_mut(), event);
+                    }
+                }
             }
         }
     }
@@ -274,12 +668,47 @@ impl Widget for Tabs {
                 listener.on_update(&mut self.state);
             }
         }
+        self.show();
     }
 
     fn on_change(&mut self, value: &str) {
-        let selected = value.parse::<i32>().unwrap();
+        if let Some(index) = value.strip_prefix("close:") {
+            match index.parse::<u32>() {
+                Ok(index) => self.close(index),
+                Err(_) => event::report_error(
+                    &self.name,
+                    &format!("invalid tab: {}", index),
+                ),
+            }
+            return;
+        }
+        if let Some(indexes) = value.strip_prefix("move:") {
+            let indexes = indexes.split_once(':').and_then(|(from, to)| {
+                Some((from.parse::<u32>().ok()?, to.parse::<u32>().ok()?))
+            });
+            match indexes {
+                Some((from, to)) => self.move_tab(from, to),
+                None => event::report_error(
+                    &self.name,
+                    &format!("invalid move: {}", value),
+                ),
+            }
+            return;
+        }
+        let selected = match value.parse::<i32>() {
+            Ok(selected) => selected,
+            Err(_) => {
+                event::report_error(
+                    &self.name,
+                    &format!("invalid tab: {}", value),
+                );
+                return;
+            }
+        };
         if selected > -1 {
             self.state.set_selected(selected as u32);
+            let key = format!("{}.selected", self.name);
+            settings::save(&key, selected.into());
         }
         match &self.listener {
             None => (),
@@ -287,5 +716,44 @@ impl Widget for Tabs {
                 listener.on_change(&self.state);
             }
         }
+        self.show();
+    }
+
+    fn values(&self) -> Vec<(String, String)> {
+        self.state
+            .children
+            .iter()
+            .flat_map(|widget| widget.values())
+            .collect()
+    }
+
+    fn is_valid(&self) -> bool {
+        self.state.children.iter().all(|widget| widget.is_valid())
+    }
+
+    fn validate(&mut self) -> bool {
+        let mut valid = true;
+        for widget in self.state.children.iter_mut() {
+            if !widget.validate() {
+                valid = false;
+            }
+        }
+        valid
+    }
+
+    fn children(&self) -> Vec<&(dyn Widget + 'static)> {
+        self.state
+            .children
+            .iter()
+            .map(|widget| widget.as_ref())
+            .collect()
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut (dyn Widget + 'static)> {
+        self.state
+            .children
+            .iter_mut()
+            .map(|widget| widget.as_mut() as &mut dyn Widget)
+            .collect()
     }
 }
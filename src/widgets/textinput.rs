@@ -1,6 +1,61 @@
 use crate::utils::event::Event;
+use crate::utils::html::{aria_label, escape};
+use crate::utils::layoutoptions::LayoutOptions;
+use crate::utils::style::Style;
+use crate::utils::validator::{self, Validator};
 use crate::widgets::widget::Widget;
 
+/// # The type of a TextInput
+///
+/// The characters typed in a `Password` input are hidden. `Email` and
+/// `Number` inputs let the webview check the format of the value.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InputType {
+    Text,
+    Password,
+    Email,
+    Number,
+}
+
+impl InputType {
+    /// Return the HTML type attribute of the input
+    fn html(&self) -> &str {
+        match self {
+            InputType::Text => "text",
+            InputType::Password => "password",
+            InputType::Email => "email",
+            InputType::Number => "number",
+        }
+    }
+}
+
+/// # When a TextInput sends its change events
+///
+/// By default, the value is sent when the input loses the focus or when
+/// Enter is pressed. It can instead be sent on each keystroke, once the
+/// typing pauses for a delay in milliseconds (`Debounce`), or at most once
+/// per delay while typing (`Throttle`). The pending value is sent right away
+/// when the input loses the focus.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ChangeMode {
+    Blur,
+    Input,
+    Debounce(u32),
+    Throttle(u32),
+}
+
+impl ChangeMode {
+    /// Return the name of the mode and its delay, as given to the page
+    fn js(&self) -> (&str, u32) {
+        match self {
+            ChangeMode::Blur => ("blur", 0),
+            ChangeMode::Input => ("input", 0),
+            ChangeMode::Debounce(delay) => ("debounce", *delay),
+            ChangeMode::Throttle(delay) => ("throttle", *delay),
+        }
+    }
+}
+
 /// # The state of a TextInput
 ///
 /// ## Fields
@@ -8,12 +63,28 @@ use crate::widgets::widget::Widget;
 /// ```text
 /// value: String
 /// size: u32
-/// stretched: bool
+/// input_type: InputType
+/// pattern: Option<String>
+/// mask: Option<String>
+/// change_mode: ChangeMode
+/// layout: LayoutOptions
+/// style: Style
+/// aria_label: Option<String>
+/// validators: Vec<Validator>
+/// error: Option<String>
 /// ```
 pub struct TextInputState {
     value: String,
     size: u32,
-    stretched: bool,
+    input_type: InputType,
+    pattern: Option<String>,
+    mask: Option<String>,
+    change_mode: ChangeMode,
+    layout: LayoutOptions,
+    style: Style,
+    aria_label: Option<String>,
+    validators: Vec<Validator>,
+    error: Option<String>,
 }
 
 impl TextInputState {
@@ -27,9 +98,54 @@ impl TextInputState {
         self.size
     }
 
+    /// Get the input type
+    pub fn input_type(&self) -> InputType {
+        self.input_type
+    }
+
+    /// Get the pattern
+    pub fn pattern(&self) -> Option<&str> {
+        self.pattern.as_ref().map(String::as_ref)
+    }
+
+    /// Get the mask
+    pub fn mask(&self) -> Option<&str> {
+        self.mask.as_ref().map(String::as_ref)
+    }
+
+    /// Get the change mode
+    pub fn change_mode(&self) -> ChangeMode {
+        self.change_mode
+    }
+
+    /// Get the layout options
+    pub fn layout(&self) -> &LayoutOptions {
+        &self.layout
+    }
+
+    /// Get the style
+    pub fn style(&self) -> &Style {
+        &self.style
+    }
+
+    /// Get the label read by screen readers
+    pub fn aria_label(&self) -> Option<&str> {
+        self.aria_label.as_deref()
+    }
+
     /// Get the stretched flag
     pub fn stretched(&self) -> bool {
-        self.stretched
+        self.layout.stretched()
+    }
+
+    /// Get the validators
+    pub fn validators(&self) -> &Vec<Validator> {
+        &self.validators
+    }
+
+    /// Get the error message
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_ref().map(String::as_ref)
     }
 
     /// Set the value
@@ -42,9 +158,69 @@ impl TextInputState {
         self.size = size;
     }
 
+    /// Set the input type
+    pub fn set_input_type(&mut self, input_type: InputType) {
+        self.input_type = input_type;
+    }
+
+    /// Set the pattern, a regular expression the value is checked against
+    /// by the webview
+    pub fn set_pattern(&mut self, pattern: Option<&str>) {
+        self.pattern = pattern.map(str::to_string);
+    }
+
+    /// Set the mask applied to the typed characters. In a mask, `9` stands
+    /// for a digit, `a` for a letter and `*` for a digit or a letter. The
+    /// other characters are inserted as they are.
+    pub fn set_mask(&mut self, mask: Option<&str>) {
+        self.mask = mask.map(str::to_string);
+    }
+
+    /// Set the change mode
+    pub fn set_change_mode(&mut self, change_mode: ChangeMode) {
+        self.change_mode = change_mode;
+    }
+
+    /// Set the layout options
+    pub fn set_layout(&mut self, layout: LayoutOptions) {
+        self.layout = layout;
+    }
+
+    /// Set the style
+    pub fn set_style(&mut self, style: Style) {
+        self.style = style;
+    }
+
+    /// Set the label read by screen readers, in place of the visible text
+    pub fn set_aria_label(&mut self, label: &str) {
+        self.aria_label = Some(label.to_string());
+    }
+
     /// Set the stretched flag
     pub fn set_stretched(&mut self, stretched: bool) {
-        self.stretched = stretched;
+        self.layout.set_stretched(stretched);
+    }
+
+    /// Set the error message
+    pub fn set_error(&mut self, error: Option<&str>) {
+        self.error = error.map(str::to_string);
+    }
+
+    /// Add a validator
+    pub fn add_validator(&mut self, validator: Validator) {
+        self.validators.push(validator);
+    }
+
+    /// Return true if the value satisfies all the validators
+    pub fn is_valid(&self) -> bool {
+        validator::validate(&self.validators, &self.value).is_none()
+    }
+
+    /// Check the value against the validators, set the error message
+    /// accordingly and return true if the value is valid
+    pub fn validate(&mut self) -> bool {
+        self.error = validator::validate(&self.validators, &self.value);
+        self.error.is_none()
     }
 }
 
@@ -59,6 +235,17 @@ pub trait TextInputListener {
 
 /// # A zone where text can be written.
 ///
+/// The input type lets the TextInput hide the typed characters, as for a
+/// password, or restrict them to an email address or a number. A pattern can
+/// be given to the webview to highlight invalid values, and a mask formats
+/// the value while it is typed (`99/99/9999` for a date for instance).
+///
+/// The change mode sets how often the typed value is sent to the listener:
+/// a debounced input waits for the typing to pause, which keeps the window
+/// responsive when `on_change` does real work. The caret is kept in place,
+/// and the characters typed while a value is being sent are not lost, when
+/// the input is rendered again.
+///
 /// ## Fields
 ///
 /// ```text
@@ -74,7 +261,15 @@ pub trait TextInputListener {
 /// state:
 ///     value: "TextInput".to_string()
 ///     size: 10
-///     stretched: false
+///     input_type: InputType::Text
+///     pattern: None
+///     mask: None
+///     change_mode: ChangeMode::Blur
+///     layout: LayoutOptions::new()
+///     style: Style::new()
+///     aria_label: None
+///     validators: vec![]
+///     error: None
 /// listener: None
 /// ```
 ///
@@ -152,12 +347,30 @@ impl TextInput {
             state: TextInputState {
                 value: "TextInput".to_string(),
                 size: 10,
-                stretched: false,
+                input_type: InputType::Text,
+                pattern: None,
+                mask: None,
+                change_mode: ChangeMode::Blur,
+                layout: LayoutOptions::new(),
+                style: Style::new(),
+                aria_label: None,
+                validators: vec![],
+                error: None,
             },
             listener: None,
         }
     }
 
+    /// Get the state
+    pub fn state(&self) -> &TextInputState {
+        &self.state
+    }
+
+    /// Get the state, to change it from outside of the listener
+    pub fn state_mut(&mut self) -> &mut TextInputState {
+        &mut self.state
+    }
+
     /// Set the value
     pub fn set_value(&mut self, value: &str) {
         self.state.set_value(value);
@@ -168,11 +381,57 @@ impl TextInput {
         self.state.set_size(size);
     }
 
+    /// Set the input type
+    pub fn set_input_type(&mut self, input_type: InputType) {
+        self.state.set_input_type(input_type);
+    }
+
+    /// Set the pattern checked by the webview
+    pub fn set_pattern(&mut self, pattern: &str) {
+        self.state.set_pattern(Some(pattern));
+    }
+
+    /// Set the mask applied to the typed characters
+    pub fn set_mask(&mut self, mask: &str) {
+        self.state.set_mask(Some(mask));
+    }
+
+    /// Set the change mode
+    pub fn set_change_mode(&mut self, change_mode: ChangeMode) {
+        self.state.set_change_mode(change_mode);
+    }
+
+    /// Send the value once the typing pauses for the given delay, in
+    /// milliseconds
+    pub fn set_debounce(&mut self, delay: u32) {
+        self.state.set_change_mode(ChangeMode::Debounce(delay));
+    }
+
+    /// Set the layout options
+    pub fn set_layout(&mut self, layout: LayoutOptions) {
+        self.state.set_layout(layout);
+    }
+
+    /// Set the style
+    pub fn set_style(&mut self, style: Style) {
+        self.state.set_style(style);
+    }
+
+    /// Set the label read by screen readers, in place of the visible text
+    pub fn set_aria_label(&mut self, label: &str) {
+        self.state.set_aria_label(label);
+    }
+
     /// Set the stretched flag to true
     pub fn set_stretched(&mut self) {
         self.state.set_stretched(true);
     }
 
+    /// Add a validator, checked each time the value changes
+    pub fn add_validator(&mut self, validator: Validator) {
+        self.state.add_validator(validator);
+    }
+
     /// Set the listener
     pub fn set_listener(&mut self, listener: Box<dyn TextInputListener>) {
         self.listener = Some(listener);
@@ -181,22 +440,65 @@ impl TextInput {
 
 impl Widget for TextInput {
     fn eval(&self) -> String {
-        let stretched = if self.state.stretched() {
-            "stretched"
-        } else {
-            ""
+        let layout = self.state.layout();
+        let (invalid, error) = match self.state.error() {
+            Some(error) => (
+                "invalid",
+                format!(
+                    r#"<div class="textinput-error">{}</div>"#,
+                    escape(error)
+                ),
+            ),
+            None => ("", "".to_string()),
+        };
+        let pattern = match self.state.pattern() {
+            Some(pattern) => format!(r#"pattern="{}""#, escape(pattern)),
+            None => "".to_string(),
+        };
+        let maxlength = match self.state.mask() {
+            Some(mask) => mask.chars().count() as u32,
+            None => self.state.size(),
         };
+        let (oninput, onchange) =
+            match (self.state.change_mode(), self.state.mask()) {
+                (ChangeMode::Blur, Some(mask)) => (
+                    Event::mask_js(mask),
+                    Event::change_js(&self.name, "value"),
+                ),
+                (ChangeMode::Blur, None) => {
+                    ("".to_string(), Event::change_js(&self.name, "value"))
+                }
+                (mode, mask) => {
+                    let (mode, delay) = mode.js();
+                    (
+                        Event::queue_change_js(&self.name, mask, mode, delay),
+                        Event::send_change_js(&self.name),
+                    )
+                }
+            };
         format!(
-            r#"<div id="{}" class="textinput {}"><input size="{}" maxlength="{}" value="{}" onchange="{}" /></div>"#,
+            r#"<div id="{}" class="textinput {} {}" style="{}{}"><input type="{}" size="{}" maxlength="{}" {} {} value="{}" oninput="{}" onchange="{}" />{}</div>"#,
             self.name,
-            stretched,
-            self.state.size(),
+            layout.class(),
+            invalid,
+            layout.style(),
+            self.state.style().css(),
+            self.state.input_type().html(),
             self.state.size(),
-            self.state.value(),
-            Event::change_js(&self.name, "value")
+            maxlength,
+            pattern,
+            aria_label(self.state.aria_label()),
+            escape(self.state.value()),
+            oninput,
+            onchange,
+            error,
         )
     }
 
+    fn name(&self) -> String {
+        self.name.to_string()
+    }
+
     fn trigger(&mut self, event: &Event) {
         match event {
             Event::Update => self.on_update(),
@@ -220,6 +522,7 @@ impl Widget for TextInput {
 
     fn on_change(&mut self, value: &str) {
         self.state.set_value(value);
+        self.state.validate();
         match &self.listener {
             None => (),
             Some(listener) => {
@@ -227,4 +530,16 @@ impl Widget for TextInput {
             }
         }
     }
+
+    fn values(&self) -> Vec<(String, String)> {
+        vec![(self.name.to_string(), self.state.value().to_string())]
+    }
+
+    fn is_valid(&self) -> bool {
+        self.state.is_valid()
+    }
+
+    fn validate(&mut self) -> bool {
+        self.state.validate()
+    }
 }
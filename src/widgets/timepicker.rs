@@ -0,0 +1,447 @@
+use crate::utils::datetime::Time;
+use crate::utils::event::Event;
+use crate::utils::html::escape;
+use crate::utils::layoutoptions::LayoutOptions;
+use crate::utils::style::Style;
+use crate::widgets::widget::Widget;
+
+/// # The state of a TimePicker
+///
+/// ## Fields
+///
+/// ```text
+/// time: Option<Time>
+/// min: Time
+/// max: Time
+/// step: u32
+/// format: String
+/// opened: bool
+/// placeholder: String
+/// layout: LayoutOptions
+/// style: Style
+/// ```
+pub struct TimePickerState {
+    time: Option<Time>,
+    min: Time,
+    max: Time,
+    step: u32,
+    format: String,
+    opened: bool,
+    placeholder: String,
+    layout: LayoutOptions,
+    style: Style,
+}
+
+impl TimePickerState {
+    /// Get the time
+    pub fn time(&self) -> Option<Time> {
+        self.time
+    }
+
+    /// Get the min time
+    pub fn min(&self) -> Time {
+        self.min
+    }
+
+    /// Get the max time
+    pub fn max(&self) -> Time {
+        self.max
+    }
+
+    /// Get the step between the proposed times, in minutes
+    pub fn step(&self) -> u32 {
+        self.step
+    }
+
+    /// Get the format
+    pub fn format(&self) -> &str {
+        &self.format
+    }
+
+    /// Get the opened flag
+    pub fn opened(&self) -> bool {
+        self.opened
+    }
+
+    /// Get the placeholder
+    pub fn placeholder(&self) -> &str {
+        &self.placeholder
+    }
+
+    /// Get the layout options
+    pub fn layout(&self) -> &LayoutOptions {
+        &self.layout
+    }
+
+    /// Get the style
+    pub fn style(&self) -> &Style {
+        &self.style
+    }
+
+    /// Get the stretched flag
+    pub fn stretched(&self) -> bool {
+        self.layout.stretched()
+    }
+
+    /// Return the time as an ISO 8601 string, or an empty string if no time
+    /// is set
+    pub fn value(&self) -> String {
+        match self.time {
+            Some(time) => time.iso(),
+            None => "".to_string(),
+        }
+    }
+
+    /// Return the time formatted with the format
+    pub fn text(&self) -> String {
+        match self.time {
+            Some(time) => time.format(&self.format),
+            None => "".to_string(),
+        }
+    }
+
+    /// Return the proposed times, from the min to the max time
+    pub fn choices(&self) -> Vec<Time> {
+        let step = self.step.max(1) * 60;
+        (self.min.seconds()..=self.max.seconds())
+            .step_by(step as usize)
+            .map(Time::from_seconds)
+            .collect()
+    }
+
+    /// Set the time, clamped between the min and the max times
+    pub fn set_time(&mut self, time: Option<Time>) {
+        self.time = time.map(|time| time.max(self.min).min(self.max));
+    }
+
+    /// Set the min time
+    pub fn set_min(&mut self, min: Time) {
+        self.min = min;
+        self.set_time(self.time);
+    }
+
+    /// Set the max time
+    pub fn set_max(&mut self, max: Time) {
+        self.max = max;
+        self.set_time(self.time);
+    }
+
+    /// Set the step between the proposed times, in minutes
+    pub fn set_step(&mut self, step: u32) {
+        self.step = step;
+    }
+
+    /// Set the format
+    pub fn set_format(&mut self, format: &str) {
+        self.format = format.to_string();
+    }
+
+    /// Set the opened flag
+    pub fn set_opened(&mut self, opened: bool) {
+        self.opened = opened;
+    }
+
+    /// Set the placeholder
+    pub fn set_placeholder(&mut self, placeholder: &str) {
+        self.placeholder = placeholder.to_string();
+    }
+
+    /// Set the layout options
+    pub fn set_layout(&mut self, layout: LayoutOptions) {
+        self.layout = layout;
+    }
+
+    /// Set the style
+    pub fn set_style(&mut self, style: Style) {
+        self.style = style;
+    }
+
+    /// Set the stretched flag
+    pub fn set_stretched(&mut self, stretched: bool) {
+        self.layout.set_stretched(stretched);
+    }
+}
+
+/// # The listener of a TimePicker
+pub trait TimePickerListener {
+    /// Function triggered on update event
+    fn on_update(&self, state: &mut TimePickerState);
+
+    /// Function triggered on change event
+    fn on_change(&self, state: &TimePickerState);
+}
+
+/// # A time input with a list of proposed times
+///
+/// The time can be typed with the format, or picked in the list opened by
+/// the button, which proposes times from the min to the max time every step
+/// minutes. It is always kept between the min and the max times. Typed
+/// values which do not match the format (nor ISO 8601) are ignored.
+///
+/// The value of the TimePicker is the time as an ISO 8601 string
+/// (HH:MM:SS), or an empty string if no time is set.
+///
+/// ## Fields
+///
+/// ```text
+/// name: String
+/// state: TimePickerState
+/// listener: Option<Box<dyn TimePickerListener>>
+/// ```
+///
+/// ## Default values
+///
+/// ```text
+/// name: name.to_string()
+/// state:
+///     time: None
+///     min: 00:00:00
+///     max: 23:59:59
+///     step: 30
+///     format: "%H:%M".to_string()
+///     opened: false
+///     placeholder: "".to_string()
+///     layout: LayoutOptions::new()
+///     style: Style::new()
+/// listener: None
+/// ```
+///
+/// ## Example
+///
+/// ```
+/// use std::cell::RefCell;
+/// use std::rc::Rc;
+///
+/// use neutrino::utils::datetime::Time;
+/// use neutrino::widgets::timepicker::{
+///     TimePicker, TimePickerListener, TimePickerState,
+/// };
+/// use neutrino::{App, Window};
+///
+///
+/// struct Alarm {
+///     time: Option<Time>,
+/// }
+///
+///
+/// struct MyTimePickerListener {
+///     alarm: Rc<RefCell<Alarm>>,
+/// }
+///
+/// impl TimePickerListener for MyTimePickerListener {
+///     fn on_update(&self, state: &mut TimePickerState) {
+///         state.set_time(self.alarm.borrow().time);
+///     }
+///
+///     fn on_change(&self, state: &TimePickerState) {
+///         self.alarm.borrow_mut().time = state.time();
+///     }
+/// }
+///
+///
+/// fn main() {
+///     let alarm = Rc::new(RefCell::new(Alarm { time: None }));
+///
+///     let mut my_timepicker = TimePicker::new("my_timepicker");
+///     my_timepicker.set_format("%I:%M %p");
+///     my_timepicker.set_step(15);
+///     my_timepicker.set_listener(Box::new(MyTimePickerListener { alarm }));
+/// }
+/// ```
+pub struct TimePicker {
+    name: String,
+    state: TimePickerState,
+    listener: Option<Box<dyn TimePickerListener>>,
+}
+
+impl TimePicker {
+    /// Create a TimePicker
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            state: TimePickerState {
+                time: None,
+                min: Time::from_seconds(0),
+                max: Time::from_seconds(86399),
+                step: 30,
+                format: "%H:%M".to_string(),
+                opened: false,
+                placeholder: "".to_string(),
+                layout: LayoutOptions::new(),
+                style: Style::new(),
+            },
+            listener: None,
+        }
+    }
+
+    /// Get the state
+    pub fn state(&self) -> &TimePickerState {
+        &self.state
+    }
+
+    /// Get the state, to change it from outside of the listener
+    pub fn state_mut(&mut self) -> &mut TimePickerState {
+        &mut self.state
+    }
+
+    /// Set the time
+    pub fn set_time(&mut self, time: Time) {
+        self.state.set_time(Some(time));
+    }
+
+    /// Set the min time
+    pub fn set_min(&mut self, min: Time) {
+        self.state.set_min(min);
+    }
+
+    /// Set the max time
+    pub fn set_max(&mut self, max: Time) {
+        self.state.set_max(max);
+    }
+
+    /// Set the step between the proposed times, in minutes
+    pub fn set_step(&mut self, step: u32) {
+        self.state.set_step(step);
+    }
+
+    /// Set the format
+    pub fn set_format(&mut self, format: &str) {
+        self.state.set_format(format);
+    }
+
+    /// Set the placeholder
+    pub fn set_placeholder(&mut self, placeholder: &str) {
+        self.state.set_placeholder(placeholder);
+    }
+
+    /// Set the layout options
+    pub fn set_layout(&mut self, layout: LayoutOptions) {
+        self.state.set_layout(layout);
+    }
+
+    /// Set the style
+    pub fn set_style(&mut self, style: Style) {
+        self.state.set_style(style);
+    }
+
+    /// Set the stretched flag to true
+    pub fn set_stretched(&mut self) {
+        self.state.set_stretched(true);
+    }
+
+    /// Set the listener
+    pub fn set_listener(&mut self, listener: Box<dyn TimePickerListener>) {
+        self.listener = Some(listener);
+    }
+}
+
+impl Widget for TimePicker {
+    fn eval(&self) -> String {
+        let layout = self.state.layout();
+        let opened = if self.state.opened() { "opened" } else { "" };
+        let mut s = format!(
+            r#"<div id="{}" class="timepicker {}" style="{}{}"><div class="timepicker-field"><input value="{}" placeholder="{}" autocomplete="off" onchange="{}" /><div class="timepicker-button {}" onmousedown="{}">&#9662;</div></div>"#,
+            self.name,
+            layout.class(),
+            layout.style(),
+            self.state.style().css(),
+            escape(&self.state.text()),
+            escape(self.state.placeholder()),
+            Event::change_js(&self.name, "'text:' + value"),
+            opened,
+            Event::change_js(&self.name, "'toggle'"),
+        );
+        if self.state.opened() {
+            s.push_str(&format!(
+                r#"<div class="timepicker-choices" data-placement="bottom" data-overlay="{}">"#,
+                self.name
+            ));
+            for time in self.state.choices() {
+                let selected = if self.state.time() == Some(time) {
+                    "selected"
+                } else {
+                    ""
+                };
+                s.push_str(&format!(
+                    r#"<div class="timepicker-choice {}" onmousedown="{}">{}</div>"#,
+                    selected,
+                    Event::change_js(
+                        &self.name,
+                        &format!("'pick:{}'", time.iso())
+                    ),
+                    escape(&time.format(self.state.format())),
+                ));
+            }
+            s.push_str("</div>");
+        }
+        s.push_str("</div>");
+        s
+    }
+
+    fn name(&self) -> String {
+        self.name.to_string()
+    }
+
+    fn trigger(&mut self, event: &Event) {
+        match event {
+            Event::Update => self.on_update(),
+            Event::Change { source, value } => {
+                if source == &self.name {
+                    self.on_change(value);
+                } else {
+                    self.state.set_opened(false);
+                }
+            }
+            Event::Focus { .. } | Event::Hover { .. } => (),
+            _ => self.state.set_opened(false),
+        }
+    }
+
+    fn on_update(&mut self) {
+        match &self.listener {
+            None => (),
+            Some(listener) => {
+                listener.on_update(&mut self.state);
+            }
+        }
+    }
+
+    fn on_change(&mut self, value: &str) {
+        let time = if value == "toggle" {
+            let opened = self.state.opened();
+            self.state.set_opened(!opened);
+            return;
+        } else if let Some(time) = value.strip_prefix("pick:") {
+            self.state.set_opened(false);
+            match Time::parse_iso(time) {
+                Some(time) => Some(time),
+                None => return,
+            }
+        } else if let Some(text) = value.strip_prefix("text:") {
+            self.state.set_opened(false);
+            if text.trim().is_empty() {
+                None
+            } else {
+                match Time::parse(text, self.state.format())
+                    .or_else(|| Time::parse_iso(text))
+                {
+                    Some(time) => Some(time),
+                    None => return,
+                }
+            }
+        } else {
+            return;
+        };
+        self.state.set_time(time);
+        match &self.listener {
+            None => (),
+            Some(listener) => {
+                listener.on_change(&self.state);
+            }
+        }
+    }
+
+    fn values(&self) -> Vec<(String, String)> {
+        vec![(self.name.to_string(), self.state.value())]
+    }
+}
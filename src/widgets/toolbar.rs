@@ -0,0 +1,532 @@
+use crate::utils::event::Event;
+use crate::utils::html::escape;
+use crate::utils::icon::Icon;
+use crate::utils::layoutoptions::LayoutOptions;
+use crate::utils::pixmap::Pixmap;
+use crate::utils::style::Style;
+use crate::widgets::widget::Widget;
+
+/// # A button of a ToolBar
+///
+/// ## Fields
+///
+/// ```text
+/// text: String
+/// icon: Option<Pixmap>
+/// toggle: bool
+/// checked: bool
+/// disabled: bool
+/// ```
+///
+/// ## Default values
+///
+/// ```text
+/// text: text.to_string()
+/// icon: None
+/// toggle: false
+/// checked: false
+/// disabled: false
+/// ```
+pub struct ToolButton {
+    text: String,
+    icon: Option<Pixmap>,
+    toggle: bool,
+    checked: bool,
+    disabled: bool,
+}
+
+impl ToolButton {
+    /// Create a ToolButton
+    pub fn new(text: &str) -> Self {
+        Self {
+            text: text.to_string(),
+            icon: None,
+            toggle: false,
+            checked: false,
+            disabled: false,
+        }
+    }
+
+    /// Get the text
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Get the icon
+    pub fn icon(&self) -> Option<&Pixmap> {
+        self.icon.as_ref()
+    }
+
+    /// Get the toggle flag
+    pub fn toggle(&self) -> bool {
+        self.toggle
+    }
+
+    /// Get the checked flag
+    pub fn checked(&self) -> bool {
+        self.checked
+    }
+
+    /// Get the disabled flag
+    pub fn disabled(&self) -> bool {
+        self.disabled
+    }
+
+    /// Set the icon
+    pub fn set_icon(&mut self, icon: Box<dyn Icon>) {
+        self.icon = Some(Pixmap::from_icon(icon));
+    }
+
+    /// Set the toggle flag to true
+    pub fn set_toggle(&mut self) {
+        self.toggle = true;
+    }
+
+    /// Set the checked flag to true
+    pub fn set_checked(&mut self) {
+        self.checked = true;
+    }
+
+    /// Set the disabled flag to true
+    pub fn set_disabled(&mut self) {
+        self.disabled = true;
+    }
+
+    /// Return the HTML representation of the button in the bar
+    fn eval(&self, name: &str, index: usize) -> String {
+        let content = match self.icon() {
+            Some(icon) => format!(
+                r#"<img src="data:image/{};base64,{}" />"#,
+                icon.extension(),
+                icon.data()
+            ),
+            None => format!("<span>{}</span>", escape(&self.text)),
+        };
+        format!(
+            r#"<div class="toolbar-button {}" title="{}" data-index="{}" onmousedown="{}">{}</div>"#,
+            self.classes(),
+            escape(&self.text),
+            index,
+            Event::change_js(name, &format!("'{}'", index)),
+            content
+        )
+    }
+
+    /// Return the HTML representation of the button in the overflow menu
+    fn eval_entry(&self, name: &str, index: usize) -> String {
+        let icon = match self.icon() {
+            Some(icon) => format!(
+                r#"<img src="data:image/{};base64,{}" />"#,
+                icon.extension(),
+                icon.data()
+            ),
+            None => "".to_string(),
+        };
+        format!(
+            r#"<div class="toolbar-entry {}" data-index="{}" onmousedown="{}">{}<span>{}</span></div>"#,
+            self.classes(),
+            index,
+            Event::change_js(name, &format!("'{}'", index)),
+            icon,
+            escape(&self.text)
+        )
+    }
+
+    fn classes(&self) -> String {
+        let mut classes = vec![];
+        if self.toggle {
+            classes.push("toggle");
+        }
+        if self.checked {
+            classes.push("checked");
+        }
+        if self.disabled {
+            classes.push("disabled");
+        }
+        classes.join(" ")
+    }
+}
+
+/// # An item of a ToolBar
+pub enum ToolItem {
+    Button(ToolButton),
+    Separator,
+}
+
+/// # The state of a ToolBar
+///
+/// ## Fields
+///
+/// ```text
+/// items: Vec<ToolItem>
+/// selected: Option<u32>
+/// opened: bool
+/// layout: LayoutOptions
+/// style: Style
+/// ```
+pub struct ToolBarState {
+    items: Vec<ToolItem>,
+    selected: Option<u32>,
+    opened: bool,
+    layout: LayoutOptions,
+    style: Style,
+}
+
+impl ToolBarState {
+    /// Get the items
+    pub fn items(&self) -> &Vec<ToolItem> {
+        &self.items
+    }
+
+    /// Get the button at the given index, if the item is a button
+    pub fn button(&self, index: u32) -> Option<&ToolButton> {
+        match self.items.get(index as usize) {
+            Some(ToolItem::Button(button)) => Some(button),
+            _ => None,
+        }
+    }
+
+    /// Get the index of the last clicked button
+    pub fn selected(&self) -> Option<u32> {
+        self.selected
+    }
+
+    /// Get the opened flag of the overflow menu
+    pub fn opened(&self) -> bool {
+        self.opened
+    }
+
+    /// Get the layout options
+    pub fn layout(&self) -> &LayoutOptions {
+        &self.layout
+    }
+
+    /// Get the style
+    pub fn style(&self) -> &Style {
+        &self.style
+    }
+
+    /// Get the stretched flag
+    pub fn stretched(&self) -> bool {
+        self.layout.stretched()
+    }
+
+    /// Set the checked flag of the button at the given index
+    pub fn set_checked(&mut self, index: u32, checked: bool) {
+        if let Some(ToolItem::Button(button)) =
+            self.items.get_mut(index as usize)
+        {
+            button.checked = checked;
+        }
+    }
+
+    /// Set the disabled flag of the button at the given index
+    pub fn set_disabled(&mut self, index: u32, disabled: bool) {
+        if let Some(ToolItem::Button(button)) =
+            self.items.get_mut(index as usize)
+        {
+            button.disabled = disabled;
+        }
+    }
+
+    /// Set the opened flag of the overflow menu
+    pub fn set_opened(&mut self, opened: bool) {
+        self.opened = opened;
+    }
+
+    /// Set the layout options
+    pub fn set_layout(&mut self, layout: LayoutOptions) {
+        self.layout = layout;
+    }
+
+    /// Set the style
+    pub fn set_style(&mut self, style: Style) {
+        self.style = style;
+    }
+
+    /// Set the stretched flag
+    pub fn set_stretched(&mut self, stretched: bool) {
+        self.layout.set_stretched(stretched);
+    }
+
+    /// Add an item
+    fn add(&mut self, item: ToolItem) {
+        self.items.push(item);
+    }
+}
+
+/// # The listener of a ToolBar
+pub trait ToolBarListener {
+    /// Function triggered on update event
+    fn on_update(&self, state: &mut ToolBarState);
+
+    /// Function triggered when a button has been clicked. The checked flag of
+    /// a toggle button is already switched.
+    fn on_change(&self, state: &ToolBarState);
+}
+
+/// # A horizontal bar of buttons
+///
+/// Buttons with an icon only display the icon, their text being shown as a
+/// tooltip. A toggle button switches its checked flag when it is clicked,
+/// and separators group the buttons.
+///
+/// When the window is too narrow to display all the items, the ones which do
+/// not fit are hidden and a button at the end of the bar opens a menu
+/// listing them. The menu is an overlay closed by a click outside of it or
+/// by the Escape key.
+///
+/// Items are indexed in the order they are added, separators included.
+///
+/// ## Fields
+///
+/// ```text
+/// name: String
+/// state: ToolBarState
+/// listener: Option<Box<dyn ToolBarListener>>
+/// ```
+///
+/// ## Default values
+///
+/// ```text
+/// name: name.to_string()
+/// state:
+///     items: vec![]
+///     selected: None
+///     opened: false
+///     layout: LayoutOptions::new()
+///     style: Style::new()
+/// listener: None
+/// ```
+///
+/// ## Example
+///
+/// ```
+/// use std::cell::RefCell;
+/// use std::rc::Rc;
+///
+/// use neutrino::utils::icon::BreezeIcon;
+/// use neutrino::widgets::toolbar::{
+///     ToolBar, ToolBarListener, ToolBarState, ToolButton,
+/// };
+/// use neutrino::{App, Window};
+///
+///
+/// struct Editor {
+///     bold: bool,
+///     saved: bool,
+/// }
+///
+///
+/// struct MyToolBarListener {
+///     editor: Rc<RefCell<Editor>>,
+/// }
+///
+/// impl ToolBarListener for MyToolBarListener {
+///     fn on_update(&self, state: &mut ToolBarState) {
+///         let editor = self.editor.borrow();
+///         state.set_disabled(0, editor.saved);
+///         state.set_checked(2, editor.bold);
+///     }
+///
+///     fn on_change(&self, state: &ToolBarState) {
+///         let mut editor = self.editor.borrow_mut();
+///         match state.selected() {
+///             Some(0) => editor.saved = true,
+///             Some(2) => editor.bold = !editor.bold,
+///             _ => (),
+///         }
+///     }
+/// }
+///
+///
+/// fn main() {
+///     let editor = Rc::new(RefCell::new(Editor {
+///         bold: false,
+///         saved: true,
+///     }));
+///
+///     let mut save = ToolButton::new("Save");
+///     save.set_icon(Box::new(BreezeIcon::Check));
+///
+///     let mut bold = ToolButton::new("Bold");
+///     bold.set_toggle();
+///
+///     let mut my_toolbar = ToolBar::new("my_toolbar");
+///     my_toolbar.add(save);
+///     my_toolbar.add_separator();
+///     my_toolbar.add(bold);
+///     my_toolbar.set_stretched();
+///     my_toolbar.set_listener(Box::new(MyToolBarListener { editor }));
+/// }
+/// ```
+pub struct ToolBar {
+    name: String,
+    state: ToolBarState,
+    listener: Option<Box<dyn ToolBarListener>>,
+}
+
+impl ToolBar {
+    /// Create a ToolBar
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            state: ToolBarState {
+                items: vec![],
+                selected: None,
+                opened: false,
+                layout: LayoutOptions::new(),
+                style: Style::new(),
+            },
+            listener: None,
+        }
+    }
+
+    /// Get the state
+    pub fn state(&self) -> &ToolBarState {
+        &self.state
+    }
+
+    /// Get the state, to change it from outside of the listener
+    pub fn state_mut(&mut self) -> &mut ToolBarState {
+        &mut self.state
+    }
+
+    /// Set the layout options
+    pub fn set_layout(&mut self, layout: LayoutOptions) {
+        self.state.set_layout(layout);
+    }
+
+    /// Set the style
+    pub fn set_style(&mut self, style: Style) {
+        self.state.set_style(style);
+    }
+
+    /// Set the stretched flag to true
+    pub fn set_stretched(&mut self) {
+        self.state.set_stretched(true);
+    }
+
+    /// Set the listener
+    pub fn set_listener(&mut self, listener: Box<dyn ToolBarListener>) {
+        self.listener = Some(listener);
+    }
+
+    /// Add a ToolButton
+    pub fn add(&mut self, button: ToolButton) {
+        self.state.add(ToolItem::Button(button));
+    }
+
+    /// Add a separator
+    pub fn add_separator(&mut self) {
+        self.state.add(ToolItem::Separator);
+    }
+}
+
+impl Widget for ToolBar {
+    fn eval(&self) -> String {
+        let layout = self.state.layout();
+        let mut s = format!(
+            r#"<div id="{}" class="toolbar {}" style="{}{}"><div class="toolbar-items">"#,
+            self.name,
+            layout.class(),
+            layout.style(),
+            self.state.style().css(),
+        );
+        for (i, item) in self.state.items().iter().enumerate() {
+            match item {
+                ToolItem::Button(button) => {
+                    s.push_str(&button.eval(&self.name, i))
+                }
+                ToolItem::Separator => s.push_str(&format!(
+                    r#"<div class="toolbar-separator" data-index="{}"></div>"#,
+                    i
+                )),
+            }
+        }
+        s.push_str(&format!(
+            r#"</div><div id="{}-more" class="toolbar-more" onmousedown="{}">&#8230;</div>"#,
+            self.name,
+            Event::change_js(&self.name, "'more'")
+        ));
+        if self.state.opened() {
+            s.push_str(&format!(
+                r#"<div class="toolbar-overflow" data-anchor="{}-more" data-placement="bottom" data-overlay="{}">"#,
+                self.name, self.name
+            ));
+            for (i, item) in self.state.items().iter().enumerate() {
+                match item {
+                    ToolItem::Button(button) => {
+                        s.push_str(&button.eval_entry(&self.name, i))
+                    }
+                    ToolItem::Separator => s.push_str(&format!(
+                        r#"<div class="toolbar-entry-separator" data-index="{}"></div>"#,
+                        i
+                    )),
+                }
+            }
+            s.push_str("</div>");
+        }
+        s.push_str("</div>");
+        s
+    }
+
+    fn name(&self) -> String {
+        self.name.to_string()
+    }
+
+    fn trigger(&mut self, event: &Event) {
+        match event {
+            Event::Update => self.on_update(),
+            Event::Change { source, value } => {
+                if source == &self.name {
+                    self.on_change(value);
+                }
+            }
+            Event::Dismiss { source } => {
+                if source == &self.name {
+                    self.state.set_opened(false);
+                }
+            }
+            _ => (),
+        }
+    }
+
+    fn on_update(&mut self) {
+        match &self.listener {
+            None => (),
+            Some(listener) => {
+                listener.on_update(&mut self.state);
+            }
+        }
+    }
+
+    fn on_change(&mut self, value: &str) {
+        if value == "more" {
+            let opened = self.state.opened();
+            self.state.set_opened(!opened);
+            return;
+        }
+        let index = match value.parse::<u32>() {
+            Ok(index) => index,
+            Err(_) => return,
+        };
+        let (toggle, checked) = match self.state.button(index) {
+            Some(button) if !button.disabled() => {
+                (button.toggle(), button.checked())
+            }
+            _ => return,
+        };
+        if toggle {
+            self.state.set_checked(index, !checked);
+        }
+        self.state.selected = Some(index);
+        self.state.set_opened(false);
+        match &self.listener {
+            None => (),
+            Some(listener) => {
+                listener.on_change(&self.state);
+            }
+        }
+    }
+}
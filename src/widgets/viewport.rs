@@ -0,0 +1,366 @@
+use crate::utils::event::Event;
+use crate::utils::layoutoptions::LayoutOptions;
+use crate::utils::style::Style;
+use crate::widgets::widget::{self, Widget};
+
+/// # The state of a Viewport
+///
+/// ## Fields
+///
+/// ```text
+/// child: Option<Box<dyn Widget>>
+/// scale: f64
+/// x: f64
+/// y: f64
+/// min_scale: f64
+/// max_scale: f64
+/// layout: LayoutOptions
+/// style: Style
+/// ```
+pub struct ViewportState {
+    child: Option<Box<dyn Widget>>,
+    scale: f64,
+    x: f64,
+    y: f64,
+    min_scale: f64,
+    max_scale: f64,
+    layout: LayoutOptions,
+    style: Style,
+}
+
+impl ViewportState {
+    /// Get the child
+    pub fn child(&self) -> Option<&(dyn Widget + 'static)> {
+        self.child.as_ref().map(|child| child.as_ref())
+    }
+
+    /// Get the scale
+    pub fn scale(&self) -> f64 {
+        self.scale
+    }
+
+    /// Get the horizontal translation in pixels
+    pub fn x(&self) -> f64 {
+        self.x
+    }
+
+    /// Get the vertical translation in pixels
+    pub fn y(&self) -> f64 {
+        self.y
+    }
+
+    /// Get the minimum scale
+    pub fn min_scale(&self) -> f64 {
+        self.min_scale
+    }
+
+    /// Get the maximum scale
+    pub fn max_scale(&self) -> f64 {
+        self.max_scale
+    }
+
+    /// Get the layout options
+    pub fn layout(&self) -> &LayoutOptions {
+        &self.layout
+    }
+
+    /// Get the style
+    pub fn style(&self) -> &Style {
+        &self.style
+    }
+
+    /// Get the stretched flag
+    pub fn stretched(&self) -> bool {
+        self.layout.stretched()
+    }
+
+    /// Set the child
+    pub fn set_child(&mut self, child: Box<dyn Widget>) {
+        self.child = Some(child);
+    }
+
+    /// Set the scale, clamped between the minimum and maximum scales
+    pub fn set_scale(&mut self, scale: f64) {
+        self.scale = scale.max(self.min_scale).min(self.max_scale);
+    }
+
+    /// Set the translation in pixels
+    pub fn set_translation(&mut self, x: f64, y: f64) {
+        self.x = x;
+        self.y = y;
+    }
+
+    /// Set the minimum and maximum scales
+    pub fn set_scale_bounds(&mut self, min_scale: f64, max_scale: f64) {
+        self.min_scale = min_scale;
+        self.max_scale = max_scale;
+        self.set_scale(self.scale);
+    }
+
+    /// Set the layout options
+    pub fn set_layout(&mut self, layout: LayoutOptions) {
+        self.layout = layout;
+    }
+
+    /// Set the style
+    pub fn set_style(&mut self, style: Style) {
+        self.style = style;
+    }
+
+    /// Set the stretched flag
+    pub fn set_stretched(&mut self, stretched: bool) {
+        self.layout.set_stretched(stretched);
+    }
+
+    /// Reset the transform to its identity
+    pub fn reset(&mut self) {
+        self.scale = 1.0;
+        self.x = 0.0;
+        self.y = 0.0;
+    }
+}
+
+/// # The listener of a Viewport
+pub trait ViewportListener {
+    /// Function triggered on update event
+    fn on_update(&self, state: &mut ViewportState);
+
+    /// Function triggered on change event, when the user zooms or pans
+    fn on_change(&self, state: &ViewportState);
+}
+
+/// # A zoomable and pannable area displaying a large child
+///
+/// The mouse wheel (or the pinch gesture of a touchpad) zooms around the
+/// pointer. Dragging the background, dragging with the middle button or
+/// dragging while holding Shift pans the content. The resulting transform is
+/// reported to the listener so it can be persisted.
+///
+/// ## Fields
+///
+/// ```text
+/// name: String
+/// state: ViewportState
+/// listener: Option<Box<dyn ViewportListener>>
+/// ```
+///
+/// ## Default values
+///
+/// ```text
+/// name: name.to_string()
+/// state:
+///     child: None
+///     scale: 1.0
+///     x: 0.0
+///     y: 0.0
+///     min_scale: 0.1
+///     max_scale: 10.0
+///     layout: LayoutOptions::new()
+///     style: Style::new()
+/// listener: None
+/// ```
+///
+/// ## Example
+///
+/// ```
+/// use std::cell::RefCell;
+/// use std::rc::Rc;
+///
+/// use neutrino::widgets::canvas::Canvas;
+/// use neutrino::widgets::viewport::{Viewport, ViewportListener, ViewportState};
+/// use neutrino::{App, Window};
+///
+///
+/// struct Camera {
+///     scale: f64,
+///     x: f64,
+///     y: f64,
+/// }
+///
+///
+/// struct MyViewportListener {
+///     camera: Rc<RefCell<Camera>>,
+/// }
+///
+/// impl ViewportListener for MyViewportListener {
+///     fn on_update(&self, state: &mut ViewportState) {
+///         let camera = self.camera.borrow();
+///         state.set_scale(camera.scale);
+///         state.set_translation(camera.x, camera.y);
+///     }
+///
+///     fn on_change(&self, state: &ViewportState) {
+///         let mut camera = self.camera.borrow_mut();
+///         camera.scale = state.scale();
+///         camera.x = state.x();
+///         camera.y = state.y();
+///     }
+/// }
+///
+///
+/// fn main() {
+///     let camera = Rc::new(RefCell::new(Camera { scale: 1.0, x: 0.0, y: 0.0 }));
+///
+///     let mut my_canvas = Canvas::new("my_canvas");
+///     my_canvas.set_size(4000, 4000);
+///
+///     let mut my_viewport = Viewport::new("my_viewport");
+///     my_viewport.set_child(Box::new(my_canvas));
+///     my_viewport.set_listener(Box::new(MyViewportListener { camera }));
+/// }
+/// ```
+pub struct Viewport {
+    name: String,
+    state: ViewportState,
+    listener: Option<Box<dyn ViewportListener>>,
+}
+
+impl Viewport {
+    /// Create a Viewport
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            state: ViewportState {
+                child: None,
+                scale: 1.0,
+                x: 0.0,
+                y: 0.0,
+                min_scale: 0.1,
+                max_scale: 10.0,
+                layout: LayoutOptions::new(),
+                style: Style::new(),
+            },
+            listener: None,
+        }
+    }
+
+    /// Get the state
+    pub fn state(&self) -> &ViewportState {
+        &self.state
+    }
+
+    /// Get the state, to change it from outside of the listener
+    pub fn state_mut(&mut self) -> &mut ViewportState {
+        &mut self.state
+    }
+
+    /// Set the child
+    pub fn set_child(&mut self, child: Box<dyn Widget>) {
+        self.state.set_child(child);
+    }
+
+    /// Set the scale
+    pub fn set_scale(&mut self, scale: f64) {
+        self.state.set_scale(scale);
+    }
+
+    /// Set the translation in pixels
+    pub fn set_translation(&mut self, x: f64, y: f64) {
+        self.state.set_translation(x, y);
+    }
+
+    /// Set the minimum and maximum scales
+    pub fn set_scale_bounds(&mut self, min_scale: f64, max_scale: f64) {
+        self.state.set_scale_bounds(min_scale, max_scale);
+    }
+
+    /// Set the layout options
+    pub fn set_layout(&mut self, layout: LayoutOptions) {
+        self.state.set_layout(layout);
+    }
+
+    /// Set the style
+    pub fn set_style(&mut self, style: Style) {
+        self.state.set_style(style);
+    }
+
+    /// Set the stretched flag to true
+    pub fn set_stretched(&mut self) {
+        self.state.set_stretched(true);
+    }
+
+    /// Set the listener
+    pub fn set_listener(&mut self, listener: Box<dyn ViewportListener>) {
+        self.listener = Some(listener);
+    }
+}
+
+impl Widget for Viewport {
+    fn eval(&self) -> String {
+        let layout = self.state.layout();
+        let child = match self.state.child() {
+            Some(child) => widget::eval(child),
+            None => "".to_string(),
+        };
+        format!(
+            r#"<div id="{}" class="viewport {}" style="{}{}" data-scale="{}" data-x="{}" data-y="{}" data-min-scale="{}" data-max-scale="{}"><div class="viewport-content" style="transform: translate({}px, {}px) scale({});">{}</div></div>"#,
+            self.name,
+            layout.class(),
+            layout.style(),
+            self.state.style().css(),
+            self.state.scale(),
+            self.state.x(),
+            self.state.y(),
+            self.state.min_scale(),
+            self.state.max_scale(),
+            self.state.x(),
+            self.state.y(),
+            self.state.scale(),
+            child,
+        )
+    }
+
+    fn name(&self) -> String {
+        self.name.to_string()
+    }
+
+    fn trigger(&mut self, event: &Event) {
+        match event {
+            Event::Update => self.on_update(),
+            Event::Change { source, value } => {
+                if source == &self.name {
+                    self.on_change(value)
+                }
+            }
+            _ => (),
+        }
+        if let Some(child) = self.state.child.as_mut() {
+            if widget::reaches(child.as_ref(), event) {
+                widget::trigger(child.as_mut(), event);
+            }
+        }
+    }
+
+    fn on_update(&mut self) {
+        match &self.listener {
+            None => (),
+            Some(listener) => {
+                listener.on_update(&mut self.state);
+            }
+        }
+    }
+
+    fn on_change(&mut self, value: &str) {
+        let values = value
+            .split(';')
+            .map(|v| v.parse::<f64>())
+            .collect::<Vec<_>>();
+        if let [Ok(scale), Ok(x), Ok(y)] = values.as_slice() {
+            self.state.set_scale(*scale);
+            self.state.set_translation(*x, *y);
+            match &self.listener {
+                None => (),
+                Some(listener) => {
+                    listener.on_change(&self.state);
+                }
+            }
+        }
+    }
+
+    fn children(&self) -> Vec<&(dyn Widget + 'static)> {
+        match self.state.child() {
+            Some(child) => vec![child],
+            None => vec![],
+        }
+    }
+}
@@ -1,4 +1,95 @@
-use crate::utils::event::Event;
+use std::any::{Any, TypeId};
+use std::cell::RefCell;
+use std::panic::{self, AssertUnwindSafe};
+
+use crate::utils::event::{self, Event};
+use crate::utils::html::escape;
+use crate::utils::i18n;
+use crate::utils::observe::{self, Status};
+
+thread_local! {
+    static FAILURES: RefCell<Vec<Failure>> = const { RefCell::new(Vec::new()) };
+}
+
+/// A widget which failed to render or to handle an event
+struct Failure {
+    name: String,
+    message: String,
+    render: bool,
+}
+
+/// # Downcasting of a widget to its type, to look it up by its type
+///
+/// It is implemented for the widgets of a widget tree, which are `'static`,
+/// so the widgets do not implement it themselves. A widget borrowing its
+/// data can still be rendered, but not looked up by its type.
+///
+/// ## Example
+///
+/// ```
+/// use neutrino::utils::event::Event;
+/// use neutrino::widgets::button::Button;
+/// use neutrino::widgets::label::Label;
+/// use neutrino::widgets::widget::{self, Downcast, Widget};
+///
+///
+/// struct Title<'a> {
+///     text: &'a str,
+/// }
+///
+/// impl Widget for Title<'_> {
+///     fn eval(&self) -> String {
+///         format!(r#"<h1 id="title">{}</h1>"#, self.text)
+///     }
+///
+///     fn name(&self) -> String {
+///         "title".to_string()
+///     }
+///
+///     fn trigger(&mut self, _event: &Event) {}
+///
+///     fn on_update(&mut self) {}
+///
+///     fn on_change(&mut self, _value: &str) {}
+/// }
+///
+///
+/// let text = "Neutrino".to_string();
+/// assert!(widget::eval(&Title { text: &text }).contains("Neutrino"));
+///
+/// let mut my_label: Box<dyn Widget> = Box::new(Label::new("my_label"));
+/// assert!(my_label.downcast_ref::<Button>().is_none());
+///
+/// my_label.downcast_mut::<Label>().unwrap().set_text("Found");
+/// assert_eq!(my_label.downcast_ref::<Label>().unwrap().state().text(), "Found");
+/// ```
+pub trait Downcast {
+    /// Return the widget as a `T`, None if it has another type
+    fn downcast_ref<T: Widget + 'static>(&self) -> Option<&T>;
+
+    /// Return the widget as a mutable `T`, None if it has another type
+    fn downcast_mut<T: Widget + 'static>(&mut self) -> Option<&mut T>;
+}
+
+impl Downcast for dyn Widget {
+    fn downcast_ref<T: Widget + 'static>(&self) -> Option<&T> {
+        if self.kind_id() == TypeId::of::<T>() {
+            // The widget is a T, as it has the type id of T
+            Some(unsafe { &*(self as *const dyn Widget as *const T) })
+        } else {
+            None
+        }
+    }
+
+    fn downcast_mut<T: Widget + 'static>(&mut self) -> Option<&mut T> {
+        if self.kind_id() == TypeId::of::<T>() {
+            // The widget is a T, as it has the type id of T
+            Some(unsafe { &mut *(self as *mut dyn Widget as *mut T) })
+        } else {
+            None
+        }
+    }
+}
 
 /// # Trait that any of the widgets have to implement
 pub trait Widget {
@@ -13,4 +104,298 @@ pub trait Widget {
 
     /// Function triggered on change event
     fn on_change(&mut self, _value: &str);
+
+    /// Return the names and values of the form fields held by the widget
+    fn values(&self) -> Vec<(String, String)> {
+        vec![]
+    }
+
+    /// Return true if the form fields held by the widget are valid
+    fn is_valid(&self) -> bool {
+        true
+    }
+
+    /// Validate the form fields held by the widget, displaying their error
+    /// messages, and return true if they are valid
+    fn validate(&mut self) -> bool {
+        true
+    }
+
+    /// Return the name of the widget, which is the id of the root element of
+    /// its HTML
+    fn name(&self) -> String;
+
+    /// Return the type of the widget, such as `Button`
+    fn kind(&self) -> &str {
+        let name = std::any::type_name::<Self>();
+        name.rsplit("::").next().unwrap_or(name)
+    }
+
+    /// Return the id of the type of the widget, to downcast it (see
+    /// `Downcast`)
+    fn kind_id(&self) -> TypeId
+    where
+        Self: 'static,
+    {
+        TypeId::of::<Self>()
+    }
+
+    /// Return the widgets held by the widget
+    fn children(&self) -> Vec<&(dyn Widget + 'static)> {
+        vec![]
+    }
+
+    /// Return the widgets held by the widget, to change them
+    fn children_mut(&mut self) -> Vec<&mut (dyn Widget + 'static)> {
+        vec![]
+    }
+
+    /// Return the HTML of the widget while the future it observes is
+    /// running or after it failed (see `observe`), None to be rendered as
+    /// the default loading or error placeholder
+    fn status_placeholder(&self, _status: &Status) -> Option<String> {
+        None
+    }
+}
+
+/// # The content displayed by a data widget in place of its data
+///
+/// It is a text, or a widget such as a container with an icon, a message and
+/// a button reloading the data.
+pub enum Placeholder {
+    Text(String),
+    Widget(Box<dyn Widget>),
+}
+
+impl Placeholder {
+    /// Return the HTML of the placeholder
+    pub fn eval(&self) -> String {
+        match self {
+            Placeholder::Text(text) => format!(
+                r#"<span class="placeholder-text">{}</span>"#,
+                escape(text)
+            ),
+            Placeholder::Widget(widget) => eval(widget.as_ref()),
+        }
+    }
+
+    /// Return the widget of the placeholder, if it is one
+    pub fn widget(&self) -> Option<&(dyn Widget + 'static)> {
+        match self {
+            Placeholder::Text(_) => None,
+            Placeholder::Widget(widget) => Some(widget.as_ref()),
+        }
+    }
+
+    /// Return the widget of the placeholder, if it is one, to change it
+    pub fn widget_mut(&mut self) -> Option<&mut (dyn Widget + 'static)> {
+        match self {
+            Placeholder::Text(_) => None,
+            Placeholder::Widget(widget) => Some(widget.as_mut()),
+        }
+    }
+}
+
+/// Return true if an event has to be triggered in a widget: the events sent
+/// to a widget only reach it and the widgets holding it, the other events
+/// reach the whole widget tree
+pub fn reaches(widget: &dyn Widget, event: &Event) -> bool {
+    match event.target() {
+        Some(target) => holds(widget, target),
+        None => true,
+    }
+}
+
+/// Return true if a widget or one of its descendants has the given name
+fn holds(widget: &dyn Widget, name: &str) -> bool {
+    widget.name() == name
+        || widget
+            .children()
+            .into_iter()
+            .any(|child| holds(child, name))
+}
+
+/// Return the HTML of a widget, or an error placeholder in its place if
+/// rendering it, or handling the last event, panicked. The error is sent to
+/// the window once, and the rest of the window is rendered as usual. A
+/// widget observing a future is rendered as a loading placeholder until it
+/// completes, and as an error placeholder if it fails, unless it renders its
+/// own with `status_placeholder` (see `observe`).
+///
+/// The widgets holding other widgets render them with this function, and
+/// send them the events with `trigger`.
+///
+/// The text of the placeholder can be translated with the `widget.error`
+/// key (see `i18n`). The debug builds also display the panic message.
+///
+/// ## Example
+///
+/// ```
+/// use neutrino::testing::Harness;
+/// use neutrino::utils::event::Event;
+/// use neutrino::widgets::container::Container;
+/// use neutrino::widgets::label::Label;
+/// use neutrino::widgets::widget::Widget;
+/// use neutrino::Window;
+///
+///
+/// struct Broken;
+///
+/// impl Widget for Broken {
+///     fn eval(&self) -> String {
+///         panic!("no data");
+///     }
+///
+///     fn name(&self) -> String {
+///         "broken".to_string()
+///     }
+///
+///     fn trigger(&mut self, _event: &Event) {}
+///
+///     fn on_update(&mut self) {}
+///
+///     fn on_change(&mut self, _value: &str) {}
+/// }
+///
+///
+/// let mut my_label = Label::new("my_label");
+/// my_label.set_text("Still here");
+///
+/// let mut my_container = Container::new("my_container");
+/// my_container.add(Box::new(Broken));
+/// my_container.add(Box::new(my_label));
+///
+/// let mut my_window = Window::new();
+/// my_window.set_child(Box::new(my_container));
+///
+/// let harness = Harness::new(my_window);
+/// assert!(harness.element("broken").unwrap().contains("widget-error"));
+/// assert!(harness.contains("Still here"));
+/// ```
+pub fn eval(widget: &dyn Widget) -> String {
+    match panic::catch_unwind(AssertUnwindSafe(|| widget.eval())) {
+        Ok(html) => {
+            if observe::any_status() {
+                let name = name(widget);
+                if let Some(status) = observe::status(&name) {
+                    return match widget.status_placeholder(&status) {
+                        Some(html) => html,
+                        None => match status {
+                            Status::Loading => observe::placeholder(&name),
+                            Status::Failed(message) => {
+                                placeholder(&name, &message)
+                            }
+                        },
+                    };
+                }
+            }
+            if FAILURES.with(|failures| failures.borrow().is_empty()) {
+                return html;
+            }
+            let name = name(widget);
+            FAILURES.with(|failures| {
+                let mut failures = failures.borrow_mut();
+                failures.retain(|failure| {
+                    !(failure.name == name && failure.render)
+                });
+                match failures.iter().find(|failure| failure.name == name) {
+                    Some(failure) => placeholder(&name, &failure.message),
+                    None => html,
+                }
+            })
+        }
+        Err(payload) => {
+            let name = name(widget);
+            let message = panic_message(payload);
+            fail(&name, &message, true);
+            placeholder(&name, &message)
+        }
+    }
+}
+
+/// Trigger an event in a widget. If it panics, the widget is rendered as an
+/// error placeholder until it handles an update event.
+pub fn trigger(widget: &mut dyn Widget, event: &Event) {
+    match panic::catch_unwind(AssertUnwindSafe(|| widget.trigger(event))) {
+        Ok(()) => {
+            if let Event::Update = event {
+                if FAILURES.with(|failures| !failures.borrow().is_empty()) {
+                    let name = name(widget);
+                    FAILURES.with(|failures| {
+                        failures.borrow_mut().retain(|failure| {
+                            failure.name != name || failure.render
+                        })
+                    });
+                }
+            }
+        }
+        Err(payload) => {
+            let name = name(widget);
+            fail(&name, &panic_message(payload), false);
+        }
+    }
+}
+
+/// Return the name of a widget, empty if getting it panics
+fn name(widget: &dyn Widget) -> String {
+    panic::catch_unwind(AssertUnwindSafe(|| widget.name())).unwrap_or_default()
+}
+
+/// Record the failure of a widget, sending it to the window unless it is
+/// already recorded
+fn fail(name: &str, message: &str, render: bool) {
+    let known = FAILURES.with(|failures| {
+        let mut failures = failures.borrow_mut();
+        let known = failures.iter().any(|failure| {
+            failure.name == name
+                && failure.message == message
+                && failure.render == render
+        });
+        if !known {
+            failures.push(Failure {
+                name: name.to_string(),
+                message: message.to_string(),
+                render,
+            });
+        }
+        known
+    });
+    if !known {
+        let action = if render { "render" } else { "update" };
+        event::report_error(name, &format!("{} failed: {}", action, message));
+    }
+}
+
+/// Return the message of a panic
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => match payload.downcast_ref::<&str>() {
+            Some(message) => message.to_string(),
+            None => "unknown error".to_string(),
+        },
+    }
+}
+
+/// Return the error placeholder of a widget
+fn placeholder(name: &str, message: &str) -> String {
+    let title = i18n::translate_or(
+        "widget.error",
+        "This part of the window could not be displayed",
+        &[],
+    );
+    let details = if cfg!(debug_assertions) {
+        format!(
+            r#"<span class="widget-error-message">{}</span>"#,
+            escape(message)
+        )
+    } else {
+        "".to_string()
+    };
+    format!(
+        r#"<div id="{}" class="widget-error" role="alert"><span class="widget-error-title">{}</span>{}</div>"#,
+        name,
+        escape(&title),
+        details
+    )
 }
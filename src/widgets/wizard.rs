@@ -0,0 +1,510 @@
+use crate::utils::event::{self, Event};
+use crate::utils::html::{aria_label, escape};
+use crate::utils::i18n;
+use crate::utils::layoutoptions::LayoutOptions;
+use crate::utils::style::Style;
+use crate::widgets::widget::{self, Widget};
+
+/// # The state of a Wizard
+///
+/// ## Fields
+///
+/// ```text
+/// titles: Vec<String>
+/// children: Vec<Box<dyn Widget>>
+/// current: u32
+/// finished: bool
+/// layout: LayoutOptions
+/// style: Style
+/// aria_label: Option<String>
+/// ```
+pub struct WizardState {
+    titles: Vec<String>,
+    children: Vec<Box<dyn Widget>>,
+    current: u32,
+    finished: bool,
+    layout: LayoutOptions,
+    style: Style,
+    aria_label: Option<String>,
+}
+
+impl WizardState {
+    /// Get the titles of the steps
+    pub fn titles(&self) -> &Vec<String> {
+        &self.titles
+    }
+
+    /// Get the children, one per step
+    pub fn children(&self) -> &Vec<Box<dyn Widget>> {
+        &self.children
+    }
+
+    /// Get the index of the current step
+    pub fn current(&self) -> u32 {
+        self.current
+    }
+
+    /// Return true if the current step is the last one
+    pub fn is_last(&self) -> bool {
+        self.current as usize + 1 >= self.children.len()
+    }
+
+    /// Return true if the wizard has been completed with the finish button
+    pub fn finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Get the layout options
+    pub fn layout(&self) -> &LayoutOptions {
+        &self.layout
+    }
+
+    /// Get the style
+    pub fn style(&self) -> &Style {
+        &self.style
+    }
+
+    /// Get the stretched flag
+    pub fn stretched(&self) -> bool {
+        self.layout.stretched()
+    }
+
+    /// Get the label read by screen readers
+    pub fn aria_label(&self) -> Option<&str> {
+        self.aria_label.as_deref()
+    }
+
+    /// Set the titles of the steps
+    pub fn set_titles(&mut self, titles: Vec<&str>) {
+        self.titles = titles.iter().map(|t| t.to_string()).collect();
+    }
+
+    /// Set the current step, ignored if there is no such step
+    pub fn set_current(&mut self, current: u32) {
+        if (current as usize) < self.children.len() {
+            self.current = current;
+        }
+    }
+
+    /// Set the finished flag
+    pub fn set_finished(&mut self, finished: bool) {
+        self.finished = finished;
+    }
+
+    /// Set the layout options
+    pub fn set_layout(&mut self, layout: LayoutOptions) {
+        self.layout = layout;
+    }
+
+    /// Set the style
+    pub fn set_style(&mut self, style: Style) {
+        self.style = style;
+    }
+
+    /// Set the stretched flag
+    pub fn set_stretched(&mut self, stretched: bool) {
+        self.layout.set_stretched(stretched);
+    }
+
+    /// Set the label read by screen readers
+    pub fn set_aria_label(&mut self, label: &str) {
+        self.aria_label = Some(label.to_string());
+    }
+
+    /// Add a step
+    fn add(&mut self, title: &str, child: Box<dyn Widget>) {
+        self.titles.push(title.to_string());
+        self.children.push(child);
+    }
+}
+
+/// # The listener of a Wizard
+pub trait WizardListener {
+    /// Function triggered on update event
+    fn on_update(&self, state: &mut WizardState);
+
+    /// Function triggered when the current step changes
+    fn on_change(&self, state: &WizardState);
+
+    /// Function triggered when the finish button is clicked on the last step
+    /// with valid fields
+    fn on_finish(&self, state: &WizardState);
+
+    /// Function validating a step before leaving it for the next one, once
+    /// its fields are valid
+    fn validate(&self, _step: u32, _state: &WizardState) -> bool {
+        true
+    }
+}
+
+/// # A container leading through a sequence of steps
+///
+/// The Wizard shows the indicators of its steps, the child of the current
+/// step, and the buttons moving to the previous and the next steps, the
+/// last step ending with a finish button. Each step is validated before
+/// moving to the next one, or finishing: the fields of its child, such as
+/// TextInputs, display their error messages (see Form), then the `validate`
+/// function of the listener checks the step as a whole. Moving back is not
+/// validated, and the indicators of the previous steps move back to them.
+///
+/// The texts of the buttons can be translated with the `wizard.back`,
+/// `wizard.next` and `wizard.finish` keys (see `i18n`).
+///
+/// ## Fields
+///
+/// ```text
+/// name: String
+/// state: WizardState
+/// listener: Option<Box<dyn WizardListener>>
+/// ```
+///
+/// ## Default values
+///
+/// ```text
+/// name: name.to_string()
+/// state:
+///     titles: vec![]
+///     children: vec![]
+///     current: 0
+///     finished: false
+///     layout: LayoutOptions::new()
+///     style: Style::new()
+///     aria_label: None
+/// listener: None
+/// ```
+///
+/// ## Example
+///
+/// ```
+/// use neutrino::testing::Harness;
+/// use neutrino::utils::validator::Validator;
+/// use neutrino::widgets::label::Label;
+/// use neutrino::widgets::textinput::TextInput;
+/// use neutrino::widgets::wizard::{Wizard, WizardListener, WizardState};
+/// use neutrino::Window;
+///
+///
+/// struct MyWizardListener;
+///
+/// impl WizardListener for MyWizardListener {
+///     fn on_update(&self, _state: &mut WizardState) {}
+///
+///     fn on_change(&self, state: &WizardState) {
+///         println!("Step {}", state.current() + 1);
+///     }
+///
+///     fn on_finish(&self, _state: &WizardState) {
+///         println!("Installed");
+///     }
+/// }
+///
+///
+/// let mut path = TextInput::new("path");
+/// path.set_value("");
+/// path.add_validator(Validator::Required);
+///
+/// let mut summary = Label::new("summary");
+/// summary.set_text("Ready to install");
+///
+/// let mut my_wizard = Wizard::new("installer");
+/// my_wizard.add("Location", Box::new(path));
+/// my_wizard.add("Summary", Box::new(summary));
+/// my_wizard.set_listener(Box::new(MyWizardListener));
+///
+/// let mut my_window = Window::new();
+/// my_window.set_child(Box::new(my_wizard));
+///
+/// let mut harness = Harness::new(my_window);
+/// harness.change("installer", "next");
+/// assert!(!harness.contains("Ready to install"));
+///
+/// harness.change("path", "/opt/app");
+/// harness.change("installer", "next");
+/// assert!(harness.contains("Ready to install"));
+/// ```
+pub struct Wizard {
+    name: String,
+    state: WizardState,
+    listener: Option<Box<dyn WizardListener>>,
+}
+
+impl Wizard {
+    /// Create a Wizard
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            state: WizardState {
+                titles: vec![],
+                children: vec![],
+                current: 0,
+                finished: false,
+                layout: LayoutOptions::new(),
+                style: Style::new(),
+                aria_label: None,
+            },
+            listener: None,
+        }
+    }
+
+    /// Get the state
+    pub fn state(&self) -> &WizardState {
+        &self.state
+    }
+
+    /// Get the state, to change it from outside of the listener
+    pub fn state_mut(&mut self) -> &mut WizardState {
+        &mut self.state
+    }
+
+    /// Set the current step
+    pub fn set_current(&mut self, current: u32) {
+        self.state.set_current(current);
+    }
+
+    /// Set the layout options
+    pub fn set_layout(&mut self, layout: LayoutOptions) {
+        self.state.set_layout(layout);
+    }
+
+    /// Set the style
+    pub fn set_style(&mut self, style: Style) {
+        self.state.set_style(style);
+    }
+
+    /// Set the stretched flag to true
+    pub fn set_stretched(&mut self) {
+        self.state.set_stretched(true);
+    }
+
+    /// Set the label read by screen readers
+    pub fn set_aria_label(&mut self, label: &str) {
+        self.state.set_aria_label(label);
+    }
+
+    /// Set the listener
+    pub fn set_listener(&mut self, listener: Box<dyn WizardListener>) {
+        self.listener = Some(listener);
+    }
+
+    /// Add a step
+    pub fn add(&mut self, title: &str, widget: Box<dyn Widget>) {
+        self.state.add(title, widget);
+    }
+
+    /// Validate the current step, its fields then the listener
+    fn validate_step(&mut self) -> bool {
+        let current = self.state.current;
+        let valid = match self.state.children.get_mut(current as usize) {
+            Some(child) => child.validate(),
+            None => return false,
+        };
+        if !valid {
+            return false;
+        }
+        match &self.listener {
+            None => true,
+            Some(listener) => listener.validate(current, &self.state),
+        }
+    }
+
+    /// Move to a step and trigger the listener
+    fn go(&mut self, step: u32) {
+        if step == self.state.current {
+            return;
+        }
+        self.state.set_current(step);
+        self.state.set_finished(false);
+        match &self.listener {
+            None => (),
+            Some(listener) => {
+                listener.on_change(&self.state);
+            }
+        }
+    }
+
+    /// Return the HTML of a navigation button
+    fn button(&self, class: &str, text: &str, value: &str) -> String {
+        format!(
+            r#"<div class="button {}" role="button" tabindex="0" onmousedown="{}" onkeydown="{}">{}</div>"#,
+            class,
+            Event::change_js(&self.name, &format!("'{}'", value)),
+            Event::keys_js(
+                &self.name,
+                &[("Enter", value.to_string()), (" ", value.to_string())]
+            ),
+            escape(text)
+        )
+    }
+}
+
+impl Widget for Wizard {
+    fn eval(&self) -> String {
+        let layout = self.state.layout();
+        let mut s = format!(
+            r#"<div id="{}" class="wizard {} {}" style="{}{}" {}><ol class="wizard-steps">"#,
+            self.name,
+            if self.state.finished() {
+                "finished"
+            } else {
+                ""
+            },
+            layout.class(),
+            layout.style(),
+            self.state.style().css(),
+            aria_label(self.state.aria_label()),
+        );
+        let current = self.state.current() as usize;
+        for (i, title) in self.state.titles.iter().enumerate() {
+            let (class, attributes) = if i < current {
+                (
+                    "done",
+                    format!(
+                        r#"role="button" tabindex="0" onmousedown="{}""#,
+                        Event::change_js(&self.name, &format!("'step:{}'", i))
+                    ),
+                )
+            } else if i == current {
+                ("current", r#"aria-current="step""#.to_string())
+            } else {
+                ("", "".to_string())
+            };
+            s.push_str(&format!(
+                r#"<li class="wizard-step {}" {}><span class="wizard-step-number">{}</span><span class="wizard-step-title">{}</span></li>"#,
+                class,
+                attributes,
+                i + 1,
+                escape(title)
+            ));
+        }
+        s.push_str("</ol>");
+        let title = self
+            .state
+            .titles
+            .get(current)
+            .map(|title| escape(title))
+            .unwrap_or_default();
+        let child = match self.state.children.get(current) {
+            Some(child) => widget::eval(child.as_ref()),
+            None => "".to_string(),
+        };
+        s.push_str(&format!(
+            r#"<div class="wizard-page" role="group" aria-label="{}">{}</div><div class="wizard-actions">"#,
+            title, child
+        ));
+        if current > 0 {
+            let back = i18n::translate_or("wizard.back", "Back", &[]);
+            s.push_str(&self.button("wizard-back", &back, "back"));
+        }
+        if self.state.is_last() {
+            let finish = i18n::translate_or("wizard.finish", "Finish", &[]);
+            s.push_str(&self.button("wizard-finish", &finish, "finish"));
+        } else {
+            let next = i18n::translate_or("wizard.next", "Next", &[]);
+            s.push_str(&self.button("wizard-next", &next, "next"));
+        }
+        s.push_str("</div></div>");
+        s
+    }
+
+    fn name(&self) -> String {
+        self.name.to_string()
+    }
+
+    fn trigger(&mut self, event: &Event) {
+        match event {
+            Event::Update => self.on_update(),
+            Event::Change { source, value } if source == &self.name => {
+                self.on_change(value);
+                return;
+            }
+            _ => (),
+        }
+        for child in self.state.children.iter_mut() {
+            if widget::reaches(child.as_ref(), event) {
+                widget::trigger(child.as_mut(), event);
+            }
+        }
+    }
+
+    fn on_update(&mut self) {
+        match &self.listener {
+            None => (),
+            Some(listener) => {
+                listener.on_update(&mut self.state);
+            }
+        }
+    }
+
+    fn on_change(&mut self, value: &str) {
+        let current = self.state.current();
+        match value {
+            "back" => {
+                if current > 0 {
+                    self.go(current - 1);
+                }
+            }
+            "next" => {
+                if !self.state.is_last() && self.validate_step() {
+                    self.go(current + 1);
+                }
+            }
+            "finish" => {
+                if self.state.is_last() && self.validate_step() {
+                    self.state.set_finished(true);
+                    match &self.listener {
+                        None => (),
+                        Some(listener) => {
+                            listener.on_finish(&self.state);
+                        }
+                    }
+                }
+            }
+            _ => match value.strip_prefix("step:").map(str::parse::<u32>) {
+                Some(Ok(step)) if step < current => self.go(step),
+                Some(Ok(_)) => (),
+                _ => event::report_error(
+                    &self.name,
+                    &format!("invalid step: {}", value),
+                ),
+            },
+        }
+    }
+
+    fn values(&self) -> Vec<(String, String)> {
+        self.state
+            .children
+            .iter()
+            .flat_map(|widget| widget.values())
+            .collect()
+    }
+
+    fn is_valid(&self) -> bool {
+        self.state.children.iter().all(|widget| widget.is_valid())
+    }
+
+    fn validate(&mut self) -> bool {
+        let mut valid = true;
+        for widget in self.state.children.iter_mut() {
+            if !widget.validate() {
+                valid = false;
+            }
+        }
+        valid
+    }
+
+    fn children(&self) -> Vec<&(dyn Widget + 'static)> {
+        self.state
+            .children
+            .iter()
+            .map(|widget| widget.as_ref())
+            .collect()
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut (dyn Widget + 'static)> {
+        self.state
+            .children
+            .iter_mut()
+            .map(|widget| widget.as_mut() as &mut dyn Widget)
+            .collect()
+    }
+}
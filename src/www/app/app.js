@@ -1,13 +1,1410 @@
 let node = document.getElementById("app");
 
+let rendering = false;
+
+let composition = null;
+
 function render(template) {
-    morphdom(node, template);
+    rendering = true;
+    node = morphdom(node, template, {
+        onBeforeElUpdated: function(from, to) {
+            if (from === composition) {
+                return false;
+            }
+            keepTyping(from, to);
+            return true;
+        },
+        onElUpdated: restoreCaret,
+        onBeforeNodeDiscarded: function(discarded) {
+            leave(discarded);
+            return true;
+        }
+    });
+    rendering = false;
+    paint();
+    paintMinimaps();
+    fitToolBars();
+    placePopovers();
+    stackOverlays();
+    scheduleToasts();
+    restoreFocus();
+    focusLockScreens();
+    reportGeometry(true);
 }
 
 function emit(arg) {
     window.external.invoke(JSON.stringify(arg));
 }
 
+window.neutrino = {
+    emit: function(name, value) {
+        emit({ type: "Custom", name: String(name), value: value === undefined ? null : value });
+    }
+};
+
+function leave(element) {
+    if (element.nodeType !== Node.ELEMENT_NODE ||
+        !element.matches(".transition-fade, .transition-slide")) {
+        return;
+    }
+    let rect = element.getBoundingClientRect();
+    if (rect.width === 0 && rect.height === 0) {
+        return;
+    }
+    let ghost = element.cloneNode(true);
+    ghost.removeAttribute("id");
+    ghost.querySelectorAll("[id]").forEach(function(child) {
+        child.removeAttribute("id");
+    });
+    ghost.classList.add("leaving");
+    ghost.style.left = rect.left + "px";
+    ghost.style.top = rect.top + "px";
+    ghost.style.width = rect.width + "px";
+    ghost.style.height = rect.height + "px";
+    ghost.addEventListener("animationend", function() {
+        ghost.remove();
+    });
+    document.body.appendChild(ghost);
+}
+
+function animate(id, name, property, from, to, duration, easing) {
+    let element = document.getElementById(id);
+    let end = function() {
+        emit({ type: "AnimationEnd", source: id, name: name });
+    };
+    if (element === null) {
+        end();
+        return;
+    }
+    let key = property.replace(/-([a-z])/g, function(match, letter) {
+        return letter.toUpperCase();
+    });
+    if (element.animate === undefined) {
+        element.style[key] = to;
+        end();
+        return;
+    }
+    let start = {};
+    let stop = {};
+    start[key] = from;
+    stop[key] = to;
+    element.animate([start, stop], {
+        duration: duration,
+        easing: easing,
+        fill: "forwards"
+    }).onfinish = end;
+}
+
+function readFile(file) {
+    return new Promise(function(resolve) {
+        let reader = new FileReader();
+        reader.onload = function() {
+            resolve(reader.result);
+        };
+        reader.onerror = function() {
+            resolve("");
+        };
+        reader.readAsDataURL(file);
+    });
+}
+
+document.addEventListener("paste", function(event) {
+    let data = event.clipboardData;
+    if (data === null) {
+        return;
+    }
+    let source = focusSource(document.activeElement) || "";
+    let text = data.getData("text/plain");
+    let uris = data.getData("text/uri-list");
+    let files = Array.prototype.slice.call(data.files);
+    Array.prototype.forEach.call(data.items, function(item) {
+        if (item.kind === "file" && files.length === 0) {
+            let file = item.getAsFile();
+            if (file !== null) {
+                files.push(file);
+            }
+        }
+    });
+    Promise.all(files.map(readFile)).then(function(results) {
+        let images = [];
+        let pasted = [];
+        files.forEach(function(file, i) {
+            if (file.type.indexOf("image/") === 0) {
+                images.push(results[i]);
+            }
+            pasted.push({
+                name: file.name,
+                mime: file.type,
+                data: results[i].substring(results[i].indexOf(",") + 1)
+            });
+        });
+        emit({
+            type: "Paste",
+            source: source,
+            text: text === "" ? null : text,
+            images: images,
+            files: pasted,
+            uris: uris
+        });
+    });
+}, true);
+
+function copyToClipboard(text, images, uris) {
+    if (images.length > 0 && window.ClipboardItem !== undefined &&
+        navigator.clipboard !== undefined) {
+        let image = images[0];
+        let mime = image.substring(5, image.indexOf(";"));
+        let bytes = atob(image.substring(image.indexOf(",") + 1));
+        let buffer = new Uint8Array(bytes.length);
+        for (let i = 0; i < bytes.length; i++) {
+            buffer[i] = bytes.charCodeAt(i);
+        }
+        let items = {};
+        items[mime] = new Blob([buffer], { type: mime });
+        if (text !== null) {
+            items["text/plain"] = new Blob([text], { type: "text/plain" });
+        }
+        navigator.clipboard.write([new ClipboardItem(items)]);
+        return;
+    }
+    let write = function(event) {
+        if (text !== null) {
+            event.clipboardData.setData("text/plain", text);
+        }
+        if (uris !== "") {
+            event.clipboardData.setData("text/uri-list", uris);
+        }
+        event.preventDefault();
+    };
+    document.addEventListener("copy", write, true);
+    document.execCommand("copy");
+    document.removeEventListener("copy", write, true);
+}
+
+function composing(element) {
+    if (composition !== null && composition === element) {
+        element.pendingInput = true;
+        return true;
+    }
+    element.pendingInput = false;
+    return false;
+}
+
+function composingKey(event) {
+    return event.isComposing || event.keyCode === 229;
+}
+
+document.addEventListener("compositionstart", function(event) {
+    composition = event.target;
+}, true);
+
+document.addEventListener("compositionend", function(event) {
+    let element = event.target;
+    composition = null;
+    setTimeout(function() {
+        if (element.pendingInput) {
+            element.dispatchEvent(new Event("input", { bubbles: true }));
+        }
+    }, 0);
+}, true);
+
+let changeTimers = {};
+
+function sendChange(element, source) {
+    clearTimeout(changeTimers[source]);
+    delete changeTimers[source];
+    if (element.sentValue !== element.value) {
+        element.sentValue = element.value;
+        emit({ type: "Change", source: source, value: element.value });
+    }
+}
+
+function queueChange(element, source, mask, mode, delay) {
+    if (mask !== null) {
+        applyMask(element, mask);
+    }
+    if (mode === "debounce") {
+        clearTimeout(changeTimers[source]);
+        changeTimers[source] = setTimeout(function() {
+            sendChange(element, source);
+        }, delay);
+    } else if (mode === "throttle") {
+        if (changeTimers[source] === undefined) {
+            sendChange(element, source);
+            changeTimers[source] = setTimeout(function() {
+                sendChange(element, source);
+            }, delay);
+        }
+    } else {
+        sendChange(element, source);
+    }
+}
+
+function hasCaret(element) {
+    return element === document.activeElement &&
+        (element.tagName === "TEXTAREA" || (element.tagName === "INPUT" &&
+        ["text", "password", "search", "tel", "url"].includes(element.type)));
+}
+
+function keepTyping(from, to) {
+    if (!hasCaret(from)) {
+        return;
+    }
+    if (from.sentValue !== undefined && from.value !== from.sentValue) {
+        to.value = from.value;
+    }
+    from.caret = { start: from.selectionStart, end: from.selectionEnd, value: from.value };
+}
+
+function restoreCaret(element) {
+    let caret = element.caret;
+    if (caret === undefined) {
+        return;
+    }
+    delete element.caret;
+    if (element.value !== caret.value && hasCaret(element)) {
+        let length = element.value.length;
+        element.setSelectionRange(Math.min(caret.start, length), Math.min(caret.end, length));
+    }
+}
+
+const FOCUSABLE = 'input, select, textarea, [tabindex]:not([tabindex="-1"])';
+
+function focusable(element) {
+    if (element.matches(FOCUSABLE)) {
+        return element;
+    }
+    return element.querySelector(FOCUSABLE);
+}
+
+function focusSource(element) {
+    let source = element.closest("[id]");
+    if (source === null || source === node || !node.contains(source)) {
+        return null;
+    }
+    return source.id;
+}
+
+function restoreFocus() {
+    let focus = node.getAttribute("data-focus");
+    if (!focus) {
+        return;
+    }
+    let active = document.activeElement;
+    if (active.closest('[role="menubar"]') !== null) {
+        return;
+    }
+    if (focusSource(active) === focus && active.getAttribute("tabindex") !== "-1") {
+        return;
+    }
+    let element = null;
+    let target = null;
+    for (let candidate of node.querySelectorAll('[id="' + CSS.escape(focus) + '"]')) {
+        element = element || candidate;
+        target = target || focusable(candidate);
+    }
+    if (element === null) {
+        return;
+    }
+    if (target === null) {
+        element.tabIndex = -1;
+        target = element;
+    }
+    if (target !== active) {
+        target.focus();
+    }
+}
+
+function focusNext(backward) {
+    let order = node.getAttribute("data-tab-order");
+    if (!order) {
+        return false;
+    }
+    order = order.split(",");
+    let current = order.indexOf(focusSource(document.activeElement));
+    let step = backward ? order.length - 1 : 1;
+    let next = current === -1 ? 0 : (current + step) % order.length;
+    for (let i = 0; i < order.length; i++) {
+        let element = document.getElementById(order[next]);
+        if (element !== null) {
+            let target = focusable(element);
+            if (target === null) {
+                element.tabIndex = 0;
+                target = element;
+            }
+            target.focus();
+            return true;
+        }
+        next = (next + step) % order.length;
+    }
+    return false;
+}
+
+document.addEventListener("focusin", function(event) {
+    let source = focusSource(event.target);
+    let related = event.relatedTarget === null ?
+        null : focusSource(event.relatedTarget);
+    if (!rendering && source !== null && source !== related) {
+        emit({ type: "Focus", source: source });
+    }
+});
+
+document.addEventListener("focusout", function(event) {
+    let source = focusSource(event.target);
+    let related = event.relatedTarget === null ?
+        null : focusSource(event.relatedTarget);
+    if (!rendering && source !== null && source !== related) {
+        emit({ type: "Blur", source: source });
+    }
+});
+
+document.addEventListener("keydown", function(event) {
+    if (event.key === "Tab" && focusNext(event.shiftKey)) {
+        event.preventDefault();
+    }
+});
+
+function menuItems(menu) {
+    return Array.from(menu.children).filter(function(child) {
+        return child.matches('[role^="menuitem"]');
+    });
+}
+
+document.addEventListener("keydown", function(event) {
+    let item = event.target.closest('[role="menubar"] [role^="menuitem"]');
+    if (item === null || (event.key !== "ArrowDown" && event.key !== "ArrowUp")) {
+        return;
+    }
+    let items = [];
+    let index = 0;
+    if (item.classList.contains("menuitem-title")) {
+        let menu = item.parentElement.querySelector(':scope > [role="menu"]');
+        if (menu !== null) {
+            items = menuItems(menu);
+            index = event.key === "ArrowDown" ? 0 : items.length - 1;
+        }
+    } else {
+        items = menuItems(item.parentElement);
+        let step = event.key === "ArrowDown" ? 1 : items.length - 1;
+        index = (items.indexOf(item) + step) % items.length;
+    }
+    if (items.length > 0) {
+        event.preventDefault();
+        items[index].focus();
+    }
+});
+
+function webviewShortcut(event) {
+    let key = event.key.toLowerCase();
+    if (["f1", "f3", "f5", "f7", "f11", "f12"].includes(key)) {
+        return true;
+    }
+    if (event.ctrlKey || event.metaKey) {
+        if (event.shiftKey && ["i", "j", "c", "r"].includes(key)) {
+            return true;
+        }
+        return ["r", "p", "s", "o", "u", "f", "g", "+", "-", "=", "0"].includes(key);
+    }
+    return event.altKey && ["arrowleft", "arrowright", "home"].includes(key);
+}
+
+document.addEventListener("keydown", function(event) {
+    if (document.body.getAttribute("data-block-shortcuts") === "true" &&
+        webviewShortcut(event)) {
+        event.preventDefault();
+    }
+}, true);
+
+document.addEventListener("wheel", function(event) {
+    if (document.body.getAttribute("data-block-shortcuts") === "true" &&
+        event.ctrlKey) {
+        event.preventDefault();
+    }
+}, { capture: true, passive: false });
+
+function position(element, event) {
+    if (event.target === element) {
+        return { x: event.offsetX, y: event.offsetY };
+    }
+    let rect = element.getBoundingClientRect();
+    return { x: event.clientX - rect.left, y: event.clientY - rect.top };
+}
+
+function mouse(type, source, element, event) {
+    let p = position(element, event);
+    emit({ type: type, source: source, button: event.button, x: p.x, y: p.y });
+}
+
+let hovers = {};
+
+function hover(source, element, event) {
+    let now = Date.now();
+    if (hovers[source] !== undefined && now - hovers[source] < 50) {
+        return;
+    }
+    hovers[source] = now;
+    let p = position(element, event);
+    emit({ type: "Hover", source: source, x: p.x, y: p.y });
+}
+
+let images = {};
+
+function paint() {
+    let canvases = document.querySelectorAll("canvas[data-commands]");
+    for (let i = 0; i < canvases.length; i++) {
+        let canvas = canvases[i];
+        let ctx = canvas.getContext("2d");
+        let commands = JSON.parse(canvas.getAttribute("data-commands"));
+        ctx.clearRect(0, 0, canvas.width, canvas.height);
+        for (let j = 0; j < commands.length; j++) {
+            paintCommand(ctx, commands[j]);
+        }
+    }
+    paintRulers();
+}
+
+function paintRulers() {
+    let rulers = document.querySelectorAll("canvas[data-ruler]");
+    for (let i = 0; i < rulers.length; i++) {
+        let ruler = rulers[i];
+        let horizontal = ruler.getAttribute("data-ruler") === "horizontal";
+        let ctx = ruler.getContext("2d");
+        let length = horizontal ? ruler.width : ruler.height;
+        let style = getComputedStyle(ruler);
+        ctx.clearRect(0, 0, ruler.width, ruler.height);
+        ctx.strokeStyle = style.color;
+        ctx.fillStyle = style.color;
+        ctx.font = "9px sans-serif";
+        ctx.lineWidth = 1;
+        ctx.beginPath();
+        for (let p = 0; p <= length; p += 10) {
+            let tick = p % 100 === 0 ? 20 : p % 50 === 0 ? 10 : 5;
+            if (horizontal) {
+                ctx.moveTo(p + 0.5, 20);
+                ctx.lineTo(p + 0.5, 20 - tick);
+            } else {
+                ctx.moveTo(20, p + 0.5);
+                ctx.lineTo(20 - tick, p + 0.5);
+            }
+            if (p % 100 === 0) {
+                if (horizontal) {
+                    ctx.fillText(p, p + 3, 9);
+                } else {
+                    ctx.save();
+                    ctx.translate(9, p + 3);
+                    ctx.rotate(Math.PI / 2);
+                    ctx.fillText(p, 0, 0);
+                    ctx.restore();
+                }
+            }
+        }
+        ctx.stroke();
+    }
+}
+
+function paintCommand(ctx, c) {
+    switch (c.type) {
+        case "line":
+            ctx.beginPath();
+            ctx.strokeStyle = c.color;
+            ctx.lineWidth = c.width;
+            ctx.moveTo(c.x1, c.y1);
+            ctx.lineTo(c.x2, c.y2);
+            ctx.stroke();
+            break;
+        case "rect":
+            if (c.fill !== null) {
+                ctx.fillStyle = c.fill;
+                ctx.fillRect(c.x, c.y, c.width, c.height);
+            }
+            if (c.stroke !== null) {
+                ctx.strokeStyle = c.stroke;
+                ctx.lineWidth = 1;
+                ctx.strokeRect(c.x, c.y, c.width, c.height);
+            }
+            break;
+        case "path":
+            if (c.points.length === 0) {
+                break;
+            }
+            ctx.beginPath();
+            ctx.moveTo(c.points[0][0], c.points[0][1]);
+            for (let k = 1; k < c.points.length; k++) {
+                ctx.lineTo(c.points[k][0], c.points[k][1]);
+            }
+            if (c.closed) {
+                ctx.closePath();
+            }
+            if (c.fill !== null) {
+                ctx.fillStyle = c.fill;
+                ctx.fill();
+            }
+            if (c.stroke !== null) {
+                ctx.strokeStyle = c.stroke;
+                ctx.lineWidth = 1;
+                ctx.stroke();
+            }
+            break;
+        case "text":
+            ctx.font = c.font;
+            ctx.fillStyle = c.color;
+            ctx.fillText(c.text, c.x, c.y);
+            break;
+        case "image":
+            let image = images[c.src];
+            if (image === undefined) {
+                image = new Image();
+                image.onload = paint;
+                image.src = c.src;
+                images[c.src] = image;
+            }
+            if (image.complete) {
+                ctx.drawImage(image, c.x, c.y, c.width, c.height);
+            }
+            break;
+    }
+}
+
+function guideDrag(root, event, orientation, index) {
+    event.preventDefault();
+    let area = root.querySelector(".canvas-area");
+    let guides = root.getAttribute("data-guides");
+    guides = guides ? guides.split(",") : [];
+    let horizontal = orientation === "h";
+    let line = index >= 0 ? area.querySelectorAll(".canvas-guide")[index] : null;
+    let created = line === null || line === undefined;
+    if (created) {
+        line = document.createElement("div");
+        line.className = "canvas-guide " + (horizontal ? "horizontal" : "vertical");
+        area.appendChild(line);
+        index = guides.length;
+    }
+    let size = horizontal ? area.offsetHeight : area.offsetWidth;
+    let position = null;
+    function move(e) {
+        let rect = area.getBoundingClientRect();
+        if (horizontal) {
+            position = (e.clientY - rect.top) * area.offsetHeight / rect.height;
+        } else {
+            position = (e.clientX - rect.left) * area.offsetWidth / rect.width;
+        }
+        position = Math.round(position);
+        line.style[horizontal ? "top" : "left"] = position + "px";
+        line.classList.toggle("removed", position < 0 || position > size);
+    }
+    function end() {
+        document.removeEventListener("mousemove", move, true);
+        document.removeEventListener("mouseup", end, true);
+        if (position === null) {
+            if (created) {
+                area.removeChild(line);
+            }
+            return;
+        }
+        if (position < 0 || position > size) {
+            guides.splice(index, 1);
+        } else {
+            guides[index] = orientation + position;
+        }
+        emit({ type: "Change", source: root.id, value: "guides:" + guides.join(",") });
+    }
+    document.addEventListener("mousemove", move, true);
+    document.addEventListener("mouseup", end, true);
+}
+
+function applyMask(input, mask) {
+    let chars = input.value.split("");
+    let result = "";
+    let j = 0;
+    for (let i = 0; i < mask.length && j < chars.length; i++) {
+        let accept = null;
+        switch (mask[i]) {
+            case "9":
+                accept = /[0-9]/;
+                break;
+            case "a":
+                accept = /[A-Za-z]/;
+                break;
+            case "*":
+                accept = /[A-Za-z0-9]/;
+                break;
+        }
+        if (accept === null) {
+            result += mask[i];
+            if (chars[j] === mask[i]) {
+                j++;
+            }
+        } else {
+            while (j < chars.length && !accept.test(chars[j])) {
+                j++;
+            }
+            if (j < chars.length) {
+                result += chars[j];
+                j++;
+            }
+        }
+    }
+    input.value = result;
+}
+
+function marquee(canvas, event) {
+    if (canvas.getAttribute("data-selectable") !== "true" || event.button !== 0) {
+        return;
+    }
+    let root = canvas.closest(".canvas");
+    let area = canvas.parentNode;
+    let box = document.createElement("div");
+    box.className = "canvas-marquee";
+    let start = { x: event.offsetX, y: event.offsetY };
+    let region = null;
+    function move(e) {
+        let rect = area.getBoundingClientRect();
+        let x = (e.clientX - rect.left) * area.offsetWidth / rect.width;
+        let y = (e.clientY - rect.top) * area.offsetHeight / rect.height;
+        region = {
+            x: Math.min(start.x, x),
+            y: Math.min(start.y, y),
+            width: Math.abs(x - start.x),
+            height: Math.abs(y - start.y),
+        };
+        if (box.parentNode === null) {
+            area.appendChild(box);
+        }
+        box.style.left = region.x + "px";
+        box.style.top = region.y + "px";
+        box.style.width = region.width + "px";
+        box.style.height = region.height + "px";
+    }
+    function end() {
+        document.removeEventListener("mousemove", move, true);
+        document.removeEventListener("mouseup", end, true);
+        if (box.parentNode !== null) {
+            box.parentNode.removeChild(box);
+        }
+        if (region !== null && region.width + region.height > 3) {
+            emit({
+                type: "Change",
+                source: root.id,
+                value: "marquee:" + region.x + ";" + region.y + ";" +
+                    region.width + ";" + region.height,
+            });
+        }
+    }
+    document.addEventListener("mousemove", move, true);
+    document.addEventListener("mouseup", end, true);
+}
+
+function dragStart(element, event) {
+    if (event.button !== 0 || event.target.matches("input, select, textarea")) {
+        return;
+    }
+    event.preventDefault();
+    event.stopPropagation();
+    let parent = element.parentNode;
+    let scale = parent.getBoundingClientRect().width / Math.max(1, parent.offsetWidth);
+    let start = {
+        x: event.clientX,
+        y: event.clientY,
+        left: element.offsetLeft,
+        top: element.offsetTop,
+    };
+    let position = null;
+    function move(e) {
+        let maxX = Math.max(0, parent.clientWidth - element.offsetWidth);
+        let maxY = Math.max(0, parent.clientHeight - element.offsetHeight);
+        position = {
+            x: Math.round(Math.min(maxX, Math.max(0, start.left + (e.clientX - start.x) / scale))),
+            y: Math.round(Math.min(maxY, Math.max(0, start.top + (e.clientY - start.y) / scale))),
+        };
+        element.style.left = position.x + "px";
+        element.style.top = position.y + "px";
+    }
+    function end() {
+        document.removeEventListener("mousemove", move, true);
+        document.removeEventListener("mouseup", end, true);
+        element.classList.remove("dragging");
+        if (position !== null) {
+            emit({ type: "Change", source: element.id, value: position.x + ";" + position.y });
+        }
+    }
+    element.classList.add("dragging");
+    document.addEventListener("mousemove", move, true);
+    document.addEventListener("mouseup", end, true);
+}
+
+function tabDragStart(element, event, source, index) {
+    emit({ type: "Change", source: source, value: String(index) });
+    event.stopPropagation();
+    if (event.button !== 0) {
+        return;
+    }
+    let start = event.clientX;
+    let target = null;
+    function move(e) {
+        if (target === null && Math.abs(e.clientX - start) < 5) {
+            return;
+        }
+        let titles = Array.from(element.parentNode.querySelectorAll(":scope > .tab-title"));
+        target = titles.length - 1;
+        for (let i = 0; i < titles.length; i++) {
+            let bounds = titles[i].getBoundingClientRect();
+            if (e.clientX < bounds.left + bounds.width / 2) {
+                target = i > index ? i - 1 : i;
+                break;
+            }
+        }
+        element.classList.add("dragging");
+        element.style.transform = "translateX(" + (e.clientX - start) + "px)";
+    }
+    function end() {
+        document.removeEventListener("mousemove", move, true);
+        document.removeEventListener("mouseup", end, true);
+        element.classList.remove("dragging");
+        element.style.removeProperty("transform");
+        if (target !== null && target !== index) {
+            emit({ type: "Change", source: source, value: "move:" + index + ":" + target });
+        }
+    }
+    document.addEventListener("mousemove", move, true);
+    document.addEventListener("mouseup", end, true);
+}
+
+function resizeStart(element, event, edge, min, max) {
+    event.preventDefault();
+    let horizontal = edge === "left" || edge === "right";
+    let sign = edge === "left" || edge === "top" ? -1 : 1;
+    let start = {
+        position: horizontal ? event.clientX : event.clientY,
+        size: horizontal ? element.offsetWidth : element.offsetHeight,
+    };
+    let size = null;
+    function move(e) {
+        let position = horizontal ? e.clientX : e.clientY;
+        size = Math.round(Math.min(max, Math.max(min, start.size + sign * (position - start.position))));
+        element.style[horizontal ? "width" : "height"] = size + "px";
+        element.style.flexShrink = 0;
+    }
+    function end() {
+        document.removeEventListener("mousemove", move, true);
+        document.removeEventListener("mouseup", end, true);
+        if (size !== null) {
+            emit({ type: "Change", source: element.id, value: "resize:" + size });
+        }
+    }
+    document.addEventListener("mousemove", move, true);
+    document.addEventListener("mouseup", end, true);
+}
+
+function rangeInput(input) {
+    let inputs = input.parentElement.querySelectorAll(".inner-range");
+    if (inputs.length === 2 && Number(inputs[0].value) > Number(inputs[1].value)) {
+        input.value = input === inputs[0] ? inputs[1].value : inputs[0].value;
+    }
+    let bubble = input.nextElementSibling;
+    if (bubble !== null && bubble.classList.contains("range-bubble")) {
+        let min = Number(input.min);
+        let max = Number(input.max);
+        let position = max > min ? (Number(input.value) - min) / (max - min) : 0;
+        bubble.style.setProperty("--position", position);
+        bubble.textContent = input.value;
+    }
+    return input.value;
+}
+
+let pulls = {};
+
+function pullStart(element, event) {
+    if (element.scrollTop === 0) {
+        pulls[element.id] = { start: event.touches[0].clientY, distance: 0 };
+    }
+}
+
+function pullMove(element, event) {
+    let pull = pulls[element.id];
+    if (pull !== undefined) {
+        pull.distance = Math.max(0, event.touches[0].clientY - pull.start);
+        element.style.setProperty("--pull", Math.min(pull.distance, 80) + "px");
+        element.classList.toggle("pulling", pull.distance > 0);
+    }
+}
+
+function pullEnd(element) {
+    let pull = pulls[element.id];
+    delete pulls[element.id];
+    element.style.removeProperty("--pull");
+    element.classList.remove("pulling");
+    return pull !== undefined && pull.distance >= 60;
+}
+
+let viewports = {};
+
+function viewportTransform(element) {
+    return {
+        scale: parseFloat(element.getAttribute("data-scale")),
+        x: parseFloat(element.getAttribute("data-x")),
+        y: parseFloat(element.getAttribute("data-y")),
+    };
+}
+
+function viewportApply(element, t) {
+    element.setAttribute("data-scale", t.scale);
+    element.setAttribute("data-x", t.x);
+    element.setAttribute("data-y", t.y);
+    element.firstElementChild.style.transform =
+        "translate(" + t.x + "px, " + t.y + "px) scale(" + t.scale + ")";
+    moveMinimaps();
+}
+
+function viewportEmit(element) {
+    let t = viewportTransform(element);
+    emit({
+        type: "Change",
+        source: element.id,
+        value: t.scale + ";" + t.x + ";" + t.y,
+    });
+}
+
+function viewportZoom(event) {
+    let element = event.target.closest(".viewport");
+    if (element === null) {
+        return;
+    }
+    event.preventDefault();
+    let t = viewportTransform(element);
+    let min = parseFloat(element.getAttribute("data-min-scale"));
+    let max = parseFloat(element.getAttribute("data-max-scale"));
+    let factor = event.deltaY < 0 ? 1.1 : 1 / 1.1;
+    let scale = Math.min(max, Math.max(min, t.scale * factor));
+    let rect = element.getBoundingClientRect();
+    let mx = event.clientX - rect.left;
+    let my = event.clientY - rect.top;
+    t.x = mx - (mx - t.x) * scale / t.scale;
+    t.y = my - (my - t.y) * scale / t.scale;
+    t.scale = scale;
+    viewportApply(element, t);
+    clearTimeout(viewports[element.id]);
+    viewports[element.id] = setTimeout(function() {
+        delete viewports[element.id];
+        viewportEmit(element);
+    }, 150);
+}
+
+function viewportPanStart(event) {
+    let element = event.target.closest(".viewport");
+    if (element === null) {
+        return;
+    }
+    let background = event.target === element ||
+        event.target === element.firstElementChild;
+    if (!(background || event.button === 1 || event.shiftKey)) {
+        return;
+    }
+    event.preventDefault();
+    event.stopPropagation();
+    let t = viewportTransform(element);
+    let start = { x: event.clientX - t.x, y: event.clientY - t.y };
+    let moved = false;
+    function move(e) {
+        moved = true;
+        t.x = e.clientX - start.x;
+        t.y = e.clientY - start.y;
+        viewportApply(element, t);
+    }
+    function end() {
+        document.removeEventListener("mousemove", move, true);
+        document.removeEventListener("mouseup", end, true);
+        if (moved) {
+            viewportEmit(element);
+        }
+    }
+    document.addEventListener("mousemove", move, true);
+    document.addEventListener("mouseup", end, true);
+}
+
+function minimapScale(minimap, content) {
+    return Math.min(
+        minimap.clientWidth / Math.max(1, content.offsetWidth),
+        minimap.clientHeight / Math.max(1, content.offsetHeight)
+    );
+}
+
+function paintMinimaps() {
+    let minimaps = document.querySelectorAll(".minimap[data-target]");
+    for (let i = 0; i < minimaps.length; i++) {
+        let minimap = minimaps[i];
+        let viewport = document.getElementById(minimap.getAttribute("data-target"));
+        if (viewport === null) {
+            continue;
+        }
+        let content = viewport.firstElementChild;
+        let copy = content.cloneNode(true);
+        copy.removeAttribute("style");
+        let elements = copy.querySelectorAll("[id], [onmousedown], [onchange]");
+        for (let j = 0; j < elements.length; j++) {
+            elements[j].removeAttribute("id");
+            elements[j].removeAttribute("onmousedown");
+            elements[j].removeAttribute("onchange");
+        }
+        let sources = content.querySelectorAll("canvas");
+        let targets = copy.querySelectorAll("canvas");
+        for (let j = 0; j < targets.length; j++) {
+            targets[j].removeAttribute("data-commands");
+            targets[j].getContext("2d").drawImage(sources[j], 0, 0);
+        }
+        let scale = minimapScale(minimap, content);
+        let container = minimap.firstElementChild;
+        container.style.transform = "scale(" + scale + ")";
+        container.innerHTML = "";
+        container.appendChild(copy);
+    }
+    moveMinimaps();
+}
+
+function moveMinimaps() {
+    let minimaps = document.querySelectorAll(".minimap[data-target]");
+    for (let i = 0; i < minimaps.length; i++) {
+        let minimap = minimaps[i];
+        let viewport = document.getElementById(minimap.getAttribute("data-target"));
+        if (viewport === null) {
+            continue;
+        }
+        let t = viewportTransform(viewport);
+        let scale = minimapScale(minimap, viewport.firstElementChild);
+        let view = minimap.lastElementChild.style;
+        view.left = (-t.x / t.scale * scale) + "px";
+        view.top = (-t.y / t.scale * scale) + "px";
+        view.width = (viewport.clientWidth / t.scale * scale) + "px";
+        view.height = (viewport.clientHeight / t.scale * scale) + "px";
+    }
+}
+
+function minimapNavigate(minimap, event) {
+    let viewport = document.getElementById(minimap.getAttribute("data-target"));
+    if (viewport === null) {
+        return;
+    }
+    event.preventDefault();
+    function move(e) {
+        let rect = minimap.getBoundingClientRect();
+        let scale = minimapScale(minimap, viewport.firstElementChild);
+        let t = viewportTransform(viewport);
+        let x = (e.clientX - rect.left) / scale;
+        let y = (e.clientY - rect.top) / scale;
+        t.x = viewport.clientWidth / 2 - x * t.scale;
+        t.y = viewport.clientHeight / 2 - y * t.scale;
+        viewportApply(viewport, t);
+    }
+    function end() {
+        document.removeEventListener("mousemove", move, true);
+        document.removeEventListener("mouseup", end, true);
+        viewportEmit(viewport);
+    }
+    move(event);
+    document.addEventListener("mousemove", move, true);
+    document.addEventListener("mouseup", end, true);
+}
+
+const opposites = { top: "bottom", bottom: "top", left: "right", right: "left" };
+
+function popoverOffset(placement, anchor, width, height) {
+    switch (placement) {
+        case "top":
+            return { x: anchor.left, y: anchor.top - height };
+        case "bottom":
+            return { x: anchor.left, y: anchor.bottom };
+        case "left":
+            return { x: anchor.left - width, y: anchor.top };
+        default:
+            return { x: anchor.right, y: anchor.top };
+    }
+}
+
+function popoverFits(placement, offset, width, height) {
+    switch (placement) {
+        case "top":
+        case "bottom":
+            return offset.y >= 0 && offset.y + height <= window.innerHeight;
+        default:
+            return offset.x >= 0 && offset.x + width <= window.innerWidth;
+    }
+}
+
+function placePopover(element) {
+    let anchor = element.dataset.anchor ?
+        document.getElementById(element.dataset.anchor) :
+        element.parentElement;
+    if (!anchor || element.offsetParent === null) {
+        return;
+    }
+    let rect = anchor.getBoundingClientRect();
+    element.style.position = "fixed";
+    element.style.minWidth = rect.width + "px";
+    let width = element.offsetWidth;
+    let height = element.offsetHeight;
+    let placement = element.dataset.placement;
+    let offset = popoverOffset(placement, rect, width, height);
+    if (!popoverFits(placement, offset, width, height)) {
+        let flipped = popoverOffset(opposites[placement], rect, width, height);
+        if (popoverFits(opposites[placement], flipped, width, height)) {
+            placement = opposites[placement];
+            offset = flipped;
+        }
+    }
+    let x = Math.max(0, Math.min(offset.x, window.innerWidth - width));
+    let y = Math.max(0, Math.min(offset.y, window.innerHeight - height));
+    element.style.left = x + "px";
+    element.style.top = y + "px";
+    element.style.right = "auto";
+    element.style.bottom = "auto";
+    element.classList.remove("placed-top", "placed-right", "placed-bottom", "placed-left");
+    element.classList.add("placed-" + placement);
+}
+
+function placePopovers() {
+    document.querySelectorAll("[data-placement]").forEach(placePopover);
+}
+
+function fitToolBar(toolbar) {
+    let row = toolbar.querySelector(".toolbar-items");
+    let items = Array.from(row.children);
+    items.forEach(function(item) {
+        item.classList.remove("overflowed");
+    });
+    toolbar.classList.remove("overflowing");
+    if (row.scrollWidth > row.clientWidth) {
+        toolbar.classList.add("overflowing");
+        let limit = row.getBoundingClientRect().right;
+        let overflowed = false;
+        items.map(function(item) {
+            return item.getBoundingClientRect().right;
+        }).forEach(function(right, i) {
+            overflowed = overflowed || right > limit;
+            if (overflowed) {
+                items[i].classList.add("overflowed");
+            }
+        });
+    }
+    toolbar.querySelectorAll(".toolbar-overflow [data-index]").forEach(function(entry) {
+        let item = row.querySelector('[data-index="' + entry.dataset.index + '"]');
+        entry.classList.toggle("fitted", !item.classList.contains("overflowed"));
+    });
+    let first = toolbar.querySelector(".toolbar-overflow [data-index]:not(.fitted)");
+    if (first !== null && first.classList.contains("toolbar-entry-separator")) {
+        first.classList.add("fitted");
+    }
+}
+
+function fitToolBars() {
+    document.querySelectorAll(".toolbar").forEach(fitToolBar);
+}
+
+let overlays = [];
+
+function overlayElements() {
+    return Array.from(document.querySelectorAll("[data-overlay]"))
+        .filter(function(element) {
+            return element.getClientRects().length > 0;
+        });
+}
+
+function stackOverlays() {
+    let elements = overlayElements();
+    let names = elements.map(function(element) {
+        return element.dataset.overlay;
+    });
+    overlays = overlays.filter(function(name) {
+        return names.includes(name);
+    });
+    names.forEach(function(name) {
+        if (!overlays.includes(name)) {
+            overlays.push(name);
+        }
+    });
+    elements.forEach(function(element) {
+        element.style.zIndex = 1000 + overlays.indexOf(element.dataset.overlay);
+    });
+}
+
+function isToast(name) {
+    return overlayElements().some(function(element) {
+        return element.dataset.overlay === name && element.dataset.toast !== undefined;
+    });
+}
+
+function overlayContains(name, target) {
+    return overlayElements().some(function(element) {
+        if (element.dataset.overlay !== name) {
+            return false;
+        }
+        let owner = element.dataset.modal !== undefined ?
+            element.firstElementChild :
+            document.getElementById(name) || element.parentElement;
+        let anchor = element.dataset.anchor ?
+            document.getElementById(element.dataset.anchor) :
+            null;
+        return owner.contains(target) || (anchor !== null && anchor.contains(target));
+    });
+}
+
+document.addEventListener("mousedown", function(event) {
+    for (let i = overlays.length - 1; i >= 0; i--) {
+        if (isToast(overlays[i])) {
+            continue;
+        }
+        if (overlayContains(overlays[i], event.target)) {
+            break;
+        }
+        emit({ type: "Dismiss", source: overlays[i] });
+    }
+}, true);
+
+document.addEventListener("keydown", function(event) {
+    let top = overlays.filter(function(name) {
+        return !isToast(name);
+    }).pop();
+    if (event.key === "Escape" && top !== undefined) {
+        emit({ type: "Dismiss", source: top });
+        event.preventDefault();
+        event.stopPropagation();
+    }
+}, true);
+
+let toasts = {};
+
+function scheduleToasts() {
+    let shown = {};
+    document.querySelectorAll("[data-toast][data-timeout]").forEach(function(element) {
+        let name = element.dataset.overlay;
+        let serial = element.dataset.serial;
+        shown[name] = true;
+        if (toasts[name] !== undefined && toasts[name].serial === serial) {
+            return;
+        }
+        if (toasts[name] !== undefined) {
+            clearTimeout(toasts[name].timer);
+        }
+        let timeout = parseInt(element.dataset.timeout);
+        toasts[name] = {
+            serial: serial,
+            timer: timeout > 0 ? setTimeout(function() {
+                delete toasts[name];
+                emit({ type: "Dismiss", source: name });
+            }, timeout) : null,
+        };
+    });
+    Object.keys(toasts).forEach(function(name) {
+        if (!shown[name]) {
+            clearTimeout(toasts[name].timer);
+            delete toasts[name];
+        }
+    });
+}
+
+function lockScreen() {
+    return document.querySelector(".lockscreen.locked");
+}
+
+function focusLockScreens() {
+    let lock = lockScreen();
+    if (lock !== null && !lock.contains(document.activeElement)) {
+        let input = lock.querySelector("input");
+        if (input !== null) {
+            input.focus();
+        }
+    }
+}
+
+document.addEventListener("keydown", function(event) {
+    let lock = lockScreen();
+    if (lock === null) {
+        return;
+    }
+    if (!lock.contains(event.target) || event.key === "Tab") {
+        event.preventDefault();
+        focusLockScreens();
+    }
+    if (event.ctrlKey || event.key === "Escape") {
+        event.stopPropagation();
+    }
+}, true);
+
+let lastActivity = Date.now();
+let idleSent = {};
+
+["mousemove", "mousedown", "keydown", "wheel", "touchstart"].forEach(function(type) {
+    document.addEventListener(type, function() {
+        lastActivity = Date.now();
+    }, { capture: true, passive: true });
+});
+
+setInterval(function() {
+    document.querySelectorAll(".lockscreen[data-idle]").forEach(function(element) {
+        let idle = parseInt(element.dataset.idle);
+        if (idle > 0 && Date.now() - lastActivity >= idle && idleSent[element.id] !== lastActivity) {
+            idleSent[element.id] = lastActivity;
+            emit({ type: "Change", source: element.id, value: "idle" });
+        }
+    });
+}, 1000);
+
+window.addEventListener("resize", fitToolBars);
+window.addEventListener("resize", placePopovers);
+document.addEventListener("scroll", placePopovers, true);
+
+document.addEventListener("wheel", viewportZoom, { capture: true, passive: false });
+document.addEventListener("mousedown", viewportPanStart, true);
+
+let handlers = {};
+
+function defineHandler(id, handler) {
+    handlers[id] = handler;
+}
+
+function delegatedEvent(event, element, stop) {
+    return new Proxy(event, {
+        get: function(target, key) {
+            if (key === "currentTarget") {
+                return element;
+            }
+            if (key === "stopPropagation") {
+                return function() {
+                    stop();
+                    target.stopPropagation();
+                };
+            }
+            let value = target[key];
+            return typeof value === "function" ? value.bind(target) : value;
+        }
+    });
+}
+
+function delegate(type, bubbles) {
+    document.addEventListener(type, function(event) {
+        let stopped = false;
+        let element = event.target;
+        while (element !== null && element.nodeType === Node.ELEMENT_NODE) {
+            let handler = handlers[element.getAttribute("data-on-" + type)];
+            if (handler !== undefined) {
+                handler.call(element, delegatedEvent(event, element, function() {
+                    stopped = true;
+                }), element);
+            }
+            if (stopped || !bubbles) {
+                break;
+            }
+            element = element.parentNode;
+        }
+    }, { capture: !bubbles, passive: false });
+}
+
+if (document.body.getAttribute("data-strict") === "true") {
+    ["mousedown", "mouseover", "mousemove", "mouseup", "dblclick",
+        "contextmenu", "input", "change", "keydown", "touchstart",
+        "touchmove", "touchend"].forEach(function(type) {
+        delegate(type, true);
+    });
+    delegate("scroll", false);
+}
+
+let pointer = null;
+let geometryTimeout = null;
+let geometryChanged = false;
+
+function reportGeometry(changed) {
+    geometryChanged = geometryChanged || changed;
+    if (geometryTimeout !== null) {
+        return;
+    }
+    geometryTimeout = setTimeout(function() {
+        geometryTimeout = null;
+        let elements = null;
+        if (geometryChanged) {
+            elements = [];
+            node.querySelectorAll("[id]").forEach(function(element) {
+                let rect = element.getBoundingClientRect();
+                elements.push({
+                    name: element.id,
+                    x: rect.left,
+                    y: rect.top,
+                    width: rect.width,
+                    height: rect.height
+                });
+            });
+            geometryChanged = false;
+        }
+        emit({ type: "Geometry", pointer: pointer, elements: elements });
+    }, 50);
+}
+
+document.addEventListener("mousemove", function(event) {
+    pointer = { x: event.clientX, y: event.clientY };
+    reportGeometry(false);
+}, true);
+
+document.documentElement.addEventListener("mouseleave", function() {
+    pointer = null;
+    reportGeometry(false);
+});
+
+document.addEventListener("scroll", function() {
+    reportGeometry(true);
+}, true);
+
+function reportMonitors() {
+    let send = function(screens) {
+        emit({ type: "Monitors", monitors: Array.from(screens).map(function(screen, index) {
+            return {
+                name: screen.label || "",
+                x: screen.availLeft || 0,
+                y: screen.availTop || 0,
+                width: screen.availWidth,
+                height: screen.availHeight,
+                primary: screen.isPrimary === undefined ? index === 0 : screen.isPrimary
+            };
+        }) });
+    };
+    if (window.getScreenDetails === undefined) {
+        send([window.screen]);
+        return;
+    }
+    window.getScreenDetails().then(function(details) {
+        send(details.screens);
+        details.addEventListener("screenschange", function() {
+            send(details.screens);
+        });
+    }).catch(function() {
+        send([window.screen]);
+    });
+}
+
+function placeWindow(x, y, width, height) {
+    window.moveTo(
+        x + Math.max(0, Math.round((width - window.outerWidth) / 2)),
+        y + Math.max(0, Math.round((height - window.outerHeight) / 2))
+    );
+}
+
+let resizeTimeout = null;
+
+function emitResize() {
+    emit({ type: "Resize", width: window.innerWidth, height: window.innerHeight });
+}
+
+window.addEventListener("resize", function() {
+    reportGeometry(true);
+    clearTimeout(resizeTimeout);
+    resizeTimeout = setTimeout(emitResize, 100);
+});
+
+function emitNetwork() {
+    emit({ type: "Network", online: navigator.onLine });
+}
+
+window.addEventListener("online", emitNetwork);
+window.addEventListener("offline", emitNetwork);
+
 window.onload = function() {
-    emit({ type: "Update" });
-}
\ No newline at end of file
+    emitResize();
+    reportMonitors();
+    emitNetwork();
+}